@@ -1,7 +1,7 @@
 use eframe::egui;
 use egui::ColorImage;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::channel;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
@@ -11,16 +11,31 @@ use std::thread;
 use std::time::Instant;
 use tracing::{debug, error, info, warn};
 
-use crate::config::AppConfig;
+use crate::config::{classes_from_names, AppConfig};
 use crate::core;
-use crate::core::dataset::{parse_label_file, Dataset, DatasetSplit};
+use crate::core::dataset::{
+    parse_label_file, Bookmarks, Dataset, DatasetLock, DatasetSplit, DatasetTargets, LockOutcome,
+};
+use crate::core::image::AugmentationType;
+use crate::core::operations::{DeletionMode, TrashManifestEntry};
+use crate::core::statistics::{self, OperationEntry, SessionStats};
+use crate::infrastructure::logging::{log_timed_op, log_timed_op_with_count};
 use crate::navigation::Navigator;
 use crate::state::{
-    BalanceAnalysisState, BatchProgressMessage, BatchState, FilterState, ImageState, 
-    IntegrityState, RebalanceState, Settings, UIState, UndoManager, UndoState,
+    AutoAnalysisStatus, BalanceAnalysisState, BatchProgressMessage, BatchRemovalMode, BatchState, BookmarkDialogState, CategoryCacheState, CleanupFile, DatasetLockState, ConvertFormatState, CropExportState, DatasetSummaryState, ExportProgressMessage,
+    ExportSubsetState, FilterState, FlipAugmentState, FlipSource, ImageState, 
+    IntegrityState, LabelEditState, PendingAdvance, PinnedImage, PrefetchMessage, PrefetchState, PrefetchedImage, RawLabelState, PREFETCH_KEEP_RADIUS, RebalanceState, RenameState, ReportExportState, ReportProgressMessage, Settings, StatusBarState, StripMetadataState, UIState, UndoManager,
+    UndoAction, UndoState, ZoomNavigateMode, MAX_AUTO_ADVANCE_DELAY_MS,
 };
 use crate::ui;
 
+/// Splits with at least this many images evaluate filters on a background thread
+const BACKGROUND_FILTER_MIN_IMAGES: usize = 2_000;
+
+/// Images decoded in the background after each navigation (ahead, behind)
+const PREFETCH_AHEAD: usize = 3;
+const PREFETCH_BEHIND: usize = 1;
+
 #[derive(Default, Clone)]
 pub struct BatchStats {
     pub total_scanned: usize,
@@ -28,6 +43,15 @@ pub struct BatchStats {
     pub current_progress: usize,
 }
 
+#[derive(Default, Clone)]
+pub struct ExportStats {
+    pub total: usize,
+    pub current_progress: usize,
+    pub images_copied: usize,
+    pub labels_copied: usize,
+    pub failed: usize,
+}
+
 pub struct DatasetCleanerApp {
     // Core application state
     pub dataset: Dataset,
@@ -35,6 +59,14 @@ pub struct DatasetCleanerApp {
     pub config: AppConfig,
     pub settings: Settings,
     pub undo_manager: UndoManager,
+    /// Operations performed this session (for the exported report)
+    pub operations_log: Vec<OperationEntry>,
+    pub session_stats: SessionStats,
+    /// Next-image navigation waiting for the auto-advance delay
+    pub pending_advance: Option<PendingAdvance>,
+    /// Bookmarked images of the loaded dataset
+    pub bookmarks: Bookmarks,
+    pub bookmark_dialog: BookmarkDialogState,
 
     // Organized state modules
     pub image: ImageState,
@@ -44,21 +76,46 @@ pub struct DatasetCleanerApp {
     pub filter: FilterState,
     pub rebalance: RebalanceState,
     pub integrity: IntegrityState,
+    pub rename: RenameState,
+    pub strip_metadata: StripMetadataState,
+    pub convert: ConvertFormatState,
+    pub flip_augment: FlipAugmentState,
+    pub crop_export: CropExportState,
+    pub report_export: ReportExportState,
+    pub prefetch: PrefetchState,
+    pub export_subset: ExportSubsetState,
+    pub categories: CategoryCacheState,
+    pub summary: DatasetSummaryState,
+    pub status_bar: StatusBarState,
+    pub raw_label: RawLabelState,
+    pub dataset_lock: DatasetLockState,
+    /// Started with `--read-only`: the dataset stays read-only for the whole session
+    pub launched_read_only: bool,
 }
 
 impl Default for DatasetCleanerApp {
     fn default() -> Self {
-        let config = AppConfig::default();
-        let settings = Settings::load();
+        let mut config = AppConfig::default();
+        let mut settings = Settings::load();
+        config.classes = settings.classes.clone();
         let mut dataset = Dataset::new();
 
         // Prefer last dataset path from settings, fallback to config default
-        let dataset_path = settings
+        let mut dataset_path = settings
             .last_dataset_path
             .clone()
             .or_else(|| Some(config.default_dataset_path.clone()))
             .unwrap();
 
+        // A removable drive may be back under another letter or mount point
+        if !dataset_path.exists() {
+            if let Some(found) = settings.last_recent_dataset().and_then(|entry| entry.locate()) {
+                info!("Last dataset moved from {:?} to {:?}", dataset_path, found);
+                settings.remember_dataset(found.clone());
+                dataset_path = found;
+            }
+        }
+
         if dataset_path.exists() {
             info!("Loading dataset from: {:?}", dataset_path);
             dataset.load(dataset_path.clone());
@@ -84,30 +141,60 @@ impl Default for DatasetCleanerApp {
             0
         };
 
+        let bookmarks = dataset
+            .dataset_path()
+            .map(|path| Bookmarks::load(path))
+            .unwrap_or_default();
+
         // Clone filter criteria before moving settings into app
         let filter_criteria = settings.filter_criteria.clone();
+        let undo_depth = settings.undo_depth;
 
         let mut app = Self {
             dataset,
             current_index,
             config,
             settings,
-            undo_manager: UndoManager::new(),
+            undo_manager: UndoManager::with_max_depth(undo_depth),
+            operations_log: Vec::new(),
+            session_stats: SessionStats::new(),
+            pending_advance: None,
+            bookmarks,
+            bookmark_dialog: BookmarkDialogState::new(),
             image: ImageState::new(),
             ui: UIState::new(),
             batch: BatchState::new(),
             balance: BalanceAnalysisState::new(),
-            filter: FilterState {
-                criteria: filter_criteria,
-                ..FilterState::new()
+            filter: {
+                let mut filter = FilterState::new();
+                filter.criteria = filter_criteria;
+                filter
             },
             rebalance: RebalanceState::new(),
             integrity: IntegrityState::new(),
+            rename: RenameState::new(),
+            strip_metadata: StripMetadataState::new(),
+            convert: ConvertFormatState::new(),
+            flip_augment: FlipAugmentState::new(),
+            crop_export: CropExportState::new(),
+            report_export: ReportExportState::new(),
+            prefetch: PrefetchState::new(),
+            export_subset: ExportSubsetState::new(),
+            categories: CategoryCacheState::new(),
+            summary: DatasetSummaryState::new(),
+            status_bar: StatusBarState::new(),
+            raw_label: RawLabelState::new(),
+            dataset_lock: DatasetLockState::new(),
+            launched_read_only: false,
         };
+        app.acquire_dataset_lock();
+        app.apply_data_yaml();
+        app.apply_dataset_targets();
 
         // Parse label for the current image if dataset was loaded
         if !app.dataset.get_image_files().is_empty() {
             app.parse_label_file();
+            app.refresh_category_cache();
 
             // Apply filters if they were restored from settings
             if app.filter.is_active() {
@@ -115,6 +202,9 @@ impl Default for DatasetCleanerApp {
             }
         }
 
+        app.refresh_dataset_summary();
+        app.auto_analyze_on_load();
+        app.show_summary_on_load();
         app
     }
 }
@@ -139,9 +229,74 @@ impl DatasetCleanerApp {
     #[deprecated(note = "Use reload_dataset_with_filters() instead")]
     fn reload_and_refresh(&mut self, reset_zoom: bool) {
         self.dataset.load_current_split();
+        self.prefetch.clear();
         self.adjust_current_index();
         self.reset_image_state(reset_zoom);
         self.parse_label_file();
+        self.refresh_category_cache();
+    }
+
+    /// Recompute the per-image category cache for the current split in the background
+    pub fn refresh_category_cache(&mut self) {
+        // Cancel any categorization still running for the old file list
+        if let Some(flag) = &self.categories.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+        self.categories.categories = None;
+        self.categories.detection_counts = None;
+
+        let image_files: Vec<PathBuf> = self.dataset.get_image_files().clone();
+        let known_class_ids = self.config.class_ids();
+        if image_files.is_empty() {
+            self.categories.receiver = None;
+            self.categories.cancel_flag = None;
+            return;
+        }
+
+        let (tx, rx) = channel();
+        self.categories.receiver = Some(rx);
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.categories.cancel_flag = Some(cancel_flag.clone());
+
+        thread::spawn(move || {
+            debug!("Categorizing {} images for category cache", image_files.len());
+            let mut entries = Vec::with_capacity(image_files.len());
+            for img_path in &image_files {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    debug!("Category cache refresh cancelled");
+                    return;
+                }
+                let label = core::operations::get_label_path_for_image(img_path)
+                    .and_then(|label_path| parse_label_file(&label_path));
+                let detections = label.as_ref().map_or(0, |l| l.detections.len());
+                entries.push((core::analysis::categorize_label(label.as_ref(), &known_class_ids), detections));
+            }
+            let _ = tx.send(entries);
+        });
+    }
+
+    /// Recompute the dataset summary (file counts and sizes per split) in the background
+    pub fn refresh_dataset_summary(&mut self) {
+        let roots: Vec<PathBuf> = self.dataset.roots().cloned().collect();
+        if roots.is_empty() {
+            self.summary.summary = None;
+            self.summary.receiver = None;
+            return;
+        }
+
+        let (tx, rx) = channel();
+        self.summary.receiver = Some(rx);
+        thread::spawn(move || {
+            let summary = core::dataset::DatasetSummary::compute(&roots);
+            debug!("Dataset summary ready: {} images", summary.total_images());
+            let _ = tx.send(summary);
+        });
+    }
+
+    /// Show the dataset summary with fresh numbers
+    pub fn open_dataset_summary(&mut self) {
+        self.refresh_dataset_summary();
+        self.summary.show_dialog = true;
     }
 
     /// Reload the dataset and automatically reapply filters if active
@@ -150,6 +305,7 @@ impl DatasetCleanerApp {
     /// (e.g., after delete, undo, batch operations) to ensure filtered
     /// indices stay in sync with the dataset.
     fn reload_dataset_with_filters(&mut self, reset_zoom: bool) {
+        #[allow(deprecated)]
         self.reload_and_refresh(reset_zoom);
         
         // Automatically reapply filters if active to keep filtered_indices in sync
@@ -164,6 +320,7 @@ impl DatasetCleanerApp {
     /// Use this when you want to control navigation yourself after reload,
     /// such as during delete operations where position should be preserved.
     fn reload_dataset_without_navigation(&mut self, reset_zoom: bool) {
+        #[allow(deprecated)]
         self.reload_and_refresh(reset_zoom);
         
         // Reapply filters but skip navigation - caller will handle position
@@ -173,20 +330,72 @@ impl DatasetCleanerApp {
         }
     }
 
+    /// Open a dataset from the recent list, looking on other drives if it moved
+    pub fn open_recent_dataset(&mut self, index: usize) {
+        let Some(entry) = self.settings.recent_datasets.get(index) else {
+            return;
+        };
+        match entry.locate() {
+            Some(path) => {
+                if path != entry.path {
+                    self.ui.toast_message = Some((
+                        format!("{} found at {}", entry.name(), path.display()),
+                        Instant::now(),
+                    ));
+                }
+                self.load_dataset(path);
+            }
+            None => {
+                warn!("Recent dataset not found: {:?}", entry.path);
+                self.ui.toast_message = Some((
+                    format!("{} not found at {} or on another drive", entry.name(), entry.path.display()),
+                    Instant::now(),
+                ));
+            }
+        }
+    }
+
     pub fn load_dataset(&mut self, path: PathBuf) {
         info!("Loading dataset from: {:?}", path);
+        self.cancel_error_scan();
         self.dataset.load(path.clone());
+        self.finish_dataset_load(path);
+    }
+
+    /// Load several dataset roots at once, merged into one view
+    ///
+    /// The first root is the primary one: it is locked, remembered in the
+    /// settings and supplies data.yaml, the balance targets and bookmarks.
+    pub fn load_merged_datasets(&mut self, paths: Vec<PathBuf>) {
+        let Some(primary) = paths.first().cloned() else {
+            return;
+        };
+        info!("Loading {} merged dataset roots: {:?}", paths.len(), paths);
+        self.cancel_error_scan();
+        self.dataset.load_multiple(paths);
+        self.finish_dataset_load(primary);
+    }
+
+    /// Refresh everything that depends on the dataset after `path` was loaded
+    fn finish_dataset_load(&mut self, path: PathBuf) {
+        self.acquire_dataset_lock();
+        self.apply_data_yaml();
+        self.apply_dataset_targets();
+        self.bookmarks = Bookmarks::load(&path);
+        self.bookmark_dialog.thumbnails.clear();
         self.current_index = 0;
         self.reset_image_state(false);
         // Parse label file for the first image
         self.parse_label_file();
+        self.refresh_category_cache();
+        self.refresh_dataset_summary();
         info!(
             "Dataset loaded successfully, total images: {}",
             self.dataset.get_image_files().len()
         );
 
         // Save dataset path to settings
-        self.settings.last_dataset_path = Some(path);
+        self.settings.remember_dataset(path);
         self.settings.save();
         
         // Reapply filters if active (using manual approach since we don't reload here)
@@ -194,15 +403,488 @@ impl DatasetCleanerApp {
             info!("Reapplying filters after loading new dataset");
             self.apply_filters();
         }
+
+        self.auto_analyze_on_load();
+        self.show_summary_on_load();
+    }
+
+    /// Lock the primary dataset root against other instances
+    ///
+    /// When another running instance holds the lock, the dataset opens
+    /// read-only and the lock dialog offers a takeover.
+    fn acquire_dataset_lock(&mut self) {
+        // Release the previous dataset first; reloading the same one re-locks it
+        self.dataset_lock = DatasetLockState::new();
+        let Some(root) = self.dataset.dataset_path().cloned() else {
+            return;
+        };
+        match DatasetLock::acquire(&root) {
+            Ok(LockOutcome::Acquired(lock)) => self.dataset_lock.lock = Some(lock),
+            Ok(LockOutcome::Held(holder)) => {
+                warn!("Dataset {:?} is open in another instance (PID {})", root, holder.pid);
+                self.dataset_lock.read_only = true;
+                self.dataset_lock.conflict = Some(holder);
+            }
+            Err(e) => warn!("Failed to lock dataset {:?}: {}", root, e),
+        }
+    }
+
+    /// Check the lock again from the read-only badge
+    ///
+    /// Leaves read-only mode when the other instance has closed meanwhile,
+    /// otherwise shows the lock dialog again.
+    pub fn review_dataset_lock(&mut self) {
+        self.acquire_dataset_lock();
+        if !self.dataset_lock.read_only {
+            self.ui.toast_message = Some((
+                "The other instance closed the dataset; changes are enabled again".to_string(),
+                Instant::now(),
+            ));
+        }
+    }
+
+    /// Keep the dataset read-only and close the lock dialog
+    pub fn open_read_only(&mut self) {
+        self.dataset_lock.conflict = None;
+        self.ui.toast_message = Some((
+            "Opened read-only: changes to the dataset are disabled".to_string(),
+            Instant::now(),
+        ));
+    }
+
+    /// Take the dataset lock from the other instance and leave read-only mode
+    pub fn take_over_dataset_lock(&mut self) {
+        let Some(root) = self.dataset.dataset_path().cloned() else {
+            return;
+        };
+        self.dataset_lock.conflict = None;
+        match DatasetLock::take_over(&root) {
+            Ok(lock) => {
+                self.dataset_lock.lock = Some(lock);
+                self.dataset_lock.read_only = false;
+                // The other instance may have changed files since the scan
+                self.reload_dataset_with_filters(false);
+                self.ui.toast_message = Some((
+                    "Took over the dataset lock".to_string(),
+                    Instant::now(),
+                ));
+            }
+            Err(e) => {
+                error!("Failed to take over dataset lock: {}", e);
+                self.ui.toast_message = Some((
+                    format!("Could not take over the dataset lock: {}", e),
+                    Instant::now(),
+                ));
+            }
+        }
+    }
+
+    /// Why changes to the dataset are disabled (None when they are allowed)
+    pub fn read_only_reason(&self) -> Option<&'static str> {
+        if self.dataset_lock.read_only {
+            Some("another instance has this dataset open")
+        } else if self.launched_read_only {
+            Some("the cleaner was started with --read-only")
+        } else if self.settings.read_only {
+            Some("read-only mode is on in Settings")
+        } else {
+            None
+        }
+    }
+
+    /// Whether changes to the dataset are disabled
+    pub fn is_read_only(&self) -> bool {
+        self.read_only_reason().is_some()
+    }
+
+    /// Tooltip for a button disabled by read-only mode
+    pub fn read_only_hint(&self) -> String {
+        self.read_only_reason()
+            .map(|reason| format!("Read-only: {}", reason))
+            .unwrap_or_default()
+    }
+
+    /// Refuse a change to the dataset while it is open read-only
+    ///
+    /// Returns true (and explains why in a toast) when the action must not run.
+    fn read_only_blocked(&mut self, action: &str) -> bool {
+        let Some(reason) = self.read_only_reason() else {
+            return false;
+        };
+        warn!("Blocked {} in read-only mode", action);
+        self.ui.toast_message = Some((
+            format!("Read-only: cannot {} because {}", action, reason),
+            Instant::now(),
+        ));
+        true
+    }
+
+    /// Take the class table from the loaded dataset's data.yaml, falling back
+    /// to the settings table; a yaml that fails to parse is reported as a toast
+    fn apply_data_yaml(&mut self) {
+        self.config.data_yaml_classes = None;
+        match self.dataset.data_yaml() {
+            Some(Ok(yaml)) if !yaml.names.is_empty() => {
+                info!("Using {} class names from data.yaml", yaml.names.len());
+                let classes = classes_from_names(&yaml.names, &self.settings.classes);
+                self.config.classes = classes.clone();
+                self.config.data_yaml_classes = Some(classes);
+                return;
+            }
+            Some(Err(e)) => {
+                warn!("Failed to parse data.yaml: {}", e);
+                self.ui.toast_message =
+                    Some((format!("⚠ Could not read data.yaml: {}", e), Instant::now()));
+            }
+            _ => {}
+        }
+        self.config.classes = self.settings.classes.clone();
+    }
+
+    /// Take the balance targets from the loaded dataset's targets file (defaults without one)
+    fn apply_dataset_targets(&mut self) {
+        let targets = self
+            .dataset
+            .dataset_path()
+            .map(|root| DatasetTargets::load(root))
+            .unwrap_or_default();
+        self.use_targets(&targets);
+    }
+
+    fn use_targets(&mut self, targets: &DatasetTargets) {
+        self.config.target_player_ratio = targets.player_ratio;
+        self.config.target_background_ratio = targets.background_ratio;
+        self.config.target_hardcase_ratio = targets.hardcase_ratio;
+        self.config.target_train_ratio = targets.train_ratio;
+        self.config.target_val_ratio = targets.val_ratio;
+        self.config.target_test_ratio = targets.test_ratio;
+    }
+
+    /// Balance targets currently in use
+    pub fn dataset_targets(&self) -> DatasetTargets {
+        DatasetTargets {
+            player_ratio: self.config.target_player_ratio,
+            background_ratio: self.config.target_background_ratio,
+            hardcase_ratio: self.config.target_hardcase_ratio,
+            train_ratio: self.config.target_train_ratio,
+            val_ratio: self.config.target_val_ratio,
+            test_ratio: self.config.target_test_ratio,
+        }
+    }
+
+    /// Category mix targets for the balance analysis and rebalancers
+    pub fn target_ratios(&self) -> core::analysis::TargetRatios {
+        core::analysis::TargetRatios {
+            player_ratio: self.config.target_player_ratio,
+            background_ratio: self.config.target_background_ratio,
+            hardcase_ratio: self.config.target_hardcase_ratio,
+        }
+    }
+
+    /// Split size targets for the global rebalancer
+    pub fn split_ratios(&self) -> core::analysis::SplitRatios {
+        core::analysis::SplitRatios {
+            train: self.config.target_train_ratio,
+            val: self.config.target_val_ratio,
+            test: self.config.target_test_ratio,
+        }
+    }
+
+    /// Use `targets` for every balance tool and save them with the dataset
+    ///
+    /// Invalid targets are rejected. In read-only mode they apply to this
+    /// session only.
+    pub fn set_dataset_targets(&mut self, targets: DatasetTargets) -> Result<(), String> {
+        targets.validate()?;
+        self.use_targets(&targets);
+        self.rebalance.split_ratios = None;
+        info!("Balance targets changed to {:?}", targets);
+
+        let Some(root) = self.dataset.dataset_path().cloned() else {
+            return Ok(());
+        };
+        if self.is_read_only() {
+            self.ui.toast_message = Some((
+                "Targets apply to this session only (read-only)".to_string(),
+                Instant::now(),
+            ));
+            return Ok(());
+        }
+        targets
+            .save(&root)
+            .map_err(|e| format!("Failed to save targets: {}", e))
+    }
+
+    /// Write the split folders and the class table to the dataset's data.yaml
+    ///
+    /// Runs from the Tools menu, and after global rebalances and split swaps
+    /// when `auto_update_data_yaml` is on.
+    pub fn write_data_yaml(&mut self) {
+        if self.read_only_blocked("write data.yaml") {
+            return;
+        }
+        let Some(root) = self.dataset.dataset_path().cloned() else {
+            return;
+        };
+
+        let names = self.config.class_names();
+        let message = match core::dataset::write_data_yaml(&root, &names) {
+            Ok(path) => {
+                info!("Wrote {:?} with {} classes", path, names.len());
+                format!("Wrote data.yaml with {} classes", names.len())
+            }
+            Err(e) => {
+                error!("Failed to write data.yaml: {}", e);
+                format!("⚠ {}", e)
+            }
+        };
+        self.ui.toast_message = Some((message, Instant::now()));
+        self.dataset.refresh_data_yaml();
+    }
+
+    /// Export a standalone `dataset.yaml` to `dest`, asking first if it would replace a file
+    pub fn request_yolo_yaml_export(&mut self, dest: PathBuf) {
+        if self.yolo_yaml_export_blocked(&dest) {
+            return;
+        }
+        if dest.exists() {
+            self.ui.pending_yaml_overwrite = Some(dest);
+        } else {
+            self.export_yolo_yaml(&dest);
+        }
+    }
+
+    /// Write a standalone `dataset.yaml` for training to `dest` (replacing any file there)
+    pub fn export_yolo_yaml(&mut self, dest: &Path) {
+        if self.yolo_yaml_export_blocked(dest) {
+            return;
+        }
+        let Some(root) = self.dataset.dataset_path().cloned() else {
+            return;
+        };
+        let names = self.config.class_names();
+        let message = match core::operations::export_yolo_yaml(&root, &names, dest) {
+            Ok(()) => {
+                info!("Exported {:?} with {} classes", dest, names.len());
+                format!("Exported {} with {} classes", dest.display(), names.len())
+            }
+            Err(e) => {
+                error!("Failed to export dataset yaml: {}", e);
+                format!("⚠ {}", e)
+            }
+        };
+        self.ui.toast_message = Some((message, Instant::now()));
+    }
+
+    /// Whether read-only mode forbids writing a `dataset.yaml` to `dest`
+    ///
+    /// Read-only protects the dataset folder only, so exports outside it are allowed.
+    fn yolo_yaml_export_blocked(&mut self, dest: &Path) -> bool {
+        let inside_dataset = self
+            .dataset
+            .dataset_path()
+            .is_some_and(|root| dest.starts_with(root));
+        inside_dataset && self.read_only_blocked("export dataset.yaml into the dataset folder")
+    }
+
+    /// Copy the pixels of the current image to the system clipboard
+    pub fn copy_image_to_clipboard(&mut self) {
+        let Some(path) = self.dataset.get_image_files().get(self.current_index).cloned() else {
+            return;
+        };
+        let result = image::open(&path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+            .and_then(|image| {
+                let image = image.to_rgba8();
+                let clipboard = match &mut self.ui.clipboard {
+                    Some(clipboard) => clipboard,
+                    empty => empty.insert(
+                        arboard::Clipboard::new().map_err(|e| format!("Clipboard unavailable: {}", e))?,
+                    ),
+                };
+                clipboard
+                    .set_image(arboard::ImageData {
+                        width: image.width() as usize,
+                        height: image.height() as usize,
+                        bytes: std::borrow::Cow::Owned(image.into_raw()),
+                    })
+                    .map_err(|e| format!("Failed to copy image: {}", e))
+            });
+        let message = match result {
+            Ok(()) => {
+                info!("Copied {:?} to the clipboard", path);
+                "Image copied to clipboard".to_string()
+            }
+            Err(e) => {
+                error!("{}", e);
+                format!("⚠ {}", e)
+            }
+        };
+        self.ui.toast_message = Some((message, Instant::now()));
+    }
+
+    /// Copy the current image and its label into `dest_dir`
+    ///
+    /// With "Burn boxes" ticked the boxes are drawn into the image copy.
+    pub fn export_current_pair(&mut self, dest_dir: &Path) {
+        let Some(path) = self.dataset.get_image_files().get(self.current_index).cloned() else {
+            return;
+        };
+        let config = &self.config;
+        let color_for = |class_id: u32| {
+            let (color, _) = config.get_class_colors(class_id);
+            [color.r(), color.g(), color.b()]
+        };
+        let burn: Option<&dyn Fn(u32) -> [u8; 3]> =
+            if self.ui.burn_boxes_on_export { Some(&color_for) } else { None };
+        let message = match core::image::export_image_pair(&path, dest_dir, burn) {
+            Ok(exported) => format!("Exported {}", exported.display()),
+            Err(e) => {
+                error!("Failed to export image pair: {}", e);
+                format!("⚠ {}", e)
+            }
+        };
+        self.ui.toast_message = Some((message, Instant::now()));
+    }
+
+    /// Write the filtered images, in navigation order, to a `.txt` or `.csv` manifest
+    pub fn export_filtered_list(&mut self, dest: &Path) {
+        let images = self.filtered_image_files();
+        let root = self
+            .dataset
+            .dataset_path()
+            .filter(|_| self.filter.export_relative)
+            .cloned();
+        let result = core::operations::export_manifest(
+            &images,
+            root.as_deref(),
+            self.filter.export_columns,
+            &self.config.class_ids(),
+            dest,
+        );
+        let message = match result {
+            Ok(()) => {
+                info!("Exported {} filtered images to {:?}", images.len(), dest);
+                format!("Exported {} images to {}", images.len(), dest.display())
+            }
+            Err(e) => {
+                error!("Failed to export filtered list: {}", e);
+                format!("⚠ {}", e)
+            }
+        };
+        self.ui.toast_message = Some((message, Instant::now()));
+    }
+
+    /// Open the dataset summary for a freshly loaded dataset, unless the
+    /// analysis dialog already opened for the analyses started on load
+    fn show_summary_on_load(&mut self) {
+        if self.dataset.dataset_path().is_some() && !self.balance.show_dialog {
+            self.summary.show_dialog = true;
+        }
+    }
+
+    /// Start the analyses enabled in settings after opening a dataset or switching splits
+    ///
+    /// Balance and integrity analysis run in the background without opening
+    /// the dialog; the top panel shows their status and the balance dialog
+    /// opens on their results. An automatic run still in progress for the
+    /// previous split is cancelled first.
+    pub fn auto_analyze_on_load(&mut self) {
+        self.cancel_auto_analysis();
+        if self.dataset.dataset_path().is_none() {
+            return;
+        }
+
+        if self.settings.auto_analyze_on_load && !self.balance.analyzing {
+            let split_index = match self.dataset.current_split() {
+                DatasetSplit::Train => 0,
+                DatasetSplit::Val => 1,
+                DatasetSplit::Test => 2,
+            };
+            info!("Auto-analyzing balance of split_index {}", split_index);
+            if self.start_balance_analysis(split_index) {
+                self.balance.auto_status = AutoAnalysisStatus::Running;
+            }
+        }
+
+        if self.settings.auto_analyze_integrity && !self.integrity.analyzing {
+            info!("Auto-analyzing integrity");
+            if self.start_integrity_analysis() {
+                self.integrity.auto_status = AutoAnalysisStatus::Running;
+            }
+        }
+    }
+
+    /// Stop automatic analyses that are still running and forget finished ones
+    ///
+    /// Manual analyses are left alone.
+    fn cancel_auto_analysis(&mut self) {
+        if self.balance.auto_status == AutoAnalysisStatus::Running {
+            info!("Cancelling automatic balance analysis");
+            if let Some(flag) = self.balance.cancel_flag.take() {
+                flag.store(true, Ordering::Relaxed);
+            }
+            self.balance.analyzing = false;
+            self.balance.progress_receiver = None;
+            self.balance.global_stats_receiver = None;
+            self.balance.rate.clear();
+        }
+        self.balance.auto_status = AutoAnalysisStatus::Idle;
+
+        if self.integrity.auto_status == AutoAnalysisStatus::Running {
+            info!("Cancelling automatic integrity analysis");
+            if let Some(flag) = self.integrity.cancel_flag.take() {
+                flag.store(true, Ordering::Relaxed);
+            }
+            self.integrity.analyzing = false;
+            self.integrity.progress_receiver = None;
+            self.integrity.rate.clear();
+        }
+        self.integrity.auto_status = AutoAnalysisStatus::Idle;
+    }
+
+    /// Open the balance dialog on the automatic analysis, or start a new analysis
+    pub fn open_balance_analysis(&mut self) {
+        if self.balance.auto_status == AutoAnalysisStatus::Idle {
+            self.analyze_balance();
+        } else {
+            self.balance.current_tab = 0;
+            self.balance.show_dialog = true;
+        }
+    }
+
+    /// Merge another dataset root into the current view (e.g. a background-only folder)
+    pub fn add_secondary_dataset(&mut self, path: PathBuf) {
+        info!("Adding secondary dataset root: {:?}", path);
+        self.dataset.add_secondary_path(path);
+        self.current_index = 0;
+        self.reset_image_state(false);
+        self.parse_label_file();
+        self.refresh_category_cache();
+        self.refresh_dataset_summary();
+        info!(
+            "Secondary dataset added, total images: {}",
+            self.dataset.get_image_files().len()
+        );
+
+        if self.filter.is_active() {
+            self.apply_filters();
+        }
     }
 
     pub fn change_split(&mut self, new_split: DatasetSplit) {
+        if !self.dataset.has_split(new_split) {
+            info!("Ignoring split change to {:?}: dataset has a flat layout", new_split);
+            return;
+        }
         info!("Changing dataset split to: {:?}", new_split);
+        self.cancel_error_scan();
         self.dataset.change_split(new_split);
         self.current_index = 0;
         self.reset_image_state(false);
         // Parse label file for the first image
         self.parse_label_file();
+        self.refresh_category_cache();
         debug!(
             "Split changed, current images count: {}",
             self.dataset.get_image_files().len()
@@ -217,6 +899,8 @@ impl DatasetCleanerApp {
             info!("Reapplying filters after changing split");
             self.apply_filters();
         }
+
+        self.auto_analyze_on_load();
     }
 
     pub fn load_current_image(&mut self, ctx: &egui::Context) {
@@ -246,6 +930,7 @@ impl DatasetCleanerApp {
                 self.image.dominant_color = Self::calculate_dominant_color(&img);
 
                 self.image.texture = Some(texture);
+                self.session_stats.record_view(img_path);
                 info!("Image loaded successfully");
             }
             Err(e) => {
@@ -260,9 +945,150 @@ impl DatasetCleanerApp {
         core::image::calculate_dominant_color(img).map(|(r, g, b)| egui::Color32::from_rgb(r, g, b))
     }
 
+    /// Show the current image from the prefetch cache
+    ///
+    /// Returns false when it has not been prefetched, in which case the
+    /// caller falls back to `load_current_image`.
+    pub fn use_prefetched_image(&mut self, ctx: &egui::Context) -> bool {
+        let Some(img_path) = self.dataset.get_image_files().get(self.current_index) else {
+            return false;
+        };
+        let Some(prefetched) = self.prefetch.prefetch_cache.remove(&self.current_index) else {
+            return false;
+        };
+        if prefetched.path != *img_path {
+            return false;
+        }
+
+        debug!("Using prefetched image: {:?}", img_path);
+        self.image.load_error = None;
+        self.image.texture = Some(ctx.load_texture(
+            "current_image",
+            prefetched.image,
+            egui::TextureOptions::LINEAR,
+        ));
+        self.image.dominant_color = prefetched.dominant_color;
+        self.session_stats.record_view(img_path);
+        true
+    }
+
+    /// Decode the images around the current one on a background thread
+    ///
+    /// Replaces any prefetch still running and evicts cached images that are
+    /// now far from the current index.
+    fn schedule_prefetch(&mut self) {
+        if let Some(flag) = self.prefetch.cancel_flag.take() {
+            flag.store(true, Ordering::Relaxed);
+        }
+        self.prefetch.receiver = None;
+        self.prefetch.evict_far_from(self.current_index);
+
+        let nav = Navigator::new(self.dataset.get_image_files().len());
+        let mut indices = Vec::with_capacity(PREFETCH_AHEAD + PREFETCH_BEHIND);
+        let mut index = self.current_index;
+        for _ in 0..PREFETCH_AHEAD {
+            match nav.next(index, &self.filter) {
+                Some(next) => {
+                    indices.push(next);
+                    index = next;
+                }
+                None => break,
+            }
+        }
+        index = self.current_index;
+        for _ in 0..PREFETCH_BEHIND {
+            match nav.prev(index, &self.filter) {
+                Some(prev) => {
+                    indices.push(prev);
+                    index = prev;
+                }
+                None => break,
+            }
+        }
+        indices.retain(|index| {
+            index.abs_diff(self.current_index) <= PREFETCH_KEEP_RADIUS
+                && !self.prefetch.prefetch_cache.contains_key(index)
+        });
+
+        self.prefetch.prefetch_queue = indices.iter().copied().collect();
+        if indices.is_empty() {
+            return;
+        }
+        let jobs: Vec<(usize, PathBuf)> = indices
+            .into_iter()
+            .filter_map(|index| {
+                self.dataset
+                    .get_image_files()
+                    .get(index)
+                    .map(|path| (index, path.clone()))
+            })
+            .collect();
+
+        let (tx, rx) = channel();
+        self.prefetch.receiver = Some(rx);
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.prefetch.cancel_flag = Some(cancel_flag.clone());
+
+        thread::spawn(move || {
+            for (index, path) in jobs {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    return;
+                }
+                let message = match image::open(&path) {
+                    Ok(img) => {
+                        let rgba = img.to_rgba8();
+                        let size = [rgba.width() as _, rgba.height() as _];
+                        let image =
+                            ColorImage::from_rgba_unmultiplied(size, rgba.as_flat_samples().as_slice());
+                        PrefetchMessage::Loaded {
+                            index,
+                            image: PrefetchedImage {
+                                dominant_color: Self::calculate_dominant_color(&img),
+                                path,
+                                image,
+                            },
+                        }
+                    }
+                    Err(e) => {
+                        debug!("Prefetch of {:?} failed: {}", path, e);
+                        PrefetchMessage::Failed { index }
+                    }
+                };
+                if tx.send(message).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Move images decoded by the prefetch thread into the cache
+    fn poll_prefetch(&mut self) {
+        let Some(receiver) = &self.prefetch.receiver else {
+            return;
+        };
+        let mut done = false;
+        while let Ok(message) = receiver.try_recv() {
+            let index = match message {
+                PrefetchMessage::Loaded { index, image } => {
+                    self.prefetch.prefetch_cache.insert(index, image);
+                    index
+                }
+                PrefetchMessage::Failed { index } => index,
+            };
+            self.prefetch.prefetch_queue.retain(|&queued| queued != index);
+            done = self.prefetch.prefetch_queue.is_empty();
+        }
+        if done {
+            self.prefetch.receiver = None;
+            self.prefetch.cancel_flag = None;
+        }
+        self.prefetch.evict_far_from(self.current_index);
+    }
+
     pub fn parse_label_file(&mut self) {
         if self.dataset.get_image_files().is_empty() {
             self.image.label = None;
+            self.raw_label.load(None);
             return;
         }
 
@@ -273,19 +1099,432 @@ impl DatasetCleanerApp {
             Some(path) => path,
             None => {
                 self.image.label = None;
+                self.raw_label.load(None);
                 return;
             }
         };
 
         // Parse label file using the dedicated module
         self.image.label = parse_label_file(&label_path);
+        self.image.pending_annotations = 0;
+        self.raw_label.load(Some(label_path));
     }
 
-    pub fn delete_current_image(&mut self) {
-        info!("=== DELETE_CURRENT_IMAGE CALLED ===");
+    /// Add a box drawn in annotation mode to the current label
+    ///
+    /// The box is only shown until `annotate_current_image` saves it.
+    pub fn add_annotation(&mut self, detection: core::dataset::YoloDetection) {
+        let label = self.image.label.get_or_insert_with(|| core::dataset::LabelInfo {
+            detections: Vec::new(),
+            resolution: None,
+            map: None,
+            location: None,
+            position: None,
+            timestamp: None,
+        });
+        label.detections.push(detection);
+        self.image.selected_detection = Some(label.detections.len() - 1);
+        self.image.pending_annotations += 1;
+    }
 
-        if self.dataset.get_image_files().is_empty() {
-            info!("ERROR: Dataset is empty, returning early");
+    /// Append the boxes drawn in annotation mode to the label file (undoable)
+    pub fn annotate_current_image(&mut self) {
+        if self.read_only_blocked("save annotations") {
+            return;
+        }
+        let count = self.image.pending_annotations;
+        if count == 0 {
+            return;
+        }
+        let Some(image_path) = self.dataset.get_image_files().get(self.current_index).cloned() else {
+            return;
+        };
+        let Some(label_path) = core::operations::get_label_path_for_image(&image_path) else {
+            return;
+        };
+        let Some(label) = &self.image.label else {
+            return;
+        };
+
+        let previous_contents = fs::read_to_string(&label_path).ok();
+        let mut new_contents = previous_contents.clone().unwrap_or_default();
+        if !new_contents.is_empty() && !new_contents.ends_with('\n') {
+            new_contents.push('\n');
+        }
+        for detection in &label.detections[label.detections.len() - count..] {
+            new_contents.push_str(&detection.to_label_line());
+            new_contents.push('\n');
+        }
+
+        if let Err(e) = core::operations::write_label_contents(&label_path, Some(&new_contents)) {
+            error!("Failed to save annotations: {}", e);
+            self.ui.toast_message = Some((format!("Saving annotations failed: {}", e), Instant::now()));
+            return;
+        }
+        info!("Saved {} annotations to {:?}", count, label_path);
+
+        self.undo_manager.push_label_edit(LabelEditState {
+            image_path,
+            label_path,
+            previous_contents,
+            new_contents,
+        });
+        self.record_operation(statistics::OP_EDIT_LABEL, 1);
+
+        self.image.selected_detection = None;
+        self.parse_label_file();
+        self.refresh_category_cache();
+        self.ui.toast_message = Some((
+            format!("Saved {} annotation{}", count, if count == 1 { "" } else { "s" }),
+            Instant::now(),
+        ));
+    }
+
+    /// Validate and save the raw label editor contents (undoable)
+    pub fn save_raw_label(&mut self) {
+        if self.read_only_blocked("save label edits") {
+            return;
+        }
+        let Some(label_path) = self.raw_label.label_path.clone() else {
+            return;
+        };
+        let Some(image_path) = self.dataset.get_image_files().get(self.current_index).cloned() else {
+            return;
+        };
+
+        let errors = core::dataset::validate_label_content(&self.raw_label.text);
+        if !errors.is_empty() {
+            warn!("Raw label edit has {} invalid lines", errors.len());
+            self.raw_label.message = Some(format!(
+                "Not saved: {} invalid line{}",
+                errors.len(),
+                if errors.len() == 1 { "" } else { "s" }
+            ));
+            self.raw_label.errors = errors;
+            return;
+        }
+
+        let new_contents = self.raw_label.text.clone();
+        if let Err(e) = core::operations::write_label_contents(&label_path, Some(&new_contents)) {
+            error!("Failed to save label file: {}", e);
+            self.raw_label.message = Some(format!("Save failed: {}", e));
+            return;
+        }
+
+        self.undo_manager.push_label_edit(LabelEditState {
+            image_path: image_path.clone(),
+            label_path,
+            previous_contents: self.raw_label.original.clone(),
+            new_contents,
+        });
+        self.record_operation(statistics::OP_EDIT_LABEL, 1);
+
+        self.image.selected_detection = None;
+        self.parse_label_file();
+        self.refresh_category_cache();
+        self.raw_label.message = Some("Saved".to_string());
+        self.schedule_auto_advance(image_path, Vec::new());
+    }
+
+    /// Discard unsaved edits in the raw label editor
+    pub fn revert_raw_label(&mut self) {
+        self.raw_label.revert();
+    }
+
+    /// Write an augmented `_aug` copy of the current image and its label next to it
+    pub fn augment_current_image(&mut self, aug: AugmentationType) {
+        if self.read_only_blocked("augment images") {
+            return;
+        }
+        let Some(image_path) = self.dataset.get_image_files().get(self.current_index).cloned() else {
+            return;
+        };
+        let label_path = core::operations::get_label_path_for_image(&image_path)
+            .filter(|path| path.exists());
+        let output_path = core::image::augmented_output_path(&image_path);
+        info!("Augmenting {:?} ({}) to {:?}", image_path, aug.as_str(), output_path);
+
+        let written = match core::image::apply_augmentation(
+            &image_path,
+            label_path.as_deref(),
+            aug,
+            &output_path,
+        ) {
+            Ok(path) => path,
+            Err(e) => {
+                error!("Augmentation failed: {}", e);
+                self.ui.augment_message = Some(format!("Augmentation failed: {}", e));
+                return;
+            }
+        };
+
+        // Undo moves the copy into the temp dir, like a deletion
+        let temp_dir = std::env::temp_dir().join("yolo_dataset_cleaner_undo");
+        if let Err(e) = fs::create_dir_all(&temp_dir) {
+            error!("ERROR creating temp directory: {}", e);
+        }
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        let temp_path_for = |path: &Path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            temp_dir.join(format!("{}_{}", timestamp, name))
+        };
+        let written_label = core::operations::get_label_path_for_image(&written)
+            .filter(|path| path.exists());
+        let image_filename = written
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        self.undo_manager.push_augment(UndoState {
+            temp_image_path: temp_path_for(&written),
+            temp_label_path: written_label.as_deref().map(temp_path_for),
+            image_path: written.clone(),
+            label_path: written_label,
+            image_filename: image_filename.clone(),
+            deleted_at: Instant::now(),
+        });
+        self.record_operation(statistics::OP_AUGMENT, 1);
+        self.ui.augment_message = Some(format!("Saved {}", image_filename));
+
+        // Show the new copy in the file list while staying on the original
+        self.reload_dataset_without_navigation(false);
+        if let Some(index) = self
+            .dataset
+            .get_image_files()
+            .iter()
+            .position(|p| p == &image_path)
+        {
+            self.current_index = index;
+            self.reset_image_state(false);
+            self.parse_label_file();
+        }
+        // The copy sorts right after the original; advance past it
+        self.schedule_auto_advance(image_path, vec![written]);
+    }
+
+    /// Move to the next image after an action on `from`, if auto-advance is on
+    ///
+    /// Navigation happens in `poll_auto_advance` once the delay has passed.
+    fn schedule_auto_advance(&mut self, from: PathBuf, skip: Vec<PathBuf>) {
+        if !self.settings.auto_advance {
+            return;
+        }
+        let delay = self.settings.auto_advance_delay_ms.min(MAX_AUTO_ADVANCE_DELAY_MS);
+        self.pending_advance = Some(PendingAdvance {
+            due: Instant::now() + std::time::Duration::from_millis(delay as u64),
+            from,
+            skip,
+        });
+    }
+
+    /// Perform a scheduled auto-advance once it is due
+    ///
+    /// Dropped if another image is shown by then (the user already moved on).
+    fn poll_auto_advance(&mut self, ctx: &egui::Context) {
+        let Some(pending) = self.pending_advance.take() else {
+            return;
+        };
+        let now = Instant::now();
+        if pending.due > now {
+            ctx.request_repaint_after(pending.due - now);
+            self.pending_advance = Some(pending);
+            return;
+        }
+
+        let files = self.dataset.get_image_files();
+        if files.get(self.current_index) != Some(&pending.from) {
+            debug!("Auto-advance dropped, {:?} is no longer shown", pending.from);
+            return;
+        }
+        let nav = Navigator::new(files.len());
+        let mut index = self.current_index;
+        while let Some(next) = nav.next(index, &self.filter) {
+            index = next;
+            if !pending.skip.contains(&files[next]) {
+                info!("Auto-advancing to image index {}", next);
+                self.navigate_to(next);
+                return;
+            }
+        }
+        debug!("Auto-advance: no next image");
+    }
+
+    /// Remove an augmented copy (undo of `augment_current_image`)
+    fn undo_augment(&mut self, state: UndoState) {
+        info!("Removing augmented copy: {}", state.image_filename);
+        if let Err(e) = core::operations::move_file(&state.image_path, &state.temp_image_path) {
+            error!("Error removing augmented image: {}", e);
+            return;
+        }
+        if let (Some(label), Some(temp_label)) = (&state.label_path, &state.temp_label_path) {
+            if let Err(e) = core::operations::move_file(label, temp_label) {
+                error!("Error removing augmented label: {}", e);
+            }
+        }
+        self.record_operation(statistics::OP_UNDO_AUGMENT, 1);
+        self.reload_dataset_with_filters(false);
+    }
+
+    /// Put an augmented copy back (redo of `augment_current_image`)
+    fn redo_augment(&mut self, state: UndoState) {
+        info!("Restoring augmented copy: {}", state.image_filename);
+        if let Err(e) = core::operations::restore_image_with_label(
+            &state.temp_image_path,
+            &state.image_path,
+            &state.temp_label_path,
+            &state.label_path,
+        ) {
+            error!("Error restoring augmented copy: {}", e);
+            return;
+        }
+        self.record_operation(statistics::OP_REDO_AUGMENT, 1);
+        self.reload_dataset_with_filters(false);
+    }
+
+    /// Undo the most recent deletion, label edit or augmentation
+    pub fn undo(&mut self) {
+        if self.read_only_blocked("undo") {
+            return;
+        }
+        if let Some(action) = self.undo_manager.undo() {
+            self.revert_action(action);
+        }
+    }
+
+    /// Undo every action newer than `index` of the undo stack, then the one at `index`
+    pub fn undo_through(&mut self, index: usize) {
+        if self.read_only_blocked("undo") {
+            return;
+        }
+        while self.undo_manager.undo_count() > index {
+            self.undo();
+        }
+    }
+
+    /// Undo the action at `index` of the undo stack without undoing the newer ones
+    ///
+    /// Only done when no newer action touches the same files.
+    pub fn undo_out_of_order(&mut self, index: usize) {
+        if self.read_only_blocked("undo") {
+            return;
+        }
+        if !self.undo_manager.can_undo_out_of_order(index) {
+            warn!("Undo history entry {} depends on newer actions", index);
+            return;
+        }
+        if let Some(action) = self.undo_manager.undo_at(index) {
+            info!("Undoing {} out of order", action.description());
+            self.revert_action(action);
+        }
+    }
+
+    fn revert_action(&mut self, action: UndoAction) {
+        match action {
+            UndoAction::Delete(state) => self.undo_delete(state),
+            UndoAction::Augment(state) => self.undo_augment(state),
+            UndoAction::Recycled(image_filename) => {
+                self.ui.delete_message = Some(format!(
+                    "{} was sent to the recycle bin and is not undoable here; restore it from the recycle bin",
+                    image_filename
+                ));
+            }
+            UndoAction::LabelEdit(edit) => {
+                self.apply_label_edit(&edit, edit.previous_contents.as_deref());
+                self.record_operation(statistics::OP_UNDO_EDIT_LABEL, 1);
+            }
+            UndoAction::IntegrityCleanup(files) => self.undo_integrity_cleanup(&files),
+            UndoAction::BoxDedupe(changes) => self.undo_box_dedupe(&changes),
+        }
+    }
+
+    /// Change how many actions the undo history keeps
+    pub fn set_undo_depth(&mut self, depth: usize) {
+        self.undo_manager.set_max_depth(depth);
+        self.discard_evicted_undo();
+    }
+
+    /// Delete the parked files of actions that fell off the end of the undo history
+    ///
+    /// Files in the dataset trash are left for Tools > Empty Cleaner Trash.
+    fn discard_evicted_undo(&mut self) {
+        let evicted = self.undo_manager.take_evicted();
+        if evicted.is_empty() {
+            return;
+        }
+        let mut removed = 0;
+        for path in evicted.iter().flat_map(|action| action.parked_files()) {
+            if core::operations::is_in_dataset_trash(&path) || !path.exists() {
+                continue;
+            }
+            match fs::remove_file(&path) {
+                Ok(()) => removed += 1,
+                Err(e) => warn!("Failed to remove old undo file {:?}: {}", path, e),
+            }
+        }
+        info!(
+            "Dropped {} old undo actions, removed {} temp files",
+            evicted.len(),
+            removed
+        );
+    }
+
+    /// Redo the most recently undone deletion, label edit or augmentation
+    pub fn redo(&mut self) {
+        if self.read_only_blocked("redo") {
+            return;
+        }
+        match self.undo_manager.redo() {
+            Some(UndoAction::Delete(state)) => self.redo_delete(state),
+            Some(UndoAction::Augment(state)) => self.redo_augment(state),
+            Some(UndoAction::Recycled(image_filename)) => {
+                self.ui.delete_message =
+                    Some(format!("{} is still in the recycle bin", image_filename));
+            }
+            Some(UndoAction::LabelEdit(edit)) => {
+                self.apply_label_edit(&edit, Some(&edit.new_contents));
+                self.record_operation(statistics::OP_REDO_EDIT_LABEL, 1);
+            }
+            Some(UndoAction::IntegrityCleanup(files)) => self.redo_integrity_cleanup(&files),
+            Some(UndoAction::BoxDedupe(changes)) => self.redo_box_dedupe(&changes),
+            None => {}
+        }
+    }
+
+    /// Write one side of a label edit and show the edited image
+    fn apply_label_edit(&mut self, edit: &LabelEditState, contents: Option<&str>) {
+        info!("Restoring label contents of {:?}", edit.label_path);
+        if let Err(e) = core::operations::write_label_contents(&edit.label_path, contents) {
+            error!("Error restoring label file: {}", e);
+            return;
+        }
+
+        if let Some(index) = self
+            .dataset
+            .get_image_files()
+            .iter()
+            .position(|p| p == &edit.image_path)
+        {
+            if index != self.current_index {
+                self.current_index = index;
+                self.reset_image_state(false);
+            }
+        }
+        self.image.selected_detection = None;
+        self.parse_label_file();
+        self.refresh_category_cache();
+    }
+
+    pub fn delete_current_image(&mut self) {
+        if self.read_only_blocked("delete images") {
+            return;
+        }
+        info!("=== DELETE_CURRENT_IMAGE CALLED ===");
+
+        if self.dataset.get_image_files().is_empty() {
+            info!("ERROR: Dataset is empty, returning early");
             return;
         }
         info!(
@@ -313,46 +1552,66 @@ impl DatasetCleanerApp {
         };
         info!("Current filtered position: {:?}", current_filtered_pos);
 
+        // The image that takes this position keeps the current zoom and scroll
+        let carried_view = self.image.view_state();
+
         // Get corresponding label file path
         let label_path = core::operations::get_label_path_for_image(img_path);
         info!("Label path: {:?}", label_path);
 
-        // Create temp directory in system temp
-        let temp_dir = std::env::temp_dir().join("yolo_dataset_cleaner_undo");
-        info!("Temp dir: {:?}", temp_dir);
-
-        if let Err(e) = fs::create_dir_all(&temp_dir) {
-            error!("ERROR creating temp directory: {}", e);
-            return;
-        }
-        info!("Temp directory created successfully");
+        if self.settings.deletion_mode == DeletionMode::RecycleBin {
+            if let Err(e) = core::operations::send_to_recycle_bin(img_path) {
+                error!("Failed to delete image: {}", e);
+                self.ui.delete_message = Some(format!("Delete failed: {}", e));
+                return;
+            }
+            self.undo_manager.push_recycled(image_filename.clone());
+        } else {
+            // Temp directory in system temp, or the trash folder of the image's dataset
+            let temp_dir = self.deletion_dir(img_path);
+            info!("Temp dir: {:?}", temp_dir);
 
-        // Generate unique temp paths using timestamp
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis();
+            if let Err(e) = fs::create_dir_all(&temp_dir) {
+                error!("ERROR creating temp directory: {}", e);
+                return;
+            }
+            info!("Temp directory created successfully");
+
+            // Generate unique temp paths using timestamp
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis();
+
+            // Delete image and label using file_operations module
+            let (temp_image_path, temp_label_path) =
+                match core::operations::delete_image_with_label(img_path, &temp_dir, timestamp) {
+                    Ok(paths) => paths,
+                    Err(e) => {
+                        error!("Failed to delete image: {}", e);
+                        return;
+                    }
+                };
 
-        // Delete image and label using file_operations module
-        let (temp_image_path, temp_label_path) =
-            match core::operations::delete_image_with_label(img_path, &temp_dir, timestamp) {
-                Ok(paths) => paths,
-                Err(e) => {
-                    error!("Failed to delete image: {}", e);
-                    return;
-                }
+            // Create undo state and push to undo manager
+            info!("Creating undo state and adding to undo manager");
+            let undo_state = UndoState {
+                image_path: img_path.clone(),
+                label_path,
+                image_filename: image_filename.clone(),
+                deleted_at: Instant::now(),
+                temp_image_path,
+                temp_label_path,
             };
-
-        // Create undo state and push to undo manager
-        info!("Creating undo state and adding to undo manager");
-        self.undo_manager.push_delete(UndoState {
-            image_path: img_path.clone(),
-            label_path,
-            image_filename: image_filename.clone(),
-            deleted_at: Instant::now(),
-            temp_image_path,
-            temp_label_path,
-        });
+            record_in_trash_manifest(&undo_state);
+            self.undo_manager.push_delete(undo_state);
+        }
+        self.record_operation(statistics::OP_DELETE_IMAGE, 1);
+        self.image.view_cache.remove(img_path);
+        if self.image.pinned.as_ref().is_some_and(|pinned| &pinned.path == img_path) {
+            info!("Deleted image was pinned, clearing the comparison reference");
+            self.image.pinned = None;
+        }
 
         // Reload the current split to refresh the file list
         info!("Reloading current split");
@@ -362,7 +1621,9 @@ impl DatasetCleanerApp {
             self.dataset.get_image_files().len()
         );
 
-        // Navigate to appropriate position after deletion
+        // Navigate to appropriate position after deletion. The next (filtered)
+        // image takes the deleted one's place, so auto-advance is not scheduled
+        // here: advancing again would skip it.
         if let Some(filtered_pos) = current_filtered_pos {
             // Filters were active - maintain position in filtered list
             info!(
@@ -393,109 +1654,342 @@ impl DatasetCleanerApp {
             // The adjust_current_index call in reload already handled this
             self.parse_label_file();
         }
+        self.image.apply_view_state(carried_view);
 
         info!("=== DELETE_CURRENT_IMAGE COMPLETED SUCCESSFULLY ===");
     }
 
-    pub fn undo_delete(&mut self) {
-        if let Some(undo_state) = self.undo_manager.undo() {
-            info!(
-                "Attempting to undo delete for: {}",
-                undo_state.image_filename
-            );
+    /// Pin the current image as the comparison reference (unpins it if already pinned)
+    pub fn toggle_pin_current_image(&mut self) {
+        let Some(path) = self.dataset.get_image_files().get(self.current_index).cloned() else {
+            return;
+        };
+        if self.image.pinned.as_ref().is_some_and(|pinned| pinned.path == path) {
+            info!("Unpinned reference image {:?}", path);
+            self.image.pinned = None;
+            return;
+        }
+        let Some(texture) = self.image.texture.clone() else {
+            warn!("Current image is not loaded yet, cannot pin it");
+            return;
+        };
+        info!("Pinned reference image {:?}", path);
+        self.image.pinned = Some(PinnedImage {
+            path,
+            texture,
+            label: self.image.label.clone(),
+        });
+    }
 
-            // Restore image and label files using file_operations module
-            if let Err(e) = core::operations::restore_image_with_label(
-                &undo_state.temp_image_path,
-                &undo_state.image_path,
-                &undo_state.temp_label_path,
-                &undo_state.label_path,
-            ) {
-                error!("Error restoring files: {}", e);
-                return;
-            }
-            debug!("Files successfully restored");
+    /// Delete the pinned image like a normal delete, then return to the compared image
+    pub fn delete_pinned_image(&mut self) {
+        if self.read_only_blocked("delete images") {
+            return;
+        }
+        let Some(pinned_path) = self.image.pinned.as_ref().map(|pinned| pinned.path.clone()) else {
+            return;
+        };
+        let files = self.dataset.get_image_files();
+        let Some(index) = files.iter().position(|p| p == &pinned_path) else {
+            warn!("Pinned image {:?} is not in the current image list", pinned_path);
+            self.ui.delete_message =
+                Some("The pinned image is not in the current split or filter".to_string());
+            return;
+        };
+        let compared_path = files.get(self.current_index).cloned();
 
-            // Reload the dataset and reapply filters if needed
-            self.reload_dataset_with_filters(false);
+        self.current_index = index;
+        self.reset_image_state(false);
+        self.parse_label_file();
+        self.delete_current_image();
 
-            // Try to find the restored image and navigate to it
-            if let Some(index) = self
-                .dataset
-                .get_image_files()
-                .iter()
-                .position(|p| p == &undo_state.image_path)
-            {
-                self.current_index = index;
+        if let Some(compared_index) = compared_path
+            .and_then(|path| self.dataset.get_image_files().iter().position(|p| p == &path))
+        {
+            if compared_index != self.current_index {
+                self.current_index = compared_index;
                 self.reset_image_state(false);
                 self.parse_label_file();
             }
         }
     }
 
-    pub fn redo_delete(&mut self) {
-        if let Some(undo_state) = self.undo_manager.redo() {
-            info!(
-                "Attempting to redo delete for: {}",
-                undo_state.image_filename
-            );
-
-            // Re-delete using file_operations module, but we need to manually handle it
-            // since delete_image_with_label expects the original paths
-            // Re-delete: move files back to temp location using move_file
-            if let Err(e) =
-                core::operations::move_file(&undo_state.image_path, &undo_state.temp_image_path)
-            {
-                error!("Error re-deleting image: {}", e);
-                return;
-            }
+    // =========================================================================
+    // BOOKMARKS
+    // =========================================================================
 
-            // Re-delete label file if it exists
-            if let (Some(orig_label), Some(temp_label)) =
-                (&undo_state.label_path, &undo_state.temp_label_path)
-            {
-                if orig_label.exists() {
-                    if let Err(e) = core::operations::move_file(orig_label, temp_label) {
-                        error!("Error re-deleting label: {}", e);
-                    }
-                }
-            }
+    /// Bookmark the current image, or remove its bookmark
+    pub fn toggle_bookmark_current_image(&mut self) {
+        let Some(path) = self.dataset.get_image_files().get(self.current_index).cloned() else {
+            return;
+        };
+        let bookmarked = self.bookmarks.toggle(&path);
+        info!(
+            "{} {:?}",
+            if bookmarked { "Bookmarked" } else { "Removed bookmark for" },
+            path
+        );
+        self.save_bookmarks();
+        self.refresh_bookmark_filter();
+    }
 
-            // Reload the dataset to refresh file list
-            self.reload_dataset_with_filters(false);
+    /// Remove a bookmark from the bookmarks dialog
+    pub fn remove_bookmark(&mut self, path: &Path) {
+        if self.bookmarks.remove(path) {
+            info!("Removed bookmark for {:?}", path);
+            self.bookmark_dialog.thumbnails.remove(path);
+            self.save_bookmarks();
+            self.refresh_bookmark_filter();
         }
     }
 
-    fn navigate_to(&mut self, new_index: usize) {
-        if new_index != self.current_index {
-            self.current_index = new_index;
-            self.reset_image_state(true);
-            self.parse_label_file();
+    /// Keep the bookmarked-only view in sync without moving away from the current image
+    fn refresh_bookmark_filter(&mut self) {
+        if self.filter.criteria.bookmarked_only {
+            self.apply_filters_no_navigation();
+        }
+    }
 
-            // Save image index to settings
-            self.settings.last_image_index = self.current_index;
-            self.settings.save();
+    /// Show a bookmarked image, switching to its split when needed
+    pub fn jump_to_bookmark(&mut self, path: &Path) {
+        let split = path
+            .parent()
+            .and_then(Path::parent)
+            .and_then(Path::file_name)
+            .and_then(|name| DatasetSplit::parse(&name.to_string_lossy()));
+        if let Some(split) = split {
+            if split != self.dataset.current_split() {
+                self.change_split(split);
+            }
+        }
 
-            info!("Navigated to image index: {}", self.current_index);
+        match self.dataset.get_image_files().iter().position(|p| p == path) {
+            Some(index) => {
+                info!("Jumping to bookmark {:?}", path);
+                self.navigate_to(index);
+            }
+            None => warn!("Bookmarked image {:?} is not in the current split", path),
         }
     }
 
-    pub fn next_image(&mut self) {
-        let nav = Navigator::new(self.dataset.get_image_files().len());
-        if let Some(new_index) = nav.next(self.current_index, &self.filter) {
-            self.navigate_to(new_index);
+    /// Point bookmarks at the new locations of moved images
+    fn relocate_bookmarks<'a>(&mut self, moves: impl IntoIterator<Item = (&'a Path, &'a Path)>) {
+        let mut relocated = 0;
+        for (from, to) in moves {
+            if self.bookmarks.relocate(from, to) {
+                self.bookmark_dialog.thumbnails.remove(from);
+                relocated += 1;
+            }
+        }
+        if relocated > 0 {
+            info!("Relocated {} bookmarks", relocated);
+            self.save_bookmarks();
         }
     }
 
-    pub fn prev_image(&mut self) {
-        let nav = Navigator::new(self.dataset.get_image_files().len());
-        if let Some(new_index) = nav.prev(self.current_index, &self.filter) {
-            self.navigate_to(new_index);
+    fn save_bookmarks(&self) {
+        if let Err(e) = self.bookmarks.save() {
+            error!("Failed to save bookmarks: {}", e);
         }
     }
 
-    pub fn jump_to_first(&mut self) {
-        let nav = Navigator::new(self.dataset.get_image_files().len());
+    fn undo_delete(&mut self, undo_state: UndoState) {
+        info!(
+            "Attempting to undo delete for: {} (deleted {:.0}s ago)",
+            undo_state.image_filename,
+            undo_state.deleted_at.elapsed().as_secs_f32()
+        );
+
+        // Restore image and label files using file_operations module
+        if let Err(e) = core::operations::restore_image_with_label(
+            &undo_state.temp_image_path,
+            &undo_state.image_path,
+            &undo_state.temp_label_path,
+            &undo_state.label_path,
+        ) {
+            error!("Error restoring files: {}", e);
+            return;
+        }
+        debug!("Files successfully restored");
+        if core::operations::is_in_dataset_trash(&undo_state.temp_image_path) {
+            if let Err(e) = core::operations::remove_manifest_entry(&undo_state.temp_image_path) {
+                warn!("Failed to update trash manifest: {}", e);
+            }
+        }
+        self.record_operation(statistics::OP_UNDO_DELETE, 1);
+
+        // Reload the dataset and reapply filters if needed
+        self.reload_dataset_with_filters(false);
+
+        // Try to find the restored image and navigate to it
+        if let Some(index) = self
+            .dataset
+            .get_image_files()
+            .iter()
+            .position(|p| p == &undo_state.image_path)
+        {
+            self.current_index = index;
+            self.reset_image_state(false);
+            self.parse_label_file();
+        }
+    }
+
+    fn redo_delete(&mut self, undo_state: UndoState) {
+        info!(
+            "Attempting to redo delete for: {}",
+            undo_state.image_filename
+        );
+
+        // Re-delete using file_operations module, but we need to manually handle it
+        // since delete_image_with_label expects the original paths
+        // Re-delete: move files back to temp location using move_file
+        if let Err(e) =
+            core::operations::move_file(&undo_state.image_path, &undo_state.temp_image_path)
+        {
+            error!("Error re-deleting image: {}", e);
+            return;
+        }
+
+        // Re-delete label file if it exists
+        if let (Some(orig_label), Some(temp_label)) =
+            (&undo_state.label_path, &undo_state.temp_label_path)
+        {
+            if orig_label.exists() {
+                if let Err(e) = core::operations::move_file(orig_label, temp_label) {
+                    error!("Error re-deleting label: {}", e);
+                }
+            }
+        }
+
+        record_in_trash_manifest(&undo_state);
+        self.record_operation(statistics::OP_REDO_DELETE, 1);
+
+        // Reload the dataset to refresh file list
+        self.reload_dataset_with_filters(false);
+    }
+
+    /// Where `delete_current_image` moves files for the temp-folder and dataset trash modes
+    fn deletion_dir(&self, image_path: &Path) -> PathBuf {
+        let root = self.dataset.root_for_image(image_path);
+        match (self.settings.deletion_mode, root) {
+            (DeletionMode::DatasetTrash, Some(root)) => core::operations::dataset_trash_dir(root),
+            _ => std::env::temp_dir().join("yolo_dataset_cleaner_undo"),
+        }
+    }
+
+    /// Files and bytes in the cleaner trash of every loaded root
+    pub fn cleaner_trash_usage(&self) -> (usize, u64) {
+        self.dataset
+            .roots()
+            .map(|root| core::operations::trash_usage(&core::operations::dataset_trash_dir(root)))
+            .fold((0, 0), |(files, bytes), (f, b)| (files + f, bytes + b))
+    }
+
+    /// Permanently delete the cleaner trash of every loaded root
+    ///
+    /// Undo history for the purged files is dropped, since it can no longer be restored.
+    pub fn empty_cleaner_trash(&mut self) {
+        if self.read_only_blocked("empty the cleaner trash") {
+            return;
+        }
+        let trash_dirs: Vec<PathBuf> = self
+            .dataset
+            .roots()
+            .map(|root| core::operations::dataset_trash_dir(root))
+            .collect();
+
+        let mut files = 0;
+        let mut bytes = 0;
+        let mut failures = Vec::new();
+        for trash_dir in &trash_dirs {
+            match core::operations::empty_dataset_trash(trash_dir) {
+                Ok((f, b)) => {
+                    files += f;
+                    bytes += b;
+                }
+                Err(e) => {
+                    error!("Failed to empty cleaner trash: {}", e);
+                    failures.push(e.to_string());
+                }
+            }
+        }
+
+        self.undo_manager.retain(|action| match action {
+            UndoAction::Delete(state) => !trash_dirs
+                .iter()
+                .any(|dir| state.temp_image_path.starts_with(dir)),
+            UndoAction::IntegrityCleanup(files) => !files
+                .iter()
+                .any(|file| trash_dirs.iter().any(|dir| file.temp_path.starts_with(dir))),
+            _ => true,
+        });
+        self.record_operation(statistics::OP_EMPTY_TRASH, files);
+
+        let mut message = format!(
+            "Emptied cleaner trash: {} files, {:.1} MB freed",
+            files,
+            bytes as f64 / (1024.0 * 1024.0)
+        );
+        if !failures.is_empty() {
+            message.push_str(&format!(" ({} failed: {})", failures.len(), failures.join("; ")));
+        }
+        self.ui.delete_message = Some(message);
+    }
+
+    fn navigate_to(&mut self, new_index: usize) {
+        if new_index != self.current_index {
+            if self.settings.remember_view_per_image {
+                if let Some(path) = self.dataset.get_image_files().get(self.current_index) {
+                    self.image.view_cache.insert(path.clone(), self.image.view_state());
+                }
+            }
+
+            self.current_index = new_index;
+            self.reset_image_state(self.settings.zoom_reset_on_navigate);
+
+            if self.settings.remember_view_per_image {
+                let view = self
+                    .dataset
+                    .get_image_files()
+                    .get(self.current_index)
+                    .and_then(|path| self.image.view_cache.get(path).copied())
+                    .unwrap_or_default();
+                self.image.apply_view_state(view);
+            }
+            self.parse_label_file();
+            self.schedule_prefetch();
+
+            // Save image index to settings
+            self.settings.last_image_index = self.current_index;
+            self.settings.save();
+
+            info!("Navigated to image index: {}", self.current_index);
+        }
+    }
+
+    /// Jump to an absolute image index (ignored if out of range)
+    pub fn jump_to_index(&mut self, index: usize) {
+        if index < self.dataset.get_image_files().len() {
+            self.navigate_to(index);
+        }
+    }
+
+    pub fn next_image(&mut self) {
+        let nav = Navigator::new(self.dataset.get_image_files().len());
+        if let Some(new_index) = nav.next(self.current_index, &self.filter) {
+            self.navigate_to(new_index);
+        }
+    }
+
+    pub fn prev_image(&mut self) {
+        let nav = Navigator::new(self.dataset.get_image_files().len());
+        if let Some(new_index) = nav.prev(self.current_index, &self.filter) {
+            self.navigate_to(new_index);
+        }
+    }
+
+    pub fn jump_to_first(&mut self) {
+        let nav = Navigator::new(self.dataset.get_image_files().len());
         if let Some(new_index) = nav.first(&self.filter) {
             self.navigate_to(new_index);
         }
@@ -515,6 +2009,181 @@ impl DatasetCleanerApp {
         }
     }
 
+    /// Jump to the next image in the filtered view that fails to decode
+    ///
+    /// Decoding every image is slow, so the scan runs on a background thread
+    /// and `poll_error_scan` navigates once it reports. Does not wrap around.
+    pub fn navigate_to_next_error(&mut self) {
+        if self.image.is_scanning_for_errors() {
+            return;
+        }
+        let image_files = self.dataset.get_image_files();
+        let indices: Vec<usize> = if self.filter.is_active() {
+            let start = self
+                .filter
+                .get_filtered_index(self.current_index)
+                .map_or(0, |position| position + 1);
+            self.filter.filtered_indices().iter().skip(start).copied().collect()
+        } else {
+            (self.current_index + 1..image_files.len()).collect()
+        };
+        let paths: Vec<PathBuf> = indices
+            .iter()
+            .filter_map(|&index| image_files.get(index).cloned())
+            .collect();
+        if paths.is_empty() {
+            self.ui.toast_message = Some(("No images after this one".to_string(), Instant::now()));
+            return;
+        }
+
+        info!("Scanning {} images for the next unreadable one", paths.len());
+        self.ui.toast_message = Some((
+            format!("Checking {} images for the next unreadable one...", paths.len()),
+            Instant::now(),
+        ));
+        let (tx, rx) = channel();
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.image.error_scan_receiver = Some(rx);
+        self.image.error_scan_cancel_flag = Some(cancel_flag.clone());
+        self.image.error_scan_generation = self.dataset.generation();
+
+        thread::spawn(move || {
+            let found = core::image::find_first_corrupt_image(
+                &paths,
+                core::image::ImageCheck::FullDecode,
+                &cancel_flag,
+            );
+            let _ = tx.send(found.map(|position| indices[position]));
+        });
+    }
+
+    /// Stop a running search for the next unreadable image
+    fn cancel_error_scan(&mut self) {
+        if let Some(flag) = self.image.error_scan_cancel_flag.take() {
+            flag.store(true, Ordering::Relaxed);
+        }
+        self.image.error_scan_receiver = None;
+    }
+
+    /// Navigate to the image found by the error scan, if it has finished
+    fn poll_error_scan(&mut self) {
+        let Some(receiver) = &self.image.error_scan_receiver else {
+            return;
+        };
+        let found = match receiver.try_recv() {
+            Ok(found) => found,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
+        };
+        self.image.error_scan_receiver = None;
+        self.image.error_scan_cancel_flag = None;
+
+        if self.image.error_scan_generation != self.dataset.generation() {
+            debug!("Dropping error scan result for an outdated file list");
+            return;
+        }
+        match found {
+            Some(index) => {
+                info!("Next unreadable image at index {}", index);
+                self.navigate_to(index);
+            }
+            None => {
+                self.ui.toast_message = Some((
+                    "No unreadable images after this one".to_string(),
+                    Instant::now(),
+                ));
+            }
+        }
+    }
+
+    /// Jump to the next (or previous) image of `category` in the filtered view
+    ///
+    /// Uses the category cache, so nothing happens until it has been built.
+    /// Wraps around at the end of the view with a toast.
+    pub fn jump_to_category(&mut self, category: core::analysis::ImageCategory, forward: bool) {
+        let image_count = self.dataset.get_image_files().len();
+        let Some(categories) = self.categories.get(image_count) else {
+            if image_count > 0 {
+                self.ui.toast_message =
+                    Some(("Still categorizing images, try again in a moment".to_string(), Instant::now()));
+            }
+            return;
+        };
+
+        let nav = Navigator::new(image_count);
+        let matches = |index: usize| categories[index] == category;
+        let found = if forward {
+            nav.next_matching(self.current_index, &self.filter, matches)
+        } else {
+            nav.prev_matching(self.current_index, &self.filter, matches)
+        };
+
+        match found {
+            Some((index, wrapped)) => {
+                if wrapped {
+                    let from = if forward { "first" } else { "last" };
+                    self.ui.toast_message = Some((
+                        format!("No more {} images, wrapped to the {}", category.as_str(), from),
+                        Instant::now(),
+                    ));
+                }
+                info!("Jumping to {} image at index {}", category.as_str(), index);
+                self.navigate_to(index);
+            }
+            None => {
+                self.ui.toast_message = Some((
+                    format!("No other {} images in this view", category.as_str()),
+                    Instant::now(),
+                ));
+            }
+        }
+    }
+
+    /// Close the settings dialog
+    pub fn close_settings_dialog(&mut self) {
+        self.ui.show_settings_dialog = false;
+        self.save_class_table();
+    }
+
+    /// Persist the class table after an edit in the settings dialog
+    ///
+    /// Classes taken from a data.yaml are only saved once they are edited.
+    /// Categories depend on the class ids, so a changed id set recategorizes
+    /// the current split.
+    pub fn save_class_table(&mut self) {
+        let baseline = self.config.data_yaml_classes.as_ref().unwrap_or(&self.settings.classes);
+        if *baseline == self.config.classes {
+            return;
+        }
+        let ids_changed = baseline.iter().map(|c| c.id).ne(self.config.classes.iter().map(|c| c.id));
+        info!("Saving class table with {} classes", self.config.classes.len());
+        self.settings.classes = self.config.classes.clone();
+        self.settings.save();
+        if ids_changed {
+            self.refresh_category_cache();
+        }
+    }
+
+    /// Set the zoom level, clamped to the configured zoom range
+    pub fn set_zoom(&mut self, zoom_level: f32) {
+        self.image.zoom_level = zoom_level.clamp(self.config.min_zoom, self.settings.max_zoom);
+    }
+
+    /// Cycle between resetting, keeping and remembering zoom per image on navigation
+    pub fn cycle_zoom_navigate_mode(&mut self) {
+        self.set_zoom_navigate_mode(self.settings.zoom_navigate_mode().next());
+    }
+
+    /// Choose what happens to zoom and scroll when navigating between images
+    pub fn set_zoom_navigate_mode(&mut self, mode: ZoomNavigateMode) {
+        self.settings.set_zoom_navigate_mode(mode);
+        if mode != ZoomNavigateMode::PerImage {
+            self.image.view_cache.clear();
+        }
+        self.settings.save();
+        info!("Zoom on navigate: {:?}", mode);
+    }
+
     pub fn toggle_fullscreen(&mut self) {
         self.ui.fullscreen_mode = !self.ui.fullscreen_mode;
         info!("Fullscreen mode toggled: {}", self.ui.fullscreen_mode);
@@ -531,42 +2200,262 @@ impl DatasetCleanerApp {
     }
 
     /// Internal method to apply filters with optional navigation
+    ///
+    /// With navigation, large splits are filtered on a background thread (see
+    /// `poll_filter_job`). Without navigation the caller relies on the new
+    /// indices right away, so filtering always runs inline.
     fn apply_filters_internal(&mut self, navigate: bool) {
+        self.cancel_filter_job();
+
+        // A manifest is matched by name, fast enough to run inline
+        if let Some(manifest) = &self.filter.manifest {
+            let image_files = self.dataset.get_image_files();
+            let matched = core::operations::match_manifest(&manifest.entries, image_files);
+            self.filter.total_count = image_files.len();
+            self.filter.set_filtered_indices(matched.indices);
+            self.filter.computed_for = Some(self.dataset.generation());
+            if let Some(manifest) = &mut self.filter.manifest {
+                manifest.missing = matched.missing;
+            }
+            info!(
+                "Manifest filter applied: {} / {} images listed",
+                self.filter.filtered_indices().len(),
+                self.filter.total_count
+            );
+            if navigate {
+                self.navigate_into_filter();
+            }
+            return;
+        }
+
         let image_files = self.dataset.get_image_files();
-        self.filter.total_count = image_files.len();
-        self.filter.filtered_indices =
-            core::filter::apply_filters(image_files, &self.filter.criteria);
+        if navigate
+            && self.filter.is_active()
+            && image_files.len() >= BACKGROUND_FILTER_MIN_IMAGES
+        {
+            self.start_filter_job();
+        } else {
+            self.filter.total_count = image_files.len();
+            let filtered = core::filter::apply_filters(
+                image_files,
+                &self.filter.criteria,
+                &self.bookmarks,
+                &self.config.class_ids(),
+            );
+            self.filter.set_filtered_indices(filtered);
+            self.filter.computed_for = Some(self.dataset.generation());
+
+            info!(
+                "Filters applied: {} / {} images match criteria",
+                self.filter.filtered_indices().len(),
+                self.filter.total_count
+            );
+
+            if navigate {
+                self.navigate_into_filter();
+            }
+        }
+
+        // Save filter settings
+        self.settings.filter_criteria = self.filter.criteria.clone();
+        self.settings.save();
+    }
+
+    /// Evaluate the filter criteria for the current split on a background thread
+    ///
+    /// The previous result stays in place for navigation until the new one lands,
+    /// unless it belongs to an older file list, in which case it is dropped.
+    fn start_filter_job(&mut self) {
+        let generation = self.dataset.generation();
+        if self.filter.computed_for != Some(generation) {
+            self.filter.set_filtered_indices(Vec::new());
+            self.filter.computed_for = None;
+        }
+
+        let image_files: Vec<PathBuf> = self.dataset.get_image_files().clone();
+        let criteria = self.filter.criteria.clone();
+        let bookmarks = self.bookmarks.clone();
+        let known_class_ids = self.config.class_ids();
+        info!("Filtering {} images in the background", image_files.len());
+
+        let (tx, rx) = channel();
+        self.filter.progress_receiver = Some(rx);
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.filter.cancel_flag = Some(cancel_flag.clone());
+        self.filter.progress = Some((0, image_files.len()));
+        self.filter.job = Some(generation);
+
+        thread::spawn(move || {
+            core::filter::apply_filters_with_progress(
+                &image_files,
+                &criteria,
+                &bookmarks,
+                &known_class_ids,
+                Some(tx),
+                Some(cancel_flag),
+            );
+        });
+    }
+
+    /// Cancel a running background filter job, keeping the current result
+    fn cancel_filter_job(&mut self) {
+        if let Some(flag) = self.filter.cancel_flag.take() {
+            flag.store(true, Ordering::Relaxed);
+        }
+        self.filter.progress_receiver = None;
+        self.filter.progress = None;
+        self.filter.job = None;
+    }
+
+    /// Receive progress and the result of the background filter job
+    fn poll_filter_job(&mut self) {
+        let mut finished = None;
+        if let Some(receiver) = &self.filter.progress_receiver {
+            while let Ok(msg) = receiver.try_recv() {
+                match msg {
+                    core::filter::FilterProgressMessage::Progress { current, total } => {
+                        self.filter.progress = Some((current, total));
+                    }
+                    core::filter::FilterProgressMessage::Complete(indices) => {
+                        finished = Some(Some(indices));
+                    }
+                    core::filter::FilterProgressMessage::Cancelled => {
+                        finished = Some(None);
+                    }
+                }
+            }
+        }
 
+        let Some(result) = finished else {
+            return;
+        };
+        let job = self.filter.job;
+        self.cancel_filter_job();
+        let (Some(indices), Some(generation)) = (result, job) else {
+            return;
+        };
+        if generation != self.dataset.generation() {
+            debug!("Dropping filter result for an outdated file list");
+            return;
+        }
+
+        self.filter.total_count = self.dataset.get_image_files().len();
+        self.filter.set_filtered_indices(indices);
+        self.filter.computed_for = Some(generation);
         info!(
             "Filters applied: {} / {} images match criteria",
-            self.filter.filtered_indices.len(),
+            self.filter.filtered_indices().len(),
             self.filter.total_count
         );
+        self.navigate_into_filter();
+    }
 
-        if navigate {
-            // If current index is not in filtered list, navigate to first filtered image
-            if self.filter.is_active() && !self.filter.filtered_indices.is_empty() {
-                if let Some(filtered_idx) = self.filter.get_filtered_index(self.current_index) {
-                    // Current image is in filtered list, navigate to it (updates display)
-                    if let Some(actual_index) = self.filter.get_actual_index(filtered_idx) {
-                        self.navigate_to(actual_index);
-                    }
-                } else {
-                    // Current image not in filtered list, go to first filtered image
-                    if let Some(actual_index) = self.filter.get_actual_index(0) {
-                        self.navigate_to(actual_index);
-                    }
+    /// Move to the current image in the filtered view, or to the first filtered image
+    fn navigate_into_filter(&mut self) {
+        // If current index is not in filtered list, navigate to first filtered image
+        if self.filter.is_active() && !self.filter.filtered_indices().is_empty() {
+            if let Some(filtered_idx) = self.filter.get_filtered_index(self.current_index) {
+                // Current image is in filtered list, navigate to it (updates display)
+                if let Some(actual_index) = self.filter.get_actual_index(filtered_idx) {
+                    self.navigate_to(actual_index);
+                }
+            } else {
+                // Current image not in filtered list, go to first filtered image
+                if let Some(actual_index) = self.filter.get_actual_index(0) {
+                    self.navigate_to(actual_index);
                 }
             }
         }
+    }
 
-        // Save filter settings
-        self.settings.filter_criteria = self.filter.criteria.clone();
+    /// Open the filter dialog, scanning the current split for available map names
+    pub fn open_filter_dialog(&mut self) {
+        self.filter.available_maps = core::filter::collect_map_names(self.dataset.get_image_files());
+        self.ui.show_filter_dialog = true;
+    }
+
+    /// Load the preset at `index` (in settings order) and apply it
+    pub fn apply_filter_preset(&mut self, index: usize) {
+        let Some(preset) = self.settings.filter_presets.get(index).cloned() else {
+            return;
+        };
+        info!("Applying filter preset '{}'", preset.name);
+        self.filter.manifest = None;
+        self.filter.criteria = preset.criteria;
+        self.filter.preset_name_input = preset.name.clone();
+        self.filter.active_preset = Some(preset.name);
+        self.apply_filters();
+    }
+
+    /// Save the current criteria as a preset called `name` (replacing one with that name)
+    pub fn save_filter_preset(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+        self.settings
+            .save_filter_preset(name, self.filter.criteria.clone());
+        self.filter.active_preset = Some(name.to_string());
         self.settings.save();
+        info!("Saved filter preset '{}'", name);
+    }
+
+    /// Delete the preset called `name`
+    pub fn delete_filter_preset(&mut self, name: &str) {
+        if self.settings.remove_filter_preset(name) {
+            if self.filter.active_preset.as_deref() == Some(name) {
+                self.filter.active_preset = None;
+            }
+            self.settings.save();
+            info!("Deleted filter preset '{}'", name);
+        }
+    }
+
+    /// Show only the images named in the manifest at `path` (filenames or relative paths)
+    ///
+    /// The manifest replaces the filter criteria until filters are cleared.
+    pub fn load_manifest_filter(&mut self, path: &Path) {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                error!("Failed to read manifest {:?}: {}", path, e);
+                self.ui.toast_message = Some((format!("⚠ Failed to read {}: {}", path.display(), e), Instant::now()));
+                return;
+            }
+        };
+        let entries = core::operations::parse_manifest(&content);
+        if entries.is_empty() {
+            self.ui.toast_message = Some((format!("⚠ {} lists no images", path.display()), Instant::now()));
+            return;
+        }
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        info!("Loading manifest {:?} with {} entries as filter", path, entries.len());
+        self.filter.manifest = Some(crate::state::ManifestFilter {
+            name: name.clone(),
+            entries,
+            missing: Vec::new(),
+        });
+        self.filter.active_preset = None;
+        self.apply_filters();
+
+        if let Some(manifest) = &self.filter.manifest {
+            let found = manifest.entries.len() - manifest.missing.len();
+            let message = if manifest.missing.is_empty() {
+                format!("{}: all {} entries found", name, found)
+            } else {
+                format!("{}: {} found, {} missing", name, found, manifest.missing.len())
+            };
+            self.ui.toast_message = Some((message, Instant::now()));
+        }
     }
 
     /// Clear all active filters
     pub fn clear_filters(&mut self) {
+        self.cancel_filter_job();
         self.filter.clear();
 
         // Save filter settings
@@ -577,17 +2466,37 @@ impl DatasetCleanerApp {
     }
 
     pub fn process_black_images(&mut self) {
+        self.process_batch_removal(BatchRemovalMode::Black);
+    }
+
+    /// Delete overexposed images whose dominant color is near white
+    pub fn process_white_images(&mut self) {
+        self.process_batch_removal(BatchRemovalMode::White);
+    }
+
+    /// Delete near-black and near-white images in a single scan
+    pub fn process_extreme_images(&mut self) {
+        self.process_batch_removal(BatchRemovalMode::Extreme);
+    }
+
+    /// Scan the current split in the background and delete the images `mode` matches
+    fn process_batch_removal(&mut self, mode: BatchRemovalMode) {
+        if self.read_only_blocked("delete images") {
+            return;
+        }
         if self.dataset.get_image_files().is_empty() {
-            warn!("No images to process for black image removal");
+            warn!("No images to process for {}", mode.title());
             return;
         }
 
         info!(
-            "Starting batch processing to remove black images, total images: {}",
+            "Starting batch processing ({}), total images: {}",
+            mode.title(),
             self.dataset.get_image_files().len()
         );
         // Set batch processing flag
         self.batch.processing = true;
+        self.batch.mode = mode;
 
         // Initialize stats
         let stats = BatchStats::default();
@@ -627,7 +2536,7 @@ impl DatasetCleanerApp {
                 // Load and analyze image
                 if let Ok(img) = image::open(img_path) {
                     if let Some((r, g, b)) = core::image::calculate_dominant_color(&img) {
-                        if core::image::is_near_black((r, g, b)) {
+                        if mode.matches((r, g, b)) {
                             // Delete image file
                             if fs::remove_file(img_path).is_ok() {
                                 // Delete corresponding label file using file_operations
@@ -666,72 +2575,196 @@ impl DatasetCleanerApp {
         }
     }
 
-    pub fn analyze_balance(&mut self) {
-        self.analyze_balance_for_split(self.balance.selected_split_index);
+    /// Images in the filtered view (or every image if no filter is active)
+    pub fn filtered_image_files(&self) -> Vec<PathBuf> {
+        let image_files = self.dataset.get_image_files();
+        if self.filter.is_active() {
+            self.filter
+                .filtered_indices()
+                .iter()
+                .filter_map(|&idx| image_files.get(idx).cloned())
+                .collect()
+        } else {
+            image_files.clone()
+        }
     }
 
-    /// Analyze balance for a specific split (0=Train, 1=Val, 2=Test, 3=All)
-    pub fn analyze_balance_for_split(&mut self, split_index: usize) {
-        if let Some(dataset_path) = self.dataset.dataset_path() {
-            info!("Starting balance analysis for split_index: {}", split_index);
-            self.balance.analyzing = true;
+    /// Copy the images in the filtered view (and their labels) to `dest/images` and `dest/labels`
+    ///
+    /// Source files are left untouched; filenames are preserved.
+    pub fn export_filtered_subset(&mut self, dest: PathBuf) {
+        let image_files = self.filtered_image_files();
+        if image_files.is_empty() {
+            warn!("No images to export");
+            return;
+        }
+
+        info!("Exporting {} images to {:?}", image_files.len(), dest);
+        self.export_subset.processing = true;
+        self.export_subset.stats = Some(ExportStats {
+            total: image_files.len(),
+            ..ExportStats::default()
+        });
+
+        let (tx, rx) = channel::<ExportProgressMessage>();
+        self.export_subset.progress_receiver = Some(rx);
+
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.export_subset.cancel_flag = Some(cancel_flag.clone());
+
+        thread::spawn(move || {
+            info!("Background thread started for subset export");
+            let mut stats = ExportStats {
+                total: image_files.len(),
+                ..ExportStats::default()
+            };
+
+            if let Err(e) = fs::create_dir_all(dest.join("images")).and_then(|_| fs::create_dir_all(dest.join("labels"))) {
+                error!("Failed to create export folders in {:?}: {}", dest, e);
+                stats.failed = image_files.len();
+                let _ = tx.send(ExportProgressMessage::Complete(stats));
+                return;
+            }
+
+            for (idx, img_path) in image_files.iter().enumerate() {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    warn!("Subset export cancelled by user at image {}/{}", idx, image_files.len());
+                    let _ = tx.send(ExportProgressMessage::Cancelled(stats));
+                    return;
+                }
+
+                stats.current_progress = idx + 1;
+
+                let label_path = core::operations::get_label_path_for_image(img_path)
+                    .filter(|label_path| label_path.exists());
+                match core::operations::copy_image_with_label(img_path, label_path.as_deref(), &dest, None) {
+                    Ok((_, label_copy)) => {
+                        stats.images_copied += 1;
+                        if label_copy.is_some() {
+                            stats.labels_copied += 1;
+                        }
+                    }
+                    Err(e) => {
+                        error!("Failed to copy {:?}: {}", img_path, e);
+                        stats.failed += 1;
+                    }
+                }
+
+                // Send progress update every 10 images or on last image
+                if idx % 10 == 0 || idx == image_files.len() - 1 {
+                    let _ = tx.send(ExportProgressMessage::Progress(stats.clone()));
+                }
+            }
+
+            info!(
+                "Subset export complete. Images: {}, labels: {}, failed: {}",
+                stats.images_copied, stats.labels_copied, stats.failed
+            );
+            let _ = tx.send(ExportProgressMessage::Complete(stats));
+        });
+    }
+
+    /// Cancel a running subset export
+    pub fn cancel_export_subset(&mut self) {
+        info!("User requested subset export cancellation");
+        if let Some(flag) = &self.export_subset.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    pub fn analyze_balance(&mut self) {
+        self.analyze_balance_for_split(self.balance.selected_split_index);
+    }
+
+    /// Analyze balance for a specific split (0=Train, 1=Val, 2=Test, 3=All)
+    ///
+    /// Takes over from a running automatic analysis.
+    pub fn analyze_balance_for_split(&mut self, split_index: usize) {
+        if self.balance.auto_status == AutoAnalysisStatus::Running {
+            if let Some(flag) = self.balance.cancel_flag.take() {
+                flag.store(true, Ordering::Relaxed);
+            }
+        }
+        self.balance.auto_status = AutoAnalysisStatus::Idle;
+        if self.start_balance_analysis(split_index) {
             self.balance.show_dialog = true;
+        }
+    }
+
+    /// Start the balance analysis thread (false if no dataset is loaded)
+    fn start_balance_analysis(&mut self, split_index: usize) -> bool {
+        if let Some(dataset_path) = self.dataset.dataset_path() {
+            info!("Starting balance analysis for split_index: {}", split_index);
+            self.balance.analyzing = true;
             self.balance.current_progress = 0;
             self.balance.total_images = 0;
             self.balance.selected_split_index = split_index;
+            self.balance.cached_best_bg_dest = None;
+            self.balance.cached_best_player_dest = None;
+            self.balance.cached_best_hard_case_dest = None;
+            self.balance.split_results.clear();
 
             // Create a channel for progress updates
             let (tx, rx) = channel();
             self.balance.progress_receiver = Some(rx);
 
+            // Stats of every split arrive separately, after the analysis itself
+            let (global_tx, global_rx) = channel();
+            self.balance.global_stats_receiver = Some(global_rx);
+
             // Create cancellation flag
             let cancel_flag = Arc::new(AtomicBool::new(false));
             self.balance.cancel_flag = Some(cancel_flag.clone());
 
             // Clone the data needed for the background thread
             let dataset_path = dataset_path.clone();
+            let known_class_ids = self.config.class_ids();
 
             if split_index == 3 {
-                // Analyze ALL splits and combine results
+                // Analyze every split in turn and report on their combined stats
                 thread::spawn(move || {
                     info!("Background thread started for ALL splits analysis");
-                    
-                    // Analyze each split
-                    let train_stats = core::analysis::analyze_dataset(
-                        &dataset_path,
-                        core::dataset::DatasetSplit::Train,
-                    );
-                    let val_stats = core::analysis::analyze_dataset(
-                        &dataset_path,
-                        core::dataset::DatasetSplit::Val,
-                    );
-                    let test_stats = core::analysis::analyze_dataset(
-                        &dataset_path,
-                        core::dataset::DatasetSplit::Test,
-                    );
-                    
-                    // Combine stats
-                    let mut combined = core::analysis::BalanceStats::new();
-                    combined.total_images = train_stats.total_images + val_stats.total_images + test_stats.total_images;
-                    combined.ct_only = train_stats.ct_only + val_stats.ct_only + test_stats.ct_only;
-                    combined.t_only = train_stats.t_only + val_stats.t_only + test_stats.t_only;
-                    combined.multiple_player = train_stats.multiple_player + val_stats.multiple_player + test_stats.multiple_player;
-                    combined.background = train_stats.background + val_stats.background + test_stats.background;
-                    combined.hard_case = train_stats.hard_case + val_stats.hard_case + test_stats.hard_case;
-                    
-                    // Combine location counts
-                    for (loc, count) in train_stats.location_counts {
-                        *combined.location_counts.entry(loc).or_insert(0) += count;
-                    }
-                    for (loc, count) in val_stats.location_counts {
-                        *combined.location_counts.entry(loc).or_insert(0) += count;
-                    }
-                    for (loc, count) in test_stats.location_counts {
-                        *combined.location_counts.entry(loc).or_insert(0) += count;
-                    }
-                    
-                    info!("ALL splits analysis complete: {} total images", combined.total_images);
-                    let _ = tx.send(core::analysis::BalanceProgressMessage::Complete(combined));
+                    let (split_tx, split_rx) = channel();
+                    let global = thread::scope(|scope| {
+                        let worker = scope.spawn(|| {
+                            core::analysis::analyze_all_splits_with_progress(
+                                &dataset_path,
+                                &known_class_ids,
+                                split_tx,
+                                cancel_flag.clone(),
+                            )
+                        });
+
+                        // Progress counts the images of finished splits plus the current one
+                        let mut done = core::analysis::BalanceStats::new();
+                        for message in split_rx {
+                            match message {
+                                core::analysis::AllSplitsProgressMessage::SplitProgress { current, total, .. } => {
+                                    let _ = tx.send(core::analysis::BalanceProgressMessage::Progress {
+                                        current: done.total_images + current,
+                                        total: done.total_images + total,
+                                        stats: done.clone(),
+                                    });
+                                }
+                                core::analysis::AllSplitsProgressMessage::SplitComplete { stats, .. } => {
+                                    done.merge(&stats);
+                                }
+                                core::analysis::AllSplitsProgressMessage::AllComplete(_) => {}
+                                core::analysis::AllSplitsProgressMessage::Cancelled(partial) => {
+                                    // The splits finished before the cancel
+                                    let _ = tx.send(core::analysis::BalanceProgressMessage::Cancelled(partial.combined()));
+                                }
+                            }
+                        }
+                        worker.join().ok()
+                    });
+
+                    let Some(global) = global.filter(|_| !cancel_flag.load(Ordering::Relaxed)) else {
+                        return;
+                    };
+                    info!("ALL splits analysis complete: {} total images", global.total_images());
+                    let _ = tx.send(core::analysis::BalanceProgressMessage::Complete(global.combined()));
+                    let _ = global_tx.send(global);
                 });
             } else {
                 // Analyze single split
@@ -744,17 +2777,156 @@ impl DatasetCleanerApp {
                 
                 thread::spawn(move || {
                     info!("Background thread started for balance analysis");
-                    let _stats = core::analysis::analyze_dataset_with_progress(
-                        &dataset_path,
-                        split,
-                        Some(tx),
-                        Some(cancel_flag),
+                    let stats = log_timed_op_with_count(
+                        "Balance analysis",
+                        || {
+                            core::analysis::analyze_dataset_with_progress(
+                                &dataset_path,
+                                split,
+                                &known_class_ids,
+                                Some(tx),
+                                Some(cancel_flag.clone()),
+                            )
+                        },
+                        |stats| stats.total_images,
                     );
                     info!("Background thread completed balance analysis");
+                    if cancel_flag.load(Ordering::Relaxed) {
+                        return;
+                    }
+
+                    // Scan the other splits once so destinations need no further scans
+                    let mut global = core::analysis::GlobalBalanceStats::default()
+                        .with_missing_splits(&dataset_path);
+                    for other in DatasetSplit::all() {
+                        *global.get_mut(other) = if other == split {
+                            stats.clone()
+                        } else {
+                            core::analysis::analyze_dataset(&dataset_path, other, &known_class_ids)
+                        };
+                    }
+                    let _ = global_tx.send(global);
                 });
             }
+            true
         } else {
             warn!("No dataset loaded, cannot analyze balance");
+            false
+        }
+    }
+
+    /// Pick the rebalance destination of each category from precomputed stats
+    fn cache_best_destinations(&mut self, stats: &core::analysis::GlobalBalanceStats) {
+        let current_split = self.dataset.current_split();
+        let target_ratios = self.target_ratios();
+        let split_ratios = self.split_ratios().renormalized(&stats.missing);
+        let best = |category| {
+            core::analysis::choose_destination_split(
+                stats,
+                current_split,
+                category,
+                &target_ratios,
+                &split_ratios,
+            )
+        };
+
+        self.balance.cached_best_bg_dest = best(core::analysis::ImageCategory::Background);
+        self.balance.cached_best_player_dest = best(core::analysis::ImageCategory::CTOnly);
+        self.balance.cached_best_hard_case_dest = best(core::analysis::ImageCategory::HardCase);
+    }
+
+    /// Analyze train, val and test one after another on a background thread
+    ///
+    /// Results land in `balance.all_splits_results` once every split is done.
+    pub fn analyze_balance_all_splits(&mut self) {
+        let Some(dataset_path) = self.dataset.dataset_path().cloned() else {
+            warn!("No dataset loaded, cannot analyze balance");
+            return;
+        };
+        info!("Starting sequential balance analysis of all splits");
+
+        self.cancel_all_splits_balance();
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.balance.all_splits_cancel_flag = Some(cancel_flag.clone());
+        self.balance.all_splits_analyzing = true;
+        self.balance.all_splits_results = None;
+        self.balance.all_splits_progress = [(0, 0); 3];
+        self.balance.all_splits_done = [false; 3];
+
+        let (tx, rx) = channel();
+        self.balance.all_splits_receiver = Some(rx);
+        let known_class_ids = self.config.class_ids();
+        thread::spawn(move || {
+            log_timed_op_with_count(
+                "Balance analysis (all splits)",
+                || {
+                    core::analysis::analyze_all_splits_with_progress(
+                        &dataset_path,
+                        &known_class_ids,
+                        tx,
+                        cancel_flag,
+                    )
+                },
+                |stats| stats.total_images(),
+            );
+        });
+    }
+
+    /// Cancel a running analysis of all splits
+    pub fn cancel_all_splits_balance(&mut self) {
+        if let Some(flag) = self.balance.all_splits_cancel_flag.take() {
+            flag.store(true, Ordering::Relaxed);
+        }
+        self.balance.all_splits_receiver = None;
+        self.balance.all_splits_analyzing = false;
+    }
+
+    /// Collect progress and results of the all-splits analysis thread
+    fn poll_all_splits_balance(&mut self) {
+        let Some(receiver) = &self.balance.all_splits_receiver else {
+            return;
+        };
+        let slot = |split: DatasetSplit| {
+            DatasetSplit::all()
+                .iter()
+                .position(|s| *s == split)
+                .unwrap_or(0)
+        };
+
+        let mut finished = None;
+        let mut cancelled = false;
+        while let Ok(message) = receiver.try_recv() {
+            match message {
+                core::analysis::AllSplitsProgressMessage::SplitProgress { split, current, total } => {
+                    self.balance.all_splits_progress[slot(split)] = (current, total);
+                }
+                core::analysis::AllSplitsProgressMessage::SplitComplete { split, stats } => {
+                    let total = stats.total_images;
+                    self.balance.all_splits_progress[slot(split)] = (total, total);
+                    self.balance.all_splits_done[slot(split)] = true;
+                }
+                core::analysis::AllSplitsProgressMessage::AllComplete(stats) => {
+                    finished = Some(stats);
+                }
+                core::analysis::AllSplitsProgressMessage::Cancelled(_) => cancelled = true,
+            }
+        }
+
+        if cancelled {
+            info!("All-splits balance analysis cancelled");
+            self.cancel_all_splits_balance();
+            return;
+        }
+        if let Some(results) = finished {
+            info!(
+                "All-splits balance analysis complete: {} total images",
+                results.total_images()
+            );
+            self.balance.all_splits_results = Some(results);
+            self.balance.analyzed_at = Some(chrono::Local::now());
+            self.balance.all_splits_analyzing = false;
+            self.balance.all_splits_receiver = None;
+            self.balance.all_splits_cancel_flag = None;
         }
     }
 
@@ -771,6 +2943,10 @@ impl DatasetCleanerApp {
             if let Some(dataset_path) = self.dataset.dataset_path() {
                 info!("Calculating rebalance plan for {:?}", config.category);
                 
+                let config = core::analysis::RebalanceConfig {
+                    known_class_ids: self.config.class_ids(),
+                    ..config
+                };
                 let plan = core::analysis::calculate_rebalance_plan(
                     dataset_path,
                     &config,
@@ -785,8 +2961,10 @@ impl DatasetCleanerApp {
                 } else {
                     info!("Plan calculated: {} images to move", plan.len());
                     self.rebalance.plan = Some(plan);
+                    self.rebalance.is_global = false;
                     self.rebalance.show_preview = true;
                     self.rebalance.error_message = None;
+                    self.estimate_rebalance_duration();
                 }
                 
                 self.rebalance.config = Some(config);
@@ -799,7 +2977,18 @@ impl DatasetCleanerApp {
     }
 
     /// Execute the current rebalance plan
+    ///
+    /// When images of the plan were moved or deleted since it was calculated,
+    /// nothing runs; the preview asks to execute the rest or recalculate.
     pub fn execute_rebalance(&mut self) {
+        if self.read_only_blocked("move images") {
+            return;
+        }
+        if let Some(stale) = self.rebalance.plan.as_ref().map(|plan| plan.stale_count()).filter(|&n| n > 0) {
+            warn!("{} images of the rebalance plan no longer exist", stale);
+            self.rebalance.stale_actions = Some(stale);
+            return;
+        }
         if let (Some(plan), Some(dataset_path)) = 
             (&self.rebalance.plan, self.dataset.dataset_path().cloned()) 
         {
@@ -823,11 +3012,17 @@ impl DatasetCleanerApp {
             // Spawn background thread
             thread::spawn(move || {
                 info!("Background thread started for rebalance execution");
-                core::analysis::execute_rebalance_plan(
-                    &dataset_path,
-                    &plan_clone,
-                    Some(tx),
-                    Some(cancel_flag),
+                log_timed_op_with_count(
+                    "Rebalance execution",
+                    || {
+                        core::analysis::execute_rebalance_plan(
+                            &dataset_path,
+                            &plan_clone,
+                            Some(tx),
+                            Some(cancel_flag),
+                        )
+                    },
+                    |results| results.len(),
                 );
                 info!("Background thread completed rebalance execution");
             });
@@ -846,236 +3041,1747 @@ impl DatasetCleanerApp {
 
     /// Undo the last rebalance operation
     pub fn undo_rebalance(&mut self) {
+        if self.read_only_blocked("undo the rebalance") {
+            return;
+        }
         if !self.rebalance.can_undo() {
             warn!("No rebalance to undo");
             return;
         }
 
-        if let Some(results) = self.rebalance.last_results.take() {
-            info!("Undoing rebalance with {} results", results.len());
+        if let Some(results) = self.rebalance.last_results.take() {
+            info!("Undoing rebalance with {} results", results.len());
+
+            // Undo results only carry the original paths, so move bookmarks back up front
+            self.relocate_bookmarks(results.iter().rev().filter(|r| r.success).filter_map(|r| {
+                r.new_image_path
+                    .as_deref()
+                    .map(|moved| (moved, r.action.image_path.as_path()))
+            }));
+
+            self.rebalance.is_active = true;
+            self.rebalance.is_undo = true;
+            let success_count = results.iter().filter(|r| r.success).count();
+            self.rebalance.progress = Some((0, success_count));
+
+            // Create channel for progress updates
+            let (tx, rx) = channel();
+            self.rebalance.progress_receiver = Some(rx);
+
+            // Create cancellation flag
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            self.rebalance.cancel_flag = Some(cancel_flag.clone());
+
+            // Spawn background thread
+            thread::spawn(move || {
+                info!("Background thread started for rebalance undo");
+                core::analysis::undo_rebalance(&results, Some(tx), Some(cancel_flag));
+                info!("Background thread completed rebalance undo");
+            });
+        }
+    }
+
+    /// Preview moving every image in the filtered view to `target`
+    ///
+    /// Builds a plan with one `MoveAction` per filtered image and opens the
+    /// rebalance preview; executing it goes through `execute_rebalance` like any
+    /// other plan, so the move can be undone. Bulk plans have no `category`.
+    pub fn bulk_move_to_split(&mut self, target: DatasetSplit) {
+        if self.read_only_blocked("move images") {
+            return;
+        }
+        let from_split = self.dataset.current_split();
+        if !self.filter.is_active() || from_split == target || self.dataset.is_multi_root() {
+            warn!("Bulk move needs an active filter on a single-root dataset and another split");
+            return;
+        }
+
+        let categories = self.categories.categories.as_ref();
+        let image_files = self.dataset.get_image_files();
+        let known_class_ids = self.config.class_ids();
+        let actions: Vec<core::analysis::MoveAction> = self
+            .filter
+            .filtered_indices()
+            .iter()
+            .filter_map(|&idx| image_files.get(idx).map(|path| (idx, path)))
+            .map(|(idx, path)| {
+                let label_path =
+                    core::operations::get_label_path_for_image(path).filter(|p| p.exists());
+                let category = categories
+                    .and_then(|c| c.get(idx).copied())
+                    .unwrap_or_else(|| {
+                        label_path
+                            .as_deref()
+                            .map(|label_path| core::analysis::categorize_image(label_path, &known_class_ids))
+                            .unwrap_or(core::analysis::ImageCategory::Background)
+                    });
+                core::analysis::MoveAction {
+                    image_path: path.clone(),
+                    label_path,
+                    category,
+                    from_split,
+                    to_split: target,
+                }
+            })
+            .collect();
+
+        if actions.is_empty() {
+            self.rebalance.error_message = Some("No filtered images to move".to_string());
+            return;
+        }
+
+        info!(
+            "Bulk move plan: {} filtered images from {} to {}",
+            actions.len(),
+            from_split.as_str(),
+            target.as_str()
+        );
+        let mut plan = core::analysis::RebalancePlan {
+            count_to_move: actions.len(),
+            actions,
+            category: None,
+            from_split: Some(from_split),
+            to_split: Some(target),
+            ..core::analysis::RebalancePlan::new()
+        };
+        if let Some(dataset_path) = self.dataset.dataset_path() {
+            plan.flag_collisions(dataset_path);
+        }
+        self.rebalance.plan = Some(plan);
+        self.rebalance.is_global = false;
+        self.rebalance.show_preview = true;
+        self.rebalance.error_message = None;
+        self.estimate_rebalance_duration();
+    }
+
+    /// Close rebalance dialogs and reset state
+    pub fn close_rebalance(&mut self) {
+        self.rebalance.reset();
+    }
+
+    /// Measure how fast files move from the `from` split's images folder to the `to` split's, in MB/s
+    ///
+    /// Moves a temporary 1 MB file with `move_file` (a rename on the same
+    /// device, a copy across devices) and removes it again. Returns None when
+    /// the dataset is read-only or the file cannot be written or moved.
+    pub fn benchmark_move_speed(&self, from: DatasetSplit, to: DatasetSplit) -> Option<f64> {
+        if self.is_read_only() {
+            return None;
+        }
+        let root = self.dataset.dataset_path()?;
+        let src_dir = core::dataset::images_dir(root, from);
+        let dest_dir = core::dataset::images_dir(root, to);
+        if !src_dir.is_dir() || !dest_dir.is_dir() {
+            return None;
+        }
+
+        const BENCHMARK_BYTES: usize = 1024 * 1024;
+        let name = format!(".cleaner_move_benchmark_{}.tmp", std::process::id());
+        let src = src_dir.join(&name);
+        let dest = dest_dir.join(&name);
+        if let Err(e) = fs::write(&src, vec![0u8; BENCHMARK_BYTES]) {
+            warn!("Failed to write move benchmark file {:?}: {}", src, e);
+            return None;
+        }
+
+        let start = Instant::now();
+        let moved = core::operations::move_file(&src, &dest);
+        let elapsed = start.elapsed().as_secs_f64();
+        let _ = fs::remove_file(&src);
+        let _ = fs::remove_file(&dest);
+
+        match moved {
+            Ok(method) => {
+                // A rename takes microseconds; keep the rate finite
+                let speed = BENCHMARK_BYTES as f64 / (1024.0 * 1024.0) / elapsed.max(1e-6);
+                info!(
+                    "Move benchmark {} -> {}: {:.1} MB/s ({:?})",
+                    from.as_str(),
+                    to.as_str(),
+                    speed,
+                    method
+                );
+                Some(speed)
+            }
+            Err(e) => {
+                warn!("Move benchmark {} -> {} failed: {}", from.as_str(), to.as_str(), e);
+                None
+            }
+        }
+    }
+
+    /// Estimate how long the previewed rebalance plan takes to move
+    ///
+    /// Each source -> destination pair is benchmarked once per session. The
+    /// estimate is left empty if any pair could not be measured.
+    fn estimate_rebalance_duration(&mut self) {
+        self.rebalance.move_estimate = None;
+        let actions: Vec<core::analysis::MoveAction> = if self.rebalance.is_global {
+            self.rebalance
+                .global_plan
+                .as_ref()
+                .map(|plan| plan.all_actions().into_iter().cloned().collect())
+                .unwrap_or_default()
+        } else {
+            self.rebalance.plan.as_ref().map(|plan| plan.actions.clone()).unwrap_or_default()
+        };
+        let Some(root) = self.dataset.dataset_path().cloned() else {
+            return;
+        };
+
+        let mut bytes_per_pair: std::collections::HashMap<(DatasetSplit, DatasetSplit), u64> =
+            std::collections::HashMap::new();
+        for action in &actions {
+            let bytes: u64 = std::iter::once(&action.image_path)
+                .chain(action.label_path.as_ref())
+                .filter_map(|path| fs::metadata(path).ok())
+                .map(|metadata| metadata.len())
+                .sum();
+            *bytes_per_pair.entry((action.from_split, action.to_split)).or_default() += bytes;
+        }
+
+        let mut total_bytes = 0u64;
+        let mut total_seconds = 0.0;
+        for ((from, to), bytes) in bytes_per_pair {
+            let key = (
+                core::dataset::images_dir(&root, from),
+                core::dataset::images_dir(&root, to),
+            );
+            let speed = match self.rebalance.move_speeds.get(&key) {
+                Some(speed) => *speed,
+                None => {
+                    let speed = self.benchmark_move_speed(from, to);
+                    self.rebalance.move_speeds.insert(key, speed);
+                    speed
+                }
+            };
+            let Some(speed) = speed else {
+                return;
+            };
+            total_bytes += bytes;
+            total_seconds += bytes as f64 / (1024.0 * 1024.0) / speed;
+        }
+
+        if total_bytes > 0 {
+            let speed = total_bytes as f64 / (1024.0 * 1024.0) / total_seconds.max(1e-6);
+            self.rebalance.move_estimate = Some((speed, std::time::Duration::from_secs_f64(total_seconds)));
+        }
+    }
+
+    /// Split sizes for "Balance All Splits": the slider values, or the dataset's targets
+    pub fn global_split_ratios(&self) -> core::analysis::SplitRatios {
+        self.rebalance
+            .split_ratios
+            .clone()
+            .unwrap_or_else(|| self.split_ratios())
+    }
+
+    /// Calculate a global rebalance plan for all splits
+    pub fn calculate_global_rebalance(&mut self) {
+        info!("calculate_global_rebalance called!");
+        if !self.global_split_ratios().is_valid() {
+            self.rebalance.error_message = Some("⚠ Split sizes must sum to 100%".to_string());
+            return;
+        }
+        if let Some(dataset_path) = self.dataset.dataset_path() {
+            info!("Calculating global rebalance plan for all splits");
+            
+            let config = core::analysis::GlobalRebalanceConfig {
+                target_ratios: self.target_ratios(),
+                split_ratios: self.global_split_ratios(),
+                selection_strategy: self.rebalance.selection_strategy,
+                create_missing_splits: self.rebalance.create_missing_splits,
+                known_class_ids: self.config.class_ids(),
+                ..Default::default()
+            };
+            // Planning rescans every split, so it is timed like the analyses
+            let plan = log_timed_op("Global rebalance planning", || {
+                core::analysis::calculate_global_rebalance_plan(dataset_path, &config)
+            });
+
+            if plan.is_empty() {
+                info!("No moves possible - splits cannot be improved by redistribution");
+                self.rebalance.error_message = Some(
+                    "ℹ️ No redistribution possible. All splits have similar ratios - consider adding background images to reach target 10% BG.".to_string()
+                );
+            } else {
+                info!("Global plan calculated: {} total moves in {} groups", 
+                    plan.total_moves, plan.moves.len());
+                self.rebalance.global_plan = Some(plan);
+                self.rebalance.is_global = true;
+                self.rebalance.show_preview = true;
+                self.rebalance.error_message = None;
+                self.estimate_rebalance_duration();
+            }
+        } else {
+            warn!("No dataset loaded, cannot calculate global rebalance");
+        }
+    }
+
+    /// Execute the current global rebalance plan (checked for stale images like `execute_rebalance`)
+    pub fn execute_global_rebalance(&mut self) {
+        if self.read_only_blocked("move images") {
+            return;
+        }
+        if let Some(stale) = self.rebalance.global_plan.as_ref().map(|plan| plan.stale_count()).filter(|&n| n > 0) {
+            warn!("{} images of the global rebalance plan no longer exist", stale);
+            self.rebalance.stale_actions = Some(stale);
+            return;
+        }
+        if let (Some(plan), Some(dataset_path)) = 
+            (&self.rebalance.global_plan, self.dataset.dataset_path().cloned()) 
+        {
+            info!("Executing global rebalance plan with {} total moves", plan.total_moves);
+            
+            self.rebalance.is_active = true;
+            self.rebalance.show_preview = false;
+            self.rebalance.progress = Some((0, plan.total_moves));
+
+            let (tx, rx) = channel();
+            self.rebalance.progress_receiver = Some(rx);
+
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            self.rebalance.cancel_flag = Some(cancel_flag.clone());
+
+            let plan_clone = plan.clone();
+            thread::spawn(move || {
+                info!("Background thread started for global rebalance execution");
+                core::analysis::execute_global_rebalance_plan(
+                    &dataset_path,
+                    &plan_clone,
+                    Some(tx),
+                    Some(cancel_flag),
+                );
+                info!("Background thread completed global rebalance execution");
+            });
+        } else {
+            warn!("No global rebalance plan to execute");
+        }
+    }
+
+    /// Drop the stale images from the previewed plan and execute the rest
+    pub fn execute_remaining_rebalance(&mut self) {
+        if self.read_only_blocked("move images") {
+            return;
+        }
+        let dropped = if self.rebalance.is_global {
+            self.rebalance.global_plan.as_mut().map_or(0, |plan| plan.drop_stale_actions())
+        } else {
+            self.rebalance.plan.as_mut().map_or(0, |plan| plan.drop_stale_actions())
+        };
+        self.rebalance.stale_actions = None;
+        self.rebalance.stale_dropped = dropped;
+        if self.rebalance.is_global {
+            self.execute_global_rebalance();
+        } else {
+            self.execute_rebalance();
+        }
+    }
+
+    /// Replace the previewed plan with one calculated from the current files
+    pub fn recalculate_rebalance_plan(&mut self) {
+        self.rebalance.stale_actions = None;
+        if self.rebalance.is_global {
+            self.calculate_global_rebalance();
+            return;
+        }
+        let Some(plan) = &self.rebalance.plan else {
+            return;
+        };
+        // Bulk moves are planned from the filtered view, the others from their config
+        match (plan.category, plan.to_split, self.rebalance.config.clone()) {
+            (None, Some(target), _) => self.bulk_move_to_split(target),
+            (Some(_), _, Some(config)) => self.calculate_rebalance_plan(config),
+            _ => warn!("Cannot recalculate a rebalance plan without its configuration"),
+        }
+    }
+
+    /// Swap every image of splits `a` and `b` (e.g. train and val assigned the wrong way round)
+    ///
+    /// Runs in the background like a global rebalance. The moves land in
+    /// `rebalance.last_results`, so "Undo All" swaps the splits back.
+    pub fn swap_split_contents(&mut self, a: DatasetSplit, b: DatasetSplit) {
+        if self.read_only_blocked("swap splits") {
+            return;
+        }
+        if a == b || self.rebalance.is_active {
+            warn!("Split swap needs two different splits and no running rebalance");
+            return;
+        }
+        let Some(dataset_path) = self.dataset.dataset_path().cloned() else {
+            warn!("No dataset loaded, cannot swap splits");
+            return;
+        };
+        if self.dataset.is_multi_root() || !self.dataset.has_split(b) || !self.dataset.has_split(a) {
+            self.rebalance.error_message =
+                Some("Swapping splits needs a single dataset root with split folders".to_string());
+            return;
+        }
+
+        let plan = core::analysis::plan_split_swap(&dataset_path, a, b, &self.config.class_ids());
+        if plan.total_moves == 0 {
+            self.rebalance.error_message = Some(format!(
+                "Both {} and {} are empty",
+                a.as_str(),
+                b.as_str()
+            ));
+            return;
+        }
+        info!(
+            "Swapping {} ({} images) with {} ({} images)",
+            a.as_str(),
+            plan.moves[0].count,
+            b.as_str(),
+            plan.moves[1].count
+        );
+
+        self.rebalance.is_active = true;
+        self.rebalance.is_global = true;
+        self.rebalance.is_swap = true;
+        self.rebalance.show_preview = false;
+        self.rebalance.error_message = None;
+        // The first split goes through staging, so it is moved twice
+        self.rebalance.progress = Some((0, 2 * plan.moves[0].count + plan.moves[1].count));
+
+        let (tx, rx) = channel();
+        self.rebalance.progress_receiver = Some(rx);
+
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.rebalance.cancel_flag = Some(cancel_flag.clone());
+
+        thread::spawn(move || {
+            info!("Background thread started for split swap");
+            let staging_dir = dataset_path.join(core::analysis::SWAP_STAGING_DIR);
+            core::analysis::execute_split_swap(
+                &dataset_path,
+                &plan,
+                &staging_dir,
+                Some(tx),
+                Some(cancel_flag),
+            );
+            info!("Background thread completed split swap");
+        });
+    }
+
+    // =========================================================================
+    // DATA INTEGRITY METHODS
+    // =========================================================================
+
+    /// Run the integrity check, taking over from a running automatic one
+    pub fn analyze_integrity(&mut self) {
+        if self.integrity.auto_status == AutoAnalysisStatus::Running {
+            if let Some(flag) = self.integrity.cancel_flag.take() {
+                flag.store(true, Ordering::Relaxed);
+            }
+        }
+        self.integrity.auto_status = AutoAnalysisStatus::Idle;
+        self.start_integrity_analysis();
+    }
+
+    /// Start the integrity check thread (false if no dataset is loaded)
+    fn start_integrity_analysis(&mut self) -> bool {
+        if let Some(dataset_path) = self.dataset.dataset_path() {
+            let all_splits = self.integrity.all_splits;
+            let compare_hashes = self.integrity.compare_duplicate_hashes;
+            let overlap_threshold = self.integrity.overlap_threshold;
+            let image_check = match (self.integrity.check_images, self.integrity.full_decode) {
+                (false, _) => None,
+                (true, false) => Some(core::image::ImageCheck::Header),
+                (true, true) => Some(core::image::ImageCheck::FullDecode),
+            };
+            info!(
+                "Starting integrity analysis for {}",
+                if all_splits { "all splits" } else { "current split" }
+            );
+            self.integrity.analyzing = true;
+            self.integrity.results_all_splits = all_splits;
+            self.integrity.current_progress = 0;
+            self.integrity.total_files = 0;
+            self.integrity.results = None;
+            self.integrity.selected_images_without_labels.clear();
+            self.integrity.selected_labels_without_images.clear();
+            self.integrity.selected_empty_labels.clear();
+            self.integrity.selected_corrupt_images.clear();
+            self.integrity.dedupe_preview = None;
+
+            let (tx, rx) = channel();
+            self.integrity.progress_receiver = Some(rx);
+
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            self.integrity.cancel_flag = Some(cancel_flag.clone());
+
+            let dataset_path = dataset_path.clone();
+            let split = self.dataset.current_split();
+            let known_class_ids = self.config.class_ids();
+
+            thread::spawn(move || {
+                info!("Background thread started for integrity analysis");
+                log_timed_op_with_count(
+                    "Integrity analysis",
+                    || {
+                        if all_splits {
+                            core::analysis::analyze_all_splits_integrity_with_progress(
+                                &dataset_path,
+                                Some(&known_class_ids),
+                                Some(overlap_threshold),
+                                compare_hashes,
+                                image_check,
+                                Some(tx),
+                                Some(cancel_flag),
+                            )
+                        } else {
+                            core::analysis::analyze_dataset_integrity_with_progress(
+                                &dataset_path,
+                                split,
+                                Some(&known_class_ids),
+                                Some(overlap_threshold),
+                                image_check,
+                                Some(tx),
+                                Some(cancel_flag),
+                            )
+                        }
+                    },
+                    |stats| stats.files_scanned,
+                );
+                info!("Background thread completed integrity analysis");
+            });
+            true
+        } else {
+            warn!("No dataset loaded, cannot analyze integrity");
+            false
+        }
+    }
+
+    /// Cancel ongoing integrity analysis
+    pub fn cancel_integrity_analysis(&mut self) {
+        info!("User requested integrity analysis cancellation");
+        if let Some(flag) = &self.integrity.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Delete selected integrity issues (orphaned files)
+    ///
+    /// Files are moved to the deletion directory like regular deletes and
+    /// pushed as one undo entry, so Ctrl+Z restores the whole cleanup.
+    pub fn delete_selected_integrity_issues(&mut self) {
+        if self.read_only_blocked("delete files") {
+            return;
+        }
+        if let Some(ref stats) = self.integrity.results {
+            // Selected images without labels, labels without images, empty label files
+            // and corrupt images (with their labels)
+            let selected = |set: &std::collections::HashSet<usize>,
+                            issues: &[core::analysis::IntegrityIssue]| {
+                let mut indices: Vec<usize> = set.iter().copied().collect();
+                indices.sort_unstable();
+                indices
+                    .into_iter()
+                    .filter_map(|idx| issues.get(idx))
+                    .map(|issue| issue.path.clone())
+                    .collect::<Vec<_>>()
+            };
+            let paths: Vec<PathBuf> = [
+                selected(&self.integrity.selected_images_without_labels, &stats.images_without_labels),
+                selected(&self.integrity.selected_labels_without_images, &stats.labels_without_images),
+                selected(&self.integrity.selected_empty_labels, &stats.empty_label_files),
+                selected(&self.integrity.selected_corrupt_images, &stats.corrupt_images),
+                self.integrity
+                    .selected_corrupt_images
+                    .iter()
+                    .filter_map(|&idx| stats.corrupt_images.get(idx))
+                    .map(|issue| issue.expected_counterpart.clone())
+                    .collect(),
+            ]
+            .concat();
+
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis();
+            let mut removed = Vec::new();
+            let mut errors = Vec::new();
+
+            for (n, path) in paths.iter().filter(|path| path.exists()).enumerate() {
+                // Orphans of different splits can share a filename
+                match self.park_integrity_file(path, timestamp + n as u128) {
+                    Ok(temp_path) => {
+                        info!("Deleted orphaned file: {:?}", path);
+                        removed.push(CleanupFile {
+                            original_path: path.clone(),
+                            temp_path,
+                        });
+                    }
+                    Err(e) => {
+                        error!("Failed to delete {:?}: {}", path, e);
+                        errors.push(format!("{}: {}", path.display(), e));
+                    }
+                }
+            }
+
+            info!("Deleted {} orphaned files", removed.len());
+            if !removed.is_empty() {
+                self.record_operation(statistics::OP_INTEGRITY_FIX, removed.len());
+                self.undo_manager.push_integrity_cleanup(removed);
+            }
+
+            if !errors.is_empty() {
+                self.integrity.error_message = Some(format!(
+                    "Failed to delete {} files. See log for details.",
+                    errors.len()
+                ));
+            }
+
+            // Clear selections
+            self.integrity.selected_images_without_labels.clear();
+            self.integrity.selected_labels_without_images.clear();
+            self.integrity.selected_empty_labels.clear();
+            self.integrity.selected_corrupt_images.clear();
+
+            // Re-run integrity analysis to refresh the list
+            self.analyze_integrity();
+            
+            // Reload dataset in case we deleted images
+            self.reload_dataset_with_filters(false);
+        }
+    }
+
+    /// Move an orphaned file into the deletion directory, returning its parked path
+    fn park_integrity_file(
+        &self,
+        path: &Path,
+        timestamp: u128,
+    ) -> core::operations::FileOpResult<PathBuf> {
+        let temp_dir = self.deletion_dir(path);
+        fs::create_dir_all(&temp_dir)?;
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let temp_path = temp_dir.join(format!("{}_{}", timestamp, filename));
+        core::operations::move_file(path, &temp_path)?;
+        Ok(temp_path)
+    }
+
+    /// Restore the files of an integrity cleanup (undo)
+    fn undo_integrity_cleanup(&mut self, files: &[CleanupFile]) {
+        let restored = files
+            .iter()
+            .filter(|file| {
+                core::operations::restore_file(&file.temp_path, &file.original_path)
+                    .map_err(|e| error!("Error restoring {:?}: {}", file.original_path, e))
+                    .is_ok()
+            })
+            .count();
+        info!("Restored {} orphaned files", restored);
+        self.record_operation(statistics::OP_UNDO_INTEGRITY_FIX, restored);
+        self.ui.delete_message = Some(format!("Restored {} orphaned files", restored));
+        self.refresh_after_integrity_undo();
+    }
+
+    /// Remove the files of an integrity cleanup again (redo)
+    fn redo_integrity_cleanup(&mut self, files: &[CleanupFile]) {
+        let removed = files
+            .iter()
+            .filter(|file| {
+                core::operations::move_file(&file.original_path, &file.temp_path)
+                    .map_err(|e| error!("Error re-deleting {:?}: {}", file.original_path, e))
+                    .is_ok()
+            })
+            .count();
+        info!("Deleted {} orphaned files again", removed);
+        self.record_operation(statistics::OP_REDO_INTEGRITY_FIX, removed);
+        self.ui.delete_message = Some(format!("Deleted {} orphaned files again", removed));
+        self.refresh_after_integrity_undo();
+    }
+
+    /// Refresh the file list, and the integrity results if they are shown
+    fn refresh_after_integrity_undo(&mut self) {
+        if self.integrity.results.is_some() {
+            self.analyze_integrity();
+        }
+        self.reload_dataset_with_filters(false);
+    }
+
+    /// Delete one split's copy of a cross-split duplicate (image and label), keeping the others
+    pub fn delete_duplicate_copy(&mut self, issue_index: usize, split: DatasetSplit) {
+        if self.read_only_blocked("delete images") {
+            return;
+        }
+        let Some(image_path) = self
+            .integrity
+            .results
+            .as_ref()
+            .and_then(|stats| stats.duplicates_across_splits.get(issue_index))
+            .and_then(|issue| issue.copies.iter().find(|(s, _)| *s == split))
+            .map(|(_, path)| path.clone())
+        else {
+            return;
+        };
+
+        let mut deleted_count = 0;
+        let label_path = core::operations::get_label_path_for_image(&image_path);
+        for path in std::iter::once(image_path).chain(label_path) {
+            if !path.exists() {
+                continue;
+            }
+            match fs::remove_file(&path) {
+                Ok(_) => {
+                    info!("Deleted duplicate copy: {:?}", path);
+                    deleted_count += 1;
+                }
+                Err(e) => {
+                    error!("Failed to delete {:?}: {}", path, e);
+                    self.integrity.error_message =
+                        Some(format!("Failed to delete {}: {}", path.display(), e));
+                }
+            }
+        }
+
+        if deleted_count > 0 {
+            self.record_operation(statistics::OP_INTEGRITY_FIX, deleted_count);
+        }
+
+        // Re-run integrity analysis to refresh the list
+        self.analyze_integrity();
+        self.reload_dataset_with_filters(false);
+    }
+
+    /// Mark the selected empty label files as confirmed backgrounds
+    ///
+    /// Writes a `# Background` comment so the file stays a valid background
+    /// label but is no longer reported as empty.
+    pub fn confirm_selected_empty_labels_as_background(&mut self) {
+        if self.read_only_blocked("edit labels") {
+            return;
+        }
+        let Some(stats) = &self.integrity.results else {
+            return;
+        };
+        let paths: Vec<PathBuf> = self
+            .integrity
+            .selected_empty_labels
+            .iter()
+            .filter_map(|idx| stats.empty_label_files.get(*idx))
+            .map(|issue| issue.path.clone())
+            .collect();
+
+        let mut confirmed_count = 0;
+        let mut failed_count = 0;
+        for path in &paths {
+            match core::operations::write_label_contents(path, Some("# Background\n")) {
+                Ok(()) => {
+                    info!("Confirmed background label: {:?}", path);
+                    confirmed_count += 1;
+                }
+                Err(e) => {
+                    error!("Failed to write {:?}: {}", path, e);
+                    failed_count += 1;
+                }
+            }
+        }
+
+        if confirmed_count > 0 {
+            self.record_operation(statistics::OP_CONFIRM_BACKGROUND, confirmed_count);
+        }
+        if failed_count > 0 {
+            self.integrity.error_message = Some(format!(
+                "Failed to update {} files. See log for details.",
+                failed_count
+            ));
+        }
+
+        self.integrity.selected_empty_labels.clear();
+        self.analyze_integrity();
+        self.parse_label_file();
+    }
+
+    /// Remove one box of every overlapping pair in the flagged label files
+    ///
+    /// With `dry_run` nothing is written and the result is kept as a preview.
+    /// Otherwise the originals are backed up and the change is pushed as one
+    /// undo entry.
+    pub fn remove_overlapping_boxes(&mut self, dry_run: bool) {
+        if !dry_run && self.read_only_blocked("edit labels") {
+            return;
+        }
+        let Some(stats) = &self.integrity.results else {
+            return;
+        };
+        let files: Vec<(DatasetSplit, PathBuf)> = stats
+            .overlapping_boxes
+            .iter()
+            .map(|issue| (issue.split, issue.path.clone()))
+            .collect();
+        if files.is_empty() {
+            return;
+        }
+
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let backup_dir = std::env::temp_dir()
+            .join("yolo_dataset_cleaner_undo")
+            .join(format!("dedupe_boxes_{}", timestamp));
+        let summary = core::operations::remove_overlapping_boxes(
+            &files,
+            self.integrity.overlap_threshold,
+            &backup_dir,
+            dry_run,
+        );
+
+        if dry_run {
+            self.integrity.dedupe_preview = Some(summary);
+            return;
+        }
+
+        self.integrity.dedupe_preview = None;
+        if !summary.errors.is_empty() {
+            self.integrity.error_message = Some(format!(
+                "Failed to update {} label files. See log for details.",
+                summary.errors.len()
+            ));
+        }
+        if !summary.changes.is_empty() {
+            self.record_operation(statistics::OP_DEDUPE_BOXES, summary.boxes_removed);
+            self.ui.delete_message = Some(format!(
+                "Removed {} duplicate boxes from {} label files",
+                summary.boxes_removed, summary.files_modified
+            ));
+            self.undo_manager.push_box_dedupe(summary.changes);
+        }
+        self.refresh_after_box_dedupe();
+    }
+
+    /// Restore the label files of a duplicate box removal from their backups (undo)
+    fn undo_box_dedupe(&mut self, changes: &[core::operations::DedupeChange]) {
+        match core::operations::restore_deduped_labels(changes) {
+            Ok(restored) => {
+                self.record_operation(statistics::OP_UNDO_DEDUPE_BOXES, restored);
+                self.ui.delete_message = Some(format!("Restored {} label files", restored));
+            }
+            Err(e) => {
+                error!("Failed to undo duplicate box removal: {}", e);
+                self.ui.delete_message = Some(format!("Undo failed: {}", e));
+            }
+        }
+        self.refresh_after_box_dedupe();
+    }
+
+    /// Write the deduplicated label contents again (redo)
+    fn redo_box_dedupe(&mut self, changes: &[core::operations::DedupeChange]) {
+        let rewritten = changes
+            .iter()
+            .filter(|change| {
+                core::operations::write_label_contents(
+                    &change.label_path,
+                    Some(&change.deduped_contents),
+                )
+                .map_err(|e| error!("Error rewriting {:?}: {}", change.label_path, e))
+                .is_ok()
+            })
+            .count();
+        self.record_operation(statistics::OP_REDO_DEDUPE_BOXES, rewritten);
+        self.ui.delete_message = Some(format!(
+            "Removed duplicate boxes from {} label files again",
+            rewritten
+        ));
+        self.refresh_after_box_dedupe();
+    }
+
+    /// Refresh the current label, the category cache and the shown integrity results
+    fn refresh_after_box_dedupe(&mut self) {
+        if self.integrity.results.is_some() {
+            self.analyze_integrity();
+        }
+        self.parse_label_file();
+        self.refresh_category_cache();
+    }
+
+    /// Clamp the coordinates of every label file flagged as out of range, in the background
+    pub fn fix_label_coordinates(&mut self) {
+        if self.read_only_blocked("edit labels") {
+            return;
+        }
+        let label_paths: Vec<PathBuf> = match &self.integrity.results {
+            Some(stats) => stats
+                .out_of_range_boxes
+                .iter()
+                .map(|issue| issue.path.clone())
+                .collect(),
+            None => return,
+        };
+        if label_paths.is_empty() {
+            return;
+        }
+        info!("Fixing out-of-range coordinates in {} label files", label_paths.len());
+
+        self.integrity.fixing_coordinates = true;
+        self.integrity.fix_coordinates_progress = None;
+        self.integrity.fix_coordinates_message = None;
+
+        let (tx, rx) = channel();
+        self.integrity.fix_coordinates_receiver = Some(rx);
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.integrity.fix_coordinates_cancel_flag = Some(cancel_flag.clone());
+
+        thread::spawn(move || {
+            core::operations::batch_clamp_label_coordinates(&label_paths, Some(tx), Some(cancel_flag));
+        });
+    }
+
+    /// Cancel a running coordinate fix
+    pub fn cancel_fix_coordinates(&mut self) {
+        info!("User requested coordinate fix cancellation");
+        if let Some(flag) = &self.integrity.fix_coordinates_cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Apply progress and completion messages from the coordinate fix thread
+    fn poll_fix_coordinates(&mut self) {
+        let mut finished = None;
+        if let Some(receiver) = &self.integrity.fix_coordinates_receiver {
+            while let Ok(message) = receiver.try_recv() {
+                match message {
+                    core::operations::ClampProgressMessage::Progress { current, total } => {
+                        self.integrity.fix_coordinates_progress = Some((current, total));
+                    }
+                    core::operations::ClampProgressMessage::Complete(report) => {
+                        finished = Some((report, false));
+                    }
+                    core::operations::ClampProgressMessage::Cancelled(report) => {
+                        finished = Some((report, true));
+                    }
+                }
+            }
+        }
+
+        let Some((report, cancelled)) = finished else {
+            return;
+        };
+        self.integrity.fixing_coordinates = false;
+        self.integrity.fix_coordinates_receiver = None;
+        self.integrity.fix_coordinates_cancel_flag = None;
+
+        let mut message = format!(
+            "{}Clamped {} boxes in {} label files",
+            if cancelled { "Cancelled. " } else { "" },
+            report.lines_fixed,
+            report.files_fixed
+        );
+        if !report.errors.is_empty() {
+            message.push_str(&format!(", {} failed", report.errors.len()));
+        }
+        self.integrity.fix_coordinates_message = Some(message);
+
+        if report.files_fixed > 0 {
+            self.record_operation(statistics::OP_FIX_COORDINATES, report.lines_fixed);
+            self.analyze_integrity();
+            self.parse_label_file();
+        }
+    }
+
+    /// Delete every orphaned file of the current integrity tab found in one split
+    pub fn delete_integrity_issues_in_split(&mut self, split: DatasetSplit) {
+        if self.read_only_blocked("delete files") {
+            return;
+        }
+        info!("Deleting integrity issues in split {:?}", split);
+        self.integrity.selected_images_without_labels.clear();
+        self.integrity.selected_labels_without_images.clear();
+        self.integrity.selected_empty_labels.clear();
+        self.integrity.selected_corrupt_images.clear();
+        self.integrity.select_split(split);
+        self.delete_selected_integrity_issues();
+    }
+
+    /// Delete all integrity issues
+    pub fn delete_all_integrity_issues(&mut self) {
+        if self.read_only_blocked("delete files") {
+            return;
+        }
+        if let Some(ref stats) = self.integrity.results {
+            // Select all issues
+            for i in 0..stats.images_without_labels.len() {
+                self.integrity.selected_images_without_labels.insert(i);
+            }
+            for i in 0..stats.labels_without_images.len() {
+                self.integrity.selected_labels_without_images.insert(i);
+            }
+            // Then delete them
+            self.delete_selected_integrity_issues();
+        }
+    }
+
+    // =========================================================================
+    // BATCH RENAME
+    // =========================================================================
+
+    /// Rename the images (and labels) in the filtered view on a background thread
+    ///
+    /// Without an active filter every image of the current split is renamed.
+    pub fn start_batch_rename(&mut self) {
+        if self.rename.processing || self.read_only_blocked("rename images") {
+            return;
+        }
+        let images = self.filtered_image_files();
+        if images.is_empty() {
+            warn!("No images to rename");
+            return;
+        }
+
+        let scheme = self.rename.scheme();
+        info!("Batch renaming {} images with {:?}", images.len(), scheme);
+        self.rename.processing = true;
+        self.rename.progress = None;
+        self.rename.result_message = None;
+
+        let (tx, rx) = channel();
+        self.rename.progress_receiver = Some(rx);
+        thread::spawn(move || {
+            let result = core::operations::batch_rename_with_progress(&images, scheme, Some(&tx))
+                .map_err(|e| e.to_string());
+            let _ = tx.send(core::operations::RenameProgressMessage::Complete(result));
+        });
+    }
+
+    /// Apply progress and the result of the batch rename thread
+    fn poll_batch_rename(&mut self) {
+        let mut finished = None;
+        if let Some(receiver) = &self.rename.progress_receiver {
+            while let Ok(message) = receiver.try_recv() {
+                match message {
+                    core::operations::RenameProgressMessage::Progress { current, total } => {
+                        self.rename.progress = Some((current, total));
+                    }
+                    core::operations::RenameProgressMessage::Complete(result) => {
+                        finished = Some(result);
+                    }
+                }
+            }
+        }
+
+        let Some(result) = finished else {
+            return;
+        };
+        self.rename.processing = false;
+        self.rename.progress = None;
+        self.rename.progress_receiver = None;
+
+        match result {
+            Ok(results) => {
+                info!("Batch rename renamed {} images", results.len());
+                self.rename.result_message = Some(format!("Renamed {} images", results.len()));
+
+                if !results.is_empty() {
+                    self.record_operation(statistics::OP_BATCH_RENAME, results.len());
+                    self.relocate_bookmarks(
+                        results
+                            .iter()
+                            .map(|r| (r.old_path.as_path(), r.new_path.as_path())),
+                    );
+
+                    // Undo history refers to the old filenames and could
+                    // restore files over the new names
+                    self.undo_manager.clear();
+                    self.rebalance.last_results = None;
+                }
+            }
+            Err(e) => {
+                error!("Batch rename failed: {}", e);
+                self.rename.result_message = Some(format!("Rename failed: {}", e));
+            }
+        }
+
+        self.reload_dataset_with_filters(false);
+    }
+
+    // =========================================================================
+    // STRIP LABEL METADATA
+    // =========================================================================
+
+    /// Start stripping metadata lines from label files in a background thread
+    ///
+    /// With `dry_run` nothing is written; the summary lists the files that would change.
+    pub fn start_strip_metadata(&mut self, dry_run: bool) {
+        if !dry_run && self.read_only_blocked("edit labels") {
+            return;
+        }
+        let Some(dataset_path) = self.dataset.dataset_path().cloned() else {
+            warn!("No dataset loaded, cannot strip label metadata");
+            return;
+        };
+
+        let mode = if self.strip_metadata.export_copy {
+            let Some(output_dir) = self.strip_metadata.output_dir.clone() else {
+                warn!("No output folder chosen for metadata export");
+                return;
+            };
+            core::operations::StripMode::ExportCopy { output_dir }
+        } else {
+            let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+            core::operations::StripMode::InPlace {
+                backup_dir: std::env::temp_dir()
+                    .join("yolo_dataset_cleaner_undo")
+                    .join(format!("strip_metadata_{}", timestamp)),
+            }
+        };
+
+        let splits: Vec<DatasetSplit> = if self.strip_metadata.all_splits {
+            DatasetSplit::all().to_vec()
+        } else {
+            vec![self.dataset.current_split()]
+        };
+
+        info!(
+            "Starting label metadata strip (dry run: {}) for {:?}",
+            dry_run, splits
+        );
+        self.strip_metadata.processing = true;
+        self.strip_metadata.progress = None;
+        self.strip_metadata.summary = None;
+        self.strip_metadata.cancelled = false;
+        self.strip_metadata.result_message = None;
+
+        let (tx, rx) = channel();
+        self.strip_metadata.progress_receiver = Some(rx);
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.strip_metadata.cancel_flag = Some(cancel_flag.clone());
+
+        thread::spawn(move || {
+            core::operations::strip_label_metadata(
+                &dataset_path,
+                &splits,
+                &mode,
+                dry_run,
+                Some(tx),
+                Some(cancel_flag),
+            );
+        });
+    }
+
+    /// Cancel a running metadata strip
+    pub fn cancel_strip_metadata(&mut self) {
+        info!("User requested metadata strip cancellation");
+        if let Some(flag) = &self.strip_metadata.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Restore the label files rewritten by the last in-place strip
+    pub fn undo_strip_metadata(&mut self) {
+        if self.read_only_blocked("edit labels") {
+            return;
+        }
+        let Some(changes) = self.strip_metadata.last_in_place_changes.take() else {
+            return;
+        };
+
+        match core::operations::restore_stripped_labels(&changes) {
+            Ok(restored) => {
+                self.strip_metadata.result_message =
+                    Some(format!("Restored {} label files", restored));
+                self.record_operation(statistics::OP_UNDO_STRIP_METADATA, restored);
+            }
+            Err(e) => {
+                error!("Failed to undo metadata strip: {}", e);
+                self.strip_metadata.result_message = Some(format!("Undo failed: {}", e));
+                // Keep the changes so the user can retry
+                self.strip_metadata.last_in_place_changes = Some(changes);
+            }
+        }
+        self.strip_metadata.summary = None;
+        self.parse_label_file();
+    }
+
+    /// Apply progress and completion messages from the metadata strip thread
+    fn poll_strip_metadata(&mut self) {
+        let mut finished = None;
+        if let Some(receiver) = &self.strip_metadata.progress_receiver {
+            while let Ok(message) = receiver.try_recv() {
+                match message {
+                    core::operations::StripProgressMessage::Progress { current, total } => {
+                        self.strip_metadata.progress = Some((current, total));
+                    }
+                    core::operations::StripProgressMessage::Complete(summary) => {
+                        finished = Some((summary, false));
+                    }
+                    core::operations::StripProgressMessage::Cancelled(summary) => {
+                        finished = Some((summary, true));
+                    }
+                }
+            }
+        }
+
+        let Some((summary, cancelled)) = finished else {
+            return;
+        };
+        self.strip_metadata.processing = false;
+        self.strip_metadata.progress_receiver = None;
+        self.strip_metadata.cancel_flag = None;
+        self.strip_metadata.cancelled = cancelled;
+
+        if !summary.dry_run && summary.files_modified > 0 {
+            self.record_operation(statistics::OP_STRIP_METADATA, summary.files_modified);
+            if !self.strip_metadata.export_copy {
+                self.strip_metadata.last_in_place_changes = Some(summary.changes.clone());
+                // The current label may have lost its metadata
+                self.parse_label_file();
+            }
+        }
+        self.strip_metadata.summary = Some(summary);
+    }
+
+    // =========================================================================
+    // CONVERT FORMAT
+    // =========================================================================
+
+    /// Start converting the current split's images in a background thread
+    pub fn start_convert_format(&mut self) {
+        if self.read_only_blocked("convert images") {
+            return;
+        }
+        let Some(dataset_path) = self.dataset.dataset_path().cloned() else {
+            warn!("No dataset loaded, cannot convert image format");
+            return;
+        };
+
+        let split = self.dataset.current_split();
+        let dest_ext = self.convert.dest_ext();
+        let quality = self.convert.quality;
+        info!(
+            "Starting format conversion of {} to {} (quality {})",
+            split.as_str(),
+            dest_ext,
+            quality
+        );
+        self.convert.processing = true;
+        self.convert.progress = None;
+        self.convert.report = None;
+        self.convert.cancelled = false;
+
+        let (tx, rx) = channel();
+        self.convert.progress_receiver = Some(rx);
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.convert.cancel_flag = Some(cancel_flag.clone());
+
+        thread::spawn(move || {
+            core::operations::batch_convert_split(
+                &dataset_path,
+                split,
+                dest_ext,
+                quality,
+                Some(tx),
+                Some(cancel_flag),
+            );
+        });
+    }
+
+    /// Cancel a running format conversion
+    pub fn cancel_convert_format(&mut self) {
+        info!("User requested format conversion cancellation");
+        if let Some(flag) = &self.convert.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Apply progress and completion messages from the format conversion thread
+    fn poll_convert_format(&mut self) {
+        let mut finished = None;
+        if let Some(receiver) = &self.convert.progress_receiver {
+            while let Ok(message) = receiver.try_recv() {
+                match message {
+                    core::operations::ConvertProgressMessage::Progress { current, total } => {
+                        self.convert.progress = Some((current, total));
+                    }
+                    core::operations::ConvertProgressMessage::Complete(report) => {
+                        finished = Some((report, false));
+                    }
+                    core::operations::ConvertProgressMessage::Cancelled(report) => {
+                        finished = Some((report, true));
+                    }
+                }
+            }
+        }
+
+        let Some((report, cancelled)) = finished else {
+            return;
+        };
+        self.convert.processing = false;
+        self.convert.progress_receiver = None;
+        self.convert.cancel_flag = None;
+        self.convert.cancelled = cancelled;
+
+        if report.converted > 0 {
+            self.record_operation(statistics::OP_CONVERT_FORMAT, report.converted);
+
+            // Undo history, the pinned reference, and bookmarks point at the old filenames
+            self.undo_manager.clear();
+            if self
+                .image
+                .pinned
+                .as_ref()
+                .is_some_and(|pinned| !pinned.path.exists())
+            {
+                self.image.pinned = None;
+            }
+            let dest_ext = self.convert.dest_ext();
+            let converted: Vec<(PathBuf, PathBuf)> = self
+                .bookmarks
+                .iter()
+                .filter(|path| !path.exists())
+                .map(|path| (path.clone(), path.with_extension(dest_ext)))
+                .filter(|(_, converted)| converted.exists())
+                .collect();
+            self.relocate_bookmarks(
+                converted
+                    .iter()
+                    .map(|(from, to)| (from.as_path(), to.as_path())),
+            );
+            self.reload_dataset_with_filters(false);
+        }
+        self.convert.report = Some(report);
+    }
+
+    // =========================================================================
+    // FLIP AUGMENTATION
+    // =========================================================================
+
+    /// Open the flip augmentation dialog with the manifest count of this dataset
+    pub fn open_flip_augment(&mut self) {
+        self.flip_augment.show_dialog = true;
+        self.flip_augment.report = None;
+        self.flip_augment.remove_message = None;
+        self.flip_augment.confirm_remove = false;
+        self.flip_augment.preview_index = 0;
+        self.refresh_flip_manifest_count();
+    }
+
+    fn refresh_flip_manifest_count(&mut self) {
+        self.flip_augment.manifest_count = self
+            .dataset
+            .dataset_path()
+            .map_or(0, |root| core::image::load_manifest(root).len());
+    }
+
+    /// Images the flip augmentation would copy, in file list order
+    ///
+    /// `None` while the category cache needed for a category source is still
+    /// being computed.
+    pub fn flip_candidates(&self) -> Option<Vec<PathBuf>> {
+        let image_files = self.dataset.get_image_files();
+        match self.flip_augment.source {
+            FlipSource::View if self.filter.is_active() => Some(
+                self.filter
+                    .filtered_indices()
+                    .iter()
+                    .filter_map(|&idx| image_files.get(idx).cloned())
+                    .collect(),
+            ),
+            FlipSource::View => Some(image_files.clone()),
+            FlipSource::Category(category) => {
+                let categories = self.categories.get(image_files.len())?;
+                Some(
+                    image_files
+                        .iter()
+                        .zip(categories)
+                        .filter(|(_, c)| **c == category)
+                        .map(|(path, _)| path.clone())
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    /// Start writing flipped copies of the candidates in a background thread
+    pub fn start_flip_augment(&mut self) {
+        if self.read_only_blocked("augment images") {
+            return;
+        }
+        let Some(dataset_path) = self.dataset.dataset_path().cloned() else {
+            warn!("No dataset loaded, cannot flip images");
+            return;
+        };
+        let Some(candidates) = self.flip_candidates().filter(|c| !c.is_empty()) else {
+            return;
+        };
+
+        let split = self
+            .flip_augment
+            .destination
+            .unwrap_or_else(|| self.dataset.current_split());
+        let dest_images = core::dataset::images_dir(&dataset_path, split);
+        let mut jobs: Vec<core::image::FlipJob> = Vec::with_capacity(candidates.len());
+        for image_path in candidates {
+            // Copies stay next to their originals unless another split was chosen
+            let dest = match self.flip_augment.destination {
+                Some(_) => dest_images.clone(),
+                None => image_path.parent().map(Path::to_path_buf).unwrap_or_else(|| dest_images.clone()),
+            };
+            let taken: Vec<PathBuf> = jobs.iter().map(|job| job.output_path.clone()).collect();
+            jobs.push(core::image::FlipJob {
+                output_path: core::image::flipped_output_path(&image_path, &dest, &taken),
+                label_path: core::operations::get_label_path_for_image(&image_path)
+                    .filter(|path| path.exists()),
+                image_path,
+            });
+        }
+        info!("Starting flip augmentation of {} images into {}", jobs.len(), split.as_str());
+
+        self.flip_augment.processing = true;
+        self.flip_augment.progress = None;
+        self.flip_augment.report = None;
+        self.flip_augment.cancelled = false;
+        self.flip_augment.remove_message = None;
+
+        let (tx, rx) = channel();
+        self.flip_augment.progress_receiver = Some(rx);
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.flip_augment.cancel_flag = Some(cancel_flag.clone());
+
+        thread::spawn(move || {
+            core::image::batch_flip(&dataset_path, &jobs, Some(tx), Some(cancel_flag));
+        });
+    }
+
+    /// Cancel a running flip augmentation
+    pub fn cancel_flip_augment(&mut self) {
+        info!("User requested flip augmentation cancellation");
+        if let Some(flag) = &self.flip_augment.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Apply progress and completion messages from the flip augmentation thread
+    fn poll_flip_augment(&mut self) {
+        let mut finished = None;
+        if let Some(receiver) = &self.flip_augment.progress_receiver {
+            while let Ok(message) = receiver.try_recv() {
+                match message {
+                    core::image::FlipProgressMessage::Progress { current, total } => {
+                        self.flip_augment.progress = Some((current, total));
+                    }
+                    core::image::FlipProgressMessage::Complete(report) => {
+                        finished = Some((report, false));
+                    }
+                    core::image::FlipProgressMessage::Cancelled(report) => {
+                        finished = Some((report, true));
+                    }
+                }
+            }
+        }
+
+        let Some((report, cancelled)) = finished else {
+            return;
+        };
+        self.flip_augment.processing = false;
+        self.flip_augment.progress_receiver = None;
+        self.flip_augment.cancel_flag = None;
+        self.flip_augment.cancelled = cancelled;
+
+        if !report.generated.is_empty() {
+            self.record_operation(statistics::OP_FLIP_AUGMENT, report.generated.len());
+            self.reload_dataset_with_filters(false);
+        }
+        self.flip_augment.report = Some(report);
+        self.refresh_flip_manifest_count();
+    }
+
+    /// Delete every file recorded in the dataset's augmentation manifest
+    pub fn remove_generated_augmentations(&mut self) {
+        if self.read_only_blocked("delete images") {
+            return;
+        }
+        let Some(dataset_path) = self.dataset.dataset_path().cloned() else {
+            return;
+        };
+        let (removed, errors) = core::image::remove_generated(&dataset_path);
+        self.flip_augment.confirm_remove = false;
+        self.flip_augment.remove_message = Some(if errors.is_empty() {
+            format!("Removed {} generated files", removed)
+        } else {
+            format!("Removed {} generated files, {} failed", removed, errors.len())
+        });
+        if removed > 0 {
+            self.record_operation(statistics::OP_REMOVE_AUGMENTED, removed);
+            // Undo entries and the pinned reference may point at removed copies
+            self.undo_manager.clear();
+            if self
+                .image
+                .pinned
+                .as_ref()
+                .is_some_and(|pinned| !pinned.path.exists())
+            {
+                self.image.pinned = None;
+            }
+            self.reload_dataset_with_filters(false);
+        }
+        self.refresh_flip_manifest_count();
+    }
+
+    /// Class names keyed by ID, used for the crop class folders
+    fn crop_class_names(&self) -> std::collections::HashMap<u32, String> {
+        self.config
+            .classes
+            .iter()
+            .map(|class| (class.id, class.name.clone()))
+            .collect()
+    }
 
-            self.rebalance.is_active = true;
-            let success_count = results.iter().filter(|r| r.success).count();
-            self.rebalance.progress = Some((0, success_count));
+    /// Start cropping every detection of the filtered images in a background thread
+    pub fn start_crop_export(&mut self) {
+        let Some(output_dir) = self.crop_export.output_dir.clone() else {
+            warn!("No output folder chosen, cannot export crops");
+            return;
+        };
+        let images = self.filtered_image_files();
+        if images.is_empty() {
+            return;
+        }
+        info!("Starting crop export of {} images into {:?}", images.len(), output_dir);
 
-            // Create channel for progress updates
-            let (tx, rx) = channel();
-            self.rebalance.progress_receiver = Some(rx);
+        self.crop_export.processing = true;
+        self.crop_export.progress = None;
+        self.crop_export.report = None;
+        self.crop_export.cancelled = false;
 
-            // Create cancellation flag
-            let cancel_flag = Arc::new(AtomicBool::new(false));
-            self.rebalance.cancel_flag = Some(cancel_flag.clone());
+        let (tx, rx) = channel();
+        self.crop_export.progress_receiver = Some(rx);
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.crop_export.cancel_flag = Some(cancel_flag.clone());
 
-            // Spawn background thread
-            thread::spawn(move || {
-                info!("Background thread started for rebalance undo");
-                core::analysis::undo_rebalance(&results, Some(tx), Some(cancel_flag));
-                info!("Background thread completed rebalance undo");
-            });
-        }
+        let class_names = self.crop_class_names();
+        let padding = self.crop_export.padding;
+        thread::spawn(move || {
+            core::image::batch_crop(
+                &images,
+                &class_names,
+                &output_dir,
+                padding,
+                Some(tx),
+                Some(cancel_flag),
+            );
+        });
     }
 
-    /// Close rebalance dialogs and reset state
-    pub fn close_rebalance(&mut self) {
-        self.rebalance.reset();
+    /// Cancel a running crop export
+    pub fn cancel_crop_export(&mut self) {
+        info!("User requested crop export cancellation");
+        if let Some(flag) = &self.crop_export.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
     }
 
-    /// Calculate a global rebalance plan for all splits
-    pub fn calculate_global_rebalance(&mut self) {
-        info!("calculate_global_rebalance called!");
-        if let Some(dataset_path) = self.dataset.dataset_path() {
-            info!("Calculating global rebalance plan for all splits");
-            
-            let config = core::analysis::GlobalRebalanceConfig::default();
-            let plan = core::analysis::calculate_global_rebalance_plan(
-                dataset_path,
-                &config,
-            );
-
-            if plan.is_empty() {
-                info!("No moves possible - splits cannot be improved by redistribution");
-                self.rebalance.error_message = Some(
-                    "ℹ️ No redistribution possible. All splits have similar ratios - consider adding background images to reach target 10% BG.".to_string()
-                );
-            } else {
-                info!("Global plan calculated: {} total moves in {} groups", 
-                    plan.total_moves, plan.moves.len());
-                self.rebalance.global_plan = Some(plan);
-                self.rebalance.is_global = true;
-                self.rebalance.show_preview = true;
-                self.rebalance.error_message = None;
+    /// Apply progress and completion messages from the crop export thread
+    fn poll_crop_export(&mut self) {
+        let mut finished = None;
+        if let Some(receiver) = &self.crop_export.progress_receiver {
+            while let Ok(message) = receiver.try_recv() {
+                match message {
+                    core::image::CropProgressMessage::Progress { current, total } => {
+                        self.crop_export.progress = Some((current, total));
+                    }
+                    core::image::CropProgressMessage::Complete(report) => {
+                        finished = Some((report, false));
+                    }
+                    core::image::CropProgressMessage::Cancelled(report) => {
+                        finished = Some((report, true));
+                    }
+                }
             }
-        } else {
-            warn!("No dataset loaded, cannot calculate global rebalance");
         }
-    }
-
-    /// Execute the current global rebalance plan
-    pub fn execute_global_rebalance(&mut self) {
-        if let (Some(plan), Some(dataset_path)) = 
-            (&self.rebalance.global_plan, self.dataset.dataset_path().cloned()) 
-        {
-            info!("Executing global rebalance plan with {} total moves", plan.total_moves);
-            
-            self.rebalance.is_active = true;
-            self.rebalance.show_preview = false;
-            self.rebalance.progress = Some((0, plan.total_moves));
 
-            let (tx, rx) = channel();
-            self.rebalance.progress_receiver = Some(rx);
+        let Some((report, cancelled)) = finished else {
+            return;
+        };
+        self.crop_export.processing = false;
+        self.crop_export.progress_receiver = None;
+        self.crop_export.cancel_flag = None;
+        self.crop_export.cancelled = cancelled;
 
-            let cancel_flag = Arc::new(AtomicBool::new(false));
-            self.rebalance.cancel_flag = Some(cancel_flag.clone());
+        if report.crops_written() > 0 {
+            self.record_operation(statistics::OP_CROP_EXPORT, report.crops_written());
+        }
+        self.crop_export.report = Some(report);
+    }
 
-            let plan_clone = plan.clone();
-            thread::spawn(move || {
-                info!("Background thread started for global rebalance execution");
-                core::analysis::execute_global_rebalance_plan(
-                    &dataset_path,
-                    &plan_clone,
-                    Some(tx),
-                    Some(cancel_flag),
-                );
-                info!("Background thread completed global rebalance execution");
-            });
-        } else {
-            warn!("No global rebalance plan to execute");
+    /// Crop one detection of the current image into the crop output folder
+    ///
+    /// Asks for the folder first when none has been chosen yet.
+    pub fn crop_current_detection(&mut self, detection_index: usize) {
+        let Some(image_path) = self.dataset.get_image_files().get(self.current_index).cloned() else {
+            return;
+        };
+        if self.crop_export.output_dir.is_none() {
+            self.crop_export.output_dir = rfd::FileDialog::new()
+                .set_title("Crop output folder")
+                .pick_folder();
         }
+        let Some(output_dir) = self.crop_export.output_dir.clone() else {
+            return;
+        };
+
+        let mut report = core::image::CropReport::default();
+        let written = core::image::crop_image_detections(
+            &image_path,
+            &self.crop_class_names(),
+            &output_dir,
+            self.crop_export.padding,
+            Some(detection_index),
+            &mut report,
+        );
+        let message = match (written.first(), report.errors.first()) {
+            (Some(path), _) => {
+                self.record_operation(statistics::OP_CROP_EXPORT, 1);
+                format!("Saved crop to {}", path.display())
+            }
+            (None, Some(e)) => format!("⚠ {}", e),
+            (None, None) => "⚠ Box has no area inside the image".to_string(),
+        };
+        self.ui.toast_message = Some((message, Instant::now()));
     }
 
     // =========================================================================
-    // DATA INTEGRITY METHODS
+    // SESSION REPORT
     // =========================================================================
 
-    /// Start analyzing dataset integrity in background thread
-    pub fn analyze_integrity(&mut self) {
-        if let Some(dataset_path) = self.dataset.dataset_path() {
-            info!("Starting integrity analysis for current split");
-            self.integrity.analyzing = true;
-            self.integrity.current_progress = 0;
-            self.integrity.total_files = 0;
-            self.integrity.results = None;
-            self.integrity.selected_images_without_labels.clear();
-            self.integrity.selected_labels_without_images.clear();
-
-            let (tx, rx) = channel();
-            self.integrity.progress_receiver = Some(rx);
-
-            let cancel_flag = Arc::new(AtomicBool::new(false));
-            self.integrity.cancel_flag = Some(cancel_flag.clone());
-
-            let dataset_path = dataset_path.clone();
-            let split = self.dataset.current_split();
-
-            thread::spawn(move || {
-                info!("Background thread started for integrity analysis");
-                core::analysis::analyze_dataset_integrity_with_progress(
-                    &dataset_path,
-                    split,
-                    Some(tx),
-                    Some(cancel_flag),
-                );
-                info!("Background thread completed integrity analysis");
-            });
-        } else {
-            warn!("No dataset loaded, cannot analyze integrity");
-        }
+    /// Append an entry to the session operations log
+    fn record_operation(&mut self, kind: &str, affected_count: usize) {
+        info!("Operation recorded: {} ({} files)", kind, affected_count);
+        self.operations_log.push(OperationEntry::new(kind, affected_count));
+        self.session_stats.record_operation(kind, affected_count);
     }
 
-    /// Cancel ongoing integrity analysis
-    pub fn cancel_integrity_analysis(&mut self) {
-        info!("User requested integrity analysis cancellation");
-        if let Some(flag) = &self.integrity.cancel_flag {
-            flag.store(true, Ordering::Relaxed);
+    /// Analyze all splits and write an HTML session report to `path` in a background thread
+    pub fn export_report(&mut self, path: PathBuf) {
+        if self.report_export.processing {
+            return;
         }
-    }
+        let Some(dataset_path) = self.dataset.dataset_path().cloned() else {
+            warn!("No dataset loaded, cannot export report");
+            return;
+        };
+        info!("Exporting session report to {:?}", path);
 
-    /// Delete selected integrity issues (orphaned files)
-    pub fn delete_selected_integrity_issues(&mut self) {
-        if let Some(ref stats) = self.integrity.results {
-            let mut deleted_count = 0;
-            let mut errors = Vec::new();
+        self.report_export.processing = true;
+        self.report_export.progress = None;
+        self.report_export.result = None;
 
-            // Delete selected images without labels
-            let selected_images: Vec<usize> = self.integrity.selected_images_without_labels
-                .iter()
-                .copied()
-                .collect();
-            
-            for idx in selected_images.iter().rev() {
-                if let Some(issue) = stats.images_without_labels.get(*idx) {
-                    if issue.path.exists() {
-                        match fs::remove_file(&issue.path) {
-                            Ok(_) => {
-                                info!("Deleted orphaned image: {:?}", issue.path);
-                                deleted_count += 1;
-                            }
-                            Err(e) => {
-                                error!("Failed to delete {:?}: {}", issue.path, e);
-                                errors.push(format!("{}: {}", issue.path.display(), e));
-                            }
-                        }
+        let (tx, rx) = channel();
+        self.report_export.progress_receiver = Some(rx);
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.report_export.cancel_flag = Some(cancel_flag.clone());
+
+        let known_class_ids = self.config.class_ids();
+        let overlap_threshold = self.integrity.overlap_threshold;
+        let operations = self.operations_log.clone();
+        thread::spawn(move || {
+            // Category counts of every split, relayed as one step
+            let (split_tx, split_rx) = channel();
+            let stats = thread::scope(|scope| {
+                let worker = scope.spawn(|| {
+                    core::analysis::analyze_all_splits_with_progress(
+                        &dataset_path,
+                        &known_class_ids,
+                        split_tx,
+                        cancel_flag.clone(),
+                    )
+                });
+                for message in split_rx {
+                    if let core::analysis::AllSplitsProgressMessage::SplitProgress { current, total, .. } = message {
+                        let _ = tx.send(ReportProgressMessage::Progress {
+                            step: "Counting images",
+                            current,
+                            total,
+                        });
                     }
                 }
-            }
+                worker.join().ok()
+            });
+            let Some(stats) = stats.filter(|_| !cancel_flag.load(Ordering::Relaxed)) else {
+                let _ = tx.send(ReportProgressMessage::Cancelled);
+                return;
+            };
 
-            // Delete selected labels without images
-            let selected_labels: Vec<usize> = self.integrity.selected_labels_without_images
-                .iter()
-                .copied()
-                .collect();
-            
-            for idx in selected_labels.iter().rev() {
-                if let Some(issue) = stats.labels_without_images.get(*idx) {
-                    if issue.path.exists() {
-                        match fs::remove_file(&issue.path) {
-                            Ok(_) => {
-                                info!("Deleted orphaned label: {:?}", issue.path);
-                                deleted_count += 1;
-                            }
-                            Err(e) => {
-                                error!("Failed to delete {:?}: {}", issue.path, e);
-                                errors.push(format!("{}: {}", issue.path.display(), e));
-                            }
-                        }
+            // Integrity issues across every split
+            let (integrity_tx, integrity_rx) = channel();
+            let integrity = thread::scope(|scope| {
+                let worker = scope.spawn(|| {
+                    core::analysis::analyze_all_splits_integrity_with_progress(
+                        &dataset_path,
+                        Some(&known_class_ids),
+                        Some(overlap_threshold),
+                        false,
+                        None,
+                        Some(integrity_tx),
+                        Some(cancel_flag.clone()),
+                    )
+                });
+                for message in integrity_rx {
+                    if let core::analysis::IntegrityProgressMessage::Progress { current, total, .. } = message {
+                        let _ = tx.send(ReportProgressMessage::Progress {
+                            step: "Checking integrity",
+                            current,
+                            total,
+                        });
                     }
                 }
-            }
-
-            info!("Deleted {} orphaned files", deleted_count);
-
-            if !errors.is_empty() {
-                self.integrity.error_message = Some(format!(
-                    "Failed to delete {} files. See log for details.",
-                    errors.len()
-                ));
-            }
+                worker.join().ok()
+            });
+            let Some(integrity) = integrity.filter(|_| !cancel_flag.load(Ordering::Relaxed)) else {
+                let _ = tx.send(ReportProgressMessage::Cancelled);
+                return;
+            };
 
-            // Clear selections
-            self.integrity.selected_images_without_labels.clear();
-            self.integrity.selected_labels_without_images.clear();
+            let html = statistics::generate_html_report(&stats, &integrity, &operations);
+            let message = match fs::write(&path, html) {
+                Ok(()) => {
+                    info!("Report written to {:?}", path);
+                    ReportProgressMessage::Complete(path)
+                }
+                Err(e) => {
+                    error!("Failed to write report {:?}: {}", path, e);
+                    ReportProgressMessage::Failed(e.to_string())
+                }
+            };
+            let _ = tx.send(message);
+        });
+    }
 
-            // Re-run integrity analysis to refresh the list
-            self.analyze_integrity();
-            
-            // Reload dataset in case we deleted images
-            self.reload_dataset_with_filters(false);
+    /// Cancel a running report export
+    pub fn cancel_report_export(&mut self) {
+        info!("User requested report export cancellation");
+        if let Some(flag) = &self.report_export.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
         }
     }
 
-    /// Delete all integrity issues
-    pub fn delete_all_integrity_issues(&mut self) {
-        if let Some(ref stats) = self.integrity.results {
-            // Select all issues
-            for i in 0..stats.images_without_labels.len() {
-                self.integrity.selected_images_without_labels.insert(i);
-            }
-            for i in 0..stats.labels_without_images.len() {
-                self.integrity.selected_labels_without_images.insert(i);
+    /// Apply progress and completion messages from the report export thread
+    fn poll_report_export(&mut self) {
+        let mut finished = None;
+        if let Some(receiver) = &self.report_export.progress_receiver {
+            while let Ok(message) = receiver.try_recv() {
+                match message {
+                    ReportProgressMessage::Progress { step, current, total } => {
+                        self.report_export.progress = Some((step, current, total));
+                    }
+                    ReportProgressMessage::Complete(path) => finished = Some(Some(Ok(path))),
+                    ReportProgressMessage::Failed(e) => finished = Some(Some(Err(e))),
+                    ReportProgressMessage::Cancelled => finished = Some(None),
+                }
             }
-            // Then delete them
-            self.delete_selected_integrity_issues();
         }
+
+        let Some(result) = finished else {
+            return;
+        };
+        if result.is_none() {
+            info!("Report export cancelled");
+        }
+        self.report_export.processing = false;
+        self.report_export.progress = None;
+        self.report_export.progress_receiver = None;
+        self.report_export.cancel_flag = None;
+        self.report_export.result = result;
     }
 }
 
@@ -1087,6 +4793,7 @@ impl eframe::App for DatasetCleanerApp {
             while let Ok(message) = receiver.try_recv() {
                 match message {
                     BatchProgressMessage::Progress(stats) => {
+                        self.batch.rate.record(stats.current_progress);
                         self.batch.stats = Some(stats);
                     }
                     BatchProgressMessage::Complete(stats)
@@ -1099,15 +4806,53 @@ impl eframe::App for DatasetCleanerApp {
 
         // Handle completion or cancellation outside of the borrow
         if let Some(stats) = complete_stats {
-            self.batch.stats = Some(stats);
             self.batch.processing = false;
             self.batch.progress_receiver = None;
             self.batch.cancel_flag = None;
+            if let Some(rate) = self.batch.rate.overall_per_second() {
+                info!("Batch scan averaged {:.1} images/s", rate);
+            }
+            self.batch.rate.clear();
+            if stats.total_deleted > 0 {
+                let kind = match self.batch.mode {
+                    BatchRemovalMode::Black => statistics::OP_REMOVE_BLACK_IMAGES,
+                    BatchRemovalMode::White => statistics::OP_REMOVE_WHITE_IMAGES,
+                    BatchRemovalMode::Extreme => statistics::OP_REMOVE_EXTREME_IMAGES,
+                };
+                self.record_operation(kind, stats.total_deleted);
+            }
+            self.batch.stats = Some(stats);
 
             // Reload dataset and refresh state (same for both cancelled and completed)
             self.reload_dataset_with_filters(false);
         }
 
+        // Poll for subset export updates
+        let mut export_finished = None;
+        if let Some(receiver) = &self.export_subset.progress_receiver {
+            while let Ok(message) = receiver.try_recv() {
+                match message {
+                    ExportProgressMessage::Progress(stats) => {
+                        self.export_subset.stats = Some(stats);
+                    }
+                    ExportProgressMessage::Complete(stats)
+                    | ExportProgressMessage::Cancelled(stats) => {
+                        export_finished = Some(stats);
+                    }
+                }
+            }
+        }
+
+        if let Some(stats) = export_finished {
+            self.export_subset.processing = false;
+            self.export_subset.progress_receiver = None;
+            self.export_subset.cancel_flag = None;
+            if stats.images_copied > 0 {
+                self.record_operation(statistics::OP_EXPORT_SUBSET, stats.images_copied);
+            }
+            self.export_subset.stats = Some(stats);
+        }
+
         // Poll for balance analysis updates
         let mut balance_messages = Vec::new();
         if let Some(receiver) = &self.balance.progress_receiver {
@@ -1126,46 +4871,51 @@ impl eframe::App for DatasetCleanerApp {
                 } => {
                     self.balance.current_progress = current;
                     self.balance.total_images = total;
+                    self.balance.rate.record(current);
                     self.balance.results = Some(stats);
                 }
                 core::analysis::BalanceProgressMessage::Complete(stats) => {
+                    if let Some(rate) = self.balance.rate.overall_per_second() {
+                        info!("Balance analysis averaged {:.1} images/s", rate);
+                    }
+                    self.balance.rate.clear();
                     self.balance.results = Some(stats.clone());
+                    self.balance.analyzed_at = Some(chrono::Local::now());
                     self.balance.analyzing = false;
                     self.balance.progress_receiver = None;
                     self.balance.cancel_flag = None;
-                    
-                    // Cache best destinations for rebalance buttons
-                    if let Some(dataset_path) = self.dataset.dataset_path() {
-                        let current_split = self.dataset.current_split();
-                        let target_ratios = core::analysis::TargetRatios {
-                            player_ratio: self.config.target_player_ratio,
-                            background_ratio: self.config.target_background_ratio,
-                            hardcase_ratio: self.config.target_hardcase_ratio,
-                        };
-                        
-                        self.balance.cached_best_bg_dest = core::analysis::find_best_destination_split(
-                            dataset_path,
-                            current_split,
-                            core::analysis::ImageCategory::Background,
-                            &target_ratios,
-                        );
-                        self.balance.cached_best_player_dest = core::analysis::find_best_destination_split(
-                            dataset_path,
-                            current_split,
-                            core::analysis::ImageCategory::CTOnly,
-                            &target_ratios,
-                        );
+                    if self.balance.auto_status == AutoAnalysisStatus::Running {
+                        self.balance.auto_status = AutoAnalysisStatus::Done;
                     }
                 }
                 core::analysis::BalanceProgressMessage::Cancelled(stats) => {
+                    self.balance.auto_status = AutoAnalysisStatus::Idle;
+                    self.balance.rate.clear();
                     self.balance.results = Some(stats);
                     self.balance.analyzing = false;
                     self.balance.progress_receiver = None;
                     self.balance.cancel_flag = None;
+                    self.balance.global_stats_receiver = None;
                 }
             }
         }
 
+        // Cache best destinations for rebalance buttons once every split is known
+        let global_stats = self
+            .balance
+            .global_stats_receiver
+            .as_ref()
+            .and_then(|receiver| receiver.try_recv().ok());
+        if let Some(global_stats) = global_stats {
+            self.balance.global_stats_receiver = None;
+            self.cache_best_destinations(&global_stats);
+            self.balance.split_results = DatasetSplit::all()
+                .into_iter()
+                .filter(|&split| global_stats.is_present(split))
+                .map(|split| (split, global_stats.get(split).clone()))
+                .collect();
+        }
+
         // Poll for integrity analysis updates
         let mut integrity_messages = Vec::new();
         if let Some(receiver) = &self.integrity.progress_receiver {
@@ -1184,15 +4934,25 @@ impl eframe::App for DatasetCleanerApp {
                 } => {
                     self.integrity.current_progress = current;
                     self.integrity.total_files = total;
+                    self.integrity.rate.record(current);
                     self.integrity.results = Some(stats);
                 }
                 core::analysis::IntegrityProgressMessage::Complete(stats) => {
+                    if let Some(rate) = self.integrity.rate.overall_per_second() {
+                        info!("Integrity check averaged {:.1} files/s", rate);
+                    }
+                    self.integrity.rate.clear();
                     self.integrity.results = Some(stats);
                     self.integrity.analyzing = false;
                     self.integrity.progress_receiver = None;
                     self.integrity.cancel_flag = None;
+                    if self.integrity.auto_status == AutoAnalysisStatus::Running {
+                        self.integrity.auto_status = AutoAnalysisStatus::Done;
+                    }
                 }
                 core::analysis::IntegrityProgressMessage::Cancelled(stats) => {
+                    self.integrity.auto_status = AutoAnalysisStatus::Idle;
+                    self.integrity.rate.clear();
                     self.integrity.results = Some(stats);
                     self.integrity.analyzing = false;
                     self.integrity.progress_receiver = None;
@@ -1209,6 +4969,7 @@ impl eframe::App for DatasetCleanerApp {
                 match message {
                     core::analysis::RebalanceProgressMessage::Progress { current, total, last_moved } => {
                         self.rebalance.progress = Some((current, total));
+                        self.rebalance.rate.record(current);
                         self.rebalance.last_moved = Some(last_moved);
                     }
                     core::analysis::RebalanceProgressMessage::Complete { success_count, failed_count, results } => {
@@ -1227,17 +4988,59 @@ impl eframe::App for DatasetCleanerApp {
         // Handle error outside of borrow
         if let Some(msg) = rebalance_error {
             self.rebalance.error_message = Some(msg);
+            self.rebalance.rate.clear();
             self.rebalance.is_active = false;
+            self.rebalance.is_undo = false;
+            self.rebalance.is_swap = false;
             self.rebalance.progress_receiver = None;
             self.rebalance.cancel_flag = None;
         }
 
         // Handle rebalance completion outside of borrow
         if let Some((_completed, success_count, _failed_count, results)) = rebalance_complete {
+            if let Some(rate) = self.rebalance.rate.overall_per_second() {
+                info!("Rebalance moved {} images at {:.1} images/s", success_count, rate);
+            }
+            let copied = results.iter().filter(|r| r.copied).count();
+            if copied > 0 {
+                info!("{} images were copied across devices instead of renamed", copied);
+            }
+            self.rebalance.rate.clear();
             self.rebalance.is_active = false;
             self.rebalance.progress_receiver = None;
             self.rebalance.cancel_flag = None;
             self.rebalance.show_result = true;
+
+            let kind = if self.rebalance.is_undo {
+                statistics::OP_UNDO_REBALANCE
+            } else if self.rebalance.is_swap {
+                statistics::OP_SWAP_SPLITS
+            } else if self.rebalance.is_global {
+                statistics::OP_GLOBAL_REBALANCE
+            } else if self.rebalance.plan.as_ref().is_some_and(|p| p.category.is_none()) {
+                statistics::OP_BULK_MOVE
+            } else {
+                statistics::OP_REBALANCE
+            };
+            if !self.rebalance.is_undo {
+                self.relocate_bookmarks(results.iter().filter(|r| r.success).filter_map(|r| {
+                    r.new_image_path
+                        .as_deref()
+                        .map(|new| (r.action.image_path.as_path(), new))
+                }));
+            }
+            let update_yaml = self.rebalance.is_global
+                && !self.rebalance.is_undo
+                && success_count > 0
+                && self.settings.auto_update_data_yaml;
+            self.rebalance.is_undo = false;
+            self.rebalance.is_swap = false;
+            if success_count > 0 {
+                self.record_operation(kind, success_count);
+            }
+            if update_yaml {
+                self.write_data_yaml();
+            }
             
             // Store results for potential undo
             if success_count > 0 {
@@ -1249,21 +5052,97 @@ impl eframe::App for DatasetCleanerApp {
             self.reload_and_refresh(false);
         }
 
+        self.poll_strip_metadata();
+        self.poll_batch_rename();
+        self.poll_error_scan();
+        if self.image.is_scanning_for_errors() {
+            // Keep polling while no spinner is animating
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        self.poll_convert_format();
+        self.poll_flip_augment();
+        self.poll_crop_export();
+        self.poll_report_export();
+        self.poll_prefetch();
+        self.poll_fix_coordinates();
+        self.poll_filter_job();
+        self.poll_all_splits_balance();
+        self.discard_evicted_undo();
+        self.poll_auto_advance(ctx);
+
+        // Poll for the dataset summary
+        if let Some(receiver) = &self.summary.receiver {
+            if let Ok(summary) = receiver.try_recv() {
+                self.summary.summary = Some(summary);
+                self.summary.receiver = None;
+            }
+        }
+
+        // Poll for category cache results
+        if let Some(receiver) = &self.categories.receiver {
+            if let Ok(entries) = receiver.try_recv() {
+                debug!("Category cache ready: {} images", entries.len());
+                let (categories, detection_counts) = entries.into_iter().unzip();
+                self.categories.categories = Some(categories);
+                self.categories.detection_counts = Some(detection_counts);
+                self.categories.receiver = None;
+                self.categories.cancel_flag = None;
+            }
+        }
+
         ui::render_top_panel(self, ctx);
+        // Added first so it sits below the bottom panel
+        ui::render_status_bar(self, ctx);
         ui::render_bottom_panel(self, ctx);
+        ui::render_minimap(self, ctx);
 
         if !self.dataset.get_image_files().is_empty() {
             ui::render_label_panel(self, ctx);
         }
+        ui::render_log_panel(self, ctx);
 
         ui::render_central_panel(self, ctx);
         ui::render_batch_delete_confirmation(self, ctx);
         ui::render_batch_progress(self, ctx);
+        ui::render_export_subset_confirmation(self, ctx);
+        ui::render_export_subset_progress(self, ctx);
+        ui::render_yaml_overwrite_confirmation(self, ctx);
         ui::render_toast_notification(self, ctx);
+        ui::render_message_toast(self, ctx);
         ui::render_filter_dialog(self, ctx);
         ui::render_balance_dialog(self, ctx);
         ui::render_rebalance_dialog(self, ctx);
+        ui::render_rename_dialog(self, ctx);
+        ui::render_strip_metadata_dialog(self, ctx);
+        ui::render_convert_dialog(self, ctx);
+        ui::render_flip_augment_dialog(self, ctx);
+        ui::render_crop_export_dialog(self, ctx);
+        ui::render_bookmarks_dialog(self, ctx);
+        ui::render_settings_dialog(self, ctx);
+        ui::render_shortcuts_dialog(self, ctx);
+        ui::render_session_stats_dialog(self, ctx);
+        ui::render_history_dialog(self, ctx);
+        ui::render_dataset_summary_dialog(self, ctx);
+        ui::render_empty_trash_dialog(self, ctx);
+        ui::render_lock_dialog(self, ctx);
 
         ui::handle_keyboard_shortcuts(self, ctx);
     }
 }
+
+/// Add a deletion to the manifest if its files were moved to a cleaner trash folder
+fn record_in_trash_manifest(state: &UndoState) {
+    if !core::operations::is_in_dataset_trash(&state.temp_image_path) {
+        return;
+    }
+    let entry = TrashManifestEntry {
+        deleted_at: chrono::Local::now().to_rfc3339(),
+        original_image: state.image_path.clone(),
+        trashed_image: state.temp_image_path.clone(),
+        original_label: state.label_path.clone(),
+        trashed_label: state.temp_label_path.clone(),
+    };
+    if let Err(e) = core::operations::add_manifest_entry(&entry) {
+        warn!("Failed to update trash manifest: {}", e);
+    }
+}
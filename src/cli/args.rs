@@ -0,0 +1,303 @@
+use std::path::PathBuf;
+
+use crate::core::dataset::DatasetSplit;
+
+/// Environment variable that turns on `--cli` mode when set to `1`
+pub const CLI_ENV_VAR: &str = "YOLO_CLEANER_CLI";
+
+/// GUI option that opens datasets read-only for the session
+pub const READ_ONLY_FLAG: &str = "--read-only";
+
+/// Output format for headless commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// A headless subcommand parsed from the command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Print balance statistics for one or more splits
+    Analyze {
+        dataset: PathBuf,
+        splits: Vec<DatasetSplit>,
+        format: OutputFormat,
+    },
+    /// Check for images without labels and labels without images
+    Integrity {
+        dataset: PathBuf,
+        splits: Vec<DatasetSplit>,
+        format: OutputFormat,
+        fail_on_issues: bool,
+    },
+    /// Print balance statistics and plan (or run) a global rebalance
+    Balance {
+        dataset: PathBuf,
+        splits: Vec<DatasetSplit>,
+        format: OutputFormat,
+        /// Plan a global rebalance across all splits
+        rebalance: bool,
+        /// Only print the plan; do not move any files
+        dry_run: bool,
+        /// Write the planned moves (or the category counts) as CSV
+        export_csv: Option<PathBuf>,
+    },
+    /// Print usage information
+    Help,
+}
+
+pub const USAGE: &str = "\
+YOLO Dataset Cleaner
+
+Usage:
+  clean-cs2vision-dataset                      Launch the GUI
+  clean-cs2vision-dataset --read-only          Launch the GUI without deleting,
+                                               moving or editing anything
+  clean-cs2vision-dataset analyze   [OPTIONS]  Print balance statistics
+  clean-cs2vision-dataset integrity [OPTIONS]  Check image/label pairing
+  clean-cs2vision-dataset rebalance [OPTIONS]  Plan and run a global rebalance
+  clean-cs2vision-dataset --cli     [OPTIONS]  Balance statistics as JSON (same as
+                                               YOLO_CLEANER_CLI=1); add --rebalance
+                                               to plan and run a global rebalance
+  clean-cs2vision-dataset help                 Show this message
+
+Options:
+  --dataset PATH       Dataset root containing train/val/test folders (required)
+  --split NAME         Split to process: train, val or test (repeatable, default: train)
+  --all-splits         Process train, val and test
+  --format FORMAT      Output format: text or json (default: text)
+  --fail-on-issues     integrity only: exit with code 2 if any issue is found
+  --rebalance          --cli only: plan and run a global rebalance
+  --dry-run            rebalance / --cli: print the plan without moving files
+  --export-csv OUT     rebalance / --cli: write the planned moves (or, without
+                       --rebalance, the category counts) to OUT as CSV
+";
+
+/// Remove a leading `--read-only` (a GUI option) and report whether it was given
+///
+/// Subcommands do not accept it, so `analyze --read-only` is still an error.
+pub fn take_read_only_flag(args: &mut Vec<String>) -> bool {
+    if args.first().is_some_and(|arg| arg == READ_ONLY_FLAG) {
+        args.remove(0);
+        true
+    } else {
+        false
+    }
+}
+
+/// Parse command-line arguments (excluding the program name).
+///
+/// Returns `Ok(None)` when the first argument is not a subcommand or `--cli`,
+/// meaning the GUI should start. Options after a subcommand are strict.
+pub fn parse_args<I, S>(args: I) -> Result<Option<Command>, String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let mut args = args.into_iter().map(Into::into);
+
+    let subcommand = match args.next() {
+        Some(sub) => sub,
+        None => return Ok(None),
+    };
+
+    let (is_integrity, is_balance, is_cli) = match subcommand.as_str() {
+        "analyze" => (false, false, false),
+        "integrity" => (true, false, false),
+        "rebalance" => (false, true, false),
+        "--cli" => (false, true, true),
+        "help" | "--help" | "-h" => return Ok(Some(Command::Help)),
+        // Anything else is not meant for the CLI, e.g. the `-psn_*` process
+        // serial number macOS passes to apps opened from Finder
+        _ => return Ok(None),
+    };
+
+    let mut dataset: Option<PathBuf> = None;
+    let mut splits: Vec<DatasetSplit> = Vec::new();
+    let mut all_splits = false;
+    // `--cli` is meant for pipelines, so it reports JSON unless told otherwise
+    let mut format = if is_cli { OutputFormat::Json } else { OutputFormat::Text };
+    let mut fail_on_issues = false;
+    let mut rebalance = !is_cli;
+    let mut dry_run = false;
+    let mut export_csv: Option<PathBuf> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dataset" => {
+                let value = args.next().ok_or("--dataset requires a path")?;
+                dataset = Some(PathBuf::from(value));
+            }
+            "--split" => {
+                let value = args.next().ok_or("--split requires a value")?;
+                let split = DatasetSplit::parse(&value)
+                    .ok_or_else(|| format!("Invalid split '{}' (expected train, val or test)", value))?;
+                if !splits.contains(&split) {
+                    splits.push(split);
+                }
+            }
+            "--all-splits" => all_splits = true,
+            "--format" => {
+                let value = args.next().ok_or("--format requires a value")?;
+                format = match value.to_ascii_lowercase().as_str() {
+                    "text" => OutputFormat::Text,
+                    "json" => OutputFormat::Json,
+                    _ => return Err(format!("Invalid format '{}' (expected text or json)", value)),
+                };
+            }
+            "--fail-on-issues" if is_integrity => fail_on_issues = true,
+            "--rebalance" if is_cli => rebalance = true,
+            "--dry-run" if is_balance => dry_run = true,
+            "--export-csv" if is_balance => {
+                let value = args.next().ok_or("--export-csv requires a path")?;
+                export_csv = Some(PathBuf::from(value));
+            }
+            "--help" | "-h" => return Ok(Some(Command::Help)),
+            other => return Err(format!("Unknown option '{}' for {}", other, subcommand)),
+        }
+    }
+
+    let dataset = dataset.ok_or("--dataset is required")?;
+
+    // A global rebalance always plans across train, val and test
+    if is_balance && rebalance {
+        if !splits.is_empty() {
+            return Err("--split cannot be used with a global rebalance, which covers every split".to_string());
+        }
+        all_splits = true;
+    }
+
+    if all_splits {
+        splits = DatasetSplit::all().to_vec();
+    } else if splits.is_empty() {
+        splits.push(DatasetSplit::Train);
+    }
+
+    Ok(Some(if is_balance {
+        Command::Balance {
+            dataset,
+            splits,
+            format,
+            rebalance,
+            dry_run,
+            export_csv,
+        }
+    } else if is_integrity {
+        Command::Integrity {
+            dataset,
+            splits,
+            format,
+            fail_on_issues,
+        }
+    } else {
+        Command::Analyze {
+            dataset,
+            splits,
+            format,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_args_launches_gui() {
+        let args: Vec<String> = Vec::new();
+        assert_eq!(parse_args(args), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_analyze() {
+        let cmd = parse_args(["analyze", "--dataset", "data", "--split", "val", "--format", "json"]);
+        assert_eq!(
+            cmd,
+            Ok(Some(Command::Analyze {
+                dataset: PathBuf::from("data"),
+                splits: vec![DatasetSplit::Val],
+                format: OutputFormat::Json,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_integrity_all_splits() {
+        let cmd = parse_args(["integrity", "--dataset", "data", "--all-splits", "--fail-on-issues"]);
+        assert_eq!(
+            cmd,
+            Ok(Some(Command::Integrity {
+                dataset: PathBuf::from("data"),
+                splits: DatasetSplit::all().to_vec(),
+                format: OutputFormat::Text,
+                fail_on_issues: true,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_cli_flag_and_rebalance() {
+        let cmd = parse_args([
+            "--cli", "--dataset", "data", "--rebalance", "--dry-run", "--export-csv", "moves.csv",
+        ]);
+        assert_eq!(
+            cmd,
+            Ok(Some(Command::Balance {
+                dataset: PathBuf::from("data"),
+                splits: DatasetSplit::all().to_vec(),
+                format: OutputFormat::Json,
+                rebalance: true,
+                dry_run: true,
+                export_csv: Some(PathBuf::from("moves.csv")),
+            }))
+        );
+
+        // The rebalance subcommand always rebalances and defaults to text
+        let cmd = parse_args(["rebalance", "--dataset", "data"]);
+        assert_eq!(
+            cmd,
+            Ok(Some(Command::Balance {
+                dataset: PathBuf::from("data"),
+                splits: DatasetSplit::all().to_vec(),
+                format: OutputFormat::Text,
+                rebalance: true,
+                dry_run: false,
+                export_csv: None,
+            }))
+        );
+
+        assert!(parse_args(["rebalance", "--dataset", "d", "--rebalance"]).is_err());
+        // A global rebalance covers every split, so picking one is rejected
+        assert!(parse_args(["rebalance", "--dataset", "d", "--split", "val"]).is_err());
+        assert!(parse_args(["--cli", "--dataset", "d", "--split", "val", "--rebalance"]).is_err());
+        assert!(parse_args(["--cli", "--dataset", "d", "--split", "val"]).is_ok());
+        assert!(parse_args(["analyze", "--dataset", "d", "--dry-run"]).is_err());
+        assert!(parse_args(["--cli", "--dataset", "d", "--export-csv"]).is_err());
+    }
+
+    #[test]
+    fn test_read_only_flag_launches_gui() {
+        let mut args = vec!["--read-only".to_string()];
+        assert!(take_read_only_flag(&mut args));
+        assert_eq!(parse_args(args), Ok(None));
+
+        let mut args = vec!["analyze".to_string(), "--read-only".to_string()];
+        assert!(!take_read_only_flag(&mut args));
+        assert!(parse_args(args).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(["analyze"]).is_err());
+        assert!(parse_args(["analyze", "--dataset"]).is_err());
+        assert!(parse_args(["analyze", "--dataset", "d", "--split", "dev"]).is_err());
+        assert!(parse_args(["analyze", "--dataset", "d", "--fail-on-issues"]).is_err());
+    }
+
+    #[test]
+    fn test_unknown_first_argument_launches_gui() {
+        assert_eq!(parse_args(["-psn_0_123456"]), Ok(None));
+        assert_eq!(parse_args(["frobnicate", "--dataset", "d"]), Ok(None));
+    }
+}
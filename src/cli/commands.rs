@@ -0,0 +1,571 @@
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::mpsc::channel;
+use std::thread;
+
+use serde_json::{json, Value};
+
+use super::args::{Command, OutputFormat, USAGE};
+use crate::core::analysis::{
+    analyze_dataset, analyze_dataset_integrity, calculate_global_rebalance_plan,
+    execute_global_rebalance_plan, BalanceStats, GlobalRebalanceConfig, GlobalRebalancePlan,
+    ImageCategory, IntegrityIssue, IntegrityStats, MoveResult, RebalanceProgressMessage, SplitRatios,
+    TargetRatios, DEFAULT_OVERLAP_IOU_THRESHOLD,
+};
+use crate::core::dataset::{DatasetLock, DatasetSplit, DatasetTargets, LayoutKind, LockOutcome};
+use crate::core::operations::csv_field;
+use crate::state::Settings;
+
+/// Exit code when `--fail-on-issues` finds problems
+const EXIT_ISSUES_FOUND: u8 = 2;
+/// Exit code for any error: usage, missing dataset, failed rebalance or export
+const EXIT_FAILED: u8 = 1;
+
+const CATEGORIES: [ImageCategory; 6] = [
+    ImageCategory::CTOnly,
+    ImageCategory::TOnly,
+    ImageCategory::MultiplePlayer,
+    ImageCategory::Background,
+    ImageCategory::HardCase,
+    ImageCategory::Unknown,
+];
+
+/// Run a parsed headless command and return the process exit code
+pub fn run(command: Command) -> ExitCode {
+    match command {
+        Command::Help => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Command::Analyze {
+            dataset,
+            splits,
+            format,
+        } => {
+            if let Err(code) = check_dataset(&dataset) {
+                return code;
+            }
+            run_analyze(&dataset, &splits, format);
+            ExitCode::SUCCESS
+        }
+        Command::Integrity {
+            dataset,
+            splits,
+            format,
+            fail_on_issues,
+        } => {
+            if let Err(code) = check_dataset(&dataset) {
+                return code;
+            }
+            let total_issues = run_integrity(&dataset, &splits, format);
+            if fail_on_issues && total_issues > 0 {
+                ExitCode::from(EXIT_ISSUES_FOUND)
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Balance {
+            dataset,
+            splits,
+            format,
+            rebalance,
+            dry_run,
+            export_csv,
+        } => {
+            if let Err(code) = check_dataset(&dataset) {
+                return code;
+            }
+            match run_balance(&dataset, &splits, format, rebalance, dry_run, export_csv.as_deref()) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(message) => {
+                    eprintln!("Error: {}", message);
+                    ExitCode::from(EXIT_FAILED)
+                }
+            }
+        }
+    }
+}
+
+/// Report a usage error on stderr and return the matching exit code
+pub fn usage_error(message: &str) -> ExitCode {
+    eprintln!("Error: {}\n", message);
+    eprint!("{}", USAGE);
+    ExitCode::from(EXIT_FAILED)
+}
+
+fn check_dataset(dataset: &Path) -> Result<(), ExitCode> {
+    if dataset.is_dir() {
+        Ok(())
+    } else {
+        eprintln!("Error: dataset path {:?} is not a directory", dataset);
+        Err(ExitCode::from(EXIT_FAILED))
+    }
+}
+
+fn run_analyze(dataset: &Path, splits: &[DatasetSplit], format: OutputFormat) {
+    let known_class_ids = known_class_ids();
+    let results: Vec<(DatasetSplit, BalanceStats)> = splits
+        .iter()
+        .map(|&split| (split, analyze_dataset(dataset, split, &known_class_ids)))
+        .collect();
+
+    match format {
+        OutputFormat::Json => {
+            let splits_json: Vec<Value> = results
+                .iter()
+                .map(|(split, stats)| balance_to_json(*split, stats))
+                .collect();
+            println!(
+                "{}",
+                json!({ "dataset": dataset, "splits": splits_json })
+            );
+        }
+        OutputFormat::Text => print_balance_text(&results),
+    }
+}
+
+fn print_balance_text(results: &[(DatasetSplit, BalanceStats)]) {
+    for (split, stats) in results {
+        println!("[{}] {} images", split.as_str(), stats.total_images);
+        for category in CATEGORIES {
+            println!(
+                "  {:<16} {:>6}  ({:.1}%)",
+                category.as_str(),
+                stats.get_count(category),
+                stats.get_percentage(category)
+            );
+        }
+    }
+}
+
+/// Analyze `splits`, then optionally plan and run a global rebalance
+///
+/// Progress goes to stderr and the result to stdout. Errors (a flat dataset,
+/// a failed export, failed moves) are returned after the report is printed.
+/// A rebalance that moves files refuses to start while another instance has
+/// the dataset open, and holds the dataset lock until it is done.
+fn run_balance(
+    dataset: &Path,
+    splits: &[DatasetSplit],
+    format: OutputFormat,
+    rebalance: bool,
+    dry_run: bool,
+    export_csv: Option<&Path>,
+) -> Result<(), String> {
+    let _lock = if rebalance && !dry_run {
+        Some(lock_dataset(dataset)?)
+    } else {
+        None
+    };
+
+    eprintln!("Analyzing {} split(s) of {}", splits.len(), dataset.display());
+    let known_class_ids = known_class_ids();
+    let results: Vec<(DatasetSplit, BalanceStats)> = splits
+        .iter()
+        .map(|&split| (split, analyze_dataset(dataset, split, &known_class_ids)))
+        .collect();
+
+    let plan = if rebalance {
+        if LayoutKind::detect(dataset) == LayoutKind::Flat {
+            return Err(format!(
+                "{} has no train/val/test folders to rebalance between",
+                dataset.display()
+            ));
+        }
+        eprintln!("Planning global rebalance");
+        // The targets edited in the GUI's balance dialog, saved with the dataset
+        let targets = DatasetTargets::load(dataset);
+        let config = GlobalRebalanceConfig {
+            target_ratios: TargetRatios {
+                player_ratio: targets.player_ratio,
+                background_ratio: targets.background_ratio,
+                hardcase_ratio: targets.hardcase_ratio,
+            },
+            split_ratios: SplitRatios {
+                train: targets.train_ratio,
+                val: targets.val_ratio,
+                test: targets.test_ratio,
+            },
+            known_class_ids,
+            ..Default::default()
+        };
+        Some(calculate_global_rebalance_plan(dataset, &config))
+    } else {
+        None
+    };
+
+    if let Some(path) = export_csv {
+        let csv = match &plan {
+            Some(plan) => moves_csv(plan),
+            None => categories_csv(&results),
+        };
+        fs::write(path, csv).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        eprintln!("Wrote {}", path.display());
+    }
+
+    let moved = plan
+        .as_ref()
+        .filter(|plan| !dry_run && !plan.is_empty())
+        .map(|plan| execute_with_progress(dataset, plan));
+    let failed = moved
+        .as_ref()
+        .map_or(0, |results| results.iter().filter(|r| !r.success).count());
+
+    match format {
+        OutputFormat::Json => {
+            let splits_json: Vec<Value> = results
+                .iter()
+                .map(|(split, stats)| balance_to_json(*split, stats))
+                .collect();
+            let rebalance_json = plan.as_ref().map(|plan| {
+                json!({
+                    "dry_run": dry_run,
+                    "total_moves": plan.total_moves,
+                    "iterations": plan.iterations_used,
+                    "moves": plan
+                        .moves
+                        .iter()
+                        .map(|group| json!({
+                            "from": group.from_split.as_str(),
+                            "to": group.to_split.as_str(),
+                            "category": group.category.as_str(),
+                            "count": group.count,
+                        }))
+                        .collect::<Vec<_>>(),
+                    "warnings": plan.warnings.iter().map(|w| w.message()).collect::<Vec<_>>(),
+                    "moved": moved.as_ref().map(|results| results.len() - failed),
+                    "failed": moved.as_ref().map(|_| failed),
+                })
+            });
+            println!(
+                "{}",
+                json!({
+                    "dataset": dataset,
+                    "splits": splits_json,
+                    "rebalance": rebalance_json,
+                })
+            );
+        }
+        OutputFormat::Text => {
+            print_balance_text(&results);
+            if let Some(plan) = &plan {
+                println!(
+                    "Rebalance plan: {} move(s){}",
+                    plan.total_moves,
+                    if dry_run { " (dry run)" } else { "" }
+                );
+                for (from, to, description) in plan.preview_as_table() {
+                    println!("  {} -> {}: {}", from, to, description);
+                }
+                for warning in &plan.warnings {
+                    println!("  warning: {}", warning.message());
+                }
+                if let Some(results) = &moved {
+                    println!("Moved {} image(s), {} failed", results.len() - failed, failed);
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} move(s) failed", failed));
+    }
+    Ok(())
+}
+
+/// Run a global rebalance plan, reporting progress on stderr
+fn execute_with_progress(dataset: &Path, plan: &GlobalRebalancePlan) -> Vec<MoveResult> {
+    let (tx, rx) = channel();
+    let printer = thread::spawn(move || {
+        for message in rx {
+            if let RebalanceProgressMessage::Progress { current, total, .. } = message {
+                if current.is_multiple_of(100) || current == total {
+                    eprintln!("Moved {}/{}", current, total);
+                }
+            }
+        }
+    });
+    // The sender is dropped when the plan finishes, which ends the printer
+    let results = execute_global_rebalance_plan(dataset, plan, Some(tx), None);
+    let _ = printer.join();
+    results
+}
+
+/// One row per planned move
+fn moves_csv(plan: &GlobalRebalancePlan) -> String {
+    let mut csv = String::from("image,label,category,from,to\n");
+    for action in plan.all_actions() {
+        let label = action
+            .label_path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_field(&action.image_path.display().to_string()),
+            csv_field(&label),
+            csv_field(action.category.as_str()),
+            action.from_split.as_str(),
+            action.to_split.as_str()
+        ));
+    }
+    csv
+}
+
+/// One row per split and category
+fn categories_csv(results: &[(DatasetSplit, BalanceStats)]) -> String {
+    let mut csv = String::from("split,category,count,percentage\n");
+    for (split, stats) in results {
+        for category in CATEGORIES {
+            csv.push_str(&format!(
+                "{},{},{},{:.2}\n",
+                split.as_str(),
+                csv_field(category.as_str()),
+                stats.get_count(category),
+                stats.get_percentage(category)
+            ));
+        }
+    }
+    csv
+}
+
+/// Lock the dataset for moving files, or fail if another instance holds it
+fn lock_dataset(dataset: &Path) -> Result<DatasetLock, String> {
+    match DatasetLock::acquire(dataset) {
+        Ok(LockOutcome::Acquired(lock)) => {
+            eprintln!("Locked {}", lock.file().display());
+            Ok(lock)
+        }
+        Ok(LockOutcome::Held(holder)) => Err(format!(
+            "{} is open in another instance (PID {}); close it before rebalancing",
+            dataset.display(),
+            holder.pid
+        )),
+        Err(e) => Err(format!("Failed to lock {}: {}", dataset.display(), e)),
+    }
+}
+
+/// Class IDs of the class table saved by the GUI
+fn known_class_ids() -> Vec<u32> {
+    Settings::load().classes.iter().map(|c| c.id).collect()
+}
+
+/// Returns the total number of issues across all processed splits
+fn run_integrity(dataset: &Path, splits: &[DatasetSplit], format: OutputFormat) -> usize {
+    let known_class_ids = known_class_ids();
+
+    let results: Vec<(DatasetSplit, IntegrityStats)> = splits
+        .iter()
+        .map(|&split| {
+            (
+                split,
+                analyze_dataset_integrity(
+                    dataset,
+                    split,
+                    Some(&known_class_ids),
+                    Some(DEFAULT_OVERLAP_IOU_THRESHOLD),
+                ),
+            )
+        })
+        .collect();
+    let total_issues: usize = results.iter().map(|(_, stats)| stats.total_issues()).sum();
+
+    match format {
+        OutputFormat::Json => {
+            let splits_json: Vec<Value> = results
+                .iter()
+                .map(|(split, stats)| integrity_to_json(*split, stats))
+                .collect();
+            println!(
+                "{}",
+                json!({
+                    "dataset": dataset,
+                    "total_issues": total_issues,
+                    "splits": splits_json,
+                })
+            );
+        }
+        OutputFormat::Text => {
+            for (split, stats) in &results {
+                println!("[{}] {} issue(s)", split.as_str(), stats.total_issues());
+                for issue in &stats.images_without_labels {
+                    println!("  image without label: {}", issue.path.display());
+                }
+                for issue in &stats.labels_without_images {
+                    println!("  label without image: {}", issue.path.display());
+                }
+                for issue in &stats.labels_with_unknown_classes {
+                    println!(
+                        "  unknown class {:?}: {}",
+                        issue.class_ids,
+                        issue.path.display()
+                    );
+                }
+                for issue in &stats.overlapping_boxes {
+                    for pair in &issue.pairs {
+                        println!(
+                            "  overlapping boxes {} and {} (IoU {:.2}): {}",
+                            pair.first,
+                            pair.second,
+                            pair.iou,
+                            issue.path.display()
+                        );
+                    }
+                }
+                for issue in &stats.out_of_range_boxes {
+                    println!(
+                        "  {} box(es) outside the image: {}",
+                        issue.detections,
+                        issue.path.display()
+                    );
+                }
+                for issue in &stats.malformed_labels {
+                    println!("  malformed label ({}): {}", issue.error, issue.path.display());
+                }
+                for issue in &stats.empty_label_files {
+                    println!("  empty label (not counted): {}", issue.path.display());
+                }
+            }
+            println!("Total issues: {}", total_issues);
+        }
+    }
+
+    total_issues
+}
+
+fn balance_to_json(split: DatasetSplit, stats: &BalanceStats) -> Value {
+    let categories: serde_json::Map<String, Value> = CATEGORIES
+        .iter()
+        .map(|&category| {
+            (
+                category.as_str().to_string(),
+                json!({
+                    "count": stats.get_count(category),
+                    "percentage": stats.get_percentage(category),
+                }),
+            )
+        })
+        .collect();
+
+    json!({
+        "split": split.as_str(),
+        "total_images": stats.total_images,
+        "player_images": stats.total_player_images(),
+        "categories": categories,
+        "locations": stats.location_counts,
+    })
+}
+
+fn integrity_to_json(split: DatasetSplit, stats: &IntegrityStats) -> Value {
+    let issue_json = |issue: &IntegrityIssue| {
+        json!({
+            "path": issue.path,
+            "expected_counterpart": issue.expected_counterpart,
+        })
+    };
+
+    json!({
+        "split": split.as_str(),
+        "total_issues": stats.total_issues(),
+        "images_without_labels": stats.images_without_labels.iter().map(issue_json).collect::<Vec<_>>(),
+        "labels_without_images": stats.labels_without_images.iter().map(issue_json).collect::<Vec<_>>(),
+        "labels_with_unknown_classes": stats
+            .labels_with_unknown_classes
+            .iter()
+            .map(|issue| json!({ "path": issue.path, "class_ids": issue.class_ids }))
+            .collect::<Vec<_>>(),
+        "overlapping_boxes": stats
+            .overlapping_boxes
+            .iter()
+            .map(|issue| {
+                let pairs: Vec<Value> = issue
+                    .pairs
+                    .iter()
+                    .map(|pair| json!({ "first": pair.first, "second": pair.second, "iou": pair.iou }))
+                    .collect();
+                json!({ "path": issue.path, "pairs": pairs })
+            })
+            .collect::<Vec<_>>(),
+        "out_of_range_boxes": stats
+            .out_of_range_boxes
+            .iter()
+            .map(|issue| json!({ "path": issue.path, "detections": issue.detections }))
+            .collect::<Vec<_>>(),
+        "malformed_labels": stats
+            .malformed_labels
+            .iter()
+            .map(|issue| json!({ "path": issue.path, "error": issue.error.to_string() }))
+            .collect::<Vec<_>>(),
+        "empty_label_files": stats.empty_label_files.iter().map(issue_json).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_errors_exit_with_code_one_and_issues_with_code_two() {
+        assert_eq!(usage_error("unknown option"), ExitCode::from(1));
+
+        let tmp = TempDir::new("yolo_cli_missing_dataset_test");
+        let missing = Command::Analyze {
+            dataset: tmp.path().join("missing"),
+            splits: vec![DatasetSplit::Train],
+            format: OutputFormat::Json,
+        };
+        assert_eq!(run(missing), ExitCode::from(1));
+        assert_eq!(run(Command::Help), ExitCode::SUCCESS);
+
+        // An image without a label is an issue, not an error
+        let tmp = TempDir::new("yolo_cli_exit_code_test");
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("train/images")).unwrap();
+        fs::create_dir_all(root.join("train/labels")).unwrap();
+        fs::write(root.join("train/images/orphan.png"), b"png").unwrap();
+        let integrity = |fail_on_issues| Command::Integrity {
+            dataset: root.clone(),
+            splits: vec![DatasetSplit::Train],
+            format: OutputFormat::Json,
+            fail_on_issues,
+        };
+        assert_eq!(run(integrity(false)), ExitCode::SUCCESS);
+        assert_eq!(run(integrity(true)), ExitCode::from(2));
+        assert_ne!(EXIT_ISSUES_FOUND, EXIT_FAILED);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rebalance_refuses_locked_dataset() {
+        use crate::core::dataset::LockInfo;
+
+        let tmp = TempDir::new("yolo_cli_lock_test");
+        let root = tmp.path().to_path_buf();
+        for split in ["train", "val", "test"] {
+            fs::create_dir_all(root.join(split).join("images")).unwrap();
+            fs::create_dir_all(root.join(split).join("labels")).unwrap();
+        }
+        for i in 0..10 {
+            fs::write(root.join(format!("train/images/{}.png", i)), b"png").unwrap();
+        }
+
+        // The parent process (the test runner) stands in for a running GUI instance
+        let holder = LockInfo {
+            pid: std::os::unix::process::parent_id(),
+            started_at: 0,
+        };
+        fs::create_dir_all(root.join(".cleaner")).unwrap();
+        fs::write(root.join(".cleaner/lock"), serde_json::to_string(&holder).unwrap()).unwrap();
+
+        let error = run_balance(&root, &[DatasetSplit::Train], OutputFormat::Json, true, false, None)
+            .unwrap_err();
+        assert!(error.contains("another instance"), "{}", error);
+        assert_eq!(fs::read_dir(root.join("train/images")).unwrap().count(), 10);
+        assert_eq!(DatasetLock::holder(&root), Some(holder));
+
+        // A dry run moves nothing, so it does not need the lock
+        assert!(run_balance(&root, &[DatasetSplit::Train], OutputFormat::Json, true, true, None).is_ok());
+    }
+}
@@ -0,0 +1,12 @@
+//! Headless command-line interface
+//!
+//! Running the executable with a subcommand (`analyze`, `integrity`,
+//! `rebalance`, `help`) or with `--cli` performs the work without opening a
+//! window, which makes the analysis usable from scripts and CI. Without a
+//! subcommand the GUI starts as usual, whatever other arguments are given.
+
+mod args;
+mod commands;
+
+pub use args::{parse_args, take_read_only_flag, CLI_ENV_VAR};
+pub use commands::{run, usage_error};
@@ -1,6 +1,108 @@
 use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// Alpha applied to a class's box color for the translucent box fill
+const CLASS_FILL_ALPHA: u8 = 30;
+
+/// Display settings for a single YOLO class
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassStyle {
+    /// YOLO class id as written in label files
+    pub id: u32,
+    /// Display name (e.g. "CT")
+    pub name: String,
+    /// Bounding box border color (RGB)
+    pub box_color: [u8; 3],
+    /// Color of the text drawn on the box label (RGB)
+    pub text_color: [u8; 3],
+}
+
+impl ClassStyle {
+    pub fn new(id: u32, name: &str, box_color: [u8; 3]) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            box_color,
+            text_color: [255, 255, 255],
+        }
+    }
+
+    /// Border color as an egui color
+    pub fn box_color32(&self) -> Color32 {
+        let [r, g, b] = self.box_color;
+        Color32::from_rgb(r, g, b)
+    }
+
+    /// Translucent fill color derived from the box color
+    pub fn fill_color32(&self) -> Color32 {
+        let [r, g, b] = self.box_color;
+        Color32::from_rgba_unmultiplied(r, g, b, CLASS_FILL_ALPHA)
+    }
+
+    /// Label text color as an egui color
+    pub fn text_color32(&self) -> Color32 {
+        let [r, g, b] = self.text_color;
+        Color32::from_rgb(r, g, b)
+    }
+}
+
+/// Built-in class table for the CS2 dataset (T and CT)
+pub fn default_classes() -> Vec<ClassStyle> {
+    vec![
+        // T - Orange
+        ClassStyle::new(0, "T", [255, 140, 0]),
+        // CT - Blue
+        ClassStyle::new(1, "CT", [100, 149, 237]),
+    ]
+}
+
+/// Box colors for classes named in a `data.yaml` that have no built-in color
+const YAML_CLASS_PALETTE: [[u8; 3]; 6] = [
+    [60, 179, 113],
+    [220, 20, 60],
+    [255, 215, 0],
+    [186, 85, 211],
+    [0, 206, 209],
+    [244, 164, 96],
+];
+
+/// Class table for the class names read from a dataset's `data.yaml`
+///
+/// A class the user customized in settings (a table that differs from the
+/// built-in one) keeps its settings entry; other names get the built-in color
+/// for their id, or a palette color. Settings classes the yaml does not name
+/// are kept at the end.
+pub fn classes_from_names(names: &[(u32, String)], settings_classes: &[ClassStyle]) -> Vec<ClassStyle> {
+    let defaults = default_classes();
+    let overrides: &[ClassStyle] = if settings_classes == defaults.as_slice() {
+        &[]
+    } else {
+        settings_classes
+    };
+
+    let mut classes: Vec<ClassStyle> = names
+        .iter()
+        .map(|(id, name)| {
+            if let Some(class) = overrides.iter().find(|class| class.id == *id) {
+                return class.clone();
+            }
+            let box_color = defaults
+                .iter()
+                .find(|class| class.id == *id)
+                .map(|class| class.box_color)
+                .unwrap_or(YAML_CLASS_PALETTE[*id as usize % YAML_CLASS_PALETTE.len()]);
+            ClassStyle::new(*id, name, box_color)
+        })
+        .collect();
+    for class in overrides {
+        if !names.iter().any(|(id, _)| *id == class.id) {
+            classes.push(class.clone());
+        }
+    }
+    classes
+}
+
 /// Application configuration containing all hardcoded values
 ///
 /// This struct centralizes configuration values to make them easier to manage
@@ -10,13 +112,24 @@ pub struct AppConfig {
     pub default_dataset_path: PathBuf,
     pub window_width: f32,
     pub window_height: f32,
-    pub class_names: Vec<&'static str>,
-    pub class_colors: Vec<(Color32, Color32)>, // (border_color, fill_color)
+    /// Class table (names and colors); loaded from and saved to `Settings`
+    pub classes: Vec<ClassStyle>,
+    /// Class table derived from the loaded dataset's `data.yaml`, if any;
+    /// only edits made on top of it are saved to `Settings`
+    pub data_yaml_classes: Option<Vec<ClassStyle>>,
     pub side_panel_width: f32,
+    /// Smallest zoom level (the maximum is user-configurable in `Settings`)
+    pub min_zoom: f32,
     // Dataset balancing target ratios
     pub target_player_ratio: f32,     // 0.85 for 85%
     pub target_background_ratio: f32, // 0.10 for 10%
     pub target_hardcase_ratio: f32,   // 0.05 for 5%
+    /// Target CT share of single-team player images for single-split rebalancing
+    pub target_ct_t_ratio: f32,       // 0.50 for 50% CT
+    // Split size target ratios for the global rebalancer
+    pub target_train_ratio: f32,      // 0.70 for 70%
+    pub target_val_ratio: f32,        // 0.20 for 20%
+    pub target_test_ratio: f32,       // 0.10 for 10%
 }
 
 impl Default for AppConfig {
@@ -27,45 +140,108 @@ impl Default for AppConfig {
             ),
             window_width: 1200.0,
             window_height: 800.0,
-            class_names: vec!["T", "CT"],
-            class_colors: vec![
-                // T - Orange
-                (
-                    Color32::from_rgb(255, 140, 0),
-                    Color32::from_rgba_unmultiplied(255, 140, 0, 30),
-                ),
-                // CT - Blue
-                (
-                    Color32::from_rgb(100, 149, 237),
-                    Color32::from_rgba_unmultiplied(100, 149, 237, 30),
-                ),
-            ],
+            classes: default_classes(),
+            data_yaml_classes: None,
             side_panel_width: 300.0,
+            min_zoom: 0.5,
             target_player_ratio: 0.85,     // 85%
             target_background_ratio: 0.10, // 10%
             target_hardcase_ratio: 0.05,   // 5%
+            target_ct_t_ratio: 0.50,       // 50% CT, 50% T
+            target_train_ratio: 0.70,      // 70%
+            target_val_ratio: 0.20,        // 20%
+            target_test_ratio: 0.10,       // 10%
         }
     }
 }
 
 impl AppConfig {
+    /// Look up the class table entry for a class ID
+    pub fn get_class(&self, class_id: u32) -> Option<&ClassStyle> {
+        self.classes.iter().find(|class| class.id == class_id)
+    }
+
+    /// Class ids and names sorted by id, as written to a dataset yaml
+    ///
+    /// The first entry wins for duplicate ids, as in class lookups.
+    pub fn class_names(&self) -> Vec<(u32, String)> {
+        let mut names: Vec<(u32, String)> = Vec::new();
+        for class in &self.classes {
+            if !names.iter().any(|(id, _)| *id == class.id) {
+                names.push((class.id, class.name.clone()));
+            }
+        }
+        names.sort_by_key(|(id, _)| *id);
+        names
+    }
+
     /// Get class name for a given class ID
     pub fn get_class_name(&self, class_id: u32) -> &str {
-        self.class_names
-            .get(class_id as usize)
-            .copied()
+        self.get_class(class_id)
+            .map(|class| class.name.as_str())
             .unwrap_or("Unknown")
     }
 
     /// Get colors for a given class ID
     /// Returns (border_color, fill_color)
     pub fn get_class_colors(&self, class_id: u32) -> (Color32, Color32) {
-        self.class_colors
-            .get(class_id as usize)
-            .copied()
+        self.get_class(class_id)
+            .map(|class| (class.box_color32(), class.fill_color32()))
             .unwrap_or((
                 Color32::GRAY,
-                Color32::from_rgba_unmultiplied(128, 128, 128, 30),
+                Color32::from_rgba_unmultiplied(128, 128, 128, CLASS_FILL_ALPHA),
             ))
     }
+
+    /// Get the label text color for a given class ID
+    pub fn get_class_text_color(&self, class_id: u32) -> Color32 {
+        self.get_class(class_id)
+            .map(|class| class.text_color32())
+            .unwrap_or(Color32::WHITE)
+    }
+
+    /// IDs of all classes in the class table
+    pub fn class_ids(&self) -> Vec<u32> {
+        self.classes.iter().map(|class| class.id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_lookup_by_id() {
+        let mut config = AppConfig::default();
+        config.classes.push(ClassStyle::new(5, "Chicken", [255, 255, 0]));
+
+        assert_eq!(config.get_class_name(0), "T");
+        assert_eq!(config.get_class_name(5), "Chicken");
+        assert_eq!(config.get_class_name(2), "Unknown");
+        assert_eq!(config.get_class_colors(5).0, Color32::from_rgb(255, 255, 0));
+        assert_eq!(config.get_class_colors(2).0, Color32::GRAY);
+        assert_eq!(config.class_ids(), vec![0, 1, 5]);
+    }
+
+    #[test]
+    fn test_classes_from_yaml_names() {
+        let names = vec![(0, "Terrorist".to_string()), (1, "CT".to_string()), (4, "Bomb".to_string())];
+
+        // An untouched settings table is replaced by the yaml names
+        let classes = classes_from_names(&names, &default_classes());
+        assert_eq!(classes.len(), 3);
+        assert_eq!(classes[0], ClassStyle::new(0, "Terrorist", [255, 140, 0]));
+        assert_eq!(classes[2].name, "Bomb");
+        assert_eq!(classes[2].box_color, YAML_CLASS_PALETTE[4]);
+
+        // Customized settings entries win, extra settings classes are kept
+        let custom = vec![
+            ClassStyle::new(1, "Counter", [1, 2, 3]),
+            ClassStyle::new(7, "Chicken", [255, 255, 0]),
+        ];
+        let classes = classes_from_names(&names, &custom);
+        assert_eq!(classes.iter().map(|c| c.id).collect::<Vec<_>>(), vec![0, 1, 4, 7]);
+        assert_eq!(classes[1], custom[0]);
+        assert_eq!(classes[3], custom[1]);
+    }
 }
@@ -1,3 +1,3 @@
 mod app_config;
 
-pub use app_config::AppConfig;
+pub use app_config::{classes_from_names, default_classes, AppConfig, ClassStyle};
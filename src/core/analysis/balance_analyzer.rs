@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -8,7 +9,11 @@ use std::sync::{
 };
 use tracing::{info, warn};
 
-use crate::core::dataset::{parse_label_file, DatasetSplit};
+use crate::core::image::{find_corrupt_images, ImageCheck};
+use crate::core::dataset::{
+    images_dir, labels_dir, overlapping_pairs, parse_label_file, parse_label_file_strict,
+    DatasetSplit, LabelInfo, LabelParseError,
+};
 
 /// Progress message types for background analysis
 #[derive(Clone)]
@@ -23,7 +28,7 @@ pub enum BalanceProgressMessage {
 }
 
 /// Categories for classifying images based on their detections
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ImageCategory {
     /// Image contains only CT players (class_id 1)
     CTOnly,
@@ -33,11 +38,25 @@ pub enum ImageCategory {
     MultiplePlayer,
     /// Image has no detections (background)
     Background,
-    /// Hard case - requires manual review
+    /// Hard case - detections exist but only of non-player classes from the
+    /// class table (e.g. a hostage class added in the settings)
     HardCase,
+    /// Detections exist but none are players, and some use a class ID that is
+    /// not in the class table
+    Unknown,
 }
 
 impl ImageCategory {
+    /// Every category, in the order they are listed in the UI
+    pub const ALL: [ImageCategory; 6] = [
+        ImageCategory::Background,
+        ImageCategory::CTOnly,
+        ImageCategory::TOnly,
+        ImageCategory::MultiplePlayer,
+        ImageCategory::HardCase,
+        ImageCategory::Unknown,
+    ];
+
     pub fn as_str(&self) -> &str {
         match self {
             ImageCategory::CTOnly => "CT Only",
@@ -45,6 +64,7 @@ impl ImageCategory {
             ImageCategory::MultiplePlayer => "Multiple Players",
             ImageCategory::Background => "Background",
             ImageCategory::HardCase => "Hard Case",
+            ImageCategory::Unknown => "Unknown Class",
         }
     }
 }
@@ -57,7 +77,10 @@ pub struct BalanceStats {
     pub t_only: usize,
     pub multiple_player: usize,
     pub background: usize,
+    /// Images without players whose classes are all in the class table
     pub hard_case: usize,
+    /// Images without players that use a class ID missing from the class table
+    pub unknown: usize,
     /// Count of images per location (e.g., "TSpawn" => 150)
     pub location_counts: HashMap<String, usize>,
 }
@@ -71,6 +94,7 @@ impl BalanceStats {
             multiple_player: 0,
             background: 0,
             hard_case: 0,
+            unknown: 0,
             location_counts: HashMap::new(),
         }
     }
@@ -83,6 +107,7 @@ impl BalanceStats {
             ImageCategory::MultiplePlayer => self.multiple_player,
             ImageCategory::Background => self.background,
             ImageCategory::HardCase => self.hard_case,
+            ImageCategory::Unknown => self.unknown,
         }
     }
 
@@ -106,6 +131,79 @@ impl BalanceStats {
         }
         (self.total_player_images() as f32 / self.total_images as f32) * 100.0
     }
+
+    /// Add the counts of another split's results
+    pub fn merge(&mut self, other: &BalanceStats) {
+        self.total_images += other.total_images;
+        self.ct_only += other.ct_only;
+        self.t_only += other.t_only;
+        self.multiple_player += other.multiple_player;
+        self.background += other.background;
+        self.hard_case += other.hard_case;
+        self.unknown += other.unknown;
+        for (location, count) in &other.location_counts {
+            *self.location_counts.entry(location.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// Share of hard-case images among all images (0.05 = 5%)
+    pub fn hard_case_ratio(&self) -> f32 {
+        if self.total_images == 0 {
+            return 0.0;
+        }
+        self.hard_case as f32 / self.total_images as f32
+    }
+
+    /// Share of CT-only images among single-team images (0.5 = balanced)
+    /// Returns None when there are no CT-only or T-only images
+    pub fn ct_t_ratio(&self) -> Option<f32> {
+        let single_team = self.ct_only + self.t_only;
+        if single_team == 0 {
+            return None;
+        }
+        Some(self.ct_only as f32 / single_team as f32)
+    }
+
+    /// Distance of the player, background and hard-case shares from `target`
+    ///
+    /// Sum of the absolute differences between each share and its target
+    /// ratio: 0 is exactly on target, 2 is as far off as possible.
+    pub fn imbalance_score(&self, target: &TargetRatios) -> f32 {
+        if self.total_images == 0 {
+            return 0.0;
+        }
+        let total = self.total_images as f32;
+        (self.total_player_images() as f32 / total - target.player_ratio).abs()
+            + (self.background as f32 / total - target.background_ratio).abs()
+            + (self.hard_case as f32 / total - target.hardcase_ratio).abs()
+    }
+
+    /// Shannon entropy (bits) of the CT-only, T-only, multiple-player,
+    /// background and hard-case counts
+    ///
+    /// `MAX_DISTRIBUTION_ENTROPY` means all five are equally common, 0 means
+    /// one category holds every image.
+    pub fn entropy(&self) -> f32 {
+        let counts = [
+            self.ct_only,
+            self.t_only,
+            self.multiple_player,
+            self.background,
+            self.hard_case,
+        ];
+        let total: usize = counts.iter().sum();
+        if total == 0 {
+            return 0.0;
+        }
+        -counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f32 / total as f32;
+                p * p.log2()
+            })
+            .sum::<f32>()
+    }
 }
 
 impl Default for BalanceStats {
@@ -143,23 +241,141 @@ pub enum IntegrityIssueType {
     ImageWithoutLabel,
     /// Label file exists but no corresponding image
     LabelWithoutImage,
+    /// The same image file stem exists in more than one split (data leak)
+    DuplicateAcrossSplits { splits: Vec<DatasetSplit> },
+    /// Label file is zero bytes or whitespace only (a valid background, but often a capture bug)
+    EmptyLabel,
+    /// Image file that cannot be decoded (truncated or not an image), with the decoder's error
+    CorruptImage { error: String },
 }
 
+/// Entropy of five equally common categories (log2 5)
+pub const MAX_DISTRIBUTION_ENTROPY: f32 = 2.321_928;
+
+/// Distribution entropy below which the categories count as uneven
+pub const GOOD_DISTRIBUTION_ENTROPY: f32 = 1.5;
+
+/// Default IoU above which two boxes in one label file count as duplicates
+pub const DEFAULT_OVERLAP_IOU_THRESHOLD: f32 = 0.85;
+
 /// A single integrity issue
 #[derive(Debug, Clone)]
 pub struct IntegrityIssue {
     pub issue_type: IntegrityIssueType,
+    /// The split the file belongs to
+    pub split: DatasetSplit,
     /// The existing file path
     pub path: PathBuf,
-    /// The missing counterpart path (for display purposes)
+    /// The missing counterpart path (for display purposes); the label of a corrupt image
     pub expected_counterpart: PathBuf,
 }
 
+/// A label file that uses class ids not present in the class table
+#[derive(Debug, Clone)]
+pub struct UnknownClassIssue {
+    pub split: DatasetSplit,
+    pub path: PathBuf,
+    /// The unknown class ids found in the file (sorted, deduplicated)
+    pub class_ids: Vec<u32>,
+}
+
+/// Two detections of one label file that overlap above the IoU threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlapPair {
+    /// Index of the first detection in the label's detection list
+    pub first: usize,
+    /// Index of the second detection (always greater than `first`)
+    pub second: usize,
+    pub iou: f32,
+}
+
+/// A label file with nearly identical boxes (usually a double auto-label)
+#[derive(Debug, Clone)]
+pub struct OverlapIssue {
+    pub split: DatasetSplit,
+    pub path: PathBuf,
+    /// Offending pairs, highest IoU first
+    pub pairs: Vec<OverlapPair>,
+}
+
+/// A label file with detections outside the image
+#[derive(Debug, Clone)]
+pub struct OutOfRangeIssue {
+    pub split: DatasetSplit,
+    pub path: PathBuf,
+    /// Detections whose coordinates need clamping
+    pub detections: usize,
+}
+
+/// A label file that does not parse strictly (unreadable or with a bad detection line)
+#[derive(Debug, Clone)]
+pub struct MalformedLabelIssue {
+    pub split: DatasetSplit,
+    pub path: PathBuf,
+    /// The first problem found in the file
+    pub error: LabelParseError,
+}
+
+/// Whether the copies of a cross-split duplicate have the same content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateContent {
+    /// Every copy hashed identically
+    Identical,
+    /// At least one copy differs
+    Different,
+    /// Hashes were not compared
+    NotCompared,
+}
+
+/// An image file stem that appears in more than one split
+#[derive(Debug, Clone)]
+pub struct DuplicateIssue {
+    /// Always `IntegrityIssueType::DuplicateAcrossSplits`
+    pub issue_type: IntegrityIssueType,
+    pub stem: String,
+    /// Each copy's split and image path, in split order
+    pub copies: Vec<(DatasetSplit, PathBuf)>,
+    pub content: DuplicateContent,
+}
+
+impl DuplicateIssue {
+    /// Human-readable description (e.g. "train, val: same name, same content")
+    pub fn description(&self) -> String {
+        let splits: Vec<&str> = match &self.issue_type {
+            IntegrityIssueType::DuplicateAcrossSplits { splits } => {
+                splits.iter().map(|split| split.as_str()).collect()
+            }
+            _ => self.copies.iter().map(|(split, _)| split.as_str()).collect(),
+        };
+        let content = match self.content {
+            DuplicateContent::Identical => "same name, same content",
+            DuplicateContent::Different => "same name, different content",
+            DuplicateContent::NotCompared => "same name",
+        };
+        format!("{}: {}", splits.join(", "), content)
+    }
+}
+
 /// Statistics about dataset integrity issues
 #[derive(Debug, Clone, Default)]
 pub struct IntegrityStats {
     pub images_without_labels: Vec<IntegrityIssue>,
     pub labels_without_images: Vec<IntegrityIssue>,
+    pub labels_with_unknown_classes: Vec<UnknownClassIssue>,
+    /// Image stems found in more than one split (all-splits analysis only)
+    pub duplicates_across_splits: Vec<DuplicateIssue>,
+    /// Zero-byte or whitespace-only label files (not counted in `total_issues`)
+    pub empty_label_files: Vec<IntegrityIssue>,
+    /// Label files with a pair of boxes above the overlap IoU threshold
+    pub overlapping_boxes: Vec<OverlapIssue>,
+    /// Label files with coordinates outside `[0, 1]` or boxes past the image border
+    pub out_of_range_boxes: Vec<OutOfRangeIssue>,
+    /// Label files that could not be read or have lines that do not parse
+    pub malformed_labels: Vec<MalformedLabelIssue>,
+    /// Images that failed to decode (only when an image check was requested)
+    pub corrupt_images: Vec<IntegrityIssue>,
+    /// Number of image and label files scanned
+    pub files_scanned: usize,
 }
 
 impl IntegrityStats {
@@ -169,6 +385,17 @@ impl IntegrityStats {
 
     /// Total count of all integrity issues
     pub fn total_issues(&self) -> usize {
+        self.orphan_count()
+            + self.labels_with_unknown_classes.len()
+            + self.duplicates_across_splits.len()
+            + self.overlapping_boxes.len()
+            + self.out_of_range_boxes.len()
+            + self.malformed_labels.len()
+            + self.corrupt_images.len()
+    }
+
+    /// Count of orphaned files (the issues that can be fixed by deletion)
+    pub fn orphan_count(&self) -> usize {
         self.images_without_labels.len() + self.labels_without_images.len()
     }
 
@@ -176,6 +403,22 @@ impl IntegrityStats {
     pub fn has_issues(&self) -> bool {
         self.total_issues() > 0
     }
+
+    /// Append the issues and scan count of another split's results
+    pub fn merge(&mut self, other: IntegrityStats) {
+        self.images_without_labels.extend(other.images_without_labels);
+        self.labels_without_images.extend(other.labels_without_images);
+        self.labels_with_unknown_classes
+            .extend(other.labels_with_unknown_classes);
+        self.duplicates_across_splits
+            .extend(other.duplicates_across_splits);
+        self.empty_label_files.extend(other.empty_label_files);
+        self.overlapping_boxes.extend(other.overlapping_boxes);
+        self.out_of_range_boxes.extend(other.out_of_range_boxes);
+        self.malformed_labels.extend(other.malformed_labels);
+        self.corrupt_images.extend(other.corrupt_images);
+        self.files_scanned += other.files_scanned;
+    }
 }
 
 /// Progress message types for integrity analysis
@@ -191,9 +434,16 @@ pub enum IntegrityProgressMessage {
 }
 
 /// Categorize an image based on its label file
-pub fn categorize_image(label_path: &PathBuf) -> ImageCategory {
-    // Try to parse the label file
-    match parse_label_file(label_path) {
+pub fn categorize_image(label_path: &Path, known_class_ids: &[u32]) -> ImageCategory {
+    categorize_label(parse_label_file(label_path).as_ref(), known_class_ids)
+}
+
+/// Categorize an already parsed label (`None` = no label file)
+///
+/// `known_class_ids` are the ids of the class table; a label without players
+/// is a hard case when all its classes are known and Unknown otherwise.
+pub fn categorize_label(label: Option<&LabelInfo>, known_class_ids: &[u32]) -> ImageCategory {
+    match label {
         Some(label_info) => {
             if label_info.detections.is_empty() {
                 // No detections = background
@@ -216,7 +466,18 @@ pub fn categorize_image(label_path: &PathBuf) -> ImageCategory {
                 (true, true) => ImageCategory::MultiplePlayer,
                 (true, false) => ImageCategory::CTOnly,
                 (false, true) => ImageCategory::TOnly,
-                (false, false) => ImageCategory::Background, // Detections but none are CT or T
+                // Detections but none are CT or T
+                (false, false) => {
+                    let all_known = label_info
+                        .detections
+                        .iter()
+                        .all(|d| known_class_ids.contains(&d.class_id));
+                    if all_known {
+                        ImageCategory::HardCase
+                    } else {
+                        ImageCategory::Unknown
+                    }
+                }
             }
         }
         None => {
@@ -228,16 +489,17 @@ pub fn categorize_image(label_path: &PathBuf) -> ImageCategory {
 
 /// Analyze dataset balance for a given split with optional progress reporting
 pub fn analyze_dataset_with_progress(
-    dataset_path: &PathBuf,
+    dataset_path: &Path,
     split: DatasetSplit,
+    known_class_ids: &[u32],
     progress_tx: Option<Sender<BalanceProgressMessage>>,
     cancel_flag: Option<Arc<AtomicBool>>,
 ) -> BalanceStats {
     let mut stats = BalanceStats::new();
 
     // Navigate to split/images folder
-    let images_path = dataset_path.join(split.as_str()).join("images");
-    let labels_path = dataset_path.join(split.as_str()).join("labels");
+    let images_path = images_dir(dataset_path, split);
+    let labels_path = labels_dir(dataset_path, split);
 
     info!("Analyzing balance for split: {:?}", split.as_str());
     info!("Images path: {:?}", images_path);
@@ -286,7 +548,7 @@ pub fn analyze_dataset_with_progress(
         if let Some(stem) = image_path.file_stem() {
             let label_path = labels_path.join(format!("{}.txt", stem.to_string_lossy()));
 
-            let category = categorize_image(&label_path);
+            let category = categorize_image(&label_path, known_class_ids);
 
             match category {
                 ImageCategory::CTOnly => stats.ct_only += 1,
@@ -294,6 +556,7 @@ pub fn analyze_dataset_with_progress(
                 ImageCategory::MultiplePlayer => stats.multiple_player += 1,
                 ImageCategory::Background => stats.background += 1,
                 ImageCategory::HardCase => stats.hard_case += 1,
+                ImageCategory::Unknown => stats.unknown += 1,
             }
 
             // Track location statistics
@@ -333,8 +596,8 @@ pub fn analyze_dataset_with_progress(
 }
 
 /// Analyze dataset balance for a given split (synchronous version)
-pub fn analyze_dataset(dataset_path: &PathBuf, split: DatasetSplit) -> BalanceStats {
-    analyze_dataset_with_progress(dataset_path, split, None, None)
+pub fn analyze_dataset(dataset_path: &Path, split: DatasetSplit, known_class_ids: &[u32]) -> BalanceStats {
+    analyze_dataset_with_progress(dataset_path, split, known_class_ids, None, None)
 }
 
 /// Generate recommendations for manual balancing
@@ -424,6 +687,20 @@ pub fn get_recommendations(stats: &BalanceStats, target_ratios: &TargetRatios) -
         ));
     }
 
+    // Overall evenness of the categories
+    let entropy = stats.entropy();
+    if entropy < GOOD_DISTRIBUTION_ENTROPY {
+        recommendations.push(format!(
+            "⚖ Categories are unevenly distributed (entropy {:.2}, aim for at least {:.1} of {:.2})",
+            entropy, GOOD_DISTRIBUTION_ENTROPY, MAX_DISTRIBUTION_ENTROPY
+        ));
+    } else {
+        recommendations.push(format!(
+            "✓ Categories are evenly distributed (entropy {:.2} of {:.2})",
+            entropy, MAX_DISTRIBUTION_ENTROPY
+        ));
+    }
+
     // Hard case recommendations
     let hc_diff = current_hc_count - ideal_hc_count;
     if current_hc_count > 0 {
@@ -453,16 +730,25 @@ pub fn get_recommendations(stats: &BalanceStats, target_ratios: &TargetRatios) -
 /// Detects:
 /// - Images without corresponding label files
 /// - Label files without corresponding images
+/// - Label files using class ids outside `known_class_ids` (skipped when `None`)
+/// - Empty (zero-byte or whitespace-only) label files
+/// - Label files with two boxes whose IoU is above `overlap_threshold`
+///   (skipped when `None`)
+/// - Images that fail `image_check` (skipped when `None`); checked last and in
+///   parallel, and the images found so far are kept when cancelled
 pub fn analyze_dataset_integrity_with_progress(
-    dataset_path: &PathBuf,
+    dataset_path: &Path,
     split: DatasetSplit,
+    known_class_ids: Option<&[u32]>,
+    overlap_threshold: Option<f32>,
+    image_check: Option<ImageCheck>,
     progress_tx: Option<Sender<IntegrityProgressMessage>>,
     cancel_flag: Option<Arc<AtomicBool>>,
 ) -> IntegrityStats {
     let mut stats = IntegrityStats::new();
 
-    let images_path = dataset_path.join(split.as_str()).join("images");
-    let labels_path = dataset_path.join(split.as_str()).join("labels");
+    let images_path = images_dir(dataset_path, split);
+    let labels_path = labels_dir(dataset_path, split);
 
     info!("Analyzing integrity for split: {:?}", split.as_str());
     info!("Images path: {:?}", images_path);
@@ -505,7 +791,11 @@ pub fn analyze_dataset_integrity_with_progress(
         }
     }
 
-    let total_files = image_paths.len() + label_paths.len();
+    stats.files_scanned = image_paths.len() + label_paths.len();
+    let total_files = match image_check {
+        Some(_) => stats.files_scanned + image_paths.len(),
+        None => stats.files_scanned,
+    };
     let mut processed = 0;
 
     // Find images without labels
@@ -527,6 +817,7 @@ pub fn analyze_dataset_integrity_with_progress(
                 let expected_label = labels_path.join(format!("{}.txt", stem_str));
                 stats.images_without_labels.push(IntegrityIssue {
                     issue_type: IntegrityIssueType::ImageWithoutLabel,
+                    split,
                     path: image_path.clone(),
                     expected_counterpart: expected_label,
                 });
@@ -558,6 +849,66 @@ pub fn analyze_dataset_integrity_with_progress(
             }
         }
 
+        // Malformed files are reported, and their valid lines still checked below
+        let label_info = match parse_label_file_strict(label_path) {
+            Ok(label_info) => Some(label_info),
+            Err(error) => {
+                stats.malformed_labels.push(MalformedLabelIssue {
+                    split,
+                    path: label_path.clone(),
+                    error,
+                });
+                parse_label_file(label_path)
+            }
+        };
+
+        if let (Some(known), Some(label_info)) = (known_class_ids, &label_info) {
+            let mut unknown: Vec<u32> = label_info
+                .detections
+                .iter()
+                .map(|d| d.class_id)
+                .filter(|id| !known.contains(id))
+                .collect();
+            unknown.sort_unstable();
+            unknown.dedup();
+            if !unknown.is_empty() {
+                stats.labels_with_unknown_classes.push(UnknownClassIssue {
+                    split,
+                    path: label_path.clone(),
+                    class_ids: unknown,
+                });
+            }
+        }
+
+        if let (Some(threshold), Some(label_info)) = (overlap_threshold, &label_info) {
+            let pairs: Vec<OverlapPair> = overlapping_pairs(&label_info.detections, threshold)
+                .into_iter()
+                .map(|(first, second, iou)| OverlapPair { first, second, iou })
+                .collect();
+            if !pairs.is_empty() {
+                stats.overlapping_boxes.push(OverlapIssue {
+                    split,
+                    path: label_path.clone(),
+                    pairs,
+                });
+            }
+        }
+
+        if let Some(label_info) = &label_info {
+            let detections = label_info
+                .detections
+                .iter()
+                .filter(|d| !d.is_within_bounds())
+                .count();
+            if detections > 0 {
+                stats.out_of_range_boxes.push(OutOfRangeIssue {
+                    split,
+                    path: label_path.clone(),
+                    detections,
+                });
+            }
+        }
+
         if let Some(stem) = label_path.file_stem() {
             let stem_str = stem.to_string_lossy().to_string();
             if !image_stems.contains(&stem_str) {
@@ -565,6 +916,7 @@ pub fn analyze_dataset_integrity_with_progress(
                 let expected_image = images_path.join(format!("{}.png", stem_str));
                 stats.labels_without_images.push(IntegrityIssue {
                     issue_type: IntegrityIssueType::LabelWithoutImage,
+                    split,
                     path: label_path.clone(),
                     expected_counterpart: expected_image,
                 });
@@ -583,10 +935,74 @@ pub fn analyze_dataset_integrity_with_progress(
         }
     }
 
+    // Find empty label files
+    for label_path in &label_paths {
+        // Check for cancellation
+        if let Some(ref cancel) = cancel_flag {
+            if cancel.load(Ordering::Relaxed) {
+                warn!("Integrity analysis cancelled by user");
+                if let Some(ref tx) = progress_tx {
+                    let _ = tx.send(IntegrityProgressMessage::Cancelled(stats.clone()));
+                }
+                return stats;
+            }
+        }
+
+        if is_empty_label_file(label_path) {
+            let stem = label_path.file_stem().unwrap_or_default().to_string_lossy();
+            stats.empty_label_files.push(IntegrityIssue {
+                issue_type: IntegrityIssueType::EmptyLabel,
+                split,
+                path: label_path.clone(),
+                expected_counterpart: images_path.join(format!("{}.png", stem)),
+            });
+        }
+    }
+
+    // Decoding is by far the slowest check, so it runs last on all cores
+    if let Some(check) = image_check {
+        let found_so_far = stats.clone();
+        let on_progress = |checked: usize| {
+            if let Some(ref tx) = progress_tx {
+                if checked.is_multiple_of(50) || checked == image_paths.len() {
+                    let _ = tx.send(IntegrityProgressMessage::Progress {
+                        current: processed + checked,
+                        total: total_files,
+                        stats: found_so_far.clone(),
+                    });
+                }
+            }
+        };
+        let corrupt = find_corrupt_images(&image_paths, check, cancel_flag.as_deref(), &on_progress);
+        for (path, error) in corrupt {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            stats.corrupt_images.push(IntegrityIssue {
+                issue_type: IntegrityIssueType::CorruptImage { error },
+                split,
+                expected_counterpart: labels_path.join(format!("{}.txt", stem)),
+                path,
+            });
+        }
+
+        if cancel_flag
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+        {
+            warn!("Integrity analysis cancelled during the image check");
+            if let Some(ref tx) = progress_tx {
+                let _ = tx.send(IntegrityProgressMessage::Cancelled(stats.clone()));
+            }
+            return stats;
+        }
+    }
+
     info!(
-        "Integrity analysis complete: {} images without labels, {} labels without images",
+        "Integrity analysis complete: {} images without labels, {} labels without images, {} labels with unknown classes, {} malformed labels, {} empty labels",
         stats.images_without_labels.len(),
-        stats.labels_without_images.len()
+        stats.labels_without_images.len(),
+        stats.labels_with_unknown_classes.len(),
+        stats.malformed_labels.len(),
+        stats.empty_label_files.len()
     );
 
     // Send completion message
@@ -597,7 +1013,379 @@ pub fn analyze_dataset_integrity_with_progress(
     stats
 }
 
+/// Whether a label file is zero bytes or contains only whitespace
+fn is_empty_label_file(label_path: &Path) -> bool {
+    match fs::metadata(label_path) {
+        Ok(metadata) if metadata.len() == 0 => true,
+        Ok(_) => fs::read(label_path)
+            .map(|bytes| bytes.iter().all(u8::is_ascii_whitespace))
+            .unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
 /// Analyze dataset integrity (synchronous version)
-pub fn analyze_dataset_integrity(dataset_path: &PathBuf, split: DatasetSplit) -> IntegrityStats {
-    analyze_dataset_integrity_with_progress(dataset_path, split, None, None)
+pub fn analyze_dataset_integrity(
+    dataset_path: &Path,
+    split: DatasetSplit,
+    known_class_ids: Option<&[u32]>,
+    overlap_threshold: Option<f32>,
+) -> IntegrityStats {
+    analyze_dataset_integrity_with_progress(
+        dataset_path,
+        split,
+        known_class_ids,
+        overlap_threshold,
+        None,
+        None,
+        None,
+    )
+}
+
+/// Analyze integrity of train, val and test in turn, merging the results
+///
+/// Each issue carries its split. Image stems present in more than one split
+/// are reported as duplicates; with `compare_hashes` their contents are
+/// compared too. Progress is reported per completed split (`current`/`total`
+/// count splits, not files).
+pub fn analyze_all_splits_integrity_with_progress(
+    dataset_path: &Path,
+    known_class_ids: Option<&[u32]>,
+    overlap_threshold: Option<f32>,
+    compare_hashes: bool,
+    image_check: Option<ImageCheck>,
+    progress_tx: Option<Sender<IntegrityProgressMessage>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> IntegrityStats {
+    let mut stats = IntegrityStats::new();
+    let splits = DatasetSplit::all();
+
+    for (idx, split) in splits.iter().enumerate() {
+        let split_stats = analyze_dataset_integrity_with_progress(
+            dataset_path,
+            *split,
+            known_class_ids,
+            overlap_threshold,
+            image_check,
+            None,
+            cancel_flag.clone(),
+        );
+        stats.merge(split_stats);
+
+        if let Some(ref cancel) = cancel_flag {
+            if cancel.load(Ordering::Relaxed) {
+                warn!("All-splits integrity analysis cancelled by user");
+                if let Some(ref tx) = progress_tx {
+                    let _ = tx.send(IntegrityProgressMessage::Cancelled(stats.clone()));
+                }
+                return stats;
+            }
+        }
+
+        if let Some(ref tx) = progress_tx {
+            let _ = tx.send(IntegrityProgressMessage::Progress {
+                current: idx + 1,
+                total: splits.len(),
+                stats: stats.clone(),
+            });
+        }
+    }
+
+    stats.duplicates_across_splits = find_duplicates_across_splits(dataset_path, compare_hashes);
+
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(IntegrityProgressMessage::Complete(stats.clone()));
+    }
+
+    stats
+}
+
+/// Analyze integrity of all splits (synchronous version)
+#[cfg(test)]
+pub fn analyze_all_splits_integrity(
+    dataset_path: &Path,
+    known_class_ids: Option<&[u32]>,
+    overlap_threshold: Option<f32>,
+    compare_hashes: bool,
+) -> IntegrityStats {
+    analyze_all_splits_integrity_with_progress(
+        dataset_path,
+        known_class_ids,
+        overlap_threshold,
+        compare_hashes,
+        None,
+        None,
+        None,
+    )
+}
+
+/// Find image file stems that exist in more than one split
+///
+/// A stem shared between splits usually means a frame leaked from train into
+/// val or test during a manual copy.
+pub fn find_duplicates_across_splits(dataset_path: &Path, compare_hashes: bool) -> Vec<DuplicateIssue> {
+    let mut by_stem: HashMap<String, Vec<(DatasetSplit, PathBuf)>> = HashMap::new();
+
+    for split in DatasetSplit::all() {
+        let images_path = images_dir(dataset_path, split);
+        let Ok(entries) = fs::read_dir(&images_path) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_image = path.extension().is_some_and(|ext| {
+                let ext = ext.to_string_lossy().to_lowercase();
+                ext == "png" || ext == "jpg" || ext == "jpeg"
+            });
+            if let (true, Some(stem)) = (is_image, path.file_stem()) {
+                by_stem
+                    .entry(stem.to_string_lossy().to_string())
+                    .or_default()
+                    .push((split, path));
+            }
+        }
+    }
+
+    let mut duplicates: Vec<DuplicateIssue> = by_stem
+        .into_iter()
+        .filter_map(|(stem, mut copies)| {
+            copies.sort_by_key(|(split, _)| DatasetSplit::all().iter().position(|s| s == split));
+            let mut splits: Vec<DatasetSplit> = copies.iter().map(|(split, _)| *split).collect();
+            splits.dedup();
+            if splits.len() < 2 {
+                return None;
+            }
+
+            let content = if compare_hashes {
+                let hashes: Vec<Option<u64>> = copies.iter().map(|(_, path)| hash_file(path)).collect();
+                if hashes[0].is_some() && hashes.iter().all(|h| *h == hashes[0]) {
+                    DuplicateContent::Identical
+                } else {
+                    DuplicateContent::Different
+                }
+            } else {
+                DuplicateContent::NotCompared
+            };
+
+            Some(DuplicateIssue {
+                issue_type: IntegrityIssueType::DuplicateAcrossSplits { splits },
+                stem,
+                copies,
+                content,
+            })
+        })
+        .collect();
+    duplicates.sort_by(|a, b| a.stem.cmp(&b.stem));
+
+    info!("Found {} file stems duplicated across splits", duplicates.len());
+    duplicates
+}
+
+/// Hash a file's contents (None if it cannot be read)
+fn hash_file(path: &Path) -> Option<u64> {
+    use std::hash::{Hash, Hasher};
+
+    let bytes = fs::read(path).ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_categorize_hard_cases_and_unknown_classes() {
+        let tmp = TempDir::new("yolo_categorize_non_player_test");
+        let root = tmp.path().to_path_buf();
+        let write = |name: &str, content: &str| {
+            let path = root.join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+
+        // Class 2 is in the class table, class 3 is not
+        let known = [0, 1, 2];
+        let hard_case = write("hard_case.txt", "2 0.5 0.5 0.1 0.1\n2 0.2 0.2 0.1 0.1\n");
+        let unknown = write("unknown.txt", "2 0.5 0.5 0.1 0.1\n3 0.2 0.2 0.1 0.1\n");
+        let mixed = write("mixed.txt", "3 0.5 0.5 0.1 0.1\n1 0.2 0.2 0.1 0.1\n");
+        let empty = write("empty.txt", "# Map: de_mirage\n");
+        assert_eq!(categorize_image(&hard_case, &known), ImageCategory::HardCase);
+        assert_eq!(categorize_image(&hard_case, &[0, 1]), ImageCategory::Unknown);
+        assert_eq!(categorize_image(&unknown, &known), ImageCategory::Unknown);
+        assert_eq!(categorize_image(&mixed, &known), ImageCategory::CTOnly);
+        assert_eq!(categorize_image(&empty, &known), ImageCategory::Background);
+        assert_eq!(categorize_image(&root.join("missing.txt"), &known), ImageCategory::Background);
+
+        let stats = BalanceStats {
+            total_images: 200,
+            hard_case: 10,
+            ..BalanceStats::new()
+        };
+        assert_eq!(stats.hard_case_ratio(), 0.05);
+        assert_eq!(BalanceStats::new().hard_case_ratio(), 0.0);
+
+        let even = BalanceStats {
+            ct_only: 10,
+            t_only: 10,
+            multiple_player: 10,
+            background: 10,
+            hard_case: 10,
+            unknown: 40,
+            ..BalanceStats::new()
+        };
+        assert!((even.entropy() - MAX_DISTRIBUTION_ENTROPY).abs() < 1e-5);
+        let halves = BalanceStats {
+            ct_only: 30,
+            background: 30,
+            ..BalanceStats::new()
+        };
+        assert!((halves.entropy() - 1.0).abs() < 1e-6);
+        assert_eq!(BalanceStats { background: 5, ..BalanceStats::new() }.entropy(), 0.0);
+        assert_eq!(BalanceStats::new().entropy(), 0.0);
+    }
+
+    #[test]
+    fn test_all_splits_integrity_tags_issue_splits() {
+        let tmp = TempDir::new("yolo_integrity_all_splits_test");
+        let root = tmp.path().to_path_buf();
+        for split in DatasetSplit::all() {
+            fs::create_dir_all(root.join(split.as_str()).join("images")).unwrap();
+            fs::create_dir_all(root.join(split.as_str()).join("labels")).unwrap();
+        }
+        fs::write(root.join("train").join("images").join("a.png"), b"").unwrap();
+        fs::write(root.join("val").join("labels").join("b.txt"), "0 0.5 0.5 0.1 0.1\n").unwrap();
+        fs::write(root.join("test").join("images").join("c.jpg"), b"").unwrap();
+
+        let stats = analyze_all_splits_integrity(&root, None, None, false);
+
+        let image_splits: Vec<_> = stats.images_without_labels.iter().map(|i| i.split).collect();
+        assert_eq!(image_splits, [DatasetSplit::Train, DatasetSplit::Test]);
+        assert_eq!(stats.labels_without_images.len(), 1);
+        assert_eq!(stats.labels_without_images[0].split, DatasetSplit::Val);
+        assert_eq!(stats.files_scanned, 3);
+        assert!(stats.duplicates_across_splits.is_empty());
+    }
+
+    #[test]
+    fn test_integrity_flags_empty_and_whitespace_labels() {
+        let tmp = TempDir::new("yolo_integrity_empty_labels_test");
+        let root = tmp.path().to_path_buf();
+        let images = root.join("train").join("images");
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&images).unwrap();
+        fs::create_dir_all(&labels).unwrap();
+        for stem in ["zero", "blank", "background", "player"] {
+            fs::write(images.join(format!("{}.png", stem)), b"").unwrap();
+        }
+        fs::write(labels.join("zero.txt"), b"").unwrap();
+        fs::write(labels.join("blank.txt"), " \n\t\r\n").unwrap();
+        fs::write(labels.join("background.txt"), "# Map: de_mirage\n").unwrap();
+        fs::write(labels.join("player.txt"), "0 0.5 0.5 0.1 0.1\n").unwrap();
+
+        let stats = analyze_dataset_integrity(&root, DatasetSplit::Train, None, None);
+
+        let mut empty: Vec<_> = stats
+            .empty_label_files
+            .iter()
+            .map(|issue| issue.path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        empty.sort();
+        assert_eq!(empty, ["blank.txt", "zero.txt"]);
+        assert_eq!(stats.empty_label_files[0].issue_type, IntegrityIssueType::EmptyLabel);
+        // Empty labels are valid backgrounds, so they are reported but not counted
+        assert_eq!(stats.total_issues(), 0);
+    }
+
+    #[test]
+    fn test_integrity_flags_overlapping_boxes() {
+        let tmp = TempDir::new("yolo_integrity_overlap_test");
+        let root = tmp.path().to_path_buf();
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&labels).unwrap();
+        fs::write(
+            labels.join("double.txt"),
+            "0 0.1 0.1 0.05 0.05\n0 0.5 0.5 0.2 0.2\n0 0.5 0.5 0.2 0.19\n",
+        )
+        .unwrap();
+        fs::write(labels.join("apart.txt"), "0 0.5 0.5 0.2 0.2\n1 0.6 0.5 0.2 0.2\n").unwrap();
+
+        let stats = analyze_dataset_integrity(&root, DatasetSplit::Train, None, Some(DEFAULT_OVERLAP_IOU_THRESHOLD));
+        assert_eq!(stats.overlapping_boxes.len(), 1);
+        let issue = &stats.overlapping_boxes[0];
+        assert!(issue.path.ends_with("double.txt"));
+        assert_eq!((issue.pairs[0].first, issue.pairs[0].second), (1, 2));
+        assert!(issue.pairs[0].iou > 0.9);
+        // Counted on top of the two labels without images
+        assert_eq!(stats.total_issues(), 3);
+
+        let unchecked = analyze_dataset_integrity(&root, DatasetSplit::Train, None, None);
+        assert!(unchecked.overlapping_boxes.is_empty());
+    }
+
+    #[test]
+    fn test_integrity_flags_out_of_range_and_malformed_labels() {
+        let tmp = TempDir::new("yolo_integrity_out_of_range_test");
+        let root = tmp.path().to_path_buf();
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&labels).unwrap();
+        fs::write(
+            labels.join("edge.txt"),
+            "0 0.5 0.5 0.2 0.2\n0 0.98 0.5 0.1 0.1\n1 0.5 1.2 0.1 0.1\n",
+        )
+        .unwrap();
+        fs::write(labels.join("fine.txt"), "0 0.95 0.5 0.1 0.1\n").unwrap();
+        fs::write(labels.join("truncated.txt"), "0 0.5 0.5 0.1 0.1\n1 0.5\n").unwrap();
+
+        let stats = analyze_dataset_integrity(&root, DatasetSplit::Train, None, None);
+        assert_eq!(stats.out_of_range_boxes.len(), 1);
+        assert!(stats.out_of_range_boxes[0].path.ends_with("edge.txt"));
+        assert_eq!(stats.out_of_range_boxes[0].detections, 2);
+
+        assert_eq!(stats.malformed_labels.len(), 1);
+        assert!(stats.malformed_labels[0].path.ends_with("truncated.txt"));
+        assert_eq!(
+            stats.malformed_labels[0].error,
+            LabelParseError::MalformedLine {
+                line_number: 2,
+                content: "1 0.5".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_find_duplicates_across_splits_compares_content() {
+        let tmp = TempDir::new("yolo_integrity_duplicates_test");
+        let root = tmp.path().to_path_buf();
+        for split in DatasetSplit::all() {
+            fs::create_dir_all(root.join(split.as_str()).join("images")).unwrap();
+        }
+        let images = |split: &str| root.join(split).join("images");
+        fs::write(images("train").join("same.png"), b"frame").unwrap();
+        fs::write(images("val").join("same.png"), b"frame").unwrap();
+        fs::write(images("train").join("changed.png"), b"one").unwrap();
+        fs::write(images("test").join("changed.jpg"), b"two").unwrap();
+        fs::write(images("val").join("unique.png"), b"x").unwrap();
+
+        let duplicates = find_duplicates_across_splits(&root, true);
+        assert_eq!(duplicates.len(), 2);
+
+        assert_eq!(duplicates[0].stem, "changed");
+        assert_eq!(duplicates[0].content, DuplicateContent::Different);
+        assert_eq!(
+            duplicates[0].issue_type,
+            IntegrityIssueType::DuplicateAcrossSplits {
+                splits: vec![DatasetSplit::Train, DatasetSplit::Test]
+            }
+        );
+
+        assert_eq!(duplicates[1].stem, "same");
+        assert_eq!(duplicates[1].content, DuplicateContent::Identical);
+        assert_eq!(duplicates[1].description(), "train, val: same name, same content");
+
+        let unhashed = find_duplicates_across_splits(&root, false);
+        assert_eq!(unhashed[1].content, DuplicateContent::NotCompared);
+    }
 }
@@ -2,18 +2,23 @@ mod balance_analyzer;
 mod rebalancer;
 
 pub use balance_analyzer::{
-    analyze_dataset, analyze_dataset_with_progress, categorize_image, get_recommendations,
+    analyze_dataset, analyze_dataset_with_progress, categorize_image, categorize_label,
+    get_recommendations,
     BalanceProgressMessage, BalanceStats, ImageCategory, TargetRatios,
     // Integrity analysis exports
+    analyze_all_splits_integrity_with_progress,
     analyze_dataset_integrity, analyze_dataset_integrity_with_progress,
-    IntegrityIssue, IntegrityIssueType, IntegrityProgressMessage, IntegrityStats,
+    DuplicateContent, IntegrityIssue, IntegrityIssueType, IntegrityProgressMessage, IntegrityStats,
+    DEFAULT_OVERLAP_IOU_THRESHOLD,
+    GOOD_DISTRIBUTION_ENTROPY, MAX_DISTRIBUTION_ENTROPY,
 };
 
 pub use rebalancer::{
-    calculate_move_count, calculate_rebalance_plan, collect_image_metadata,
-    execute_rebalance_plan, find_best_destination_split, undo_rebalance,
-    analyze_all_splits, calculate_global_rebalance_plan, execute_global_rebalance_plan,
-    ImageMetadata, MoveAction, MoveResult, RebalanceConfig, RebalancePlan, 
-    RebalanceProgressMessage, SelectionStrategy, SplitRatios,
-    GlobalBalanceStats, GlobalMoveAction, GlobalRebalancePlan, GlobalRebalanceConfig,
+    calculate_ct_t_fix, calculate_move_count, calculate_rebalance_plan,
+    choose_destination_split, execute_rebalance_plan, undo_rebalance,
+    analyze_all_splits_with_progress, calculate_global_rebalance_plan, execute_global_rebalance_plan,
+    execute_split_swap, plan_split_swap, SWAP_STAGING_DIR,
+    CollisionPolicy, MoveAction, MoveResult, RebalanceConfig, RebalancePlan, 
+    AllSplitsProgressMessage, RebalanceProgressMessage, SelectionStrategy, SplitRatios,
+    GlobalBalanceStats, GlobalRebalancePlan, GlobalRebalanceConfig,
 };
@@ -3,20 +3,25 @@
 //! This module provides functionality to analyze and rebalance dataset splits
 //! by moving images from over-represented categories to other splits.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
-    mpsc::Sender,
+    mpsc::{channel, Sender},
     Arc,
 };
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
-use crate::core::dataset::{parse_label_file, DatasetSplit};
-use crate::core::operations::{get_label_path_for_image, move_file};
+use crate::core::dataset::{
+    images_dir, labels_dir, max_pairwise_iou, parse_label_file, split_exists, DatasetSplit,
+};
+use crate::core::operations::{move_file, MoveMethod};
 
-use super::{categorize_image, BalanceStats, ImageCategory, TargetRatios};
+use super::{
+    analyze_dataset_with_progress, categorize_image, BalanceProgressMessage, BalanceStats,
+    ImageCategory, TargetRatios,
+};
 
 /// Strategy for selecting which images to move
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
@@ -30,6 +35,11 @@ pub enum SelectionStrategy {
     OldestFirst,
     /// Select newest files first
     NewestFirst,
+    /// Sample every map location in proportion to its share of the candidates
+    /// (random within each location)
+    LocationStratified,
+    /// Select images whose boxes overlap the most first (highest pairwise IoU)
+    HighestOverlap,
 }
 
 impl SelectionStrategy {
@@ -39,6 +49,8 @@ impl SelectionStrategy {
             SelectionStrategy::FewestDetections => "Fewest Detections",
             SelectionStrategy::OldestFirst => "Oldest First",
             SelectionStrategy::NewestFirst => "Newest First",
+            SelectionStrategy::LocationStratified => "Location Stratified",
+            SelectionStrategy::HighestOverlap => "Highest Overlap",
         }
     }
 
@@ -48,8 +60,26 @@ impl SelectionStrategy {
             SelectionStrategy::FewestDetections,
             SelectionStrategy::OldestFirst,
             SelectionStrategy::NewestFirst,
+            SelectionStrategy::LocationStratified,
+            SelectionStrategy::HighestOverlap,
         ]
     }
+
+    /// Order two candidates so the images this strategy moves first come first
+    ///
+    /// Random and location-stratified selection have no order of their own
+    /// (`Equal`); callers shuffle or keep their order.
+    pub fn compare(&self, a: &ImageMetadata, b: &ImageMetadata) -> std::cmp::Ordering {
+        match self {
+            SelectionStrategy::Random | SelectionStrategy::LocationStratified => {
+                std::cmp::Ordering::Equal
+            }
+            SelectionStrategy::FewestDetections => a.detection_count.cmp(&b.detection_count),
+            SelectionStrategy::OldestFirst => a.path.cmp(&b.path),
+            SelectionStrategy::NewestFirst => b.path.cmp(&a.path),
+            SelectionStrategy::HighestOverlap => b.max_overlap.total_cmp(&a.max_overlap),
+        }
+    }
 }
 
 /// A single move action in a rebalance plan
@@ -67,16 +97,53 @@ pub struct MoveAction {
     pub to_split: DatasetSplit,
 }
 
+impl MoveAction {
+    /// The move that brings the files back from where this move put them
+    pub fn reversed(&self, image_path: &Path, label_path: Option<&Path>) -> MoveAction {
+        MoveAction {
+            image_path: image_path.to_path_buf(),
+            label_path: label_path.map(Path::to_path_buf),
+            category: self.category,
+            from_split: self.to_split,
+            to_split: self.from_split,
+        }
+    }
+}
+
 /// Result of a single move operation
 #[derive(Debug, Clone)]
 pub struct MoveResult {
     pub action: MoveAction,
+    /// Exact inverse of a successful move: the files at their new paths, back to
+    /// `action`'s paths (None for failed moves and for undo results)
+    pub undo_action: Option<MoveAction>,
     pub success: bool,
     pub error: Option<String>,
     /// New image path after move
     pub new_image_path: Option<PathBuf>,
     /// New label path after move
     pub new_label_path: Option<PathBuf>,
+    /// Image or label was copied + deleted because the destination is on another device
+    pub copied: bool,
+}
+
+/// What to do when a file name is already taken in the destination split
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollisionPolicy {
+    /// Leave the image in place and record the collision as a failed move
+    #[default]
+    Skip,
+    /// Move under the first free name with a numeric suffix (`frame_1.png`)
+    Rename,
+}
+
+impl CollisionPolicy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CollisionPolicy::Skip => "Skip",
+            CollisionPolicy::Rename => "Rename with suffix",
+        }
+    }
 }
 
 /// A complete rebalance plan
@@ -96,6 +163,12 @@ pub struct RebalancePlan {
     pub current_stats: Option<BalanceStats>,
     /// Projected stats after rebalance
     pub projected_stats: Option<BalanceStats>,
+    /// How to move images whose name is already taken in the destination
+    pub collision_policy: CollisionPolicy,
+    /// Images whose name (or label name) already exists in the destination
+    pub collisions: Vec<PathBuf>,
+    /// Imbalance score of the source split (before, after), see `estimate_post_rebalance_quality`
+    pub quality: Option<(f32, f32)>,
 }
 
 impl RebalancePlan {
@@ -110,6 +183,146 @@ impl RebalancePlan {
     pub fn len(&self) -> usize {
         self.actions.len()
     }
+
+    /// Rows of (source, destination, description) for the preview dialog
+    pub fn preview_as_table(&self) -> Vec<(String, String, String)> {
+        let split_name = |split: Option<DatasetSplit>| {
+            split
+                .map(|s| s.as_str().to_uppercase())
+                .unwrap_or_else(|| "?".to_string())
+        };
+        let category = self.category.as_ref().map(|c| c.as_str()).unwrap_or("filtered");
+        vec![(
+            split_name(self.from_split),
+            split_name(self.to_split),
+            format!("{} {} images", self.len(), category),
+        )]
+    }
+
+    /// Number of actions whose image no longer exists (moved or deleted since planning)
+    pub fn stale_count(&self) -> usize {
+        self.actions.iter().filter(|action| !action.image_path.exists()).count()
+    }
+
+    /// Drop the actions whose image no longer exists and return how many were dropped
+    ///
+    /// The dropped images are gone from the source split, so they are taken
+    /// out of the current stats; the projected source stats already exclude them.
+    pub fn drop_stale_actions(&mut self) -> usize {
+        let (fresh, stale): (Vec<_>, Vec<_>) =
+            self.actions.drain(..).partition(|action| action.image_path.exists());
+        self.actions = fresh;
+        self.collisions.retain(|path| path.exists());
+        self.count_to_move = self.count_to_move.saturating_sub(stale.len());
+        if let Some(current) = self.current_stats.as_mut() {
+            for action in &stale {
+                remove_from_category(current, action.category);
+            }
+        }
+        if !stale.is_empty() {
+            info!("Dropped {} stale actions from the rebalance plan", stale.len());
+        }
+        stale.len()
+    }
+
+    /// Record which actions would land on a name already taken in the destination
+    pub fn flag_collisions(&mut self, dataset_path: &Path) {
+        self.collisions = match self.to_split {
+            Some(to_split) => {
+                let dest_images = images_dir(dataset_path, to_split);
+                let dest_labels = labels_dir(dataset_path, to_split);
+                self.actions
+                    .iter()
+                    .filter(|action| destination_taken(action, &dest_images, &dest_labels))
+                    .map(|action| action.image_path.clone())
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        if !self.collisions.is_empty() {
+            warn!(
+                "{} images of the plan collide with existing names in the destination",
+                self.collisions.len()
+            );
+        }
+    }
+}
+
+/// Take one image of `category` out of `stats`
+fn remove_from_category(stats: &mut BalanceStats, category: ImageCategory) {
+    let count = match category {
+        ImageCategory::CTOnly => &mut stats.ct_only,
+        ImageCategory::TOnly => &mut stats.t_only,
+        ImageCategory::MultiplePlayer => &mut stats.multiple_player,
+        ImageCategory::Background => &mut stats.background,
+        ImageCategory::HardCase => &mut stats.hard_case,
+        ImageCategory::Unknown => &mut stats.unknown,
+    };
+    *count = count.saturating_sub(1);
+    stats.total_images = stats.total_images.saturating_sub(1);
+}
+
+/// Whether the image or label of `action` would overwrite a file in the destination
+fn destination_taken(action: &MoveAction, dest_images: &Path, dest_labels: &Path) -> bool {
+    let image_taken = action
+        .image_path
+        .file_name()
+        .is_some_and(|name| dest_images.join(name).exists());
+    let label_taken = action
+        .label_path
+        .as_ref()
+        .and_then(|label| label.file_name())
+        .is_some_and(|name| dest_labels.join(name).exists());
+    image_taken || label_taken
+}
+
+/// Pick the destination image and label paths of a move under `policy`
+///
+/// With `Rename`, image and label get the same numeric suffix so they stay a
+/// pair. Returns an error message when the move is skipped.
+fn resolve_destination(
+    action: &MoveAction,
+    dest_images: &Path,
+    dest_labels: &Path,
+    policy: CollisionPolicy,
+) -> Result<(PathBuf, Option<PathBuf>), String> {
+    let image_name = action.image_path.file_name().unwrap_or_default();
+    let label_name = action.label_path.as_ref().and_then(|label| label.file_name());
+    let image_path = dest_images.join(image_name);
+    let label_path = label_name.map(|name| dest_labels.join(name));
+
+    if !destination_taken(action, dest_images, dest_labels) {
+        return Ok((image_path, label_path));
+    }
+    if policy == CollisionPolicy::Skip {
+        return Err(format!(
+            "{} already exists in the destination",
+            image_name.to_string_lossy()
+        ));
+    }
+
+    let stem = action
+        .image_path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let with_suffix = |path: &Path, n: usize| {
+        let name = match path.extension() {
+            Some(ext) => format!("{}_{}.{}", stem, n, ext.to_string_lossy()),
+            None => format!("{}_{}", stem, n),
+        };
+        path.with_file_name(name)
+    };
+    (1..)
+        .map(|n| {
+            (
+                with_suffix(&image_path, n),
+                label_path.as_deref().map(|label| with_suffix(label, n)),
+            )
+        })
+        .find(|(image, label)| !image.exists() && !label.as_ref().is_some_and(|l| l.exists()))
+        .ok_or_else(|| "No free name in the destination".to_string())
 }
 
 /// Configuration for rebalancing
@@ -127,6 +340,15 @@ pub struct RebalanceConfig {
     pub destination_split: DatasetSplit,
     /// Category to rebalance
     pub category: ImageCategory,
+    /// Exact number of images to move (None = excess over the target ratios)
+    pub move_count: Option<usize>,
+    /// Target CT share of the source split's single-team images when
+    /// `preserve_ct_t_balance` is set (0.50 = 50% CT, 50% T)
+    pub target_ct_t_ratio: f32,
+    /// How to move images whose name is already taken in the destination
+    pub collision_policy: CollisionPolicy,
+    /// Class IDs of the class table, used to tell hard cases from unknown classes
+    pub known_class_ids: Vec<u32>,
 }
 
 impl Default for RebalanceConfig {
@@ -138,10 +360,91 @@ impl Default for RebalanceConfig {
             source_split: DatasetSplit::Train,
             destination_split: DatasetSplit::Val,
             category: ImageCategory::Background,
+            move_count: None,
+            target_ct_t_ratio: 0.50,
+            collision_policy: CollisionPolicy::default(),
+            known_class_ids: vec![0, 1],
         }
     }
 }
 
+/// Take `count` images so that each location is sampled in proportion to its
+/// share of the candidates
+///
+/// Each location's quota is `count * location_total / candidates.len()`; the
+/// images left over by rounding go to the locations with the largest
+/// remainders. Within a location images are taken in their current order, so
+/// the caller's ordering (e.g. a shuffle) is the inner selection strategy.
+/// Images without location metadata form their own group.
+pub fn select_location_stratified(
+    candidates: Vec<ImageMetadata>,
+    count: usize,
+) -> Vec<ImageMetadata> {
+    let total = candidates.len();
+    if count >= total {
+        return candidates;
+    }
+
+    // Group by location, keeping groups in order of first appearance
+    let mut groups: Vec<(Option<String>, Vec<ImageMetadata>)> = Vec::new();
+    for m in candidates {
+        match groups.iter_mut().find(|(loc, _)| *loc == m.location) {
+            Some((_, images)) => images.push(m),
+            None => groups.push((m.location.clone(), vec![m])),
+        }
+    }
+
+    let mut quotas: Vec<usize> = groups
+        .iter()
+        .map(|(_, images)| count * images.len() / total)
+        .collect();
+    let mut by_remainder: Vec<usize> = (0..groups.len()).collect();
+    by_remainder.sort_by_key(|&i| std::cmp::Reverse(count * groups[i].1.len() % total));
+    let leftover = count - quotas.iter().sum::<usize>();
+    for &i in by_remainder.iter().take(leftover) {
+        quotas[i] += 1;
+    }
+
+    groups
+        .into_iter()
+        .zip(quotas)
+        .flat_map(|((_, images), quota)| images.into_iter().take(quota))
+        .collect()
+}
+
+/// Decide how many CT-only, T-only and multi-player images to move
+///
+/// Multi-player images take their proportional share of `count`; the rest is
+/// divided so the remaining single-team images hold `target_ct_ratio` CT, as
+/// far as the available counts allow. Returns `(ct, t, multi)`.
+pub fn split_player_move_count(
+    ct: usize,
+    t: usize,
+    multi: usize,
+    count: usize,
+    target_ct_ratio: f32,
+) -> (usize, usize, usize) {
+    let total = ct + t + multi;
+    let count = count.min(total);
+    if count == 0 {
+        return (0, 0, 0);
+    }
+
+    let singles = ct + t;
+    let mut multi_moves = ((count * multi) as f32 / total as f32).round() as usize;
+    // Keep the singles share within what is available
+    multi_moves = multi_moves.clamp(count.saturating_sub(singles), multi.min(count));
+    let single_moves = count - multi_moves;
+
+    let remaining = singles - single_moves;
+    let desired_ct_remaining = (remaining as f32 * target_ct_ratio.clamp(0.0, 1.0)).round() as usize;
+    let ct_moves = ct
+        .saturating_sub(desired_ct_remaining)
+        .clamp(single_moves.saturating_sub(t), ct.min(single_moves));
+
+    (ct_moves, single_moves - ct_moves, multi_moves)
+}
+
 /// Progress message for rebalance execution
 #[derive(Debug, Clone)]
 pub enum RebalanceProgressMessage {
@@ -172,14 +475,39 @@ pub struct GlobalBalanceStats {
     pub train: BalanceStats,
     pub val: BalanceStats,
     pub test: BalanceStats,
+    /// Splits without an images folder (their stats are all zero)
+    pub missing: Vec<DatasetSplit>,
 }
 
 impl GlobalBalanceStats {
+    /// Record which splits have no images folder under `dataset_path`
+    pub fn with_missing_splits(mut self, dataset_path: &Path) -> Self {
+        self.missing = DatasetSplit::all()
+            .into_iter()
+            .filter(|&split| !split_exists(dataset_path, split))
+            .collect();
+        self
+    }
+
+    /// Whether `split` has an images folder
+    pub fn is_present(&self, split: DatasetSplit) -> bool {
+        !self.missing.contains(&split)
+    }
+
     /// Get total images across all splits
     pub fn total_images(&self) -> usize {
         self.train.total_images + self.val.total_images + self.test.total_images
     }
 
+    /// Stats of all splits added together
+    pub fn combined(&self) -> BalanceStats {
+        let mut combined = BalanceStats::new();
+        for split in DatasetSplit::all() {
+            combined.merge(self.get(split));
+        }
+        combined
+    }
+
     /// Get stats for a specific split
     pub fn get(&self, split: DatasetSplit) -> &BalanceStats {
         match split {
@@ -229,6 +557,53 @@ pub struct GlobalMoveAction {
     pub actions: Vec<MoveAction>,
 }
 
+/// Why a global rebalance plan stopped short of its targets
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanWarning {
+    /// A split is below its target size but no split has images to spare
+    NoExcessAvailable { split: DatasetSplit, deficit: usize },
+    /// The source split ran out of movable images before the move was complete
+    CandidatesExhausted {
+        from_split: DatasetSplit,
+        to_split: DatasetSplit,
+        requested: usize,
+        planned: usize,
+    },
+    /// A location stayed imbalanced because moving it would break the split ratios
+    LocationUnresolved { location: String },
+    /// The iteration limit was reached while a split was still outside tolerance
+    IterationLimitReached { iterations: usize },
+}
+
+impl PlanWarning {
+    /// Human-readable description for the preview dialog
+    pub fn message(&self) -> String {
+        match self {
+            PlanWarning::NoExcessAvailable { split, deficit } => format!(
+                "{} needs {} more images but no split has excess images",
+                split.as_str().to_uppercase(),
+                deficit
+            ),
+            PlanWarning::CandidatesExhausted { from_split, to_split, requested, planned } => format!(
+                "Only {} of {} images could be planned from {} to {}: no more candidates in {}",
+                planned,
+                requested,
+                from_split.as_str().to_uppercase(),
+                to_split.as_str().to_uppercase(),
+                from_split.as_str().to_uppercase()
+            ),
+            PlanWarning::LocationUnresolved { location } => format!(
+                "Location '{}' imbalance could not be fixed without violating split ratios",
+                location
+            ),
+            PlanWarning::IterationLimitReached { iterations } => format!(
+                "Stopped after {} iterations with splits still outside tolerance",
+                iterations
+            ),
+        }
+    }
+}
+
 /// A complete global rebalance plan with moves across all splits
 #[derive(Debug, Clone, Default)]
 pub struct GlobalRebalancePlan {
@@ -242,6 +617,13 @@ pub struct GlobalRebalancePlan {
     pub total_moves: usize,
     /// Number of iterations used to calculate
     pub iterations_used: usize,
+    /// Targets the plan could not satisfy
+    pub warnings: Vec<PlanWarning>,
+    /// How to move images whose name is already taken in the destination
+    pub collision_policy: CollisionPolicy,
+    /// Images whose name is already taken in their destination, by an existing
+    /// file or by an earlier move of the plan
+    pub collisions: Vec<PathBuf>,
 }
 
 impl GlobalRebalancePlan {
@@ -257,6 +639,119 @@ impl GlobalRebalancePlan {
     pub fn all_actions(&self) -> Vec<&MoveAction> {
         self.moves.iter().flat_map(|m| m.actions.iter()).collect()
     }
+
+    /// Number of actions whose image no longer exists (moved or deleted since planning)
+    pub fn stale_count(&self) -> usize {
+        self.all_actions()
+            .iter()
+            .filter(|action| !action.image_path.exists())
+            .count()
+    }
+
+    /// Drop the actions whose image no longer exists and return how many were dropped
+    ///
+    /// The dropped images are gone from their source split and will not reach
+    /// the destination, so both the current and the projected stats lose them.
+    /// Move groups left without actions are removed.
+    pub fn drop_stale_actions(&mut self) -> usize {
+        let mut dropped = 0;
+        for group in &mut self.moves {
+            let (fresh, stale): (Vec<_>, Vec<_>) =
+                group.actions.drain(..).partition(|action| action.image_path.exists());
+            group.actions = fresh;
+            group.count = group.actions.len();
+            dropped += stale.len();
+            for action in &stale {
+                if let Some(current) = self.current_stats.as_mut() {
+                    remove_from_category(current.get_mut(group.from_split), action.category);
+                }
+                if let Some(projected) = self.projected_stats.as_mut() {
+                    remove_from_category(projected.get_mut(group.to_split), action.category);
+                }
+            }
+        }
+        self.moves.retain(|group| !group.actions.is_empty());
+        self.collisions.retain(|path| path.exists());
+        self.total_moves = self.total_moves.saturating_sub(dropped);
+        if dropped > 0 {
+            info!("Dropped {} stale actions from the global rebalance plan", dropped);
+        }
+        dropped
+    }
+
+    /// Record which actions would land on a name already taken in their destination
+    ///
+    /// Two splits can hold the same file name, so an image moving into a split
+    /// also collides with one moved there earlier in the same plan.
+    pub fn flag_collisions(&mut self, dataset_path: &Path) {
+        let mut claimed: HashSet<PathBuf> = HashSet::new();
+        let mut collisions = Vec::new();
+        for group in &self.moves {
+            let dest_images = images_dir(dataset_path, group.to_split);
+            let dest_labels = labels_dir(dataset_path, group.to_split);
+            for action in &group.actions {
+                let taken = destination_taken(action, &dest_images, &dest_labels);
+                let claimed_earlier = action
+                    .image_path
+                    .file_name()
+                    .is_some_and(|name| !claimed.insert(dest_images.join(name)));
+                if taken || claimed_earlier {
+                    collisions.push(action.image_path.clone());
+                }
+            }
+        }
+        self.collisions = collisions;
+        if !self.collisions.is_empty() {
+            warn!(
+                "{} images of the global plan collide with names in their destination",
+                self.collisions.len()
+            );
+        }
+    }
+
+    /// Net change in image count per split: +1 per move arriving, -1 per move departing
+    ///
+    /// Every split is present, with 0 when the plan leaves its size unchanged.
+    pub fn split_deltas(&self) -> HashMap<DatasetSplit, i32> {
+        let mut deltas: HashMap<DatasetSplit, i32> =
+            DatasetSplit::all().into_iter().map(|split| (split, 0)).collect();
+        for group in &self.moves {
+            *deltas.entry(group.from_split).or_default() -= group.count as i32;
+            *deltas.entry(group.to_split).or_default() += group.count as i32;
+        }
+        deltas
+    }
+
+    /// Splits that would lose more than `max_loss` (0.20 = 20%) of their current images
+    pub fn shrinking_splits(&self, max_loss: f32) -> Vec<DatasetSplit> {
+        let Some(current) = &self.current_stats else {
+            return Vec::new();
+        };
+        let deltas = self.split_deltas();
+        DatasetSplit::all()
+            .into_iter()
+            .filter(|split| {
+                let size = current.get(*split).total_images;
+                let loss = -deltas[split];
+                size > 0 && loss as f32 > size as f32 * max_loss
+            })
+            .collect()
+    }
+
+    /// Rows of (source, destination, description) for the preview dialog,
+    /// one per move group
+    pub fn preview_as_table(&self) -> Vec<(String, String, String)> {
+        self.moves
+            .iter()
+            .map(|group| {
+                (
+                    group.from_split.as_str().to_uppercase(),
+                    group.to_split.as_str().to_uppercase(),
+                    format!("{} {} images", group.count, group.category.as_str()),
+                )
+            })
+            .collect()
+    }
 }
 
 /// Target ratios for train/val/test split distribution
@@ -286,6 +781,43 @@ impl SplitRatios {
             DatasetSplit::Test => self.test,
         }
     }
+
+    /// Ratios with `missing` splits at zero and the others scaled to sum to one
+    pub fn renormalized(&self, missing: &[DatasetSplit]) -> SplitRatios {
+        let ratio = |split: DatasetSplit| {
+            if missing.contains(&split) {
+                0.0
+            } else {
+                self.get(split)
+            }
+        };
+        let present: f32 = DatasetSplit::all().into_iter().map(ratio).sum();
+        if present <= 0.0 {
+            return self.clone();
+        }
+        SplitRatios {
+            train: ratio(DatasetSplit::Train) / present,
+            val: ratio(DatasetSplit::Val) / present,
+            test: ratio(DatasetSplit::Test) / present,
+        }
+    }
+
+    /// Scale the ratios so they sum to one (left unchanged if they sum to zero)
+    pub fn normalize(&mut self) {
+        let sum = self.train + self.val + self.test;
+        if sum <= 0.0 {
+            return;
+        }
+        self.train /= sum;
+        self.val /= sum;
+        self.test /= sum;
+    }
+
+    /// Whether no ratio is negative and they sum to one (within 0.001)
+    pub fn is_valid(&self) -> bool {
+        let ratios = [self.train, self.val, self.test];
+        ratios.iter().all(|&ratio| ratio >= 0.0) && (ratios.iter().sum::<f32>() - 1.0).abs() <= 0.001
+    }
 }
 
 /// Configuration for global rebalancing
@@ -302,6 +834,40 @@ pub struct GlobalRebalanceConfig {
     pub max_iterations: usize,
     /// Whether to balance locations when moving images
     pub balance_locations: bool,
+    /// Whether moves may create a split that has no images folder yet
+    ///
+    /// When false, missing splits get no images and the split ratios are
+    /// spread over the existing splits.
+    pub create_missing_splits: bool,
+    /// Whether a split folder without images is filled to its share
+    ///
+    /// When true, the empty split's target is its full share of the dataset
+    /// and it is filled from the largest split. When false, empty splits are
+    /// left alone and their ratio is spread over the others.
+    pub balance_empty_splits: bool,
+    /// How to move images whose name is already taken in the destination
+    pub collision_policy: CollisionPolicy,
+    /// Class IDs of the class table, used to tell hard cases from unknown classes
+    pub known_class_ids: Vec<u32>,
+}
+
+impl GlobalRebalanceConfig {
+    /// Split ratios to plan with for a dataset with `stats`
+    pub fn effective_split_ratios(&self, stats: &GlobalBalanceStats) -> SplitRatios {
+        let mut skipped = if self.create_missing_splits {
+            Vec::new()
+        } else {
+            stats.missing.clone()
+        };
+        if !self.balance_empty_splits {
+            skipped.extend(
+                DatasetSplit::all()
+                    .into_iter()
+                    .filter(|&split| stats.is_present(split) && stats.get(split).total_images == 0),
+            );
+        }
+        self.split_ratios.renormalized(&skipped)
+    }
 }
 
 impl Default for GlobalRebalanceConfig {
@@ -314,6 +880,10 @@ impl Default for GlobalRebalanceConfig {
             tolerance: 0.02, // 2% tolerance
             max_iterations: 10,
             balance_locations: true,
+            create_missing_splits: false,
+            balance_empty_splits: true,
+            collision_policy: CollisionPolicy::default(),
+            known_class_ids: vec![0, 1],
         }
     }
 }
@@ -326,15 +896,18 @@ pub struct ImageMetadata {
     pub detection_count: usize,
     /// Location from label metadata (e.g., "TSpawn", "LongDoors")
     pub location: Option<String>,
+    /// Largest IoU between two of the image's detections
+    pub max_overlap: f32,
 }
 
 /// Collect metadata for all images in a split
 pub fn collect_image_metadata(
-    dataset_path: &PathBuf,
+    dataset_path: &Path,
     split: DatasetSplit,
+    known_class_ids: &[u32],
 ) -> Vec<ImageMetadata> {
-    let images_path = dataset_path.join(split.as_str()).join("images");
-    let labels_path = dataset_path.join(split.as_str()).join("labels");
+    let images_path = images_dir(dataset_path, split);
+    let labels_path = labels_dir(dataset_path, split);
 
     let mut metadata = Vec::new();
 
@@ -347,13 +920,17 @@ pub fn collect_image_metadata(
                     // Get label path and categorize
                     if let Some(stem) = image_path.file_stem() {
                         let label_path = labels_path.join(format!("{}.txt", stem.to_string_lossy()));
-                        let category = categorize_image(&label_path);
+                        let category = categorize_image(&label_path, known_class_ids);
                         
-                        // Parse label to get detection count and location
-                        let (detection_count, location) = if let Some(label_info) = parse_label_file(&label_path) {
-                            (label_info.detections.len(), label_info.location)
+                        // Parse label to get detection count, location and overlap
+                        let (detection_count, location, max_overlap) = if let Some(label_info) = parse_label_file(&label_path) {
+                            (
+                                label_info.detections.len(),
+                                label_info.location,
+                                max_pairwise_iou(&label_info.detections),
+                            )
                         } else {
-                            (0, None)
+                            (0, None, 0.0)
                         };
 
                         metadata.push(ImageMetadata {
@@ -361,6 +938,7 @@ pub fn collect_image_metadata(
                             category,
                             detection_count,
                             location,
+                            max_overlap,
                         });
                     }
                 }
@@ -399,69 +977,240 @@ pub fn calculate_move_count(
             let target = total * target_ratios.hardcase_ratio;
             (current - target).round() as i32
         }
+        // Unknown-class images need relabeling, not redistribution
+        ImageCategory::Unknown => 0,
+    }
+}
+
+/// Calculate the move that brings the CT/T ratio of a split back to 50/50.
+///
+/// Returns the over-represented single-team category and how many of its images
+/// to move out, or None when the split is already balanced.
+pub fn calculate_ct_t_fix(stats: &BalanceStats) -> Option<(ImageCategory, usize)> {
+    if stats.ct_only > stats.t_only {
+        Some((ImageCategory::CTOnly, stats.ct_only - stats.t_only))
+    } else if stats.t_only > stats.ct_only {
+        Some((ImageCategory::TOnly, stats.t_only - stats.ct_only))
+    } else {
+        None
     }
 }
 
 /// Find the best destination split for moving excess images of a category.
-/// Returns the split that needs the most images of that category, along with how many it needs.
+/// Returns the chosen split along with how many images it should receive.
+///
+/// Scans every split on each call; callers that already have the stats of
+/// all splits should use [`choose_destination_split`] directly.
+// Kept for path-based callers; the app picks destinations from cached stats
+#[allow(dead_code)]
 pub fn find_best_destination_split(
-    dataset_path: &PathBuf,
+    dataset_path: &Path,
     source_split: DatasetSplit,
     category: ImageCategory,
     target_ratios: &TargetRatios,
+    split_ratios: &SplitRatios,
+    known_class_ids: &[u32],
 ) -> Option<(DatasetSplit, i32)> {
-    use super::analyze_dataset;
-    
-    let other_splits: Vec<DatasetSplit> = [DatasetSplit::Train, DatasetSplit::Val, DatasetSplit::Test]
-        .into_iter()
-        .filter(|s| *s != source_split)
-        .collect();
+    let stats = analyze_all_splits(dataset_path, known_class_ids);
+    choose_destination_split(&stats, source_split, category, target_ratios, split_ratios)
+}
+
+/// Pick the destination for excess `category` images of `source_split`.
+///
+/// A split is a candidate when it needs more of the category. The suggested
+/// count is capped so the destination does not grow past its target share of
+/// all images; splits already at or above their share are skipped. Candidates
+/// whose move improves both the category balance and the split sizes win,
+/// then the largest combined improvement. Ties keep the earlier split
+/// (train, val, test order).
+pub fn choose_destination_split(
+    stats: &GlobalBalanceStats,
+    source_split: DatasetSplit,
+    category: ImageCategory,
+    target_ratios: &TargetRatios,
+    split_ratios: &SplitRatios,
+) -> Option<(DatasetSplit, i32)> {
+    let total = stats.total_images();
+    if total == 0 {
+        return None;
+    }
+    let current_size_deviation = calculate_split_size_deviation(stats, split_ratios);
 
-    let mut best_split: Option<(DatasetSplit, i32)> = None;
+    // (split, count, improves both, combined improvement)
+    let mut best: Option<(DatasetSplit, i32, bool, f32)> = None;
+
+    for split in DatasetSplit::all().into_iter().filter(|s| *s != source_split) {
+        let dest = stats.get(split);
 
-    for split in other_splits {
-        let stats = analyze_dataset(dataset_path, split);
-        let excess = calculate_move_count(&stats, category, target_ratios);
-        
         // Negative excess means this split needs MORE images
-        // We want to find the split with the most negative excess (needs the most)
-        if excess < 0 {
-            let needed = -excess; // Convert to positive "needed" count
-            if let Some((_, current_best)) = best_split {
-                if needed > current_best {
-                    best_split = Some((split, needed));
-                }
-            } else {
-                best_split = Some((split, needed));
+        let needed = -calculate_move_count(dest, category, target_ratios);
+        if needed <= 0 {
+            continue;
+        }
+
+        let target_size = (total as f32 * split_ratios.get(split)).floor() as i32;
+        let capacity = target_size - dest.total_images as i32;
+        if capacity <= 0 {
+            debug!(
+                "{} is at its target share ({} / {}), skipping as destination",
+                split.as_str(),
+                dest.total_images,
+                target_size
+            );
+            continue;
+        }
+        let count = needed.min(capacity);
+
+        let category_improvement = calculate_balance_improvement(
+            stats,
+            source_split,
+            split,
+            category,
+            count as usize,
+            target_ratios,
+        );
+        let mut moved = stats.clone();
+        moved.get_mut(source_split).total_images =
+            moved.get(source_split).total_images.saturating_sub(count as usize);
+        moved.get_mut(split).total_images += count as usize;
+        let size_improvement =
+            current_size_deviation - calculate_split_size_deviation(&moved, split_ratios);
+
+        let improves_both = category_improvement > 0.0 && size_improvement > 0.0;
+        let score = category_improvement + size_improvement;
+        let better = match best {
+            None => true,
+            Some((_, _, best_both, best_score)) => {
+                (improves_both, score) > (best_both, best_score)
             }
+        };
+        if better {
+            best = Some((split, count, improves_both, score));
         }
     }
 
-    best_split
+    best.map(|(split, count, _, _)| (split, count))
 }
 
 /// Analyze all splits and return combined statistics
-pub fn analyze_all_splits(dataset_path: &PathBuf) -> GlobalBalanceStats {
+pub fn analyze_all_splits(dataset_path: &Path, known_class_ids: &[u32]) -> GlobalBalanceStats {
     use super::analyze_dataset;
     
     GlobalBalanceStats {
-        train: analyze_dataset(dataset_path, DatasetSplit::Train),
-        val: analyze_dataset(dataset_path, DatasetSplit::Val),
-        test: analyze_dataset(dataset_path, DatasetSplit::Test),
+        train: analyze_dataset(dataset_path, DatasetSplit::Train, known_class_ids),
+        val: analyze_dataset(dataset_path, DatasetSplit::Val, known_class_ids),
+        test: analyze_dataset(dataset_path, DatasetSplit::Test, known_class_ids),
+        missing: Vec::new(),
+    }
+    .with_missing_splits(dataset_path)
+}
+
+/// Progress message for a sequential analysis of all splits
+#[derive(Debug, Clone)]
+pub enum AllSplitsProgressMessage {
+    /// Images analyzed so far in the split being scanned
+    SplitProgress {
+        split: DatasetSplit,
+        current: usize,
+        total: usize,
+    },
+    /// A split finished; the next one starts right after
+    SplitComplete {
+        split: DatasetSplit,
+        stats: BalanceStats,
+    },
+    AllComplete(GlobalBalanceStats),
+    /// Cancelled before every split finished (holds the splits done so far)
+    Cancelled(GlobalBalanceStats),
+}
+
+/// Analyze train, val and test one after another, reporting progress for
+/// each split as it is scanned
+pub fn analyze_all_splits_with_progress(
+    dataset_path: &Path,
+    known_class_ids: &[u32],
+    progress_tx: Sender<AllSplitsProgressMessage>,
+    cancel_flag: Arc<AtomicBool>,
+) -> GlobalBalanceStats {
+    let mut global = GlobalBalanceStats::default().with_missing_splits(dataset_path);
+
+    for split in DatasetSplit::all() {
+        // Scan on a scoped thread and relay its per-image progress as we go
+        let (split_tx, split_rx) = channel();
+        let outcome = std::thread::scope(|scope| {
+            let cancel_flag = cancel_flag.clone();
+            scope.spawn(move || {
+                analyze_dataset_with_progress(dataset_path, split, known_class_ids, Some(split_tx), Some(cancel_flag))
+            });
+
+            let mut outcome = None;
+            for message in split_rx {
+                match message {
+                    BalanceProgressMessage::Progress { current, total, .. } => {
+                        let _ = progress_tx.send(AllSplitsProgressMessage::SplitProgress {
+                            split,
+                            current,
+                            total,
+                        });
+                    }
+                    BalanceProgressMessage::Complete(stats) => outcome = Some(stats),
+                    BalanceProgressMessage::Cancelled(_) => outcome = None,
+                }
+            }
+            outcome
+        });
+
+        let Some(stats) = outcome.filter(|_| !cancel_flag.load(Ordering::Relaxed)) else {
+            warn!("All-splits analysis cancelled during {}", split.as_str());
+            let _ = progress_tx.send(AllSplitsProgressMessage::Cancelled(global.clone()));
+            return global;
+        };
+        *global.get_mut(split) = stats.clone();
+        let _ = progress_tx.send(AllSplitsProgressMessage::SplitComplete { split, stats });
     }
+
+    info!(
+        "All-splits analysis complete: {} total images",
+        global.total_images()
+    );
+    let _ = progress_tx.send(AllSplitsProgressMessage::AllComplete(global.clone()));
+    global
 }
 
 /// Calculate a global rebalance plan that redistributes images between splits
 /// to match target split ratios (e.g., 70%/15%/15% for train/val/test)
+///
+/// Moves onto a name already taken in the destination are listed in the
+/// plan's `collisions` and handled with `config.collision_policy`.
 pub fn calculate_global_rebalance_plan(
-    dataset_path: &PathBuf,
+    dataset_path: &Path,
+    config: &GlobalRebalanceConfig,
+) -> GlobalRebalancePlan {
+    let mut plan = plan_global_moves(dataset_path, config);
+    plan.collision_policy = config.collision_policy;
+    plan.flag_collisions(dataset_path);
+    plan
+}
+
+fn plan_global_moves(
+    dataset_path: &Path,
     config: &GlobalRebalanceConfig,
 ) -> GlobalRebalancePlan {
     let mut plan = GlobalRebalancePlan::new();
     
     // Analyze all splits
-    let initial_stats = analyze_all_splits(dataset_path);
+    let initial_stats = analyze_all_splits(dataset_path, &config.known_class_ids);
     plan.current_stats = Some(initial_stats.clone());
+
+    // Missing splits stay missing unless the config allows creating them
+    let split_ratios = config.effective_split_ratios(&initial_stats);
+    if !initial_stats.missing.is_empty() {
+        info!(
+            "Splits not present: {:?} ({})",
+            initial_stats.missing,
+            if config.create_missing_splits { "may be created" } else { "ratios spread over the others" }
+        );
+    }
     
     // Calculate total images across all splits
     let total_images = initial_stats.train.total_images 
@@ -475,16 +1224,37 @@ pub fn calculate_global_rebalance_plan(
     }
     
     // Calculate target counts for each split
-    let target_train = (total_images as f32 * config.split_ratios.train).round() as usize;
-    let target_val = (total_images as f32 * config.split_ratios.val).round() as usize;
-    let target_test = total_images - target_train - target_val; // Remainder goes to test
+    let target_of = |split: DatasetSplit| (total_images as f32 * split_ratios.get(split)).round() as usize;
+    // An empty split being filled gets its full share, so the rounding
+    // remainder goes to test, or to the first split that has images
+    let fills_empty = |split: DatasetSplit| {
+        config.balance_empty_splits && initial_stats.get(split).total_images == 0
+    };
+    let remainder_split = [DatasetSplit::Test, DatasetSplit::Train, DatasetSplit::Val]
+        .into_iter()
+        .find(|&split| !fills_empty(split))
+        .unwrap_or(DatasetSplit::Test);
+    let target_for = |split: DatasetSplit| {
+        if split != remainder_split {
+            return target_of(split);
+        }
+        let others: usize = DatasetSplit::all()
+            .into_iter()
+            .filter(|&other| other != remainder_split)
+            .map(target_of)
+            .sum();
+        total_images.saturating_sub(others)
+    };
+    let target_train = target_for(DatasetSplit::Train);
+    let target_val = target_for(DatasetSplit::Val);
+    let target_test = target_for(DatasetSplit::Test);
     
     info!(
         "Split balancing: Total={}, Target Train={} ({}%), Val={} ({}%), Test={} ({}%)",
         total_images,
-        target_train, (config.split_ratios.train * 100.0) as i32,
-        target_val, (config.split_ratios.val * 100.0) as i32,
-        target_test, (config.split_ratios.test * 100.0) as i32
+        target_train, (split_ratios.train * 100.0) as i32,
+        target_val, (split_ratios.val * 100.0) as i32,
+        target_test, (split_ratios.test * 100.0) as i32
     );
     
     info!(
@@ -508,7 +1278,7 @@ pub fn calculate_global_rebalance_plan(
     let splits_balanced = excess.values().all(|&e| e.abs() <= tolerance_count);
     
     // Check if locations are imbalanced (if location balancing is enabled)
-    let locations_imbalanced = if config.balance_locations {
+    let imbalanced_locations: Vec<String> = if config.balance_locations {
         // Collect all unique locations across all splits
         let mut all_locations: HashMap<String, Vec<(DatasetSplit, usize)>> = HashMap::new();
         for (split, stats) in [
@@ -525,33 +1295,34 @@ pub fn calculate_global_rebalance_plan(
         
         // Check if any location is significantly imbalanced across splits
         // (e.g., one split has 80% of a location while another has 5%)
-        let mut has_imbalance = false;
+        let mut imbalanced = Vec::new();
         for (loc, split_counts) in &all_locations {
             // Only check locations that exist in at least 1 split with meaningful count
             let total: usize = split_counts.iter().map(|(_, c)| c).sum();
             if total >= 5 {  // Only check if there are enough samples
                 for &(split, count) in split_counts {
                     let expected_ratio = match split {
-                        DatasetSplit::Train => config.split_ratios.train,
-                        DatasetSplit::Val => config.split_ratios.val,
-                        DatasetSplit::Test => config.split_ratios.test,
+                        DatasetSplit::Train => split_ratios.train,
+                        DatasetSplit::Val => split_ratios.val,
+                        DatasetSplit::Test => split_ratios.test,
                     };
                     let actual_ratio = count as f32 / total as f32;
                     // Consider imbalanced if ratio differs by more than 10% from expected
                     if (actual_ratio - expected_ratio).abs() > 0.10 {
                         info!("Location '{}' is imbalanced in {:?}: actual {:.1}% vs expected {:.1}%", 
                             loc, split, actual_ratio * 100.0, expected_ratio * 100.0);
-                        has_imbalance = true;
+                        imbalanced.push(loc.clone());
                         break;
                     }
                 }
             }
-            if has_imbalance { break; }
         }
-        has_imbalance
+        imbalanced.sort();
+        imbalanced
     } else {
-        false
+        Vec::new()
     };
+    let locations_imbalanced = !imbalanced_locations.is_empty();
     
     if splits_balanced && !locations_imbalanced {
         info!("Splits already balanced within {}% tolerance and locations are balanced", (config.tolerance * 100.0) as i32);
@@ -562,7 +1333,7 @@ pub fn calculate_global_rebalance_plan(
     // Collect metadata for all splits
     let mut metadata: HashMap<DatasetSplit, Vec<ImageMetadata>> = HashMap::new();
     for split in [DatasetSplit::Train, DatasetSplit::Val, DatasetSplit::Test] {
-        metadata.insert(split, collect_image_metadata(dataset_path, split));
+        metadata.insert(split, collect_image_metadata(dataset_path, split, &config.known_class_ids));
     }
     
     // If splits are balanced but locations aren't, use SMART SWAP MODE
@@ -646,11 +1417,11 @@ pub fn calculate_global_rebalance_plan(
             let images_a = metadata.get(&split_a).unwrap();
             let images_b = metadata.get(&split_b).unwrap();
             
-            let mut swap_from_a: Vec<&ImageMetadata> = images_a.iter()
+            let swap_from_a: Vec<&ImageMetadata> = images_a.iter()
                 .filter(|img| img.location.as_ref().map(|l| a_overrep_locations.contains(l)).unwrap_or(false))
                 .collect();
             
-            let mut swap_from_b: Vec<&ImageMetadata> = images_b.iter()
+            let swap_from_b: Vec<&ImageMetadata> = images_b.iter()
                 .filter(|img| img.location.as_ref().map(|l| b_overrep_locations.contains(l)).unwrap_or(false))
                 .collect();
             
@@ -662,7 +1433,7 @@ pub fn calculate_global_rebalance_plan(
                 info!("Planning {} bidirectional swaps between {:?} and {:?}", swap_count, split_a, split_b);
                 
                 // Create A → B moves
-                let labels_path_a = dataset_path.join(split_a.as_str()).join("labels");
+                let labels_path_a = labels_dir(dataset_path, split_a);
                 let mut actions_a_to_b = Vec::new();
                 for meta in swap_from_a.iter().take(swap_count) {
                     let label_path = if let Some(stem) = meta.path.file_stem() {
@@ -681,7 +1452,7 @@ pub fn calculate_global_rebalance_plan(
                 }
                 
                 // Create B → A moves
-                let labels_path_b = dataset_path.join(split_b.as_str()).join("labels");
+                let labels_path_b = labels_dir(dataset_path, split_b);
                 let mut actions_b_to_a = Vec::new();
                 for meta in swap_from_b.iter().take(swap_count) {
                     let label_path = if let Some(stem) = meta.path.file_stem() {
@@ -751,7 +1522,7 @@ pub fn calculate_global_rebalance_plan(
                         info!("Planning {} one-directional moves from {:?} to {:?} for locations: {:?}", 
                             move_count, source_split, dest_split, source_locations);
                         
-                        let labels_path = dataset_path.join(source_split.as_str()).join("labels");
+                        let labels_path = labels_dir(dataset_path, source_split);
                         let mut actions = Vec::new();
                         for meta in candidates.iter().take(move_count) {
                             let label_path = if let Some(stem) = meta.path.file_stem() {
@@ -796,6 +1567,11 @@ pub fn calculate_global_rebalance_plan(
         // Fall through: if swap mode didn't produce a plan, try normal mode anyway
         if plan.moves.is_empty() {
             info!("Attempting normal redistribution mode as fallback");
+            plan.warnings.extend(
+                imbalanced_locations
+                    .iter()
+                    .map(|location| PlanWarning::LocationUnresolved { location: location.clone() }),
+            );
             // Continue to normal mode below instead of returning
         } else {
             plan.projected_stats = Some(initial_stats);
@@ -808,6 +1584,7 @@ pub fn calculate_global_rebalance_plan(
     
     // Find splits with excess and splits with deficit
     let mut iterations = 0;
+    let mut stopped = false;
     for _iteration in 0..config.max_iterations {
         iterations += 1;
         
@@ -827,6 +1604,7 @@ pub fn calculate_global_rebalance_plan(
             
             let to_deficit = -(*excess.get(&to_split).unwrap_or(&0));
             if to_deficit <= 0 {
+                stopped = true;
                 break; // No deficit to fill
             }
             
@@ -834,20 +1612,29 @@ pub fn calculate_global_rebalance_plan(
             // If deficit > tolerance, accept any positive excess
             let min_excess = if to_deficit > tolerance_count { 0 } else { tolerance_count };
             
+            // An empty split is filled from the largest split, others from the one with most excess
+            let fill_empty = config.balance_empty_splits && initial_stats.get(to_split).total_images == 0;
             let from_split = *excess.iter()
                 .filter(|(_, &e)| e > min_excess)
-                .max_by_key(|(_, &e)| e)
+                .max_by_key(|(s, &e)| if fill_empty { projected.get(**s).total_images as i32 } else { e })
                 .map(|(s, _)| s)
                 .unwrap_or(&DatasetSplit::Train);
             
             let from_excess = *excess.get(&from_split).unwrap_or(&0);
             if from_excess <= 0 {
+                warn!("{:?} needs {} more images but no split has excess", to_split, to_deficit);
+                plan.warnings.push(PlanWarning::NoExcessAvailable {
+                    split: to_split,
+                    deficit: to_deficit as usize,
+                });
+                stopped = true;
                 break; // No excess to redistribute
             }
             
             // Calculate how many to move - limit to what's needed
             let move_count = std::cmp::min(from_excess, to_deficit) as usize;
             if move_count == 0 {
+                stopped = true;
                 break;
             }
             
@@ -858,7 +1645,7 @@ pub fn calculate_global_rebalance_plan(
         };
         
         // Get images from source split
-        let labels_path = dataset_path.join(from_split.as_str()).join("labels");
+        let labels_path = labels_dir(dataset_path, from_split);
         let available = metadata.get_mut(&from_split).unwrap();
         
         // Calculate CT/T balance for destination split to decide which to prefer
@@ -875,6 +1662,31 @@ pub fn calculate_global_rebalance_plan(
             true // No players yet, prefer CT
         };
         
+        // Rank players, backgrounds and hard cases by how far the destination
+        // is below its target share of them at its target size
+        let to_target = match to_split {
+            DatasetSplit::Train => target_train,
+            DatasetSplit::Val => target_val,
+            DatasetSplit::Test => target_test,
+        } as f32;
+        let ratios = &config.target_ratios;
+        let mut needs = [
+            (0, to_target * ratios.player_ratio - to_stats.total_player_images() as f32),
+            (1, to_target * ratios.background_ratio - to_stats.background as f32),
+            (2, to_target * ratios.hardcase_ratio - to_stats.hard_case as f32),
+        ];
+        // Stable sort: on a tie players go first, then backgrounds
+        needs.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let rank = |group: usize| needs.iter().position(|&(g, _)| g == group).unwrap_or(0) * 3;
+        let priority = |category: ImageCategory| match category {
+            ImageCategory::CTOnly => rank(0) + if prefer_ct { 0 } else { 1 },
+            ImageCategory::TOnly => rank(0) + if prefer_ct { 1 } else { 0 },
+            ImageCategory::MultiplePlayer => rank(0) + 2,
+            ImageCategory::Background => rank(1),
+            ImageCategory::HardCase => rank(2),
+            ImageCategory::Unknown => 9,
+        };
+
         // Calculate location balance for destination split (if enabled)
         let dest_location_counts = &to_stats.location_counts;
         let avg_location_count = if config.balance_locations && !dest_location_counts.is_empty() {
@@ -884,33 +1696,19 @@ pub fn calculate_global_rebalance_plan(
         };
         
         // Sort available images to prioritize:
-        // 1. Needed player type (CT/T balance)
-        // 2. Locations underrepresented in destination (if balance_locations enabled)
+        // 1. Categories the destination needs most for its target ratios
+        // 2. Needed player type (CT/T balance)
+        // 3. Locations underrepresented in destination (if balance_locations enabled)
+        // 4. The order of the selection strategy
         available.sort_by(|a, b| {
-            // First: category priority
-            let priority_a = match a.category {
-                ImageCategory::CTOnly => if prefer_ct { 0 } else { 1 },
-                ImageCategory::TOnly => if prefer_ct { 1 } else { 0 },
-                ImageCategory::MultiplePlayer => 2,
-                ImageCategory::Background => 3,
-                ImageCategory::HardCase => 4,
-            };
-            let priority_b = match b.category {
-                ImageCategory::CTOnly => if prefer_ct { 0 } else { 1 },
-                ImageCategory::TOnly => if prefer_ct { 1 } else { 0 },
-                ImageCategory::MultiplePlayer => 2,
-                ImageCategory::Background => 3,
-                ImageCategory::HardCase => 4,
-            };
-            
             // Primary sort by category
-            let cat_cmp = priority_a.cmp(&priority_b);
+            let cat_cmp = priority(a.category).cmp(&priority(b.category));
             if cat_cmp != std::cmp::Ordering::Equal {
                 return cat_cmp;
             }
             
             // Secondary sort by location (prefer underrepresented locations in destination)
-            if config.balance_locations && avg_location_count > 0.0 {
+            let location_cmp = if config.balance_locations && avg_location_count > 0.0 {
                 let loc_count_a = a.location.as_ref()
                     .and_then(|loc| dest_location_counts.get(loc))
                     .copied()
@@ -925,13 +1723,10 @@ pub fn calculate_global_rebalance_plan(
                 loc_count_a.cmp(&loc_count_b)
             } else {
                 std::cmp::Ordering::Equal
-            }
+            };
+            location_cmp.then_with(|| config.selection_strategy.compare(a, b))
         });
         
-        // Shuffle within same priority groups for variety
-        use rand::seq::SliceRandom;
-        let mut rng = rand::thread_rng();
-        
         let mut actions = Vec::new();
         let mut moved_indices = Vec::new();
         
@@ -962,6 +1757,23 @@ pub fn calculate_global_rebalance_plan(
             available.remove(idx);
         }
 
+        if actions.len() < move_count {
+            warn!(
+                "Only {} of {} images available to move from {:?} to {:?}",
+                actions.len(), move_count, from_split, to_split
+            );
+            plan.warnings.push(PlanWarning::CandidatesExhausted {
+                from_split,
+                to_split,
+                requested: move_count,
+                planned: actions.len(),
+            });
+            if actions.is_empty() {
+                stopped = true;
+                break;
+            }
+        }
+
         // Update projected stats
         for action in &actions {
             let from_stats = projected.get_mut(from_split);
@@ -971,6 +1783,7 @@ pub fn calculate_global_rebalance_plan(
                 ImageCategory::MultiplePlayer => from_stats.multiple_player = from_stats.multiple_player.saturating_sub(1),
                 ImageCategory::Background => from_stats.background = from_stats.background.saturating_sub(1),
                 ImageCategory::HardCase => from_stats.hard_case = from_stats.hard_case.saturating_sub(1),
+                ImageCategory::Unknown => from_stats.unknown = from_stats.unknown.saturating_sub(1),
             }
             from_stats.total_images = from_stats.total_images.saturating_sub(1);
 
@@ -981,6 +1794,7 @@ pub fn calculate_global_rebalance_plan(
                 ImageCategory::MultiplePlayer => to_stats.multiple_player += 1,
                 ImageCategory::Background => to_stats.background += 1,
                 ImageCategory::HardCase => to_stats.hard_case += 1,
+                ImageCategory::Unknown => to_stats.unknown += 1,
             }
             to_stats.total_images += 1;
         }
@@ -1005,14 +1819,29 @@ pub fn calculate_global_rebalance_plan(
         }
     }
     
+    if !stopped {
+        let outside_tolerance = [
+            (projected.train.total_images, target_train),
+            (projected.val.total_images, target_val),
+            (projected.test.total_images, target_test),
+        ]
+        .iter()
+        .any(|&(actual, target)| (actual as i32 - target as i32).abs() > tolerance_count);
+        if outside_tolerance {
+            plan.warnings.push(PlanWarning::IterationLimitReached { iterations });
+        }
+    }
+
     plan.iterations_used = iterations;
+    let ratios_balanced = projected.is_balanced(&config.target_ratios, config.tolerance);
     plan.projected_stats = Some(projected);
     
     info!(
-        "Split rebalance plan: {} moves in {} groups, {} iterations",
+        "Split rebalance plan: {} moves in {} groups, {} iterations (category ratios {} tolerance)",
         plan.total_moves,
         plan.moves.len(),
-        plan.iterations_used
+        plan.iterations_used,
+        if ratios_balanced { "within" } else { "outside" }
     );
 
     plan
@@ -1036,7 +1865,9 @@ fn calculate_balance_improvement(
     let from_stats = simulated.get_mut(from_split);
     match category {
         ImageCategory::Background => from_stats.background = from_stats.background.saturating_sub(count),
-        _ => {
+        ImageCategory::HardCase => from_stats.hard_case = from_stats.hard_case.saturating_sub(count),
+        ImageCategory::Unknown => from_stats.unknown = from_stats.unknown.saturating_sub(count),
+        ImageCategory::CTOnly | ImageCategory::TOnly | ImageCategory::MultiplePlayer => {
             // For player types, distribute evenly among CT/T/Multi
             let each = count / 3;
             from_stats.ct_only = from_stats.ct_only.saturating_sub(each);
@@ -1049,7 +1880,9 @@ fn calculate_balance_improvement(
     let to_stats = simulated.get_mut(to_split);
     match category {
         ImageCategory::Background => to_stats.background += count,
-        _ => {
+        ImageCategory::HardCase => to_stats.hard_case += count,
+        ImageCategory::Unknown => to_stats.unknown += count,
+        ImageCategory::CTOnly | ImageCategory::TOnly | ImageCategory::MultiplePlayer => {
             let each = count / 3;
             to_stats.ct_only += each;
             to_stats.t_only += each;
@@ -1064,6 +1897,22 @@ fn calculate_balance_improvement(
     current_deviation - new_deviation
 }
 
+/// Deviation of the split sizes from their target shares (sum of squared differences)
+fn calculate_split_size_deviation(stats: &GlobalBalanceStats, ratios: &SplitRatios) -> f32 {
+    let total = stats.total_images() as f32;
+    if total == 0.0 {
+        return 0.0;
+    }
+
+    DatasetSplit::all()
+        .into_iter()
+        .map(|split| {
+            let diff = stats.get(split).total_images as f32 / total - ratios.get(split);
+            diff * diff
+        })
+        .sum()
+}
+
 /// Calculate total deviation from target across all splits (sum of squared differences)
 fn calculate_total_deviation(stats: &GlobalBalanceStats, target: &TargetRatios) -> f32 {
     let mut total = 0.0;
@@ -1076,14 +1925,15 @@ fn calculate_total_deviation(stats: &GlobalBalanceStats, target: &TargetRatios)
         
         let bg_diff = s.get_percentage(ImageCategory::Background) / 100.0 - target.background_ratio;
         let player_diff = s.player_percentage() / 100.0 - target.player_ratio;
+        let hard_case_diff = s.get_percentage(ImageCategory::HardCase) / 100.0 - target.hardcase_ratio;
         
-        total += bg_diff * bg_diff + player_diff * player_diff;
+        total += bg_diff * bg_diff + player_diff * player_diff + hard_case_diff * hard_case_diff;
     }
     
     total
 }
 pub fn calculate_rebalance_plan(
-    dataset_path: &PathBuf,
+    dataset_path: &Path,
     config: &RebalanceConfig,
     source_stats: &BalanceStats,
 ) -> RebalancePlan {
@@ -1092,9 +1942,13 @@ pub fn calculate_rebalance_plan(
     plan.to_split = Some(config.destination_split);
     plan.category = Some(config.category);
     plan.current_stats = Some(source_stats.clone());
+    plan.collision_policy = config.collision_policy;
 
     // Calculate how many to move
-    let excess = calculate_move_count(source_stats, config.category, &config.target_ratios);
+    let excess = config
+        .move_count
+        .map(|count| count as i32)
+        .unwrap_or_else(|| calculate_move_count(source_stats, config.category, &config.target_ratios));
     
     if excess <= 0 {
         info!("No excess images to move for category {:?}", config.category);
@@ -1105,7 +1959,7 @@ pub fn calculate_rebalance_plan(
     plan.count_to_move = count_to_move;
 
     // Collect image metadata for the source split
-    let mut metadata = collect_image_metadata(dataset_path, config.source_split);
+    let mut metadata = collect_image_metadata(dataset_path, config.source_split, &config.known_class_ids);
 
     // Filter to only the target category (or player categories if balancing players)
     let target_categories: Vec<ImageCategory> = match config.category {
@@ -1123,23 +1977,17 @@ pub fn calculate_rebalance_plan(
 
     // Sort based on strategy
     match config.selection_strategy {
-        SelectionStrategy::Random => {
+        // Location stratification picks randomly within each location
+        SelectionStrategy::Random | SelectionStrategy::LocationStratified => {
             use rand::seq::SliceRandom;
             let mut rng = rand::thread_rng();
             metadata.shuffle(&mut rng);
         }
-        SelectionStrategy::FewestDetections => {
-            metadata.sort_by_key(|m| m.detection_count);
-        }
-        SelectionStrategy::OldestFirst => {
-            metadata.sort_by(|a, b| a.path.cmp(&b.path));
-        }
-        SelectionStrategy::NewestFirst => {
-            metadata.sort_by(|a, b| b.path.cmp(&a.path));
-        }
+        strategy => metadata.sort_by(|a, b| strategy.compare(a, b)),
     }
 
-    // If preserving CT/T balance, interleave selections from each category
+    // If preserving CT/T balance, take CT and T images in the proportions that
+    // move the source split's remaining CT share toward the target ratio
     if config.preserve_ct_t_balance && matches!(config.category, 
         ImageCategory::CTOnly | ImageCategory::TOnly | ImageCategory::MultiplePlayer) 
     {
@@ -1147,36 +1995,46 @@ pub fn calculate_rebalance_plan(
         for m in metadata {
             by_category.entry(m.category).or_default().push(m);
         }
-
-        let mut selected = Vec::new();
-        let mut indices: HashMap<ImageCategory, usize> = HashMap::new();
-        let categories = [ImageCategory::CTOnly, ImageCategory::TOnly, ImageCategory::MultiplePlayer];
-
-        while selected.len() < count_to_move {
-            let mut added_any = false;
-            for cat in &categories {
-                if selected.len() >= count_to_move {
-                    break;
-                }
-                if let Some(images) = by_category.get(cat) {
-                    let idx = indices.entry(*cat).or_insert(0);
-                    if *idx < images.len() {
-                        selected.push(images[*idx].clone());
-                        *idx += 1;
-                        added_any = true;
-                    }
+        let available = |cat: ImageCategory| by_category.get(&cat).map_or(0, |v| v.len());
+
+        let (ct_count, t_count, multi_count) = split_player_move_count(
+            available(ImageCategory::CTOnly),
+            available(ImageCategory::TOnly),
+            available(ImageCategory::MultiplePlayer),
+            count_to_move,
+            config.target_ct_t_ratio,
+        );
+        info!(
+            "CT/T split for {} player moves (target {:.0}% CT): {} CT, {} T, {} multi",
+            count_to_move,
+            config.target_ct_t_ratio * 100.0,
+            ct_count,
+            t_count,
+            multi_count
+        );
+
+        let mut selected = Vec::with_capacity(count_to_move);
+        for (cat, take) in [
+            (ImageCategory::CTOnly, ct_count),
+            (ImageCategory::TOnly, t_count),
+            (ImageCategory::MultiplePlayer, multi_count),
+        ] {
+            if let Some(images) = by_category.remove(&cat) {
+                if config.selection_strategy == SelectionStrategy::LocationStratified {
+                    selected.extend(select_location_stratified(images, take));
+                } else {
+                    selected.extend(images.into_iter().take(take));
                 }
             }
-            if !added_any {
-                break;
-            }
         }
 
         metadata = selected;
+    } else if config.selection_strategy == SelectionStrategy::LocationStratified {
+        metadata = select_location_stratified(metadata, count_to_move);
     }
 
     // Take the required number of images
-    let labels_path = dataset_path.join(config.source_split.as_str()).join("labels");
+    let labels_path = labels_dir(dataset_path, config.source_split);
     
     for m in metadata.into_iter().take(count_to_move) {
         let label_path = if let Some(stem) = m.path.file_stem() {
@@ -1194,6 +2052,7 @@ pub fn calculate_rebalance_plan(
             to_split: config.destination_split,
         });
     }
+    plan.flag_collisions(dataset_path);
 
     // Calculate projected stats
     let mut projected = source_stats.clone();
@@ -1204,10 +2063,12 @@ pub fn calculate_rebalance_plan(
             ImageCategory::MultiplePlayer => projected.multiple_player = projected.multiple_player.saturating_sub(1),
             ImageCategory::Background => projected.background = projected.background.saturating_sub(1),
             ImageCategory::HardCase => projected.hard_case = projected.hard_case.saturating_sub(1),
+            ImageCategory::Unknown => projected.unknown = projected.unknown.saturating_sub(1),
         }
         projected.total_images = projected.total_images.saturating_sub(1);
     }
     plan.projected_stats = Some(projected);
+    plan.quality = Some(estimate_post_rebalance_quality(&plan, &config.target_ratios));
 
     info!(
         "Rebalance plan: move {} {:?} images from {:?} to {:?}",
@@ -1220,9 +2081,25 @@ pub fn calculate_rebalance_plan(
     plan
 }
 
+/// Imbalance score of the plan's source split before and after the moves
+///
+/// Scores come from `BalanceStats::imbalance_score` (lower is better). A plan
+/// without stats, such as a bulk move, scores (0, 0).
+pub fn estimate_post_rebalance_quality(plan: &RebalancePlan, target: &TargetRatios) -> (f32, f32) {
+    let before = plan
+        .current_stats
+        .as_ref()
+        .map_or(0.0, |stats| stats.imbalance_score(target));
+    let after = plan
+        .projected_stats
+        .as_ref()
+        .map_or(before, |stats| stats.imbalance_score(target));
+    (before, after)
+}
+
 /// Execute a rebalance plan, moving files between splits
 pub fn execute_rebalance_plan(
-    dataset_path: &PathBuf,
+    dataset_path: &Path,
     plan: &RebalancePlan,
     progress_tx: Option<Sender<RebalanceProgressMessage>>,
     cancel_flag: Option<Arc<AtomicBool>>,
@@ -1242,8 +2119,8 @@ pub fn execute_rebalance_plan(
     }
 
     let to_split = plan.to_split.unwrap_or(DatasetSplit::Val);
-    let dest_images = dataset_path.join(to_split.as_str()).join("images");
-    let dest_labels = dataset_path.join(to_split.as_str()).join("labels");
+    let dest_images = images_dir(dataset_path, to_split);
+    let dest_labels = labels_dir(dataset_path, to_split);
 
     // Ensure destination directories exist
     if let Err(e) = fs::create_dir_all(&dest_images) {
@@ -1287,27 +2164,44 @@ pub fn execute_rebalance_plan(
             .and_then(|n| n.to_str())
             .unwrap_or("unknown");
 
-        // Calculate destination paths
-        let new_image_path = dest_images.join(filename);
-        let new_label_path = action.label_path.as_ref().and_then(|lp| {
-            lp.file_name().map(|n| dest_labels.join(n))
-        });
+        // Calculate destination paths, never overwriting a file in the destination
+        let (new_image_path, new_label_path) =
+            match resolve_destination(action, &dest_images, &dest_labels, plan.collision_policy) {
+                Ok(paths) => paths,
+                Err(e) => {
+                    warn!("Skipping {:?}: {}", action.image_path, e);
+                    results.push(MoveResult {
+                        action: action.clone(),
+                        undo_action: None,
+                        success: false,
+                        error: Some(e),
+                        new_image_path: None,
+                        new_label_path: None,
+                        copied: false,
+                    });
+                    failed_count += 1;
+                    continue;
+                }
+            };
 
         // Move image file
-        let image_result = move_file(&action.image_path, &new_image_path);
-        
-        if let Err(e) = image_result {
-            error!("Failed to move image {:?}: {}", action.image_path, e);
-            results.push(MoveResult {
-                action: action.clone(),
-                success: false,
-                error: Some(format!("Failed to move image: {}", e)),
-                new_image_path: None,
-                new_label_path: None,
-            });
-            failed_count += 1;
-            continue;
-        }
+        let mut copied = match move_file(&action.image_path, &new_image_path) {
+            Ok(method) => method == MoveMethod::Copied,
+            Err(e) => {
+                error!("Failed to move image {:?}: {}", action.image_path, e);
+                results.push(MoveResult {
+                    action: action.clone(),
+                    undo_action: None,
+                    success: false,
+                    error: Some(format!("Failed to move image: {}", e)),
+                    new_image_path: None,
+                    new_label_path: None,
+                    copied: false,
+                });
+                failed_count += 1;
+                continue;
+            }
+        };
 
         // Move label file if exists
         let mut label_moved = true;
@@ -1315,22 +2209,28 @@ pub fn execute_rebalance_plan(
         
         if let (Some(src_label), Some(dst_label)) = (&action.label_path, &new_label_path) {
             if src_label.exists() {
-                if let Err(e) = move_file(src_label, dst_label) {
-                    warn!("Failed to move label {:?}: {}", src_label, e);
-                    label_moved = false;
-                    // Don't fail entirely - the image was moved successfully
-                } else {
-                    final_label_path = Some(dst_label.clone());
+                match move_file(src_label, dst_label) {
+                    Ok(method) => {
+                        copied |= method == MoveMethod::Copied;
+                        final_label_path = Some(dst_label.clone());
+                    }
+                    Err(e) => {
+                        warn!("Failed to move label {:?}: {}", src_label, e);
+                        label_moved = false;
+                        // Don't fail entirely - the image was moved successfully
+                    }
                 }
             }
         }
 
         results.push(MoveResult {
             action: action.clone(),
+            undo_action: Some(action.reversed(&new_image_path, final_label_path.as_deref())),
             success: true,
             error: if label_moved { None } else { Some("Label move failed".to_string()) },
             new_image_path: Some(new_image_path),
             new_label_path: final_label_path,
+            copied,
         });
         success_count += 1;
 
@@ -1364,7 +2264,7 @@ pub fn execute_rebalance_plan(
 
 /// Execute a global rebalance plan (all move groups)
 pub fn execute_global_rebalance_plan(
-    dataset_path: &PathBuf,
+    dataset_path: &Path,
     plan: &GlobalRebalancePlan,
     progress_tx: Option<Sender<RebalanceProgressMessage>>,
     cancel_flag: Option<Arc<AtomicBool>>,
@@ -1375,8 +2275,8 @@ pub fn execute_global_rebalance_plan(
 
     for move_group in &plan.moves {
         // Ensure destination directories exist
-        let dest_images = dataset_path.join(move_group.to_split.as_str()).join("images");
-        let dest_labels = dataset_path.join(move_group.to_split.as_str()).join("labels");
+        let dest_images = images_dir(dataset_path, move_group.to_split);
+        let dest_labels = labels_dir(dataset_path, move_group.to_split);
 
         if let Err(e) = fs::create_dir_all(&dest_images) {
             error!("Failed to create destination images dir: {}", e);
@@ -1410,42 +2310,69 @@ pub fn execute_global_rebalance_plan(
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown");
 
-            let new_image_path = dest_images.join(filename);
-            let new_label_path = action.label_path.as_ref().and_then(|lp| {
-                lp.file_name().map(|n| dest_labels.join(n))
-            });
+            // Calculate destination paths, never overwriting a file in the destination
+            let (new_image_path, new_label_path) =
+                match resolve_destination(action, &dest_images, &dest_labels, plan.collision_policy) {
+                    Ok(paths) => paths,
+                    Err(e) => {
+                        warn!("Skipping {:?}: {}", action.image_path, e);
+                        all_results.push(MoveResult {
+                            action: action.clone(),
+                            undo_action: None,
+                            success: false,
+                            error: Some(e),
+                            new_image_path: None,
+                            new_label_path: None,
+                            copied: false,
+                        });
+                        continue;
+                    }
+                };
 
             // Move image
-            let image_result = move_file(&action.image_path, &new_image_path);
-            
-            if let Err(e) = image_result {
-                error!("Failed to move image {:?}: {}", action.image_path, e);
-                all_results.push(MoveResult {
-                    action: action.clone(),
-                    success: false,
-                    error: Some(format!("Failed to move image: {}", e)),
-                    new_image_path: None,
-                    new_label_path: None,
-                });
-                continue;
-            }
+            let mut copied = match move_file(&action.image_path, &new_image_path) {
+                Ok(method) => method == MoveMethod::Copied,
+                Err(e) => {
+                    error!("Failed to move image {:?}: {}", action.image_path, e);
+                    all_results.push(MoveResult {
+                        action: action.clone(),
+                        undo_action: None,
+                        success: false,
+                        error: Some(format!("Failed to move image: {}", e)),
+                        new_image_path: None,
+                        new_label_path: None,
+                        copied: false,
+                    });
+                    continue;
+                }
+            };
 
             // Move label if exists
+            let mut label_moved = true;
             let mut final_label_path = None;
             if let (Some(src_label), Some(dst_label)) = (&action.label_path, &new_label_path) {
                 if src_label.exists() {
-                    if let Ok(()) = move_file(src_label, dst_label) {
-                        final_label_path = Some(dst_label.clone());
+                    match move_file(src_label, dst_label) {
+                        Ok(method) => {
+                            copied |= method == MoveMethod::Copied;
+                            final_label_path = Some(dst_label.clone());
+                        }
+                        Err(e) => {
+                            warn!("Failed to move label {:?}: {}", src_label, e);
+                            label_moved = false;
+                        }
                     }
                 }
             }
 
             all_results.push(MoveResult {
                 action: action.clone(),
+                undo_action: Some(action.reversed(&new_image_path, final_label_path.as_deref())),
                 success: true,
-                error: None,
+                error: if label_moved { None } else { Some("Label move failed".to_string()) },
                 new_image_path: Some(new_image_path),
                 new_label_path: final_label_path,
+                copied,
             });
 
             processed += 1;
@@ -1481,13 +2408,231 @@ pub fn execute_global_rebalance_plan(
 
     all_results
 }
+
+/// Staging folder of a split swap, relative to the dataset root
+pub const SWAP_STAGING_DIR: &str = ".cleaner/swap_staging";
+
+/// Plan swapping the contents of two splits
+///
+/// The plan has two groups: every image of `a` moving to `b`, and every image
+/// of `b` moving to `a`. A group holds all categories, so its `category` is
+/// only the most common one. Run it with `execute_split_swap`.
+pub fn plan_split_swap(
+    dataset_path: &Path,
+    a: DatasetSplit,
+    b: DatasetSplit,
+    known_class_ids: &[u32],
+) -> GlobalRebalancePlan {
+    let group = |from: DatasetSplit, to: DatasetSplit| {
+        let labels_path = labels_dir(dataset_path, from);
+        let mut counts: HashMap<ImageCategory, usize> = HashMap::new();
+        let mut actions: Vec<MoveAction> = collect_image_metadata(dataset_path, from, known_class_ids)
+            .into_iter()
+            .map(|m| {
+                *counts.entry(m.category).or_default() += 1;
+                let label_path = m
+                    .path
+                    .file_stem()
+                    .map(|stem| labels_path.join(format!("{}.txt", stem.to_string_lossy())))
+                    .filter(|lp| lp.exists());
+                MoveAction {
+                    image_path: m.path,
+                    label_path,
+                    category: m.category,
+                    from_split: from,
+                    to_split: to,
+                }
+            })
+            .collect();
+        actions.sort_by(|x, y| x.image_path.cmp(&y.image_path));
+        let category = counts
+            .into_iter()
+            .max_by_key(|&(category, count)| (count, std::cmp::Reverse(category as usize)))
+            .map_or(ImageCategory::Background, |(category, _)| category);
+        GlobalMoveAction {
+            from_split: from,
+            to_split: to,
+            category,
+            count: actions.len(),
+            actions,
+        }
+    };
+
+    let mut plan = GlobalRebalancePlan::new();
+    plan.moves = vec![group(a, b), group(b, a)];
+    plan.total_moves = plan.moves.iter().map(|m| m.count).sum();
+    plan
+}
+
+/// Swap two splits following a `plan_split_swap` plan
+///
+/// `move_file` overwrites its destination, so the first group is parked in
+/// `staging_dir` before the second group takes its place, then moved on from
+/// there. The results list all three steps in order; `undo_rebalance` replays
+/// them backwards to restore both splits. If any file fails to reach staging
+/// the swap stops before anything is moved into the first split.
+pub fn execute_split_swap(
+    dataset_path: &Path,
+    plan: &GlobalRebalancePlan,
+    staging_dir: &Path,
+    progress_tx: Option<Sender<RebalanceProgressMessage>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> Vec<MoveResult> {
+    let mut results = Vec::new();
+    let [first, second] = plan.moves.as_slice() else {
+        warn!("Split swap plan must have exactly two groups");
+        return results;
+    };
+    // The first group is moved twice (in and out of staging)
+    let total = 2 * first.count + second.count;
+    let mut processed = 0;
+
+    let send_complete = |results: &[MoveResult]| {
+        let success_count = results.iter().filter(|r| r.success).count();
+        let failed_count = results.len() - success_count;
+        info!("Split swap finished: {} moves succeeded, {} failed", success_count, failed_count);
+        if let Some(ref tx) = progress_tx {
+            let _ = tx.send(RebalanceProgressMessage::Complete {
+                success_count,
+                failed_count,
+                results: results.to_vec(),
+            });
+        }
+    };
+
+    // 1. Park the first split in staging
+    let staged_from = results.len();
+    let staging = (staging_dir.join("images"), staging_dir.join("labels"));
+    if !move_actions_into(&first.actions, &staging, &mut results, &mut processed, total, &progress_tx, &cancel_flag) {
+        return results;
+    }
+    if results[staged_from..].iter().any(|r| !r.success) {
+        error!("Split swap stopped: not every {} image could be staged", first.from_split.as_str());
+        send_complete(&results);
+        return results;
+    }
+    let staged: Vec<MoveAction> = results[staged_from..]
+        .iter()
+        .filter_map(|r| {
+            Some(MoveAction {
+                image_path: r.new_image_path.clone()?,
+                label_path: r.new_label_path.clone(),
+                category: r.action.category,
+                from_split: first.from_split,
+                to_split: first.to_split,
+            })
+        })
+        .collect();
+
+    // 2. Move the second split into the first one's place
+    let dest = (images_dir(dataset_path, second.to_split), labels_dir(dataset_path, second.to_split));
+    if !move_actions_into(&second.actions, &dest, &mut results, &mut processed, total, &progress_tx, &cancel_flag) {
+        return results;
+    }
+
+    // 3. Move the staged files on to the second split
+    let dest = (images_dir(dataset_path, first.to_split), labels_dir(dataset_path, first.to_split));
+    if !move_actions_into(&staged, &dest, &mut results, &mut processed, total, &progress_tx, &cancel_flag) {
+        return results;
+    }
+
+    let _ = fs::remove_dir_all(staging_dir);
+    send_complete(&results);
+    results
+}
+
+/// Move `actions` into the `(images, labels)` folders of `dest`, appending to `results`
+///
+/// Returns false (after sending `Cancelled`) when the cancel flag is set.
+fn move_actions_into(
+    actions: &[MoveAction],
+    dest: &(PathBuf, PathBuf),
+    results: &mut Vec<MoveResult>,
+    processed: &mut usize,
+    total: usize,
+    progress_tx: &Option<Sender<RebalanceProgressMessage>>,
+    cancel_flag: &Option<Arc<AtomicBool>>,
+) -> bool {
+    let (dest_images, dest_labels) = dest;
+    if let Err(e) = fs::create_dir_all(dest_images).and_then(|_| fs::create_dir_all(dest_labels)) {
+        error!("Failed to create destination dirs {:?}: {}", dest_images, e);
+    }
+
+    for action in actions {
+        if cancel_flag.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            warn!("Split swap cancelled at {}/{}", processed, total);
+            if let Some(tx) = progress_tx {
+                let _ = tx.send(RebalanceProgressMessage::Cancelled {
+                    completed_count: *processed,
+                    results: results.clone(),
+                });
+            }
+            return false;
+        }
+
+        let filename = action.image_path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let new_image_path = dest_images.join(&filename);
+
+        let mut copied = match move_file(&action.image_path, &new_image_path) {
+            Ok(method) => method == MoveMethod::Copied,
+            Err(e) => {
+                error!("Failed to move image {:?}: {}", action.image_path, e);
+                results.push(MoveResult {
+                    action: action.clone(),
+                    undo_action: None,
+                    success: false,
+                    error: Some(format!("Failed to move image: {}", e)),
+                    new_image_path: None,
+                    new_label_path: None,
+                    copied: false,
+                });
+                continue;
+            }
+        };
+
+        let new_label_path = action.label_path.as_ref().and_then(|src_label| {
+            let dst_label = dest_labels.join(src_label.file_name()?);
+            let method = move_file(src_label, &dst_label).ok()?;
+            copied |= method == MoveMethod::Copied;
+            Some(dst_label)
+        });
+
+        results.push(MoveResult {
+            action: action.clone(),
+            undo_action: Some(action.reversed(&new_image_path, new_label_path.as_deref())),
+            success: true,
+            error: None,
+            new_image_path: Some(new_image_path),
+            new_label_path,
+            copied,
+        });
+
+        *processed += 1;
+        if let Some(tx) = progress_tx {
+            if (*processed).is_multiple_of(5) || *processed == total {
+                let _ = tx.send(RebalanceProgressMessage::Progress {
+                    current: *processed,
+                    total,
+                    last_moved: filename,
+                });
+            }
+        }
+    }
+
+    true
+}
+
 pub fn undo_rebalance(
     results: &[MoveResult],
     progress_tx: Option<Sender<RebalanceProgressMessage>>,
     cancel_flag: Option<Arc<AtomicBool>>,
 ) -> Vec<MoveResult> {
     let mut undo_results = Vec::new();
-    let successful_moves: Vec<_> = results.iter().filter(|r| r.success).collect();
+    // Backwards, so chained moves (e.g. through a swap's staging folder) unwind in order
+    let successful_moves: Vec<_> = results.iter().rev().filter(|r| r.success).collect();
     let total = successful_moves.len();
 
     if total == 0 {
@@ -1524,37 +2669,79 @@ pub fn undo_rebalance(
             .and_then(|n| n.to_str())
             .unwrap_or("unknown");
 
-        // Move image back
-        if let Some(ref new_path) = result.new_image_path {
-            if let Err(e) = move_file(new_path, &original_action.image_path) {
-                error!("Failed to undo image move: {}", e);
-                undo_results.push(MoveResult {
-                    action: original_action.clone(),
-                    success: false,
-                    error: Some(format!("Undo failed: {}", e)),
-                    new_image_path: None,
-                    new_label_path: None,
-                });
-                failed_count += 1;
-                continue;
+        // The inverse recorded at move time; results without one rebuild it from the new paths
+        let (source_image, source_label) = match &result.undo_action {
+            Some(undo) => (Some(undo.image_path.clone()), undo.label_path.clone()),
+            None => (result.new_image_path.clone(), result.new_label_path.clone()),
+        };
+
+        // A file moved into the original place since would be overwritten
+        if original_action.image_path.exists() && source_image.as_ref() != Some(&original_action.image_path) {
+            error!("Cannot undo move of {}: original path is taken", filename);
+            undo_results.push(MoveResult {
+                action: original_action.clone(),
+                undo_action: None,
+                success: false,
+                error: Some(format!(
+                    "Undo failed: {} already exists",
+                    original_action.image_path.display()
+                )),
+                new_image_path: None,
+                new_label_path: None,
+                copied: false,
+            });
+            failed_count += 1;
+            continue;
+        }
+
+        // Move image back (recreating folders that were cleaned up, like a swap's staging)
+        for path in [Some(&original_action.image_path), original_action.label_path.as_ref()].into_iter().flatten() {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+        }
+        let mut copied = false;
+        if let Some(ref new_path) = source_image {
+            match move_file(new_path, &original_action.image_path) {
+                Ok(method) => copied = method == MoveMethod::Copied,
+                Err(e) => {
+                    error!("Failed to undo image move: {}", e);
+                    undo_results.push(MoveResult {
+                        action: original_action.clone(),
+                        undo_action: None,
+                        success: false,
+                        error: Some(format!("Undo failed: {}", e)),
+                        new_image_path: None,
+                        new_label_path: None,
+                        copied: false,
+                    });
+                    failed_count += 1;
+                    continue;
+                }
             }
         }
 
         // Move label back
-        if let (Some(ref new_label), Some(ref orig_label)) = 
-            (&result.new_label_path, &original_action.label_path) 
+        if let (Some(ref new_label), Some(ref orig_label)) =
+            (&source_label, &original_action.label_path)
         {
-            if new_label.exists() {
-                let _ = move_file(new_label, orig_label);
+            if orig_label.exists() {
+                warn!("Label {:?} was not moved back: original path is taken", new_label);
+            } else if new_label.exists() {
+                if let Ok(method) = move_file(new_label, orig_label) {
+                    copied |= method == MoveMethod::Copied;
+                }
             }
         }
 
         undo_results.push(MoveResult {
             action: original_action.clone(),
+            undo_action: None,
             success: true,
             error: None,
             new_image_path: Some(original_action.image_path.clone()),
             new_label_path: original_action.label_path.clone(),
+            copied,
         });
         success_count += 1;
 
@@ -1589,6 +2776,27 @@ pub fn undo_rebalance(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_ct_t_ratio_and_fix() {
+        let mut stats = BalanceStats {
+            total_images: 1000,
+            ct_only: 600,
+            t_only: 400,
+            ..BalanceStats::new()
+        };
+        assert_eq!(stats.ct_t_ratio(), Some(0.6));
+        assert_eq!(calculate_ct_t_fix(&stats), Some((ImageCategory::CTOnly, 200)));
+
+        stats.ct_only = 400;
+        assert_eq!(stats.ct_t_ratio(), Some(0.5));
+        assert_eq!(calculate_ct_t_fix(&stats), None);
+
+        stats.ct_only = 0;
+        stats.t_only = 0;
+        assert_eq!(stats.ct_t_ratio(), None);
+    }
 
     #[test]
     fn test_calculate_move_count_excess_background() {
@@ -1599,12 +2807,132 @@ mod tests {
             multiple_player: 50,
             background: 150,  // 15% - target is 10%
             hard_case: 0,
+            unknown: 0,
+            location_counts: HashMap::new(),
         };
         let target = TargetRatios::default();
         let excess = calculate_move_count(&stats, ImageCategory::Background, &target);
         assert_eq!(excess, 50);  // Need to remove 50 to get to 10%
     }
 
+    fn split_stats(total_images: usize, background: usize) -> BalanceStats {
+        BalanceStats {
+            total_images,
+            ct_only: total_images - background,
+            background,
+            ..BalanceStats::new()
+        }
+    }
+
+    #[test]
+    fn test_choose_destination_split_none_without_capacity() {
+        // Val and test need backgrounds but are already over their 20% / 10% share
+        let stats = GlobalBalanceStats {
+            train: split_stats(500, 200),
+            val: split_stats(300, 0),
+            test: split_stats(200, 0),
+            missing: Vec::new(),
+        };
+        let dest = choose_destination_split(
+            &stats,
+            DatasetSplit::Train,
+            ImageCategory::Background,
+            &TargetRatios::default(),
+            &SplitRatios::default(),
+        );
+        assert_eq!(dest, None);
+    }
+
+    #[test]
+    fn test_choose_destination_split_caps_at_target_share() {
+        // Val needs 19 backgrounds but only has room for 10 more images; test is full
+        let stats = GlobalBalanceStats {
+            train: split_stats(710, 300),
+            val: split_stats(190, 0),
+            test: split_stats(100, 0),
+            missing: Vec::new(),
+        };
+        let dest = choose_destination_split(
+            &stats,
+            DatasetSplit::Train,
+            ImageCategory::Background,
+            &TargetRatios::default(),
+            &SplitRatios::default(),
+        );
+        assert_eq!(dest, Some((DatasetSplit::Val, 10)));
+    }
+
+    #[test]
+    fn test_choose_destination_split_tie_keeps_split_order() {
+        let stats = GlobalBalanceStats {
+            train: split_stats(700, 140),
+            val: split_stats(100, 0),
+            test: split_stats(100, 0),
+            missing: Vec::new(),
+        };
+        let ratios = SplitRatios {
+            train: 0.70,
+            val: 0.15,
+            test: 0.15,
+        };
+        let dest = choose_destination_split(
+            &stats,
+            DatasetSplit::Train,
+            ImageCategory::Background,
+            &TargetRatios::default(),
+            &ratios,
+        );
+        assert_eq!(dest, Some((DatasetSplit::Val, 10)));
+    }
+
+    #[test]
+    fn test_balance_improvement_moves_only_hard_cases() {
+        // Train has 20% hard cases, val none; the 5% target favors moving them to val
+        let hard_cases = |total, hard_case| BalanceStats {
+            total_images: total,
+            ct_only: total - hard_case,
+            hard_case,
+            ..BalanceStats::new()
+        };
+        let stats = GlobalBalanceStats {
+            train: hard_cases(500, 100),
+            val: hard_cases(200, 0),
+            test: hard_cases(100, 5),
+            missing: Vec::new(),
+        };
+        let target = TargetRatios::default();
+        let improvement = calculate_balance_improvement(
+            &stats,
+            DatasetSplit::Train,
+            DatasetSplit::Val,
+            ImageCategory::HardCase,
+            10,
+            &target,
+        );
+        assert!(improvement > 0.0);
+
+        // Moving the hard cases the other way makes things worse
+        let reverse = calculate_balance_improvement(
+            &stats,
+            DatasetSplit::Test,
+            DatasetSplit::Train,
+            ImageCategory::HardCase,
+            5,
+            &target,
+        );
+        assert!(reverse < 0.0);
+
+        // Player counts are untouched, so a hard case move scores the same as
+        // the hard case share change alone
+        let mut moved = stats.clone();
+        moved.train.hard_case -= 10;
+        moved.train.total_images -= 10;
+        moved.val.hard_case += 10;
+        moved.val.total_images += 10;
+        let expected = calculate_total_deviation(&stats, &target) - calculate_total_deviation(&moved, &target);
+        assert!((improvement - expected).abs() < 1e-6);
+    }
+
     #[test]
     fn test_calculate_move_count_no_excess() {
         let stats = BalanceStats {
@@ -1614,15 +2942,664 @@ mod tests {
             multiple_player: 50,
             background: 100,  // Exactly 10%
             hard_case: 50,
+            unknown: 0,
+            location_counts: HashMap::new(),
         };
         let target = TargetRatios::default();
         let excess = calculate_move_count(&stats, ImageCategory::Background, &target);
         assert_eq!(excess, 0);
     }
 
+    #[test]
+    fn test_split_player_move_count_targets_ct_ratio() {
+        // 600 CT / 400 T: moving 200 singles should leave 400 / 400
+        assert_eq!(split_player_move_count(600, 400, 0, 200, 0.5), (200, 0, 0));
+        // Multi-player images keep their proportional share
+        assert_eq!(split_player_move_count(450, 450, 100, 100, 0.5), (45, 45, 10));
+        // A 60% CT target leaves more CT behind
+        assert_eq!(split_player_move_count(500, 500, 0, 500, 0.6), (200, 300, 0));
+        // Cannot move more T images than exist
+        assert_eq!(split_player_move_count(100, 10, 0, 50, 1.0), (40, 10, 0));
+        assert_eq!(split_player_move_count(10, 10, 0, 50, 0.5), (10, 10, 0));
+    }
+
+    #[test]
+    fn test_plan_warning_messages() {
+        let warning = PlanWarning::NoExcessAvailable { split: DatasetSplit::Val, deficit: 120 };
+        assert_eq!(warning.message(), "VAL needs 120 more images but no split has excess images");
+
+        let warning = PlanWarning::LocationUnresolved { location: "TSpawn".to_string() };
+        assert_eq!(
+            warning.message(),
+            "Location 'TSpawn' imbalance could not be fixed without violating split ratios"
+        );
+    }
+
+    #[test]
+    fn test_plan_preview_rows() {
+        let plan = RebalancePlan {
+            category: Some(ImageCategory::Background),
+            from_split: Some(DatasetSplit::Train),
+            to_split: Some(DatasetSplit::Val),
+            ..RebalancePlan::new()
+        };
+        assert_eq!(
+            plan.preview_as_table(),
+            vec![("TRAIN".to_string(), "VAL".to_string(), "0 Background images".to_string())]
+        );
+        assert_eq!(RebalancePlan::new().preview_as_table()[0].2, "0 filtered images");
+
+        let plan = GlobalRebalancePlan {
+            moves: vec![GlobalMoveAction {
+                from_split: DatasetSplit::Test,
+                to_split: DatasetSplit::Train,
+                category: ImageCategory::CTOnly,
+                count: 12,
+                actions: Vec::new(),
+            }],
+            ..GlobalRebalancePlan::new()
+        };
+        assert_eq!(
+            plan.preview_as_table(),
+            vec![("TEST".to_string(), "TRAIN".to_string(), format!("12 {} images", ImageCategory::CTOnly.as_str()))]
+        );
+    }
+
+    #[test]
+    fn test_split_deltas_and_shrinking_splits() {
+        let group = |from_split, to_split, count| GlobalMoveAction {
+            from_split,
+            to_split,
+            category: ImageCategory::Background,
+            count,
+            actions: Vec::new(),
+        };
+        let stats = |total_images| BalanceStats {
+            total_images,
+            ..BalanceStats::new()
+        };
+        let mut plan = GlobalRebalancePlan {
+            moves: vec![
+                group(DatasetSplit::Train, DatasetSplit::Val, 89),
+                group(DatasetSplit::Train, DatasetSplit::Test, 60),
+                group(DatasetSplit::Test, DatasetSplit::Train, 6),
+            ],
+            ..GlobalRebalancePlan::new()
+        };
+
+        let deltas = plan.split_deltas();
+        assert_eq!(deltas[&DatasetSplit::Train], -143);
+        assert_eq!(deltas[&DatasetSplit::Val], 89);
+        assert_eq!(deltas[&DatasetSplit::Test], 54);
+        assert_eq!(deltas.values().sum::<i32>(), 0);
+        assert!(GlobalRebalancePlan::new().split_deltas().values().all(|&delta| delta == 0));
+
+        // Without current stats there is nothing to compare against
+        assert!(plan.shrinking_splits(0.2).is_empty());
+        plan.current_stats = Some(GlobalBalanceStats {
+            train: stats(800),
+            val: stats(100),
+            test: stats(100),
+            missing: Vec::new(),
+        });
+        assert!(plan.shrinking_splits(0.2).is_empty());
+        plan.current_stats.as_mut().unwrap().train = stats(600);
+        assert_eq!(plan.shrinking_splits(0.2), vec![DatasetSplit::Train]);
+    }
+
     #[test]
     fn test_selection_strategy_display() {
         assert_eq!(SelectionStrategy::Random.as_str(), "Random");
         assert_eq!(SelectionStrategy::FewestDetections.as_str(), "Fewest Detections");
+        assert_eq!(SelectionStrategy::LocationStratified.as_str(), "Location Stratified");
+        assert!(SelectionStrategy::all().contains(&SelectionStrategy::LocationStratified));
+    }
+
+    #[test]
+    fn test_selection_strategy_orders_candidates() {
+        let meta = |name: &str, detection_count: usize, max_overlap: f32| ImageMetadata {
+            path: PathBuf::from(name),
+            category: ImageCategory::CTOnly,
+            detection_count,
+            location: None,
+            max_overlap,
+        };
+        let mut images = vec![meta("b.png", 3, 0.1), meta("a.png", 1, 0.9), meta("c.png", 2, 0.5)];
+        let names = |images: &[ImageMetadata]| -> Vec<String> {
+            images.iter().map(|m| m.path.to_string_lossy().to_string()).collect()
+        };
+
+        images.sort_by(|a, b| SelectionStrategy::FewestDetections.compare(a, b));
+        assert_eq!(names(&images), ["a.png", "c.png", "b.png"]);
+        images.sort_by(|a, b| SelectionStrategy::NewestFirst.compare(a, b));
+        assert_eq!(names(&images), ["c.png", "b.png", "a.png"]);
+        images.sort_by(|a, b| SelectionStrategy::HighestOverlap.compare(a, b));
+        assert_eq!(names(&images), ["a.png", "c.png", "b.png"]);
+        // Random keeps the current order for the caller to shuffle
+        images.sort_by(|a, b| SelectionStrategy::Random.compare(a, b));
+        assert_eq!(names(&images), ["a.png", "c.png", "b.png"]);
+    }
+
+    #[test]
+    fn test_split_swap_roundtrips_through_undo() {
+        let tmp = TempDir::new("split_swap_test");
+        let root = tmp.path().to_path_buf();
+        for split in ["train", "val"] {
+            fs::create_dir_all(root.join(split).join("images")).unwrap();
+            fs::create_dir_all(root.join(split).join("labels")).unwrap();
+        }
+        // "b" exists in both splits, so a direct move would overwrite it
+        fs::write(root.join("train/images/a.png"), b"train a").unwrap();
+        fs::write(root.join("train/labels/a.txt"), "0 0.5 0.5 0.1 0.1\n").unwrap();
+        fs::write(root.join("train/images/b.png"), b"train b").unwrap();
+        fs::write(root.join("val/images/b.png"), b"val b").unwrap();
+
+        let plan = plan_split_swap(&root, DatasetSplit::Train, DatasetSplit::Val, &[0, 1]);
+        assert_eq!(plan.moves.len(), 2);
+        assert_eq!(plan.total_moves, 3);
+
+        let staging = root.join(SWAP_STAGING_DIR);
+        let results = execute_split_swap(&root, &plan, &staging, None, None);
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|r| r.success));
+        let read = |path: &str| fs::read(root.join(path)).unwrap();
+        assert_eq!(read("val/images/a.png"), b"train a");
+        assert_eq!(read("val/images/b.png"), b"train b");
+        assert_eq!(read("train/images/b.png"), b"val b");
+        assert!(root.join("val/labels/a.txt").exists());
+        assert!(!root.join("train/images/a.png").exists());
+        assert!(!staging.exists());
+
+        undo_rebalance(&results, None, None);
+        assert_eq!(read("train/images/a.png"), b"train a");
+        assert_eq!(read("train/images/b.png"), b"train b");
+        assert_eq!(read("val/images/b.png"), b"val b");
+        assert!(root.join("train/labels/a.txt").exists());
+        assert!(!root.join("val/images/a.png").exists());
+    }
+
+    #[test]
+    fn test_rebalance_collisions_skip_or_rename_and_undo() {
+        let tmp = TempDir::new("rebalance_collision_test");
+        let root = tmp.path().to_path_buf();
+        for split in ["train", "val"] {
+            fs::create_dir_all(root.join(split).join("images")).unwrap();
+            fs::create_dir_all(root.join(split).join("labels")).unwrap();
+        }
+        fs::write(root.join("train/images/a.png"), b"train a").unwrap();
+        fs::write(root.join("train/labels/a.txt"), "0 0.5 0.5 0.1 0.1\n").unwrap();
+        fs::write(root.join("train/images/b.png"), b"train b").unwrap();
+        fs::write(root.join("val/images/a.png"), b"val a").unwrap();
+        fs::write(root.join("val/images/a_1.png"), b"val a_1").unwrap();
+
+        let action = |name: &str, label: Option<&str>| MoveAction {
+            image_path: root.join("train/images").join(name),
+            label_path: label.map(|l| root.join("train/labels").join(l)),
+            category: ImageCategory::Background,
+            from_split: DatasetSplit::Train,
+            to_split: DatasetSplit::Val,
+        };
+        let mut plan = RebalancePlan {
+            actions: vec![action("a.png", Some("a.txt")), action("b.png", None)],
+            to_split: Some(DatasetSplit::Val),
+            ..RebalancePlan::new()
+        };
+        plan.flag_collisions(&root);
+        assert_eq!(plan.collisions, vec![root.join("train/images/a.png")]);
+
+        // Skip leaves the colliding pair in place
+        let results = execute_rebalance_plan(&root, &plan, None, None);
+        assert!(!results[0].success && results[1].success);
+        assert!(root.join("train/images/a.png").exists());
+        assert_eq!(fs::read(root.join("val/images/a.png")).unwrap(), b"val a");
+        undo_rebalance(&results, None, None);
+
+        // Rename moves image and label under the same free suffix
+        plan.collision_policy = CollisionPolicy::Rename;
+        let results = execute_rebalance_plan(&root, &plan, None, None);
+        assert!(results.iter().all(|r| r.success));
+        assert_eq!(fs::read(root.join("val/images/a_2.png")).unwrap(), b"train a");
+        assert!(root.join("val/labels/a_2.txt").exists());
+        assert_eq!(fs::read(root.join("val/images/a.png")).unwrap(), b"val a");
+
+        // The recorded inverse starts from the renamed files
+        let undo = results[0].undo_action.as_ref().unwrap();
+        assert_eq!(undo.image_path, root.join("val/images/a_2.png"));
+        assert_eq!(undo.label_path, Some(root.join("val/labels/a_2.txt")));
+        assert_eq!((undo.from_split, undo.to_split), (DatasetSplit::Val, DatasetSplit::Train));
+
+        undo_rebalance(&results, None, None);
+        assert_eq!(fs::read(root.join("train/images/a.png")).unwrap(), b"train a");
+        assert!(root.join("train/labels/a.txt").exists());
+        assert!(!root.join("val/images/a_2.png").exists());
+        assert!(!root.join("val/labels/a_2.txt").exists());
+
+        // A new file in the original place is not overwritten by the undo
+        let results = execute_rebalance_plan(&root, &plan, None, None);
+        fs::write(root.join("train/images/b.png"), b"new b").unwrap();
+        let undone = undo_rebalance(&results, None, None);
+        assert!(undone.iter().any(|r| !r.success && r.action.image_path == root.join("train/images/b.png")));
+        assert_eq!(fs::read(root.join("train/images/b.png")).unwrap(), b"new b");
+        assert_eq!(fs::read(root.join("val/images/b.png")).unwrap(), b"train b");
+        assert_eq!(fs::read(root.join("train/images/a.png")).unwrap(), b"train a");
+    }
+
+    #[test]
+    fn test_global_rebalance_never_overwrites_a_shared_name() {
+        let tmp = TempDir::new("global_collision_test");
+        let root = tmp.path().to_path_buf();
+        for split in ["train", "val", "test"] {
+            fs::create_dir_all(root.join(split).join("images")).unwrap();
+            fs::create_dir_all(root.join(split).join("labels")).unwrap();
+        }
+        // Train and val both hold a.png and both send it to test
+        for split in ["train", "val"] {
+            fs::write(root.join(split).join("images/a.png"), format!("{} a", split)).unwrap();
+            fs::write(root.join(split).join("labels/a.txt"), format!("# {}\n", split)).unwrap();
+        }
+
+        let group = |from: DatasetSplit| GlobalMoveAction {
+            from_split: from,
+            to_split: DatasetSplit::Test,
+            category: ImageCategory::Background,
+            count: 1,
+            actions: vec![MoveAction {
+                image_path: images_dir(&root, from).join("a.png"),
+                label_path: Some(labels_dir(&root, from).join("a.txt")),
+                category: ImageCategory::Background,
+                from_split: from,
+                to_split: DatasetSplit::Test,
+            }],
+        };
+        let mut plan = GlobalRebalancePlan {
+            moves: vec![group(DatasetSplit::Train), group(DatasetSplit::Val)],
+            total_moves: 2,
+            ..GlobalRebalancePlan::new()
+        };
+        plan.flag_collisions(&root);
+        assert_eq!(plan.collisions, vec![root.join("val/images/a.png")]);
+
+        // Skip keeps the first arrival and leaves the second in place
+        let results = execute_global_rebalance_plan(&root, &plan, None, None);
+        assert!(results[0].success && !results[1].success);
+        assert!(results[1].error.is_some());
+        assert_eq!(fs::read(root.join("test/images/a.png")).unwrap(), b"train a");
+        assert_eq!(fs::read(root.join("test/labels/a.txt")).unwrap(), b"# train\n");
+        assert_eq!(fs::read(root.join("val/images/a.png")).unwrap(), b"val a");
+        undo_rebalance(&results, None, None);
+        assert_eq!(fs::read(root.join("train/images/a.png")).unwrap(), b"train a");
+
+        // Rename gives the second arrival a suffix shared by image and label
+        plan.collision_policy = CollisionPolicy::Rename;
+        let results = execute_global_rebalance_plan(&root, &plan, None, None);
+        assert!(results.iter().all(|r| r.success && r.error.is_none()));
+        assert_eq!(fs::read(root.join("test/images/a.png")).unwrap(), b"train a");
+        assert_eq!(fs::read(root.join("test/images/a_1.png")).unwrap(), b"val a");
+        assert_eq!(fs::read(root.join("test/labels/a_1.txt")).unwrap(), b"# val\n");
+
+        undo_rebalance(&results, None, None);
+        assert_eq!(fs::read(root.join("val/images/a.png")).unwrap(), b"val a");
+        assert_eq!(fs::read(root.join("val/labels/a.txt")).unwrap(), b"# val\n");
+        assert!(!root.join("test/images/a_1.png").exists());
+    }
+
+    #[test]
+    fn test_estimate_post_rebalance_quality() {
+        let target = TargetRatios::default();
+        let current = BalanceStats {
+            total_images: 100,
+            ct_only: 60,
+            background: 40,
+            ..BalanceStats::new()
+        };
+        let projected = BalanceStats {
+            total_images: 80,
+            ct_only: 60,
+            background: 20,
+            ..BalanceStats::new()
+        };
+        let plan = RebalancePlan {
+            current_stats: Some(current),
+            projected_stats: Some(projected),
+            ..RebalancePlan::new()
+        };
+        let (before, after) = estimate_post_rebalance_quality(&plan, &target);
+        // 60% players, 40% background, 0% hard cases
+        assert!((before - 0.6).abs() < 1e-6);
+        // 75% players, 25% background
+        assert!((after - 0.3).abs() < 1e-6);
+        assert_eq!(estimate_post_rebalance_quality(&RebalancePlan::new(), &target), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_drop_stale_actions_updates_counts_and_stats() {
+        let tmp = TempDir::new("rebalance_stale_test");
+        let root = tmp.path().to_path_buf();
+        fs::write(root.join("kept.png"), b"png").unwrap();
+        let action = |name: &str| MoveAction {
+            image_path: root.join(name),
+            label_path: None,
+            category: ImageCategory::Background,
+            from_split: DatasetSplit::Train,
+            to_split: DatasetSplit::Val,
+        };
+        let stats = |background: usize| BalanceStats {
+            total_images: background,
+            background,
+            ..BalanceStats::new()
+        };
+
+        let mut plan = RebalancePlan {
+            actions: vec![action("kept.png"), action("deleted.png")],
+            count_to_move: 2,
+            current_stats: Some(stats(10)),
+            ..RebalancePlan::new()
+        };
+        assert_eq!(plan.stale_count(), 1);
+        assert_eq!(plan.drop_stale_actions(), 1);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan.count_to_move, 1);
+        assert_eq!(plan.current_stats.as_ref().unwrap().background, 9);
+
+        let mut global = GlobalRebalancePlan {
+            moves: vec![
+                GlobalMoveAction {
+                    from_split: DatasetSplit::Train,
+                    to_split: DatasetSplit::Val,
+                    category: ImageCategory::Background,
+                    count: 2,
+                    actions: vec![action("kept.png"), action("deleted.png")],
+                },
+                GlobalMoveAction {
+                    from_split: DatasetSplit::Train,
+                    to_split: DatasetSplit::Test,
+                    category: ImageCategory::Background,
+                    count: 1,
+                    actions: vec![action("gone.png")],
+                },
+            ],
+            projected_stats: Some(GlobalBalanceStats {
+                train: stats(7),
+                val: stats(2),
+                test: stats(1),
+                missing: Vec::new(),
+            }),
+            total_moves: 3,
+            ..GlobalRebalancePlan::new()
+        };
+        assert_eq!(global.stale_count(), 2);
+        assert_eq!(global.drop_stale_actions(), 2);
+        assert_eq!(global.moves.len(), 1);
+        assert_eq!(global.moves[0].count, 1);
+        assert_eq!(global.total_moves, 1);
+        let projected = global.projected_stats.as_ref().unwrap();
+        assert_eq!((projected.train.background, projected.val.background, projected.test.background), (7, 1, 0));
+    }
+
+    #[test]
+    fn test_analyze_all_splits_with_progress_reports_each_split() {
+        let tmp = TempDir::new("all_splits_progress_test");
+        let root = tmp.path().to_path_buf();
+        for split in ["train", "test"] {
+            fs::create_dir_all(root.join(split).join("images")).unwrap();
+            fs::create_dir_all(root.join(split).join("labels")).unwrap();
+        }
+        fs::write(root.join("train/images/a.png"), b"a").unwrap();
+        fs::write(root.join("train/labels/a.txt"), "1 0.5 0.5 0.1 0.1\n").unwrap();
+        fs::write(root.join("train/images/b.png"), b"b").unwrap();
+        fs::write(root.join("test/images/c.png"), b"c").unwrap();
+
+        let (tx, rx) = channel();
+        let stats = analyze_all_splits_with_progress(&root, &[0, 1], tx, Arc::new(AtomicBool::new(false)));
+        assert_eq!(stats.train.total_images, 2);
+        assert_eq!(stats.train.ct_only, 1);
+        assert_eq!(stats.val.total_images, 0);
+        assert_eq!(stats.test.background, 1);
+        assert_eq!(stats.missing, vec![DatasetSplit::Val]);
+
+        let completed: Vec<DatasetSplit> = rx
+            .try_iter()
+            .filter_map(|message| match message {
+                AllSplitsProgressMessage::SplitComplete { split, .. } => Some(split),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(completed, DatasetSplit::all().to_vec());
+
+        // A pre-set cancel flag stops before the first split finishes
+        let (tx, rx) = channel();
+        let stats = analyze_all_splits_with_progress(&root, &[0, 1], tx, Arc::new(AtomicBool::new(true)));
+        assert_eq!(stats.total_images(), 0);
+        assert!(matches!(rx.try_iter().last(), Some(AllSplitsProgressMessage::Cancelled(_))));
+    }
+
+    #[test]
+    fn test_global_stats_combined() {
+        let mut global = GlobalBalanceStats::default();
+        global.train.total_images = 10;
+        global.train.background = 2;
+        global.train.location_counts.insert("A".to_string(), 4);
+        global.test.total_images = 5;
+        global.test.background = 1;
+        global.test.location_counts.insert("A".to_string(), 1);
+
+        let combined = global.combined();
+        assert_eq!(combined.total_images, 15);
+        assert_eq!(combined.background, 3);
+        assert_eq!(combined.location_counts["A"], 5);
+    }
+
+    #[test]
+    fn test_split_ratios_normalize() {
+        let mut ratios = SplitRatios { train: 0.8, val: 0.3, test: 0.1 };
+        assert!(!ratios.is_valid());
+        ratios.normalize();
+        assert!(ratios.is_valid());
+        assert!((ratios.train - 0.8 / 1.2).abs() < 1e-6);
+        assert!(SplitRatios::default().is_valid());
+
+        // Nothing to scale: stays invalid
+        let mut zero = SplitRatios { train: 0.0, val: 0.0, test: 0.0 };
+        zero.normalize();
+        assert!(!zero.is_valid());
+        assert!(!SplitRatios { train: 1.2, val: -0.2, test: 0.0 }.is_valid());
+    }
+
+    #[test]
+    fn test_global_plan_leaves_missing_split_alone() {
+        let tmp = TempDir::new("missing_split_test");
+        let root = tmp.path().to_path_buf();
+        for split in ["train", "val"] {
+            fs::create_dir_all(root.join(split).join("images")).unwrap();
+            fs::create_dir_all(root.join(split).join("labels")).unwrap();
+        }
+        for i in 0..100 {
+            fs::write(root.join(format!("train/images/{:03}.png", i)), b"x").unwrap();
+        }
+
+        let ratios = SplitRatios::default().renormalized(&[DatasetSplit::Test]);
+        assert_eq!(ratios.test, 0.0);
+        assert!((ratios.train + ratios.val - 1.0).abs() < 1e-6);
+
+        // Train 78 / val 22 over the existing splits; test is not invented
+        let plan = calculate_global_rebalance_plan(&root, &GlobalRebalanceConfig::default());
+        assert_eq!(plan.current_stats.as_ref().unwrap().missing, vec![DatasetSplit::Test]);
+        assert!(plan.moves.iter().all(|group| group.to_split == DatasetSplit::Val));
+        assert_eq!(plan.total_moves, 22);
+
+        let config = GlobalRebalanceConfig {
+            create_missing_splits: true,
+            ..Default::default()
+        };
+        let plan = calculate_global_rebalance_plan(&root, &config);
+        assert!(plan.moves.iter().any(|group| group.to_split == DatasetSplit::Test));
+    }
+
+    #[test]
+    fn test_global_plan_fills_or_skips_empty_test_split() {
+        let tmp = TempDir::new("empty_split_test");
+        let root = tmp.path().to_path_buf();
+        for split in ["train", "val", "test"] {
+            fs::create_dir_all(root.join(split).join("images")).unwrap();
+            fs::create_dir_all(root.join(split).join("labels")).unwrap();
+        }
+        for i in 0..80 {
+            fs::write(root.join(format!("train/images/{:03}.png", i)), b"x").unwrap();
+        }
+        for i in 0..20 {
+            fs::write(root.join(format!("val/images/{:03}.png", i)), b"x").unwrap();
+        }
+
+        // Test is present but empty: it gets round(100 * 0.10) images, all from train
+        let plan = calculate_global_rebalance_plan(&root, &GlobalRebalanceConfig::default());
+        assert!(plan.current_stats.as_ref().unwrap().missing.is_empty());
+        let to_test: usize = plan
+            .moves
+            .iter()
+            .filter(|group| group.to_split == DatasetSplit::Test)
+            .map(|group| group.actions.len())
+            .sum();
+        assert_eq!(to_test, 10);
+        assert!(plan
+            .moves
+            .iter()
+            .filter(|group| group.to_split == DatasetSplit::Test)
+            .all(|group| group.from_split == DatasetSplit::Train));
+        assert_eq!(plan.projected_stats.as_ref().unwrap().test.total_images, 10);
+
+        // Skipped: nothing moves into test, train/val are planned on their own
+        let config = GlobalRebalanceConfig {
+            balance_empty_splits: false,
+            ..Default::default()
+        };
+        let ratios = config.effective_split_ratios(plan.current_stats.as_ref().unwrap());
+        assert_eq!(ratios.test, 0.0);
+        let plan = calculate_global_rebalance_plan(&root, &config);
+        assert!(plan.moves.iter().all(|group| group.to_split != DatasetSplit::Test));
+        assert_eq!(plan.projected_stats.as_ref().unwrap().test.total_images, 0);
+    }
+
+    #[test]
+    fn test_global_plan_fills_or_skips_empty_val_split() {
+        let tmp = TempDir::new("empty_val_split_test");
+        let root = tmp.path().to_path_buf();
+        for split in ["train", "val", "test"] {
+            fs::create_dir_all(root.join(split).join("images")).unwrap();
+            fs::create_dir_all(root.join(split).join("labels")).unwrap();
+        }
+        for i in 0..80 {
+            fs::write(root.join(format!("train/images/{:03}.png", i)), b"x").unwrap();
+        }
+        for i in 0..20 {
+            fs::write(root.join(format!("test/images/{:03}.png", i)), b"x").unwrap();
+        }
+
+        // Val is present but empty: it gets round(100 * 0.20) images, starting from train
+        let plan = calculate_global_rebalance_plan(&root, &GlobalRebalanceConfig::default());
+        let into_val: Vec<&GlobalMoveAction> = plan
+            .moves
+            .iter()
+            .filter(|group| group.to_split == DatasetSplit::Val)
+            .collect();
+        assert_eq!(into_val.iter().map(|group| group.actions.len()).sum::<usize>(), 20);
+        assert_eq!(into_val[0].from_split, DatasetSplit::Train);
+        let projected = plan.projected_stats.as_ref().unwrap();
+        assert_eq!(
+            (projected.train.total_images, projected.val.total_images, projected.test.total_images),
+            (70, 20, 10)
+        );
+
+        // Skipped: nothing moves into val
+        let config = GlobalRebalanceConfig {
+            balance_empty_splits: false,
+            ..Default::default()
+        };
+        let plan = calculate_global_rebalance_plan(&root, &config);
+        assert!(plan.moves.iter().all(|group| group.to_split != DatasetSplit::Val));
+        assert_eq!(plan.projected_stats.as_ref().unwrap().val.total_images, 0);
+    }
+
+    #[test]
+    fn test_global_plan_moves_the_categories_the_target_ratios_ask_for() {
+        let tmp = TempDir::new("global_target_ratios_test");
+        let root = tmp.path().to_path_buf();
+        for split in ["train", "val", "test"] {
+            fs::create_dir_all(root.join(split).join("images")).unwrap();
+            fs::create_dir_all(root.join(split).join("labels")).unwrap();
+        }
+        let image = |split: &str, name: String, label: &str| {
+            fs::write(root.join(split).join("images").join(format!("{}.png", name)), b"x").unwrap();
+            fs::write(root.join(split).join("labels").join(format!("{}.txt", name)), label).unwrap();
+        };
+        // 20 images at 70/20/10: train has 2 too many, val 2 too few
+        for i in 0..8 {
+            image("train", format!("bg_{}", i), "");
+            image("train", format!("ct_{}", i), "1 0.5 0.5 0.1 0.1\n");
+        }
+        for split in ["val", "test"] {
+            for i in 0..2 {
+                image(split, format!("ct_{}", i), "1 0.5 0.5 0.1 0.1\n");
+            }
+        }
+        let moved = |plan: &GlobalRebalancePlan| -> Vec<ImageCategory> {
+            plan.all_actions().iter().map(|action| action.category).collect()
+        };
+
+        // Default 85% players: val needs players most
+        let plan = calculate_global_rebalance_plan(&root, &GlobalRebalanceConfig::default());
+        assert_eq!(moved(&plan), vec![ImageCategory::CTOnly; 2]);
+
+        // Edited targets asking for mostly backgrounds move backgrounds instead
+        let config = GlobalRebalanceConfig {
+            target_ratios: TargetRatios {
+                player_ratio: 0.10,
+                background_ratio: 0.85,
+                hardcase_ratio: 0.05,
+            },
+            ..Default::default()
+        };
+        let plan = calculate_global_rebalance_plan(&root, &config);
+        assert_eq!(moved(&plan), vec![ImageCategory::Background; 2]);
+    }
+
+    #[test]
+    fn test_select_location_stratified_is_proportional() {
+        let image = |i: usize, location: Option<&str>| ImageMetadata {
+            path: PathBuf::from(format!("img_{:03}.png", i)),
+            category: ImageCategory::Background,
+            detection_count: 0,
+            location: location.map(str::to_string),
+            max_overlap: 0.0,
+        };
+        // 60 LongDoors, 30 TSpawn, 10 without location
+        let candidates: Vec<ImageMetadata> = (0..100)
+            .map(|i| match i % 10 {
+                0..=5 => image(i, Some("LongDoors")),
+                6..=8 => image(i, Some("TSpawn")),
+                _ => image(i, None),
+            })
+            .collect();
+
+        let count_for = |selected: &[ImageMetadata], location: Option<&str>| {
+            selected
+                .iter()
+                .filter(|m| m.location.as_deref() == location)
+                .count()
+        };
+
+        let selected = select_location_stratified(candidates.clone(), 20);
+        assert_eq!(selected.len(), 20);
+        assert_eq!(count_for(&selected, Some("LongDoors")), 12);
+        assert_eq!(count_for(&selected, Some("TSpawn")), 6);
+        assert_eq!(count_for(&selected, None), 2);
+
+        // Rounding leftovers go to the largest remainders: 6.6 / 3.3 / 1.1
+        let selected = select_location_stratified(candidates.clone(), 11);
+        assert_eq!(selected.len(), 11);
+        assert_eq!(count_for(&selected, Some("LongDoors")), 7);
+        assert_eq!(count_for(&selected, Some("TSpawn")), 3);
+        assert_eq!(count_for(&selected, None), 1);
+
+        assert_eq!(select_location_stratified(candidates, 500).len(), 100);
     }
 }
@@ -0,0 +1,144 @@
+//! Per-dataset image bookmarks.
+//!
+//! Bookmarks are stored in `.cleaner/bookmarks.json` under the dataset root as
+//! a JSON array of `/`-separated paths relative to the root, so the file stays
+//! valid when the dataset folder is moved or shared.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Bookmarks file, relative to the dataset root
+pub const BOOKMARKS_FILE: &str = ".cleaner/bookmarks.json";
+
+/// The bookmarked images of one dataset (absolute paths)
+#[derive(Debug, Clone, Default)]
+pub struct Bookmarks {
+    root: Option<PathBuf>,
+    paths: BTreeSet<PathBuf>,
+}
+
+impl Bookmarks {
+    /// Load the bookmarks of the dataset at `root` (empty if there is no file yet)
+    pub fn load(root: &Path) -> Self {
+        let file = root.join(BOOKMARKS_FILE);
+        let relative: Vec<String> = match fs::read_to_string(&file) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Failed to parse bookmarks {:?}: {}", file, e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        let paths = relative
+            .iter()
+            .map(|rel| {
+                rel.split('/')
+                    .fold(root.to_path_buf(), |path, part| path.join(part))
+            })
+            .collect();
+        Self {
+            root: Some(root.to_path_buf()),
+            paths,
+        }
+    }
+
+    /// Write the bookmarks back to the dataset's bookmarks file
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(root) = &self.root else {
+            return Ok(());
+        };
+
+        let relative: Vec<String> = self
+            .paths
+            .iter()
+            .map(|path| {
+                let rel = path.strip_prefix(root).unwrap_or(path);
+                rel.components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect();
+
+        let file = root.join(BOOKMARKS_FILE);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&relative).map_err(std::io::Error::other)?;
+        fs::write(file, json)
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.paths.contains(path)
+    }
+
+    /// Bookmark `path`, or remove its bookmark. Returns whether it is now bookmarked.
+    pub fn toggle(&mut self, path: &Path) -> bool {
+        if self.paths.remove(path) {
+            false
+        } else {
+            self.paths.insert(path.to_path_buf());
+            true
+        }
+    }
+
+    /// Remove a bookmark. Returns whether it existed.
+    pub fn remove(&mut self, path: &Path) -> bool {
+        self.paths.remove(path)
+    }
+
+    /// Point a bookmark at a file's new location. Returns whether `from` was bookmarked.
+    pub fn relocate(&mut self, from: &Path, to: &Path) -> bool {
+        if self.paths.remove(from) {
+            self.paths.insert(to.to_path_buf());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Bookmarked paths in sorted order
+    pub fn iter(&self) -> impl Iterator<Item = &PathBuf> {
+        self.paths.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn bookmarks_roundtrip_as_relative_paths() {
+        let tmp = TempDir::new("bookmarks_test");
+        let root = tmp.path().to_path_buf();
+        let image = root.join("train").join("images").join("a.png");
+        let moved = root.join("val").join("images").join("a.png");
+
+        let mut bookmarks = Bookmarks::load(&root);
+        assert!(bookmarks.is_empty());
+        assert!(bookmarks.toggle(&image));
+        bookmarks.save().unwrap();
+
+        let content = fs::read_to_string(root.join(BOOKMARKS_FILE)).unwrap();
+        assert!(content.contains("\"train/images/a.png\""));
+
+        let mut loaded = Bookmarks::load(&root);
+        assert!(loaded.contains(&image));
+        assert!(loaded.relocate(&image, &moved));
+        assert!(!loaded.contains(&image));
+        assert!(loaded.contains(&moved));
+        assert!(!loaded.toggle(&moved));
+        assert_eq!(loaded.len(), 0);
+    }
+}
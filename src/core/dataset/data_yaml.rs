@@ -0,0 +1,149 @@
+//! Ultralytics-style `data.yaml` at the dataset root.
+//!
+//! Only the parts the cleaner uses are read: the class names and the images
+//! folder of each split. Split entries that are file lists (`.txt`) or that
+//! point at folders that do not exist are ignored, so a yaml copied from
+//! another machine falls back to the default layout.
+
+use super::DatasetSplit;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Dataset description file, relative to the dataset root
+pub const DATA_YAML_FILE: &str = "data.yaml";
+
+#[derive(Deserialize)]
+struct RawDataYaml {
+    #[serde(default)]
+    path: Option<PathBuf>,
+    #[serde(default)]
+    train: Option<SplitEntry>,
+    #[serde(default)]
+    val: Option<SplitEntry>,
+    #[serde(default)]
+    test: Option<SplitEntry>,
+    #[serde(default)]
+    names: Option<ClassNames>,
+}
+
+/// A split is a folder, or a list of folders (only the first is used)
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SplitEntry {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// `names: [a, b]` or `names: {0: a, 1: b}`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ClassNames {
+    List(Vec<String>),
+    Map(BTreeMap<u32, String>),
+}
+
+/// What the cleaner takes from a dataset's `data.yaml`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DataYaml {
+    /// Class names by class id
+    pub names: Vec<(u32, String)>,
+    /// Images folder of each split listed in the file
+    pub split_images: HashMap<DatasetSplit, PathBuf>,
+}
+
+impl DataYaml {
+    /// Read `data.yaml` from `root`; `None` if the dataset has none
+    pub fn load(root: &Path) -> Option<Result<Self, String>> {
+        let content = fs::read_to_string(root.join(DATA_YAML_FILE)).ok()?;
+        Some(Self::parse(&content, root))
+    }
+
+    /// Parse the contents of a `data.yaml` describing the dataset at `root`
+    pub fn parse(content: &str, root: &Path) -> Result<Self, String> {
+        let raw: RawDataYaml = serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+
+        let names = match raw.names {
+            Some(ClassNames::List(names)) => (0..).zip(names).collect(),
+            Some(ClassNames::Map(names)) => names.into_iter().collect(),
+            None => Vec::new(),
+        };
+
+        // `path` is relative to the root; a missing folder means the yaml was
+        // written elsewhere, so the split entries are resolved against the root
+        let base = raw
+            .path
+            .map(|path| root.join(path))
+            .filter(|path| path.is_dir())
+            .unwrap_or_else(|| root.to_path_buf());
+
+        let mut split_images = HashMap::new();
+        for (split, entry) in [
+            (DatasetSplit::Train, raw.train),
+            (DatasetSplit::Val, raw.val),
+            (DatasetSplit::Test, raw.test),
+        ] {
+            let first = match entry {
+                Some(SplitEntry::One(path)) => Some(path),
+                Some(SplitEntry::Many(paths)) => paths.into_iter().next(),
+                None => None,
+            };
+            let Some(first) = first else {
+                continue;
+            };
+            let dir = base.join(first);
+            if dir.is_dir() {
+                split_images.insert(split, dir);
+            }
+        }
+
+        Ok(Self {
+            names,
+            split_images,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_parse_names_and_split_folders() {
+        let tmp = TempDir::new("data_yaml_test");
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("images").join("train")).unwrap();
+
+        let yaml = "path: .\n\
+                    train: images/train\n\
+                    val: images/val\n\
+                    test: test.txt\n\
+                    names:\n  0: T\n  1: CT\n  3: Chicken\n";
+        let data = DataYaml::parse(yaml, &root).unwrap();
+        assert_eq!(
+            data.names,
+            vec![
+                (0, "T".to_string()),
+                (1, "CT".to_string()),
+                (3, "Chicken".to_string())
+            ]
+        );
+        // val does not exist and test is a file list, so only train is taken
+        assert_eq!(data.split_images.len(), 1);
+        assert!(data
+            .split_images
+            .get(&DatasetSplit::Train)
+            .is_some_and(|dir| dir.ends_with("images/train") && dir.is_dir()));
+
+        let list = DataYaml::parse("names: [T, CT]\n", &root).unwrap();
+        assert_eq!(
+            list.names,
+            vec![(0, "T".to_string()), (1, "CT".to_string())]
+        );
+        assert!(list.split_images.is_empty());
+
+        assert!(DataYaml::parse("names: [T, CT\n", &root).is_err());
+    }
+}
@@ -1,8 +1,12 @@
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::fs;
 use tracing::{info, warn};
 use serde::{Deserialize, Serialize};
 
+use super::data_yaml::DataYaml;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DatasetSplit {
     Train,
@@ -18,12 +22,112 @@ impl DatasetSplit {
             DatasetSplit::Test => "test",
         }
     }
+
+    /// All splits in their canonical order
+    pub fn all() -> [DatasetSplit; 3] {
+        [DatasetSplit::Train, DatasetSplit::Val, DatasetSplit::Test]
+    }
+
+    /// Parse a split from its folder name (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "train" => Some(DatasetSplit::Train),
+            "val" => Some(DatasetSplit::Val),
+            "test" => Some(DatasetSplit::Test),
+            _ => None,
+        }
+    }
+}
+
+/// How the images of a dataset root are organized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutKind {
+    /// `train/`, `val/` and `test/` folders, each with `images/` and `labels/`
+    #[default]
+    Split,
+    /// `images/` and `labels/` directly under the root, without split folders.
+    /// The root is exposed as a single pseudo-split in the train slot.
+    Flat,
+}
+
+impl LayoutKind {
+    /// Detect the layout of a dataset root (split folders, or split paths in a
+    /// `data.yaml`, win over a root `images/`)
+    pub fn detect(root: &Path) -> Self {
+        let has_split = DatasetSplit::all()
+            .iter()
+            .any(|split| root.join(split.as_str()).join("images").is_dir())
+            || data_yaml_images_dirs(root)
+                .is_some_and(|dirs| dirs.values().any(|dir| *dir != root.join("images")));
+        if !has_split && root.join("images").is_dir() {
+            LayoutKind::Flat
+        } else {
+            LayoutKind::Split
+        }
+    }
+}
+
+/// Folder holding the `images/` and `labels/` of `split` under `root`
+///
+/// For a flat root this is the root itself in the train slot; the other
+/// splits map to folders that do not exist, so they read as empty.
+pub fn split_dir(root: &Path, split: DatasetSplit) -> PathBuf {
+    if split == DatasetSplit::Train && LayoutKind::detect(root) == LayoutKind::Flat {
+        root.to_path_buf()
+    } else {
+        root.join(split.as_str())
+    }
+}
+
+/// Split images folders listed in the root's `data.yaml` (if it parses)
+fn data_yaml_images_dirs(root: &Path) -> Option<HashMap<DatasetSplit, PathBuf>> {
+    DataYaml::load(root)?.ok().map(|yaml| yaml.split_images)
+}
+
+/// Images folder of `split` under `root`
+///
+/// A split path in the root's `data.yaml` wins over the default layout.
+pub fn images_dir(root: &Path, split: DatasetSplit) -> PathBuf {
+    data_yaml_images_dirs(root)
+        .and_then(|mut dirs| dirs.remove(&split))
+        .unwrap_or_else(|| split_dir(root, split).join("images"))
+}
+
+/// Whether `split` has an images folder under `root`
+pub fn split_exists(root: &Path, split: DatasetSplit) -> bool {
+    images_dir(root, split).is_dir()
+}
+
+/// Labels folder of `split` under `root`: the images folder with its last
+/// `images` component replaced by `labels` (`images/train` → `labels/train`)
+pub fn labels_dir(root: &Path, split: DatasetSplit) -> PathBuf {
+    let images = images_dir(root, split);
+    let components: Vec<_> = images.components().collect();
+    match components.iter().rposition(|c| c.as_os_str() == "images") {
+        Some(idx) => components
+            .iter()
+            .enumerate()
+            .map(|(i, c)| if i == idx { Path::new("labels") } else { Path::new(c.as_os_str()) })
+            .collect(),
+        None => images.with_file_name("labels"),
+    }
 }
 
 pub struct Dataset {
+    /// Primary dataset root (used by analysis, rebalancing and settings)
     dataset_path: Option<PathBuf>,
+    /// Additional roots whose images are merged into the view
+    secondary_paths: Vec<PathBuf>,
+    /// Layout of the primary root
+    layout: LayoutKind,
+    /// The primary root's `data.yaml`, or why it could not be parsed
+    data_yaml: Option<Result<DataYaml, String>>,
     current_split: DatasetSplit,
     image_files: Vec<PathBuf>,
+    /// Image counts per split, filled lazily by `image_count_for_split`
+    count_cache: RefCell<HashMap<DatasetSplit, usize>>,
+    /// Bumped every time `image_files` is rescanned
+    generation: u64,
 }
 
 impl Default for Dataset {
@@ -36,49 +140,114 @@ impl Dataset {
     pub fn new() -> Self {
         Self {
             dataset_path: None,
+            secondary_paths: Vec::new(),
+            layout: LayoutKind::Split,
+            data_yaml: None,
             current_split: DatasetSplit::Train,
             image_files: Vec::new(),
+            count_cache: RefCell::new(HashMap::new()),
+            generation: 0,
         }
     }
     
+    /// Whether `path` looks like a dataset root, i.e. has at least one `<split>/images`
+    /// folder, a flat `images/` folder or a `data.yaml`
+    pub fn is_dataset_root(path: &Path) -> bool {
+        path.join("images").is_dir()
+            || path.join(super::data_yaml::DATA_YAML_FILE).is_file()
+            || DatasetSplit::all()
+                .iter()
+                .any(|split| path.join(split.as_str()).join("images").is_dir())
+    }
+
     pub fn load(&mut self, path: PathBuf) {
         self.dataset_path = Some(path);
+        self.secondary_paths.clear();
+        self.detect_layout();
+        self.load_current_split();
+    }
+
+    /// Load several dataset roots and merge their images into one view
+    ///
+    /// The first path becomes the primary root. Images keep their absolute
+    /// paths, so label lookup and deletion stay inside the tree they came from.
+    pub fn load_multiple(&mut self, paths: Vec<PathBuf>) {
+        let mut paths = paths.into_iter();
+        self.dataset_path = paths.next();
+        self.secondary_paths = paths.collect();
+        self.detect_layout();
+        self.load_current_split();
+    }
+
+    /// Detect the primary root's layout, falling back to the train slot for flat roots
+    fn detect_layout(&mut self) {
+        self.refresh_data_yaml();
+        self.layout = self
+            .dataset_path
+            .as_deref()
+            .map_or(LayoutKind::Split, LayoutKind::detect);
+        if !self.has_split(self.current_split) {
+            self.current_split = DatasetSplit::Train;
+        }
+    }
+
+    /// Add another dataset root to the merged view
+    pub fn add_secondary_path(&mut self, path: PathBuf) {
+        if self.roots().any(|root| root == &path) {
+            warn!("Dataset root already loaded: {:?}", path);
+            return;
+        }
+        self.secondary_paths.push(path);
         self.load_current_split();
     }
     
     pub fn load_current_split(&mut self) {
+        self.generation += 1;
         self.image_files.clear();
-        
-        if let Some(base_path) = &self.dataset_path {
-            // Navigate to split/images folder
-            let images_path = base_path
-                .join(self.current_split.as_str())
-                .join("images");
-            
-            // Load all image files from the split directory
-            if let Ok(entries) = fs::read_dir(&images_path) {
-                info!("Reading images from: {:?}", images_path);
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if let Some(ext) = path.extension() {
-                        let ext = ext.to_string_lossy().to_lowercase();
-                        if ext == "png" || ext == "jpg" || ext == "jpeg" {
-                            self.image_files.push(path);
-                        }
-                    }
-                }
-                info!("Found {} images in {:?}", self.image_files.len(), images_path);
-            } else {
-                warn!("Failed to read directory: {:?}", images_path);
-            }
-            
-            // Sort files for consistent ordering
-            self.image_files.sort();
+        // Files may have moved between splits since the last scan
+        self.invalidate_count_cache();
+
+        self.image_files = self.scan_all_roots(self.current_split);
+        if self.dataset_path.is_some() {
+            self.count_cache
+                .borrow_mut()
+                .insert(self.current_split, self.image_files.len());
+        }
+    }
+
+    /// Scan a split in every root, merged and ordered by filename
+    fn scan_all_roots(&self, split: DatasetSplit) -> Vec<PathBuf> {
+        let mut image_files: Vec<PathBuf> = self
+            .roots()
+            .flat_map(|root| scan_split_images(root, split))
+            .collect();
+        if !self.secondary_paths.is_empty() {
+            image_files.sort_by(|a, b| a.file_name().cmp(&b.file_name()).then_with(|| a.cmp(b)));
         }
+        image_files
+    }
+
+    /// Number of images in a split, scanning its folder only on first use
+    pub fn image_count_for_split(&self, split: DatasetSplit) -> usize {
+        if let Some(&count) = self.count_cache.borrow().get(&split) {
+            return count;
+        }
+
+        let count = self
+            .roots()
+            .map(|root| scan_split_images(root, split).len())
+            .sum();
+        self.count_cache.borrow_mut().insert(split, count);
+        count
+    }
+
+    /// Forget cached split counts so they are rescanned on next request
+    pub fn invalidate_count_cache(&mut self) {
+        self.count_cache.get_mut().clear();
     }
     
     pub fn change_split(&mut self, new_split: DatasetSplit) {
-        if self.current_split != new_split {
+        if self.current_split != new_split && self.has_split(new_split) {
             self.current_split = new_split;
             self.load_current_split();
         }
@@ -88,11 +257,191 @@ impl Dataset {
         &self.image_files
     }
     
+    /// Identifies the current file list; changes whenever the split is rescanned,
+    /// so indices computed for an older list can be recognized as stale
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
     pub fn current_split(&self) -> DatasetSplit {
         self.current_split
     }
+
+    pub fn layout(&self) -> LayoutKind {
+        self.layout
+    }
+
+    /// Re-read the primary root's `data.yaml` (e.g. after it was written)
+    pub fn refresh_data_yaml(&mut self) {
+        self.data_yaml = self.dataset_path.as_deref().and_then(DataYaml::load);
+    }
+
+    /// The primary root's `data.yaml` (`Err` with the parse error if it is invalid)
+    pub fn data_yaml(&self) -> Option<&Result<DataYaml, String>> {
+        self.data_yaml.as_ref()
+    }
+
+    /// Whether `split` can be shown (a flat dataset only has the train slot)
+    pub fn has_split(&self, split: DatasetSplit) -> bool {
+        self.layout == LayoutKind::Split || split == DatasetSplit::Train
+    }
     
     pub fn dataset_path(&self) -> Option<&PathBuf> {
         self.dataset_path.as_ref()
     }
+
+    /// All loaded roots, primary first
+    pub fn roots(&self) -> impl Iterator<Item = &PathBuf> {
+        self.dataset_path.iter().chain(self.secondary_paths.iter())
+    }
+
+    /// Whether more than one dataset root is loaded
+    pub fn is_multi_root(&self) -> bool {
+        self.dataset_path.is_some() && !self.secondary_paths.is_empty()
+    }
+
+    /// The root an image belongs to
+    pub fn root_for_image(&self, image_path: &Path) -> Option<&PathBuf> {
+        self.roots().find(|root| image_path.starts_with(root))
+    }
+}
+
+/// List all image files in `split/images`, sorted for consistent ordering
+fn scan_split_images(base_path: &Path, split: DatasetSplit) -> Vec<PathBuf> {
+    let images_path = images_dir(base_path, split);
+    let mut image_files = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(&images_path) {
+        info!("Reading images from: {:?}", images_path);
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if let Some(ext) = path.extension() {
+                let ext = ext.to_string_lossy().to_lowercase();
+                if ext == "png" || ext == "jpg" || ext == "jpeg" {
+                    image_files.push(path);
+                }
+            }
+        }
+        info!("Found {} images in {:?}", image_files.len(), images_path);
+    } else {
+        warn!("Failed to read directory: {:?}", images_path);
+    }
+
+    image_files.sort();
+    image_files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_image_count_for_split_is_cached_until_reload() {
+        let tmp = TempDir::new("yolo_dataset_count_test");
+        let root = tmp.path().to_path_buf();
+        let val_images = root.join("val").join("images");
+        fs::create_dir_all(root.join("train").join("images")).unwrap();
+        fs::create_dir_all(&val_images).unwrap();
+        fs::write(root.join("train").join("images").join("a.png"), b"").unwrap();
+        fs::write(val_images.join("b.jpg"), b"").unwrap();
+        fs::write(val_images.join("notes.txt"), b"").unwrap();
+
+        let mut dataset = Dataset::new();
+        dataset.load(root.clone());
+        assert_eq!(dataset.image_count_for_split(DatasetSplit::Train), 1);
+        assert_eq!(dataset.image_count_for_split(DatasetSplit::Val), 1);
+        assert_eq!(dataset.image_count_for_split(DatasetSplit::Test), 0);
+
+        // New files are not seen until the cache is invalidated
+        fs::write(val_images.join("c.png"), b"").unwrap();
+        assert_eq!(dataset.image_count_for_split(DatasetSplit::Val), 1);
+        dataset.load_current_split();
+        assert_eq!(dataset.image_count_for_split(DatasetSplit::Val), 2);
+    }
+
+    #[test]
+    fn test_is_dataset_root_requires_split_images_folder() {
+        let tmp = TempDir::new("yolo_dataset_root_test");
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("val")).unwrap();
+        assert!(!Dataset::is_dataset_root(&root));
+
+        fs::create_dir_all(root.join("val").join("images")).unwrap();
+        assert!(Dataset::is_dataset_root(&root));
+        // A split folder on its own reads as a flat dataset
+        assert_eq!(LayoutKind::detect(&root.join("val")), LayoutKind::Flat);
+        assert!(!Dataset::is_dataset_root(&root.join("val").join("images")));
+    }
+
+    #[test]
+    fn test_flat_layout_is_loaded_as_train_slot() {
+        let tmp = TempDir::new("yolo_dataset_flat_test");
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("images")).unwrap();
+        fs::create_dir_all(root.join("labels")).unwrap();
+        fs::write(root.join("images").join("a.png"), b"").unwrap();
+        fs::write(root.join("images").join("b.jpg"), b"").unwrap();
+        assert!(Dataset::is_dataset_root(&root));
+        assert_eq!(LayoutKind::detect(&root), LayoutKind::Flat);
+        assert_eq!(split_dir(&root, DatasetSplit::Train), root);
+
+        let mut dataset = Dataset::new();
+        dataset.change_split(DatasetSplit::Val);
+        dataset.load(root.clone());
+        assert_eq!(dataset.layout(), LayoutKind::Flat);
+        assert_eq!(dataset.current_split(), DatasetSplit::Train);
+        assert_eq!(dataset.get_image_files().len(), 2);
+        assert_eq!(dataset.image_count_for_split(DatasetSplit::Val), 0);
+        assert_eq!(
+            crate::core::operations::get_label_path_for_image(&dataset.get_image_files()[0]),
+            Some(root.join("labels").join("a.txt"))
+        );
+
+        // Other splits cannot be selected
+        dataset.change_split(DatasetSplit::Test);
+        assert_eq!(dataset.current_split(), DatasetSplit::Train);
+
+        // A split folder makes it a normal split layout again
+        fs::create_dir_all(root.join("val").join("images")).unwrap();
+        assert_eq!(LayoutKind::detect(&root), LayoutKind::Split);
+    }
+
+    #[test]
+    fn test_load_multiple_merges_roots_by_filename() {
+        let tmp = TempDir::new("yolo_dataset_multi_test");
+        let base = tmp.path().to_path_buf();
+        let players = base.join("players");
+        let backgrounds = base.join("backgrounds");
+        for (root, name) in [(&players, "b.png"), (&backgrounds, "a.png"), (&players, "c.png")] {
+            let images = root.join("train").join("images");
+            fs::create_dir_all(&images).unwrap();
+            fs::write(images.join(name), b"").unwrap();
+        }
+
+        let mut dataset = Dataset::new();
+        dataset.load_multiple(vec![players.clone(), backgrounds.clone()]);
+        assert!(dataset.is_multi_root());
+        assert_eq!(dataset.dataset_path(), Some(&players));
+
+        let names: Vec<_> = dataset
+            .get_image_files()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["a.png", "b.png", "c.png"]);
+        assert_eq!(dataset.image_count_for_split(DatasetSplit::Train), 3);
+
+        // Each image keeps its own tree for label lookups
+        let first = &dataset.get_image_files()[0];
+        assert_eq!(dataset.root_for_image(first), Some(&backgrounds));
+        assert_eq!(
+            crate::core::operations::get_label_path_for_image(first),
+            Some(backgrounds.join("train").join("labels").join("a.txt"))
+        );
+
+        dataset.load(players.clone());
+        assert!(!dataset.is_multi_root());
+        assert_eq!(dataset.get_image_files().len(), 2);
+    }
 }
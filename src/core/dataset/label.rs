@@ -1,5 +1,6 @@
 use std::fs;
-use std::path::PathBuf;
+use std::io::ErrorKind;
+use std::path::Path;
 
 #[derive(Debug, Clone)]
 pub struct YoloDetection {
@@ -8,6 +9,105 @@ pub struct YoloDetection {
     pub y_center: f32,
     pub width: f32,
     pub height: f32,
+    /// Auto-labeler confidence from an optional 6th column
+    pub confidence: Option<f32>,
+}
+
+impl YoloDetection {
+    /// Format the detection as a label file line, keeping the confidence column if present
+    pub fn to_label_line(&self) -> String {
+        let mut line = format!(
+            "{} {:.6} {:.6} {:.6} {:.6}",
+            self.class_id, self.x_center, self.y_center, self.width, self.height
+        );
+        if let Some(confidence) = self.confidence {
+            line.push_str(&format!(" {:.4}", confidence));
+        }
+        line
+    }
+
+    /// Copy of the detection with its box clamped inside the image
+    ///
+    /// Center and size are limited to `[0, 1]` first, then any part of the
+    /// box past the image border is cut off.
+    pub fn clamped(&self) -> YoloDetection {
+        fn clamp_axis(center: f32, size: f32) -> (f32, f32) {
+            let size = size.clamp(0.0, 1.0);
+            let center = center.clamp(0.0, 1.0);
+            let min = (center - size / 2.0).max(0.0);
+            let max = (center + size / 2.0).min(1.0);
+            ((min + max) / 2.0, max - min)
+        }
+
+        let (x_center, width) = clamp_axis(self.x_center, self.width);
+        let (y_center, height) = clamp_axis(self.y_center, self.height);
+        YoloDetection {
+            x_center,
+            y_center,
+            width,
+            height,
+            ..self.clone()
+        }
+    }
+
+    /// Whether the coordinates and every box edge lie within `[0, 1]`
+    ///
+    /// Allows for the rounding of coordinates written with six decimals.
+    pub fn is_within_bounds(&self) -> bool {
+        const TOLERANCE: f32 = 1e-5;
+        let clamped = self.clamped();
+        (clamped.x_center - self.x_center).abs() <= TOLERANCE
+            && (clamped.y_center - self.y_center).abs() <= TOLERANCE
+            && (clamped.width - self.width).abs() <= TOLERANCE
+            && (clamped.height - self.height).abs() <= TOLERANCE
+    }
+}
+
+/// Intersection over union of two boxes in normalized YOLO coordinates
+fn iou(a: &YoloDetection, b: &YoloDetection) -> f32 {
+    let overlap_w = (a.x_center + a.width / 2.0).min(b.x_center + b.width / 2.0)
+        - (a.x_center - a.width / 2.0).max(b.x_center - b.width / 2.0);
+    let overlap_h = (a.y_center + a.height / 2.0).min(b.y_center + b.height / 2.0)
+        - (a.y_center - a.height / 2.0).max(b.y_center - b.height / 2.0);
+    if overlap_w <= 0.0 || overlap_h <= 0.0 {
+        return 0.0;
+    }
+
+    let intersection = overlap_w * overlap_h;
+    let union = a.width * a.height + b.width * b.height - intersection;
+    if union <= 0.0 {
+        0.0
+    } else {
+        intersection / union
+    }
+}
+
+/// Largest IoU between any two detections (0.0 with fewer than two)
+pub fn max_pairwise_iou(detections: &[YoloDetection]) -> f32 {
+    let mut max = 0.0f32;
+    for (i, a) in detections.iter().enumerate() {
+        for b in &detections[i + 1..] {
+            max = max.max(iou(a, b));
+        }
+    }
+    max
+}
+
+/// Pairs of detections (indices into `detections`) whose IoU is above `threshold`
+///
+/// Sorted by IoU, highest first.
+pub fn overlapping_pairs(detections: &[YoloDetection], threshold: f32) -> Vec<(usize, usize, f32)> {
+    let mut pairs = Vec::new();
+    for (i, a) in detections.iter().enumerate() {
+        for (j, b) in detections.iter().enumerate().skip(i + 1) {
+            let overlap = iou(a, b);
+            if overlap > threshold {
+                pairs.push((i, j, overlap));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
+    pairs
 }
 
 #[derive(Debug, Clone)]
@@ -28,10 +128,80 @@ pub struct LabelInfo {
 /// # Returns
 /// * `Some(LabelInfo)` if the file exists and can be parsed
 /// * `None` if the file doesn't exist or cannot be read
-pub fn parse_label_file(label_path: &PathBuf) -> Option<LabelInfo> {
+pub fn parse_label_file(label_path: &Path) -> Option<LabelInfo> {
     // Read and parse label file
     let content = fs::read_to_string(label_path).ok()?;
-    
+    Some(parse_label_content(&content))
+}
+
+/// Why a label file could not be parsed strictly
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelParseError {
+    FileNotFound,
+    /// The file exists but could not be read
+    Io(String),
+    /// The file is not valid UTF-8 text
+    Utf8Error,
+    /// A detection line without 5 or 6 numeric columns (1-based line number)
+    MalformedLine { line_number: usize, content: String },
+    /// A coordinate that parsed but is not a finite number (NaN or infinity)
+    InvalidCoordinate { line_number: usize, value: f32 },
+}
+
+impl std::fmt::Display for LabelParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelParseError::FileNotFound => write!(f, "file not found"),
+            LabelParseError::Io(msg) => write!(f, "cannot read file: {}", msg),
+            LabelParseError::Utf8Error => write!(f, "not valid UTF-8 text"),
+            LabelParseError::MalformedLine { line_number, content } => {
+                write!(f, "line {}: malformed detection '{}'", line_number, content)
+            }
+            LabelParseError::InvalidCoordinate { line_number, value } => {
+                write!(f, "line {}: invalid coordinate {}", line_number, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LabelParseError {}
+
+/// Parse a YOLO format label file, failing on the first problem.
+///
+/// Unlike `parse_label_file`, which skips what it cannot read, this reports
+/// why the file was rejected: a missing or unreadable file, non-UTF-8
+/// content, or the first detection line that does not parse.
+pub fn parse_label_file_strict(label_path: &Path) -> Result<LabelInfo, LabelParseError> {
+    let bytes = fs::read(label_path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => LabelParseError::FileNotFound,
+        _ => LabelParseError::Io(e.to_string()),
+    })?;
+    let content = String::from_utf8(bytes).map_err(|_| LabelParseError::Utf8Error)?;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_number = index + 1;
+        let detection = parse_detection_line(line).map_err(|_| LabelParseError::MalformedLine {
+            line_number,
+            content: line.to_string(),
+        })?;
+        let coords = [detection.x_center, detection.y_center, detection.width, detection.height];
+        if let Some(&value) = coords.iter().find(|value| !value.is_finite()) {
+            return Err(LabelParseError::InvalidCoordinate { line_number, value });
+        }
+    }
+
+    Ok(parse_label_content(&content))
+}
+
+/// Parse the contents of a YOLO format label file.
+///
+/// Detection lines have 5 columns (`class_id x_center y_center width height`)
+/// or 6 with a trailing confidence value; other lines are ignored.
+pub fn parse_label_content(content: &str) -> LabelInfo {
     let mut detections = Vec::new();
     let mut resolution = None;
     let mut map = None;
@@ -44,8 +214,8 @@ pub fn parse_label_file(label_path: &PathBuf) -> Option<LabelInfo> {
         
         // Parse metadata from comment line
         // Format: # Resolution: 2560x1440, Map: de_dust2, Location: ARamp, Position: (1324.38,3023.45,154.30), Time: 1764776144
-        if line.starts_with('#') {
-            let parts: Vec<&str> = line[1..].split(',').collect();
+        if let Some(metadata) = line.strip_prefix('#') {
+            let parts: Vec<&str> = metadata.split(',').collect();
             for part in parts {
                 let part = part.trim();
                 if let Some(res) = part.strip_prefix("Resolution:") {
@@ -61,35 +231,208 @@ pub fn parse_label_file(label_path: &PathBuf) -> Option<LabelInfo> {
                 }
             }
         } else if !line.is_empty() {
-            // Parse detection line
-            // Format: class_id x_center y_center width height
-            let values: Vec<&str> = line.split_whitespace().collect();
-            if values.len() == 5 {
-                if let (Ok(class_id), Ok(x), Ok(y), Ok(w), Ok(h)) = (
-                    values[0].parse::<u32>(),
-                    values[1].parse::<f32>(),
-                    values[2].parse::<f32>(),
-                    values[3].parse::<f32>(),
-                    values[4].parse::<f32>(),
-                ) {
-                    detections.push(YoloDetection {
-                        class_id,
-                        x_center: x,
-                        y_center: y,
-                        width: w,
-                        height: h,
-                    });
-                }
+            // Parse detection line; malformed lines are skipped
+            if let Ok(detection) = parse_detection_line(line) {
+                detections.push(detection);
             }
         }
     }
     
-    Some(LabelInfo {
+    LabelInfo {
         detections,
         resolution,
         map,
         location,
         position,
         timestamp,
+    }
+}
+
+/// Parse a single detection line.
+///
+/// Format: `class_id x_center y_center width height [confidence]`. The error
+/// describes the first column that could not be read.
+pub fn parse_detection_line(line: &str) -> Result<YoloDetection, String> {
+    let values: Vec<&str> = line.split_whitespace().collect();
+    if values.len() != 5 && values.len() != 6 {
+        return Err(format!("expected 5 or 6 columns, found {}", values.len()));
+    }
+
+    let class_id = values[0]
+        .parse::<u32>()
+        .map_err(|_| format!("invalid class id '{}'", values[0]))?;
+    let mut coords = [0.0f32; 4];
+    for (coord, (name, value)) in coords
+        .iter_mut()
+        .zip(["x_center", "y_center", "width", "height"].iter().zip(&values[1..5]))
+    {
+        *coord = value
+            .parse::<f32>()
+            .map_err(|_| format!("invalid {} '{}'", name, value))?;
+    }
+    let confidence = match values.get(5) {
+        Some(value) => Some(
+            value
+                .parse::<f32>()
+                .map_err(|_| format!("invalid confidence '{}'", value))?,
+        ),
+        None => None,
+    };
+
+    Ok(YoloDetection {
+        class_id,
+        x_center: coords[0],
+        y_center: coords[1],
+        width: coords[2],
+        height: coords[3],
+        confidence,
     })
 }
+
+/// A label file line that is neither a comment nor a valid detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelLineError {
+    /// 1-based line number
+    pub line_number: usize,
+    pub message: String,
+}
+
+/// Check every detection line of label file contents with `parse_detection_line`
+pub fn validate_label_content(content: &str) -> Vec<LabelLineError> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            parse_detection_line(line).err().map(|message| LabelLineError {
+                line_number: index + 1,
+                message,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_parse_five_and_six_column_lines() {
+        let content = "# Resolution: 2560x1440, Map: de_dust2\n\
+                       0 0.5 0.5 0.1 0.2\n\
+                       1 0.25 0.75 0.05 0.1 0.42\n\
+                       1 0.25 0.75 0.05 0.1 high\n";
+        let label = parse_label_content(content);
+
+        assert_eq!(label.map.as_deref(), Some("de_dust2"));
+        // The line with a non-numeric confidence is skipped
+        assert_eq!(label.detections.len(), 2);
+        assert_eq!(label.detections[0].confidence, None);
+        assert_eq!(label.detections[1].confidence, Some(0.42));
+    }
+
+    #[test]
+    fn test_label_line_roundtrip_keeps_confidence() {
+        let label = parse_label_content("0 0.5 0.5 0.1 0.2\n1 0.25 0.75 0.05 0.1 0.42\n");
+        let lines: Vec<String> = label.detections.iter().map(|d| d.to_label_line()).collect();
+
+        assert_eq!(lines[0], "0 0.500000 0.500000 0.100000 0.200000");
+        assert_eq!(lines[1], "1 0.250000 0.750000 0.050000 0.100000 0.4200");
+        let reparsed = parse_label_content(&lines.join("\n"));
+        assert_eq!(reparsed.detections[1].confidence, Some(0.42));
+    }
+
+    #[test]
+    fn test_clamped_keeps_box_inside_image() {
+        let inside = parse_detection_line("0 0.5 0.5 0.2 0.2").unwrap();
+        assert!(inside.is_within_bounds());
+
+        // Box crossing the right edge is cut at x = 1
+        let edge = parse_detection_line("0 0.95 0.5 0.2 0.2").unwrap();
+        assert!(!edge.is_within_bounds());
+        let clamped = edge.clamped();
+        assert!((clamped.x_center - 0.925).abs() < 1e-6);
+        assert!((clamped.width - 0.15).abs() < 1e-6);
+        assert!(clamped.is_within_bounds());
+
+        // Negative center and oversized height
+        let clamped = parse_detection_line("1 -0.1 0.5 0.1 1.4").unwrap().clamped();
+        assert_eq!((clamped.x_center, clamped.width), (0.025, 0.05));
+        assert_eq!((clamped.y_center, clamped.height), (0.5, 1.0));
+    }
+
+    #[test]
+    fn test_max_pairwise_iou() {
+        let label = parse_label_content(
+            "0 0.5 0.5 0.2 0.2\n\
+             1 0.6 0.5 0.2 0.2\n\
+             1 0.1 0.1 0.05 0.05\n",
+        );
+        // The first two boxes overlap by half their width: 0.02 / 0.06
+        let iou = max_pairwise_iou(&label.detections);
+        assert!((iou - 1.0 / 3.0).abs() < 1e-4, "iou = {}", iou);
+        assert_eq!(max_pairwise_iou(&label.detections[2..]), 0.0);
+        assert_eq!(max_pairwise_iou(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_validate_label_content_reports_bad_lines() {
+        let content = "# Map: de_mirage\n\
+                       0 0.5 0.5 0.1 0.2\n\
+                       \n\
+                       1 0.5 0.5\n\
+                       x 0.5 0.5 0.1 0.2\n\
+                       1 0.5 0.5 zero 0.2 0.9\n";
+        let errors = validate_label_content(content);
+
+        let lines: Vec<usize> = errors.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [4, 5, 6]);
+        assert_eq!(errors[0].message, "expected 5 or 6 columns, found 3");
+        assert_eq!(errors[1].message, "invalid class id 'x'");
+        assert_eq!(errors[2].message, "invalid width 'zero'");
+        // Valid contents parse to the same detections the panel shows
+        assert!(validate_label_content("0 0.5 0.5 0.1 0.2 0.8\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_label_file_strict_reports_first_error() {
+        let tmp = TempDir::new("yolo_strict_label_test");
+        let root = tmp.path().to_path_buf();
+        let write = |name: &str, bytes: &[u8]| {
+            let path = root.join(name);
+            fs::write(&path, bytes).unwrap();
+            path
+        };
+
+        let good = write("good.txt", b"# Map: de_nuke\n0 0.5 0.5 0.1 0.2\n\n1 0.2 0.2 0.1 0.1 0.9\n");
+        assert_eq!(parse_label_file_strict(&good).unwrap().detections.len(), 2);
+
+        let malformed = write("malformed.txt", b"0 0.5 0.5 0.1 0.2\n1 0.5 0.5\n2 x 0.5 0.1 0.2\n");
+        assert_eq!(
+            parse_label_file_strict(&malformed).unwrap_err(),
+            LabelParseError::MalformedLine {
+                line_number: 2,
+                content: "1 0.5 0.5".to_string()
+            }
+        );
+        // The lenient parser still returns the valid line
+        assert_eq!(parse_label_file(&malformed).unwrap().detections.len(), 1);
+
+        let nan = write("nan.txt", b"0 0.5 NaN 0.1 0.2\n");
+        assert!(matches!(
+            parse_label_file_strict(&nan),
+            Err(LabelParseError::InvalidCoordinate { line_number: 1, value }) if value.is_nan()
+        ));
+
+        let binary = write("binary.txt", &[0x30, 0x20, 0xff, 0xfe]);
+        assert_eq!(parse_label_file_strict(&binary).unwrap_err(), LabelParseError::Utf8Error);
+        assert_eq!(
+            parse_label_file_strict(&root.join("missing.txt")).unwrap_err(),
+            LabelParseError::FileNotFound
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Per-dataset single-instance lock.
+//!
+//! The instance that has a dataset open writes its PID and process start time
+//! to `.cleaner/lock` under the dataset root. A lock whose process has exited,
+//! or whose PID now belongs to a different process, is stale and replaced
+//! without asking.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tracing::{info, warn};
+
+/// Lock file, relative to the dataset root
+pub const LOCK_FILE: &str = ".cleaner/lock";
+
+/// Start times that differ by less than this are the same process (clock rounding)
+const START_TIME_TOLERANCE_SECS: u64 = 2;
+
+/// The instance holding a dataset lock
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub pid: u32,
+    /// Process start time, Unix seconds (0 if unknown)
+    pub started_at: u64,
+}
+
+impl LockInfo {
+    /// This process
+    pub fn current() -> Self {
+        let pid = std::process::id();
+        Self {
+            pid,
+            started_at: process_start_time(pid).unwrap_or(0),
+        }
+    }
+
+    /// Whether the process that wrote the lock is still running
+    ///
+    /// A running process with a different start time reused the PID, so the
+    /// lock is stale.
+    pub fn is_alive(&self) -> bool {
+        match process_start_time(self.pid) {
+            Some(started_at) => {
+                self.started_at == 0
+                    || started_at == 0
+                    || started_at.abs_diff(self.started_at) < START_TIME_TOLERANCE_SECS
+            }
+            None => false,
+        }
+    }
+}
+
+/// Start time of a running process, Unix seconds (None if it is not running)
+fn process_start_time(pid: u32) -> Option<u64> {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).map(|process| process.start_time())
+}
+
+/// Result of trying to lock a dataset
+#[derive(Debug)]
+pub enum LockOutcome {
+    Acquired(DatasetLock),
+    /// Another running instance holds the lock
+    Held(LockInfo),
+}
+
+/// A held dataset lock; the lock file is removed when this is dropped
+#[derive(Debug)]
+pub struct DatasetLock {
+    file: PathBuf,
+    info: LockInfo,
+}
+
+impl DatasetLock {
+    /// Lock the dataset at `root`, replacing a stale lock
+    pub fn acquire(root: &Path) -> std::io::Result<LockOutcome> {
+        Self::acquire_as(root, LockInfo::current())
+    }
+
+    fn acquire_as(root: &Path, info: LockInfo) -> std::io::Result<LockOutcome> {
+        let file = root.join(LOCK_FILE);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Two attempts: the second follows removing a stale lock
+        for _ in 0..2 {
+            match fs::OpenOptions::new().write(true).create_new(true).open(&file) {
+                Ok(mut handle) => {
+                    let json = serde_json::to_string(&info).map_err(std::io::Error::other)?;
+                    handle.write_all(json.as_bytes())?;
+                    info!("Locked dataset {:?} (PID {})", root, info.pid);
+                    return Ok(LockOutcome::Acquired(DatasetLock { file, info }));
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    match Self::holder(root) {
+                        Some(holder) if holder.pid != info.pid && holder.is_alive() => {
+                            return Ok(LockOutcome::Held(holder));
+                        }
+                        holder => {
+                            warn!("Removing stale dataset lock {:?}: {:?}", file, holder);
+                            fs::remove_file(&file)?;
+                        }
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(LockOutcome::Held(Self::holder(root).unwrap_or(info)))
+    }
+
+    /// Lock the dataset at `root` even though another instance holds it
+    pub fn take_over(root: &Path) -> std::io::Result<DatasetLock> {
+        let file = root.join(LOCK_FILE);
+        if let Some(holder) = Self::holder(root) {
+            warn!("Taking over dataset lock {:?} from PID {}", file, holder.pid);
+        }
+        match fs::remove_file(&file) {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        match Self::acquire(root)? {
+            LockOutcome::Acquired(lock) => Ok(lock),
+            LockOutcome::Held(holder) => Err(std::io::Error::other(format!(
+                "lock was taken again by PID {}",
+                holder.pid
+            ))),
+        }
+    }
+
+    /// The instance recorded in the lock file of `root` (None if there is no readable lock)
+    pub fn holder(root: &Path) -> Option<LockInfo> {
+        let content = fs::read_to_string(root.join(LOCK_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Path of the lock file
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+}
+
+impl Drop for DatasetLock {
+    fn drop(&mut self) {
+        // Leave a lock another instance took over in place
+        let root = self.file.parent().and_then(Path::parent);
+        if root.and_then(Self::holder).as_ref() != Some(&self.info) {
+            return;
+        }
+        match fs::remove_file(&self.file) {
+            Ok(()) => info!("Released dataset lock {:?}", self.file),
+            Err(e) => warn!("Failed to remove dataset lock {:?}: {}", self.file, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_lock_is_exclusive_and_stale_locks_are_replaced() {
+        let tmp = TempDir::new("yolo_lock_test");
+        let root = tmp.path().to_path_buf();
+
+        // A lock left behind by a process that is no longer running
+        let crashed = LockInfo {
+            pid: u32::MAX - 1,
+            started_at: 1,
+        };
+        assert!(!crashed.is_alive());
+        fs::create_dir_all(root.join(".cleaner")).unwrap();
+        fs::write(root.join(LOCK_FILE), serde_json::to_string(&crashed).unwrap()).unwrap();
+
+        let LockOutcome::Acquired(lock) = DatasetLock::acquire(&root).unwrap() else {
+            panic!("stale lock was not replaced");
+        };
+        assert_eq!(DatasetLock::holder(&root), Some(LockInfo::current()));
+        assert!(LockInfo::current().is_alive());
+
+        // A live holder (this test process, seen from another PID) blocks the lock
+        let other = LockInfo {
+            pid: u32::MAX - 2,
+            started_at: 0,
+        };
+        let held = DatasetLock::acquire_as(&root, other).unwrap();
+        assert!(matches!(held, LockOutcome::Held(holder) if holder.pid == std::process::id()));
+
+        // Dropping the lock removes the file
+        drop(lock);
+        assert!(!root.join(LOCK_FILE).exists());
+
+        // A taken-over lock is not removed by the previous holder
+        let LockOutcome::Acquired(first) = DatasetLock::acquire(&root).unwrap() else {
+            panic!("lock not acquired");
+        };
+        fs::write(
+            root.join(LOCK_FILE),
+            serde_json::to_string(&LockInfo {
+                pid: 1,
+                started_at: 0,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        drop(first);
+        assert!(root.join(LOCK_FILE).exists());
+    }
+}
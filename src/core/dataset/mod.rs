@@ -1,5 +1,22 @@
+mod bookmarks;
+mod data_yaml;
+#[allow(clippy::module_inception)]
 mod dataset;
 mod label;
+mod lock;
+mod recent;
+mod summary;
+mod targets;
+mod yaml_writer;
 
-pub use dataset::{Dataset, DatasetSplit};
-pub use label::{parse_label_file, LabelInfo, YoloDetection};
+pub use bookmarks::Bookmarks;
+pub use lock::{DatasetLock, LockInfo, LockOutcome};
+pub use recent::{push_recent_dataset, RecentDataset};
+pub use dataset::{images_dir, labels_dir, split_exists, Dataset, DatasetSplit, LayoutKind};
+pub use summary::DatasetSummary;
+pub use targets::{DatasetTargets, TARGETS_FILE};
+pub use yaml_writer::write_data_yaml;
+pub use label::{
+    max_pairwise_iou, overlapping_pairs, parse_detection_line, parse_label_file, parse_label_file_strict,
+    validate_label_content, LabelInfo, LabelLineError, LabelParseError, YoloDetection,
+};
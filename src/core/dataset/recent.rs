@@ -0,0 +1,213 @@
+//! Recently opened datasets.
+//!
+//! Each dataset gets a random identifier in `.cleaner/dataset_id` the first
+//! time it is opened. The recent list keeps that identifier next to the
+//! absolute path, so a dataset on a removable drive that comes back under
+//! another drive letter or mount point can be found again by searching the
+//! other drive roots for a folder with the same identifier.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tracing::{info, warn};
+
+/// Dataset identifier file, relative to the dataset root
+pub const DATASET_ID_FILE: &str = ".cleaner/dataset_id";
+
+/// Most datasets kept in the recent list
+pub const MAX_RECENT_DATASETS: usize = 10;
+
+/// Identifier of the dataset at `root` (None if it has none yet)
+pub fn read_dataset_id(root: &Path) -> Option<String> {
+    let id = fs::read_to_string(root.join(DATASET_ID_FILE)).ok()?;
+    let id = id.trim();
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+/// Identifier of the dataset at `root`, created on first use
+///
+/// Returns None when the dataset has no identifier and one cannot be written
+/// (e.g. a read-only drive); the folder name is used to find it instead.
+pub fn ensure_dataset_id(root: &Path) -> Option<String> {
+    if let Some(id) = read_dataset_id(root) {
+        return Some(id);
+    }
+
+    let id = format!("{:016x}", rand::thread_rng().gen::<u64>());
+    let file = root.join(DATASET_ID_FILE);
+    let written = file
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&file, &id));
+    match written {
+        Ok(()) => {
+            info!("Created dataset identifier {} in {:?}", id, file);
+            Some(id)
+        }
+        Err(e) => {
+            warn!("Failed to write dataset identifier {:?}: {}", file, e);
+            None
+        }
+    }
+}
+
+/// A dataset in the recent list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentDataset {
+    /// Absolute path the dataset was last opened from
+    pub path: PathBuf,
+    /// Contents of the dataset's identifier file (None if it could not be written)
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+impl RecentDataset {
+    /// Entry for the dataset at `path`, giving it an identifier if it has none
+    pub fn new(path: PathBuf) -> Self {
+        let id = ensure_dataset_id(&path);
+        Self { path, id }
+    }
+
+    /// Folder name shown in the recent list
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+
+    /// Whether the folder at `path` is this dataset
+    ///
+    /// Compares identifiers when the entry has one, otherwise folder names.
+    fn is_at(&self, path: &Path) -> bool {
+        if !path.is_dir() {
+            return false;
+        }
+        match &self.id {
+            Some(id) => read_dataset_id(path).as_ref() == Some(id),
+            None => path.file_name().is_some() && path.file_name() == self.path.file_name(),
+        }
+    }
+
+    /// Where the dataset is now: its stored path, or the same folder under another drive root
+    pub fn locate(&self) -> Option<PathBuf> {
+        self.locate_in(&drive_roots())
+    }
+
+    /// Like `locate`, searching `roots` instead of the available drive roots
+    ///
+    /// Under each root, the stored path is tried without its drive prefix,
+    /// then with leading folders dropped one at a time (mount points such as
+    /// `/media/<user>/<label>` differ between machines).
+    pub fn locate_in(&self, roots: &[PathBuf]) -> Option<PathBuf> {
+        if self.is_at(&self.path) {
+            return Some(self.path.clone());
+        }
+
+        let folders: Vec<&std::ffi::OsStr> = self
+            .path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name),
+                _ => None,
+            })
+            .collect();
+
+        for start in 0..folders.len() {
+            let tail: PathBuf = folders[start..].iter().collect();
+            for root in roots {
+                let candidate = root.join(&tail);
+                if candidate != self.path && self.is_at(&candidate) {
+                    info!("Found dataset {:?} at {:?}", self.path, candidate);
+                    return Some(candidate);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Add `entry` to the front of `recent`, dropping an older entry for the same dataset
+pub fn push_recent_dataset(recent: &mut Vec<RecentDataset>, entry: RecentDataset) {
+    recent.retain(|other| {
+        other.path != entry.path && (entry.id.is_none() || other.id != entry.id)
+    });
+    recent.insert(0, entry);
+    recent.truncate(MAX_RECENT_DATASETS);
+}
+
+/// Roots of the drives and mounted volumes a dataset could be on
+pub fn drive_roots() -> Vec<PathBuf> {
+    if cfg!(windows) {
+        return (b'A'..=b'Z')
+            .map(|letter| PathBuf::from(format!("{}:\\", letter as char)))
+            .filter(|root| root.exists())
+            .collect();
+    }
+
+    // Removable drives are mounted one or two levels below these (/media/<user>/<label>)
+    let mut roots = vec![PathBuf::from("/")];
+    for base in ["/media", "/mnt", "/Volumes", "/run/media"] {
+        for mount in subdirectories(Path::new(base)) {
+            roots.extend(subdirectories(&mount));
+            roots.push(mount);
+        }
+    }
+    roots
+}
+
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_recent_dataset_is_found_under_another_root() {
+        let tmp = TempDir::new("yolo_recent_test");
+        let root = tmp.path().to_path_buf();
+        let dataset = root.join("drive_e").join("data").join("cs2");
+        fs::create_dir_all(&dataset).unwrap();
+
+        let entry = RecentDataset::new(dataset.clone());
+        assert!(entry.id.is_some());
+        assert_eq!(read_dataset_id(&dataset), entry.id);
+        assert_eq!(entry.locate_in(&[]), Some(dataset.clone()));
+
+        // The drive comes back under another root
+        let moved_drive = root.join("drive_f");
+        fs::create_dir_all(&moved_drive).unwrap();
+        fs::rename(root.join("drive_e").join("data"), moved_drive.join("data")).unwrap();
+        let moved = moved_drive.join("data").join("cs2");
+        assert_eq!(entry.locate_in(&[root.join("drive_g"), moved_drive.clone()]), Some(moved.clone()));
+
+        // A folder with the same name but another identifier is not the dataset
+        fs::write(moved.join(DATASET_ID_FILE), "someone-else").unwrap();
+        assert_eq!(entry.locate_in(&[moved_drive]), None);
+
+        // Newest first, one entry per dataset, at most MAX_RECENT_DATASETS
+        let mut recent = Vec::new();
+        for index in 0..12 {
+            push_recent_dataset(
+                &mut recent,
+                RecentDataset { path: root.join(index.to_string()), id: None },
+            );
+        }
+        push_recent_dataset(&mut recent, RecentDataset { path: root.join("5"), id: None });
+        assert_eq!(recent.len(), MAX_RECENT_DATASETS);
+        assert_eq!(recent[0].path, root.join("5"));
+        assert_eq!(recent.iter().filter(|entry| entry.path == root.join("5")).count(), 1);
+    }
+}
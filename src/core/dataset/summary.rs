@@ -0,0 +1,131 @@
+//! Cheap per-split overview of a dataset.
+//!
+//! Built from directory listings and file metadata only, so it is fast enough
+//! to compute on every load; labels are counted but never parsed.
+
+use super::{images_dir, labels_dir, DatasetSplit};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// File counts and sizes of one split (summed over all roots)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitSummary {
+    pub split: DatasetSplit,
+    pub images: usize,
+    pub labels: usize,
+    /// Bytes of the image and label files
+    pub disk_bytes: u64,
+    /// Most recent modification time of any image or label file
+    pub last_modified: Option<SystemTime>,
+}
+
+/// Overview of every split of a dataset
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetSummary {
+    pub splits: Vec<SplitSummary>,
+}
+
+impl DatasetSummary {
+    /// Summarize the splits of the given dataset roots
+    pub fn compute(roots: &[PathBuf]) -> Self {
+        let splits = DatasetSplit::all()
+            .into_iter()
+            .map(|split| {
+                let mut summary = SplitSummary {
+                    split,
+                    images: 0,
+                    labels: 0,
+                    disk_bytes: 0,
+                    last_modified: None,
+                };
+                for root in roots {
+                    summary.images +=
+                        scan_dir(&images_dir(root, split), &["png", "jpg", "jpeg"], &mut summary);
+                    summary.labels += scan_dir(&labels_dir(root, split), &["txt"], &mut summary);
+                }
+                summary
+            })
+            .collect();
+        Self { splits }
+    }
+
+    pub fn total_images(&self) -> usize {
+        self.splits.iter().map(|s| s.images).sum()
+    }
+
+    pub fn total_labels(&self) -> usize {
+        self.splits.iter().map(|s| s.labels).sum()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.splits.iter().map(|s| s.disk_bytes).sum()
+    }
+
+    /// Most recent modification across all splits
+    pub fn last_modified(&self) -> Option<SystemTime> {
+        self.splits.iter().filter_map(|s| s.last_modified).max()
+    }
+}
+
+/// Count the files in `dir` with one of `exts`, adding their sizes and times to `summary`
+fn scan_dir(dir: &Path, exts: &[&str], summary: &mut SplitSummary) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    let mut count = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let matches = path.extension().is_some_and(|ext| {
+            let ext = ext.to_string_lossy().to_ascii_lowercase();
+            exts.contains(&ext.as_str())
+        });
+        if !matches {
+            continue;
+        }
+        count += 1;
+        if let Ok(metadata) = entry.metadata() {
+            summary.disk_bytes += metadata.len();
+            if let Ok(modified) = metadata.modified() {
+                summary.last_modified = summary.last_modified.max(Some(modified));
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn summary_counts_files_per_split() {
+        let tmp = TempDir::new("summary_test");
+        let root = tmp.path().to_path_buf();
+        let images = root.join("train").join("images");
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&images).unwrap();
+        fs::create_dir_all(&labels).unwrap();
+        fs::create_dir_all(root.join("val").join("images")).unwrap();
+        fs::write(images.join("a.png"), b"12345").unwrap();
+        fs::write(images.join("b.JPG"), b"123").unwrap();
+        fs::write(images.join("notes.md"), b"ignored").unwrap();
+        fs::write(labels.join("a.txt"), b"0 0.5 0.5 0.1 0.1\n").unwrap();
+
+        let summary = DatasetSummary::compute(std::slice::from_ref(&root));
+        let train = &summary.splits[0];
+        assert_eq!(train.split, DatasetSplit::Train);
+        assert_eq!(train.images, 2);
+        assert_eq!(train.labels, 1);
+        assert_eq!(train.disk_bytes, 5 + 3 + 18);
+        assert!(train.last_modified.is_some());
+        assert_eq!(summary.splits[1].images, 0);
+        assert_eq!(summary.splits[1].last_modified, None);
+        assert_eq!(summary.splits[2].images, 0);
+        assert_eq!(summary.total_images(), 2);
+        assert_eq!(summary.total_labels(), 1);
+        assert_eq!(summary.last_modified(), train.last_modified);
+    }
+}
@@ -0,0 +1,126 @@
+//! Per-dataset balance targets.
+//!
+//! The category mix (players / backgrounds / hard cases) and the split sizes
+//! (train / val / test) the balance tools aim for are stored in
+//! `.cleaner/targets.json` under the dataset root, so each project keeps its
+//! own targets. Datasets without the file use the defaults.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tracing::warn;
+
+/// Targets file, relative to the dataset root
+pub const TARGETS_FILE: &str = ".cleaner/targets.json";
+
+/// How far a group of ratios may be from summing to one
+const SUM_TOLERANCE: f32 = 0.001;
+
+/// Target shares of one dataset (fractions, each group sums to 1.0)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatasetTargets {
+    pub player_ratio: f32,
+    pub background_ratio: f32,
+    pub hardcase_ratio: f32,
+    pub train_ratio: f32,
+    pub val_ratio: f32,
+    pub test_ratio: f32,
+}
+
+impl Default for DatasetTargets {
+    fn default() -> Self {
+        Self {
+            player_ratio: 0.85,
+            background_ratio: 0.10,
+            hardcase_ratio: 0.05,
+            train_ratio: 0.70,
+            val_ratio: 0.20,
+            test_ratio: 0.10,
+        }
+    }
+}
+
+impl DatasetTargets {
+    /// Load the targets of the dataset at `root` (defaults if there is no valid file)
+    pub fn load(root: &Path) -> Self {
+        let file = root.join(TARGETS_FILE);
+        let Ok(content) = fs::read_to_string(&file) else {
+            return Self::default();
+        };
+        let targets: Self = match serde_json::from_str(&content) {
+            Ok(targets) => targets,
+            Err(e) => {
+                warn!("Failed to parse targets {:?}: {}", file, e);
+                return Self::default();
+            }
+        };
+        match targets.validate() {
+            Ok(()) => targets,
+            Err(e) => {
+                warn!("Ignoring targets {:?}: {}", file, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Write the targets to the dataset's targets file
+    pub fn save(&self, root: &Path) -> std::io::Result<()> {
+        let file = root.join(TARGETS_FILE);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        fs::write(file, json)
+    }
+
+    /// Check that every ratio is within 0..=1 and each group sums to 100%
+    pub fn validate(&self) -> Result<(), String> {
+        let groups = [
+            ("Categories", [self.player_ratio, self.background_ratio, self.hardcase_ratio]),
+            ("Splits", [self.train_ratio, self.val_ratio, self.test_ratio]),
+        ];
+        for (name, ratios) in groups {
+            if ratios.iter().any(|ratio| !(0.0..=1.0).contains(ratio)) {
+                return Err(format!("{} must each be between 0% and 100%", name));
+            }
+            let sum: f32 = ratios.iter().sum();
+            if (sum - 1.0).abs() > SUM_TOLERANCE {
+                return Err(format!("{} sum to {:.1}%, not 100%", name, sum * 100.0));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_targets_roundtrip_and_validation() {
+        let tmp = TempDir::new("yolo_targets_test");
+        let root = tmp.path().to_path_buf();
+        assert_eq!(DatasetTargets::load(&root), DatasetTargets::default());
+
+        let targets = DatasetTargets {
+            player_ratio: 0.75,
+            background_ratio: 0.20,
+            hardcase_ratio: 0.05,
+            train_ratio: 0.80,
+            val_ratio: 0.20,
+            test_ratio: 0.0,
+        };
+        assert_eq!(targets.validate(), Ok(()));
+        targets.save(&root).unwrap();
+        assert_eq!(DatasetTargets::load(&root), targets);
+
+        let invalid = DatasetTargets { val_ratio: 0.30, ..targets };
+        assert_eq!(invalid.validate(), Err("Splits sum to 110.0%, not 100%".to_string()));
+
+        // An invalid file falls back to the defaults
+        invalid.save(&root).unwrap();
+        assert_eq!(DatasetTargets::load(&root), DatasetTargets::default());
+    }
+}
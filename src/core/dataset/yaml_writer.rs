@@ -0,0 +1,125 @@
+//! Generate or update the dataset's `data.yaml` for training.
+//!
+//! Only the keys the cleaner knows about are written: the images folder of
+//! each split that exists, `nc` and `names`. An existing file is updated in
+//! place, so `path`, `download` and any other keys keep their values and
+//! their order.
+
+use super::data_yaml::DATA_YAML_FILE;
+use super::{images_dir, DatasetSplit};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Write `data.yaml` at `root` for the given class names (sorted by id)
+///
+/// Returns the path of the written file. A yaml that exists but fails to
+/// parse is left untouched and reported as an error.
+pub fn write_data_yaml(root: &Path, names: &[(u32, String)]) -> Result<PathBuf, String> {
+    let path = root.join(DATA_YAML_FILE);
+    let existing = match fs::read_to_string(&path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let content = update_data_yaml(existing.as_deref(), root, names)?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Merge the dataset's split folders and class names into `existing` yaml text
+pub fn update_data_yaml(
+    existing: Option<&str>,
+    root: &Path,
+    names: &[(u32, String)],
+) -> Result<String, String> {
+    let mut mapping = match existing {
+        Some(content) if !content.trim().is_empty() => match serde_yaml::from_str(content) {
+            Ok(Value::Mapping(mapping)) => mapping,
+            Ok(_) => return Err(format!("{} is not a mapping", DATA_YAML_FILE)),
+            Err(e) => return Err(format!("Could not parse {}: {}", DATA_YAML_FILE, e)),
+        },
+        _ => Mapping::new(),
+    };
+
+    // Split entries are relative to `path`, which stays as the user wrote it
+    let base = mapping
+        .get("path")
+        .and_then(Value::as_str)
+        .map(|path| root.join(path))
+        .filter(|path| path.is_dir())
+        .unwrap_or_else(|| root.to_path_buf());
+
+    for split in DatasetSplit::all() {
+        let dir = images_dir(root, split);
+        if !dir.is_dir() {
+            continue;
+        }
+        let relative = dir.strip_prefix(&base).unwrap_or(&dir);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        mapping.insert(split.as_str().into(), relative.into());
+    }
+
+    mapping.insert("nc".into(), (names.len() as u64).into());
+
+    // A list when the ids are 0..n, otherwise an id → name map
+    let contiguous = names
+        .iter()
+        .enumerate()
+        .all(|(idx, (id, _))| *id as usize == idx);
+    let names_value = if contiguous {
+        Value::Sequence(names.iter().map(|(_, name)| name.as_str().into()).collect())
+    } else {
+        Value::Mapping(
+            names
+                .iter()
+                .map(|(id, name)| (u64::from(*id).into(), name.as_str().into()))
+                .collect(),
+        )
+    };
+    mapping.insert("names".into(), names_value);
+
+    serde_yaml::to_string(&mapping).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use crate::core::dataset::data_yaml::DataYaml;
+
+    #[test]
+    fn test_update_keeps_unknown_keys() {
+        let tmp = TempDir::new("yaml_writer_test");
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("train").join("images")).unwrap();
+        fs::create_dir_all(root.join("val").join("images")).unwrap();
+
+        let names = vec![(0, "T".to_string()), (1, "CT".to_string())];
+        let existing = "download: https://example.com/cs2.zip\nnc: 5\nnames: [a, b, c, d, e]\n";
+        let content = update_data_yaml(Some(existing), &root, &names).unwrap();
+
+        let value: Value = serde_yaml::from_str(&content).unwrap();
+        assert_eq!(
+            value["download"].as_str(),
+            Some("https://example.com/cs2.zip")
+        );
+        assert_eq!(value["nc"].as_u64(), Some(2));
+        assert_eq!(value["train"].as_str(), Some("train/images"));
+        assert_eq!(value["val"].as_str(), Some("val/images"));
+        assert!(value.get("test").is_none());
+        // The existing key order is kept, new keys are appended
+        assert!(content.starts_with("download:"));
+
+        let parsed = DataYaml::parse(&content, &root).unwrap();
+        assert_eq!(parsed.names, names);
+        assert_eq!(parsed.split_images.len(), 2);
+
+        // Gaps in the class ids are written as a map
+        let sparse = vec![(0, "T".to_string()), (3, "Chicken".to_string())];
+        let content = update_data_yaml(None, &root, &sparse).unwrap();
+        assert_eq!(DataYaml::parse(&content, &root).unwrap().names, sparse);
+
+        assert!(update_data_yaml(Some("names: [T, CT\n"), &root, &names).is_err());
+    }
+}
@@ -1,7 +1,28 @@
-use crate::core::dataset::{parse_label_file, LabelInfo};
+use crate::core::analysis::ImageCategory;
+use crate::core::dataset::{parse_label_file, Bookmarks, LabelInfo};
 use crate::core::operations::get_label_path_for_image;
+use chrono::{Local, NaiveDate, TimeZone};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+/// Images evaluated between progress messages of a background filter job
+const FILTER_PROGRESS_INTERVAL: usize = 200;
+
+/// Common CS2 maps offered as quick-select shortcuts in the filter dialog
+pub const COMMON_MAPS: [&str; 7] = [
+    "de_dust2",
+    "de_mirage",
+    "de_inferno",
+    "de_nuke",
+    "de_ancient",
+    "de_anubis",
+    "de_vertigo",
+];
 
 /// Team filter options
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -35,26 +56,283 @@ pub enum PlayerCountFilter {
     Background,
 }
 
+impl TeamFilter {
+    /// Every option, in the order the filter dialog lists them
+    pub const ALL: [TeamFilter; 6] = [
+        TeamFilter::All,
+        TeamFilter::TOnly,
+        TeamFilter::CTOnly,
+        TeamFilter::Both,
+        TeamFilter::TExclusive,
+        TeamFilter::CTExclusive,
+    ];
+
+    /// Check an image's cached category against the team filter
+    pub fn matches_category(self, category: ImageCategory) -> bool {
+        let has_t = matches!(category, ImageCategory::TOnly | ImageCategory::MultiplePlayer);
+        let has_ct = matches!(category, ImageCategory::CTOnly | ImageCategory::MultiplePlayer);
+        match self {
+            TeamFilter::All => true,
+            TeamFilter::TOnly => has_t,
+            TeamFilter::CTOnly => has_ct,
+            TeamFilter::Both => has_t && has_ct,
+            TeamFilter::TExclusive => has_t && !has_ct,
+            TeamFilter::CTExclusive => has_ct && !has_t,
+        }
+    }
+}
+
+impl PlayerCountFilter {
+    /// Every option, in the order the filter dialog lists them
+    pub const ALL: [PlayerCountFilter; 4] = [
+        PlayerCountFilter::Any,
+        PlayerCountFilter::Single,
+        PlayerCountFilter::Multiple,
+        PlayerCountFilter::Background,
+    ];
+
+    /// Check a label's detection count against the player count filter
+    pub fn matches_count(self, detections: usize) -> bool {
+        match self {
+            PlayerCountFilter::Any => true,
+            PlayerCountFilter::Single => detections == 1,
+            PlayerCountFilter::Multiple => detections >= 2,
+            PlayerCountFilter::Background => detections == 0,
+        }
+    }
+}
+
 /// Filter criteria configuration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+///
+/// Team, player count, category and map criteria each have an invert flag.
+/// Location metadata has no criterion, so excluding by location is out of scope.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct FilterCriteria {
     pub team: TeamFilter,
     pub player_count: PlayerCountFilter,
+    /// Allowed map names (None or empty = all maps)
+    #[serde(default)]
+    pub map_filter: Option<Vec<String>>,
+    /// Only images with a detection whose confidence is below this value
+    #[serde(default)]
+    pub low_confidence_below: Option<f32>,
+    /// Only bookmarked images
+    #[serde(default)]
+    pub bookmarked_only: bool,
+    /// Only images with a detection whose class ID is missing from the class table
+    #[serde(default)]
+    pub unknown_classes: bool,
+    /// Keep the images that do *not* match the team filter
+    #[serde(default)]
+    pub invert_team: bool,
+    /// Keep the images that do *not* match the player count filter
+    #[serde(default)]
+    pub invert_player_count: bool,
+    /// Keep the images that are *not* on one of the selected maps
+    #[serde(default)]
+    pub invert_maps: bool,
+    /// Only images in this balance category (None = all categories)
+    #[serde(default)]
+    pub category: Option<ImageCategory>,
+    /// Keep the images that are *not* in the selected category
+    #[serde(default)]
+    pub invert_category: bool,
+    /// Only images whose total detection count lies in this range (`0..=0` = backgrounds)
+    #[serde(default)]
+    pub detection_count_range: Option<RangeInclusive<usize>>,
+    /// Only images captured at or after this Unix timestamp (label `Time:` metadata)
+    #[serde(default)]
+    pub min_timestamp: Option<i64>,
+    /// Only images captured at or before this Unix timestamp
+    #[serde(default)]
+    pub max_timestamp: Option<i64>,
+}
+
+/// How many images each team and player count option matches on its own
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilterOptionCounts {
+    team: [usize; 6],
+    player_count: [usize; 4],
+}
+
+impl FilterOptionCounts {
+    /// Count the options in one pass over the cached categories and detection counts
+    pub fn from_index(categories: &[ImageCategory], detection_counts: &[usize]) -> Self {
+        let mut counts = Self::default();
+        for (&category, &detections) in categories.iter().zip(detection_counts) {
+            for (slot, team) in TeamFilter::ALL.iter().enumerate() {
+                if team.matches_category(category) {
+                    counts.team[slot] += 1;
+                }
+            }
+            for (slot, filter) in PlayerCountFilter::ALL.iter().enumerate() {
+                if filter.matches_count(detections) {
+                    counts.player_count[slot] += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    pub fn team(&self, team: TeamFilter) -> usize {
+        let slot = TeamFilter::ALL.iter().position(|t| *t == team).unwrap_or(0);
+        self.team[slot]
+    }
+
+    pub fn player_count(&self, filter: PlayerCountFilter) -> usize {
+        let slot = PlayerCountFilter::ALL.iter().position(|f| *f == filter).unwrap_or(0);
+        self.player_count[slot]
+    }
+}
+
+/// A named filter combination saved in the settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterPreset {
+    pub name: String,
+    pub criteria: FilterCriteria,
 }
 
 impl FilterCriteria {
     /// Check if any filters are active
     pub fn is_active(&self) -> bool {
-        self.team != TeamFilter::All || self.player_count != PlayerCountFilter::Any
+        self.team != TeamFilter::All
+            || self.player_count != PlayerCountFilter::Any
+            || self.category.is_some()
+            || self.has_map_filter()
+            || self.low_confidence_below.is_some()
+            || self.bookmarked_only
+            || self.unknown_classes
+            || self.detection_count_range.is_some()
+            || self.has_date_filter()
+    }
+
+    /// Whether every set criterion can be checked with `matches_indexed`
+    ///
+    /// Map, confidence, unknown-class and date filters need the full label.
+    pub fn is_indexable(&self) -> bool {
+        !self.has_map_filter()
+            && self.low_confidence_below.is_none()
+            && !self.unknown_classes
+            && !self.has_date_filter()
+    }
+
+    /// Check if a capture date bound is set
+    pub fn has_date_filter(&self) -> bool {
+        self.min_timestamp.is_some() || self.max_timestamp.is_some()
+    }
+
+    /// Check a capture timestamp against the date bounds (None = no timestamp)
+    ///
+    /// Images without a timestamp never match a date filter.
+    pub fn allows_timestamp(&self, timestamp: Option<i64>) -> bool {
+        if !self.has_date_filter() {
+            return true;
+        }
+        timestamp.is_some_and(|time| {
+            self.min_timestamp.is_none_or(|min| time >= min)
+                && self.max_timestamp.is_none_or(|max| time <= max)
+        })
+    }
+
+    /// Check an image from its cached category and detection count
+    ///
+    /// Agrees with the label-based check whenever `is_indexable` is true.
+    pub fn matches_indexed(&self, category: ImageCategory, detections: usize, bookmarked: bool) -> bool {
+        if self.bookmarked_only && !bookmarked {
+            return false;
+        }
+        if !filter_by_detection_count_range(self.detection_count_range.as_ref(), detections) {
+            return false;
+        }
+        if self.player_count != PlayerCountFilter::Any
+            && self.player_count.matches_count(detections) == self.invert_player_count
+        {
+            return false;
+        }
+        if self.team != TeamFilter::All
+            && self.team.matches_category(category) == self.invert_team
+        {
+            return false;
+        }
+        if self
+            .category
+            .is_some_and(|wanted| (wanted == category) == self.invert_category)
+        {
+            return false;
+        }
+        true
+    }
+
+    /// Check if a non-empty map filter is set
+    pub fn has_map_filter(&self) -> bool {
+        self.map_filter.as_ref().is_some_and(|maps| !maps.is_empty())
+    }
+
+    /// Check if a map passes the map filter
+    pub fn allows_map(&self, map: Option<&str>) -> bool {
+        match &self.map_filter {
+            Some(maps) if !maps.is_empty() => {
+                map.is_some_and(|map| maps.iter().any(|allowed| allowed == map))
+            }
+            _ => true,
+        }
+    }
+
+    /// Add or remove a map from the map filter
+    pub fn set_map_allowed(&mut self, map: &str, allowed: bool) {
+        let maps = self.map_filter.get_or_insert_with(Vec::new);
+        maps.retain(|existing| existing != map);
+        if allowed {
+            maps.push(map.to_string());
+        }
+        if maps.is_empty() {
+            self.map_filter = None;
+        }
     }
 
     /// Clear all filters
     pub fn clear(&mut self) {
         self.team = TeamFilter::All;
         self.player_count = PlayerCountFilter::Any;
+        self.map_filter = None;
+        self.low_confidence_below = None;
+        self.bookmarked_only = false;
+        self.unknown_classes = false;
+        self.invert_team = false;
+        self.invert_player_count = false;
+        self.invert_maps = false;
+        self.category = None;
+        self.invert_category = false;
+        self.detection_count_range = None;
+        self.min_timestamp = None;
+        self.max_timestamp = None;
     }
 }
 
+/// Unix timestamp of local midnight at the start of `date`
+pub fn date_start_timestamp(date: NaiveDate) -> i64 {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map_or_else(|| midnight.and_utc().timestamp(), |time| time.timestamp())
+}
+
+/// Local calendar date of a Unix timestamp
+pub fn timestamp_date(timestamp: i64) -> Option<NaiveDate> {
+    chrono::DateTime::from_timestamp(timestamp, 0).map(|time| time.with_timezone(&Local).date_naive())
+}
+
+/// Parse a date typed in the filter dialog (`YYYY-MM-DD`)
+pub fn parse_filter_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok()
+}
+
+/// Check a detection count against an optional range (None = any count)
+pub fn filter_by_detection_count_range(range: Option<&RangeInclusive<usize>>, detections: usize) -> bool {
+    range.is_none_or(|range| range.contains(&detections))
+}
+
 /// Analyze label to determine team composition
 fn analyze_team_composition(label: &LabelInfo) -> (bool, bool) {
     let mut has_t = false;
@@ -71,44 +349,134 @@ fn analyze_team_composition(label: &LabelInfo) -> (bool, bool) {
     (has_t, has_ct)
 }
 
+/// Check if a label matches a player count filter (missing labels are backgrounds)
+fn matches_player_count(label_info: Option<&LabelInfo>, filter: PlayerCountFilter) -> bool {
+    filter.matches_count(label_info.map(|l| l.detections.len()).unwrap_or(0))
+}
+
+/// Check if a label matches a team filter (missing labels match no team)
+fn matches_team(label_info: Option<&LabelInfo>, filter: TeamFilter) -> bool {
+    let (has_t, has_ct) = label_info
+        .map(analyze_team_composition)
+        .unwrap_or((false, false));
+    match filter {
+        TeamFilter::All => true,
+        TeamFilter::TOnly => has_t,
+        TeamFilter::CTOnly => has_ct,
+        TeamFilter::Both => has_t && has_ct,
+        TeamFilter::TExclusive => has_t && !has_ct,
+        TeamFilter::CTExclusive => has_ct && !has_t,
+    }
+}
+
 /// Check if an image matches the filter criteria
-fn matches_criteria(label_info: Option<&LabelInfo>, criteria: &FilterCriteria) -> bool {
-    // Handle player count filter for background images
-    if criteria.player_count == PlayerCountFilter::Background {
-        return label_info.map(|l| l.detections.is_empty()).unwrap_or(true);
+///
+/// Every set criterion must pass (AND). An inverted criterion passes when the
+/// image does not match it; unset criteria are ignored regardless of their flag.
+///
+/// `known_class_ids` is the class table the unknown-class filter checks against.
+fn matches_criteria(
+    label_info: Option<&LabelInfo>,
+    criteria: &FilterCriteria,
+    known_class_ids: &[u32],
+) -> bool {
+    // Map filter: images without a known map never match a map selection
+    if criteria.has_map_filter() {
+        let on_map = criteria.allows_map(label_info.and_then(|l| l.map.as_deref()));
+        if on_map == criteria.invert_maps {
+            return false;
+        }
     }
 
-    // If no label info and not looking for background, doesn't match
-    let label = match label_info {
-        Some(l) => l,
-        None => return false,
-    };
+    // Confidence filter: detections without a confidence value never count as weak
+    if let Some(threshold) = criteria.low_confidence_below {
+        let has_weak_detection = label_info.is_some_and(|l| {
+            l.detections
+                .iter()
+                .any(|d| d.confidence.is_some_and(|c| c < threshold))
+        });
+        if !has_weak_detection {
+            return false;
+        }
+    }
 
-    let player_count = label.detections.len();
+    // Unknown-class filter: keep labels with at least one class missing from the class table
+    if criteria.unknown_classes {
+        let has_unknown = label_info.is_some_and(|l| {
+            l.detections
+                .iter()
+                .any(|d| !known_class_ids.contains(&d.class_id))
+        });
+        if !has_unknown {
+            return false;
+        }
+    }
 
-    // Check player count filter
-    let count_match = match criteria.player_count {
-        PlayerCountFilter::Any => player_count > 0,
-        PlayerCountFilter::Single => player_count == 1,
-        PlayerCountFilter::Multiple => player_count >= 2,
-        PlayerCountFilter::Background => player_count == 0,
-    };
+    // Capture date: labels without a parseable `Time:` never match a date range
+    if criteria.has_date_filter() {
+        let timestamp = label_info
+            .and_then(|l| l.timestamp.as_deref())
+            .and_then(|time| time.parse::<i64>().ok());
+        if !criteria.allows_timestamp(timestamp) {
+            return false;
+        }
+    }
 
-    if !count_match {
+    // Detection count range: missing labels count as zero detections
+    let detections = label_info.map_or(0, |l| l.detections.len());
+    if !filter_by_detection_count_range(criteria.detection_count_range.as_ref(), detections) {
         return false;
     }
 
-    // Check team filter
-    let (has_t, has_ct) = analyze_team_composition(label);
+    if criteria.player_count != PlayerCountFilter::Any
+        && matches_player_count(label_info, criteria.player_count) == criteria.invert_player_count
+    {
+        return false;
+    }
 
-    match criteria.team {
-        TeamFilter::All => true,
-        TeamFilter::TOnly => has_t,
-        TeamFilter::CTOnly => has_ct,
-        TeamFilter::Both => has_t && has_ct,
-        TeamFilter::TExclusive => has_t && !has_ct,
-        TeamFilter::CTExclusive => has_ct && !has_t,
+    if criteria.team != TeamFilter::All
+        && matches_team(label_info, criteria.team) == criteria.invert_team
+    {
+        return false;
+    }
+
+    if let Some(wanted) = criteria.category {
+        let category = crate::core::analysis::categorize_label(label_info, known_class_ids);
+        if (wanted == category) == criteria.invert_category {
+            return false;
+        }
     }
+
+    true
+}
+
+/// Progress message for filter evaluation on a background thread
+#[derive(Debug, Clone)]
+pub enum FilterProgressMessage {
+    Progress { current: usize, total: usize },
+    Complete(Vec<usize>),
+    Cancelled,
+}
+
+/// Whether a single image passes the filter criteria
+fn image_matches(
+    img_path: &Path,
+    criteria: &FilterCriteria,
+    bookmarks: &Bookmarks,
+    known_class_ids: &[u32],
+) -> bool {
+    if criteria.bookmarked_only && !bookmarks.contains(img_path) {
+        return false;
+    }
+
+    // Get label path and parse it
+    let Some(label_path) = get_label_path_for_image(img_path) else {
+        return false;
+    };
+    let label_info = parse_label_file(&label_path);
+
+    // Check if matches criteria
+    matches_criteria(label_info.as_ref(), criteria, known_class_ids)
 }
 
 /// Apply filters to a list of image files and return filtered indices
@@ -116,10 +484,17 @@ fn matches_criteria(label_info: Option<&LabelInfo>, criteria: &FilterCriteria) -
 /// # Arguments
 /// * `image_files` - List of all image file paths
 /// * `criteria` - Filter criteria to apply
+/// * `bookmarks` - Bookmarks of the dataset (for the bookmarked-only filter)
+/// * `known_class_ids` - Class IDs of the class table (for the unknown-class filter)
 ///
 /// # Returns
 /// * Vector of indices that match the filter criteria
-pub fn apply_filters(image_files: &[PathBuf], criteria: &FilterCriteria) -> Vec<usize> {
+pub fn apply_filters(
+    image_files: &[PathBuf],
+    criteria: &FilterCriteria,
+    bookmarks: &Bookmarks,
+    known_class_ids: &[u32],
+) -> Vec<usize> {
     if !criteria.is_active() {
         // No filters active, return all indices
         return (0..image_files.len()).collect();
@@ -128,21 +503,60 @@ pub fn apply_filters(image_files: &[PathBuf], criteria: &FilterCriteria) -> Vec<
     image_files
         .iter()
         .enumerate()
-        .filter_map(|(idx, img_path)| {
-            // Get label path and parse it
-            let label_path = get_label_path_for_image(img_path)?;
-            let label_info = parse_label_file(&label_path);
-
-            // Check if matches criteria
-            if matches_criteria(label_info.as_ref(), criteria) {
-                Some(idx)
-            } else {
-                None
-            }
-        })
+        .filter(|(_, img_path)| image_matches(img_path, criteria, bookmarks, known_class_ids))
+        .map(|(idx, _)| idx)
         .collect()
 }
 
+/// Apply filters like `apply_filters`, reporting progress and checking for cancellation
+///
+/// Returns `None` if the job was cancelled before every image was evaluated.
+pub fn apply_filters_with_progress(
+    image_files: &[PathBuf],
+    criteria: &FilterCriteria,
+    bookmarks: &Bookmarks,
+    known_class_ids: &[u32],
+    progress_tx: Option<Sender<FilterProgressMessage>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> Option<Vec<usize>> {
+    let total = image_files.len();
+    let mut indices = Vec::new();
+
+    for (idx, img_path) in image_files.iter().enumerate() {
+        if idx % FILTER_PROGRESS_INTERVAL == 0 {
+            if cancel_flag.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+                if let Some(ref tx) = progress_tx {
+                    let _ = tx.send(FilterProgressMessage::Cancelled);
+                }
+                return None;
+            }
+            if let Some(ref tx) = progress_tx {
+                let _ = tx.send(FilterProgressMessage::Progress { current: idx, total });
+            }
+        }
+
+        if !criteria.is_active() || image_matches(img_path, criteria, bookmarks, known_class_ids) {
+            indices.push(idx);
+        }
+    }
+
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(FilterProgressMessage::Complete(indices.clone()));
+    }
+    Some(indices)
+}
+
+/// Collect the unique map names found in the labels of the given images (sorted)
+pub fn collect_map_names(image_files: &[PathBuf]) -> Vec<String> {
+    let maps: BTreeSet<String> = image_files
+        .iter()
+        .filter_map(|img_path| get_label_path_for_image(img_path))
+        .filter_map(|label_path| parse_label_file(&label_path))
+        .filter_map(|label| label.map)
+        .collect();
+    maps.into_iter().collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,10 +572,13 @@ mod tests {
                     y_center: 0.5,
                     width: 0.1,
                     height: 0.1,
+                    confidence: None,
                 })
                 .collect(),
             resolution: None,
             map: None,
+            location: None,
+            position: None,
             timestamp: None,
         }
     }
@@ -172,8 +589,20 @@ mod tests {
         let criteria = FilterCriteria {
             team: TeamFilter::TOnly,
             player_count: PlayerCountFilter::Any,
+            map_filter: None,
+            low_confidence_below: None,
+            bookmarked_only: false,
+            unknown_classes: false,
+            invert_team: false,
+            invert_player_count: false,
+            invert_maps: false,
+            category: None,
+            invert_category: false,
+            detection_count_range: None,
+            min_timestamp: None,
+            max_timestamp: None,
         };
-        assert!(matches_criteria(Some(&label), &criteria));
+        assert!(matches_criteria(Some(&label), &criteria, &[0, 1]));
     }
 
     #[test]
@@ -182,8 +611,20 @@ mod tests {
         let criteria = FilterCriteria {
             team: TeamFilter::Both,
             player_count: PlayerCountFilter::Any,
+            map_filter: None,
+            low_confidence_below: None,
+            bookmarked_only: false,
+            unknown_classes: false,
+            invert_team: false,
+            invert_player_count: false,
+            invert_maps: false,
+            category: None,
+            invert_category: false,
+            detection_count_range: None,
+            min_timestamp: None,
+            max_timestamp: None,
         };
-        assert!(matches_criteria(Some(&label), &criteria));
+        assert!(matches_criteria(Some(&label), &criteria, &[0, 1]));
     }
 
     #[test]
@@ -192,8 +633,20 @@ mod tests {
         let criteria = FilterCriteria {
             team: TeamFilter::All,
             player_count: PlayerCountFilter::Single,
+            map_filter: None,
+            low_confidence_below: None,
+            bookmarked_only: false,
+            unknown_classes: false,
+            invert_team: false,
+            invert_player_count: false,
+            invert_maps: false,
+            category: None,
+            invert_category: false,
+            detection_count_range: None,
+            min_timestamp: None,
+            max_timestamp: None,
         };
-        assert!(matches_criteria(Some(&label), &criteria));
+        assert!(matches_criteria(Some(&label), &criteria, &[0, 1]));
     }
 
     #[test]
@@ -202,8 +655,20 @@ mod tests {
         let criteria = FilterCriteria {
             team: TeamFilter::All,
             player_count: PlayerCountFilter::Multiple,
+            map_filter: None,
+            low_confidence_below: None,
+            bookmarked_only: false,
+            unknown_classes: false,
+            invert_team: false,
+            invert_player_count: false,
+            invert_maps: false,
+            category: None,
+            invert_category: false,
+            detection_count_range: None,
+            min_timestamp: None,
+            max_timestamp: None,
         };
-        assert!(matches_criteria(Some(&label), &criteria));
+        assert!(matches_criteria(Some(&label), &criteria, &[0, 1]));
     }
 
     #[test]
@@ -212,7 +677,351 @@ mod tests {
         let criteria = FilterCriteria {
             team: TeamFilter::All,
             player_count: PlayerCountFilter::Background,
+            map_filter: None,
+            low_confidence_below: None,
+            bookmarked_only: false,
+            unknown_classes: false,
+            invert_team: false,
+            invert_player_count: false,
+            invert_maps: false,
+            category: None,
+            invert_category: false,
+            detection_count_range: None,
+            min_timestamp: None,
+            max_timestamp: None,
+        };
+        assert!(matches_criteria(Some(&label), &criteria, &[0, 1]));
+    }
+
+    #[test]
+    fn test_unknown_classes_filter() {
+        let criteria = FilterCriteria {
+            unknown_classes: true,
+            ..Default::default()
+        };
+        assert!(criteria.is_active());
+        assert!(matches_criteria(Some(&create_test_label(vec![3])), &criteria, &[0, 1]));
+        assert!(matches_criteria(Some(&create_test_label(vec![0, 2])), &criteria, &[0, 1]));
+        assert!(!matches_criteria(Some(&create_test_label(vec![0, 1])), &criteria, &[0, 1]));
+        assert!(!matches_criteria(None, &criteria, &[0, 1]));
+
+        // Classes added to the class table are no longer unknown
+        assert!(!matches_criteria(Some(&create_test_label(vec![0, 2])), &criteria, &[0, 1, 2]));
+        assert!(matches_criteria(Some(&create_test_label(vec![2, 3])), &criteria, &[0, 1, 2]));
+
+        // Combines with the team filter
+        let criteria = FilterCriteria {
+            team: TeamFilter::TOnly,
+            ..criteria
         };
-        assert!(matches_criteria(Some(&label), &criteria));
+        assert!(matches_criteria(Some(&create_test_label(vec![0, 2])), &criteria, &[0, 1]));
+        assert!(!matches_criteria(Some(&create_test_label(vec![2])), &criteria, &[0, 1]));
+    }
+
+    #[test]
+    fn test_detection_count_range_filter() {
+        let criteria = FilterCriteria {
+            detection_count_range: Some(1..=2),
+            ..Default::default()
+        };
+        assert!(criteria.is_active());
+        assert!(criteria.is_indexable());
+        assert!(!matches_criteria(Some(&create_test_label(vec![])), &criteria, &[0, 1]));
+        assert!(matches_criteria(Some(&create_test_label(vec![0])), &criteria, &[0, 1]));
+        assert!(matches_criteria(Some(&create_test_label(vec![0, 1])), &criteria, &[0, 1]));
+        assert!(!matches_criteria(Some(&create_test_label(vec![0, 1, 1])), &criteria, &[0, 1]));
+        assert!(criteria.matches_indexed(ImageCategory::MultiplePlayer, 2, false));
+        assert!(!criteria.matches_indexed(ImageCategory::MultiplePlayer, 3, false));
+
+        // 0..=0 keeps backgrounds, including images without a label file
+        let criteria = FilterCriteria {
+            detection_count_range: Some(0..=0),
+            ..Default::default()
+        };
+        assert!(matches_criteria(None, &criteria, &[0, 1]));
+        assert!(matches_criteria(Some(&create_test_label(vec![])), &criteria, &[0, 1]));
+        assert!(!matches_criteria(Some(&create_test_label(vec![1])), &criteria, &[0, 1]));
+    }
+
+    #[test]
+    fn test_capture_date_range_filter() {
+        let at = |time: &str| LabelInfo {
+            timestamp: Some(time.to_string()),
+            ..create_test_label(vec![0])
+        };
+        let criteria = FilterCriteria {
+            min_timestamp: Some(1_700_000_000),
+            max_timestamp: Some(1_700_086_399),
+            ..Default::default()
+        };
+        assert!(criteria.is_active());
+        assert!(!criteria.is_indexable());
+        assert!(matches_criteria(Some(&at("1700000000")), &criteria, &[0, 1]));
+        assert!(matches_criteria(Some(&at("1700086399")), &criteria, &[0, 1]));
+        assert!(!matches_criteria(Some(&at("1699999999")), &criteria, &[0, 1]));
+        assert!(!matches_criteria(Some(&at("1700086400")), &criteria, &[0, 1]));
+        // No or unparseable timestamp never matches a date range
+        assert!(!matches_criteria(Some(&at("yesterday")), &criteria, &[0, 1]));
+        assert!(!matches_criteria(Some(&create_test_label(vec![0])), &criteria, &[0, 1]));
+        assert!(!matches_criteria(None, &criteria, &[0, 1]));
+
+        // Dates typed in the dialog round-trip through local midnight
+        let date = parse_filter_date(" 2024-03-15 ").unwrap();
+        assert_eq!(timestamp_date(date_start_timestamp(date)), Some(date));
+        assert_eq!(
+            timestamp_date(date_start_timestamp(date) - 1),
+            date.pred_opt()
+        );
+        assert!(parse_filter_date("15/03/2024").is_none());
+    }
+
+    #[test]
+    fn test_inverted_filters() {
+        let background = create_test_label(vec![]);
+        let t_player = create_test_label(vec![0]);
+        let mut on_dust2 = create_test_label(vec![1]);
+        on_dust2.map = Some("de_dust2".to_string());
+
+        // Everything except backgrounds; missing labels count as backgrounds
+        let criteria = FilterCriteria {
+            player_count: PlayerCountFilter::Background,
+            invert_player_count: true,
+            ..Default::default()
+        };
+        assert!(!matches_criteria(Some(&background), &criteria, &[0, 1]));
+        assert!(!matches_criteria(None, &criteria, &[0, 1]));
+        assert!(matches_criteria(Some(&t_player), &criteria, &[0, 1]));
+
+        // Not on de_dust2, including images without map metadata
+        let mut criteria = FilterCriteria {
+            invert_maps: true,
+            ..Default::default()
+        };
+        criteria.set_map_allowed("de_dust2", true);
+        assert!(!matches_criteria(Some(&on_dust2), &criteria, &[0, 1]));
+        assert!(matches_criteria(Some(&t_player), &criteria, &[0, 1]));
+
+        // Include and exclude criteria compose with AND
+        criteria.team = TeamFilter::CTOnly;
+        assert!(!matches_criteria(Some(&on_dust2), &criteria, &[0, 1]));
+        assert!(!matches_criteria(Some(&t_player), &criteria, &[0, 1]));
+        criteria.invert_team = true;
+        assert!(matches_criteria(Some(&t_player), &criteria, &[0, 1]));
+        assert!(matches_criteria(Some(&background), &criteria, &[0, 1]));
+
+        // A flag on an unset criterion does nothing
+        let criteria = FilterCriteria {
+            invert_team: true,
+            ..Default::default()
+        };
+        assert!(!criteria.is_active());
+        assert!(matches_criteria(Some(&t_player), &criteria, &[0, 1]));
+    }
+
+    #[test]
+    fn test_indexed_matches_agree_with_labels() {
+        let labels = [
+            None,
+            Some(create_test_label(vec![])),
+            Some(create_test_label(vec![0])),
+            Some(create_test_label(vec![1, 1])),
+            Some(create_test_label(vec![0, 1, 1])),
+            Some(create_test_label(vec![2])),
+            Some(create_test_label(vec![0, 3])),
+        ];
+        for team in TeamFilter::ALL {
+            for player_count in PlayerCountFilter::ALL {
+                for invert in [false, true] {
+                    let criteria = FilterCriteria {
+                        team,
+                        player_count,
+                        invert_team: invert,
+                        invert_player_count: !invert,
+                        ..Default::default()
+                    };
+                    assert!(criteria.is_indexable());
+                    for label in &labels {
+                        let category = crate::core::analysis::categorize_label(label.as_ref(), &[0, 1]);
+                        let detections = label.as_ref().map_or(0, |l| l.detections.len());
+                        assert_eq!(
+                            criteria.matches_indexed(category, detections, false),
+                            matches_criteria(label.as_ref(), &criteria, &[0, 1]),
+                            "{:?} on {:?}",
+                            criteria,
+                            label
+                        );
+                    }
+                }
+            }
+        }
+
+        let criteria = FilterCriteria {
+            bookmarked_only: true,
+            ..Default::default()
+        };
+        assert!(criteria.matches_indexed(ImageCategory::Background, 0, true));
+        assert!(!criteria.matches_indexed(ImageCategory::Background, 0, false));
+        assert!(!FilterCriteria {
+            unknown_classes: true,
+            ..Default::default()
+        }
+        .is_indexable());
+    }
+
+    #[test]
+    fn test_filter_option_counts() {
+        let categories = [
+            ImageCategory::CTOnly,
+            ImageCategory::CTOnly,
+            ImageCategory::TOnly,
+            ImageCategory::MultiplePlayer,
+            ImageCategory::Background,
+            ImageCategory::Unknown,
+        ];
+        let detection_counts = [1, 2, 1, 3, 0, 1];
+        let counts = FilterOptionCounts::from_index(&categories, &detection_counts);
+
+        assert_eq!(counts.team(TeamFilter::All), 6);
+        assert_eq!(counts.team(TeamFilter::CTOnly), 3);
+        assert_eq!(counts.team(TeamFilter::TOnly), 2);
+        assert_eq!(counts.team(TeamFilter::Both), 1);
+        assert_eq!(counts.team(TeamFilter::CTExclusive), 2);
+        assert_eq!(counts.team(TeamFilter::TExclusive), 1);
+        assert_eq!(counts.player_count(PlayerCountFilter::Any), 6);
+        assert_eq!(counts.player_count(PlayerCountFilter::Single), 3);
+        assert_eq!(counts.player_count(PlayerCountFilter::Multiple), 2);
+        assert_eq!(counts.player_count(PlayerCountFilter::Background), 1);
+    }
+
+    #[test]
+    fn test_criteria_without_invert_flags_deserialize_as_is() {
+        let json = r#"{"team":"CTOnly","player_count":"Any","map_filter":["de_dust2"]}"#;
+        let criteria: FilterCriteria = serde_json::from_str(json).unwrap();
+        assert_eq!(criteria.team, TeamFilter::CTOnly);
+        assert!(!criteria.invert_team);
+        assert!(!criteria.invert_player_count);
+        assert!(!criteria.invert_maps);
+        assert_eq!(criteria.category, None);
+        assert!(!criteria.invert_category);
+    }
+
+    #[test]
+    fn test_category_filter_and_its_inverse() {
+        let hard_case = create_test_label(vec![2]);
+        let t_player = create_test_label(vec![0]);
+        let mut criteria = FilterCriteria {
+            category: Some(ImageCategory::HardCase),
+            ..Default::default()
+        };
+        assert!(criteria.is_active());
+        assert!(criteria.is_indexable());
+        assert!(matches_criteria(Some(&hard_case), &criteria, &[0, 1, 2]));
+        assert!(!matches_criteria(Some(&t_player), &criteria, &[0, 1, 2]));
+
+        // Everything except hard cases, composed with AND on the player count
+        criteria.invert_category = true;
+        assert!(!matches_criteria(Some(&hard_case), &criteria, &[0, 1, 2]));
+        assert!(matches_criteria(Some(&t_player), &criteria, &[0, 1, 2]));
+        assert!(matches_criteria(None, &criteria, &[0, 1, 2]));
+        criteria.player_count = PlayerCountFilter::Background;
+        assert!(!matches_criteria(Some(&t_player), &criteria, &[0, 1, 2]));
+        assert!(matches_criteria(None, &criteria, &[0, 1, 2]));
+
+        for label in [None, Some(&hard_case), Some(&t_player)] {
+            let category = crate::core::analysis::categorize_label(label, &[0, 1, 2]);
+            let detections = label.map_or(0, |l| l.detections.len());
+            assert_eq!(
+                criteria.matches_indexed(category, detections, false),
+                matches_criteria(label, &criteria, &[0, 1, 2])
+            );
+        }
+
+        criteria.clear();
+        assert_eq!(criteria.category, None);
+        assert!(!criteria.invert_category);
+    }
+
+    #[test]
+    fn test_low_confidence_filter() {
+        let mut label = create_test_label(vec![0, 1]);
+        let criteria = FilterCriteria {
+            low_confidence_below: Some(0.5),
+            ..Default::default()
+        };
+        // No confidence column: nothing to review
+        assert!(!matches_criteria(Some(&label), &criteria, &[0, 1]));
+
+        label.detections[0].confidence = Some(0.9);
+        label.detections[1].confidence = Some(0.3);
+        assert!(matches_criteria(Some(&label), &criteria, &[0, 1]));
+
+        label.detections[1].confidence = Some(0.6);
+        assert!(!matches_criteria(Some(&label), &criteria, &[0, 1]));
+    }
+
+    #[test]
+    fn test_map_filter() {
+        let mut label = create_test_label(vec![]);
+        label.map = Some("de_dust2".to_string());
+
+        let mut criteria = FilterCriteria::default();
+        criteria.set_map_allowed("de_dust2", true);
+        assert!(criteria.is_active());
+        // Map-only filter keeps background images on that map
+        assert!(matches_criteria(Some(&label), &criteria, &[0, 1]));
+
+        criteria.set_map_allowed("de_dust2", false);
+        criteria.set_map_allowed("de_mirage", true);
+        assert!(!matches_criteria(Some(&label), &criteria, &[0, 1]));
+        assert!(!matches_criteria(None, &criteria, &[0, 1]));
+
+        // Removing the last map disables the map filter
+        criteria.set_map_allowed("de_mirage", false);
+        assert_eq!(criteria.map_filter, None);
+        assert!(!criteria.is_active());
+    }
+
+    #[test]
+    fn test_bookmarked_only_filter() {
+        let root = std::env::temp_dir().join("filter_bookmarks_test");
+        let images = vec![root.join("a.png"), root.join("b.png"), root.join("c.png")];
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.toggle(&images[1]);
+
+        let criteria = FilterCriteria {
+            bookmarked_only: true,
+            ..Default::default()
+        };
+        assert!(criteria.is_active());
+        assert_eq!(apply_filters(&images, &criteria, &bookmarks, &[0, 1]), vec![1]);
+        assert_eq!(
+            apply_filters(&images, &FilterCriteria::default(), &bookmarks, &[0, 1]),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_apply_filters_with_progress_matches_and_cancels() {
+        let root = std::env::temp_dir().join("filter_progress_test");
+        let images: Vec<PathBuf> = (0..5).map(|i| root.join(format!("{}.png", i))).collect();
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.toggle(&images[3]);
+        let criteria = FilterCriteria {
+            bookmarked_only: true,
+            ..Default::default()
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let result = apply_filters_with_progress(&images, &criteria, &bookmarks, &[0, 1], Some(tx), None);
+        assert_eq!(result, Some(vec![3]));
+        assert!(rx
+            .try_iter()
+            .any(|msg| matches!(msg, FilterProgressMessage::Complete(ref indices) if indices == &[3])));
+
+        let cancel = Arc::new(AtomicBool::new(true));
+        assert_eq!(
+            apply_filters_with_progress(&images, &criteria, &bookmarks, &[0, 1], None, Some(cancel)),
+            None
+        );
     }
 }
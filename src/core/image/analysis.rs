@@ -5,6 +5,9 @@ use palette::{FromColor, Lab, Srgb};
 /// RGB threshold value below which a color is considered "near black"
 pub const BLACK_THRESHOLD: f32 = 10.0;
 
+/// RGB threshold value above which a color is considered "near white"
+pub const WHITE_THRESHOLD: f32 = 245.0;
+
 /// Calculates the dominant color in an image using k-means clustering
 /// 
 /// This function samples pixels from the image (up to 10,000 samples) and uses
@@ -65,7 +68,7 @@ pub fn calculate_dominant_color(img: &DynamicImage) -> Option<(u8, u8, u8)> {
         })
         .collect();
     
-    centroids_with_counts.sort_by(|a, b| b.1.cmp(&a.1));
+    centroids_with_counts.sort_by_key(|c| std::cmp::Reverse(c.1));
     
     if let Some((dominant_lab, _)) = centroids_with_counts.first() {
         let rgb: Srgb = Srgb::from_color(**dominant_lab);
@@ -92,6 +95,14 @@ pub fn is_near_black(color: (u8, u8, u8)) -> bool {
     r_f < BLACK_THRESHOLD && g_f < BLACK_THRESHOLD && b_f < BLACK_THRESHOLD
 }
 
+/// Determines if a color is near white (an overexposed frame)
+///
+/// A color is considered near white if all RGB values are above WHITE_THRESHOLD
+pub fn is_near_white(color: (u8, u8, u8)) -> bool {
+    let (r, g, b) = color;
+    (r as f32) > WHITE_THRESHOLD && (g as f32) > WHITE_THRESHOLD && (b as f32) > WHITE_THRESHOLD
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,4 +117,13 @@ mod tests {
         assert!(!is_near_black((255, 255, 255))); // White
         assert!(!is_near_black((9, 9, 15))); // One channel above threshold
     }
+
+    #[test]
+    fn test_is_near_white() {
+        assert!(is_near_white((255, 255, 255))); // Pure white
+        assert!(is_near_white((246, 250, 246))); // Overexposed
+        assert!(!is_near_white((245, 245, 245))); // At the threshold
+        assert!(!is_near_white((255, 255, 200))); // One channel below threshold
+        assert!(!is_near_white((0, 0, 0))); // Black
+    }
 }
@@ -0,0 +1,134 @@
+//! Burn bounding boxes into an image and export an image/label pair.
+//!
+//! Used for bug reports: the pair is copied to a scratch folder under its
+//! original names, optionally with the boxes drawn into the image copy at
+//! native resolution.
+
+use image::{Rgb, RgbImage};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::core::dataset::{parse_label_file, YoloDetection};
+use crate::core::operations::get_label_path_for_image;
+
+/// Outline thickness for an image of the given size (about 0.25% of the shorter side)
+fn line_thickness(width: u32, height: u32) -> u32 {
+    (width.min(height) / 400).max(2)
+}
+
+/// Draw the outline of each detection onto `image` in its class color
+pub fn draw_detections(
+    image: &mut RgbImage,
+    detections: &[YoloDetection],
+    color_for: &dyn Fn(u32) -> [u8; 3],
+) {
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+    let thickness = line_thickness(width, height);
+    let to_px = |value: f32, size: u32| ((value * size as f32).round().max(0.0) as u32).min(size - 1);
+
+    for detection in detections {
+        let x_min = to_px(detection.x_center - detection.width / 2.0, width);
+        let x_max = to_px(detection.x_center + detection.width / 2.0, width);
+        let y_min = to_px(detection.y_center - detection.height / 2.0, height);
+        let y_max = to_px(detection.y_center + detection.height / 2.0, height);
+        let color = Rgb(color_for(detection.class_id));
+
+        for t in 0..thickness {
+            // Grow inward so boxes at the border stay visible
+            let (left, right) = (x_min.saturating_add(t).min(x_max), x_max.saturating_sub(t).max(x_min));
+            let (top, bottom) = (y_min.saturating_add(t).min(y_max), y_max.saturating_sub(t).max(y_min));
+            for x in x_min..=x_max {
+                image.put_pixel(x, top, color);
+                image.put_pixel(x, bottom, color);
+            }
+            for y in y_min..=y_max {
+                image.put_pixel(left, y, color);
+                image.put_pixel(right, y, color);
+            }
+        }
+    }
+}
+
+/// Copy an image and its label (if any) into `dest_dir`, keeping their file names
+///
+/// With `burn_colors`, the image copy is re-encoded with the label's boxes
+/// drawn in. Returns the path of the exported image.
+pub fn export_image_pair(
+    image_path: &Path,
+    dest_dir: &Path,
+    burn_colors: Option<&dyn Fn(u32) -> [u8; 3]>,
+) -> Result<PathBuf, String> {
+    let file_name = image_path
+        .file_name()
+        .ok_or_else(|| format!("{} has no file name", image_path.display()))?;
+    fs::create_dir_all(dest_dir)
+        .map_err(|e| format!("Failed to create {}: {}", dest_dir.display(), e))?;
+    let dest_image = dest_dir.join(file_name);
+    let label_path = get_label_path_for_image(image_path).filter(|path| path.exists());
+
+    match burn_colors {
+        Some(color_for) => {
+            let mut image = image::open(image_path)
+                .map_err(|e| format!("Failed to open {}: {}", image_path.display(), e))?
+                .to_rgb8();
+            if let Some(label) = label_path.as_deref().and_then(parse_label_file) {
+                draw_detections(&mut image, &label.detections, color_for);
+            }
+            image
+                .save(&dest_image)
+                .map_err(|e| format!("Failed to write {}: {}", dest_image.display(), e))?;
+        }
+        None => {
+            fs::copy(image_path, &dest_image)
+                .map_err(|e| format!("Failed to copy {}: {}", image_path.display(), e))?;
+        }
+    }
+
+    if let Some(label_path) = label_path {
+        if let Some(label_name) = label_path.file_name() {
+            fs::copy(&label_path, dest_dir.join(label_name))
+                .map_err(|e| format!("Failed to copy {}: {}", label_path.display(), e))?;
+        }
+    }
+
+    info!("Exported {:?} to {:?}", image_path, dest_dir);
+    Ok(dest_image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_export_pair_burns_boxes_into_copy() {
+        let tmp = TempDir::new("yolo_annotate_test");
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("images")).unwrap();
+        fs::create_dir_all(root.join("labels")).unwrap();
+
+        let image_path = root.join("images").join("shot.png");
+        RgbImage::from_pixel(100, 100, Rgb([0, 0, 0])).save(&image_path).unwrap();
+        fs::write(root.join("labels").join("shot.txt"), "1 0.5 0.5 0.5 0.5\n").unwrap();
+
+        let plain_dir = root.join("plain");
+        let plain = export_image_pair(&image_path, &plain_dir, None).unwrap();
+        assert_eq!(fs::read(&plain).unwrap(), fs::read(&image_path).unwrap());
+        assert!(plain_dir.join("shot.txt").exists());
+
+        let burned_dir = root.join("burned");
+        let red = |_: u32| [255, 0, 0];
+        let burned = export_image_pair(&image_path, &burned_dir, Some(&red)).unwrap();
+        let image = image::open(&burned).unwrap().to_rgb8();
+        // Box spans 25..=75: edge pixels are drawn, the inside and outside are not
+        assert_eq!(image.get_pixel(25, 50), &Rgb([255, 0, 0]));
+        assert_eq!(image.get_pixel(50, 75), &Rgb([255, 0, 0]));
+        assert_eq!(image.get_pixel(50, 50), &Rgb([0, 0, 0]));
+        assert_eq!(image.get_pixel(10, 10), &Rgb([0, 0, 0]));
+        assert_eq!(fs::read_to_string(burned_dir.join("shot.txt")).unwrap(), "1 0.5 0.5 0.5 0.5\n");
+    }
+}
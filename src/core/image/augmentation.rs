@@ -0,0 +1,206 @@
+//! In-place dataset augmentation.
+//!
+//! Writes an augmented copy of an image next to the original, together with a
+//! label file whose boxes are transformed to match.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::core::dataset::parse_detection_line;
+use crate::core::operations::get_label_path_for_image;
+
+/// Result type for augmentation
+pub type AugmentationResult<T> = Result<T, AugmentationError>;
+
+/// Error types for augmentation
+#[derive(Debug)]
+pub enum AugmentationError {
+    Image(image::ImageError),
+    IoError(std::io::Error),
+}
+
+impl std::fmt::Display for AugmentationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AugmentationError::Image(e) => write!(f, "Image error: {}", e),
+            AugmentationError::IoError(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AugmentationError {}
+
+impl From<image::ImageError> for AugmentationError {
+    fn from(error: image::ImageError) -> Self {
+        AugmentationError::Image(error)
+    }
+}
+
+impl From<std::io::Error> for AugmentationError {
+    fn from(error: std::io::Error) -> Self {
+        AugmentationError::IoError(error)
+    }
+}
+
+/// A transformation applied to an image and its label
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AugmentationType {
+    /// Mirror the image left to right (box x centers become `1.0 - x_center`)
+    HorizontalFlip,
+    /// Shift brightness by a fraction of the full range (-1.0 to 1.0, negative darkens)
+    BrightnessJitter(f32),
+}
+
+impl AugmentationType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AugmentationType::HorizontalFlip => "Horizontal Flip",
+            AugmentationType::BrightnessJitter(_) => "Brightness Jitter",
+        }
+    }
+}
+
+/// Write an augmented copy of `image_path` to `output_path`
+///
+/// If `label_path` is given, the transformed label is written next to the
+/// output image (its `labels` folder counterpart). Comment and unparseable
+/// lines are copied unchanged. Returns the path of the written image.
+pub fn apply_augmentation(
+    image_path: &Path,
+    label_path: Option<&Path>,
+    aug: AugmentationType,
+    output_path: &Path,
+) -> AugmentationResult<PathBuf> {
+    let img = image::open(image_path)?;
+    let augmented = match aug {
+        AugmentationType::HorizontalFlip => img.fliph(),
+        AugmentationType::BrightnessJitter(amount) => {
+            img.brighten((amount.clamp(-1.0, 1.0) * 255.0).round() as i32)
+        }
+    };
+    augmented.save(output_path)?;
+    info!("Wrote {} copy {:?}", aug.as_str(), output_path);
+
+    if let Some(label_path) = label_path {
+        let contents = fs::read_to_string(label_path)?;
+        if let Some(output_label) = get_label_path_for_image(output_path) {
+            if let Some(parent) = output_label.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&output_label, augment_label_content(&contents, aug))?;
+            info!("Wrote augmented label {:?}", output_label);
+        }
+    }
+
+    Ok(output_path.to_path_buf())
+}
+
+/// Transform the detections in label file contents for an augmentation
+pub fn augment_label_content(content: &str, aug: AugmentationType) -> String {
+    let mut output = String::with_capacity(content.len());
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let detection = if trimmed.is_empty() || trimmed.starts_with('#') {
+            None
+        } else {
+            parse_detection_line(trimmed).ok()
+        };
+
+        match (detection, aug) {
+            (Some(mut detection), AugmentationType::HorizontalFlip) => {
+                detection.x_center = 1.0 - detection.x_center;
+                output.push_str(&detection.to_label_line());
+            }
+            _ => output.push_str(line),
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// First unused `<stem>_aug.<ext>` path next to an image (`_aug2`, `_aug3`, ...)
+pub fn augmented_output_path(image_path: &Path) -> PathBuf {
+    let stem = image_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = image_path
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "png".to_string());
+
+    let mut suffix = 1;
+    loop {
+        let name = if suffix == 1 {
+            format!("{}_aug.{}", stem, ext)
+        } else {
+            format!("{}_aug{}.{}", stem, suffix, ext)
+        };
+        let candidate = image_path.with_file_name(name);
+        if !candidate.exists() {
+            return candidate;
+        }
+        suffix += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_flip_mirrors_x_center_and_keeps_comments() {
+        let content = "# Map: de_inferno\n0 0.250000 0.5 0.1 0.2\n1 0.9 0.3 0.05 0.1 0.75\n";
+        let flipped = augment_label_content(content, AugmentationType::HorizontalFlip);
+        let lines: Vec<&str> = flipped.lines().collect();
+
+        assert_eq!(lines[0], "# Map: de_inferno");
+        assert_eq!(lines[1], "0 0.750000 0.500000 0.100000 0.200000");
+        assert_eq!(lines[2], "1 0.100000 0.300000 0.050000 0.100000 0.7500");
+        // Brightness does not move boxes
+        assert_eq!(
+            augment_label_content(content, AugmentationType::BrightnessJitter(0.2)),
+            content
+        );
+    }
+
+    #[test]
+    fn test_apply_augmentation_writes_image_and_label() {
+        let tmp = TempDir::new("yolo_dataset_augment_test");
+        let root = tmp.path().to_path_buf();
+        let images = root.join("train").join("images");
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&images).unwrap();
+        fs::create_dir_all(&labels).unwrap();
+
+        let image_path = images.join("shot.png");
+        let label_path = labels.join("shot.txt");
+        let mut img = image::RgbImage::new(4, 2);
+        img.put_pixel(0, 0, image::Rgb([255, 0, 0]));
+        img.save(&image_path).unwrap();
+        fs::write(&label_path, "0 0.2 0.5 0.1 0.1\n").unwrap();
+
+        let output = augmented_output_path(&image_path);
+        assert_eq!(output, images.join("shot_aug.png"));
+        let written = apply_augmentation(
+            &image_path,
+            Some(&label_path),
+            AugmentationType::HorizontalFlip,
+            &output,
+        )
+        .unwrap();
+
+        let flipped = image::open(&written).unwrap().to_rgb8();
+        assert_eq!(flipped.get_pixel(3, 0), &image::Rgb([255, 0, 0]));
+        assert_eq!(
+            fs::read_to_string(labels.join("shot_aug.txt")).unwrap(),
+            "0 0.800000 0.500000 0.100000 0.100000\n"
+        );
+        assert_eq!(
+            augmented_output_path(&image_path),
+            images.join("shot_aug2.png")
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Batch horizontal-flip augmentation for underrepresented categories.
+//!
+//! Each flipped copy is written as `<stem>_flip.<ext>` (with a mirrored label)
+//! into the source split or another split. Every generated file is listed in
+//! `.cleaner/augmented.json` under the dataset root, as `/`-separated paths
+//! relative to the root, so the copies can be removed again in one go.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use super::augmentation::{apply_augmentation, AugmentationType};
+use crate::core::operations::get_label_path_for_image;
+
+/// Manifest of generated augmentation files, relative to the dataset root
+pub const AUGMENT_MANIFEST_FILE: &str = ".cleaner/augmented.json";
+
+/// One image to flip
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlipJob {
+    pub image_path: PathBuf,
+    /// Label of the source image (None = background without a label file)
+    pub label_path: Option<PathBuf>,
+    /// Where the flipped image is written
+    pub output_path: PathBuf,
+}
+
+/// Result of a batch flip
+#[derive(Debug, Clone, Default)]
+pub struct FlipReport {
+    pub total: usize,
+    /// Generated image files
+    pub generated: Vec<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+/// Progress message for a batch flip
+#[derive(Debug, Clone)]
+pub enum FlipProgressMessage {
+    Progress { current: usize, total: usize },
+    Complete(FlipReport),
+    Cancelled(FlipReport),
+}
+
+/// First unused `<stem>_flip.<ext>` path in `dest_images` (`_flip2`, `_flip3`, ...)
+///
+/// Names already claimed by earlier jobs of the same batch are skipped too.
+pub fn flipped_output_path(image_path: &Path, dest_images: &Path, taken: &[PathBuf]) -> PathBuf {
+    let stem = image_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = image_path
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "png".to_string());
+
+    let mut suffix = 1;
+    loop {
+        let name = if suffix == 1 {
+            format!("{}_flip.{}", stem, ext)
+        } else {
+            format!("{}_flip{}.{}", stem, suffix, ext)
+        };
+        let candidate = dest_images.join(name);
+        if !candidate.exists() && !taken.contains(&candidate) {
+            return candidate;
+        }
+        suffix += 1;
+    }
+}
+
+/// Flip every job's image and label, then add the generated files to the manifest
+///
+/// The manifest is also updated when the batch is cancelled, so files written
+/// before the cancel can still be removed.
+pub fn batch_flip(
+    dataset_path: &Path,
+    jobs: &[FlipJob],
+    progress_tx: Option<Sender<FlipProgressMessage>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> FlipReport {
+    let mut report = FlipReport {
+        total: jobs.len(),
+        ..FlipReport::default()
+    };
+    let mut written = Vec::new();
+    info!("Flipping {} images", report.total);
+
+    let mut cancelled = false;
+    for (idx, job) in jobs.iter().enumerate() {
+        if cancel_flag
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+        {
+            warn!("Batch flip cancelled at {}/{}", idx, report.total);
+            cancelled = true;
+            break;
+        }
+
+        let result = job
+            .output_path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .map_err(|e| e.to_string())
+            .and_then(|_| {
+                apply_augmentation(
+                    &job.image_path,
+                    job.label_path.as_deref(),
+                    AugmentationType::HorizontalFlip,
+                    &job.output_path,
+                )
+                .map_err(|e| e.to_string())
+            });
+        match result {
+            Ok(image) => {
+                if job.label_path.is_some() {
+                    written.extend(get_label_path_for_image(&image).filter(|p| p.exists()));
+                }
+                written.push(image.clone());
+                report.generated.push(image);
+            }
+            Err(e) => {
+                error!("Failed to flip {:?}: {}", job.image_path, e);
+                report
+                    .errors
+                    .push(format!("{}: {}", job.image_path.display(), e));
+            }
+        }
+
+        if let Some(ref tx) = progress_tx {
+            let _ = tx.send(FlipProgressMessage::Progress {
+                current: idx + 1,
+                total: report.total,
+            });
+        }
+    }
+
+    let mut manifest = load_manifest(dataset_path);
+    manifest.extend(written);
+    if let Err(e) = save_manifest(dataset_path, &manifest) {
+        error!("Failed to save augmentation manifest: {}", e);
+        report.errors.push(format!("Manifest not saved: {}", e));
+    }
+
+    info!(
+        "Batch flip finished: {} / {} images generated, {} errors",
+        report.generated.len(),
+        report.total,
+        report.errors.len()
+    );
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(if cancelled {
+            FlipProgressMessage::Cancelled(report.clone())
+        } else {
+            FlipProgressMessage::Complete(report.clone())
+        });
+    }
+    report
+}
+
+/// Generated files listed in the dataset's manifest (absolute paths)
+pub fn load_manifest(dataset_path: &Path) -> Vec<PathBuf> {
+    let file = dataset_path.join(AUGMENT_MANIFEST_FILE);
+    let relative: Vec<String> = match fs::read_to_string(&file) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("Failed to parse augmentation manifest {:?}: {}", file, e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+    relative
+        .iter()
+        .map(|rel| {
+            rel.split('/')
+                .fold(dataset_path.to_path_buf(), |path, part| path.join(part))
+        })
+        .collect()
+}
+
+/// Write the manifest, removing the file when `files` is empty
+fn save_manifest(dataset_path: &Path, files: &[PathBuf]) -> std::io::Result<()> {
+    let file = dataset_path.join(AUGMENT_MANIFEST_FILE);
+    if files.is_empty() {
+        return match fs::remove_file(&file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+
+    let relative: Vec<String> = files
+        .iter()
+        .map(|path| {
+            let rel = path.strip_prefix(dataset_path).unwrap_or(path);
+            rel.components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .collect();
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(&relative)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    fs::write(file, json)
+}
+
+/// Delete every generated file in the manifest and clear it
+///
+/// Returns the number of files removed; files that fail to delete stay in
+/// the manifest and are listed in the errors.
+pub fn remove_generated(dataset_path: &Path) -> (usize, Vec<String>) {
+    let mut removed = 0;
+    let mut errors = Vec::new();
+    let mut kept = Vec::new();
+    for path in load_manifest(dataset_path) {
+        match fs::remove_file(&path) {
+            Ok(()) => removed += 1,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                errors.push(format!("{}: {}", path.display(), e));
+                kept.push(path);
+            }
+        }
+    }
+    if let Err(e) = save_manifest(dataset_path, &kept) {
+        errors.push(format!("Manifest not saved: {}", e));
+    }
+    info!("Removed {} generated augmentation files", removed);
+    (removed, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_batch_flip_into_other_split_and_remove() {
+        let tmp = TempDir::new("yolo_dataset_batch_flip");
+        let root = tmp.path().to_path_buf();
+        let images = root.join("train").join("images");
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&images).unwrap();
+        fs::create_dir_all(&labels).unwrap();
+        for name in ["a", "b"] {
+            image::RgbImage::new(4, 2)
+                .save(images.join(format!("{}.png", name)))
+                .unwrap();
+        }
+        fs::write(labels.join("a.txt"), "0 0.2 0.5 0.1 0.1\n").unwrap();
+
+        // Both copies go to val; b has no label (background)
+        let val_images = root.join("val").join("images");
+        let mut jobs: Vec<FlipJob> = Vec::new();
+        for (name, label) in [("a", Some(labels.join("a.txt"))), ("b", None)] {
+            let image_path = images.join(format!("{}.png", name));
+            let taken: Vec<PathBuf> = jobs.iter().map(|j| j.output_path.clone()).collect();
+            jobs.push(FlipJob {
+                output_path: flipped_output_path(&image_path, &val_images, &taken),
+                image_path,
+                label_path: label,
+            });
+        }
+        assert_eq!(jobs[0].output_path, val_images.join("a_flip.png"));
+
+        let report = batch_flip(&root, &jobs, None, None);
+        assert_eq!(report.generated.len(), 2);
+        assert!(report.errors.is_empty());
+        assert_eq!(
+            fs::read_to_string(root.join("val").join("labels").join("a_flip.txt")).unwrap(),
+            "0 0.800000 0.500000 0.100000 0.100000\n"
+        );
+        assert_eq!(load_manifest(&root).len(), 3);
+        assert_eq!(
+            flipped_output_path(&images.join("a.png"), &val_images, &[]),
+            val_images.join("a_flip2.png")
+        );
+
+        let (removed, errors) = remove_generated(&root);
+        assert_eq!(removed, 3);
+        assert!(errors.is_empty());
+        assert!(!val_images.join("a_flip.png").exists());
+        assert!(!root.join(AUGMENT_MANIFEST_FILE).exists());
+        assert!(images.join("a.png").exists());
+    }
+}
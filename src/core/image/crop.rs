@@ -0,0 +1,272 @@
+//! Detection crops for building a classification side-dataset.
+//!
+//! Every box of an image is cut from the full-resolution file (with optional
+//! padding) and written as `<output>/<class>/<stem>_<index>.png`, where
+//! `<index>` is the detection's position in the label file.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::core::dataset::{parse_label_file, YoloDetection};
+use crate::core::operations::get_label_path_for_image;
+
+/// Result of cropping one or more images
+#[derive(Debug, Clone, Default)]
+pub struct CropReport {
+    /// Images processed
+    pub total: usize,
+    /// Crops written per class folder
+    pub per_class: BTreeMap<String, usize>,
+    pub errors: Vec<String>,
+}
+
+impl CropReport {
+    /// Crops written across all classes
+    pub fn crops_written(&self) -> usize {
+        self.per_class.values().sum()
+    }
+}
+
+/// Progress message for a batch crop
+#[derive(Debug, Clone)]
+pub enum CropProgressMessage {
+    Progress { current: usize, total: usize },
+    Complete(CropReport),
+    Cancelled(CropReport),
+}
+
+/// Folder name for a class: its lowercase name with unsafe characters replaced
+///
+/// Classes without a name fall back to `class_<id>`.
+pub fn class_folder_name(class_names: &HashMap<u32, String>, class_id: u32) -> String {
+    let folder: String = class_names
+        .get(&class_id)
+        .map(|name| name.trim().to_lowercase())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if folder.is_empty() {
+        format!("class_{}", class_id)
+    } else {
+        folder
+    }
+}
+
+/// Pixel rectangle (x, y, width, height) of a detection in a `width` x `height` image
+///
+/// `padding` grows the box by that fraction of its size on every side; the
+/// result is clamped to the image. Returns None for boxes with no area inside it.
+pub fn crop_rect(
+    detection: &YoloDetection,
+    width: u32,
+    height: u32,
+    padding: f32,
+) -> Option<(u32, u32, u32, u32)> {
+    let pad_w = detection.width * padding.max(0.0);
+    let pad_h = detection.height * padding.max(0.0);
+    let x_min = ((detection.x_center - detection.width / 2.0 - pad_w) * width as f32)
+        .round()
+        .clamp(0.0, width as f32) as u32;
+    let x_max = ((detection.x_center + detection.width / 2.0 + pad_w) * width as f32)
+        .round()
+        .clamp(0.0, width as f32) as u32;
+    let y_min = ((detection.y_center - detection.height / 2.0 - pad_h) * height as f32)
+        .round()
+        .clamp(0.0, height as f32) as u32;
+    let y_max = ((detection.y_center + detection.height / 2.0 + pad_h) * height as f32)
+        .round()
+        .clamp(0.0, height as f32) as u32;
+
+    if x_max <= x_min || y_max <= y_min {
+        return None;
+    }
+    Some((x_min, y_min, x_max - x_min, y_max - y_min))
+}
+
+/// Write crops of an image's detections, adding them to `report`
+///
+/// With `only` set, just that detection index is cropped. Returns the
+/// written crop paths.
+pub fn crop_image_detections(
+    image_path: &Path,
+    class_names: &HashMap<u32, String>,
+    output_dir: &Path,
+    padding: f32,
+    only: Option<usize>,
+    report: &mut CropReport,
+) -> Vec<PathBuf> {
+    let detections = get_label_path_for_image(image_path)
+        .and_then(|label| parse_label_file(&label))
+        .map(|label| label.detections)
+        .unwrap_or_default();
+    if detections.is_empty() {
+        return Vec::new();
+    }
+
+    let img = match image::open(image_path) {
+        Ok(img) => img,
+        Err(e) => {
+            error!("Failed to open {:?} for cropping: {}", image_path, e);
+            report
+                .errors
+                .push(format!("{}: {}", image_path.display(), e));
+            return Vec::new();
+        }
+    };
+    let stem = image_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut written = Vec::new();
+    for (idx, detection) in detections.iter().enumerate() {
+        if only.is_some_and(|only| only != idx) {
+            continue;
+        }
+        let Some((x, y, w, h)) = crop_rect(detection, img.width(), img.height(), padding) else {
+            warn!("Detection {} of {:?} lies outside the image", idx, image_path);
+            continue;
+        };
+
+        let folder = class_folder_name(class_names, detection.class_id);
+        let crop_path = output_dir
+            .join(&folder)
+            .join(format!("{}_{}.png", stem, idx));
+        let result = fs::create_dir_all(output_dir.join(&folder))
+            .map_err(|e| e.to_string())
+            .and_then(|_| {
+                img.crop_imm(x, y, w, h)
+                    .save(&crop_path)
+                    .map_err(|e| e.to_string())
+            });
+        match result {
+            Ok(()) => {
+                *report.per_class.entry(folder).or_default() += 1;
+                written.push(crop_path);
+            }
+            Err(e) => {
+                error!("Failed to write crop {:?}: {}", crop_path, e);
+                report.errors.push(format!("{}: {}", crop_path.display(), e));
+            }
+        }
+    }
+    written
+}
+
+/// Crop every detection of every image into `output_dir`
+pub fn batch_crop(
+    images: &[PathBuf],
+    class_names: &HashMap<u32, String>,
+    output_dir: &Path,
+    padding: f32,
+    progress_tx: Option<Sender<CropProgressMessage>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> CropReport {
+    let mut report = CropReport {
+        total: images.len(),
+        ..CropReport::default()
+    };
+    info!("Cropping detections of {} images into {:?}", report.total, output_dir);
+
+    for (idx, image_path) in images.iter().enumerate() {
+        if cancel_flag
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+        {
+            warn!("Batch crop cancelled at {}/{}", idx, report.total);
+            if let Some(tx) = progress_tx {
+                let _ = tx.send(CropProgressMessage::Cancelled(report.clone()));
+            }
+            return report;
+        }
+
+        crop_image_detections(image_path, class_names, output_dir, padding, None, &mut report);
+
+        if let Some(ref tx) = progress_tx {
+            let _ = tx.send(CropProgressMessage::Progress {
+                current: idx + 1,
+                total: report.total,
+            });
+        }
+    }
+
+    info!(
+        "Batch crop finished: {} crops from {} images, {} errors",
+        report.crops_written(),
+        report.total,
+        report.errors.len()
+    );
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(CropProgressMessage::Complete(report.clone()));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn detection(class_id: u32, x: f32, y: f32, w: f32, h: f32) -> YoloDetection {
+        YoloDetection {
+            class_id,
+            x_center: x,
+            y_center: y,
+            width: w,
+            height: h,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_crop_rect_pads_and_clamps() {
+        let d = detection(0, 0.5, 0.5, 0.2, 0.4);
+        assert_eq!(crop_rect(&d, 100, 50, 0.0), Some((40, 15, 20, 20)));
+        // 10% padding adds 2 px horizontally and 2 px vertically per side
+        assert_eq!(crop_rect(&d, 100, 50, 0.1), Some((38, 13, 24, 24)));
+        // A box at the edge is clamped to the image
+        let edge = detection(0, 0.0, 0.5, 0.2, 0.2);
+        assert_eq!(crop_rect(&edge, 100, 100, 0.0), Some((0, 40, 10, 20)));
+        assert_eq!(crop_rect(&detection(0, 1.5, 0.5, 0.2, 0.2), 100, 100, 0.0), None);
+    }
+
+    #[test]
+    fn test_batch_crop_writes_class_folders() {
+        let tmp = TempDir::new("yolo_dataset_crop_test");
+        let root = tmp.path().to_path_buf();
+        let images = root.join("train").join("images");
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&images).unwrap();
+        fs::create_dir_all(&labels).unwrap();
+        image::RgbImage::new(40, 20).save(images.join("shot.png")).unwrap();
+        fs::write(
+            labels.join("shot.txt"),
+            "# Map: de_dust2\n1 0.25 0.5 0.5 1.0\n0 0.75 0.5 0.25 0.5\n7 0.5 0.5 0.1 0.1\n",
+        )
+        .unwrap();
+        let class_names: HashMap<u32, String> =
+            [(0, "T".to_string()), (1, "CT".to_string())].into_iter().collect();
+
+        let out = root.join("crops");
+        let report = batch_crop(&[images.join("shot.png")], &class_names, &out, 0.0, None, None);
+        assert_eq!(report.crops_written(), 3);
+        assert_eq!(report.per_class.get("ct"), Some(&1));
+        assert_eq!(report.per_class.get("class_7"), Some(&1));
+
+        let ct = image::open(out.join("ct").join("shot_0.png")).unwrap();
+        assert_eq!((ct.width(), ct.height()), (20, 20));
+        assert!(out.join("t").join("shot_1.png").exists());
+
+        // Single-detection mode
+        let mut single = CropReport::default();
+        let written =
+            crop_image_detections(&images.join("shot.png"), &class_names, &out, 0.0, Some(1), &mut single);
+        assert_eq!(written, vec![out.join("t").join("shot_1.png")]);
+    }
+}
@@ -0,0 +1,152 @@
+//! Read-only image metadata for the label panel.
+//!
+//! Every format reports its size and color type. PNG files add their
+//! physical pixel density (`pHYs`), last-modified time (`tIME`) and text
+//! chunks (`tEXt`, `iTXt`) before the image data.
+
+use image::{ImageDecoder, ImageReader};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Bytes every PNG file starts with
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Metadata fields of the image at `path` ("Width", "DPI", "Text: Software", ...)
+///
+/// Returns an empty map when the file cannot be read.
+pub fn read_image_metadata(path: &Path) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    let is_png = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+    if is_png && read_png_metadata(path, &mut fields).is_some() {
+        return fields;
+    }
+
+    let Ok(reader) = ImageReader::open(path).and_then(|reader| reader.with_guessed_format()) else {
+        return fields;
+    };
+    let format = reader.format();
+    let Ok(decoder) = reader.into_decoder() else {
+        return fields;
+    };
+    let (width, height) = decoder.dimensions();
+    fields.insert("Width".to_string(), width.to_string());
+    fields.insert("Height".to_string(), height.to_string());
+    fields.insert("Color type".to_string(), format!("{:?}", decoder.color_type()));
+    if let Some(format) = format {
+        fields.insert("Format".to_string(), format!("{:?}", format));
+    }
+    fields
+}
+
+/// Header, pixel density and text chunks of a PNG (None if it is not a readable PNG)
+fn read_png_metadata(path: &Path, fields: &mut HashMap<String, String>) -> Option<()> {
+    let decoder = png::Decoder::new(BufReader::new(File::open(path).ok()?));
+    let reader = decoder.read_info().ok()?;
+    let info = reader.info();
+
+    fields.insert("Format".to_string(), "Png".to_string());
+    fields.insert("Width".to_string(), info.width.to_string());
+    fields.insert("Height".to_string(), info.height.to_string());
+    fields.insert("Bit depth".to_string(), (info.bit_depth as u8).to_string());
+    fields.insert("Color type".to_string(), format!("{:?}", info.color_type));
+
+    if let Some(dims) = info.pixel_dims {
+        let density = match dims.unit {
+            // Pixels per meter → per inch
+            png::Unit::Meter => format!("{:.0} x {:.0}", dims.xppu as f64 * 0.0254, dims.yppu as f64 * 0.0254),
+            png::Unit::Unspecified => format!("{} x {} (aspect ratio only)", dims.xppu, dims.yppu),
+        };
+        fields.insert("DPI".to_string(), density);
+    }
+
+    for chunk in &info.uncompressed_latin1_text {
+        fields.insert(format!("Text: {}", chunk.keyword), chunk.text.clone());
+    }
+    for chunk in &info.utf8_text {
+        if let Ok(text) = chunk.get_text() {
+            fields.insert(format!("Text: {}", chunk.keyword), text);
+        }
+    }
+
+    if let Some(time) = read_png_time(path) {
+        fields.insert("Modified".to_string(), time);
+    }
+    Some(())
+}
+
+/// The `tIME` chunk of a PNG as `YYYY-MM-DD HH:MM:SS` (UTC)
+///
+/// Walks the chunk headers, skipping their data, since the decoder does not
+/// keep this chunk.
+fn read_png_time(path: &Path) -> Option<String> {
+    let mut file = BufReader::new(File::open(path).ok()?);
+    let mut signature = [0u8; 8];
+    file.read_exact(&mut signature).ok()?;
+    if signature != PNG_SIGNATURE {
+        return None;
+    }
+
+    loop {
+        let mut header = [0u8; 8];
+        file.read_exact(&mut header).ok()?;
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+        match &header[4..8] {
+            b"tIME" if length == 7 => {
+                let mut data = [0u8; 7];
+                file.read_exact(&mut data).ok()?;
+                let year = u16::from_be_bytes([data[0], data[1]]);
+                return Some(format!(
+                    "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                    year, data[2], data[3], data[4], data[5], data[6]
+                ));
+            }
+            b"IEND" => return None,
+            // Data plus CRC
+            _ => {
+                file.seek(SeekFrom::Current(length as i64 + 4)).ok()?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use std::io::Write;
+
+    #[test]
+    fn test_png_metadata_reads_density_time_and_text() {
+        let tmp = TempDir::new("yolo_metadata_test");
+        let root = tmp.path().to_path_buf();
+
+        let path = root.join("shot.png");
+        let mut encoder = png::Encoder::new(File::create(&path).unwrap(), 4, 2);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_pixel_dims(Some(png::PixelDimensions { xppu: 3780, yppu: 3780, unit: png::Unit::Meter }));
+        encoder.add_text_chunk("Software".to_string(), "CS2".to_string()).unwrap();
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_chunk(png::chunk::tIME, &[0x07, 0xE8, 3, 14, 9, 26, 53]).unwrap();
+        writer.write_image_data(&[0u8; 24]).unwrap();
+        writer.finish().unwrap();
+
+        let fields = read_image_metadata(&path);
+        assert_eq!(fields["Width"], "4");
+        assert_eq!(fields["Height"], "2");
+        assert_eq!(fields["Bit depth"], "8");
+        assert_eq!(fields["Color type"], "Rgb");
+        assert_eq!(fields["DPI"], "96 x 96");
+        assert_eq!(fields["Modified"], "2024-03-14 09:26:53");
+        assert_eq!(fields["Text: Software"], "CS2");
+
+        // Not an image at all: nothing to show
+        let garbage = root.join("garbage.png");
+        File::create(&garbage).unwrap().write_all(b"not an image").unwrap();
+        assert!(read_image_metadata(&garbage).is_empty());
+    }
+}
@@ -1,3 +1,20 @@
 pub mod analysis;
+pub mod annotate;
+pub mod augmentation;
+pub mod batch_flip;
+pub mod crop;
+pub mod metadata;
+pub mod verify;
 
-pub use analysis::{calculate_dominant_color, is_near_black};
+pub use analysis::{calculate_dominant_color, is_near_black, is_near_white};
+pub use annotate::export_image_pair;
+pub use augmentation::{
+    apply_augmentation, augmented_output_path, AugmentationType,
+};
+pub use batch_flip::{
+    batch_flip, flipped_output_path, load_manifest, remove_generated, FlipJob, FlipProgressMessage,
+    FlipReport,
+};
+pub use metadata::read_image_metadata;
+pub use crop::{batch_crop, crop_image_detections, CropProgressMessage, CropReport};
+pub use verify::{find_corrupt_images, find_first_corrupt_image, ImageCheck};
@@ -0,0 +1,142 @@
+//! Decode checks for truncated or corrupt image files.
+
+use image::ImageReader;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// How thoroughly each image is checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageCheck {
+    /// Read format and dimensions from the header only (fast, misses truncated pixel data)
+    Header,
+    /// Decode every pixel (finds truncated files, much slower)
+    FullDecode,
+}
+
+/// Check that an image can be read, returning the decoder's error text if not
+pub fn check_image(path: &Path, check: ImageCheck) -> Result<(), String> {
+    let reader = ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| e.to_string())?;
+    match check {
+        ImageCheck::Header => reader.into_dimensions().map(|_| ()),
+        ImageCheck::FullDecode => reader.decode().map(|_| ()),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Check every image on all cores, returning the unreadable ones with their error
+///
+/// `on_progress` is called from the worker threads with the number of images
+/// checked so far. When `cancel_flag` is set the workers stop after their
+/// current image and the images found so far are returned. Results keep the
+/// order of `paths`.
+pub fn find_corrupt_images(
+    paths: &[PathBuf],
+    check: ImageCheck,
+    cancel_flag: Option<&AtomicBool>,
+    on_progress: &(dyn Fn(usize) + Sync),
+) -> Vec<(PathBuf, String)> {
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(paths.len())
+        .max(1);
+    info!("Checking {} images ({:?}) on {} threads", paths.len(), check, workers);
+
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let corrupt = Mutex::new(Vec::new());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                if cancel_flag.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+                    break;
+                }
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = paths.get(index) else {
+                    break;
+                };
+                if let Err(error) = check_image(path, check) {
+                    warn!("Corrupt image {:?}: {}", path, error);
+                    corrupt.lock().unwrap().push((index, path.clone(), error));
+                }
+                on_progress(done.fetch_add(1, Ordering::Relaxed) + 1);
+            });
+        }
+    });
+
+    let mut corrupt = corrupt.into_inner().unwrap();
+    corrupt.sort_by_key(|(index, _, _)| *index);
+    corrupt.into_iter().map(|(_, path, error)| (path, error)).collect()
+}
+
+/// Position of the first image in `paths` that fails `check`, in order
+///
+/// Returns None when every image is readable or `cancel_flag` gets set.
+pub fn find_first_corrupt_image(
+    paths: &[PathBuf],
+    check: ImageCheck,
+    cancel_flag: &AtomicBool,
+) -> Option<usize> {
+    for (index, path) in paths.iter().enumerate() {
+        if cancel_flag.load(Ordering::Relaxed) {
+            return None;
+        }
+        if let Err(error) = check_image(path, check) {
+            warn!("Corrupt image {:?}: {}", path, error);
+            return Some(index);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use std::fs;
+
+    #[test]
+    fn test_find_corrupt_images_header_and_full_decode() {
+        let tmp = TempDir::new("yolo_verify_test");
+        let root = tmp.path().to_path_buf();
+
+        let good = root.join("good.png");
+        image::RgbImage::from_fn(64, 64, |x, y| image::Rgb([x as u8 * 4, y as u8 * 4, 128]))
+            .save(&good)
+            .unwrap();
+        let bytes = fs::read(&good).unwrap();
+        let truncated = root.join("truncated.png");
+        fs::write(&truncated, &bytes[..bytes.len() / 2]).unwrap();
+        let garbage = root.join("garbage.png");
+        fs::write(&garbage, b"not an image").unwrap();
+        let paths = vec![good, truncated.clone(), garbage.clone()];
+
+        let progress = AtomicUsize::new(0);
+        let on_progress = |done: usize| {
+            progress.fetch_max(done, Ordering::Relaxed);
+        };
+
+        // The header of a truncated file is intact
+        let header = find_corrupt_images(&paths, ImageCheck::Header, None, &on_progress);
+        assert_eq!(header.len(), 1);
+        assert_eq!(header[0].0, garbage);
+        assert!(!header[0].1.is_empty());
+        assert_eq!(progress.load(Ordering::Relaxed), 3);
+
+        let full = find_corrupt_images(&paths, ImageCheck::FullDecode, None, &on_progress);
+        let found: Vec<&PathBuf> = full.iter().map(|(path, _)| path).collect();
+        assert_eq!(found, [&truncated, &garbage]);
+
+        let keep_going = AtomicBool::new(false);
+        assert_eq!(find_first_corrupt_image(&paths, ImageCheck::FullDecode, &keep_going), Some(1));
+        assert_eq!(find_first_corrupt_image(&paths[..1], ImageCheck::FullDecode, &keep_going), None);
+
+        // Cancelled before the first image: nothing checked
+        let cancel = AtomicBool::new(true);
+        assert!(find_corrupt_images(&paths, ImageCheck::FullDecode, Some(&cancel), &on_progress).is_empty());
+    }
+}
@@ -3,3 +3,4 @@ pub mod dataset;
 pub mod filter;
 pub mod image;
 pub mod operations;
+pub mod statistics;
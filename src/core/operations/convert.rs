@@ -0,0 +1,259 @@
+//! Image format conversion within a dataset.
+//!
+//! Converted images replace their originals under the same stem, so the YOLO
+//! label file (which is format-agnostic) keeps matching without changes.
+
+use crate::core::dataset::{images_dir, DatasetSplit};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Result type for format conversion
+pub type ConvertResult<T> = Result<T, ConvertError>;
+
+/// Error types for format conversion
+#[derive(Debug)]
+pub enum ConvertError {
+    Image(image::ImageError),
+    IoError(std::io::Error),
+    UnsupportedFormat(String),
+    DestinationExists(PathBuf),
+}
+
+impl std::fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConvertError::Image(e) => write!(f, "Image error: {}", e),
+            ConvertError::IoError(e) => write!(f, "I/O error: {}", e),
+            ConvertError::UnsupportedFormat(ext) => write!(f, "Unsupported format: {}", ext),
+            ConvertError::DestinationExists(path) => {
+                write!(f, "Destination already exists: {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+impl From<image::ImageError> for ConvertError {
+    fn from(error: image::ImageError) -> Self {
+        ConvertError::Image(error)
+    }
+}
+
+impl From<std::io::Error> for ConvertError {
+    fn from(error: std::io::Error) -> Self {
+        ConvertError::IoError(error)
+    }
+}
+
+/// Result of converting a split
+#[derive(Debug, Clone, Default)]
+pub struct ConvertReport {
+    /// Images that had a source format for the requested conversion
+    pub total: usize,
+    pub converted: usize,
+    /// Bytes of the originals and of the converted files
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub errors: Vec<String>,
+}
+
+/// Progress message for format conversion
+#[derive(Debug, Clone)]
+pub enum ConvertProgressMessage {
+    Progress { current: usize, total: usize },
+    Complete(ConvertReport),
+    Cancelled(ConvertReport),
+}
+
+/// Source extensions that are converted to `dest_ext`
+///
+/// Converting to JPEG picks up PNGs, converting to PNG picks up JPEGs.
+fn source_extensions(dest_ext: &str) -> ConvertResult<&'static [&'static str]> {
+    match dest_ext.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => Ok(&["png"]),
+        "png" => Ok(&["jpg", "jpeg"]),
+        other => Err(ConvertError::UnsupportedFormat(other.to_string())),
+    }
+}
+
+/// Convert `src` to the `dest_ext` format next to the original
+///
+/// JPEG output is encoded at `quality` (1-100) and drops any alpha channel.
+/// The original is removed once the converted file is written. Returns the
+/// path of the converted image.
+pub fn convert_image_format(src: &Path, dest_ext: &str, quality: u8) -> ConvertResult<PathBuf> {
+    let dest_ext = dest_ext.to_ascii_lowercase();
+    source_extensions(&dest_ext)?;
+
+    let dest = src.with_extension(&dest_ext);
+    if dest.exists() {
+        return Err(ConvertError::DestinationExists(dest));
+    }
+
+    let img = image::open(src)?;
+    if dest_ext == "png" {
+        img.save_with_format(&dest, image::ImageFormat::Png)?;
+    } else {
+        let file = fs::File::create(&dest)?;
+        let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(
+            std::io::BufWriter::new(file),
+            quality.clamp(1, 100),
+        );
+        if let Err(e) = encoder.encode_image(&img.to_rgb8()) {
+            let _ = fs::remove_file(&dest);
+            return Err(e.into());
+        }
+    }
+
+    fs::remove_file(src)?;
+    info!("Converted {:?} -> {:?}", src, dest);
+    Ok(dest)
+}
+
+/// List the images of a split with one of the given extensions (sorted)
+fn list_source_images(dataset_path: &Path, split: DatasetSplit, exts: &[&str]) -> Vec<PathBuf> {
+    let images_path = images_dir(dataset_path, split);
+    let mut files: Vec<PathBuf> = match fs::read_dir(&images_path) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().is_some_and(|ext| {
+                    let ext = ext.to_string_lossy().to_ascii_lowercase();
+                    exts.contains(&ext.as_str())
+                })
+            })
+            .collect(),
+        Err(e) => {
+            warn!("Failed to read images directory {:?}: {}", images_path, e);
+            Vec::new()
+        }
+    };
+    files.sort();
+    files
+}
+
+/// Convert every image of `split` to the `dest_ext` format
+///
+/// Label files are left untouched. Images that fail to convert keep their
+/// original and are listed in the report's errors.
+pub fn batch_convert_split(
+    dataset_path: &Path,
+    split: DatasetSplit,
+    dest_ext: &str,
+    quality: u8,
+    progress_tx: Option<Sender<ConvertProgressMessage>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> ConvertReport {
+    let mut report = ConvertReport::default();
+
+    let files = match source_extensions(dest_ext) {
+        Ok(exts) => list_source_images(dataset_path, split, exts),
+        Err(e) => {
+            report.errors.push(e.to_string());
+            Vec::new()
+        }
+    };
+    report.total = files.len();
+    info!(
+        "Converting {} images in {} to {} (quality {})",
+        report.total,
+        split.as_str(),
+        dest_ext,
+        quality
+    );
+
+    for (idx, src) in files.iter().enumerate() {
+        if let Some(ref cancel) = cancel_flag {
+            if cancel.load(Ordering::Relaxed) {
+                warn!("Format conversion cancelled at {}/{}", idx, report.total);
+                if let Some(ref tx) = progress_tx {
+                    let _ = tx.send(ConvertProgressMessage::Cancelled(report.clone()));
+                }
+                return report;
+            }
+        }
+
+        let size_before = fs::metadata(src).map(|m| m.len()).unwrap_or(0);
+        match convert_image_format(src, dest_ext, quality) {
+            Ok(dest) => {
+                report.converted += 1;
+                report.bytes_before += size_before;
+                report.bytes_after += fs::metadata(&dest).map(|m| m.len()).unwrap_or(0);
+            }
+            Err(e) => {
+                error!("Failed to convert {:?}: {}", src, e);
+                report.errors.push(format!("{}: {}", src.display(), e));
+            }
+        }
+
+        if let Some(ref tx) = progress_tx {
+            let _ = tx.send(ConvertProgressMessage::Progress {
+                current: idx + 1,
+                total: report.total,
+            });
+        }
+    }
+
+    info!(
+        "Format conversion complete: {} / {} images converted, {} errors",
+        report.converted,
+        report.total,
+        report.errors.len()
+    );
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(ConvertProgressMessage::Complete(report.clone()));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn converts_png_split_to_jpg_and_keeps_labels() {
+        let tmp = TempDir::new("convert_test");
+        let root = tmp.path().to_path_buf();
+        let images = root.join("train").join("images");
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&images).unwrap();
+        fs::create_dir_all(&labels).unwrap();
+
+        image::RgbaImage::new(4, 4)
+            .save(images.join("a.png"))
+            .unwrap();
+        image::RgbImage::new(4, 4)
+            .save(images.join("b.jpg"))
+            .unwrap();
+        fs::write(labels.join("a.txt"), "0 0.5 0.5 0.1 0.1\n").unwrap();
+
+        let report = batch_convert_split(&root, DatasetSplit::Train, "jpg", 90, None, None);
+
+        assert_eq!(report.total, 1);
+        assert_eq!(report.converted, 1);
+        assert!(report.errors.is_empty());
+        assert!(!images.join("a.png").exists());
+        assert!(image::open(images.join("a.jpg")).is_ok());
+        assert!(images.join("b.jpg").exists());
+        assert_eq!(
+            fs::read_to_string(labels.join("a.txt")).unwrap(),
+            "0 0.5 0.5 0.1 0.1\n"
+        );
+
+        // Converting back refuses to overwrite an existing file
+        image::RgbImage::new(4, 4)
+            .save(images.join("b.png"))
+            .unwrap();
+        assert!(matches!(
+            convert_image_format(&images.join("b.jpg"), "png", 90),
+            Err(ConvertError::DestinationExists(_))
+        ));
+    }
+}
@@ -0,0 +1,249 @@
+use crate::core::dataset::{overlapping_pairs, parse_detection_line, DatasetSplit, YoloDetection};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{error, info};
+
+use super::file_ops::{FileOpError, FileOpResult};
+
+/// A label file that had (or would have) duplicate boxes removed
+#[derive(Debug, Clone)]
+pub struct DedupeChange {
+    pub label_path: PathBuf,
+    /// Original content backup (None in a dry run)
+    pub backup_path: Option<PathBuf>,
+    /// Content after removing the duplicates (written again on redo)
+    pub deduped_contents: String,
+    /// The removed boxes, in file order
+    pub removed: Vec<YoloDetection>,
+}
+
+/// Result of a duplicate box removal (or dry run)
+#[derive(Debug, Clone, Default)]
+pub struct DedupeSummary {
+    pub files_modified: usize,
+    pub boxes_removed: usize,
+    pub changes: Vec<DedupeChange>,
+    pub errors: Vec<String>,
+}
+
+/// Which box of an overlapping pair is dropped
+///
+/// The lower-confidence box when both have a confidence column, otherwise
+/// the smaller box; ties drop the later box.
+fn pick_duplicate(detections: &[YoloDetection], first: usize, second: usize) -> usize {
+    let (a, b) = (&detections[first], &detections[second]);
+    if let (Some(ca), Some(cb)) = (a.confidence, b.confidence) {
+        if ca != cb {
+            return if ca < cb { first } else { second };
+        }
+    }
+    if a.width * a.height < b.width * b.height {
+        first
+    } else {
+        second
+    }
+}
+
+/// Remove one box of every pair of detections whose IoU is above `threshold`.
+///
+/// Pairs are resolved highest IoU first; a pair whose box was already dropped
+/// is skipped. Comment and unparseable lines are kept unchanged. Returns the
+/// cleaned content and the removed boxes, or None when nothing overlaps.
+pub fn dedupe_label_content(content: &str, threshold: f32) -> Option<(String, Vec<YoloDetection>)> {
+    // Detection index of every line that parses as a detection
+    let mut detections = Vec::new();
+    let mut line_detection = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let detection = if trimmed.is_empty() || trimmed.starts_with('#') {
+            None
+        } else {
+            parse_detection_line(trimmed).ok()
+        };
+        line_detection.push(detection.map(|d| {
+            detections.push(d);
+            detections.len() - 1
+        }));
+    }
+
+    let mut drop = vec![false; detections.len()];
+    for (first, second, _) in overlapping_pairs(&detections, threshold) {
+        if !drop[first] && !drop[second] {
+            drop[pick_duplicate(&detections, first, second)] = true;
+        }
+    }
+    if !drop.contains(&true) {
+        return None;
+    }
+
+    let mut output = String::with_capacity(content.len());
+    for (line, detection) in content.lines().zip(&line_detection) {
+        if detection.is_some_and(|idx| drop[idx]) {
+            continue;
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    let removed = detections
+        .into_iter()
+        .zip(drop)
+        .filter(|(_, dropped)| *dropped)
+        .map(|(detection, _)| detection)
+        .collect();
+    Some((output, removed))
+}
+
+/// Remove duplicate boxes from the given label files.
+///
+/// Originals are copied to `backup_dir/<split>/` before being rewritten. A
+/// dry run only reports which boxes would be removed.
+pub fn remove_overlapping_boxes(
+    label_files: &[(DatasetSplit, PathBuf)],
+    threshold: f32,
+    backup_dir: &Path,
+    dry_run: bool,
+) -> DedupeSummary {
+    let mut summary = DedupeSummary::default();
+    info!(
+        "Removing overlapping boxes (IoU > {:.2}) from {} label files, dry run: {}",
+        threshold,
+        label_files.len(),
+        dry_run
+    );
+
+    for (split, label_path) in label_files {
+        match dedupe_one(label_path, *split, threshold, backup_dir, dry_run) {
+            Ok(Some(change)) => {
+                summary.files_modified += 1;
+                summary.boxes_removed += change.removed.len();
+                summary.changes.push(change);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                error!("Failed to remove overlapping boxes from {:?}: {}", label_path, e);
+                summary
+                    .errors
+                    .push(format!("{}: {}", label_path.display(), e));
+            }
+        }
+    }
+
+    info!(
+        "Overlapping box removal complete: {} boxes from {} files, {} errors",
+        summary.boxes_removed,
+        summary.files_modified,
+        summary.errors.len()
+    );
+    summary
+}
+
+/// Process a single label file
+fn dedupe_one(
+    label_path: &Path,
+    split: DatasetSplit,
+    threshold: f32,
+    backup_dir: &Path,
+    dry_run: bool,
+) -> FileOpResult<Option<DedupeChange>> {
+    let content = fs::read_to_string(label_path)?;
+    let Some((deduped_contents, removed)) = dedupe_label_content(&content, threshold) else {
+        return Ok(None);
+    };
+
+    let backup_path = if dry_run {
+        None
+    } else {
+        let file_name = label_path
+            .file_name()
+            .ok_or_else(|| FileOpError::CopyFailed("Label path has no file name".to_string()))?;
+        let backup = backup_dir.join(split.as_str()).join(file_name);
+        if let Some(parent) = backup.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&backup, &content)?;
+        fs::write(label_path, &deduped_contents)?;
+        Some(backup)
+    };
+
+    Ok(Some(DedupeChange {
+        label_path: label_path.to_path_buf(),
+        backup_path,
+        deduped_contents,
+        removed,
+    }))
+}
+
+/// Restore label files rewritten by a duplicate box removal from their backups.
+///
+/// Returns the number of files restored.
+pub fn restore_deduped_labels(changes: &[DedupeChange]) -> FileOpResult<usize> {
+    let mut restored = 0;
+    for change in changes {
+        if let Some(backup) = &change.backup_path {
+            fs::copy(backup, &change.label_path).map_err(|e| {
+                FileOpError::CopyFailed(format!(
+                    "Failed to restore {:?} from {:?}: {}",
+                    change.label_path, backup, e
+                ))
+            })?;
+            restored += 1;
+        }
+    }
+    info!("Restored {} label files from overlap backups", restored);
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_dedupe_drops_smaller_or_less_confident_box() {
+        let content = "# Map: de_nuke\n\
+                       0 0.5 0.5 0.20 0.20\n\
+                       0 0.5 0.5 0.19 0.20\n\
+                       1 0.1 0.1 0.05 0.05\n";
+        let (cleaned, removed) = dedupe_label_content(content, 0.85).unwrap();
+        assert_eq!(
+            cleaned,
+            "# Map: de_nuke\n0 0.5 0.5 0.20 0.20\n1 0.1 0.1 0.05 0.05\n"
+        );
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].width, 0.19);
+
+        // Confidence wins over size when both boxes have it
+        let content = "0 0.5 0.5 0.20 0.20 0.40\n0 0.5 0.5 0.19 0.20 0.90\n";
+        let (cleaned, _) = dedupe_label_content(content, 0.85).unwrap();
+        assert_eq!(cleaned, "0 0.5 0.5 0.19 0.20 0.90\n");
+
+        // Below the threshold nothing changes
+        assert!(dedupe_label_content("0 0.5 0.5 0.2 0.2\n0 0.6 0.5 0.2 0.2\n", 0.85).is_none());
+    }
+
+    #[test]
+    fn test_remove_overlapping_boxes_with_dry_run_and_restore() {
+        let tmp = TempDir::new("yolo_dedupe_boxes_test");
+        let root = tmp.path().to_path_buf();
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&labels).unwrap();
+        let original = "0 0.5 0.5 0.2 0.2\n0 0.5 0.5 0.2 0.2\n0 0.5 0.5 0.2 0.2\n";
+        let label = labels.join("a.txt");
+        fs::write(&label, original).unwrap();
+        let files = [(DatasetSplit::Train, label.clone())];
+        let backup_dir = root.join("backup");
+
+        let preview = remove_overlapping_boxes(&files, 0.85, &backup_dir, true);
+        assert_eq!(preview.boxes_removed, 2);
+        assert!(preview.changes[0].backup_path.is_none());
+        assert_eq!(fs::read_to_string(&label).unwrap(), original);
+
+        let summary = remove_overlapping_boxes(&files, 0.85, &backup_dir, false);
+        assert_eq!(summary.files_modified, 1);
+        assert!(summary.errors.is_empty());
+        assert_eq!(fs::read_to_string(&label).unwrap(), "0 0.5 0.5 0.2 0.2\n");
+
+        assert_eq!(restore_deduped_labels(&summary.changes).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&label).unwrap(), original);
+    }
+}
@@ -0,0 +1,90 @@
+//! Export a standalone `dataset.yaml` in the canonical YOLO layout.
+//!
+//! Unlike `write_data_yaml`, which updates the dataset's own file in place,
+//! this writes a fresh file anywhere: an absolute `path`, the images folder
+//! of each split that exists relative to it, `nc` and `names`.
+
+use crate::core::dataset::{images_dir, split_exists, DatasetSplit};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Contents of a `dataset.yaml` for the dataset at `root` with `names` (sorted by id)
+pub fn yolo_yaml_content(root: &Path, names: &[(u32, String)]) -> String {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let mut yaml = String::new();
+    let _ = writeln!(yaml, "path: {}", quote(&root.to_string_lossy().replace('\\', "/")));
+    for split in DatasetSplit::all() {
+        if !split_exists(&root, split) {
+            continue;
+        }
+        let dir = images_dir(&root, split);
+        let relative = dir.strip_prefix(&root).unwrap_or(&dir);
+        let _ = writeln!(
+            yaml,
+            "{}: {}",
+            split.as_str(),
+            quote(&relative.to_string_lossy().replace('\\', "/"))
+        );
+    }
+    let _ = writeln!(yaml, "nc: {}", names.len());
+
+    // A list when the ids are 0..n, otherwise an id → name map
+    let contiguous = names
+        .iter()
+        .enumerate()
+        .all(|(idx, (id, _))| *id as usize == idx);
+    if contiguous {
+        let list: Vec<String> = names.iter().map(|(_, name)| quote(name)).collect();
+        let _ = writeln!(yaml, "names: [{}]", list.join(", "));
+    } else {
+        yaml.push_str("names:\n");
+        for (id, name) in names {
+            let _ = writeln!(yaml, "  {}: {}", id, quote(name));
+        }
+    }
+    yaml
+}
+
+/// Write a `dataset.yaml` for the dataset at `root` to `dest`, replacing any file there
+pub fn export_yolo_yaml(root: &Path, names: &[(u32, String)], dest: &Path) -> Result<(), String> {
+    fs::write(dest, yolo_yaml_content(root, names))
+        .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))
+}
+
+/// Single-quoted YAML scalar (quotes doubled)
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use serde_yaml::Value;
+
+    #[test]
+    fn test_export_yolo_yaml_round_trips() {
+        let tmp = TempDir::new("yolo_yaml_export_test");
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("train").join("images")).unwrap();
+        fs::create_dir_all(root.join("val").join("images")).unwrap();
+
+        let names = vec![(0, "T".to_string()), (1, "CT's".to_string())];
+        let dest = root.join("dataset.yaml");
+        export_yolo_yaml(&root, &names, &dest).unwrap();
+        let content = fs::read_to_string(&dest).unwrap();
+        assert!(content.contains("train: 'train/images'\nval: 'val/images'\nnc: 2\n"));
+        assert!(content.ends_with("names: ['T', 'CT''s']\n"));
+        assert!(!content.contains("test:"));
+
+        let value: Value = serde_yaml::from_str(&content).unwrap();
+        assert_eq!(value["names"][1].as_str(), Some("CT's"));
+        assert!(Path::new(value["path"].as_str().unwrap()).is_absolute());
+
+        // Gaps in the class ids are written as a map
+        let sparse = vec![(0, "T".to_string()), (3, "Chicken".to_string())];
+        let value: Value = serde_yaml::from_str(&yolo_yaml_content(&root, &sparse)).unwrap();
+        assert_eq!(value["names"][3].as_str(), Some("Chicken"));
+    }
+}
@@ -1,6 +1,7 @@
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// Result type for file operations
 pub type FileOpResult<T> = Result<T, FileOpError>;
@@ -10,6 +11,7 @@ pub type FileOpResult<T> = Result<T, FileOpError>;
 pub enum FileOpError {
     CopyFailed(String),
     RemoveFailed(String),
+    RenameFailed(String),
     IoError(std::io::Error),
 }
 
@@ -18,6 +20,7 @@ impl std::fmt::Display for FileOpError {
         match self {
             FileOpError::CopyFailed(msg) => write!(f, "Copy failed: {}", msg),
             FileOpError::RemoveFailed(msg) => write!(f, "Remove failed: {}", msg),
+            FileOpError::RenameFailed(msg) => write!(f, "Rename failed: {}", msg),
             FileOpError::IoError(e) => write!(f, "I/O error: {}", e),
         }
     }
@@ -31,20 +34,58 @@ impl From<std::io::Error> for FileOpError {
     }
 }
 
-/// Move a file from source to destination using copy + remove pattern
-/// for cross-drive compatibility.
+/// How `move_file` got a file to its destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveMethod {
+    /// Renamed on the same device
+    Renamed,
+    /// Copied, verified and deleted because the destination is on another device
+    Copied,
+}
+
+/// Move a file from source to destination.
+///
+/// Renames the file when possible. When source and destination are on
+/// different devices (other drives, junction points) the rename fails, and
+/// the file is copied, checked and then deleted instead.
 ///
 /// # Arguments
-/// * `src` - Source file path
+/// * `src` - Source file path (`&PathBuf` arguments coerce to `&Path`)
 /// * `dest` - Destination file path
 ///
 /// # Returns
-/// * `Ok(())` if successful
-/// * `Err(FileOpError)` if copy or remove failed
-pub fn move_file(src: &PathBuf, dest: &PathBuf) -> FileOpResult<()> {
+/// * `Ok(MoveMethod)` telling whether the file was renamed or copied
+/// * `Err(FileOpError)` if the rename, copy or remove failed
+pub fn move_file(src: &Path, dest: &Path) -> FileOpResult<MoveMethod> {
     info!("Moving file from {:?} to {:?}", src, dest);
 
-    // Copy the file to the destination
+    match fs::rename(src, dest) {
+        Ok(()) => {
+            info!("File moved successfully");
+            Ok(MoveMethod::Renamed)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            info!("{:?} and {:?} are on different devices, copying instead", src, dest);
+            copy_across_devices(src, dest)?;
+            Ok(MoveMethod::Copied)
+        }
+        Err(e) => {
+            error!("Failed to move file from {:?} to {:?}: {}", src, dest, e);
+            Err(FileOpError::RenameFailed(format!(
+                "Failed to move from {:?} to {:?}: {}",
+                src, dest, e
+            )))
+        }
+    }
+}
+
+/// Move a file by copy + remove, for destinations on another device.
+///
+/// The copy must have the size of the source and gets its modification time
+/// before the source is removed. If the source cannot be removed the copy is
+/// deleted again, so a failed move never leaves the file in both places.
+fn copy_across_devices(src: &Path, dest: &Path) -> FileOpResult<()> {
+    let src_metadata = fs::metadata(src)?;
     if let Err(e) = fs::copy(src, dest) {
         error!("Failed to copy file from {:?} to {:?}: {}", src, dest, e);
         return Err(FileOpError::CopyFailed(format!(
@@ -53,6 +94,35 @@ pub fn move_file(src: &PathBuf, dest: &PathBuf) -> FileOpResult<()> {
         )));
     }
 
+    // Verify the copy before touching the source
+    let copied_len = fs::metadata(dest).map(|m| m.len()).unwrap_or(0);
+    if copied_len != src_metadata.len() {
+        error!(
+            "Copy of {:?} is {} bytes instead of {}",
+            src,
+            copied_len,
+            src_metadata.len()
+        );
+        let _ = fs::remove_file(dest);
+        return Err(FileOpError::CopyFailed(format!(
+            "Copy of {:?} is incomplete ({} of {} bytes)",
+            src,
+            copied_len,
+            src_metadata.len()
+        )));
+    }
+
+    // Keep the modification time, which fs::copy does not carry over
+    if let Ok(modified) = src_metadata.modified() {
+        let result = fs::File::options()
+            .write(true)
+            .open(dest)
+            .and_then(|file| file.set_modified(modified));
+        if let Err(e) = result {
+            warn!("Failed to keep modification time of {:?}: {}", dest, e);
+        }
+    }
+
     // Remove the original file after successful copy
     if let Err(e) = fs::remove_file(src) {
         error!("Failed to remove original file {:?} after copy: {}", src, e);
@@ -64,7 +134,30 @@ pub fn move_file(src: &PathBuf, dest: &PathBuf) -> FileOpResult<()> {
         )));
     }
 
-    info!("File moved successfully");
+    info!("File copied across devices successfully");
+    Ok(())
+}
+
+/// Copy a file from source to destination, leaving the source in place.
+///
+/// # Arguments
+/// * `src` - Source file path (`&PathBuf` arguments coerce to `&Path`)
+/// * `dest` - Destination file path (overwritten if it exists)
+///
+/// # Returns
+/// * `Ok(())` if successful
+/// * `Err(io::Error)` if the copy failed, keeping the original error kind
+pub fn copy_file(src: &Path, dest: &Path) -> Result<(), io::Error> {
+    info!("Copying file from {:?} to {:?}", src, dest);
+
+    if let Err(e) = fs::copy(src, dest) {
+        error!("Failed to copy file from {:?} to {:?}: {}", src, dest, e);
+        return Err(io::Error::new(
+            e.kind(),
+            format!("Failed to copy from {:?} to {:?}: {}", src, dest, e),
+        ));
+    }
+
     Ok(())
 }
 
@@ -78,9 +171,9 @@ pub fn move_file(src: &PathBuf, dest: &PathBuf) -> FileOpResult<()> {
 /// # Returns
 /// * `Ok(())` if successful
 /// * `Err(FileOpError)` if restoration failed
-pub fn restore_file(temp_path: &PathBuf, original_path: &PathBuf) -> FileOpResult<()> {
+pub fn restore_file(temp_path: &Path, original_path: &Path) -> FileOpResult<()> {
     info!("Restoring file from {:?} to {:?}", temp_path, original_path);
-    move_file(temp_path, original_path)
+    move_file(temp_path, original_path).map(|_| ())
 }
 
 /// Get the corresponding label file path for an image file path.
@@ -102,6 +195,39 @@ pub fn get_label_path_for_image(image_path: &Path) -> Option<PathBuf> {
     })
 }
 
+/// Write label file contents, or remove the file when `contents` is `None`.
+///
+/// Used to save raw label edits and to replay them on undo/redo, where `None`
+/// stands for "the label file did not exist".
+///
+/// # Arguments
+/// * `label_path` - Path to the label file
+/// * `contents` - New file contents, or `None` to remove the file
+///
+/// # Returns
+/// * `Ok(())` if successful
+/// * `Err(FileOpError)` if the file could not be written or removed
+pub fn write_label_contents(label_path: &Path, contents: Option<&str>) -> FileOpResult<()> {
+    match contents {
+        Some(contents) => {
+            if let Some(parent) = label_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(label_path, contents)?;
+            info!("Wrote label file {:?}", label_path);
+        }
+        None => {
+            if label_path.exists() {
+                fs::remove_file(label_path).map_err(|e| {
+                    FileOpError::RemoveFailed(format!("Failed to remove {:?}: {}", label_path, e))
+                })?;
+                info!("Removed label file {:?}", label_path);
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Delete an image file and its corresponding label file if it exists.
 /// Moves both files to a temporary directory for potential undo.
 ///
@@ -114,8 +240,8 @@ pub fn get_label_path_for_image(image_path: &Path) -> Option<PathBuf> {
 /// * `Ok((temp_image_path, temp_label_path))` with paths to the moved files
 /// * `Err(FileOpError)` if the operation failed
 pub fn delete_image_with_label(
-    image_path: &PathBuf,
-    temp_dir: &PathBuf,
+    image_path: &Path,
+    temp_dir: &Path,
     timestamp: u128,
 ) -> FileOpResult<(PathBuf, Option<PathBuf>)> {
     // Get image filename for temp path
@@ -167,6 +293,68 @@ pub fn delete_image_with_label(
     Ok((temp_image_path, temp_label_path))
 }
 
+/// Copy an image and its label file into a YOLO-style folder.
+/// The image goes to `dest_dir/images/` and the label to `dest_dir/labels/`.
+///
+/// # Arguments
+/// * `image_src` - Path to the image file
+/// * `label_src` - Path to the label file, if the image has one
+/// * `dest_dir` - Folder receiving the `images` and `labels` subfolders
+/// * `timestamp` - Optional prefix for the copied file names, to avoid collisions.
+///   Unlike `delete_image_with_label`, which always needs a unique temp name,
+///   this is optional so exports can keep the original file names (`None`).
+///
+/// # Returns
+/// * `Ok((image_path, label_path))` with paths to the copies
+/// * `Err(io::Error)` if either copy failed (a copied image is removed again
+///   when its label fails, so the pair stays consistent)
+pub fn copy_image_with_label(
+    image_src: &Path,
+    label_src: Option<&Path>,
+    dest_dir: &Path,
+    timestamp: Option<u128>,
+) -> Result<(PathBuf, Option<PathBuf>), io::Error> {
+    let dest_name = |path: &Path| -> Result<String, io::Error> {
+        let name = path.file_name().and_then(|n| n.to_str()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Failed to get filename of {:?}", path),
+            )
+        })?;
+        Ok(match timestamp {
+            Some(ts) => format!("{}_{}", ts, name),
+            None => name.to_string(),
+        })
+    };
+
+    let images_dir = dest_dir.join("images");
+    let labels_dir = dest_dir.join("labels");
+    fs::create_dir_all(&images_dir)?;
+
+    let dest_image = images_dir.join(dest_name(image_src)?);
+    copy_file(image_src, &dest_image)?;
+
+    let dest_label = match label_src {
+        Some(label_src) => {
+            let copied = dest_name(label_src).and_then(|name| {
+                fs::create_dir_all(&labels_dir)?;
+                let dest_label = labels_dir.join(name);
+                copy_file(label_src, &dest_label).map(|_| dest_label)
+            });
+            match copied {
+                Ok(dest_label) => Some(dest_label),
+                Err(e) => {
+                    let _ = fs::remove_file(&dest_image);
+                    return Err(e);
+                }
+            }
+        }
+        None => None,
+    };
+
+    Ok((dest_image, dest_label))
+}
+
 /// Restore an image and its label from temporary locations.
 ///
 /// # Arguments
@@ -179,8 +367,8 @@ pub fn delete_image_with_label(
 /// * `Ok(())` if successful
 /// * `Err(FileOpError)` if restoration failed
 pub fn restore_image_with_label(
-    temp_image_path: &PathBuf,
-    original_image_path: &PathBuf,
+    temp_image_path: &Path,
+    original_image_path: &Path,
     temp_label_path: &Option<PathBuf>,
     original_label_path: &Option<PathBuf>,
 ) -> FileOpResult<()> {
@@ -197,3 +385,63 @@ pub fn restore_image_with_label(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_copy_image_with_label_keeps_sources() {
+        let tmp = TempDir::new("file_ops_copy_test");
+        let root = tmp.path().to_path_buf();
+        let src_images = root.join("src").join("train").join("images");
+        let src_labels = root.join("src").join("train").join("labels");
+        fs::create_dir_all(&src_images).unwrap();
+        fs::create_dir_all(&src_labels).unwrap();
+        let image = src_images.join("a.png");
+        let label = src_labels.join("a.txt");
+        fs::write(&image, b"png").unwrap();
+        fs::write(&label, "0 0.5 0.5 0.1 0.1\n").unwrap();
+
+        let dest = root.join("dest");
+        let (dest_image, dest_label) =
+            copy_image_with_label(&image, Some(&label), &dest, Some(42)).unwrap();
+        assert_eq!(dest_image, dest.join("images").join("42_a.png"));
+        assert_eq!(dest_label, Some(dest.join("labels").join("42_a.txt")));
+        assert!(image.exists() && label.exists());
+        assert_eq!(fs::read(&dest_image).unwrap(), b"png");
+
+        // A missing label source fails the pair and removes the copied image
+        let missing = src_labels.join("missing.txt");
+        assert!(copy_image_with_label(&image, Some(&missing), &dest, None).is_err());
+        assert!(!dest.join("images").join("a.png").exists());
+    }
+
+    #[test]
+    fn test_move_file_renames_and_copy_fallback_keeps_mtime() {
+        let tmp = TempDir::new("file_ops_move_test");
+        let root = tmp.path().to_path_buf();
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::create_dir_all(root.join("b")).unwrap();
+
+        let src = root.join("a").join("frame.png");
+        fs::write(&src, b"png data").unwrap();
+        let dest = root.join("b").join("frame.png");
+        assert_eq!(move_file(&src, &dest).unwrap(), MoveMethod::Renamed);
+        assert!(!src.exists());
+        assert_eq!(fs::read(&dest).unwrap(), b"png data");
+
+        // The cross-device fallback, run directly since temp dirs share a device
+        let modified = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000);
+        fs::File::options().write(true).open(&dest).unwrap().set_modified(modified).unwrap();
+        copy_across_devices(&dest, &src).unwrap();
+        assert!(!dest.exists());
+        assert_eq!(fs::read(&src).unwrap(), b"png data");
+        assert_eq!(fs::metadata(&src).unwrap().modified().unwrap(), modified);
+
+        // A missing source fails without creating the destination
+        assert!(copy_across_devices(&dest, &root.join("c.png")).is_err());
+        assert!(!root.join("c.png").exists());
+    }
+}
@@ -0,0 +1,248 @@
+//! Image list manifests: export a list of images (e.g. the filtered view) and
+//! load one back as a filter.
+//!
+//! A `.csv` destination gets a header row and an `index` column (1-based
+//! position in the list); any other extension gets one path per line with the
+//! optional columns tab-separated after it.
+
+use crate::core::analysis::categorize_label;
+use crate::core::dataset::parse_label_file;
+use crate::core::operations::get_label_path_for_image;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Optional per-image columns written after the path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ManifestColumns {
+    pub category: bool,
+    pub detections: bool,
+    pub map: bool,
+    pub location: bool,
+}
+
+impl ManifestColumns {
+    /// Whether any column needs the image's label
+    pub fn any(&self) -> bool {
+        self.category || self.detections || self.map || self.location
+    }
+
+    fn headers(&self) -> Vec<&'static str> {
+        [
+            (self.category, "category"),
+            (self.detections, "detections"),
+            (self.map, "map"),
+            (self.location, "location"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, header)| enabled.then_some(header))
+        .collect()
+    }
+}
+
+/// Quote a CSV field when it contains a separator, quote or newline
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Values of the selected columns for one image, read from its label
+fn column_values(image: &Path, columns: ManifestColumns, known_class_ids: &[u32]) -> Vec<String> {
+    let label = get_label_path_for_image(image).and_then(|path| parse_label_file(&path));
+    let mut values = Vec::new();
+    if columns.category {
+        values.push(categorize_label(label.as_ref(), known_class_ids).as_str().to_string());
+    }
+    if columns.detections {
+        values.push(label.as_ref().map_or(0, |l| l.detections.len()).to_string());
+    }
+    if columns.map {
+        values.push(label.as_ref().and_then(|l| l.map.clone()).unwrap_or_default());
+    }
+    if columns.location {
+        values.push(label.as_ref().and_then(|l| l.location.clone()).unwrap_or_default());
+    }
+    values
+}
+
+/// Contents of a manifest listing `images` in the given order
+///
+/// With `relative_to`, paths under that folder are written relative to it
+/// (with `/` separators); others stay absolute. `known_class_ids` is the
+/// class table used for the category column.
+pub fn manifest_content(
+    images: &[PathBuf],
+    relative_to: Option<&Path>,
+    columns: ManifestColumns,
+    known_class_ids: &[u32],
+    csv: bool,
+) -> String {
+    let mut content = String::new();
+    if csv {
+        let mut header = vec!["index", "path"];
+        header.extend(columns.headers());
+        content.push_str(&header.join(","));
+        content.push('\n');
+    }
+
+    for (position, image) in images.iter().enumerate() {
+        let path = match relative_to.and_then(|root| image.strip_prefix(root).ok()) {
+            Some(relative) => relative.to_string_lossy().replace('\\', "/"),
+            None => image.display().to_string(),
+        };
+        let values = if columns.any() { column_values(image, columns, known_class_ids) } else { Vec::new() };
+
+        let line = if csv {
+            let mut fields = vec![(position + 1).to_string(), csv_field(&path)];
+            fields.extend(values.iter().map(|value| csv_field(value)));
+            fields.join(",")
+        } else {
+            let mut fields = vec![path];
+            fields.extend(values);
+            fields.join("\t")
+        };
+        content.push_str(&line);
+        content.push('\n');
+    }
+    content
+}
+
+/// Write a manifest of `images` to `dest`, as CSV when it ends in `.csv`
+pub fn export_manifest(
+    images: &[PathBuf],
+    relative_to: Option<&Path>,
+    columns: ManifestColumns,
+    known_class_ids: &[u32],
+    dest: &Path,
+) -> Result<(), String> {
+    let csv = dest
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    let content = manifest_content(images, relative_to, columns, known_class_ids, csv);
+    fs::write(dest, content).map_err(|e| format!("Failed to write {}: {}", dest.display(), e))
+}
+
+/// Images named by a manifest, matched against a file list
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestMatch {
+    /// Indices of the matched images, in file list order
+    pub indices: Vec<usize>,
+    /// Entries that matched no image
+    pub missing: Vec<String>,
+}
+
+/// Entries of a manifest: one filename or relative path per line
+///
+/// Blank lines and `#` comments are skipped; of a tab-separated line (as
+/// written by `export_manifest`) only the path is kept.
+pub fn parse_manifest(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split('\t').next().unwrap_or_default().trim())
+        .filter(|entry| !entry.is_empty() && !entry.starts_with('#'))
+        .map(|entry| entry.replace('\\', "/"))
+        .collect()
+}
+
+/// Match manifest entries against `image_files`
+///
+/// A bare filename matches any image with that name; an entry with folders
+/// matches images whose path ends with it (e.g. `train/images/a.png`).
+pub fn match_manifest(entries: &[String], image_files: &[PathBuf]) -> ManifestMatch {
+    let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, image) in image_files.iter().enumerate() {
+        if let Some(name) = image.file_name() {
+            by_name.entry(name.to_string_lossy().into_owned()).or_default().push(index);
+        }
+    }
+
+    let mut indices = BTreeSet::new();
+    let mut missing = Vec::new();
+    for entry in entries {
+        let name = entry.rsplit('/').next().unwrap_or(entry);
+        let suffix = format!("/{}", entry);
+        let matched: Vec<usize> = by_name
+            .get(name)
+            .map(|candidates| {
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|&index| {
+                        let path = image_files[index].to_string_lossy().replace('\\', "/");
+                        path == *entry || path.ends_with(&suffix)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        if matched.is_empty() {
+            missing.push(entry.clone());
+        }
+        indices.extend(matched);
+    }
+
+    ManifestMatch {
+        indices: indices.into_iter().collect(),
+        missing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_manifest_keeps_order_and_writes_columns() {
+        let tmp = TempDir::new("yolo_manifest_test");
+        let root = tmp.path().to_path_buf();
+        let images = root.join("train").join("images");
+        let labels = root.join("train").join("labels");
+        fs::create_dir_all(&images).unwrap();
+        fs::create_dir_all(&labels).unwrap();
+
+        let b = images.join("b.png");
+        let a = images.join("a, copy.png");
+        fs::write(&b, b"").unwrap();
+        fs::write(&a, b"").unwrap();
+        fs::write(labels.join("b.txt"), "0 0.5 0.5 0.1 0.1\n1 0.2 0.2 0.1 0.1\n").unwrap();
+        fs::write(labels.join("a, copy.txt"), "").unwrap();
+        let list = vec![b.clone(), a.clone()];
+
+        let txt = manifest_content(&list, Some(&root), ManifestColumns::default(), &[0, 1], false);
+        assert_eq!(txt, "train/images/b.png\ntrain/images/a, copy.png\n");
+
+        let absolute = manifest_content(&list, None, ManifestColumns::default(), &[0, 1], false);
+        assert_eq!(absolute.lines().next(), Some(b.display().to_string().as_str()));
+
+        let columns = ManifestColumns { category: true, detections: true, ..Default::default() };
+        let csv = manifest_content(&list, Some(&root), columns, &[0, 1], true);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "index,path,category,detections");
+        assert!(lines[1].starts_with("1,train/images/b.png,"));
+        assert!(lines[1].ends_with(",2"));
+        assert_eq!(lines[2], "2,\"train/images/a, copy.png\",Background,0");
+
+        let dest = root.join("list.csv");
+        export_manifest(&list, Some(&root), columns, &[0, 1], &dest).unwrap();
+        assert_eq!(fs::read_to_string(&dest).unwrap(), csv);
+    }
+
+    #[test]
+    fn test_manifest_entries_match_names_and_relative_paths() {
+        let images = vec![
+            PathBuf::from("/data/train/images/a.png"),
+            PathBuf::from("/data/train/images/b.png"),
+            PathBuf::from("/data/train/images/ab.png"),
+        ];
+        let entries = parse_manifest("# worst loss\nb.png\t0.93\n\ntrain\\images\\a.png\nval/images/b.png\nain/images/a.png\nzzz.png\n");
+        assert_eq!(entries, ["b.png", "train/images/a.png", "val/images/b.png", "ain/images/a.png", "zzz.png"]);
+
+        // `b.png` must not match `ab.png`; results follow the file list order
+        let matched = match_manifest(&entries, &images);
+        assert_eq!(matched.indices, vec![0, 1]);
+        assert_eq!(matched.missing, ["val/images/b.png", "ain/images/a.png", "zzz.png"]);
+    }
+}
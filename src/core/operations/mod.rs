@@ -1,5 +1,40 @@
+pub mod convert;
+pub mod dedupe_boxes;
+pub mod export;
 pub mod file_ops;
+pub mod manifest;
+pub mod rename;
+pub mod strip_metadata;
+pub mod trash;
+pub mod validate;
 
+pub use convert::{
+    batch_convert_split, ConvertProgressMessage, ConvertReport,
+};
+pub use dedupe_boxes::{
+    remove_overlapping_boxes, restore_deduped_labels, DedupeChange, DedupeSummary,
+};
 pub use file_ops::{
-    delete_image_with_label, get_label_path_for_image, move_file, restore_image_with_label,
+    copy_image_with_label, delete_image_with_label, get_label_path_for_image,
+    move_file, restore_file, restore_image_with_label, write_label_contents, FileOpResult,
+    MoveMethod,
+};
+pub use export::export_yolo_yaml;
+pub use manifest::{
+    csv_field, export_manifest, match_manifest, parse_manifest, ManifestColumns,
+};
+pub use rename::{
+    batch_rename_with_progress, plan_rename_stems, RenameProgressMessage, RenameScheme,
+};
+pub use strip_metadata::{
+    restore_stripped_labels, strip_label_metadata, StripChange, StripMode, StripProgressMessage,
+    StripSummary,
+};
+pub use trash::{
+    add_manifest_entry, dataset_trash_dir, empty_dataset_trash, is_in_dataset_trash,
+    remove_manifest_entry, send_to_recycle_bin, trash_usage, DeletionMode,
+    TrashManifestEntry,
+};
+pub use validate::{
+    batch_clamp_label_coordinates, ClampProgressMessage,
 };
@@ -0,0 +1,441 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::time::UNIX_EPOCH;
+use tracing::{error, info, warn};
+
+use super::file_ops::{get_label_path_for_image, FileOpError, FileOpResult};
+use crate::core::dataset::parse_label_file;
+
+/// Renamed pairs between progress messages
+const RENAME_PROGRESS_INTERVAL: usize = 50;
+
+/// Naming scheme applied by `batch_rename`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameScheme {
+    /// `{prefix}{index}` with the 1-based index zero-padded to `digits` (e.g. `img_00001.png`)
+    Sequential { prefix: String, digits: usize },
+    /// `{prefix}{unix_time}` using the label's `Time:` metadata, falling back to
+    /// the file's modification time (e.g. `img_1712345678.png`)
+    Timestamp { prefix: String },
+}
+
+/// Outcome of renaming a single image (and its label, if any)
+#[derive(Debug, Clone)]
+pub struct RenameResult {
+    pub old_path: PathBuf,
+    pub new_path: PathBuf,
+    /// (old, new) label paths when the image had a label file
+    pub label: Option<(PathBuf, PathBuf)>,
+}
+
+/// Progress message for a batch rename running on a background thread
+#[derive(Debug)]
+pub enum RenameProgressMessage {
+    /// Renames done so far out of `total` (two per image: to a temporary name, then the final one)
+    Progress { current: usize, total: usize },
+    /// The renamed images, or why the rename failed (everything rolled back)
+    Complete(Result<Vec<RenameResult>, String>),
+}
+
+/// Compute the new file stems for each image without touching the disk.
+///
+/// Duplicate names (e.g. two images with the same timestamp) get a `_2`, `_3`, ...
+/// suffix so every target is unique.
+pub fn plan_rename_stems(images: &[PathBuf], scheme: &RenameScheme) -> Vec<String> {
+    let mut used = HashSet::new();
+
+    images
+        .iter()
+        .enumerate()
+        .map(|(idx, image)| {
+            let base = match scheme {
+                RenameScheme::Sequential { prefix, digits } => {
+                    format!("{}{:0width$}", prefix, idx + 1, width = *digits)
+                }
+                RenameScheme::Timestamp { prefix } => {
+                    format!("{}{}", prefix, image_timestamp(image))
+                }
+            };
+
+            let mut stem = base.clone();
+            let mut suffix = 2;
+            while !used.insert(stem.clone()) {
+                stem = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            stem
+        })
+        .collect()
+}
+
+/// Rename every image (and its label) to the given scheme.
+///
+/// Each pair is renamed image first, then label; if the label rename fails the
+/// image rename is reverted. Renaming goes through temporary names so schemes
+/// that reuse existing names (e.g. re-numbering a sequential split) are safe.
+/// On failure every rename already performed is rolled back.
+///
+/// # Returns
+/// * `Ok(results)` with one entry per image whose name changed
+/// * `Err(FileOpError)` if a target already exists or a rename failed
+// The rename dialog goes through `batch_rename_with_progress`
+#[allow(dead_code)]
+pub fn batch_rename(images: &[PathBuf], scheme: RenameScheme) -> FileOpResult<Vec<RenameResult>> {
+    batch_rename_with_progress(images, scheme, None)
+}
+
+/// Rename like `batch_rename`, sending `Progress` messages as pairs are renamed
+///
+/// The caller sends `Complete` with the returned result.
+pub fn batch_rename_with_progress(
+    images: &[PathBuf],
+    scheme: RenameScheme,
+    progress_tx: Option<&Sender<RenameProgressMessage>>,
+) -> FileOpResult<Vec<RenameResult>> {
+    let stems = plan_rename_stems(images, &scheme);
+    let sources: HashSet<&PathBuf> = images.iter().collect();
+    let source_labels: HashSet<PathBuf> = images
+        .iter()
+        .filter_map(|image| get_label_path_for_image(image))
+        .collect();
+
+    // Build the list of pairs that actually change name
+    let mut planned = Vec::new();
+    for (image, stem) in images.iter().zip(stems) {
+        let new_name = match image.extension() {
+            Some(ext) => format!("{}.{}", stem, ext.to_string_lossy()),
+            None => stem,
+        };
+        let new_path = image.with_file_name(new_name);
+        if &new_path == image {
+            continue;
+        }
+
+        // Refuse to clobber files that are not part of this rename
+        if new_path.exists() && !sources.contains(&new_path) {
+            return Err(FileOpError::RenameFailed(format!(
+                "Target {:?} already exists",
+                new_path
+            )));
+        }
+
+        // Same for labels: another label there would be overwritten or paired with this image
+        let new_label = get_label_path_for_image(&new_path);
+        if let Some(new_label) = new_label.as_ref().filter(|l| l.exists()) {
+            if !source_labels.contains(new_label) {
+                return Err(FileOpError::RenameFailed(format!(
+                    "Target label {:?} already exists",
+                    new_label
+                )));
+            }
+        }
+
+        let label = get_label_path_for_image(image)
+            .filter(|label| label.exists())
+            .zip(new_label);
+
+        planned.push(RenameResult {
+            old_path: image.clone(),
+            new_path,
+            label,
+        });
+    }
+
+    info!("Batch rename: {} of {} images need renaming", planned.len(), images.len());
+
+    // Phase 1: move everything to unique temporary names
+    let staged: Vec<RenameResult> = planned
+        .iter()
+        .enumerate()
+        .map(|(idx, result)| RenameResult {
+            old_path: result.old_path.clone(),
+            new_path: temp_name(&result.old_path, idx),
+            label: result
+                .label
+                .as_ref()
+                .map(|(old, _)| (old.clone(), temp_name(old, idx))),
+        })
+        .collect();
+    let total = planned.len() * 2;
+    let progress = |done: usize| {
+        if let Some(tx) = progress_tx {
+            let _ = tx.send(RenameProgressMessage::Progress { current: done, total });
+        }
+    };
+    run_phase(&staged, &progress)?;
+
+    // Phase 2: move temporary names to their final names
+    let finalize: Vec<RenameResult> = staged
+        .iter()
+        .zip(&planned)
+        .map(|(staged, target)| RenameResult {
+            old_path: staged.new_path.clone(),
+            new_path: target.new_path.clone(),
+            label: staged
+                .label
+                .as_ref()
+                .zip(target.label.as_ref())
+                .map(|((_, tmp), (_, new))| (tmp.clone(), new.clone())),
+        })
+        .collect();
+    if let Err(e) = run_phase(&finalize, &|done| progress(planned.len() + done)) {
+        // Put the staged files back where they started
+        rollback(&staged);
+        return Err(e);
+    }
+
+    info!("Batch rename complete: {} images renamed", planned.len());
+    Ok(planned)
+}
+
+/// Rename every pair in order, rolling back on the first failure
+///
+/// `on_progress` gets the number of pairs renamed so far.
+fn run_phase(pairs: &[RenameResult], on_progress: &dyn Fn(usize)) -> FileOpResult<()> {
+    for (idx, pair) in pairs.iter().enumerate() {
+        if let Err(e) = rename_pair(pair) {
+            rollback(&pairs[..idx]);
+            return Err(e);
+        }
+        if (idx + 1) % RENAME_PROGRESS_INTERVAL == 0 || idx + 1 == pairs.len() {
+            on_progress(idx + 1);
+        }
+    }
+    Ok(())
+}
+
+/// Rename image first, then label; revert the image if the label fails
+fn rename_pair(pair: &RenameResult) -> FileOpResult<()> {
+    fs::rename(&pair.old_path, &pair.new_path).map_err(|e| {
+        error!("Failed to rename {:?} to {:?}: {}", pair.old_path, pair.new_path, e);
+        FileOpError::RenameFailed(format!(
+            "Failed to rename {:?} to {:?}: {}",
+            pair.old_path, pair.new_path, e
+        ))
+    })?;
+
+    if let Some((old_label, new_label)) = &pair.label {
+        if let Err(e) = fs::rename(old_label, new_label) {
+            error!("Failed to rename label {:?} to {:?}: {}", old_label, new_label, e);
+            let _ = fs::rename(&pair.new_path, &pair.old_path);
+            return Err(FileOpError::RenameFailed(format!(
+                "Failed to rename label {:?} to {:?}: {}",
+                old_label, new_label, e
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Undo already-applied renames in reverse order (best effort)
+fn rollback(done: &[RenameResult]) {
+    for pair in done.iter().rev() {
+        if let Some((old_label, new_label)) = &pair.label {
+            if let Err(e) = fs::rename(new_label, old_label) {
+                warn!("Rollback failed for label {:?}: {}", new_label, e);
+            }
+        }
+        if let Err(e) = fs::rename(&pair.new_path, &pair.old_path) {
+            warn!("Rollback failed for {:?}: {}", pair.new_path, e);
+        }
+    }
+}
+
+fn temp_name(path: &Path, idx: usize) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".renaming_{}_{}", idx, name))
+}
+
+/// Unix timestamp for an image: label `Time:` metadata, else file modification time
+fn image_timestamp(image: &Path) -> u64 {
+    let from_label = get_label_path_for_image(image)
+        .and_then(|label| parse_label_file(&label))
+        .and_then(|info| info.timestamp)
+        .and_then(|ts| ts.trim().parse::<u64>().ok());
+
+    from_label.unwrap_or_else(|| {
+        fs::metadata(image)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn setup_split(name: &str, files: &[(&str, Option<&str>)]) -> TempDir {
+        let tmp = TempDir::new(&format!("yolo_rename_test_{}", name));
+        let root = tmp.path();
+        fs::create_dir_all(root.join("images")).unwrap();
+        fs::create_dir_all(root.join("labels")).unwrap();
+        for (image, label) in files {
+            fs::write(root.join("images").join(image), image.as_bytes()).unwrap();
+            if let Some(content) = label {
+                let stem = Path::new(image).file_stem().unwrap().to_str().unwrap();
+                fs::write(root.join("labels").join(format!("{}.txt", stem)), content).unwrap();
+            }
+        }
+        tmp
+    }
+
+    #[test]
+    fn test_plan_sequential_and_timestamp_stems() {
+        let images = vec![PathBuf::from("a.png"), PathBuf::from("b.png")];
+        let sequential = RenameScheme::Sequential {
+            prefix: "img_".to_string(),
+            digits: 5,
+        };
+        assert_eq!(
+            plan_rename_stems(&images, &sequential),
+            vec!["img_00001", "img_00002"]
+        );
+
+        // Missing files fall back to timestamp 0; duplicates get a suffix
+        let timestamp = RenameScheme::Timestamp {
+            prefix: "img_".to_string(),
+        };
+        assert_eq!(
+            plan_rename_stems(&images, &timestamp),
+            vec!["img_0", "img_0_2"]
+        );
+    }
+
+    #[test]
+    fn test_batch_rename_moves_image_and_label() {
+        let tmp = setup_split(
+            "basic",
+            &[("zeta.png", Some("0 0.5 0.5 0.1 0.1")), ("alpha.jpg", None)],
+        );
+        let root = tmp.path();
+        let images = vec![
+            root.join("images").join("zeta.png"),
+            root.join("images").join("alpha.jpg"),
+        ];
+
+        let results = batch_rename(
+            &images,
+            RenameScheme::Sequential {
+                prefix: "img_".to_string(),
+                digits: 3,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(root.join("images/img_001.png").exists());
+        assert!(root.join("labels/img_001.txt").exists());
+        assert!(root.join("images/img_002.jpg").exists());
+        assert!(!root.join("images/zeta.png").exists());
+        assert!(!root.join("labels/zeta.txt").exists());
+        assert!(results[1].label.is_none());
+    }
+
+    #[test]
+    fn test_batch_rename_reports_progress() {
+        let tmp = setup_split("progress", &[("b.png", None), ("a.png", Some("0 0.5 0.5 0.1 0.1"))]);
+        let root = tmp.path();
+        let images = vec![root.join("images").join("b.png"), root.join("images").join("a.png")];
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let scheme = RenameScheme::Sequential { prefix: "img_".to_string(), digits: 2 };
+        let results = batch_rename_with_progress(&images, scheme, Some(&tx)).unwrap();
+        assert_eq!(results.len(), 2);
+
+        let progress: Vec<(usize, usize)> = rx
+            .try_iter()
+            .map(|message| match message {
+                RenameProgressMessage::Progress { current, total } => (current, total),
+                RenameProgressMessage::Complete(_) => panic!("Complete is sent by the caller"),
+            })
+            .collect();
+        assert_eq!(progress.last(), Some(&(4, 4)));
+        assert!(root.join("labels/img_02.txt").exists());
+    }
+
+    #[test]
+    fn test_batch_rename_handles_swapped_names() {
+        // Re-numbering where targets overlap existing sources
+        let tmp = setup_split(
+            "swap",
+            &[("img_2.png", Some("first")), ("img_1.png", Some("second"))],
+        );
+        let root = tmp.path();
+        let images = vec![
+            root.join("images").join("img_2.png"),
+            root.join("images").join("img_1.png"),
+        ];
+
+        batch_rename(
+            &images,
+            RenameScheme::Sequential {
+                prefix: "img_".to_string(),
+                digits: 1,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(root.join("labels/img_1.txt")).unwrap(), "first");
+        assert_eq!(fs::read_to_string(root.join("labels/img_2.txt")).unwrap(), "second");
+    }
+
+    #[test]
+    fn test_batch_rename_refuses_to_overwrite() {
+        let tmp = setup_split(
+            "conflict",
+            &[("a.png", None), ("img_1.png", None)],
+        );
+        let root = tmp.path();
+        // Only rename a.png; img_1.png is an unrelated existing file
+        let images = vec![root.join("images").join("a.png")];
+
+        let result = batch_rename(
+            &images,
+            RenameScheme::Sequential {
+                prefix: "img_".to_string(),
+                digits: 1,
+            },
+        );
+
+        assert!(result.is_err());
+        assert!(root.join("images/a.png").exists());
+    }
+
+    #[test]
+    fn test_batch_rename_refuses_to_overwrite_label() {
+        let tmp = setup_split(
+            "label_conflict",
+            &[("b.png", Some("b")), ("a.png", Some("a"))],
+        );
+        let root = tmp.path();
+        // An orphan label sits where the second image's label would go
+        fs::write(root.join("labels/img_2.txt"), "orphan").unwrap();
+        let images = vec![root.join("images").join("b.png"), root.join("images").join("a.png")];
+
+        let result = batch_rename(
+            &images,
+            RenameScheme::Sequential {
+                prefix: "img_".to_string(),
+                digits: 1,
+            },
+        );
+
+        // Nothing was renamed, not even the first pair
+        assert!(result.is_err());
+        assert!(root.join("images/b.png").exists());
+        assert!(root.join("images/a.png").exists());
+        assert!(!root.join("images/img_1.png").exists());
+        assert_eq!(fs::read_to_string(root.join("labels/b.txt")).unwrap(), "b");
+        assert_eq!(fs::read_to_string(root.join("labels/img_2.txt")).unwrap(), "orphan");
+    }
+}
@@ -0,0 +1,331 @@
+use crate::core::dataset::{labels_dir, DatasetSplit};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use super::file_ops::{FileOpError, FileOpResult};
+
+/// Where stripped label files are written
+#[derive(Debug, Clone)]
+pub enum StripMode {
+    /// Rewrite label files in place, backing up originals to `backup_dir` for undo
+    InPlace { backup_dir: PathBuf },
+    /// Write a cleaned copy of each split's labels folder to `output_dir/<split>/labels`
+    ExportCopy { output_dir: PathBuf },
+}
+
+/// A label file that had (or would have) metadata lines removed
+#[derive(Debug, Clone)]
+pub struct StripChange {
+    pub label_path: PathBuf,
+    /// Original content backup (in-place mode only)
+    pub backup_path: Option<PathBuf>,
+    pub removed_lines: usize,
+}
+
+/// Result of a strip operation (or dry run)
+#[derive(Debug, Clone, Default)]
+pub struct StripSummary {
+    pub dry_run: bool,
+    pub files_scanned: usize,
+    pub files_modified: usize,
+    pub lines_removed: usize,
+    pub changes: Vec<StripChange>,
+    pub errors: Vec<String>,
+}
+
+/// Progress message for metadata stripping
+#[derive(Debug, Clone)]
+pub enum StripProgressMessage {
+    Progress { current: usize, total: usize },
+    Complete(StripSummary),
+    Cancelled(StripSummary),
+}
+
+/// Remove metadata comment lines (starting with `#`) from label content.
+///
+/// Returns the cleaned content and the number of removed lines, or None when
+/// there is nothing to strip.
+pub fn strip_metadata_lines(content: &str) -> Option<(String, usize)> {
+    let mut removed = 0;
+    let mut kept = Vec::new();
+    for line in content.lines() {
+        if line.trim_start().starts_with('#') {
+            removed += 1;
+        } else {
+            kept.push(line);
+        }
+    }
+
+    if removed == 0 {
+        return None;
+    }
+
+    let mut cleaned = kept.join("\n");
+    if !cleaned.is_empty() {
+        cleaned.push('\n');
+    }
+    Some((cleaned, removed))
+}
+
+/// List the label files of a split (sorted)
+fn list_label_files(dataset_path: &Path, split: DatasetSplit) -> Vec<PathBuf> {
+    let labels_path = labels_dir(dataset_path, split);
+    let mut files: Vec<PathBuf> = match fs::read_dir(&labels_path) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("txt"))
+            })
+            .collect(),
+        Err(e) => {
+            warn!("Failed to read labels directory {:?}: {}", labels_path, e);
+            Vec::new()
+        }
+    };
+    files.sort();
+    files
+}
+
+/// Strip metadata comment lines from all label files in the given splits.
+///
+/// In `InPlace` mode the originals are backed up before being rewritten; in
+/// `ExportCopy` mode every label file is written to the output folder (files
+/// without metadata are copied unchanged) and the originals are untouched.
+/// A dry run only reports which files would change.
+pub fn strip_label_metadata(
+    dataset_path: &Path,
+    splits: &[DatasetSplit],
+    mode: &StripMode,
+    dry_run: bool,
+    progress_tx: Option<Sender<StripProgressMessage>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> StripSummary {
+    let mut summary = StripSummary {
+        dry_run,
+        ..StripSummary::default()
+    };
+
+    let files: Vec<(DatasetSplit, PathBuf)> = splits
+        .iter()
+        .flat_map(|&split| {
+            list_label_files(dataset_path, split)
+                .into_iter()
+                .map(move |path| (split, path))
+        })
+        .collect();
+    let total = files.len();
+    info!(
+        "Stripping label metadata: {} files, mode {:?}, dry run: {}",
+        total, mode, dry_run
+    );
+
+    for (idx, (split, label_path)) in files.iter().enumerate() {
+        if let Some(ref cancel) = cancel_flag {
+            if cancel.load(Ordering::Relaxed) {
+                warn!("Metadata stripping cancelled at {}/{}", idx, total);
+                if let Some(ref tx) = progress_tx {
+                    let _ = tx.send(StripProgressMessage::Cancelled(summary.clone()));
+                }
+                return summary;
+            }
+        }
+
+        if let Err(e) = strip_one(label_path, *split, mode, dry_run, &mut summary) {
+            error!("Failed to strip metadata from {:?}: {}", label_path, e);
+            summary
+                .errors
+                .push(format!("{}: {}", label_path.display(), e));
+        }
+        summary.files_scanned += 1;
+
+        if let Some(ref tx) = progress_tx {
+            let _ = tx.send(StripProgressMessage::Progress {
+                current: idx + 1,
+                total,
+            });
+        }
+    }
+
+    info!(
+        "Metadata stripping complete: {} / {} files modified, {} lines removed, {} errors",
+        summary.files_modified,
+        summary.files_scanned,
+        summary.lines_removed,
+        summary.errors.len()
+    );
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(StripProgressMessage::Complete(summary.clone()));
+    }
+    summary
+}
+
+/// Process a single label file, recording any change in `summary`
+fn strip_one(
+    label_path: &Path,
+    split: DatasetSplit,
+    mode: &StripMode,
+    dry_run: bool,
+    summary: &mut StripSummary,
+) -> FileOpResult<()> {
+    let content = fs::read_to_string(label_path)?;
+    let file_name = label_path
+        .file_name()
+        .ok_or_else(|| FileOpError::CopyFailed("Label path has no file name".to_string()))?;
+
+    let (dest_path, backup_path) = match mode {
+        StripMode::InPlace { backup_dir } => (
+            label_path.to_path_buf(),
+            Some(backup_dir.join(split.as_str()).join(file_name)),
+        ),
+        StripMode::ExportCopy { output_dir } => (
+            output_dir
+                .join(split.as_str())
+                .join("labels")
+                .join(file_name),
+            None,
+        ),
+    };
+
+    let stripped = strip_metadata_lines(&content);
+
+    if !dry_run {
+        match (&stripped, mode) {
+            (Some((cleaned, _)), StripMode::InPlace { .. }) => {
+                let backup = backup_path
+                    .as_ref()
+                    .expect("in-place mode always has a backup path");
+                if let Some(parent) = backup.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(backup, &content)?;
+                fs::write(&dest_path, cleaned)?;
+            }
+            (_, StripMode::ExportCopy { .. }) => {
+                if let Some(parent) = dest_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let output = stripped
+                    .as_ref()
+                    .map(|(cleaned, _)| cleaned.as_str())
+                    .unwrap_or(&content);
+                fs::write(&dest_path, output)?;
+            }
+            (None, StripMode::InPlace { .. }) => {}
+        }
+    }
+
+    if let Some((_, removed_lines)) = stripped {
+        summary.files_modified += 1;
+        summary.lines_removed += removed_lines;
+        summary.changes.push(StripChange {
+            label_path: label_path.to_path_buf(),
+            backup_path,
+            removed_lines,
+        });
+    }
+
+    Ok(())
+}
+
+/// Restore label files rewritten by an in-place strip from their backups.
+///
+/// Returns the number of files restored.
+pub fn restore_stripped_labels(changes: &[StripChange]) -> FileOpResult<usize> {
+    let mut restored = 0;
+    for change in changes {
+        if let Some(backup) = &change.backup_path {
+            fs::copy(backup, &change.label_path).map_err(|e| {
+                FileOpError::CopyFailed(format!(
+                    "Failed to restore {:?} from {:?}: {}",
+                    change.label_path, backup, e
+                ))
+            })?;
+            restored += 1;
+        }
+    }
+    info!("Restored {} label files from metadata backups", restored);
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    const LABEL_WITH_METADATA: &str =
+        "# Resolution: 2560x1440, Map: de_dust2\n0 0.5 0.5 0.1 0.2\n1 0.2 0.3 0.05 0.1\n";
+
+    fn setup_dataset(name: &str) -> TempDir {
+        let tmp = TempDir::new(&format!("yolo_strip_test_{}", name));
+        let labels = tmp.path().join("train").join("labels");
+        fs::create_dir_all(&labels).unwrap();
+        fs::write(labels.join("a.txt"), LABEL_WITH_METADATA).unwrap();
+        fs::write(labels.join("b.txt"), "0 0.5 0.5 0.1 0.2\n").unwrap();
+        tmp
+    }
+
+    #[test]
+    fn test_strip_metadata_lines() {
+        let (cleaned, removed) = strip_metadata_lines(LABEL_WITH_METADATA).unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(cleaned, "0 0.5 0.5 0.1 0.2\n1 0.2 0.3 0.05 0.1\n");
+        assert!(strip_metadata_lines("0 0.5 0.5 0.1 0.2\n").is_none());
+        assert_eq!(
+            strip_metadata_lines("# Map: de_nuke\n"),
+            Some((String::new(), 1))
+        );
+    }
+
+    #[test]
+    fn test_in_place_strip_with_dry_run_and_undo() {
+        let tmp = setup_dataset("in_place");
+        let root = tmp.path();
+        let label_a = root.join("train").join("labels").join("a.txt");
+        let mode = StripMode::InPlace {
+            backup_dir: root.join("backup"),
+        };
+
+        let preview = strip_label_metadata(root, &[DatasetSplit::Train], &mode, true, None, None);
+        assert_eq!(preview.files_scanned, 2);
+        assert_eq!(preview.files_modified, 1);
+        assert_eq!(fs::read_to_string(&label_a).unwrap(), LABEL_WITH_METADATA);
+
+        let summary = strip_label_metadata(root, &[DatasetSplit::Train], &mode, false, None, None);
+        assert_eq!(summary.files_modified, 1);
+        assert!(summary.errors.is_empty());
+        assert!(!fs::read_to_string(&label_a).unwrap().contains('#'));
+
+        assert_eq!(restore_stripped_labels(&summary.changes).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&label_a).unwrap(), LABEL_WITH_METADATA);
+    }
+
+    #[test]
+    fn test_export_copy_keeps_originals() {
+        let tmp = setup_dataset("export");
+        let root = tmp.path();
+        let output = root.join("export");
+        let mode = StripMode::ExportCopy {
+            output_dir: output.clone(),
+        };
+
+        let summary = strip_label_metadata(root, &[DatasetSplit::Train], &mode, false, None, None);
+        assert_eq!(summary.files_modified, 1);
+
+        let exported = output.join("train").join("labels");
+        assert!(!fs::read_to_string(exported.join("a.txt"))
+            .unwrap()
+            .contains('#'));
+        assert_eq!(
+            fs::read_to_string(exported.join("b.txt")).unwrap(),
+            "0 0.5 0.5 0.1 0.2\n"
+        );
+        let original = root.join("train").join("labels").join("a.txt");
+        assert_eq!(fs::read_to_string(original).unwrap(), LABEL_WITH_METADATA);
+    }
+}
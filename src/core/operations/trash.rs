@@ -0,0 +1,217 @@
+//! Where deleted images go.
+//!
+//! Deleted files are either moved to the system temp directory (wiped by the
+//! OS eventually), sent to the OS recycle bin, or moved to a `.cleaner/trash/`
+//! folder inside the dataset root with a manifest recording where each file
+//! came from.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use super::file_ops::{get_label_path_for_image, FileOpError, FileOpResult};
+
+/// Trash folder inside a dataset root
+pub const CLEANER_TRASH_DIR: &str = ".cleaner/trash";
+
+/// Manifest listing the files in the cleaner trash (one JSON object per line)
+const MANIFEST_FILE: &str = "manifest.jsonl";
+
+/// Where deleted images and labels are moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DeletionMode {
+    /// System temp directory (the OS may clear it)
+    #[default]
+    TempDir,
+    /// OS recycle bin; deletions cannot be undone from the app
+    RecycleBin,
+    /// `.cleaner/trash/` inside the dataset, with a manifest
+    DatasetTrash,
+}
+
+impl DeletionMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DeletionMode::TempDir => "Temp folder",
+            DeletionMode::RecycleBin => "Recycle bin",
+            DeletionMode::DatasetTrash => "Dataset trash (.cleaner/trash)",
+        }
+    }
+
+    pub fn all() -> [DeletionMode; 3] {
+        [
+            DeletionMode::TempDir,
+            DeletionMode::RecycleBin,
+            DeletionMode::DatasetTrash,
+        ]
+    }
+}
+
+/// One trashed image in the cleaner trash manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashManifestEntry {
+    /// When the image was deleted (RFC 3339)
+    pub deleted_at: String,
+    pub original_image: PathBuf,
+    pub trashed_image: PathBuf,
+    pub original_label: Option<PathBuf>,
+    pub trashed_label: Option<PathBuf>,
+}
+
+/// The cleaner trash folder for a dataset root
+pub fn dataset_trash_dir(dataset_root: &Path) -> PathBuf {
+    dataset_root.join(CLEANER_TRASH_DIR)
+}
+
+/// Whether a path lies inside a cleaner trash folder
+pub fn is_in_dataset_trash(path: &Path) -> bool {
+    path.parent()
+        .is_some_and(|parent| parent.ends_with(CLEANER_TRASH_DIR))
+}
+
+/// Append an entry to the manifest of the trash folder it was moved into
+pub fn add_manifest_entry(entry: &TrashManifestEntry) -> FileOpResult<()> {
+    let Some(trash_dir) = entry.trashed_image.parent() else {
+        return Ok(());
+    };
+    let line = serde_json::to_string(entry)
+        .map_err(|e| FileOpError::CopyFailed(format!("Failed to write manifest: {}", e)))?;
+    let mut manifest = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(trash_dir.join(MANIFEST_FILE))?;
+    writeln!(manifest, "{}", line)?;
+    Ok(())
+}
+
+/// Remove the entry for a trashed image once it has been restored
+pub fn remove_manifest_entry(trashed_image: &Path) -> FileOpResult<()> {
+    let Some(trash_dir) = trashed_image.parent() else {
+        return Ok(());
+    };
+    let entries: Vec<TrashManifestEntry> = read_manifest(trash_dir)
+        .into_iter()
+        .filter(|entry| entry.trashed_image != trashed_image)
+        .collect();
+
+    let mut contents = String::new();
+    for entry in &entries {
+        if let Ok(line) = serde_json::to_string(entry) {
+            contents.push_str(&line);
+            contents.push('\n');
+        }
+    }
+    fs::write(trash_dir.join(MANIFEST_FILE), contents)?;
+    Ok(())
+}
+
+/// Read the manifest of a cleaner trash folder (skipping unreadable lines)
+pub fn read_manifest(trash_dir: &Path) -> Vec<TrashManifestEntry> {
+    let Ok(contents) = fs::read_to_string(trash_dir.join(MANIFEST_FILE)) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                warn!("Skipping unreadable trash manifest line: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Send an image and its label file (if any) to the OS recycle bin
+pub fn send_to_recycle_bin(image_path: &Path) -> FileOpResult<()> {
+    trash::delete(image_path).map_err(|e| {
+        FileOpError::RemoveFailed(format!(
+            "Failed to send {:?} to the recycle bin: {}",
+            image_path, e
+        ))
+    })?;
+
+    if let Some(label_path) = get_label_path_for_image(image_path) {
+        if label_path.exists() {
+            if let Err(e) = trash::delete(&label_path) {
+                warn!(
+                    "Failed to send label {:?} to the recycle bin: {}",
+                    label_path, e
+                );
+            }
+        }
+    }
+    info!("Sent {:?} to the recycle bin", image_path);
+    Ok(())
+}
+
+/// Number of files and total bytes in a cleaner trash folder
+pub fn trash_usage(trash_dir: &Path) -> (usize, u64) {
+    let Ok(entries) = fs::read_dir(trash_dir) else {
+        return (0, 0);
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name() != MANIFEST_FILE)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .fold((0, 0), |(files, bytes), metadata| {
+            (files + 1, bytes + metadata.len())
+        })
+}
+
+/// Permanently delete everything in a cleaner trash folder
+///
+/// Returns the number of files removed and the bytes freed.
+pub fn empty_dataset_trash(trash_dir: &Path) -> FileOpResult<(usize, u64)> {
+    if !trash_dir.exists() {
+        return Ok((0, 0));
+    }
+    let usage = trash_usage(trash_dir);
+    fs::remove_dir_all(trash_dir).map_err(|e| {
+        FileOpError::RemoveFailed(format!("Failed to empty {:?}: {}", trash_dir, e))
+    })?;
+    info!(
+        "Emptied cleaner trash {:?}: {} files, {} bytes",
+        trash_dir, usage.0, usage.1
+    );
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_manifest_roundtrip_and_empty() {
+        let tmp = TempDir::new("yolo_dataset_trash_test");
+        let root = tmp.path().to_path_buf();
+        let trash_dir = dataset_trash_dir(&root);
+        fs::create_dir_all(&trash_dir).unwrap();
+
+        let entry = |name: &str| TrashManifestEntry {
+            deleted_at: "2026-01-01T00:00:00+00:00".to_string(),
+            original_image: root.join("train/images").join(name),
+            trashed_image: trash_dir.join(format!("1_{}", name)),
+            original_label: None,
+            trashed_label: None,
+        };
+        for name in ["a.png", "b.png"] {
+            fs::write(trash_dir.join(format!("1_{}", name)), b"12345").unwrap();
+            add_manifest_entry(&entry(name)).unwrap();
+        }
+        assert!(is_in_dataset_trash(&entry("a.png").trashed_image));
+        assert!(!is_in_dataset_trash(&entry("a.png").original_image));
+
+        remove_manifest_entry(&entry("a.png").trashed_image).unwrap();
+        assert_eq!(read_manifest(&trash_dir), vec![entry("b.png")]);
+
+        assert_eq!(trash_usage(&trash_dir), (2, 10));
+        assert_eq!(empty_dataset_trash(&trash_dir).unwrap(), (2, 10));
+        assert!(!trash_dir.exists());
+        assert_eq!(empty_dataset_trash(&trash_dir).unwrap(), (0, 0));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::core::dataset::parse_detection_line;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use super::file_ops::FileOpResult;
+
+/// Result of clamping the coordinates of a set of label files
+#[derive(Debug, Clone, Default)]
+pub struct ClampReport {
+    /// Label files processed
+    pub total: usize,
+    pub files_fixed: usize,
+    pub lines_fixed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Progress message for a batch coordinate fix
+#[derive(Debug, Clone)]
+pub enum ClampProgressMessage {
+    Progress { current: usize, total: usize },
+    Complete(ClampReport),
+    Cancelled(ClampReport),
+}
+
+/// Clamp every out-of-range detection in label content to the image.
+///
+/// Only the detection lines that change are rewritten; comments and
+/// unparseable lines are kept as they are. Returns the new content and the
+/// number of fixed lines, or None when every box is already inside the image.
+pub fn clamp_label_content(content: &str) -> Option<(String, usize)> {
+    let mut fixed = 0;
+    let mut output = String::with_capacity(content.len());
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let detection = if trimmed.is_empty() || trimmed.starts_with('#') {
+            None
+        } else {
+            parse_detection_line(trimmed).ok()
+        };
+        match detection {
+            Some(detection) if !detection.is_within_bounds() => {
+                fixed += 1;
+                output.push_str(&detection.clamped().to_label_line());
+            }
+            _ => output.push_str(line),
+        }
+        output.push('\n');
+    }
+
+    if fixed == 0 {
+        None
+    } else {
+        Some((output, fixed))
+    }
+}
+
+/// Clamp out-of-range coordinates of a label file in place.
+///
+/// The file is only written when a line changed. Returns the number of fixed
+/// lines.
+pub fn clamp_label_coordinates(label_path: &Path) -> FileOpResult<usize> {
+    let content = fs::read_to_string(label_path)?;
+    let Some((clamped, fixed)) = clamp_label_content(&content) else {
+        return Ok(0);
+    };
+    fs::write(label_path, clamped)?;
+    info!("Clamped {} detections in {:?}", fixed, label_path);
+    Ok(fixed)
+}
+
+/// Clamp out-of-range coordinates of every given label file
+pub fn batch_clamp_label_coordinates(
+    label_paths: &[PathBuf],
+    progress_tx: Option<Sender<ClampProgressMessage>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> ClampReport {
+    let mut report = ClampReport {
+        total: label_paths.len(),
+        ..ClampReport::default()
+    };
+    info!("Fixing label coordinates in {} files", report.total);
+
+    for (idx, label_path) in label_paths.iter().enumerate() {
+        if cancel_flag
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+        {
+            warn!("Coordinate fix cancelled at {}/{}", idx, report.total);
+            if let Some(tx) = progress_tx {
+                let _ = tx.send(ClampProgressMessage::Cancelled(report.clone()));
+            }
+            return report;
+        }
+
+        match clamp_label_coordinates(label_path) {
+            Ok(0) => {}
+            Ok(fixed) => {
+                report.files_fixed += 1;
+                report.lines_fixed += fixed;
+            }
+            Err(e) => {
+                error!("Failed to fix coordinates in {:?}: {}", label_path, e);
+                report
+                    .errors
+                    .push(format!("{}: {}", label_path.display(), e));
+            }
+        }
+
+        if let Some(ref tx) = progress_tx {
+            let _ = tx.send(ClampProgressMessage::Progress {
+                current: idx + 1,
+                total: report.total,
+            });
+        }
+    }
+
+    info!(
+        "Coordinate fix complete: {} lines in {} files, {} errors",
+        report.lines_fixed,
+        report.files_fixed,
+        report.errors.len()
+    );
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(ClampProgressMessage::Complete(report.clone()));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_clamp_label_content_rewrites_only_bad_lines() {
+        let content = "# Map: de_inferno\n\
+                       0 0.5 0.5 0.2 0.2\n\
+                       1 1.05 0.5 0.2 0.2 0.80\n\
+                       not a detection\n";
+        let (clamped, fixed) = clamp_label_content(content).unwrap();
+        assert_eq!(fixed, 1);
+        assert_eq!(
+            clamped,
+            "# Map: de_inferno\n\
+             0 0.5 0.5 0.2 0.2\n\
+             1 0.950000 0.500000 0.100000 0.200000 0.8000\n\
+             not a detection\n"
+        );
+        assert!(clamp_label_content("0 0.5 0.5 0.2 0.2\n").is_none());
+    }
+
+    #[test]
+    fn test_clamp_label_coordinates_writes_only_when_changed() {
+        let tmp = TempDir::new("yolo_clamp_test");
+        let root = tmp.path().to_path_buf();
+        let good = root.join("good.txt");
+        let bad = root.join("bad.txt");
+        fs::write(&good, "0 0.5 0.5 0.2 0.2").unwrap();
+        fs::write(&bad, "0 0.5 -0.2 0.2 0.2\n1 0.5 0.5 1.5 0.2\n").unwrap();
+
+        let report = batch_clamp_label_coordinates(&[good.clone(), bad.clone()], None, None);
+        assert_eq!(report.files_fixed, 1);
+        assert_eq!(report.lines_fixed, 2);
+        // Untouched file keeps its original formatting (no trailing newline added)
+        assert_eq!(fs::read_to_string(&good).unwrap(), "0 0.5 0.5 0.2 0.2");
+        assert_eq!(clamp_label_coordinates(&bad).unwrap(), 0);
+    }
+}
@@ -0,0 +1,12 @@
+mod report;
+mod session;
+
+pub use report::{
+    generate_html_report, OperationEntry, OP_AUGMENT, OP_BATCH_RENAME, OP_BULK_MOVE, OP_CONFIRM_BACKGROUND,
+    OP_CONVERT_FORMAT, OP_CROP_EXPORT, OP_DEDUPE_BOXES, OP_DELETE_IMAGE, OP_EDIT_LABEL, OP_EMPTY_TRASH, OP_EXPORT_SUBSET, OP_FIX_COORDINATES, OP_FLIP_AUGMENT,
+    OP_GLOBAL_REBALANCE, OP_INTEGRITY_FIX, OP_REBALANCE, OP_REDO_AUGMENT, OP_REDO_DEDUPE_BOXES, OP_REDO_DELETE, OP_REDO_EDIT_LABEL,
+    OP_REDO_INTEGRITY_FIX, OP_REMOVE_AUGMENTED, OP_REMOVE_BLACK_IMAGES, OP_REMOVE_EXTREME_IMAGES,
+    OP_REMOVE_WHITE_IMAGES, OP_STRIP_METADATA, OP_SWAP_SPLITS, OP_UNDO_AUGMENT, OP_UNDO_DEDUPE_BOXES, OP_UNDO_DELETE, OP_UNDO_EDIT_LABEL,
+    OP_UNDO_INTEGRITY_FIX, OP_UNDO_REBALANCE, OP_UNDO_STRIP_METADATA,
+};
+pub use session::{format_duration, SessionStats};
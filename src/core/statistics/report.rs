@@ -0,0 +1,317 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Local};
+
+use crate::core::analysis::{
+    GlobalBalanceStats, ImageCategory, IntegrityIssue, IntegrityIssueType, IntegrityStats,
+};
+use crate::core::dataset::DatasetSplit;
+
+/// Operation kinds recorded in the session operations log
+pub const OP_DELETE_IMAGE: &str = "Delete image";
+pub const OP_UNDO_DELETE: &str = "Undo delete";
+pub const OP_REDO_DELETE: &str = "Redo delete";
+pub const OP_EDIT_LABEL: &str = "Edit label file";
+pub const OP_UNDO_EDIT_LABEL: &str = "Undo label edit";
+pub const OP_REDO_EDIT_LABEL: &str = "Redo label edit";
+pub const OP_AUGMENT: &str = "Augment image";
+pub const OP_UNDO_AUGMENT: &str = "Undo augment";
+pub const OP_REDO_AUGMENT: &str = "Redo augment";
+pub const OP_REMOVE_BLACK_IMAGES: &str = "Remove black images";
+pub const OP_REMOVE_WHITE_IMAGES: &str = "Remove white images";
+pub const OP_REMOVE_EXTREME_IMAGES: &str = "Remove black and white images";
+pub const OP_REBALANCE: &str = "Rebalance split";
+pub const OP_GLOBAL_REBALANCE: &str = "Global rebalance";
+pub const OP_BULK_MOVE: &str = "Move filtered to split";
+pub const OP_UNDO_REBALANCE: &str = "Undo rebalance";
+pub const OP_SWAP_SPLITS: &str = "Swap splits";
+pub const OP_INTEGRITY_FIX: &str = "Delete orphaned files";
+pub const OP_UNDO_INTEGRITY_FIX: &str = "Undo delete orphaned files";
+pub const OP_REDO_INTEGRITY_FIX: &str = "Redo delete orphaned files";
+pub const OP_DEDUPE_BOXES: &str = "Remove duplicate boxes";
+pub const OP_UNDO_DEDUPE_BOXES: &str = "Undo remove duplicate boxes";
+pub const OP_REDO_DEDUPE_BOXES: &str = "Redo remove duplicate boxes";
+pub const OP_FIX_COORDINATES: &str = "Clamp label coordinates";
+pub const OP_CONFIRM_BACKGROUND: &str = "Confirm empty labels as background";
+pub const OP_BATCH_RENAME: &str = "Batch rename";
+pub const OP_EXPORT_SUBSET: &str = "Export subset";
+pub const OP_STRIP_METADATA: &str = "Strip label metadata";
+pub const OP_UNDO_STRIP_METADATA: &str = "Undo strip label metadata";
+pub const OP_EMPTY_TRASH: &str = "Empty cleaner trash";
+pub const OP_CONVERT_FORMAT: &str = "Convert image format";
+pub const OP_FLIP_AUGMENT: &str = "Flip augment images";
+pub const OP_REMOVE_AUGMENTED: &str = "Remove generated augmentations";
+pub const OP_CROP_EXPORT: &str = "Export detection crops";
+
+const CATEGORIES: [ImageCategory; 6] = [
+    ImageCategory::CTOnly,
+    ImageCategory::TOnly,
+    ImageCategory::MultiplePlayer,
+    ImageCategory::Background,
+    ImageCategory::HardCase,
+    ImageCategory::Unknown,
+];
+
+/// A single entry in the session operations log
+#[derive(Debug, Clone)]
+pub struct OperationEntry {
+    /// When the operation finished
+    pub timestamp: DateTime<Local>,
+    /// Human-readable operation kind (see the `OP_*` constants)
+    pub kind: String,
+    /// Number of files affected by the operation
+    pub affected_count: usize,
+}
+
+impl OperationEntry {
+    /// Create an entry stamped with the current local time
+    pub fn new(kind: &str, affected_count: usize) -> Self {
+        Self {
+            timestamp: Local::now(),
+            kind: kind.to_string(),
+            affected_count,
+        }
+    }
+}
+
+/// Generate a self-contained HTML report of the dataset state and session history
+pub fn generate_html_report(
+    stats: &GlobalBalanceStats,
+    integrity: &IntegrityStats,
+    operations_log: &[OperationEntry],
+) -> String {
+    let mut html = String::new();
+
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>YOLO Dataset Cleaner Report</title>\n");
+    html.push_str(STYLE);
+    html.push_str("</head>\n<body>\n");
+
+    let _ = writeln!(html, "<h1>YOLO Dataset Cleaner Report</h1>");
+    let _ = writeln!(
+        html,
+        "<p class=\"meta\">Generated {} &middot; {} images across all splits</p>",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        stats.total_images()
+    );
+
+    // Split breakdown
+    html.push_str("<h2>Split Breakdown</h2>\n");
+    for split in DatasetSplit::all() {
+        write_split_table(&mut html, split, stats);
+    }
+
+    // Integrity
+    let fixed: usize = operations_log
+        .iter()
+        .filter(|op| op.kind == OP_INTEGRITY_FIX)
+        .map(|op| op.affected_count)
+        .sum();
+
+    html.push_str("<h2>Integrity</h2>\n");
+    let _ = writeln!(
+        html,
+        "<p>Issues found: <b>{}</b> &middot; Orphaned files fixed this session: <b>{}</b></p>",
+        integrity.total_issues(),
+        fixed
+    );
+    if integrity.has_issues() {
+        html.push_str("<table>\n<tr><th>Issue</th><th>File</th><th>Missing counterpart</th></tr>\n");
+        for issue in &integrity.images_without_labels {
+            write_issue_row(&mut html, "Image without label", issue);
+        }
+        for issue in &integrity.labels_without_images {
+            write_issue_row(&mut html, "Label without image", issue);
+        }
+        for issue in &integrity.labels_with_unknown_classes {
+            let ids: Vec<String> = issue.class_ids.iter().map(|id| id.to_string()).collect();
+            let _ = writeln!(
+                html,
+                "<tr><td>Unknown class</td><td>{}</td><td>class ids {}</td></tr>",
+                escape_html(&issue.path.display().to_string()),
+                ids.join(", ")
+            );
+        }
+        for issue in &integrity.overlapping_boxes {
+            let _ = writeln!(
+                html,
+                "<tr><td>Overlapping boxes</td><td>{}</td><td>{} pair(s), max IoU {:.2}</td></tr>",
+                escape_html(&issue.path.display().to_string()),
+                issue.pairs.len(),
+                issue.pairs.first().map_or(0.0, |pair| pair.iou)
+            );
+        }
+        for issue in &integrity.out_of_range_boxes {
+            let _ = writeln!(
+                html,
+                "<tr><td>Box outside image</td><td>{}</td><td>{} detection(s)</td></tr>",
+                escape_html(&issue.path.display().to_string()),
+                issue.detections
+            );
+        }
+        for issue in &integrity.corrupt_images {
+            if let IntegrityIssueType::CorruptImage { error } = &issue.issue_type {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>Corrupt image</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&issue.path.display().to_string()),
+                    escape_html(error)
+                );
+            }
+        }
+        for issue in &integrity.malformed_labels {
+            let _ = writeln!(
+                html,
+                "<tr><td>Malformed label</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&issue.path.display().to_string()),
+                escape_html(&issue.error.to_string())
+            );
+        }
+        for issue in &integrity.duplicates_across_splits {
+            let paths: Vec<String> = issue
+                .copies
+                .iter()
+                .map(|(_, path)| escape_html(&path.display().to_string()))
+                .collect();
+            let _ = writeln!(
+                html,
+                "<tr><td>Duplicate across splits</td><td>{}</td><td>{}</td></tr>",
+                paths.join("<br>"),
+                escape_html(&issue.description())
+            );
+        }
+        html.push_str("</table>\n");
+    } else {
+        html.push_str("<p class=\"ok\">No integrity issues found.</p>\n");
+    }
+
+    // Operations log
+    html.push_str("<h2>Operations Performed</h2>\n");
+    if operations_log.is_empty() {
+        html.push_str("<p class=\"meta\">No operations recorded this session.</p>\n");
+    } else {
+        html.push_str("<table>\n<tr><th>Time</th><th>Operation</th><th>Files affected</th></tr>\n");
+        for op in operations_log {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td></tr>",
+                op.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                escape_html(&op.kind),
+                op.affected_count
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn write_split_table(html: &mut String, split: DatasetSplit, stats: &GlobalBalanceStats) {
+    if !stats.is_present(split) {
+        let _ = writeln!(html, "<h3>{} <span class=\"meta\">(not present)</span></h3>", split.as_str());
+        return;
+    }
+    let split_stats = stats.get(split);
+    let _ = writeln!(
+        html,
+        "<h3>{} <span class=\"meta\">({} images)</span></h3>",
+        split.as_str(),
+        split_stats.total_images
+    );
+    html.push_str("<table>\n<tr><th>Category</th><th>Count</th><th>Percentage</th></tr>\n");
+    for category in CATEGORIES {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td></tr>",
+            category.as_str(),
+            split_stats.get_count(category),
+            split_stats.get_percentage(category)
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn write_issue_row(html: &mut String, label: &str, issue: &IntegrityIssue) {
+    let _ = writeln!(
+        html,
+        "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+        label,
+        escape_html(&issue.path.display().to_string()),
+        escape_html(&issue.expected_counterpart.display().to_string())
+    );
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "<style>
+body { font-family: -apple-system, 'Segoe UI', sans-serif; background: #1e1e1e; color: #ddd; margin: 2em; }
+h1, h2, h3 { color: #fff; }
+table { border-collapse: collapse; margin-bottom: 1.5em; min-width: 400px; }
+th, td { border: 1px solid #444; padding: 4px 10px; text-align: left; }
+th { background: #2d2d30; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+.meta { color: #999; font-weight: normal; }
+.ok { color: #64c864; }
+</style>
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::analysis::{BalanceStats, IntegrityIssueType};
+    use std::path::PathBuf;
+
+    fn sample_stats() -> GlobalBalanceStats {
+        let mut train = BalanceStats::new();
+        train.total_images = 10;
+        train.ct_only = 4;
+        train.background = 6;
+        GlobalBalanceStats {
+            train,
+            val: BalanceStats::new(),
+            test: BalanceStats::new(),
+            missing: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_report_contains_splits_and_operations() {
+        let log = vec![
+            OperationEntry::new(OP_DELETE_IMAGE, 1),
+            OperationEntry::new(OP_INTEGRITY_FIX, 3),
+        ];
+        let html = generate_html_report(&sample_stats(), &IntegrityStats::new(), &log);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        for split in ["train", "val", "test"] {
+            assert!(html.contains(&format!("<h3>{} ", split)));
+        }
+        assert!(html.contains("<td class=\"num\">40.0%</td>"));
+        assert!(html.contains(OP_DELETE_IMAGE));
+        assert!(html.contains("fixed this session: <b>3</b>"));
+        assert!(html.contains("No integrity issues found."));
+    }
+
+    #[test]
+    fn test_report_escapes_paths() {
+        let mut integrity = IntegrityStats::new();
+        integrity.images_without_labels.push(IntegrityIssue {
+            issue_type: IntegrityIssueType::ImageWithoutLabel,
+            split: DatasetSplit::Train,
+            path: PathBuf::from("train/images/<odd>&name.png"),
+            expected_counterpart: PathBuf::from("train/labels/<odd>&name.txt"),
+        });
+
+        let html = generate_html_report(&sample_stats(), &integrity, &[]);
+
+        assert!(html.contains("&lt;odd&gt;&amp;name.png"));
+        assert!(!html.contains("<odd>"));
+        assert!(html.contains("No operations recorded this session."));
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::report::{
+    OP_DELETE_IMAGE, OP_GLOBAL_REBALANCE, OP_REBALANCE, OP_REDO_DELETE, OP_REMOVE_BLACK_IMAGES,
+    OP_REMOVE_EXTREME_IMAGES, OP_REMOVE_WHITE_IMAGES, OP_UNDO_AUGMENT, OP_UNDO_DELETE, OP_UNDO_EDIT_LABEL, OP_UNDO_REBALANCE, OP_UNDO_STRIP_METADATA,
+};
+
+/// Review throughput counters for the current session (reset on app start)
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    /// When the session started
+    started_at: Instant,
+    /// Distinct images shown in the viewer
+    viewed: HashSet<PathBuf>,
+    /// Images deleted (single deletes, redone deletes and black/white-image removal)
+    pub deletes: usize,
+    /// Undo actions performed
+    pub undos: usize,
+    /// Images moved between splits by rebalancing
+    pub moves: usize,
+}
+
+impl SessionStats {
+    /// Start a new session now
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            viewed: HashSet::new(),
+            deletes: 0,
+            undos: 0,
+            moves: 0,
+        }
+    }
+
+    /// Count an image as viewed (each image counts once per session)
+    pub fn record_view(&mut self, image_path: &Path) {
+        if !self.viewed.contains(image_path) {
+            self.viewed.insert(image_path.to_path_buf());
+        }
+    }
+
+    /// Update the counters for an operation from the operations log
+    pub fn record_operation(&mut self, kind: &str, affected_count: usize) {
+        match kind {
+            OP_DELETE_IMAGE
+            | OP_REDO_DELETE
+            | OP_REMOVE_BLACK_IMAGES
+            | OP_REMOVE_WHITE_IMAGES
+            | OP_REMOVE_EXTREME_IMAGES => self.deletes += affected_count,
+            OP_UNDO_DELETE
+            | OP_UNDO_EDIT_LABEL
+            | OP_UNDO_AUGMENT
+            | OP_UNDO_REBALANCE
+            | OP_UNDO_STRIP_METADATA => self.undos += 1,
+            OP_REBALANCE | OP_GLOBAL_REBALANCE => self.moves += affected_count,
+            _ => {}
+        }
+    }
+
+    /// Number of distinct images viewed
+    pub fn images_viewed(&self) -> usize {
+        self.viewed.len()
+    }
+
+    /// Time since the session started
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Average review time per viewed image, in seconds
+    pub fn average_seconds_per_image(&self) -> Option<f64> {
+        average_seconds(self.elapsed(), self.images_viewed())
+    }
+
+    /// Plain-text summary for pasting into a work log
+    pub fn to_text(&self) -> String {
+        format_summary(self, self.elapsed())
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Format a duration as `H:MM:SS`
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds / 60) % 60,
+        seconds % 60
+    )
+}
+
+fn average_seconds(elapsed: Duration, images: usize) -> Option<f64> {
+    (images > 0).then(|| elapsed.as_secs_f64() / images as f64)
+}
+
+fn format_summary(stats: &SessionStats, elapsed: Duration) -> String {
+    let average = match average_seconds(elapsed, stats.images_viewed()) {
+        Some(seconds) => format!("{:.1} s", seconds),
+        None => "-".to_string(),
+    };
+    format!(
+        "Session: {}\nImages viewed: {}\nDeleted: {}\nUndos: {}\nMoved: {}\nAverage per image: {}",
+        format_duration(elapsed),
+        stats.images_viewed(),
+        stats.deletes,
+        stats.undos,
+        stats.moves,
+        average
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::statistics::OP_BATCH_RENAME;
+
+    #[test]
+    fn test_session_stats_counts_and_summary() {
+        let mut stats = SessionStats::new();
+        stats.record_view(Path::new("train/images/a.png"));
+        stats.record_view(Path::new("train/images/b.png"));
+        stats.record_view(Path::new("train/images/a.png"));
+        stats.record_operation(OP_DELETE_IMAGE, 1);
+        stats.record_operation(OP_REMOVE_BLACK_IMAGES, 4);
+        stats.record_operation(OP_UNDO_DELETE, 1);
+        stats.record_operation(OP_GLOBAL_REBALANCE, 12);
+        stats.record_operation(OP_BATCH_RENAME, 50);
+
+        assert_eq!(stats.images_viewed(), 2);
+        assert_eq!((stats.deletes, stats.undos, stats.moves), (5, 1, 12));
+
+        let text = format_summary(&stats, Duration::from_secs(3725));
+        assert!(text.starts_with("Session: 1:02:05\n"));
+        assert!(text.contains("Deleted: 5\n"));
+        assert!(text.ends_with("Average per image: 1862.5 s"));
+        assert_eq!(average_seconds(Duration::from_secs(10), 0), None);
+    }
+}
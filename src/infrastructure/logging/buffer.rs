@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Local};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Most recent log events kept for the log panel
+pub const LOG_BUFFER_CAPACITY: usize = 200;
+
+/// One log event as shown in the log panel
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub level: Level,
+    /// Message followed by any other fields as `key=value`
+    pub message: String,
+    pub timestamp: DateTime<Local>,
+}
+
+/// The last `LOG_BUFFER_CAPACITY` debug-or-higher events, oldest first
+#[derive(Debug)]
+pub struct LogBuffer {
+    entries: Mutex<VecDeque<LogEntry>>,
+}
+
+static LOG_BUFFER: LogBuffer = LogBuffer::new();
+
+/// The buffer filled by the `LogBufferLayer` installed in GUI mode
+pub fn log_buffer() -> &'static LogBuffer {
+    &LOG_BUFFER
+}
+
+impl LogBuffer {
+    pub const fn new() -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Append an entry, dropping the oldest once the buffer is full
+    pub fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() == LOG_BUFFER_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Entries at `max_level` or more severe (WARN shows WARN and ERROR), oldest first
+    pub fn entries(&self, max_level: Level) -> Vec<LogEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .iter()
+            .filter(|entry| entry.level <= max_level)
+            .cloned()
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+/// Tracing layer that copies DEBUG and more severe events into `log_buffer()`
+pub struct LogBufferLayer;
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let level = *event.metadata().level();
+        if level == Level::TRACE {
+            return;
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        log_buffer().push(LogEntry {
+            level,
+            message: visitor.message,
+            timestamp: Local::now(),
+        });
+    }
+}
+
+/// Joins the `message` field and any other fields into one line
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.message.is_empty() {
+            self.message.push(' ');
+        }
+        if field.name() == "message" {
+            self.message.push_str(&format!("{:?}", value));
+        } else {
+            self.message.push_str(&format!("{}={:?}", field.name(), value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_log_buffer_keeps_recent_entries_and_filters_by_level() {
+        let buffer = LogBuffer::new();
+        for n in 0..LOG_BUFFER_CAPACITY + 5 {
+            let level = if n % 2 == 0 { Level::INFO } else { Level::WARN };
+            buffer.push(LogEntry {
+                level,
+                message: n.to_string(),
+                timestamp: Local::now(),
+            });
+        }
+
+        let all = buffer.entries(Level::DEBUG);
+        assert_eq!(all.len(), LOG_BUFFER_CAPACITY);
+        assert_eq!(all[0].message, "5");
+        assert_eq!(all.last().unwrap().message, (LOG_BUFFER_CAPACITY + 4).to_string());
+        assert!(buffer.entries(Level::WARN).iter().all(|entry| entry.level == Level::WARN));
+        assert!(buffer.entries(Level::ERROR).is_empty());
+
+        buffer.clear();
+        assert!(buffer.entries(Level::TRACE).is_empty());
+    }
+
+    #[test]
+    fn test_layer_records_message_and_fields() {
+        let subscriber = tracing_subscriber::registry().with(LogBufferLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(files = 3, "log buffer layer test");
+            tracing::trace!("log buffer layer trace");
+        });
+
+        let entries = log_buffer().entries(Level::TRACE);
+        let entry = entries
+            .iter()
+            .find(|entry| entry.message.starts_with("log buffer layer"))
+            .unwrap();
+        assert_eq!(entry.level, Level::WARN);
+        assert_eq!(entry.message, "log buffer layer test files=3");
+        assert!(!entries.iter().any(|entry| entry.message == "log buffer layer trace"));
+    }
+}
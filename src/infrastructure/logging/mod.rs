@@ -2,11 +2,17 @@
 //!
 //! This module provides:
 //! - Custom log formatting with bracketed output
-//! - Dual logging (file + stdout)
+//! - Dual logging (file + stdout) for the GUI, stderr-only for headless runs
 //! - Log file management with timestamps
+//! - An in-memory buffer of recent events for the GUI log panel
+//! - Timing wrappers for long-running operations
 
+mod buffer;
 mod formatter;
 mod setup;
+mod timing;
 
 // Re-export the public API
-pub use setup::setup_logging;
+pub use buffer::{log_buffer, LOG_BUFFER_CAPACITY};
+pub use setup::{setup_logging, LogMode};
+pub use timing::{log_timed_op, log_timed_op_with_count};
@@ -3,9 +3,33 @@ use std::path::PathBuf;
 use tracing::info;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
+use super::buffer::LogBufferLayer;
 use super::formatter::BracketedFormatter;
 
-pub fn setup_logging() -> PathBuf {
+/// How the process is running, which decides where log output goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogMode {
+    /// Interactive window: log to a timestamped file and stdout
+    Gui,
+    /// Headless CLI run: stdout is reserved for command output, so logs go
+    /// to stderr only and no log file is created
+    Headless,
+}
+
+/// Initialise the global tracing subscriber for the given mode.
+///
+/// Returns the path of the log file when one was created.
+pub fn setup_logging(mode: LogMode) -> Option<PathBuf> {
+    match mode {
+        LogMode::Gui => Some(setup_gui_logging()),
+        LogMode::Headless => {
+            setup_headless_logging();
+            None
+        }
+    }
+}
+
+fn setup_gui_logging() -> PathBuf {
     // Create logs directory
     let log_dir = std::env::current_dir().unwrap().join("logs");
     fs::create_dir_all(&log_dir).expect("Failed to create logs directory");
@@ -43,6 +67,7 @@ pub fn setup_logging() -> PathBuf {
         }))
         .with(file_layer)
         .with(stdout_layer)
+        .with(LogBufferLayer)
         .init();
 
     info!("Starting YOLO Dataset Cleaner application");
@@ -50,3 +75,17 @@ pub fn setup_logging() -> PathBuf {
 
     log_path
 }
+
+fn setup_headless_logging() {
+    let stderr_layer = fmt::layer()
+        .event_format(BracketedFormatter)
+        .with_writer(std::io::stderr);
+
+    tracing_subscriber::registry()
+        .with(
+            // Only warnings by default so CI logs stay readable; RUST_LOG overrides
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        )
+        .with(stderr_layer)
+        .init();
+}
@@ -0,0 +1,36 @@
+use std::time::Instant;
+use tracing::info;
+
+/// Run `f` and log how long it took
+pub fn log_timed_op<F: FnOnce() -> T, T>(name: &str, f: F) -> T {
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed().as_secs_f64();
+    info!("{} completed in {:.2}s", name, elapsed);
+    result
+}
+
+/// Run `f` and log how long it took, with the number of files it processed
+///
+/// `count` extracts the file count from the result so the log line also
+/// carries a throughput estimate.
+pub fn log_timed_op_with_count<F, T, C>(name: &str, f: F, count: C) -> T
+where
+    F: FnOnce() -> T,
+    C: FnOnce(&T) -> usize,
+{
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed().as_secs_f64();
+    let files = count(&result);
+    let throughput = if elapsed > 0.0 {
+        files as f64 / elapsed
+    } else {
+        0.0
+    };
+    info!(
+        "{} completed in {:.2}s ({} files, {:.1} files/s)",
+        name, elapsed, files, throughput
+    );
+    result
+}
@@ -1,22 +1,56 @@
 #![windows_subsystem = "windows"]
 
+use std::process::ExitCode;
+
 use eframe::egui;
-use tracing::info;
+use tracing::{error, info};
 
 mod app;
+mod cli;
 mod config;
 mod core;
 mod infrastructure;
 mod navigation;
 mod state;
+#[cfg(test)]
+mod test_util;
 mod ui;
 
 use app::DatasetCleanerApp;
+use infrastructure::logging::LogMode;
 use state::Settings;
 
-fn main() -> Result<(), eframe::Error> {
+fn main() -> ExitCode {
+    // Headless subcommands skip window creation entirely;
+    // YOLO_CLEANER_CLI=1 is the same as passing --cli (subcommands still win).
+    // Any other first argument starts the GUI.
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let read_only = cli::take_read_only_flag(&mut args);
+    if std::env::var(cli::CLI_ENV_VAR).is_ok_and(|value| value == "1")
+        && args
+            .first()
+            .is_none_or(|arg| arg.starts_with("--") && arg != "--cli" && arg != "--help")
+    {
+        args.insert(0, "--cli".to_string());
+    }
+    match cli::parse_args(&args) {
+        Ok(Some(command)) => {
+            attach_parent_console();
+            infrastructure::logging::setup_logging(LogMode::Headless);
+            return cli::run(command);
+        }
+        Ok(None) => {}
+        Err(message) => {
+            attach_parent_console();
+            return cli::usage_error(&message);
+        }
+    }
+
     // Setup logging
-    infrastructure::logging::setup_logging();
+    infrastructure::logging::setup_logging(LogMode::Gui);
+    if !args.is_empty() {
+        info!("Ignoring command-line arguments: {:?}", args);
+    }
 
     // Load settings to get window dimensions
     let settings = Settings::load();
@@ -29,7 +63,7 @@ fn main() -> Result<(), eframe::Error> {
     };
 
     info!("Launching application window");
-    eframe::run_native(
+    let result = eframe::run_native(
         "YOLO Dataset Cleaner",
         options,
         Box::new(|cc| {
@@ -37,7 +71,40 @@ fn main() -> Result<(), eframe::Error> {
             let mut fonts = egui::FontDefinitions::default();
             egui_phosphor::add_to_fonts(&mut fonts, egui_phosphor::Variant::Regular);
             cc.egui_ctx.set_fonts(fonts);
-            Ok(Box::new(DatasetCleanerApp::default()))
+            let mut app = DatasetCleanerApp::default();
+            if read_only {
+                info!("Started read-only");
+                app.launched_read_only = true;
+            }
+            Ok(Box::new(app))
         }),
-    )
+    );
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            error!("Application error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Release builds use the Windows GUI subsystem, which starts without a
+/// console. Attach to the parent's console so CLI output reaches the shell.
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // SAFETY: AttachConsole has no preconditions; failure (e.g. no parent
+    // console) is harmless and simply leaves output detached.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
 }
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
@@ -56,7 +56,7 @@ impl Navigator {
     pub fn first(&self, filter: &FilterState) -> Option<usize> {
         if filter.is_active() {
             // Jump to first filtered image
-            if !filter.filtered_indices.is_empty() {
+            if !filter.filtered_indices().is_empty() {
                 filter.get_actual_index(0)
             } else {
                 None
@@ -128,6 +128,57 @@ impl Navigator {
             Some(new_index)
         }
     }
+
+    /// Next image after `current_index` for which `matches` is true, wrapping past the end
+    ///
+    /// Only images in the filtered view are considered. Returns the index and
+    /// whether the search wrapped around, or None when no other image matches.
+    pub fn next_matching(
+        &self,
+        current_index: usize,
+        filter: &FilterState,
+        matches: impl Fn(usize) -> bool,
+    ) -> Option<(usize, bool)> {
+        let order = self.view_indices(filter);
+        let start = order
+            .iter()
+            .position(|&index| index > current_index)
+            .unwrap_or(order.len());
+        order[start..]
+            .iter()
+            .map(|&index| (index, false))
+            .chain(order[..start].iter().map(|&index| (index, true)))
+            .find(|&(index, _)| index != current_index && matches(index))
+    }
+
+    /// Previous image before `current_index` for which `matches` is true, wrapping past the start
+    pub fn prev_matching(
+        &self,
+        current_index: usize,
+        filter: &FilterState,
+        matches: impl Fn(usize) -> bool,
+    ) -> Option<(usize, bool)> {
+        let order = self.view_indices(filter);
+        let end = order
+            .iter()
+            .rposition(|&index| index < current_index)
+            .map_or(0, |position| position + 1);
+        order[..end]
+            .iter()
+            .rev()
+            .map(|&index| (index, false))
+            .chain(order[end..].iter().rev().map(|&index| (index, true)))
+            .find(|&(index, _)| index != current_index && matches(index))
+    }
+
+    /// Actual indices of the images in the (filtered) view, in dataset order
+    fn view_indices(&self, filter: &FilterState) -> Vec<usize> {
+        if filter.is_active() {
+            filter.filtered_indices().to_vec()
+        } else {
+            (0..self.total_images).collect()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -156,6 +207,26 @@ mod tests {
         assert_eq!(nav.jump_by_offset(2, -2, &filter), Some(0));
     }
 
+    #[test]
+    fn test_navigation_to_matching_image() {
+        let nav = Navigator::new(10);
+        let mut filter = FilterState::new();
+        let even = |index: usize| index.is_multiple_of(2);
+
+        assert_eq!(nav.next_matching(3, &filter, even), Some((4, false)));
+        assert_eq!(nav.prev_matching(3, &filter, even), Some((2, false)));
+        assert_eq!(nav.next_matching(8, &filter, even), Some((0, true)));
+        assert_eq!(nav.prev_matching(0, &filter, even), Some((8, true)));
+        assert_eq!(nav.next_matching(4, &filter, |index| index == 4), None);
+
+        // Only the filtered view is searched, even when the current image is outside it
+        filter.criteria.bookmarked_only = true;
+        filter.set_filtered_indices(vec![1, 2, 5, 6]);
+        assert_eq!(nav.next_matching(3, &filter, even), Some((6, false)));
+        assert_eq!(nav.next_matching(6, &filter, even), Some((2, true)));
+        assert_eq!(nav.prev_matching(2, &filter, even), Some((6, true)));
+    }
+
     #[test]
     fn test_navigation_empty() {
         let nav = Navigator::new(0);
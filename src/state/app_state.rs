@@ -1,10 +1,14 @@
 use egui::TextureHandle;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
 use std::sync::mpsc::Receiver;
 use std::sync::{atomic::AtomicBool, Arc};
 
-use crate::app::BatchStats;
+use crate::app::{BatchStats, ExportStats};
 use crate::core::dataset::LabelInfo;
 
+use super::ProgressRate;
+
 /// Batch progress message types for communication between threads
 pub enum BatchProgressMessage {
     Progress(BatchStats),
@@ -12,6 +16,28 @@ pub enum BatchProgressMessage {
     Cancelled(BatchStats),
 }
 
+/// Subset export progress message types
+pub enum ExportProgressMessage {
+    Progress(ExportStats),
+    Complete(ExportStats),
+    Cancelled(ExportStats),
+}
+
+/// Session report export progress message types
+pub enum ReportProgressMessage {
+    /// Progress (current, total) of the running step
+    Progress {
+        step: &'static str,
+        current: usize,
+        total: usize,
+    },
+    /// The report was written to this path
+    Complete(PathBuf),
+    /// Writing the report failed
+    Failed(String),
+    Cancelled,
+}
+
 /// Image-related state including texture, label, analysis, and display settings
 #[derive(Default)]
 pub struct ImageState {
@@ -25,6 +51,27 @@ pub struct ImageState {
     pub load_error: Option<String>,
     /// Current zoom level for image display
     pub zoom_level: f32,
+    /// Scroll offset to apply to the image view on the next frame (zoom-to-cursor, panning)
+    pub pending_scroll_offset: Option<egui::Vec2>,
+    /// Scroll offset of the image view as of the last frame
+    pub scroll_offset: egui::Vec2,
+    /// Zoom and scroll each image was last viewed with this session
+    pub view_cache: HashMap<PathBuf, ViewState>,
+    /// Detection highlighted in both the image view and the label panel
+    pub selected_detection: Option<usize>,
+    /// Whether the label panel should scroll the selected detection into view
+    pub scroll_to_selected: bool,
+    /// Reference image shown next to the current one for comparison (kept across navigation)
+    pub pinned: Option<PinnedImage>,
+    /// Boxes drawn in annotation mode at the end of `label.detections`, not yet saved
+    pub pending_annotations: usize,
+    /// File metadata of the last image shown in the label panel, read on demand
+    pub file_metadata: Option<(PathBuf, HashMap<String, String>)>,
+    /// Receives the index of the next unreadable image (None if there is none)
+    pub(crate) error_scan_receiver: Option<Receiver<Option<usize>>>,
+    pub(crate) error_scan_cancel_flag: Option<Arc<AtomicBool>>,
+    /// Dataset generation the running error scan walks
+    pub(crate) error_scan_generation: u64,
 }
 
 impl ImageState {
@@ -36,23 +83,90 @@ impl ImageState {
             dominant_color: None,
             load_error: None,
             zoom_level: 1.0,
+            pending_scroll_offset: None,
+            scroll_offset: egui::Vec2::ZERO,
+            view_cache: HashMap::new(),
+            selected_detection: None,
+            scroll_to_selected: false,
+            pinned: None,
+            pending_annotations: 0,
+            file_metadata: None,
+            error_scan_receiver: None,
+            error_scan_cancel_flag: None,
+            error_scan_generation: 0,
         }
     }
 
+    /// Whether a background search for the next unreadable image is running
+    pub fn is_scanning_for_errors(&self) -> bool {
+        self.error_scan_receiver.is_some()
+    }
+
     /// Reset all image state (optionally preserving zoom level)
     pub fn reset(&mut self, reset_zoom: bool) {
         self.texture = None;
         self.label = None;
         self.dominant_color = None;
         self.load_error = None;
+        self.selected_detection = None;
+        self.scroll_to_selected = false;
+        self.pending_annotations = 0;
         if reset_zoom {
             self.zoom_level = 1.0;
+            self.pending_scroll_offset = None;
+        }
+    }
+
+    /// Zoom and scroll currently shown
+    pub fn view_state(&self) -> ViewState {
+        ViewState {
+            zoom: self.zoom_level,
+            scroll_offset: self.scroll_offset,
+        }
+    }
+
+    /// Show the next image with the given zoom and scroll
+    pub fn apply_view_state(&mut self, view: ViewState) {
+        self.zoom_level = view.zoom;
+        self.scroll_offset = view.scroll_offset;
+        self.pending_scroll_offset = Some(view.scroll_offset);
+    }
+}
+
+/// An auto-advance scheduled after an action on the current image
+pub struct PendingAdvance {
+    /// When to move on
+    pub due: std::time::Instant,
+    /// The image the action was applied to (the advance is dropped once the user leaves it)
+    pub from: PathBuf,
+    /// Images to step over, such as a just-written augmented copy
+    pub skip: Vec<PathBuf>,
+}
+
+/// An image pinned as the comparison reference
+pub struct PinnedImage {
+    pub path: PathBuf,
+    pub texture: TextureHandle,
+    pub label: Option<LabelInfo>,
+}
+
+/// Zoom and scroll position of the image view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewState {
+    pub zoom: f32,
+    pub scroll_offset: egui::Vec2,
+}
+
+impl Default for ViewState {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            scroll_offset: egui::Vec2::ZERO,
         }
     }
 }
 
 /// UI-related state for dialogs, modes, and user input
-#[derive(Default)]
 pub struct UIState {
     /// Whether fullscreen mode is active
     pub fullscreen_mode: bool,
@@ -60,8 +174,44 @@ pub struct UIState {
     pub show_filter_dialog: bool,
     /// Whether the batch delete confirmation dialog is shown
     pub show_batch_delete_confirm: bool,
+    /// Whether the settings dialog is shown
+    pub show_settings_dialog: bool,
+    /// Whether the keyboard shortcuts dialog is shown
+    pub show_shortcuts_dialog: bool,
+    /// Whether the session statistics dialog is shown
+    pub show_session_stats: bool,
+    /// Whether the undo history dialog is shown
+    pub show_history_dialog: bool,
+    /// Whether the log panel is shown
+    pub show_log_panel: bool,
+    /// Least severe level listed in the log panel
+    pub log_level: tracing::Level,
+    /// dataset.yaml export waiting for confirmation to replace an existing file
+    pub pending_yaml_overwrite: Option<PathBuf>,
+    /// Category the bottom panel's previous/next category buttons jump to
+    pub jump_category: crate::core::analysis::ImageCategory,
     /// Manual index input field content
     pub manual_index_input: String,
+    /// Result of the last augmentation (shown in the label panel)
+    pub augment_message: Option<String>,
+    /// Recycle bin and trash notices (shown in the bottom panel)
+    pub delete_message: Option<String>,
+    /// Files and bytes in the cleaner trash while the Empty Cleaner Trash dialog is open
+    pub empty_trash_preview: Option<(usize, u64)>,
+    /// Why the last folder dropped onto the window was rejected
+    pub drop_message: Option<String>,
+    /// Transient notice shown as a toast, with the time it was raised
+    pub toast_message: Option<(String, std::time::Instant)>,
+    /// Draw new boxes on the image with left-click-drag
+    pub annotation_mode: bool,
+    /// Class ID given to boxes drawn in annotation mode
+    pub annotation_class: u32,
+    /// Screen position where the box being drawn started
+    pub annotation_drag_start: Option<egui::Pos2>,
+    /// Draw the boxes into the image copy made by "Export pair to folder"
+    pub burn_boxes_on_export: bool,
+    /// System clipboard, opened on first copy and kept so the copied image stays available
+    pub clipboard: Option<arboard::Clipboard>,
 }
 
 impl UIState {
@@ -71,7 +221,79 @@ impl UIState {
             fullscreen_mode: false,
             show_filter_dialog: false,
             show_batch_delete_confirm: false,
+            show_settings_dialog: false,
+            show_shortcuts_dialog: false,
+            show_session_stats: false,
+            show_history_dialog: false,
+            show_log_panel: false,
+            log_level: tracing::Level::DEBUG,
+            jump_category: crate::core::analysis::ImageCategory::Background,
+            pending_yaml_overwrite: None,
             manual_index_input: String::from("1"),
+            augment_message: None,
+            delete_message: None,
+            empty_trash_preview: None,
+            drop_message: None,
+            toast_message: None,
+            annotation_mode: false,
+            annotation_class: 0,
+            annotation_drag_start: None,
+            burn_boxes_on_export: false,
+            clipboard: None,
+        }
+    }
+}
+
+impl Default for UIState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State for the bookmarks dialog
+#[derive(Default)]
+pub struct BookmarkDialogState {
+    /// Whether the bookmarks dialog is shown
+    pub show_dialog: bool,
+    /// Thumbnails of bookmarked images (None when the image could not be loaded)
+    pub thumbnails: HashMap<PathBuf, Option<TextureHandle>>,
+}
+
+impl BookmarkDialogState {
+    /// Create a new BookmarkDialogState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Which brightness extreme a batch removal deletes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchRemovalMode {
+    /// Near-black dominant color (loading screens, black frames)
+    #[default]
+    Black,
+    /// Near-white dominant color (overexposed frames)
+    White,
+    /// Either extreme, checked in one pass
+    Extreme,
+}
+
+impl BatchRemovalMode {
+    pub fn title(&self) -> &'static str {
+        match self {
+            BatchRemovalMode::Black => "Remove Black Images",
+            BatchRemovalMode::White => "Remove White Images",
+            BatchRemovalMode::Extreme => "Remove Extreme Images",
+        }
+    }
+
+    /// Whether an image with this dominant color should be removed
+    pub fn matches(&self, color: (u8, u8, u8)) -> bool {
+        use crate::core::image::{is_near_black, is_near_white};
+        match self {
+            BatchRemovalMode::Black => is_near_black(color),
+            BatchRemovalMode::White => is_near_white(color),
+            BatchRemovalMode::Extreme => is_near_black(color) || is_near_white(color),
         }
     }
 }
@@ -81,8 +303,12 @@ impl UIState {
 pub struct BatchState {
     /// Whether batch processing is currently active
     pub processing: bool,
+    /// What the current/last batch removal looks for
+    pub mode: BatchRemovalMode,
     /// Statistics about the current/last batch operation
     pub stats: Option<BatchStats>,
+    /// Scan throughput of the running batch
+    pub rate: ProgressRate,
     /// Channel receiver for progress updates from background thread
     pub(crate) progress_receiver: Option<Receiver<BatchProgressMessage>>,
     /// Flag to signal cancellation to background thread
@@ -94,13 +320,48 @@ impl BatchState {
     pub fn new() -> Self {
         Self {
             processing: false,
+            mode: BatchRemovalMode::Black,
             stats: None,
+            rate: ProgressRate::new(),
             progress_receiver: None,
             cancel_flag: None,
         }
     }
 }
 
+/// State for exporting a copy of the filtered images
+#[derive(Default)]
+pub struct ExportSubsetState {
+    /// Destination folder awaiting confirmation
+    pub pending_dest: Option<std::path::PathBuf>,
+    /// Whether an export is currently running
+    pub processing: bool,
+    /// Statistics about the current/last export
+    pub stats: Option<ExportStats>,
+    /// Channel receiver for progress updates from background thread
+    pub(crate) progress_receiver: Option<Receiver<ExportProgressMessage>>,
+    /// Flag to signal cancellation to background thread
+    pub(crate) cancel_flag: Option<Arc<AtomicBool>>,
+}
+
+impl ExportSubsetState {
+    /// Create a new ExportSubsetState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Progress of an analysis started automatically after opening a dataset or switching splits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AutoAnalysisStatus {
+    /// No automatic analysis (or a manual one took over)
+    #[default]
+    Idle,
+    Running,
+    /// Finished; the results cover the current split
+    Done,
+}
+
 /// Balance analysis state for dataset balance statistics
 #[derive(Default)]
 pub struct BalanceAnalysisState {
@@ -110,25 +371,55 @@ pub struct BalanceAnalysisState {
     pub results: Option<crate::core::analysis::BalanceStats>,
     /// Whether to show the balance dialog
     pub show_dialog: bool,
-    /// Current tab in the dialog (0 = Balance, 1 = Integrity)
+    /// Current tab in the dialog (0 = Balance, 1 = Integrity, 2 = All Splits)
     pub current_tab: usize,
     /// Current progress (images analyzed so far)
     pub current_progress: usize,
     /// Total images to analyze
     pub total_images: usize,
+    /// Throughput of the running analysis
+    pub rate: ProgressRate,
     /// Tracked minimum height for popup (grows but never shrinks)
     pub tracked_min_height: f32,
     /// Cached best destination for background images (split, needed count)
     pub cached_best_bg_dest: Option<(crate::core::dataset::DatasetSplit, i32)>,
     /// Cached best destination for player images (split, needed count)
     pub cached_best_player_dest: Option<(crate::core::dataset::DatasetSplit, i32)>,
+    /// Cached best destination for hard-case images (split, needed count)
+    pub cached_best_hard_case_dest: Option<(crate::core::dataset::DatasetSplit, i32)>,
     /// Selected split to analyze (0=Train, 1=Val, 2=Test, 3=All)
     pub selected_split_index: usize,
+    /// Stats of each present split, from the scan that follows every analysis
+    pub split_results: HashMap<crate::core::dataset::DatasetSplit, crate::core::analysis::BalanceStats>,
     /// Channel receiver for progress updates from background thread
     pub(crate) progress_receiver:
         Option<std::sync::mpsc::Receiver<crate::core::analysis::BalanceProgressMessage>>,
     /// Flag to signal cancellation to background thread
     pub(crate) cancel_flag: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    /// Stats of every split, computed once after the analysis to pick rebalance destinations
+    pub(crate) global_stats_receiver:
+        Option<std::sync::mpsc::Receiver<crate::core::analysis::GlobalBalanceStats>>,
+    /// When `results` (or `all_splits_results`) last finished computing
+    pub analyzed_at: Option<chrono::DateTime<chrono::Local>>,
+    /// Whether the side-by-side analysis of all splits is running
+    pub all_splits_analyzing: bool,
+    /// Per-split results of the last side-by-side analysis
+    pub all_splits_results: Option<crate::core::analysis::GlobalBalanceStats>,
+    /// Per-split progress (images analyzed, total) of the running side-by-side analysis
+    pub all_splits_progress: [(usize, usize); 3],
+    /// Which splits (train, val, test) the running analysis has finished
+    pub all_splits_done: [bool; 3],
+    /// Channel receiver for the sequential all-splits analysis
+    pub(crate) all_splits_receiver:
+        Option<std::sync::mpsc::Receiver<crate::core::analysis::AllSplitsProgressMessage>>,
+    /// Flag to signal cancellation to the all-splits analysis thread
+    pub(crate) all_splits_cancel_flag: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    /// Targets being edited in the analysis dialog (None while not editing)
+    pub targets_edit: Option<crate::core::dataset::DatasetTargets>,
+    /// Why the last attempt to save the edited targets failed
+    pub targets_error: Option<String>,
+    /// Whether the current analysis was started automatically, without the dialog
+    pub auto_status: AutoAnalysisStatus,
 }
 
 impl BalanceAnalysisState {
@@ -141,25 +432,80 @@ impl BalanceAnalysisState {
             current_tab: 0,
             current_progress: 0,
             total_images: 0,
+            rate: ProgressRate::new(),
             tracked_min_height: 400.0,
             cached_best_bg_dest: None,
             cached_best_player_dest: None,
+            cached_best_hard_case_dest: None,
             selected_split_index: 0, // Default to Train
+            split_results: HashMap::new(),
             progress_receiver: None,
             cancel_flag: None,
+            global_stats_receiver: None,
+            analyzed_at: None,
+            all_splits_analyzing: false,
+            all_splits_results: None,
+            all_splits_progress: [(0, 0); 3],
+            all_splits_done: [false; 3],
+            all_splits_receiver: None,
+            all_splits_cancel_flag: None,
+            targets_edit: None,
+            targets_error: None,
+            auto_status: AutoAnalysisStatus::Idle,
         }
     }
 }
 
+/// A manifest file loaded as the filter ("Load manifest as filter")
+#[derive(Debug, Clone)]
+pub struct ManifestFilter {
+    /// File name shown in place of the criteria
+    pub name: String,
+    /// Entries read from the file, matched again whenever the file list changes
+    pub entries: Vec<String>,
+    /// Entries that matched no image in the current split
+    pub missing: Vec<String>,
+}
+
 /// Filter state for image filtering by team, player count, etc.
 #[derive(Default)]
 pub struct FilterState {
     /// Current filter criteria
     pub criteria: crate::core::filter::FilterCriteria,
     /// Cached list of filtered indices (indices into the original image list)
-    pub filtered_indices: Vec<usize>,
+    ///
+    /// Set through `set_filtered_indices` so `filtered_positions` stays in sync.
+    filtered_indices: Vec<usize>,
+    /// Reverse map of `filtered_indices`: actual index -> filtered index
+    filtered_positions: HashMap<usize, usize>,
     /// Total number of images before filtering
     pub total_count: usize,
+    /// Map names found in the current split (scanned when the filter dialog opens)
+    pub available_maps: Vec<String>,
+    /// Name of the preset the criteria were last loaded from or saved to
+    pub active_preset: Option<String>,
+    /// Name typed into the filter dialog's preset field
+    pub preset_name_input: String,
+    /// Text of the filter dialog's "Captured after" field (`YYYY-MM-DD`)
+    pub captured_after_input: String,
+    /// Text of the filter dialog's "Captured before" field (`YYYY-MM-DD`)
+    pub captured_before_input: String,
+    /// Dataset generation `filtered_indices` was computed for
+    pub(crate) computed_for: Option<u64>,
+    /// Progress of the background filter job (current, total)
+    pub progress: Option<(usize, usize)>,
+    /// Channel receiver for the background filter job
+    pub(crate) progress_receiver: Option<Receiver<crate::core::filter::FilterProgressMessage>>,
+    /// Flag to cancel the background filter job
+    pub(crate) cancel_flag: Option<Arc<AtomicBool>>,
+    /// Dataset generation the running job evaluates
+    pub(crate) job: Option<u64>,
+    /// Manifest installed as the filter; replaces the criteria until cleared
+    pub manifest: Option<ManifestFilter>,
+    /// Columns written next to each path by "Export list"
+    pub export_columns: crate::core::operations::ManifestColumns,
+    /// Whether "Export list" writes paths relative to the dataset root
+    pub export_relative: bool,
 }
 
 impl FilterState {
@@ -168,19 +514,66 @@ impl FilterState {
         Self {
             criteria: Default::default(),
             filtered_indices: Vec::new(),
+            filtered_positions: HashMap::new(),
             total_count: 0,
+            available_maps: Vec::new(),
+            active_preset: None,
+            preset_name_input: String::new(),
+            captured_after_input: String::new(),
+            captured_before_input: String::new(),
+            computed_for: None,
+            progress: None,
+            progress_receiver: None,
+            cancel_flag: None,
+            job: None,
+            manifest: None,
+            export_columns: Default::default(),
+            export_relative: true,
         }
     }
 
-    /// Check if any filters are currently active
+    /// Whether a background filter job is running
+    pub fn is_filtering(&self) -> bool {
+        self.job.is_some()
+    }
+
+    /// Indices into the original image list that pass the filter, in navigation order
+    pub fn filtered_indices(&self) -> &[usize] {
+        &self.filtered_indices
+    }
+
+    /// Replace the filtered indices and rebuild the reverse map
+    pub fn set_filtered_indices(&mut self, indices: Vec<usize>) {
+        self.filtered_positions = indices
+            .iter()
+            .enumerate()
+            .map(|(filtered, &actual)| (actual, filtered))
+            .collect();
+        self.filtered_indices = indices;
+        self.debug_check_positions();
+    }
+
+    /// Debug builds verify that the reverse map matches `filtered_indices`
+    fn debug_check_positions(&self) {
+        debug_assert_eq!(self.filtered_positions.len(), self.filtered_indices.len());
+        debug_assert!(self
+            .filtered_indices
+            .iter()
+            .enumerate()
+            .all(|(filtered, actual)| self.filtered_positions.get(actual) == Some(&filtered)));
+    }
+
+    /// Check if any filters (criteria or a manifest) are currently active
     pub fn is_active(&self) -> bool {
-        self.criteria.is_active()
+        self.criteria.is_active() || self.manifest.is_some()
     }
 
     /// Clear all filters and reset to unfiltered state
     pub fn clear(&mut self) {
         self.criteria.clear();
+        self.manifest = None;
         self.filtered_indices.clear();
+        self.filtered_positions.clear();
         self.total_count = 0;
     }
 
@@ -198,9 +591,12 @@ impl FilterState {
     /// Returns None if the actual index is not in the filtered list
     pub fn get_filtered_index(&self, actual_index: usize) -> Option<usize> {
         if self.is_active() {
-            self.filtered_indices
-                .iter()
-                .position(|&idx| idx == actual_index)
+            debug_assert_eq!(
+                self.filtered_positions.len(),
+                self.filtered_indices.len(),
+                "filtered_indices was modified without set_filtered_indices"
+            );
+            self.filtered_positions.get(&actual_index).copied()
         } else {
             Some(actual_index)
         }
@@ -223,6 +619,20 @@ pub struct RebalanceState {
     pub is_active: bool,
     /// Whether this is a global (all splits) rebalance
     pub is_global: bool,
+    /// Whether the running operation is an undo of a previous rebalance
+    pub is_undo: bool,
+    /// Whether the running operation swaps the contents of two splits
+    pub is_swap: bool,
+    /// Let "Balance All Splits" move images into splits that have no folder yet
+    pub create_missing_splits: bool,
+    /// How rebalancing picks the images to move within a category
+    pub selection_strategy: crate::core::analysis::SelectionStrategy,
+    /// Split sizes set with the global rebalance sliders (None = the dataset's targets)
+    pub split_ratios: Option<crate::core::analysis::SplitRatios>,
+    /// Splits picked in the "Swap Splits" controls (train and val until changed)
+    pub swap_pair: Option<(crate::core::dataset::DatasetSplit, crate::core::dataset::DatasetSplit)>,
+    /// The swap was requested and is waiting for confirmation
+    pub confirm_swap: bool,
     /// Current rebalance plan (if calculated) - single split
     pub plan: Option<crate::core::analysis::RebalancePlan>,
     /// Current global rebalance plan (if calculated) - all splits
@@ -231,6 +641,8 @@ pub struct RebalanceState {
     pub config: Option<crate::core::analysis::RebalanceConfig>,
     /// Execution progress (current, total)
     pub progress: Option<(usize, usize)>,
+    /// Move throughput of the running execution
+    pub rate: ProgressRate,
     /// Last moved filename (for progress display)
     pub last_moved: Option<String>,
     /// Results from last execution (for undo)
@@ -240,8 +652,16 @@ pub struct RebalanceState {
         Option<std::sync::mpsc::Receiver<crate::core::analysis::RebalanceProgressMessage>>,
     /// Flag to signal cancellation
     pub(crate) cancel_flag: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    /// Images of the plan found missing when Execute was clicked, awaiting a choice
+    pub stale_actions: Option<usize>,
+    /// Stale images dropped from the plan that was executed
+    pub stale_dropped: usize,
     /// Show the rebalance preview dialog
     pub show_preview: bool,
+    /// Measured move throughput (MB/s) and estimated duration of the previewed plan
+    pub move_estimate: Option<(f64, std::time::Duration)>,
+    /// Move benchmark results per (source, destination) images folder, kept for the session
+    pub move_speeds: HashMap<(PathBuf, PathBuf), Option<f64>>,
     /// Show the execution result dialog
     pub show_result: bool,
     /// Error message if something went wrong
@@ -257,12 +677,19 @@ impl RebalanceState {
     /// Reset all state (called after closing dialogs)
     pub fn reset(&mut self) {
         self.is_active = false;
+        self.is_undo = false;
+        self.is_swap = false;
+        self.confirm_swap = false;
         self.plan = None;
         self.progress = None;
+        self.rate.clear();
         self.last_moved = None;
         self.progress_receiver = None;
         self.cancel_flag = None;
+        self.stale_actions = None;
+        self.stale_dropped = 0;
         self.show_preview = false;
+        self.move_estimate = None;
         self.show_result = false;
         self.error_message = None;
         // Note: keep last_results and config for undo capability
@@ -282,25 +709,60 @@ impl RebalanceState {
 pub struct IntegrityState {
     /// Whether integrity check is currently running
     pub analyzing: bool,
+    /// Scan train, val and test instead of only the current split
+    pub all_splits: bool,
+    /// Whether the current results (or running scan) cover all splits
+    pub results_all_splits: bool,
+    /// Compare file contents of cross-split duplicates (slower)
+    pub compare_duplicate_hashes: bool,
+    /// IoU above which two boxes in one label file are reported as duplicates
+    pub overlap_threshold: f32,
+    /// Check that every image can be read
+    pub check_images: bool,
+    /// Decode every pixel instead of reading only the header (finds truncated files)
+    pub full_decode: bool,
+    /// Dry-run result of removing the duplicate boxes, shown before applying
+    pub dedupe_preview: Option<crate::core::operations::DedupeSummary>,
+    /// Whether out-of-range coordinates are being clamped
+    pub fixing_coordinates: bool,
+    /// Progress (current, total) of the running coordinate fix
+    pub fix_coordinates_progress: Option<(usize, usize)>,
+    /// Result of the last coordinate fix
+    pub fix_coordinates_message: Option<String>,
+    /// Channel receiver for coordinate fix progress
+    pub(crate) fix_coordinates_receiver:
+        Option<std::sync::mpsc::Receiver<crate::core::operations::ClampProgressMessage>>,
+    /// Flag to cancel the coordinate fix
+    pub(crate) fix_coordinates_cancel_flag: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
     /// Results of the integrity check  
     pub results: Option<crate::core::analysis::IntegrityStats>,
     /// Selected issue indices (for images without labels tab)
     pub selected_images_without_labels: std::collections::HashSet<usize>,
     /// Selected issue indices (for labels without images tab)
     pub selected_labels_without_images: std::collections::HashSet<usize>,
-    /// Current tab (0 = images without labels, 1 = labels without images)
+    /// Selected issue indices (for empty labels tab)
+    pub selected_empty_labels: std::collections::HashSet<usize>,
+    /// Selected issue indices (for corrupt images tab)
+    pub selected_corrupt_images: std::collections::HashSet<usize>,
+    /// Current tab (0 = images without labels, 1 = labels without images,
+    /// 2 = unknown classes, 3 = duplicates, 4 = empty labels, 5 = overlapping boxes,
+    /// 6 = out-of-range coordinates, 7 = malformed labels, 8 = corrupt images)
     pub current_tab: usize,
+    /// List the parse error of each malformed label, not only the file name
+    pub show_parse_errors: bool,
     /// Current progress during analysis
     pub current_progress: usize,
     /// Total files to analyze
     pub total_files: usize,
+    /// Throughput of the running analysis
+    pub rate: ProgressRate,
     /// Channel receiver for progress updates
     pub(crate) progress_receiver:
         Option<std::sync::mpsc::Receiver<crate::core::analysis::IntegrityProgressMessage>>,
     /// Flag to signal cancellation
     pub(crate) cancel_flag: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
-    /// Whether deletion is in progress
-    pub deleting: bool,
+    /// Whether the current check was started automatically, without the dialog
+    pub auto_status: AutoAnalysisStatus,
     /// Error message if something went wrong
     pub error_message: Option<String>,
 }
@@ -308,30 +770,35 @@ pub struct IntegrityState {
 impl IntegrityState {
     /// Create a new IntegrityState with default values
     pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Reset the state
-    pub fn reset(&mut self) {
-        self.analyzing = false;
-        self.results = None;
-        self.selected_images_without_labels.clear();
-        self.selected_labels_without_images.clear();
-        self.current_tab = 0;
-        self.current_progress = 0;
-        self.total_files = 0;
-        self.progress_receiver = None;
-        self.cancel_flag = None;
-        self.deleting = false;
-        self.error_message = None;
+        Self {
+            overlap_threshold: crate::core::analysis::DEFAULT_OVERLAP_IOU_THRESHOLD,
+            show_parse_errors: true,
+            ..Self::default()
+        }
     }
 
-    /// Check if there are any selected items in the current tab
-    pub fn has_selection(&self) -> bool {
-        match self.current_tab {
-            0 => !self.selected_images_without_labels.is_empty(),
-            1 => !self.selected_labels_without_images.is_empty(),
-            _ => false,
+    /// Select every issue of one split in the current tab
+    pub fn select_split(&mut self, split: crate::core::dataset::DatasetSplit) {
+        let Some(stats) = &self.results else {
+            return;
+        };
+        let (issues, selected) = match self.current_tab {
+            0 => (
+                &stats.images_without_labels,
+                &mut self.selected_images_without_labels,
+            ),
+            1 => (
+                &stats.labels_without_images,
+                &mut self.selected_labels_without_images,
+            ),
+            4 => (&stats.empty_label_files, &mut self.selected_empty_labels),
+            8 => (&stats.corrupt_images, &mut self.selected_corrupt_images),
+            _ => return,
+        };
+        for (i, issue) in issues.iter().enumerate() {
+            if issue.split == split {
+                selected.insert(i);
+            }
         }
     }
 
@@ -340,7 +807,632 @@ impl IntegrityState {
         match self.current_tab {
             0 => self.selected_images_without_labels.len(),
             1 => self.selected_labels_without_images.len(),
+            4 => self.selected_empty_labels.len(),
+            8 => self.selected_corrupt_images.len(),
             _ => 0,
         }
     }
 }
+
+/// State for the batch rename tool
+pub struct RenameState {
+    /// Whether the batch rename dialog is shown
+    pub show_dialog: bool,
+    /// Use the timestamp scheme instead of sequential numbering
+    pub use_timestamp: bool,
+    /// Filename prefix (e.g. "img_")
+    pub prefix: String,
+    /// Zero-padded digit count for sequential numbering
+    pub digits: usize,
+    /// Result message from the last rename
+    pub result_message: Option<String>,
+    /// Whether the dialog is asking to confirm the rename
+    pub confirm: bool,
+    /// Whether a rename is running on the background thread
+    pub processing: bool,
+    /// Progress (renames done, total) of the running rename
+    pub progress: Option<(usize, usize)>,
+    /// Channel receiver for the running rename
+    pub(crate) progress_receiver: Option<Receiver<crate::core::operations::RenameProgressMessage>>,
+}
+
+impl RenameState {
+    /// Create a new RenameState with default values
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            use_timestamp: false,
+            prefix: String::from("img_"),
+            digits: 5,
+            result_message: None,
+            confirm: false,
+            processing: false,
+            progress: None,
+            progress_receiver: None,
+        }
+    }
+
+    /// Build the rename scheme from the current dialog settings
+    pub fn scheme(&self) -> crate::core::operations::RenameScheme {
+        if self.use_timestamp {
+            crate::core::operations::RenameScheme::Timestamp {
+                prefix: self.prefix.clone(),
+            }
+        } else {
+            crate::core::operations::RenameScheme::Sequential {
+                prefix: self.prefix.clone(),
+                digits: self.digits,
+            }
+        }
+    }
+}
+
+impl Default for RenameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State for the strip label metadata tool
+pub struct StripMetadataState {
+    /// Whether the strip metadata dialog is shown
+    pub show_dialog: bool,
+    /// Process every split instead of only the current one
+    pub all_splits: bool,
+    /// Export a cleaned copy instead of rewriting labels in place
+    pub export_copy: bool,
+    /// Output folder for export mode
+    pub output_dir: Option<std::path::PathBuf>,
+    /// Whether a strip (or dry run) is currently running
+    pub processing: bool,
+    /// Progress (current, total) of the running operation
+    pub progress: Option<(usize, usize)>,
+    /// Summary of the last dry run or strip
+    pub summary: Option<crate::core::operations::StripSummary>,
+    /// Whether the last run was cancelled
+    pub cancelled: bool,
+    /// Changes from the last in-place strip (for undo)
+    pub last_in_place_changes: Option<Vec<crate::core::operations::StripChange>>,
+    /// Result message from the last undo or error
+    pub result_message: Option<String>,
+    /// Channel receiver for progress updates
+    pub(crate) progress_receiver:
+        Option<Receiver<crate::core::operations::StripProgressMessage>>,
+    /// Flag to signal cancellation
+    pub(crate) cancel_flag: Option<Arc<AtomicBool>>,
+}
+
+impl StripMetadataState {
+    /// Create a new StripMetadataState with default values
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            all_splits: true,
+            export_copy: false,
+            output_dir: None,
+            processing: false,
+            progress: None,
+            summary: None,
+            cancelled: false,
+            last_in_place_changes: None,
+            result_message: None,
+            progress_receiver: None,
+            cancel_flag: None,
+        }
+    }
+
+    /// Check if there is an in-place strip that can be undone
+    pub fn can_undo(&self) -> bool {
+        self.last_in_place_changes
+            .as_ref()
+            .is_some_and(|changes| !changes.is_empty())
+    }
+}
+
+impl Default for StripMetadataState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Default JPEG quality for format conversion
+const DEFAULT_CONVERT_QUALITY: u8 = 90;
+
+/// State for the convert format dialog (Tools > Convert Format)
+pub struct ConvertFormatState {
+    /// Whether the convert format dialog is shown
+    pub show_dialog: bool,
+    /// Convert PNG to JPG (true) or JPG to PNG (false)
+    pub to_jpeg: bool,
+    /// JPEG quality (1-100) used when converting to JPG
+    pub quality: u8,
+    /// Whether a conversion is currently running
+    pub processing: bool,
+    /// Progress (current, total) of the running conversion
+    pub progress: Option<(usize, usize)>,
+    /// Report of the last conversion
+    pub report: Option<crate::core::operations::ConvertReport>,
+    /// Whether the last conversion was cancelled
+    pub cancelled: bool,
+    /// Channel receiver for progress updates
+    pub(crate) progress_receiver:
+        Option<Receiver<crate::core::operations::ConvertProgressMessage>>,
+    /// Flag to signal cancellation
+    pub(crate) cancel_flag: Option<Arc<AtomicBool>>,
+}
+
+impl ConvertFormatState {
+    /// Create a new ConvertFormatState with default values
+    pub fn new() -> Self {
+        Self {
+            show_dialog: false,
+            to_jpeg: true,
+            quality: DEFAULT_CONVERT_QUALITY,
+            processing: false,
+            progress: None,
+            report: None,
+            cancelled: false,
+            progress_receiver: None,
+            cancel_flag: None,
+        }
+    }
+
+    /// Target extension for the selected direction
+    pub fn dest_ext(&self) -> &'static str {
+        if self.to_jpeg {
+            "jpg"
+        } else {
+            "png"
+        }
+    }
+}
+
+impl Default for ConvertFormatState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Which images the flip augmentation tool copies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlipSource {
+    /// The images in the current view (the filtered images when a filter is active)
+    #[default]
+    View,
+    /// Every image of one category in the current split
+    Category(crate::core::analysis::ImageCategory),
+}
+
+/// State for the flip augmentation dialog (Tools > Flip Augment)
+#[derive(Default)]
+pub struct FlipAugmentState {
+    /// Whether the flip augmentation dialog is shown
+    pub show_dialog: bool,
+    /// Which images are flipped
+    pub source: FlipSource,
+    /// Split the flipped copies are written to (None = the split of the originals)
+    pub destination: Option<crate::core::dataset::DatasetSplit>,
+    /// Candidate shown in the preview
+    pub preview_index: usize,
+    /// Image the preview was made from, and its flipped texture (None if it could not be loaded)
+    pub preview: Option<(PathBuf, Option<TextureHandle>)>,
+    /// Whether a batch flip is currently running
+    pub processing: bool,
+    /// Progress (current, total) of the running batch flip
+    pub progress: Option<(usize, usize)>,
+    /// Report of the last batch flip
+    pub report: Option<crate::core::image::FlipReport>,
+    /// Whether the last batch flip was cancelled
+    pub cancelled: bool,
+    /// Generated files listed in the dataset's augmentation manifest
+    pub manifest_count: usize,
+    /// Whether the Remove Generated button is waiting for confirmation
+    pub confirm_remove: bool,
+    /// Result of the last removal of generated files
+    pub remove_message: Option<String>,
+    /// Channel receiver for progress updates
+    pub(crate) progress_receiver: Option<Receiver<crate::core::image::FlipProgressMessage>>,
+    /// Flag to signal cancellation
+    pub(crate) cancel_flag: Option<Arc<AtomicBool>>,
+}
+
+impl FlipAugmentState {
+    /// Create a new FlipAugmentState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// State for the detection crop export (Tools > Export Crops)
+pub struct CropExportState {
+    /// Whether the crop export dialog is shown
+    pub show_dialog: bool,
+    /// Padding around each box as a fraction of its size
+    pub padding: f32,
+    /// Folder the class subfolders are written to
+    pub output_dir: Option<PathBuf>,
+    /// Whether a batch crop is currently running
+    pub processing: bool,
+    /// Progress (current, total) of the running batch crop
+    pub progress: Option<(usize, usize)>,
+    /// Report of the last batch crop
+    pub report: Option<crate::core::image::CropReport>,
+    /// Whether the last batch crop was cancelled
+    pub cancelled: bool,
+    /// Channel receiver for progress updates
+    pub(crate) progress_receiver: Option<Receiver<crate::core::image::CropProgressMessage>>,
+    /// Flag to signal cancellation
+    pub(crate) cancel_flag: Option<Arc<AtomicBool>>,
+}
+
+impl Default for CropExportState {
+    fn default() -> Self {
+        Self {
+            show_dialog: false,
+            padding: 0.1,
+            output_dir: None,
+            processing: false,
+            progress: None,
+            report: None,
+            cancelled: false,
+            progress_receiver: None,
+            cancel_flag: None,
+        }
+    }
+}
+
+impl CropExportState {
+    /// Create a new CropExportState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// State for the session report export (bottom panel "Export Report")
+#[derive(Default)]
+pub struct ReportExportState {
+    /// Whether a report is being built
+    pub processing: bool,
+    /// Step and progress (current, total) of the running export
+    pub progress: Option<(&'static str, usize, usize)>,
+    /// Path written by the last export, or why it failed (shown in the status bar)
+    pub result: Option<Result<PathBuf, String>>,
+    /// Channel receiver for progress updates
+    pub(crate) progress_receiver: Option<Receiver<ReportProgressMessage>>,
+    /// Flag to signal cancellation
+    pub(crate) cancel_flag: Option<Arc<AtomicBool>>,
+}
+
+impl ReportExportState {
+    /// Create a new ReportExportState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Images kept decoded around the current index (in either direction)
+pub const PREFETCH_KEEP_RADIUS: usize = 5;
+
+/// An image decoded ahead of navigation by the prefetch thread
+pub struct PrefetchedImage {
+    /// Path the image was decoded from (guards against index shifts)
+    pub path: PathBuf,
+    pub image: egui::ColorImage,
+    pub dominant_color: Option<egui::Color32>,
+}
+
+/// Prefetch message sent from the background loader
+pub enum PrefetchMessage {
+    Loaded { index: usize, image: PrefetchedImage },
+    Failed { index: usize },
+}
+
+/// Background decoding of the images next to the current one
+#[derive(Default)]
+pub struct PrefetchState {
+    /// Indices the running prefetch task has still to load, in load order
+    pub prefetch_queue: VecDeque<usize>,
+    /// Decoded images by index
+    pub prefetch_cache: HashMap<usize, PrefetchedImage>,
+    /// Channel receiver for loaded images
+    pub(crate) receiver: Option<Receiver<PrefetchMessage>>,
+    /// Flag to abandon the running prefetch task
+    pub(crate) cancel_flag: Option<Arc<AtomicBool>>,
+}
+
+impl PrefetchState {
+    /// Create a new PrefetchState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop the running task and drop every cached image
+    pub fn clear(&mut self) {
+        if let Some(flag) = self.cancel_flag.take() {
+            flag.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+        self.receiver = None;
+        self.prefetch_queue.clear();
+        self.prefetch_cache.clear();
+    }
+
+    /// Drop cached images more than `PREFETCH_KEEP_RADIUS` away from `current`
+    pub fn evict_far_from(&mut self, current: usize) {
+        self.prefetch_cache
+            .retain(|&index, _| index.abs_diff(current) <= PREFETCH_KEEP_RADIUS);
+    }
+}
+
+/// Raw label file editor in the label panel
+#[derive(Default)]
+pub struct RawLabelState {
+    /// Label file shown in the editor
+    pub label_path: Option<PathBuf>,
+    /// Contents on disk when the file was loaded (`None` if it does not exist)
+    pub original: Option<String>,
+    /// Text being edited
+    pub text: String,
+    /// Lines that failed validation on the last save attempt
+    pub errors: Vec<crate::core::dataset::LabelLineError>,
+    /// Result of the last save or revert
+    pub message: Option<String>,
+}
+
+impl RawLabelState {
+    /// Create a new RawLabelState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a label file into the editor
+    ///
+    /// Unsaved edits survive a reload of the same file as long as it has not
+    /// changed on disk in the meantime.
+    pub fn load(&mut self, label_path: Option<PathBuf>) {
+        let contents = label_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok());
+        if label_path == self.label_path && contents == self.original {
+            return;
+        }
+
+        self.text = contents.clone().unwrap_or_default();
+        self.original = contents;
+        self.label_path = label_path;
+        self.errors.clear();
+        self.message = None;
+    }
+
+    /// Whether the text differs from the file on disk
+    pub fn is_modified(&self) -> bool {
+        self.text != self.original.as_deref().unwrap_or_default()
+    }
+
+    /// Discard unsaved edits
+    pub fn revert(&mut self) {
+        self.text = self.original.clone().unwrap_or_default();
+        self.errors.clear();
+        self.message = None;
+    }
+}
+
+/// Single-instance lock of the open dataset
+#[derive(Debug, Default)]
+pub struct DatasetLockState {
+    /// The lock this instance holds (None while read-only or without a dataset)
+    pub lock: Option<crate::core::dataset::DatasetLock>,
+    /// The other instance holding the lock, while the warning dialog is open
+    pub conflict: Option<crate::core::dataset::LockInfo>,
+    /// Changes to the dataset are disabled because another instance holds the lock
+    pub read_only: bool,
+}
+
+impl DatasetLockState {
+    /// Create a new DatasetLockState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// How often the status bar re-reads system memory usage
+const MEMORY_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Cached values shown in the status bar
+pub struct StatusBarState {
+    /// Image the cached dimensions belong to
+    dimensions_path: Option<PathBuf>,
+    /// Pixel dimensions of that image (None if the header could not be read)
+    dimensions: Option<(u32, u32)>,
+    /// System handle used to query memory usage
+    system: sysinfo::System,
+    /// When memory usage was last refreshed
+    memory_refreshed_at: Option<std::time::Instant>,
+}
+
+impl StatusBarState {
+    /// Create a new StatusBarState with default values
+    pub fn new() -> Self {
+        Self {
+            dimensions_path: None,
+            dimensions: None,
+            system: sysinfo::System::new(),
+            memory_refreshed_at: None,
+        }
+    }
+
+    /// Dimensions of an image, reading its header only when the image changes
+    pub fn image_dimensions(&mut self, path: &std::path::Path) -> Option<(u32, u32)> {
+        if self.dimensions_path.as_deref() != Some(path) {
+            self.dimensions = image::image_dimensions(path).ok();
+            self.dimensions_path = Some(path.to_path_buf());
+        }
+        self.dimensions
+    }
+
+    /// Used and total system memory in bytes, refreshed at most every couple of seconds
+    pub fn memory_usage(&mut self) -> (u64, u64) {
+        let stale = self
+            .memory_refreshed_at
+            .is_none_or(|at| at.elapsed() >= MEMORY_REFRESH_INTERVAL);
+        if stale {
+            self.system.refresh_memory();
+            self.memory_refreshed_at = Some(std::time::Instant::now());
+        }
+        (self.system.used_memory(), self.system.total_memory())
+    }
+}
+
+impl Default for StatusBarState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Dataset summary overview (shown on load and from the top panel)
+#[derive(Default)]
+pub struct DatasetSummaryState {
+    /// Whether to show the summary window
+    pub show_dialog: bool,
+    /// Last computed summary
+    pub summary: Option<crate::core::dataset::DatasetSummary>,
+    /// Channel receiver for the background summary computation
+    pub(crate) receiver: Option<Receiver<crate::core::dataset::DatasetSummary>>,
+}
+
+impl DatasetSummaryState {
+    /// Create a new DatasetSummaryState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a summary is being computed
+    pub fn is_computing(&self) -> bool {
+        self.receiver.is_some()
+    }
+}
+
+/// Per-image category cache for the current split (used by the minimap)
+#[derive(Default)]
+pub struct CategoryCacheState {
+    /// Category of each image, aligned with `Dataset::get_image_files()`
+    pub categories: Option<Vec<crate::core::analysis::ImageCategory>>,
+    /// Number of detections in each image's label, aligned like `categories`
+    pub detection_counts: Option<Vec<usize>>,
+    /// Channel receiver for the background categorization result
+    pub(crate) receiver: Option<Receiver<Vec<(crate::core::analysis::ImageCategory, usize)>>>,
+    /// Flag to cancel a categorization that is no longer needed
+    pub(crate) cancel_flag: Option<Arc<AtomicBool>>,
+}
+
+impl CategoryCacheState {
+    /// Create a new CategoryCacheState with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the cached categories if they match the current image count
+    pub fn get(&self, image_count: usize) -> Option<&[crate::core::analysis::ImageCategory]> {
+        self.categories
+            .as_deref()
+            .filter(|categories| categories.len() == image_count)
+    }
+
+    /// Get the cached categories and detection counts if they match the current image count
+    pub fn get_indexed(
+        &self,
+        image_count: usize,
+    ) -> Option<(&[crate::core::analysis::ImageCategory], &[usize])> {
+        let categories = self.get(image_count)?;
+        let detection_counts = self
+            .detection_counts
+            .as_deref()
+            .filter(|counts| counts.len() == image_count)?;
+        Some((categories, detection_counts))
+    }
+
+    /// Whether the background categorization is still running
+    pub fn is_indexing(&self) -> bool {
+        self.receiver.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookmarked_filter() -> FilterState {
+        let mut filter = FilterState::new();
+        filter.criteria.bookmarked_only = true;
+        filter
+    }
+
+    #[test]
+    fn test_filtered_index_reverse_map() {
+        let mut filter = bookmarked_filter();
+        filter.set_filtered_indices(vec![2, 5, 9]);
+
+        assert_eq!(filter.get_filtered_index(5), Some(1));
+        assert_eq!(filter.get_filtered_index(3), None);
+        assert_eq!(filter.get_actual_index(2), Some(9));
+
+        filter.set_filtered_indices(vec![5]);
+        assert_eq!(filter.get_filtered_index(5), Some(0));
+        assert_eq!(filter.get_filtered_index(9), None);
+    }
+
+    #[test]
+    fn test_filtered_index_lookup_matches_linear_scan() {
+        let mut filter = bookmarked_filter();
+        let indices: Vec<usize> = (0..5_000).map(|i| i * 3 + i % 2).collect();
+        filter.set_filtered_indices(indices.clone());
+
+        // Every actual index, filtered or not, resolves like a scan of the list
+        for actual in 0..indices.last().unwrap() + 5 {
+            let scanned = indices.iter().position(|&idx| idx == actual);
+            assert_eq!(filter.get_filtered_index(actual), scanned, "index {}", actual);
+        }
+    }
+
+    /// Benchmark: `cargo test --release -- --ignored filtered_index_lookup`
+    #[test]
+    #[ignore = "timing benchmark, run on demand"]
+    fn bench_filtered_index_lookup_does_not_scale_with_size() {
+        // The same 100k lookups against a 2k- and a 200k-image filter; a linear
+        // scan would be ~100x slower on the large one, the reverse map is not
+        let time_lookups = |size: usize| {
+            let mut filter = bookmarked_filter();
+            filter.set_filtered_indices((0..size).map(|i| i * 2).collect());
+            let start = std::time::Instant::now();
+            for i in 0..100_000 {
+                std::hint::black_box(filter.get_filtered_index((i * 7_919) % (size * 2)));
+            }
+            start.elapsed()
+        };
+        let small = time_lookups(2_000);
+        let large = time_lookups(200_000);
+        assert!(large < small * 10, "2k: {:?}, 200k: {:?}", small, large);
+    }
+
+    #[test]
+    fn test_prefetch_evicts_outside_keep_radius() {
+        let mut prefetch = PrefetchState::new();
+        for index in [0, 4, 10, 15, 16] {
+            prefetch.prefetch_cache.insert(
+                index,
+                PrefetchedImage {
+                    path: PathBuf::from(format!("{}.jpg", index)),
+                    image: egui::ColorImage::new([1, 1], egui::Color32::BLACK),
+                    dominant_color: None,
+                },
+            );
+        }
+
+        prefetch.evict_far_from(10);
+        let mut kept: Vec<usize> = prefetch.prefetch_cache.keys().copied().collect();
+        kept.sort_unstable();
+        assert_eq!(kept, vec![10, 15]);
+
+        prefetch.clear();
+        assert!(prefetch.prefetch_cache.is_empty());
+    }
+}
@@ -1,10 +1,18 @@
 mod app_state;
+mod progress_rate;
 mod settings;
 mod undo_manager;
 
 pub use app_state::{
-    BalanceAnalysisState, BatchProgressMessage, BatchState, FilterState, ImageState, 
-    IntegrityState, RebalanceState, UIState,
+    AutoAnalysisStatus, BalanceAnalysisState, BatchProgressMessage, BatchRemovalMode, BatchState, BookmarkDialogState, CategoryCacheState,
+    DatasetLockState, ManifestFilter,
+    ConvertFormatState, CropExportState, DatasetSummaryState, ExportProgressMessage, ExportSubsetState, FilterState, FlipAugmentState,
+    FlipSource, ImageState,
+    IntegrityState, PendingAdvance, PinnedImage, PrefetchMessage, PrefetchState, PrefetchedImage, RawLabelState, PREFETCH_KEEP_RADIUS, RebalanceState, RenameState, ReportExportState, ReportProgressMessage, StatusBarState, StripMetadataState,
+    UIState,
+};
+pub use progress_rate::ProgressRate;
+pub use settings::{Settings, ZoomNavigateMode, MAX_AUTO_ADVANCE_DELAY_MS};
+pub use undo_manager::{
+    CleanupFile, LabelEditState, UndoAction, UndoManager, UndoState, MAX_UNDO_DEPTH,
 };
-pub use settings::Settings;
-pub use undo_manager::{UndoManager, UndoState};
@@ -0,0 +1,115 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::core::statistics::format_duration;
+
+/// How far back the moving average looks
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// Throughput and time remaining of a running background job
+///
+/// Fed with the progress count of each progress message; the rate is a
+/// moving average over the last few seconds so it follows a slowing disk.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressRate {
+    /// First sample of the job, for the overall average
+    first: Option<(Instant, usize)>,
+    /// Recent (time, items done) samples, oldest first
+    samples: VecDeque<(Instant, usize)>,
+}
+
+impl ProgressRate {
+    /// Create an empty ProgressRate
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the number of items done so far
+    pub fn record(&mut self, current: usize) {
+        self.record_at(Instant::now(), current);
+    }
+
+    fn record_at(&mut self, now: Instant, current: usize) {
+        self.first.get_or_insert((now, current));
+        self.samples.push_back((now, current));
+        // Keep two samples at least so slow jobs still have a rate
+        while self.samples.len() > 2
+            && self
+                .samples
+                .front()
+                .is_some_and(|(time, _)| now.duration_since(*time) > RATE_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Forget the samples of a finished job
+    pub fn clear(&mut self) {
+        self.first = None;
+        self.samples.clear();
+    }
+
+    fn rate_between(from: (Instant, usize), to: (Instant, usize)) -> Option<f64> {
+        let seconds = to.0.duration_since(from.0).as_secs_f64();
+        let done = to.1.checked_sub(from.1)?;
+        (seconds > 0.0 && done > 0).then(|| done as f64 / seconds)
+    }
+
+    /// Items per second over the recent window
+    pub fn per_second(&self) -> Option<f64> {
+        Self::rate_between(*self.samples.front()?, *self.samples.back()?)
+    }
+
+    /// Items per second over the whole job so far
+    pub fn overall_per_second(&self) -> Option<f64> {
+        Self::rate_between(self.first?, *self.samples.back()?)
+    }
+
+    /// Estimated time until `total` items are done at the recent rate
+    pub fn eta(&self, current: usize, total: usize) -> Option<Duration> {
+        let rate = self.per_second()?;
+        let remaining = total.saturating_sub(current) as f64;
+        Some(Duration::from_secs_f64(remaining / rate))
+    }
+
+    /// Rate and ETA for a progress label, e.g. "12.5 images/s · ETA 0:01:20"
+    ///
+    /// Empty until two samples with progress between them have been recorded.
+    pub fn label(&self, current: usize, total: usize, unit: &str) -> String {
+        match (self.per_second(), self.eta(current, total)) {
+            (Some(rate), Some(eta)) => {
+                format!("{:.1} {}/s · ETA {}", rate, unit, format_duration(eta))
+            }
+            _ => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_and_eta_follow_recent_samples() {
+        let start = Instant::now();
+        let mut rate = ProgressRate::new();
+        assert_eq!(rate.per_second(), None);
+        assert_eq!(rate.label(0, 100, "files"), "");
+
+        // 10 files/s for 20 seconds, then 2 files/s
+        for second in 0..=20 {
+            rate.record_at(start + Duration::from_secs(second), second as usize * 10);
+        }
+        for second in 21..=40 {
+            rate.record_at(start + Duration::from_secs(second), 200 + (second as usize - 20) * 2);
+        }
+
+        assert!((rate.per_second().unwrap() - 2.0).abs() < 1e-9);
+        assert!((rate.overall_per_second().unwrap() - 6.0).abs() < 1e-9);
+        assert_eq!(rate.eta(240, 300), Some(Duration::from_secs(30)));
+        assert_eq!(rate.label(240, 300, "files"), "2.0 files/s · ETA 0:00:30");
+
+        rate.clear();
+        assert_eq!(rate.overall_per_second(), None);
+    }
+}
@@ -1,4 +1,8 @@
-use crate::core::filter::FilterCriteria;
+use crate::config::{default_classes, AppConfig, ClassStyle};
+use crate::core::dataset::{push_recent_dataset, RecentDataset};
+use crate::core::filter::{FilterCriteria, FilterPreset};
+use crate::core::operations::DeletionMode;
+use crate::state::undo_manager::DEFAULT_UNDO_DEPTH;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -10,6 +14,10 @@ pub struct Settings {
     /// Last dataset path that was opened
     pub last_dataset_path: Option<PathBuf>,
 
+    /// Recently opened datasets, newest first (at most `MAX_RECENT_DATASETS`)
+    #[serde(default)]
+    pub recent_datasets: Vec<RecentDataset>,
+
     /// Last window width
     pub window_width: f32,
 
@@ -25,22 +33,215 @@ pub struct Settings {
     /// Last active filter configuration
     #[serde(default)]
     pub filter_criteria: FilterCriteria,
+
+    /// Class table (names and colors)
+    #[serde(default = "default_classes")]
+    pub classes: Vec<ClassStyle>,
+
+    /// Reset zoom to 100% when navigating to another image
+    #[serde(default = "default_true")]
+    pub zoom_reset_on_navigate: bool,
+
+    /// Remember zoom and scroll per image during the session (overrides `zoom_reset_on_navigate`)
+    #[serde(default)]
+    pub remember_view_per_image: bool,
+
+    /// Largest zoom level allowed (1.0 = fit to view)
+    #[serde(default = "default_max_zoom")]
+    pub max_zoom: f32,
+
+    /// Label panel width after the user resized it (None = config default)
+    #[serde(default)]
+    pub label_panel_width: Option<f32>,
+
+    /// Open/closed state of the label panel sections
+    #[serde(default)]
+    pub label_panel_sections: LabelPanelSections,
+
+    /// Where deleted images and labels are moved
+    #[serde(default)]
+    pub deletion_mode: DeletionMode,
+
+    /// Move to the next image after saving a label edit or augmenting the current image
+    #[serde(default)]
+    pub auto_advance: bool,
+
+    /// Pause before auto-advancing, in milliseconds (0 to `MAX_AUTO_ADVANCE_DELAY_MS`)
+    #[serde(default)]
+    pub auto_advance_delay_ms: u32,
+
+    /// Analyze balance in the background whenever a dataset is opened or the split changes
+    #[serde(default)]
+    pub auto_analyze_on_load: bool,
+
+    /// Run the integrity check whenever a dataset is opened or the split changes
+    #[serde(default)]
+    pub auto_analyze_integrity: bool,
+
+    /// Named filter combinations (the first nine are on Ctrl+1 to Ctrl+9)
+    #[serde(default)]
+    pub filter_presets: Vec<FilterPreset>,
+
+    /// Rewrite the dataset's data.yaml after moving images between splits
+    #[serde(default)]
+    pub auto_update_data_yaml: bool,
+
+    /// Most actions kept in the undo history (1 to `MAX_UNDO_DEPTH`)
+    #[serde(default = "default_undo_depth")]
+    pub undo_depth: usize,
+
+    /// Browse without changing the dataset (deleting, moving and editing disabled)
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// Longest configurable auto-advance pause
+pub const MAX_AUTO_ADVANCE_DELAY_MS: u32 = 500;
+
+/// What happens to zoom and scroll when navigating to another image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoomNavigateMode {
+    /// Reset to fit on every navigation
+    Reset,
+    /// Keep the current zoom level for the next image
+    Keep,
+    /// Restore the zoom and scroll each image was last viewed with
+    PerImage,
+}
+
+impl ZoomNavigateMode {
+    /// The next mode when cycling with the zoom badge
+    pub fn next(self) -> Self {
+        match self {
+            ZoomNavigateMode::Reset => ZoomNavigateMode::Keep,
+            ZoomNavigateMode::Keep => ZoomNavigateMode::PerImage,
+            ZoomNavigateMode::PerImage => ZoomNavigateMode::Reset,
+        }
+    }
+}
+
+/// Which collapsible sections of the label panel are expanded
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LabelPanelSections {
+    #[serde(default = "default_true")]
+    pub dominant_color: bool,
+    #[serde(default = "default_true")]
+    pub metadata: bool,
+    #[serde(default = "default_true")]
+    pub detections: bool,
+    #[serde(default)]
+    pub raw_label: bool,
+    /// Image thumbnail at the top of the panel
+    #[serde(default = "default_true")]
+    pub thumbnail: bool,
+    /// Size, density and embedded chunks read from the image file
+    #[serde(default)]
+    pub image_metadata: bool,
+}
+
+impl Default for LabelPanelSections {
+    fn default() -> Self {
+        Self {
+            dominant_color: true,
+            metadata: true,
+            detections: true,
+            raw_label: false,
+            thumbnail: true,
+            image_metadata: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_zoom() -> f32 {
+    3.0
+}
+
+fn default_undo_depth() -> usize {
+    DEFAULT_UNDO_DEPTH
 }
 
 impl Default for Settings {
     fn default() -> Self {
+        let config = AppConfig::default();
         Self {
             last_dataset_path: None,
-            window_width: 1200.0,
-            window_height: 800.0,
+            recent_datasets: Vec::new(),
+            window_width: config.window_width,
+            window_height: config.window_height,
             last_split: "train".to_string(),
             last_image_index: 0,
             filter_criteria: FilterCriteria::default(),
+            classes: default_classes(),
+            zoom_reset_on_navigate: true,
+            remember_view_per_image: false,
+            max_zoom: default_max_zoom(),
+            label_panel_width: None,
+            label_panel_sections: LabelPanelSections::default(),
+            deletion_mode: DeletionMode::default(),
+            auto_advance: false,
+            auto_advance_delay_ms: 0,
+            auto_analyze_on_load: false,
+            auto_analyze_integrity: false,
+            filter_presets: Vec::new(),
+            auto_update_data_yaml: false,
+            undo_depth: DEFAULT_UNDO_DEPTH,
+            read_only: false,
         }
     }
 }
 
 impl Settings {
+    /// Save `criteria` under `name`, replacing a preset with the same name in place
+    pub fn save_filter_preset(&mut self, name: &str, criteria: FilterCriteria) {
+        match self.filter_presets.iter_mut().find(|p| p.name == name) {
+            Some(preset) => preset.criteria = criteria,
+            None => self.filter_presets.push(FilterPreset {
+                name: name.to_string(),
+                criteria,
+            }),
+        }
+    }
+
+    /// Remove the preset called `name`. Returns whether it existed.
+    pub fn remove_filter_preset(&mut self, name: &str) -> bool {
+        let before = self.filter_presets.len();
+        self.filter_presets.retain(|p| p.name != name);
+        self.filter_presets.len() != before
+    }
+
+    /// Record `path` as the last opened dataset and move it to the top of the recent list
+    pub fn remember_dataset(&mut self, path: PathBuf) {
+        self.last_dataset_path = Some(path.clone());
+        push_recent_dataset(&mut self.recent_datasets, RecentDataset::new(path));
+    }
+
+    /// Recent-list entry of the last opened dataset
+    pub fn last_recent_dataset(&self) -> Option<&RecentDataset> {
+        let last = self.last_dataset_path.as_ref()?;
+        self.recent_datasets.iter().find(|entry| &entry.path == last)
+    }
+
+    /// The zoom behavior selected by `zoom_reset_on_navigate` and `remember_view_per_image`
+    pub fn zoom_navigate_mode(&self) -> ZoomNavigateMode {
+        if self.remember_view_per_image {
+            ZoomNavigateMode::PerImage
+        } else if self.zoom_reset_on_navigate {
+            ZoomNavigateMode::Reset
+        } else {
+            ZoomNavigateMode::Keep
+        }
+    }
+
+    /// Store a zoom behavior in the underlying flags
+    pub fn set_zoom_navigate_mode(&mut self, mode: ZoomNavigateMode) {
+        self.remember_view_per_image = mode == ZoomNavigateMode::PerImage;
+        self.zoom_reset_on_navigate = mode == ZoomNavigateMode::Reset;
+    }
+
     /// Get the path to the settings file (in the same directory as the executable)
     pub fn get_config_path() -> Option<PathBuf> {
         std::env::current_exe()
@@ -122,17 +323,50 @@ mod tests {
         assert_eq!(settings.last_image_index, 0);
         assert!(settings.last_dataset_path.is_none());
         assert!(!settings.filter_criteria.is_active());
+        assert!(settings.zoom_reset_on_navigate);
     }
 
     #[test]
     fn test_settings_serialization_roundtrip() {
         let settings = Settings {
             last_dataset_path: Some(PathBuf::from("test/path/dataset")),
+            recent_datasets: vec![RecentDataset {
+                path: PathBuf::from("test/path/dataset"),
+                id: Some("0123456789abcdef".to_string()),
+            }],
             window_width: 1280.0,
             window_height: 720.0,
             last_split: "val".to_string(),
             last_image_index: 42,
             filter_criteria: FilterCriteria::default(),
+            classes: vec![ClassStyle::new(2, "Chicken", [255, 255, 0])],
+            zoom_reset_on_navigate: false,
+            remember_view_per_image: true,
+            max_zoom: 8.0,
+            label_panel_width: Some(420.0),
+            label_panel_sections: LabelPanelSections {
+                dominant_color: false,
+                metadata: true,
+                detections: true,
+                raw_label: true,
+                thumbnail: false,
+                image_metadata: true,
+            },
+            deletion_mode: DeletionMode::DatasetTrash,
+            auto_advance: true,
+            auto_advance_delay_ms: 250,
+            auto_analyze_on_load: true,
+            auto_analyze_integrity: true,
+            auto_update_data_yaml: true,
+            undo_depth: 25,
+            read_only: true,
+            filter_presets: vec![FilterPreset {
+                name: "Background only".to_string(),
+                criteria: FilterCriteria {
+                    player_count: crate::core::filter::PlayerCountFilter::Background,
+                    ..Default::default()
+                },
+            }],
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -142,10 +376,93 @@ mod tests {
             loaded.last_dataset_path,
             Some(PathBuf::from("test/path/dataset"))
         );
+        assert_eq!(loaded.recent_datasets, settings.recent_datasets);
         assert_eq!(loaded.window_width, 1280.0);
         assert_eq!(loaded.window_height, 720.0);
         assert_eq!(loaded.last_split, "val");
         assert_eq!(loaded.last_image_index, 42);
         assert!(!loaded.filter_criteria.is_active());
+        assert_eq!(loaded.classes, vec![ClassStyle::new(2, "Chicken", [255, 255, 0])]);
+        assert!(!loaded.zoom_reset_on_navigate);
+        assert_eq!(loaded.zoom_navigate_mode(), ZoomNavigateMode::PerImage);
+        assert_eq!(loaded.max_zoom, 8.0);
+        assert_eq!(loaded.label_panel_width, Some(420.0));
+        assert!(!loaded.label_panel_sections.dominant_color);
+        assert!(loaded.label_panel_sections.detections);
+        assert!(loaded.label_panel_sections.raw_label);
+        assert_eq!(loaded.deletion_mode, DeletionMode::DatasetTrash);
+        assert!(loaded.auto_advance);
+        assert_eq!(loaded.auto_advance_delay_ms, 250);
+        assert!(loaded.auto_analyze_on_load);
+        assert!(loaded.auto_analyze_integrity);
+        assert!(loaded.auto_update_data_yaml);
+        assert_eq!(loaded.undo_depth, 25);
+        assert!(loaded.read_only);
+        assert_eq!(loaded.filter_presets, settings.filter_presets);
+    }
+
+    #[test]
+    fn test_filter_presets_replace_by_name() {
+        let mut settings = Settings::default();
+        let background = FilterCriteria {
+            player_count: crate::core::filter::PlayerCountFilter::Background,
+            ..Default::default()
+        };
+        settings.save_filter_preset("Daily", FilterCriteria::default());
+        settings.save_filter_preset("Other", FilterCriteria::default());
+        settings.save_filter_preset("Daily", background.clone());
+
+        let names: Vec<_> = settings.filter_presets.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Daily", "Other"]);
+        assert_eq!(settings.filter_presets[0].criteria, background);
+
+        assert!(settings.remove_filter_preset("Daily"));
+        assert!(!settings.remove_filter_preset("Daily"));
+        assert_eq!(settings.filter_presets.len(), 1);
+    }
+
+    #[test]
+    fn test_zoom_navigate_mode_cycles_through_flags() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.zoom_navigate_mode(), ZoomNavigateMode::Reset);
+
+        for expected in [
+            ZoomNavigateMode::Keep,
+            ZoomNavigateMode::PerImage,
+            ZoomNavigateMode::Reset,
+        ] {
+            let next = settings.zoom_navigate_mode().next();
+            settings.set_zoom_navigate_mode(next);
+            assert_eq!(settings.zoom_navigate_mode(), expected);
+        }
+        assert!(settings.zoom_reset_on_navigate);
+        assert!(!settings.remember_view_per_image);
+    }
+
+    #[test]
+    fn test_settings_missing_fields_use_defaults() {
+        let json = r#"{
+            "last_dataset_path": null,
+            "window_width": 1200.0,
+            "window_height": 800.0,
+            "last_split": "train",
+            "last_image_index": 0
+        }"#;
+
+        let loaded: Settings = serde_json::from_str(json).unwrap();
+        assert_eq!(loaded.classes, default_classes());
+        assert!(loaded.zoom_reset_on_navigate);
+        assert_eq!(loaded.zoom_navigate_mode(), ZoomNavigateMode::Reset);
+        assert_eq!(loaded.max_zoom, 3.0);
+        assert_eq!(loaded.label_panel_width, None);
+        assert_eq!(loaded.label_panel_sections, LabelPanelSections::default());
+        assert_eq!(loaded.deletion_mode, DeletionMode::TempDir);
+        assert!(!loaded.auto_advance);
+        assert_eq!(loaded.auto_advance_delay_ms, 0);
+        assert!(!loaded.auto_analyze_on_load);
+        assert!(!loaded.auto_analyze_integrity);
+        assert!(loaded.filter_presets.is_empty());
+        assert!(!loaded.auto_update_data_yaml);
+        assert!(loaded.recent_datasets.is_empty());
     }
 }
@@ -1,6 +1,13 @@
+use chrono::{DateTime, Local};
 use std::path::PathBuf;
 use std::time::Instant;
 
+/// Default number of actions kept on the undo stack
+pub const DEFAULT_UNDO_DEPTH: usize = 100;
+
+/// Largest configurable undo depth
+pub const MAX_UNDO_DEPTH: usize = 1000;
+
 /// Represents a single deletion that can be undone or redone
 #[derive(Clone)]
 pub struct UndoState {
@@ -12,51 +19,273 @@ pub struct UndoState {
     pub temp_label_path: Option<PathBuf>,
 }
 
-/// Manages undo and redo stacks for image deletion operations
+/// Represents a raw label file edit that can be undone or redone
+#[derive(Clone)]
+pub struct LabelEditState {
+    pub image_path: PathBuf,
+    pub label_path: PathBuf,
+    /// File contents before the edit (`None` if the file did not exist)
+    pub previous_contents: Option<String>,
+    pub new_contents: String,
+}
+
+/// A file removed by an integrity cleanup, parked in the deletion directory
+#[derive(Clone)]
+pub struct CleanupFile {
+    pub original_path: PathBuf,
+    pub temp_path: PathBuf,
+}
+
+/// A single entry on the undo or redo stack
+#[derive(Clone)]
+pub enum UndoAction {
+    Delete(UndoState),
+    LabelEdit(LabelEditState),
+    /// An augmented copy; undo moves it to the temp paths, redo restores it
+    Augment(UndoState),
+    /// An image sent to the recycle bin (holds its filename); it can only be
+    /// restored from the OS, so undo and redo just explain that
+    Recycled(String),
+    /// Orphaned files removed together from the integrity dialog; undone and
+    /// redone as one batch
+    IntegrityCleanup(Vec<CleanupFile>),
+    /// Duplicate boxes removed from label files in one batch; undo restores
+    /// the backups, redo writes the deduplicated contents again
+    BoxDedupe(Vec<crate::core::operations::DedupeChange>),
+}
+
+impl UndoAction {
+    /// Short description for tooltips (e.g. "delete of frame_001.png")
+    pub fn description(&self) -> String {
+        match self {
+            UndoAction::Delete(state) => format!("delete of {}", state.image_filename),
+            UndoAction::LabelEdit(edit) => format!(
+                "label edit of {}",
+                edit.image_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default()
+            ),
+            UndoAction::Augment(state) => format!("augmented copy {}", state.image_filename),
+            UndoAction::Recycled(image_filename) => {
+                format!("recycle bin delete of {}", image_filename)
+            }
+            UndoAction::IntegrityCleanup(files) => {
+                format!("cleanup of {} orphaned files", files.len())
+            }
+            UndoAction::BoxDedupe(changes) => {
+                format!("duplicate box removal in {} label files", changes.len())
+            }
+        }
+    }
+
+    /// Kind of operation for the history list (e.g. "Delete")
+    pub fn kind(&self) -> &'static str {
+        match self {
+            UndoAction::Delete(_) => "Delete",
+            UndoAction::LabelEdit(_) => "Label edit",
+            UndoAction::Augment(_) => "Augment",
+            UndoAction::Recycled(_) => "Recycle bin",
+            UndoAction::IntegrityCleanup(_) => "Integrity cleanup",
+            UndoAction::BoxDedupe(_) => "Box dedupe",
+        }
+    }
+
+    /// Dataset files this action changes
+    pub fn dataset_paths(&self) -> Vec<PathBuf> {
+        match self {
+            UndoAction::Delete(state) | UndoAction::Augment(state) => {
+                std::iter::once(state.image_path.clone())
+                    .chain(state.label_path.clone())
+                    .collect()
+            }
+            UndoAction::LabelEdit(edit) => vec![edit.label_path.clone()],
+            UndoAction::Recycled(_) => Vec::new(),
+            UndoAction::IntegrityCleanup(files) => {
+                files.iter().map(|file| file.original_path.clone()).collect()
+            }
+            UndoAction::BoxDedupe(changes) => {
+                changes.iter().map(|change| change.label_path.clone()).collect()
+            }
+        }
+    }
+
+    /// Files parked outside the dataset so this action can be undone or redone
+    ///
+    /// Deleted when the action falls off the end of the history; files that
+    /// were already moved back are simply missing.
+    pub fn parked_files(&self) -> Vec<PathBuf> {
+        match self {
+            UndoAction::Delete(state) | UndoAction::Augment(state) => {
+                std::iter::once(state.temp_image_path.clone())
+                    .chain(state.temp_label_path.clone())
+                    .collect()
+            }
+            UndoAction::LabelEdit(_) | UndoAction::Recycled(_) => Vec::new(),
+            UndoAction::IntegrityCleanup(files) => {
+                files.iter().map(|file| file.temp_path.clone()).collect()
+            }
+            UndoAction::BoxDedupe(changes) => changes
+                .iter()
+                .filter_map(|change| change.backup_path.clone())
+                .collect(),
+        }
+    }
+}
+
+/// An action on the undo or redo stack with the time it was performed
+#[derive(Clone)]
+pub struct UndoEntry {
+    pub action: UndoAction,
+    pub recorded_at: DateTime<Local>,
+}
+
+/// Manages undo and redo stacks for image deletions, label edits and augmentations
 pub struct UndoManager {
-    undo_stack: Vec<UndoState>,
-    redo_stack: Vec<UndoState>,
+    undo_stack: Vec<UndoEntry>,
+    redo_stack: Vec<UndoEntry>,
+    /// Most actions kept on the undo stack; older ones are evicted
+    max_depth: usize,
+    /// Actions that fell off the end, waiting for their parked files to be removed
+    evicted: Vec<UndoAction>,
 }
 
 impl UndoManager {
     /// Create a new empty undo manager
     pub fn new() -> Self {
+        Self::with_max_depth(DEFAULT_UNDO_DEPTH)
+    }
+
+    /// Create a new empty undo manager that keeps at most `max_depth` actions
+    pub fn with_max_depth(max_depth: usize) -> Self {
         Self {
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
+            max_depth: max_depth.max(1),
+            evicted: Vec::new(),
+        }
+    }
+
+    /// Most actions kept on the undo stack
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Change the undo depth, evicting the oldest actions beyond it
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth.max(1);
+        self.evict_overflow();
+    }
+
+    fn evict_overflow(&mut self) {
+        if self.undo_stack.len() > self.max_depth {
+            let overflow = self.undo_stack.len() - self.max_depth;
+            self.evicted
+                .extend(self.undo_stack.drain(..overflow).map(|entry| entry.action));
         }
+        if self.redo_stack.len() > self.max_depth {
+            let overflow = self.redo_stack.len() - self.max_depth;
+            self.evicted
+                .extend(self.redo_stack.drain(..overflow).map(|entry| entry.action));
+        }
+    }
+
+    /// Take the actions evicted since the last call, so their parked files can be removed
+    pub fn take_evicted(&mut self) -> Vec<UndoAction> {
+        std::mem::take(&mut self.evicted)
     }
 
     /// Push a new deletion onto the undo stack and clear the redo stack
     /// This is called when a user deletes an image
     pub fn push_delete(&mut self, state: UndoState) {
-        self.undo_stack.push(state);
-        // Clear redo stack when a new action is performed (standard behavior)
-        self.redo_stack.clear();
+        self.push(UndoAction::Delete(state));
+    }
+
+    /// Push a saved label edit onto the undo stack and clear the redo stack
+    pub fn push_label_edit(&mut self, edit: LabelEditState) {
+        self.push(UndoAction::LabelEdit(edit));
+    }
+
+    /// Push a newly written augmented copy onto the undo stack and clear the redo stack
+    pub fn push_augment(&mut self, state: UndoState) {
+        self.push(UndoAction::Augment(state));
+    }
+
+    /// Push an image sent to the recycle bin onto the undo stack and clear the redo stack
+    pub fn push_recycled(&mut self, image_filename: String) {
+        self.push(UndoAction::Recycled(image_filename));
     }
 
-    /// Pop the most recent deletion from the undo stack
-    /// Returns the state to restore, and pushes it onto the redo stack
-    pub fn undo(&mut self) -> Option<UndoState> {
-        if let Some(state) = self.undo_stack.pop() {
-            let state_clone = state.clone();
-            self.redo_stack.push(state);
-            Some(state_clone)
-        } else {
-            None
+    /// Push a batch of removed orphaned files onto the undo stack and clear the redo stack
+    pub fn push_integrity_cleanup(&mut self, files: Vec<CleanupFile>) {
+        self.push(UndoAction::IntegrityCleanup(files));
+    }
+
+    /// Push a batch of duplicate box removals onto the undo stack and clear the redo stack
+    pub fn push_box_dedupe(&mut self, changes: Vec<crate::core::operations::DedupeChange>) {
+        self.push(UndoAction::BoxDedupe(changes));
+    }
+
+    fn push(&mut self, action: UndoAction) {
+        self.undo_stack.push(UndoEntry {
+            action,
+            recorded_at: Local::now(),
+        });
+        // Clear redo stack when a new action is performed (standard behavior);
+        // the redone files are back in the dataset, so nothing is parked
+        self.evicted
+            .extend(self.redo_stack.drain(..).map(|entry| entry.action));
+        self.evict_overflow();
+    }
+
+    /// Pop the most recent action from the undo stack
+    /// Returns the action to revert, and pushes it onto the redo stack
+    pub fn undo(&mut self) -> Option<UndoAction> {
+        let index = self.undo_stack.len().checked_sub(1)?;
+        self.undo_at(index)
+    }
+
+    /// Undo the action at `index` of the undo stack (0 = oldest), out of order
+    ///
+    /// Callers check `can_undo_out_of_order` first; the action is put on top
+    /// of the redo stack.
+    pub fn undo_at(&mut self, index: usize) -> Option<UndoAction> {
+        if index >= self.undo_stack.len() {
+            return None;
         }
+        let entry = self.undo_stack.remove(index);
+        let action = entry.action.clone();
+        self.redo_stack.push(entry);
+        Some(action)
     }
 
-    /// Pop the most recent undo from the redo stack
-    /// Returns the state to re-delete, and pushes it onto the undo stack
-    pub fn redo(&mut self) -> Option<UndoState> {
-        if let Some(state) = self.redo_stack.pop() {
-            let state_clone = state.clone();
-            self.undo_stack.push(state);
-            Some(state_clone)
-        } else {
-            None
+    /// Whether the action at `index` of the undo stack can be undone before
+    /// the newer ones: no newer action touches the same files, and it is not
+    /// a recycle bin delete (which is not undoable here)
+    pub fn can_undo_out_of_order(&self, index: usize) -> bool {
+        let Some(entry) = self.undo_stack.get(index) else {
+            return false;
+        };
+        if matches!(entry.action, UndoAction::Recycled(_)) {
+            return false;
         }
+        let paths = entry.action.dataset_paths();
+        !self.undo_stack[index + 1..].iter().any(|newer| {
+            newer
+                .action
+                .dataset_paths()
+                .iter()
+                .any(|path| paths.contains(path))
+        })
+    }
+
+    /// Pop the most recent undo from the redo stack
+    /// Returns the action to apply again, and pushes it onto the undo stack
+    pub fn redo(&mut self) -> Option<UndoAction> {
+        let entry = self.redo_stack.pop()?;
+        let action = entry.action.clone();
+        self.undo_stack.push(entry);
+        Some(action)
     }
 
     /// Check if undo is available
@@ -69,6 +298,16 @@ impl UndoManager {
         !self.redo_stack.is_empty()
     }
 
+    /// The action the next undo would revert, without popping it
+    pub fn peek_undo(&self) -> Option<&UndoAction> {
+        self.undo_stack.last().map(|entry| &entry.action)
+    }
+
+    /// The action the next redo would apply, without popping it
+    pub fn peek_redo(&self) -> Option<&UndoAction> {
+        self.redo_stack.last().map(|entry| &entry.action)
+    }
+
     /// Get the number of available undos
     pub fn undo_count(&self) -> usize {
         self.undo_stack.len()
@@ -85,13 +324,20 @@ impl UndoManager {
         self.redo_stack.clear();
     }
 
-    /// Get a reference to the undo stack (for cleanup operations)
-    pub fn undo_stack(&self) -> &Vec<UndoState> {
+    /// Keep only the actions for which `keep` returns true (on both stacks)
+    /// Used to drop history whose files no longer exist
+    pub fn retain(&mut self, mut keep: impl FnMut(&UndoAction) -> bool) {
+        self.undo_stack.retain(|entry| keep(&entry.action));
+        self.redo_stack.retain(|entry| keep(&entry.action));
+    }
+
+    /// Get a reference to the undo stack, oldest first
+    pub fn undo_stack(&self) -> &[UndoEntry] {
         &self.undo_stack
     }
 
-    /// Get a reference to the redo stack (for cleanup operations)
-    pub fn redo_stack(&self) -> &Vec<UndoState> {
+    /// Get a reference to the redo stack; the next redo is last
+    pub fn redo_stack(&self) -> &[UndoEntry] {
         &self.redo_stack
     }
 }
@@ -240,4 +486,143 @@ mod tests {
         assert!(!manager.can_undo());
         assert!(!manager.can_redo());
     }
+
+    #[test]
+    fn test_label_edit_shares_history_with_deletes() {
+        let mut manager = UndoManager::new();
+
+        manager.push_delete(create_test_undo_state("test1.jpg"));
+        manager.push_label_edit(LabelEditState {
+            image_path: PathBuf::from("/images/test2.jpg"),
+            label_path: PathBuf::from("/labels/test2.txt"),
+            previous_contents: None,
+            new_contents: "0 0.5 0.5 0.1 0.1\n".to_string(),
+        });
+        assert_eq!(manager.undo_count(), 2);
+
+        // The most recent action is undone first
+        assert!(matches!(manager.undo(), Some(UndoAction::LabelEdit(edit)) if edit.previous_contents.is_none()));
+        assert!(matches!(manager.undo(), Some(UndoAction::Delete(_))));
+        assert!(matches!(manager.redo(), Some(UndoAction::Delete(_))));
+        assert_eq!(manager.redo_count(), 1);
+    }
+
+    #[test]
+    fn test_peek_redo_describes_next_redo() {
+        let mut manager = UndoManager::new();
+        assert!(manager.peek_redo().is_none());
+
+        manager.push_delete(create_test_undo_state("test1.jpg"));
+        manager.push_delete(create_test_undo_state("test2.jpg"));
+        manager.undo();
+        manager.undo();
+
+        // The most recently undone action is redone first
+        assert_eq!(
+            manager.peek_redo().map(|action| action.description()),
+            Some("delete of test1.jpg".to_string())
+        );
+        assert_eq!(manager.redo_count(), 2);
+    }
+
+    #[test]
+    fn test_retain_drops_matching_actions_from_both_stacks() {
+        let mut manager = UndoManager::new();
+
+        manager.push_delete(create_test_undo_state("test1.jpg"));
+        manager.push_recycled("test2.jpg".to_string());
+        manager.push_delete(create_test_undo_state("test3.jpg"));
+        manager.undo();
+
+        manager.retain(|action| match action {
+            UndoAction::Delete(state) => state.image_filename != "test3.jpg",
+            _ => true,
+        });
+        assert_eq!(manager.undo_count(), 2);
+        assert_eq!(manager.redo_count(), 0);
+        assert!(matches!(manager.undo(), Some(UndoAction::Recycled(name)) if name == "test2.jpg"));
+    }
+
+    #[test]
+    fn test_integrity_cleanup_is_one_batch() {
+        let mut manager = UndoManager::new();
+        let files: Vec<CleanupFile> = ["a.png", "b.txt", "c.txt"]
+            .iter()
+            .map(|name| CleanupFile {
+                original_path: PathBuf::from(format!("/dataset/{}", name)),
+                temp_path: PathBuf::from(format!("/temp/{}", name)),
+            })
+            .collect();
+
+        manager.push_delete(create_test_undo_state("test1.jpg"));
+        manager.push_integrity_cleanup(files);
+        assert_eq!(manager.undo_count(), 2);
+
+        let undone = manager.undo();
+        assert!(matches!(&undone, Some(UndoAction::IntegrityCleanup(files)) if files.len() == 3));
+        assert_eq!(
+            undone.map(|action| action.description()),
+            Some("cleanup of 3 orphaned files".to_string())
+        );
+        assert_eq!(manager.undo_count(), 1);
+        assert!(matches!(manager.redo(), Some(UndoAction::IntegrityCleanup(_))));
+    }
+
+    #[test]
+    fn test_max_depth_evicts_oldest_actions() {
+        let mut manager = UndoManager::with_max_depth(2);
+        manager.push_delete(create_test_undo_state("test1.jpg"));
+        manager.push_delete(create_test_undo_state("test2.jpg"));
+        manager.push_delete(create_test_undo_state("test3.jpg"));
+
+        assert_eq!(manager.undo_count(), 2);
+        let evicted = manager.take_evicted();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(
+            evicted[0].parked_files(),
+            vec![
+                PathBuf::from("/temp/test1.jpg"),
+                PathBuf::from("/temp/test1.jpg.txt")
+            ]
+        );
+        assert!(manager.take_evicted().is_empty());
+
+        // Shrinking the depth evicts again; the newest action stays
+        manager.set_max_depth(1);
+        assert_eq!(manager.take_evicted().len(), 1);
+        assert_eq!(
+            manager.peek_undo().map(|action| action.description()),
+            Some("delete of test3.jpg".to_string())
+        );
+    }
+
+    #[test]
+    fn test_undo_out_of_order_only_for_independent_actions() {
+        let mut manager = UndoManager::new();
+        manager.push_delete(create_test_undo_state("test1.jpg"));
+        manager.push_label_edit(LabelEditState {
+            image_path: PathBuf::from("/images/test2.jpg"),
+            label_path: PathBuf::from("/labels/test2.jpg.txt"),
+            previous_contents: None,
+            new_contents: String::new(),
+        });
+        manager.push_delete(create_test_undo_state("test2.jpg"));
+        manager.push_recycled("test3.jpg".to_string());
+
+        // The label edit is followed by a delete of the same label file
+        assert!(manager.can_undo_out_of_order(0));
+        assert!(!manager.can_undo_out_of_order(1));
+        assert!(manager.can_undo_out_of_order(2));
+        assert!(!manager.can_undo_out_of_order(3));
+        assert!(!manager.can_undo_out_of_order(4));
+
+        let undone = manager.undo_at(0);
+        assert!(matches!(undone, Some(UndoAction::Delete(state)) if state.image_filename == "test1.jpg"));
+        assert_eq!(manager.undo_count(), 3);
+        assert_eq!(manager.undo_stack()[0].action.kind(), "Label edit");
+        assert_eq!(
+            manager.peek_redo().map(|action| action.description()),
+            Some("delete of test1.jpg".to_string())
+        );
+    }
 }
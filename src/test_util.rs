@@ -0,0 +1,39 @@
+//! Helpers shared by the unit tests.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Directories created by this test process so far, for unique names
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Empty scratch directory under the system temp folder, removed on drop
+///
+/// The name combines `prefix`, the process id and a per-process counter, so
+/// tests running in parallel (or in several test processes) never share one.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Create a fresh directory named after `prefix`
+    pub fn new(prefix: &str) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("{}_{}_{}", prefix, std::process::id(), id));
+        // Left over from an earlier run that reused this process id
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    /// Path of the directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -1,18 +1,12 @@
 use crate::app::DatasetCleanerApp;
 use crate::core::analysis::{
-    get_recommendations, ImageCategory, RebalanceConfig, SelectionStrategy, TargetRatios,
+    calculate_ct_t_fix, get_recommendations, BalanceStats, DuplicateContent, GOOD_DISTRIBUTION_ENTROPY, MAX_DISTRIBUTION_ENTROPY, ImageCategory, IntegrityIssueType,
+    RebalanceConfig, SelectionStrategy, TargetRatios,
 };
-use crate::core::dataset::DatasetSplit;
+use crate::core::dataset::{split_exists, DatasetSplit, DatasetTargets, LayoutKind, TARGETS_FILE};
+use crate::ui::distribution_bars::render_target_bars;
 use eframe::egui;
 
-/// State for the balance dialog tabs
-#[derive(Default, Clone, Copy, PartialEq)]
-pub enum BalanceDialogTab {
-    #[default]
-    Balance,
-    Integrity,
-}
-
 /// Render the balance analysis dialog
 pub fn render_balance_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     if !app.balance.show_dialog {
@@ -20,7 +14,10 @@ pub fn render_balance_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     }
 
     let mut show_dialog = app.balance.show_dialog;
-    let needs_repaint = app.balance.analyzing || app.integrity.analyzing;
+    let needs_repaint = app.balance.analyzing
+        || app.balance.all_splits_analyzing
+        || app.balance.global_stats_receiver.is_some()
+        || app.integrity.analyzing;
     
     // Get screen center for initial position
     let screen_rect = ctx.screen_rect();
@@ -47,6 +44,16 @@ pub fn render_balance_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                     0,
                     egui::RichText::new("📊 Balance Analysis").size(14.0),
                 );
+                // A flat dataset has nothing to compare across splits
+                if app.dataset.layout() == LayoutKind::Split {
+                    ui.selectable_value(
+                        &mut app.balance.current_tab,
+                        2,
+                        egui::RichText::new("🗂 All Splits").size(14.0),
+                    );
+                } else if app.balance.current_tab == 2 {
+                    app.balance.current_tab = 0;
+                }
                 ui.selectable_value(
                     &mut app.balance.current_tab,
                     1,
@@ -60,6 +67,7 @@ pub fn render_balance_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
             match app.balance.current_tab {
                 0 => render_balance_tab(app, ui),
                 1 => render_integrity_tab(app, ui),
+                2 => render_all_splits_tab(app, ui),
                 _ => {}
             }
             
@@ -74,6 +82,7 @@ pub fn render_balance_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     if !show_dialog {
         app.balance.show_dialog = false;
         app.balance.results = None;
+        app.balance.all_splits_results = None;
         app.rebalance.error_message = None;
     }
 
@@ -83,6 +92,103 @@ pub fn render_balance_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     }
 }
 
+/// Render the editor for the dataset's category and split targets
+fn render_targets_editor(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
+    let Some(mut targets) = app.balance.targets_edit else {
+        let response = ui.add_enabled(app.dataset.dataset_path().is_some(), egui::Button::new("✏ Edit targets"));
+        if response.on_hover_text(format!("Saved per dataset in {}", TARGETS_FILE)).clicked() {
+            app.balance.targets_edit = Some(app.dataset_targets());
+            app.balance.targets_error = None;
+        }
+        return;
+    };
+
+    let percent = |ui: &mut egui::Ui, label: &str, ratio: &mut f32| {
+        ui.label(label);
+        let mut value = *ratio * 100.0;
+        if ui
+            .add(egui::DragValue::new(&mut value).range(0.0..=100.0).speed(0.5).suffix("%"))
+            .changed()
+        {
+            *ratio = value / 100.0;
+        }
+    };
+
+    egui::Grid::new("targets_editor").num_columns(6).spacing([6.0, 4.0]).show(ui, |ui| {
+        percent(ui, "Player", &mut targets.player_ratio);
+        percent(ui, "Background", &mut targets.background_ratio);
+        percent(ui, "Hard case", &mut targets.hardcase_ratio);
+        ui.end_row();
+        percent(ui, "Train", &mut targets.train_ratio);
+        percent(ui, "Val", &mut targets.val_ratio);
+        percent(ui, "Test", &mut targets.test_ratio);
+        ui.end_row();
+    });
+
+    let validation = targets.validate();
+    if let Err(e) = &validation {
+        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), format!("⚠ {}", e));
+    }
+    if let Some(e) = &app.balance.targets_error {
+        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), format!("❌ {}", e));
+    }
+
+    let mut editing = true;
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(validation.is_ok(), egui::Button::new("💾 Save"))
+            .on_hover_text(format!("Saved per dataset in {}", TARGETS_FILE))
+            .clicked()
+        {
+            match app.set_dataset_targets(targets) {
+                Ok(()) => {
+                    editing = false;
+                    app.balance.targets_error = None;
+                }
+                Err(e) => app.balance.targets_error = Some(e),
+            }
+        }
+        if ui.button("↺ Reset to defaults").clicked() {
+            targets = DatasetTargets::default();
+        }
+        if ui.button("Cancel").clicked() {
+            editing = false;
+            app.balance.targets_error = None;
+        }
+    });
+
+    app.balance.targets_edit = editing.then_some(targets);
+}
+
+/// Render one row per analyzed split: image count and category shares
+fn render_per_split_breakdown(
+    ui: &mut egui::Ui,
+    split_results: &std::collections::HashMap<DatasetSplit, BalanceStats>,
+) {
+    egui::Grid::new("per_split_breakdown")
+        .num_columns(5)
+        .striped(true)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            for header in ["Split", "Images", "Players", "Backgrounds", "Hard cases"] {
+                ui.label(egui::RichText::new(header).strong());
+            }
+            ui.end_row();
+
+            for split in DatasetSplit::all() {
+                let Some(stats) = split_results.get(&split) else {
+                    continue;
+                };
+                ui.label(split.as_str().to_uppercase());
+                ui.label(stats.total_images.to_string());
+                ui.label(format!("{:.1}%", stats.player_percentage()));
+                ui.label(format!("{:.1}%", stats.get_percentage(ImageCategory::Background)));
+                ui.label(format!("{:.1}%", stats.get_percentage(ImageCategory::HardCase)));
+                ui.end_row();
+            }
+        });
+}
+
 /// Render the Balance Analysis tab
 fn render_balance_tab(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
     if app.balance.analyzing {
@@ -94,24 +200,18 @@ fn render_balance_tab(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
         ui.vertical_centered(|ui| {
             ui.add_space(10.0);
             
-            // Split selector
-            ui.horizontal(|ui| {
-                ui.label("Analyze split:");
-                egui::ComboBox::from_id_salt("split_selector")
-                    .selected_text(match app.balance.selected_split_index {
-                        0 => "Train",
-                        1 => "Val",
-                        2 => "Test",
-                        3 => "All",
-                        _ => "Train",
-                    })
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut app.balance.selected_split_index, 0, "Train");
-                        ui.selectable_value(&mut app.balance.selected_split_index, 1, "Val");
-                        ui.selectable_value(&mut app.balance.selected_split_index, 2, "Test");
-                        ui.selectable_value(&mut app.balance.selected_split_index, 3, "All");
-                    });
-            });
+            // Split selector (a flat dataset only has its root images)
+            if app.dataset.layout() == LayoutKind::Flat {
+                app.balance.selected_split_index = 0;
+                ui.label("Flat layout: analyzing the images/ folder at the dataset root");
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label("Analyze split:");
+                    for (idx, name) in [(0, "Train"), (1, "Val"), (2, "Test"), (3, "All")] {
+                        ui.radio_value(&mut app.balance.selected_split_index, idx, name);
+                    }
+                });
+            }
             
             ui.add_space(15.0);
             
@@ -124,9 +224,9 @@ fn render_balance_tab(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
                     
                     ui.horizontal(|ui| {
                         ui.label(egui::RichText::new("📂 Split Size:").size(11.0));
-                        ui.label(egui::RichText::new("Train 70%").size(10.0).color(egui::Color32::from_rgb(100, 200, 255)));
-                        ui.label(egui::RichText::new("/ Val 20%").size(10.0).color(egui::Color32::from_rgb(100, 255, 100)));
-                        ui.label(egui::RichText::new("/ Test 10%").size(10.0).color(egui::Color32::from_rgb(255, 200, 100)));
+                        ui.label(egui::RichText::new(format!("Train {:.0}%", app.config.target_train_ratio * 100.0)).size(10.0).color(egui::Color32::from_rgb(100, 200, 255)));
+                        ui.label(egui::RichText::new(format!("/ Val {:.0}%", app.config.target_val_ratio * 100.0)).size(10.0).color(egui::Color32::from_rgb(100, 255, 100)));
+                        ui.label(egui::RichText::new(format!("/ Test {:.0}%", app.config.target_test_ratio * 100.0)).size(10.0).color(egui::Color32::from_rgb(255, 200, 100)));
                     });
                     
                     ui.horizontal(|ui| {
@@ -142,11 +242,15 @@ fn render_balance_tab(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
                     ui.horizontal(|ui| {
                         ui.label(egui::RichText::new("🎯 Category:").size(11.0));
                         ui.label(egui::RichText::new(format!(
-                            "Player {:.0}% / Background {:.0}%",
+                            "Player {:.0}% / Background {:.0}% / Hard case {:.0}%",
                             app.config.target_player_ratio * 100.0,
-                            app.config.target_background_ratio * 100.0
+                            app.config.target_background_ratio * 100.0,
+                            app.config.target_hardcase_ratio * 100.0
                         )).size(10.0).color(egui::Color32::from_rgb(150, 255, 150)));
                     });
+
+                    ui.add_space(5.0);
+                    render_targets_editor(app, ui);
                 });
             });
             
@@ -155,6 +259,15 @@ fn render_balance_tab(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
             if ui.button(egui::RichText::new("🔄 Start Balance Analysis").size(14.0)).clicked() {
                 app.analyze_balance_for_split(app.balance.selected_split_index);
             }
+            if app.dataset.layout() != LayoutKind::Flat
+                && ui
+                    .button(egui::RichText::new("📊 Analyze All Splits").size(14.0))
+                    .on_hover_text("Scan train, val and test one after another and compare them side by side")
+                    .clicked()
+            {
+                app.balance.current_tab = 2;
+                app.analyze_balance_all_splits();
+            }
             
             ui.add_space(10.0);
         });
@@ -162,7 +275,7 @@ fn render_balance_tab(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
 }
 
 /// Render analyzing state with progress bar
-fn render_analyzing_state(app: &DatasetCleanerApp, ui: &mut egui::Ui) {
+fn render_analyzing_state(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
     ui.heading("Analyzing dataset...");
     ui.add_space(10.0);
     
@@ -173,6 +286,10 @@ fn render_analyzing_state(app: &DatasetCleanerApp, ui: &mut egui::Ui) {
             app.balance.current_progress,
             app.balance.total_images
         )));
+        let rate = app.balance.rate.label(app.balance.current_progress, app.balance.total_images, "images");
+        if !rate.is_empty() {
+            ui.label(egui::RichText::new(rate).small().weak());
+        }
     } else {
         ui.spinner();
     }
@@ -186,6 +303,11 @@ fn render_analyzing_state(app: &DatasetCleanerApp, ui: &mut egui::Ui) {
         ui.label(format!("  • Player images: {}", stats.total_player_images()));
         ui.label(format!("  • Background: {}", stats.background));
     }
+
+    ui.add_space(10.0);
+    if ui.button("❌ Cancel").clicked() {
+        app.cancel_balance_analysis();
+    }
 }
 
 /// Render balance results with all sections
@@ -196,11 +318,7 @@ fn render_balance_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
         None => return,
     };
     
-    let target_ratios = TargetRatios {
-        player_ratio: app.config.target_player_ratio,
-        background_ratio: app.config.target_background_ratio,
-        hardcase_ratio: app.config.target_hardcase_ratio,
-    };
+    let target_ratios = app.target_ratios();
 
     // Show which split was analyzed with re-analyze button
     let split_name = match app.balance.selected_split_index {
@@ -211,29 +329,78 @@ fn render_balance_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
         _ => "TRAIN",
     };
 
+    // The CT/T auto-fix moves images out of the current split, so it needs that split's stats
+    let analyzed_split = match app.balance.selected_split_index {
+        0 => Some(DatasetSplit::Train),
+        1 => Some(DatasetSplit::Val),
+        2 => Some(DatasetSplit::Test),
+        _ => None,
+    };
+    let is_flat = app.dataset.layout() == LayoutKind::Flat;
+    let can_fix_ct_t = !is_flat && analyzed_split == Some(app.dataset.current_split());
+
     egui::ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
         // Current Distribution Section
         let header_text = format!("📊 Current Distribution ({})", split_name);
+        let mut fix_ct_t = false;
         egui::CollapsingHeader::new(
             egui::RichText::new(header_text).strong().size(15.0)
         )
         .default_open(true)
         .show(ui, |ui| {
-            render_distribution_section(ui, &stats);
+            fix_ct_t = render_distribution_section(ui, &stats, can_fix_ct_t);
+
+            // Current share of each category against its target
+            ui.add_space(8.0);
+            render_target_bars(ui, &stats, &target_ratios, &app.config);
+
+            // "All" reports the combined stats; break them down per split
+            if app.balance.selected_split_index == 3 && !app.balance.split_results.is_empty() {
+                ui.add_space(10.0);
+                render_per_split_breakdown(ui, &app.balance.split_results);
+            }
             
             // Re-analyze with different split
             ui.add_space(10.0);
-            ui.horizontal(|ui| {
-                ui.label("Analyze different split:");
-                for (idx, name) in [(0, "Train"), (1, "Val"), (2, "Test"), (3, "All")] {
-                    if ui.small_button(name).clicked() {
-                        app.balance.results = None; // Clear results to show start screen
-                        app.balance.selected_split_index = idx;
+            if !is_flat {
+                ui.horizontal(|ui| {
+                    ui.label("Analyze different split:");
+                    for (idx, name) in [(0, "Train"), (1, "Val"), (2, "Test"), (3, "All")] {
+                        if ui.small_button(name).clicked() {
+                            app.balance.results = None; // Clear results to show start screen
+                            app.balance.selected_split_index = idx;
+                        }
                     }
-                }
-            });
+                });
+            }
         });
 
+        if fix_ct_t {
+            if let Some((category, count)) = calculate_ct_t_fix(&stats) {
+                let current_split = app.dataset.current_split();
+                let destination_split = app
+                    .balance
+                    .cached_best_player_dest
+                    .map(|(dest, _)| dest)
+                    .unwrap_or(match current_split {
+                        DatasetSplit::Train => DatasetSplit::Val,
+                        DatasetSplit::Val => DatasetSplit::Train,
+                        DatasetSplit::Test => DatasetSplit::Train,
+                    });
+                app.calculate_rebalance_plan(RebalanceConfig {
+                    target_ratios: target_ratios.clone(),
+                    selection_strategy: app.rebalance.selection_strategy,
+                    preserve_ct_t_balance: false,
+                    source_split: current_split,
+                    destination_split,
+                    category,
+                    move_count: Some(count),
+                    target_ct_t_ratio: app.config.target_ct_t_ratio,
+                    ..RebalanceConfig::default()
+                });
+            }
+        }
+
         ui.add_space(10.0);
 
         // Target Distribution Section
@@ -261,6 +428,15 @@ fn render_balance_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
 
         ui.add_space(10.0);
 
+        // Rebalancing moves images between split folders
+        if is_flat {
+            ui.label(
+                egui::RichText::new("🔄 Rebalancing needs train/val/test folders; this dataset has a flat images/ + labels/ layout.")
+                    .color(egui::Color32::GRAY),
+            );
+            return;
+        }
+
         // Auto-Rebalance Section
         let current_split = app.dataset.current_split();
         let error_message = app.rebalance.error_message.clone();
@@ -275,6 +451,11 @@ fn render_balance_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
             ImageCategory::CTOnly,
             &target_ratios,
         );
+        let hard_case_excess = crate::core::analysis::calculate_move_count(
+            &stats,
+            ImageCategory::HardCase,
+            &target_ratios,
+        );
 
         let mut pending_config: Option<RebalanceConfig> = None;
 
@@ -291,9 +472,35 @@ fn render_balance_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
                 current_split,
                 bg_excess,
                 player_excess,
+                hard_case_excess,
                 &mut pending_config,
             );
 
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                ui.label("CT/T target:");
+                let mut ct_pct = app.config.target_ct_t_ratio * 100.0;
+                if ui
+                    .add(egui::Slider::new(&mut ct_pct, 0.0..=100.0).suffix("% CT").step_by(1.0))
+                    .on_hover_text("CT share of the single-team images left in this split after moving players")
+                    .changed()
+                {
+                    app.config.target_ct_t_ratio = ct_pct / 100.0;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Pick images:");
+                egui::ComboBox::from_id_salt("rebalance_selection_strategy")
+                    .selected_text(app.rebalance.selection_strategy.as_str())
+                    .show_ui(ui, |ui| {
+                        for strategy in SelectionStrategy::all() {
+                            ui.selectable_value(&mut app.rebalance.selection_strategy, strategy, strategy.as_str());
+                        }
+                    })
+                    .response
+                    .on_hover_text("Which images of a category move first; Balance All Splits applies it after category and location");
+            });
+
             if let Some(error) = &error_message {
                 ui.add_space(5.0);
                 ui.colored_label(egui::Color32::from_rgb(255, 200, 100), error);
@@ -318,7 +525,12 @@ fn render_balance_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
 }
 
 /// Render the distribution section
-fn render_distribution_section(ui: &mut egui::Ui, stats: &crate::core::analysis::BalanceStats) {
+/// Returns true when the CT/T balance bar was clicked to request an auto-fix
+fn render_distribution_section(
+    ui: &mut egui::Ui,
+    stats: &crate::core::analysis::BalanceStats,
+    can_fix_ct_t: bool,
+) -> bool {
     ui.label(format!("📂 Total Images: {}", stats.total_images));
     ui.add_space(5.0);
 
@@ -343,6 +555,9 @@ fn render_distribution_section(ui: &mut egui::Ui, stats: &crate::core::analysis:
         ui.label(format!("• Multiple Players: {} ({:.1}%)", multi_count, multi_pct));
     });
 
+    ui.add_space(5.0);
+    let fix_ct_t = render_ct_t_balance_bar(ui, stats, can_fix_ct_t);
+
     ui.add_space(5.0);
 
     let bg_count = stats.get_count(ImageCategory::Background);
@@ -359,9 +574,40 @@ fn render_distribution_section(ui: &mut egui::Ui, stats: &crate::core::analysis:
         ui.label(
             egui::RichText::new(format!("⚠ Hard Cases: {} ({:.1}%)", hc_count, hc_pct))
                 .color(egui::Color32::from_rgb(255, 200, 0)),
-        );
+        )
+        .on_hover_text("Labels without CT or T whose classes are all in the class table (Settings → Classes)");
+    }
+
+    let unknown_count = stats.get_count(ImageCategory::Unknown);
+    if unknown_count > 0 {
+        let unknown_pct = stats.get_percentage(ImageCategory::Unknown);
+        ui.add_space(5.0);
+        ui.label(
+            egui::RichText::new(format!("❓ Unknown Classes: {} ({:.1}%)", unknown_count, unknown_pct))
+                .color(egui::Color32::from_rgb(230, 200, 40)),
+        )
+        .on_hover_text("Labels without CT or T that use class IDs missing from the class table. Use the Unknown classes filter to review them.");
     }
 
+    let entropy = stats.entropy();
+    let entropy_color = if entropy >= GOOD_DISTRIBUTION_ENTROPY {
+        egui::Color32::from_rgb(100, 200, 100)
+    } else {
+        egui::Color32::from_rgb(255, 200, 0)
+    };
+    ui.add_space(5.0);
+    ui.label(
+        egui::RichText::new(format!(
+            "📐 Distribution entropy: {:.2} / {:.2}",
+            entropy, MAX_DISTRIBUTION_ENTROPY
+        ))
+        .color(entropy_color),
+    )
+    .on_hover_text(format!(
+        "How evenly CT only, T only, multiple players, background and hard cases are spread. {:.2} is perfectly even, 0 is a single category; {:.1} or more is good.",
+        MAX_DISTRIBUTION_ENTROPY, GOOD_DISTRIBUTION_ENTROPY
+    ));
+
     // Location Distribution
     if !stats.location_counts.is_empty() {
         ui.add_space(8.0);
@@ -384,6 +630,69 @@ fn render_distribution_section(ui: &mut egui::Ui, stats: &crate::core::analysis:
             }
         });
     }
+
+    fix_ct_t
+}
+
+/// Render the CT/T balance bar with a 50% target marker
+///
+/// Returns true when the bar was clicked (only sensed when `can_fix` is set)
+fn render_ct_t_balance_bar(
+    ui: &mut egui::Ui,
+    stats: &crate::core::analysis::BalanceStats,
+    can_fix: bool,
+) -> bool {
+    let Some(ratio) = stats.ct_t_ratio() else {
+        return false;
+    };
+
+    let deviation = (ratio - 0.5).abs();
+    let bar_color = if deviation <= 0.05 {
+        egui::Color32::from_rgb(100, 200, 100)
+    } else if deviation <= 0.10 {
+        egui::Color32::from_rgb(255, 200, 0)
+    } else {
+        egui::Color32::from_rgb(255, 100, 100)
+    };
+
+    ui.label("⚖ CT/T Balance");
+
+    let sense = if can_fix { egui::Sense::click() } else { egui::Sense::hover() };
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(ui.available_width().min(300.0), 18.0), sense);
+    let painter = ui.painter();
+
+    // Background track and filled CT share
+    painter.rect_filled(rect, 3.0, egui::Color32::from_gray(60));
+    let fill_rect = egui::Rect::from_min_size(
+        rect.min,
+        egui::vec2(rect.width() * ratio, rect.height()),
+    );
+    painter.rect_filled(fill_rect, 3.0, bar_color);
+
+    // Target marker at 50%
+    let target_x = rect.center().x;
+    painter.line_segment(
+        [egui::pos2(target_x, rect.min.y - 2.0), egui::pos2(target_x, rect.max.y + 2.0)],
+        egui::Stroke::new(2.0, egui::Color32::WHITE),
+    );
+
+    painter.text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        format!("CT {:.0}% / T {:.0}%", ratio * 100.0, (1.0 - ratio) * 100.0),
+        egui::FontId::proportional(11.0),
+        egui::Color32::WHITE,
+    );
+
+    let hover_text = if !can_fix {
+        "Analyze the current split to auto-fix the CT/T balance"
+    } else if calculate_ct_t_fix(stats).is_some() {
+        "Click to plan a rebalance that restores a 50/50 CT/T split"
+    } else {
+        "CT and T images are perfectly balanced"
+    };
+    response.on_hover_text(hover_text).clicked()
 }
 
 /// Render target distribution section
@@ -398,6 +707,7 @@ fn render_target_section(ui: &mut egui::Ui, app: &DatasetCleanerApp) {
 }
 
 /// Render auto-rebalance section
+#[allow(clippy::too_many_arguments)]
 fn render_rebalance_section(
     ui: &mut egui::Ui,
     app: &DatasetCleanerApp,
@@ -406,6 +716,7 @@ fn render_rebalance_section(
     current_split: DatasetSplit,
     bg_excess: i32,
     player_excess: i32,
+    hard_case_excess: i32,
     pending_config: &mut Option<RebalanceConfig>,
 ) {
     ui.horizontal(|ui| {
@@ -429,6 +740,10 @@ fn render_rebalance_section(
     let current_player_pct = stats.player_percentage();
     let ideal_player_count = (stats.total_images as f32 * target_ratios.player_ratio) as usize;
 
+    let target_hc_pct = target_ratios.hardcase_ratio * 100.0;
+    let current_hc_pct = stats.hard_case_ratio() * 100.0;
+    let ideal_hc_count = (stats.total_images as f32 * target_ratios.hardcase_ratio) as usize;
+
     egui::Grid::new("rebalance_comparison")
         .num_columns(4)
         .spacing([10.0, 4.0])
@@ -450,6 +765,12 @@ fn render_rebalance_section(
             ui.label(format!("{} ({:.0}%)", ideal_player_count, target_player_pct));
             render_excess_label(ui, player_excess);
             ui.end_row();
+
+            ui.label("⚠ Hard Cases");
+            ui.label(format!("{} ({:.1}%)", stats.hard_case, current_hc_pct));
+            ui.label(format!("{} ({:.0}%)", ideal_hc_count, target_hc_pct));
+            render_excess_label(ui, hard_case_excess);
+            ui.end_row();
         });
 
     ui.add_space(5.0);
@@ -477,11 +798,14 @@ fn render_rebalance_section(
         )).clicked() {
             *pending_config = Some(RebalanceConfig {
                 target_ratios: target_ratios.clone(),
-                selection_strategy: SelectionStrategy::Random,
+                selection_strategy: app.rebalance.selection_strategy,
                 preserve_ct_t_balance: true,
                 source_split: current_split,
                 destination_split: dest_split,
                 category: ImageCategory::Background,
+                move_count: None,
+                target_ct_t_ratio: app.config.target_ct_t_ratio,
+                ..RebalanceConfig::default()
             });
         }
     }
@@ -507,16 +831,52 @@ fn render_rebalance_section(
         )).clicked() {
             *pending_config = Some(RebalanceConfig {
                 target_ratios: target_ratios.clone(),
-                selection_strategy: SelectionStrategy::Random,
+                selection_strategy: app.rebalance.selection_strategy,
                 preserve_ct_t_balance: true,
                 source_split: current_split,
                 destination_split: dest_split,
                 category: ImageCategory::CTOnly,
+                move_count: None,
+                target_ct_t_ratio: app.config.target_ct_t_ratio,
+                ..RebalanceConfig::default()
+            });
+        }
+    }
+
+    if hard_case_excess > 0 {
+        // Get best destination: cached value or first available split
+        let (dest_split, to_move) = if let Some((best_dest, dest_needs)) = app.balance.cached_best_hard_case_dest {
+            (best_dest, hard_case_excess.min(dest_needs) as usize)
+        } else {
+            // Default to first available split (not current)
+            let default_dest = match current_split {
+                DatasetSplit::Train => DatasetSplit::Val,
+                DatasetSplit::Val => DatasetSplit::Train,
+                DatasetSplit::Test => DatasetSplit::Train,
+            };
+            (default_dest, hard_case_excess as usize)
+        };
+        
+        if ui.button(format!(
+            "Move {} hard cases → {}", 
+            to_move, 
+            dest_split.as_str().to_uppercase()
+        )).clicked() {
+            *pending_config = Some(RebalanceConfig {
+                target_ratios: target_ratios.clone(),
+                selection_strategy: app.rebalance.selection_strategy,
+                preserve_ct_t_balance: false,
+                source_split: current_split,
+                destination_split: dest_split,
+                category: ImageCategory::HardCase,
+                move_count: None,
+                target_ct_t_ratio: app.config.target_ct_t_ratio,
+                ..RebalanceConfig::default()
             });
         }
     }
 
-    if bg_excess <= 0 && player_excess <= 0 {
+    if bg_excess <= 0 && player_excess <= 0 && hard_case_excess <= 0 {
         ui.colored_label(
             egui::Color32::from_rgb(100, 200, 100),
             "✓ No excess images - this split is balanced!",
@@ -546,21 +906,336 @@ fn render_excess_label(ui: &mut egui::Ui, excess: i32) {
 
 /// Render global balance section
 fn render_global_balance_section(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
+    let missing: Vec<DatasetSplit> = app
+        .dataset
+        .dataset_path()
+        .map(|root| {
+            DatasetSplit::all()
+                .into_iter()
+                .filter(|&split| !split_exists(root, split))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Compact per-split summary from the cached folder counts (no analysis needed)
+    let summary = DatasetSplit::all()
+        .iter()
+        .map(|&split| {
+            if missing.contains(&split) {
+                format!("{} not present", split.as_str().to_uppercase())
+            } else {
+                format!(
+                    "{} {}",
+                    split.as_str().to_uppercase(),
+                    app.dataset.image_count_for_split(split)
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" / ");
+    ui.label(egui::RichText::new(summary).monospace().strong());
+    ui.add_space(5.0);
+
     ui.label("Automatically balance your dataset across all splits:");
     ui.add_space(5.0);
 
+    // Split size sliders, scaled back to 100% once a slider is released
+    let mut edited = app.global_split_ratios();
+    let mut changed = false;
+    let mut released = false;
+    ui.horizontal(|ui| {
+        for (name, ratio) in [("Train", &mut edited.train), ("Val", &mut edited.val), ("Test", &mut edited.test)] {
+            ui.label(name);
+            let mut percent = *ratio * 100.0;
+            let response = ui.add(
+                egui::Slider::new(&mut percent, 0.0..=100.0)
+                    .suffix("%")
+                    .fixed_decimals(0),
+            );
+            if response.changed() {
+                *ratio = percent / 100.0;
+                changed = true;
+            }
+            released |= response.drag_stopped() || (response.changed() && !response.dragged());
+        }
+        if app.rebalance.split_ratios.is_some()
+            && ui.small_button("↺").on_hover_text("Back to the dataset's split targets").clicked()
+        {
+            app.rebalance.split_ratios = None;
+        }
+    });
+    if released {
+        edited.normalize();
+    }
+    if changed || released {
+        app.rebalance.split_ratios = Some(edited);
+    }
+    let split_ratios = app.global_split_ratios();
+    let valid = split_ratios.is_valid();
+
+    // Missing splits get no share unless they may be created
+    let mut ratios = if app.rebalance.create_missing_splits {
+        split_ratios.clone()
+    } else {
+        split_ratios.renormalized(&missing)
+    };
+    ratios.normalize();
     ui.horizontal(|ui| {
         ui.label(egui::RichText::new("Target:").size(10.0).color(egui::Color32::GRAY));
-        ui.label(egui::RichText::new("Train 70%").size(10.0).color(egui::Color32::from_rgb(100, 200, 255)));
-        ui.label(egui::RichText::new("/ Val 20%").size(10.0).color(egui::Color32::from_rgb(100, 255, 100)));
-        ui.label(egui::RichText::new("/ Test 10%").size(10.0).color(egui::Color32::from_rgb(255, 200, 100)));
+        let names = [
+            ("Train", egui::Color32::from_rgb(100, 200, 255)),
+            ("Val", egui::Color32::from_rgb(100, 255, 100)),
+            ("Test", egui::Color32::from_rgb(255, 200, 100)),
+        ];
+        for (index, (split, (name, color))) in DatasetSplit::all().into_iter().zip(names).enumerate() {
+            let text = if ratios.get(split) > 0.0 {
+                format!("{} {:.0}%", name, ratios.get(split) * 100.0)
+            } else {
+                format!("{} —", name)
+            };
+            let text = if index == 0 { text } else { format!("/ {}", text) };
+            ui.label(egui::RichText::new(text).size(10.0).color(color));
+        }
     });
-    
+
+    if !missing.is_empty() {
+        let names: Vec<String> = missing.iter().map(|split| split.as_str().to_uppercase()).collect();
+        ui.checkbox(
+            &mut app.rebalance.create_missing_splits,
+            format!("Create missing splits ({})", names.join(", ")),
+        )
+        .on_hover_text("Move images into splits that have no folder yet. Otherwise their share is spread over the existing splits.");
+    }
+
     ui.add_space(5.0);
 
-    if ui.button("🔄 Balance All Splits").clicked() {
+    if !valid {
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 100, 100),
+            format!(
+                "⚠ Split sizes sum to {:.0}%, not 100%",
+                (split_ratios.train + split_ratios.val + split_ratios.test) * 100.0
+            ),
+        );
+    }
+
+    if ui
+        .add_enabled(valid, egui::Button::new("🔄 Balance All Splits"))
+        .on_disabled_hover_text("Split sizes must sum to 100%")
+        .clicked()
+    {
         app.calculate_global_rebalance();
     }
+
+    ui.add_space(10.0);
+    ui.separator();
+    render_swap_splits(app, ui);
+}
+
+/// Render the "Swap Splits" controls (two split pickers and a confirmation step)
+fn render_swap_splits(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
+    let (mut a, mut b) = app
+        .rebalance
+        .swap_pair
+        .unwrap_or((DatasetSplit::Train, DatasetSplit::Val));
+    let mut swap = false;
+
+    ui.label("Swap the contents of two splits (e.g. train and val assigned the wrong way round):");
+    ui.horizontal(|ui| {
+        for (id, split) in [("swap_split_a", &mut a), ("swap_split_b", &mut b)] {
+            egui::ComboBox::from_id_salt(id)
+                .selected_text(split.as_str().to_uppercase())
+                .show_ui(ui, |ui| {
+                    for option in DatasetSplit::all() {
+                        ui.selectable_value(split, option, option.as_str().to_uppercase());
+                    }
+                });
+            if id == "swap_split_a" {
+                ui.label("⇄");
+            }
+        }
+
+        if !app.rebalance.confirm_swap {
+            let read_only_hint = app.read_only_hint();
+            let response = ui.add_enabled(
+                a != b && read_only_hint.is_empty(),
+                egui::Button::new("🔀 Swap Splits"),
+            );
+            let hint = if a == b { "Pick two different splits".to_string() } else { read_only_hint };
+            if response.on_disabled_hover_text(hint).clicked() {
+                app.rebalance.confirm_swap = true;
+            }
+        }
+    });
+
+    if app.rebalance.confirm_swap {
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 200, 100),
+            format!(
+                "⚠️ Move all {} {} images to {} and all {} {} images to {}? This can be undone.",
+                app.dataset.image_count_for_split(a),
+                a.as_str().to_uppercase(),
+                b.as_str().to_uppercase(),
+                app.dataset.image_count_for_split(b),
+                b.as_str().to_uppercase(),
+                a.as_str().to_uppercase()
+            ),
+        );
+        ui.horizontal(|ui| {
+            if ui.button(egui::RichText::new("✓ Confirm Swap").color(egui::Color32::GREEN)).clicked() {
+                swap = true;
+            }
+            if ui.button("❌ Cancel").clicked() {
+                app.rebalance.confirm_swap = false;
+            }
+        });
+    }
+
+    // Changing either split asks for confirmation again
+    if app.rebalance.swap_pair.is_some_and(|pair| pair != (a, b)) {
+        app.rebalance.confirm_swap = false;
+    }
+    app.rebalance.swap_pair = Some((a, b));
+
+    if swap && a != b {
+        app.rebalance.confirm_swap = false;
+        app.swap_split_contents(a, b);
+    }
+}
+
+// =============================================================================
+// ALL SPLITS TAB
+// =============================================================================
+
+/// Category rows of the side-by-side split comparison
+const ALL_SPLITS_CATEGORIES: [(&str, ImageCategory); 6] = [
+    ("• CT Only", ImageCategory::CTOnly),
+    ("• T Only", ImageCategory::TOnly),
+    ("• Multiple Players", ImageCategory::MultiplePlayer),
+    ("🌄 Background", ImageCategory::Background),
+    ("⚠ Hard Cases", ImageCategory::HardCase),
+    ("❓ Unknown Classes", ImageCategory::Unknown),
+];
+
+/// Render the All Splits tab (train / val / test side by side)
+fn render_all_splits_tab(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
+    if app.balance.all_splits_analyzing {
+        ui.heading("Analyzing all splits...");
+        ui.add_space(10.0);
+
+        // One phase per split, scanned in order
+        let active = app.balance.all_splits_done.iter().position(|done| !done);
+        for (idx, split) in DatasetSplit::all().iter().enumerate() {
+            let (current, total) = app.balance.all_splits_progress[idx];
+            let done = app.balance.all_splits_done[idx];
+            let fraction = if done {
+                1.0
+            } else if total > 0 {
+                current as f32 / total as f32
+            } else {
+                0.0
+            };
+            let (color, text) = if done {
+                (egui::Color32::from_rgb(100, 200, 100), format!("✓ {} images", total))
+            } else if active == Some(idx) {
+                (egui::Color32::from_rgb(100, 150, 255), format!("{} / {} images", current, total))
+            } else {
+                (egui::Color32::DARK_GRAY, "Waiting".to_string())
+            };
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{}. {:<5}", idx + 1, split.as_str().to_uppercase()))
+                        .monospace()
+                        .color(if done { color } else { ui.visuals().text_color() }),
+                );
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .fill(color)
+                        .text(text)
+                        .animate(active == Some(idx)),
+                );
+            });
+        }
+
+        ui.add_space(10.0);
+        if ui.button("⏹ Cancel").clicked() {
+            app.cancel_all_splits_balance();
+        }
+        return;
+    }
+
+    let Some(stats) = app.balance.all_splits_results.clone() else {
+        ui.vertical_centered(|ui| {
+            ui.add_space(10.0);
+            ui.label("Analyze train, val and test one after another and compare their category mix.");
+            ui.add_space(15.0);
+            if ui
+                .button(egui::RichText::new("🔄 Analyze All Splits").size(14.0))
+                .clicked()
+            {
+                app.analyze_balance_all_splits();
+            }
+        });
+        return;
+    };
+
+    ui.label(egui::RichText::new("📈 Category Distribution by Split").strong().size(16.0));
+    ui.add_space(5.0);
+
+    ui.group(|ui| {
+        egui::Grid::new("all_splits_grid")
+            .num_columns(4)
+            .spacing([30.0, 6.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("");
+                for split in DatasetSplit::all() {
+                    ui.label(egui::RichText::new(split.as_str().to_uppercase()).strong());
+                }
+                ui.end_row();
+
+                ui.label("Total images");
+                for split in DatasetSplit::all() {
+                    if stats.is_present(split) {
+                        ui.label(egui::RichText::new(stats.get(split).total_images.to_string()).monospace());
+                    } else {
+                        ui.label(egui::RichText::new("not present").italics().color(egui::Color32::GRAY))
+                            .on_hover_text("This split has no images folder");
+                    }
+                }
+                ui.end_row();
+
+                for (label, category) in ALL_SPLITS_CATEGORIES {
+                    ui.label(label);
+                    for split in DatasetSplit::all() {
+                        if !stats.is_present(split) {
+                            ui.label(egui::RichText::new("—").color(egui::Color32::GRAY));
+                            continue;
+                        }
+                        let split_stats = stats.get(split);
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{:.1}% ({})",
+                                split_stats.get_percentage(category),
+                                split_stats.get_count(category)
+                            ))
+                            .monospace(),
+                        );
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+
+    ui.add_space(10.0);
+    ui.label(
+        egui::RichText::new(format!("{} images across all splits", stats.total_images()))
+            .color(egui::Color32::GRAY),
+    );
+    ui.add_space(5.0);
+    if ui.button("🔄 Re-analyze").clicked() {
+        app.analyze_balance_all_splits();
+    }
 }
 
 // =============================================================================
@@ -598,13 +1273,33 @@ fn render_integrity_start(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
             ui.vertical(|ui| {
                 ui.label("• Images without corresponding label files");
                 ui.label("• Label files without corresponding images");
+                ui.label("• Label files with nearly identical boxes");
+                ui.label("• Label files with lines that do not parse");
+                ui.label("• Images that cannot be read (optional)");
             });
         });
         
         ui.add_space(15.0);
-        
-        if ui.button(egui::RichText::new("🔄 Analyze Integrity").size(14.0)).clicked() {
-            app.analyze_integrity();
+
+        ui.horizontal(|ui| {
+            ui.label("Duplicate box IoU above:");
+            ui.add(egui::Slider::new(&mut app.integrity.overlap_threshold, 0.5..=0.99).fixed_decimals(2));
+        });
+        ui.checkbox(&mut app.integrity.check_images, "Check that images can be read");
+        if app.integrity.check_images {
+            ui.checkbox(&mut app.integrity.full_decode, "Decode every pixel (finds truncated files, slower)");
+        }
+        ui.checkbox(&mut app.integrity.all_splits, "All splits (train, val, test)");
+        if app.integrity.all_splits {
+            ui.checkbox(
+                &mut app.integrity.compare_duplicate_hashes,
+                "Compare contents of duplicate filenames (slower)",
+            );
+        }
+        ui.add_space(5.0);
+        
+        if ui.button(egui::RichText::new("🔄 Analyze Integrity").size(14.0)).clicked() {
+            app.analyze_integrity();
         }
         
         ui.add_space(20.0);
@@ -612,17 +1307,23 @@ fn render_integrity_start(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
 }
 
 /// Render integrity analyzing state
-fn render_integrity_analyzing(app: &DatasetCleanerApp, ui: &mut egui::Ui) {
+fn render_integrity_analyzing(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
     ui.heading("Scanning files...");
     ui.add_space(10.0);
     
     if app.integrity.total_files > 0 {
         let progress = app.integrity.current_progress as f32 / app.integrity.total_files as f32;
+        let unit = if app.integrity.results_all_splits { "splits" } else { "files" };
         ui.add(egui::ProgressBar::new(progress).text(format!(
-            "Scanned {} / {} files",
+            "Scanned {} / {} {}",
             app.integrity.current_progress,
-            app.integrity.total_files
+            app.integrity.total_files,
+            unit
         )));
+        let rate = app.integrity.rate.label(app.integrity.current_progress, app.integrity.total_files, unit);
+        if !rate.is_empty() {
+            ui.label(egui::RichText::new(rate).small().weak());
+        }
     } else {
         ui.spinner();
     }
@@ -636,19 +1337,33 @@ fn render_integrity_analyzing(app: &DatasetCleanerApp, ui: &mut egui::Ui) {
             stats.labels_without_images.len()
         ));
     }
+
+    ui.add_space(10.0);
+    if ui.button("❌ Cancel").clicked() {
+        app.cancel_integrity_analysis();
+    }
 }
 
 /// Render integrity results
 fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
     // Extract counts upfront to avoid borrowing issues
-    let (img_count, lbl_count, total_issues) = match &app.integrity.results {
-        Some(stats) => (
-            stats.images_without_labels.len(),
-            stats.labels_without_images.len(),
-            stats.total_issues(),
-        ),
-        None => return,
-    };
+    let (img_count, lbl_count, unknown_count, duplicate_count, empty_count, overlap_count, range_count, malformed_count, corrupt_count, orphan_count, total_issues) =
+        match &app.integrity.results {
+            Some(stats) => (
+                stats.images_without_labels.len(),
+                stats.labels_without_images.len(),
+                stats.labels_with_unknown_classes.len(),
+                stats.duplicates_across_splits.len(),
+                stats.empty_label_files.len(),
+                stats.overlapping_boxes.len(),
+                stats.out_of_range_boxes.len(),
+                stats.malformed_labels.len(),
+                stats.corrupt_images.len(),
+                stats.orphan_count(),
+                stats.total_issues(),
+            ),
+            None => return,
+        };
     
     // Summary cards
     ui.horizontal(|ui| {
@@ -691,11 +1406,33 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
                 );
             });
         });
+
+        ui.add_space(10.0);
+
+        // Labels with unknown classes card
+        let unknown_color = if unknown_count == 0 {
+            egui::Color32::from_rgb(100, 200, 100)
+        } else {
+            egui::Color32::from_rgb(255, 150, 100)
+        };
+
+        ui.group(|ui| {
+            ui.set_min_width(200.0);
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new("❓ Unknown Classes").strong());
+                ui.label(
+                    egui::RichText::new(format!("{}", unknown_count))
+                        .size(28.0)
+                        .color(unknown_color)
+                );
+            });
+        });
     });
 
     ui.add_space(10.0);
 
-    if total_issues == 0 {
+    // Empty labels are valid backgrounds, so they are listed without counting as issues
+    if total_issues == 0 && empty_count == 0 {
         ui.vertical_centered(|ui| {
             ui.add_space(20.0);
             ui.label(
@@ -726,14 +1463,71 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
         ).clicked() {
             app.integrity.current_tab = 1;
         }
+        if ui.selectable_label(
+            app.integrity.current_tab == 2,
+            format!("❓ Unknown Classes ({}) ", unknown_count)
+        ).clicked() {
+            app.integrity.current_tab = 2;
+        }
+        if ui.selectable_label(
+            app.integrity.current_tab == 3,
+            format!("👯 Duplicates ({}) ", duplicate_count)
+        ).clicked() {
+            app.integrity.current_tab = 3;
+        }
+        if ui.selectable_label(
+            app.integrity.current_tab == 4,
+            format!("📄 Empty Labels ({}) ", empty_count)
+        ).clicked() {
+            app.integrity.current_tab = 4;
+        }
+        if ui.selectable_label(
+            app.integrity.current_tab == 5,
+            format!("🔲 Overlapping Boxes ({}) ", overlap_count)
+        ).clicked() {
+            app.integrity.current_tab = 5;
+        }
+        if ui.selectable_label(
+            app.integrity.current_tab == 6,
+            format!("📐 Out of Range ({}) ", range_count)
+        ).clicked() {
+            app.integrity.current_tab = 6;
+        }
+        if ui.selectable_label(
+            app.integrity.current_tab == 7,
+            format!("🚫 Malformed ({}) ", malformed_count)
+        ).clicked() {
+            app.integrity.current_tab = 7;
+        }
+        if ui.selectable_label(
+            app.integrity.current_tab == 8,
+            format!("💥 Corrupt Images ({}) ", corrupt_count)
+        ).clicked() {
+            app.integrity.current_tab = 8;
+        }
     });
 
     ui.separator();
 
+    // Per-split select/delete requested from a split header (applied after the list)
+    let show_split_headers = app.integrity.results_all_splits;
+    let mut split_action: Option<(DatasetSplit, SplitIssueAction)> = None;
+    let mut delete_duplicate: Option<(usize, DatasetSplit)> = None;
+    let read_only_hint = app.read_only_hint();
+    let mut dedupe_request: Option<bool> = None;
+    let mut show_parse_errors = app.integrity.show_parse_errors;
+
     // Issue list - we need to access the actual vectors via app.integrity.results
     if let Some(ref results) = app.integrity.results {
         let images_issues = &results.images_without_labels;
         let labels_issues = &results.labels_without_images;
+        let unknown_issues = &results.labels_with_unknown_classes;
+        let duplicate_issues = &results.duplicates_across_splits;
+        let empty_issues = &results.empty_label_files;
+        let overlap_issues = &results.overlapping_boxes;
+        let range_issues = &results.out_of_range_boxes;
+        let malformed_issues = &results.malformed_labels;
+        let corrupt_issues = &results.corrupt_images;
         
         egui::ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
             match app.integrity.current_tab {
@@ -749,6 +1543,12 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
                         });
                     } else {
                         for (idx, issue) in images_issues.iter().enumerate() {
+                            if show_split_headers && (idx == 0 || images_issues[idx - 1].split != issue.split) {
+                                let count = images_issues.iter().filter(|i| i.split == issue.split).count();
+                                if let Some(action) = render_split_header(ui, issue.split, count, true, &read_only_hint) {
+                                    split_action = Some((issue.split, action));
+                                }
+                            }
                             let mut is_selected = app.integrity.selected_images_without_labels.contains(&idx);
                             ui.horizontal(|ui| {
                                 if ui.checkbox(&mut is_selected, "").clicked() {
@@ -779,6 +1579,12 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
                         });
                     } else {
                         for (idx, issue) in labels_issues.iter().enumerate() {
+                            if show_split_headers && (idx == 0 || labels_issues[idx - 1].split != issue.split) {
+                                let count = labels_issues.iter().filter(|i| i.split == issue.split).count();
+                                if let Some(action) = render_split_header(ui, issue.split, count, true, &read_only_hint) {
+                                    split_action = Some((issue.split, action));
+                                }
+                            }
                             let mut is_selected = app.integrity.selected_labels_without_images.contains(&idx);
                             ui.horizontal(|ui| {
                                 if ui.checkbox(&mut is_selected, "").clicked() {
@@ -797,10 +1603,356 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
                         }
                     }
                 }
+                2 => {
+                    if unknown_issues.is_empty() {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(20.0);
+                            ui.label(
+                                egui::RichText::new("✓ All classes are in the class table")
+                                    .color(egui::Color32::from_rgb(100, 200, 100))
+                            );
+                            ui.add_space(20.0);
+                        });
+                    } else {
+                        ui.label(
+                            egui::RichText::new("Add these class ids in ⚙ Settings to name and color them.")
+                                .small()
+                                .color(egui::Color32::GRAY)
+                        );
+                        for (idx, issue) in unknown_issues.iter().enumerate() {
+                            if show_split_headers && (idx == 0 || unknown_issues[idx - 1].split != issue.split) {
+                                let count = unknown_issues.iter().filter(|i| i.split == issue.split).count();
+                                render_split_header(ui, issue.split, count, false, &read_only_hint);
+                            }
+                            let ids: Vec<String> = issue.class_ids.iter().map(|id| id.to_string()).collect();
+                            ui.horizontal(|ui| {
+                                if let Some(filename) = issue.path.file_name() {
+                                    ui.label(filename.to_string_lossy().as_ref());
+                                } else {
+                                    ui.label(issue.path.display().to_string());
+                                }
+                                ui.label(
+                                    egui::RichText::new(format!("class {}", ids.join(", ")))
+                                        .color(egui::Color32::from_rgb(255, 150, 100))
+                                );
+                            });
+                        }
+                    }
+                }
+                3 => {
+                    if !show_split_headers {
+                        ui.label(
+                            egui::RichText::new("Tick \"All splits\" and re-analyze to detect filenames shared between splits.")
+                                .small()
+                                .color(egui::Color32::GRAY)
+                        );
+                    } else if duplicate_issues.is_empty() {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(20.0);
+                            ui.label(
+                                egui::RichText::new("✓ No filenames shared between splits")
+                                    .color(egui::Color32::from_rgb(100, 200, 100))
+                            );
+                            ui.add_space(20.0);
+                        });
+                    } else {
+                        ui.label(
+                            egui::RichText::new("Delete the copy from one split to keep the others.")
+                                .small()
+                                .color(egui::Color32::GRAY)
+                        );
+                        for (idx, issue) in duplicate_issues.iter().enumerate() {
+                            let color = match issue.content {
+                                DuplicateContent::Different => egui::Color32::from_rgb(255, 200, 100),
+                                _ => egui::Color32::from_rgb(255, 150, 100),
+                            };
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(&issue.stem).strong());
+                                ui.label(egui::RichText::new(issue.description()).color(color));
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add_space(20.0);
+                                for (split, _) in &issue.copies {
+                                    if ui
+                                        .add_enabled(
+                                            read_only_hint.is_empty(),
+                                            egui::Button::new(format!(
+                                                "🗑️ Delete from {}",
+                                                split.as_str().to_uppercase()
+                                            ))
+                                            .small(),
+                                        )
+                                        .on_disabled_hover_text(&read_only_hint)
+                                        .clicked()
+                                    {
+                                        delete_duplicate = Some((idx, *split));
+                                    }
+                                }
+                            });
+                        }
+                    }
+                }
+                4 => {
+                    if empty_issues.is_empty() {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(20.0);
+                            ui.label(
+                                egui::RichText::new("✓ No empty label files")
+                                    .color(egui::Color32::from_rgb(100, 200, 100))
+                            );
+                            ui.add_space(20.0);
+                        });
+                    } else {
+                        ui.label(
+                            egui::RichText::new("Empty labels are valid backgrounds but often mean the capture pipeline dropped the boxes. Confirm real backgrounds to stop listing them.")
+                                .small()
+                                .color(egui::Color32::GRAY)
+                        );
+                        for (idx, issue) in empty_issues.iter().enumerate() {
+                            if show_split_headers && (idx == 0 || empty_issues[idx - 1].split != issue.split) {
+                                let count = empty_issues.iter().filter(|i| i.split == issue.split).count();
+                                if let Some(action) = render_split_header(ui, issue.split, count, true, &read_only_hint) {
+                                    split_action = Some((issue.split, action));
+                                }
+                            }
+                            let mut is_selected = app.integrity.selected_empty_labels.contains(&idx);
+                            ui.horizontal(|ui| {
+                                if ui.checkbox(&mut is_selected, "").clicked() {
+                                    if is_selected {
+                                        app.integrity.selected_empty_labels.insert(idx);
+                                    } else {
+                                        app.integrity.selected_empty_labels.remove(&idx);
+                                    }
+                                }
+                                if let Some(filename) = issue.path.file_name() {
+                                    ui.label(filename.to_string_lossy().as_ref());
+                                } else {
+                                    ui.label(issue.path.display().to_string());
+                                }
+                            });
+                        }
+                    }
+                }
+                5 => {
+                    if overlap_issues.is_empty() {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(20.0);
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "✓ No boxes overlap above IoU {:.2}",
+                                    app.integrity.overlap_threshold
+                                ))
+                                .color(egui::Color32::from_rgb(100, 200, 100))
+                            );
+                            ui.add_space(20.0);
+                        });
+                    } else {
+                        ui.label(
+                            egui::RichText::new("Boxes are numbered in file order. The fix keeps the larger box of each pair, or the more confident one when both have a confidence.")
+                                .small()
+                                .color(egui::Color32::GRAY)
+                        );
+                        for (idx, issue) in overlap_issues.iter().enumerate() {
+                            if show_split_headers && (idx == 0 || overlap_issues[idx - 1].split != issue.split) {
+                                let count = overlap_issues.iter().filter(|i| i.split == issue.split).count();
+                                render_split_header(ui, issue.split, count, false, &read_only_hint);
+                            }
+                            let pairs: Vec<String> = issue
+                                .pairs
+                                .iter()
+                                .map(|pair| format!("#{} & #{} ({:.2})", pair.first + 1, pair.second + 1, pair.iou))
+                                .collect();
+                            ui.horizontal(|ui| {
+                                if let Some(filename) = issue.path.file_name() {
+                                    ui.label(filename.to_string_lossy().as_ref());
+                                } else {
+                                    ui.label(issue.path.display().to_string());
+                                }
+                                ui.label(
+                                    egui::RichText::new(pairs.join(", "))
+                                        .color(egui::Color32::from_rgb(255, 150, 100))
+                                );
+                            });
+                        }
+                    }
+
+                    // Dry-run result of the fix
+                    if let Some(preview) = &app.integrity.dedupe_preview {
+                        ui.add_space(5.0);
+                        ui.separator();
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Preview: {} boxes would be removed from {} label files",
+                                preview.boxes_removed, preview.files_modified
+                            ))
+                            .strong()
+                        );
+                        for change in &preview.changes {
+                            let boxes: Vec<String> = change
+                                .removed
+                                .iter()
+                                .map(|detection| detection.to_label_line())
+                                .collect();
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{}: {}",
+                                    change.label_path.file_name().unwrap_or_default().to_string_lossy(),
+                                    boxes.join(" | ")
+                                ))
+                                .small()
+                                .monospace()
+                            );
+                        }
+                    }
+                }
+                6 => {
+                    if range_issues.is_empty() {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(20.0);
+                            ui.label(
+                                egui::RichText::new("✓ Every box lies inside its image")
+                                    .color(egui::Color32::from_rgb(100, 200, 100))
+                            );
+                            ui.add_space(20.0);
+                        });
+                    } else {
+                        ui.label(
+                            egui::RichText::new("Coordinates outside 0..1 or boxes past the image border. The fix clamps each box to the image.")
+                                .small()
+                                .color(egui::Color32::GRAY)
+                        );
+                        for (idx, issue) in range_issues.iter().enumerate() {
+                            if show_split_headers && (idx == 0 || range_issues[idx - 1].split != issue.split) {
+                                let count = range_issues.iter().filter(|i| i.split == issue.split).count();
+                                render_split_header(ui, issue.split, count, false, &read_only_hint);
+                            }
+                            ui.horizontal(|ui| {
+                                if let Some(filename) = issue.path.file_name() {
+                                    ui.label(filename.to_string_lossy().as_ref());
+                                } else {
+                                    ui.label(issue.path.display().to_string());
+                                }
+                                ui.label(
+                                    egui::RichText::new(format!("{} box(es)", issue.detections))
+                                        .color(egui::Color32::from_rgb(255, 150, 100))
+                                );
+                            });
+                        }
+                    }
+                }
+                7 => {
+                    if malformed_issues.is_empty() {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(20.0);
+                            ui.label(
+                                egui::RichText::new("✓ Every label file parses")
+                                    .color(egui::Color32::from_rgb(100, 200, 100))
+                            );
+                            ui.add_space(20.0);
+                        });
+                    } else {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new("Lines that do not parse are ignored everywhere else; fix them in the Raw label editor.")
+                                    .small()
+                                    .color(egui::Color32::GRAY)
+                            );
+                            ui.checkbox(&mut show_parse_errors, "Show errors");
+                        });
+                        for (idx, issue) in malformed_issues.iter().enumerate() {
+                            if show_split_headers && (idx == 0 || malformed_issues[idx - 1].split != issue.split) {
+                                let count = malformed_issues.iter().filter(|i| i.split == issue.split).count();
+                                render_split_header(ui, issue.split, count, false, &read_only_hint);
+                            }
+                            ui.horizontal(|ui| {
+                                if let Some(filename) = issue.path.file_name() {
+                                    ui.label(filename.to_string_lossy().as_ref());
+                                } else {
+                                    ui.label(issue.path.display().to_string());
+                                }
+                                if show_parse_errors {
+                                    ui.label(
+                                        egui::RichText::new(issue.error.to_string())
+                                            .monospace()
+                                            .color(egui::Color32::from_rgb(255, 150, 100))
+                                    );
+                                }
+                            });
+                        }
+                    }
+                }
+                8 => {
+                    if corrupt_issues.is_empty() {
+                        let message = if app.integrity.check_images {
+                            "✓ Every image can be read"
+                        } else {
+                            "Tick \"Check that images can be read\" and re-analyze to find corrupt images."
+                        };
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(20.0);
+                            ui.label(
+                                egui::RichText::new(message)
+                                    .color(egui::Color32::from_rgb(100, 200, 100))
+                            );
+                            ui.add_space(20.0);
+                        });
+                    } else {
+                        ui.label(
+                            egui::RichText::new("Deleting a corrupt image also deletes its label.")
+                                .small()
+                                .color(egui::Color32::GRAY)
+                        );
+                        for (idx, issue) in corrupt_issues.iter().enumerate() {
+                            if show_split_headers && (idx == 0 || corrupt_issues[idx - 1].split != issue.split) {
+                                let count = corrupt_issues.iter().filter(|i| i.split == issue.split).count();
+                                if let Some(action) = render_split_header(ui, issue.split, count, true, &read_only_hint) {
+                                    split_action = Some((issue.split, action));
+                                }
+                            }
+                            let mut is_selected = app.integrity.selected_corrupt_images.contains(&idx);
+                            ui.horizontal(|ui| {
+                                if ui.checkbox(&mut is_selected, "").clicked() {
+                                    if is_selected {
+                                        app.integrity.selected_corrupt_images.insert(idx);
+                                    } else {
+                                        app.integrity.selected_corrupt_images.remove(&idx);
+                                    }
+                                }
+                                if let Some(filename) = issue.path.file_name() {
+                                    ui.label(filename.to_string_lossy().as_ref());
+                                } else {
+                                    ui.label(issue.path.display().to_string());
+                                }
+                                if let IntegrityIssueType::CorruptImage { error } = &issue.issue_type {
+                                    ui.label(
+                                        egui::RichText::new(error)
+                                            .monospace()
+                                            .color(egui::Color32::from_rgb(255, 150, 100))
+                                    );
+                                }
+                            });
+                        }
+                    }
+                }
                 _ => {}
             }
         });
     }
+    app.integrity.show_parse_errors = show_parse_errors;
+
+    if let Some((idx, split)) = delete_duplicate {
+        app.delete_duplicate_copy(idx, split);
+        return;
+    }
+
+    match split_action {
+        Some((split, SplitIssueAction::Select)) => app.integrity.select_split(split),
+        Some((split, SplitIssueAction::Delete)) => {
+            app.delete_integrity_issues_in_split(split);
+            return;
+        }
+        None => {}
+    }
 
     ui.add_space(10.0);
 
@@ -809,8 +1961,12 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
     let current_issues = match app.integrity.current_tab {
         0 => img_count,
         1 => lbl_count,
+        4 => empty_count,
+        8 => corrupt_count,
         _ => 0,
     };
+    let read_only_hint = app.read_only_hint();
+    let writable = read_only_hint.is_empty();
 
     ui.horizontal(|ui| {
         // Select All / Deselect All
@@ -828,6 +1984,16 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
                                 app.integrity.selected_labels_without_images.insert(i);
                             }
                         }
+                        4 => {
+                            for i in 0..empty_count {
+                                app.integrity.selected_empty_labels.insert(i);
+                            }
+                        }
+                        8 => {
+                            for i in 0..corrupt_count {
+                                app.integrity.selected_corrupt_images.insert(i);
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -836,6 +2002,8 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
                     match app.integrity.current_tab {
                         0 => app.integrity.selected_images_without_labels.clear(),
                         1 => app.integrity.selected_labels_without_images.clear(),
+                        4 => app.integrity.selected_empty_labels.clear(),
+                        8 => app.integrity.selected_corrupt_images.clear(),
                         _ => {}
                     }
                 }
@@ -847,24 +2015,109 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
         // Delete Selected button
         let delete_enabled = selection_count > 0;
         if ui.add_enabled(
-            delete_enabled,
+            delete_enabled && writable,
             egui::Button::new(format!("🗑️ Delete Selected ({})", selection_count))
-        ).clicked() {
+        )
+        .on_disabled_hover_text(&read_only_hint)
+        .clicked() {
             app.delete_selected_integrity_issues();
         }
 
-        // Delete All button
-        if total_issues > 0 {
+        // Overlapping boxes are fixed by rewriting the labels, with a dry run first
+        if app.integrity.current_tab == 5 {
+            if ui.add_enabled(
+                overlap_count > 0,
+                egui::Button::new("🔍 Preview Fix")
+            )
+            .on_hover_text("List the boxes that would be removed without changing any file")
+            .clicked() {
+                dedupe_request = Some(true);
+            }
+            if ui.add_enabled(
+                overlap_count > 0 && writable,
+                egui::Button::new(format!("🧹 Remove Duplicate Boxes ({} files)", overlap_count))
+            )
+            .on_hover_text("Originals are backed up; Ctrl+Z restores them")
+            .on_disabled_hover_text(&read_only_hint)
+            .clicked() {
+                dedupe_request = Some(false);
+            }
+        }
+
+        // Out-of-range boxes are clamped in place on a background thread
+        if app.integrity.current_tab == 6 {
+            if app.integrity.fixing_coordinates {
+                if ui.button("⏹ Cancel").clicked() {
+                    app.cancel_fix_coordinates();
+                }
+            } else if ui.add_enabled(
+                range_count > 0 && writable,
+                egui::Button::new(format!("📐 Fix Coordinates ({} files)", range_count))
+            )
+            .on_hover_text("Clamp every box to the image and rewrite only the lines that change")
+            .on_disabled_hover_text(&read_only_hint)
+            .clicked() {
+                app.fix_label_coordinates();
+            }
+        }
+
+        // Empty labels can instead be kept as confirmed backgrounds
+        if app.integrity.current_tab == 4 {
             ui.add_space(10.0);
-            if ui.button(
-                egui::RichText::new(format!("⚠️ Delete All ({})", total_issues))
-                    .color(egui::Color32::from_rgb(255, 100, 100))
-            ).clicked() {
+            if ui.add_enabled(
+                delete_enabled && writable,
+                egui::Button::new(format!("✓ Confirm as Background ({})", selection_count))
+            )
+            .on_hover_text("Write a \"# Background\" comment so the label is no longer reported as empty")
+            .on_disabled_hover_text(&read_only_hint)
+            .clicked() {
+                app.confirm_selected_empty_labels_as_background();
+            }
+        }
+
+        // Delete All button (orphaned files only; unknown classes are not deleted)
+        if orphan_count > 0 {
+            ui.add_space(10.0);
+            if ui.add_enabled(
+                writable,
+                egui::Button::new(
+                    egui::RichText::new(format!("⚠️ Delete All ({})", orphan_count))
+                        .color(egui::Color32::from_rgb(255, 100, 100))
+                )
+            )
+            .on_disabled_hover_text(&read_only_hint)
+            .clicked() {
                 app.delete_all_integrity_issues();
             }
         }
     });
 
+    if let Some(dry_run) = dedupe_request {
+        app.remove_overlapping_boxes(dry_run);
+    }
+
+    // Progress and result of the coordinate fix
+    if app.integrity.fixing_coordinates {
+        ui.add_space(5.0);
+        match app.integrity.fix_coordinates_progress {
+            Some((current, total)) => {
+                let fraction = if total > 0 { current as f32 / total as f32 } else { 0.0 };
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .text(format!("{} / {} label files", current, total))
+                        .animate(true),
+                );
+            }
+            None => {
+                ui.spinner();
+            }
+        }
+        ui.ctx().request_repaint();
+    } else if let Some(message) = &app.integrity.fix_coordinates_message {
+        ui.add_space(5.0);
+        ui.label(message);
+    }
+
     // Error message
     if let Some(error) = &app.integrity.error_message {
         ui.add_space(5.0);
@@ -874,47 +2127,58 @@ fn render_integrity_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
     ui.add_space(5.0);
     
     // Re-analyze button
-    if ui.button("🔄 Re-analyze").clicked() {
-        app.analyze_integrity();
-    }
+    ui.horizontal(|ui| {
+        if ui.button("🔄 Re-analyze").clicked() {
+            app.analyze_integrity();
+        }
+        ui.checkbox(&mut app.integrity.all_splits, "All splits");
+    });
 }
 
-/// Render a list of integrity issues with checkboxes
-fn render_issue_list(
-    ui: &mut egui::Ui,
-    issues: &[crate::core::analysis::IntegrityIssue],
-    selected: &mut std::collections::HashSet<usize>,
-    empty_message: &str,
-) {
-    if issues.is_empty() {
-        ui.vertical_centered(|ui| {
-            ui.add_space(20.0);
-            ui.label(
-                egui::RichText::new(format!("✓ {}", empty_message))
-                    .color(egui::Color32::from_rgb(100, 200, 100))
-            );
-            ui.add_space(20.0);
-        });
-        return;
-    }
+/// Action requested from a split header in the all-splits integrity results
+enum SplitIssueAction {
+    Select,
+    Delete,
+}
 
-    for (idx, issue) in issues.iter().enumerate() {
-        let mut is_selected = selected.contains(&idx);
-        
-        ui.horizontal(|ui| {
-            if ui.checkbox(&mut is_selected, "").clicked() {
-                if is_selected {
-                    selected.insert(idx);
-                } else {
-                    selected.remove(&idx);
-                }
+/// Render a split section header; orphan tabs get select/delete buttons for that split
+///
+/// `read_only_hint` is non-empty when deleting is disabled and explains why.
+fn render_split_header(
+    ui: &mut egui::Ui,
+    split: DatasetSplit,
+    count: usize,
+    with_actions: bool,
+    read_only_hint: &str,
+) -> Option<SplitIssueAction> {
+    let mut action = None;
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.label(
+            egui::RichText::new(format!("{} ({})", split.as_str().to_uppercase(), count))
+                .strong()
+                .color(egui::Color32::from_rgb(150, 200, 255)),
+        );
+        if with_actions {
+            if ui.small_button("☑ Select split").clicked() {
+                action = Some(SplitIssueAction::Select);
             }
-            
-            if let Some(filename) = issue.path.file_name() {
-                ui.label(filename.to_string_lossy().as_ref());
-            } else {
-                ui.label(issue.path.display().to_string());
+            if ui
+                .add_enabled(
+                    read_only_hint.is_empty(),
+                    egui::Button::new(
+                        egui::RichText::new(format!("🗑️ Delete split ({})", count))
+                            .color(egui::Color32::from_rgb(255, 100, 100)),
+                    )
+                    .small(),
+                )
+                .on_disabled_hover_text(read_only_hint)
+                .clicked()
+            {
+                action = Some(SplitIssueAction::Delete);
             }
-        });
-    }
+        }
+    });
+    ui.separator();
+    action
 }
@@ -1,18 +1,32 @@
 // Additional UI functions for batch processing
 
 use crate::app::DatasetCleanerApp;
+use crate::state::BatchRemovalMode;
 use eframe::egui;
 
 /// Render the batch delete confirmation dialog
 pub fn render_batch_delete_confirmation(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     if app.ui.show_batch_delete_confirm {
-        egui::Window::new("✨ Remove Black Images")
+        egui::Window::new(format!("✨ {}", app.batch.mode.title()))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut app.batch.mode, BatchRemovalMode::Black, "Black");
+                    ui.radio_value(&mut app.batch.mode, BatchRemovalMode::White, "White");
+                    ui.radio_value(&mut app.batch.mode, BatchRemovalMode::Extreme, "Both");
+                });
+                ui.add_space(5.0);
+
                 ui.label("This will scan all images in the current split and delete images with");
-                ui.label("black or near-black dominant colors (RGB < 10).");
+                ui.label(match app.batch.mode {
+                    BatchRemovalMode::Black => "black or near-black dominant colors (RGB < 10).",
+                    BatchRemovalMode::White => "white or near-white dominant colors (RGB > 245).",
+                    BatchRemovalMode::Extreme => {
+                        "near-black (RGB < 10) or near-white (RGB > 245) dominant colors."
+                    }
+                });
                 ui.add_space(10.0);
 
                 ui.label(format!("Current split: {:?}", app.dataset.current_split()));
@@ -32,7 +46,11 @@ pub fn render_batch_delete_confirmation(app: &mut DatasetCleanerApp, ctx: &egui:
                 ui.horizontal(|ui| {
                     if ui.button("✓ Yes, Scan & Delete").clicked() {
                         app.ui.show_batch_delete_confirm = false;
-                        app.process_black_images();
+                        match app.batch.mode {
+                            BatchRemovalMode::Black => app.process_black_images(),
+                            BatchRemovalMode::White => app.process_white_images(),
+                            BatchRemovalMode::Extreme => app.process_extreme_images(),
+                        }
                     }
 
                     if ui.button("✗ Cancel").clicked() {
@@ -57,11 +75,16 @@ pub fn render_batch_progress(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
         .show(ctx, |ui| {
             if let Some(stats) = &app.batch.stats {
                 if app.batch.processing {
+                    let total = stats.total_scanned.max(stats.current_progress);
                     ui.label(format!(
                         "Scanning images: {}/{}",
                         stats.current_progress,
-                        stats.total_scanned.max(stats.current_progress)
+                        total
                     ));
+                    let rate = app.batch.rate.label(stats.current_progress, total, "images");
+                    if !rate.is_empty() {
+                        ui.label(egui::RichText::new(rate).small().weak());
+                    }
                     ui.add_space(5.0);
                     ui.label(format!("Images deleted so far: {}", stats.total_deleted));
                     ui.add_space(10.0);
@@ -98,3 +121,124 @@ pub fn render_batch_progress(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
         }
     }
 }
+
+/// Render the export subset confirmation dialog (shown after picking a folder)
+pub fn render_export_subset_confirmation(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    let Some(dest) = app.export_subset.pending_dest.clone() else {
+        return;
+    };
+
+    egui::Window::new("📤 Export Subset")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            let (count, scope) = if app.filter.is_active() {
+                (app.filter.filtered_indices().len(), "filtered")
+            } else {
+                (app.dataset.get_image_files().len(), "all")
+            };
+            ui.label(format!(
+                "Copy {} {} images and their labels to:",
+                count, scope
+            ));
+            ui.label(egui::RichText::new(dest.display().to_string()).monospace());
+            ui.add_space(5.0);
+            ui.label("Images go to images/ and labels to labels/. The source dataset is not modified.");
+
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button(format!("✓ Export {} Images", count)).clicked() {
+                    app.export_subset.pending_dest = None;
+                    app.export_filtered_subset(dest.clone());
+                }
+
+                if ui.button("✗ Cancel").clicked() {
+                    app.export_subset.pending_dest = None;
+                }
+            });
+        });
+}
+
+/// Render the confirmation for a dataset.yaml export that would replace an existing file
+pub fn render_yaml_overwrite_confirmation(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    let Some(dest) = app.ui.pending_yaml_overwrite.clone() else {
+        return;
+    };
+
+    egui::Window::new("📄 Export dataset.yaml")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("This file already exists:");
+            ui.label(egui::RichText::new(dest.display().to_string()).monospace());
+            ui.add_space(5.0);
+            ui.label("Replace it with the split folders and class names of this dataset?");
+
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button("✓ Replace").clicked() {
+                    app.ui.pending_yaml_overwrite = None;
+                    app.export_yolo_yaml(&dest);
+                }
+
+                if ui.button("✗ Cancel").clicked() {
+                    app.ui.pending_yaml_overwrite = None;
+                }
+            });
+        });
+}
+
+/// Render the export subset progress/results dialog
+pub fn render_export_subset_progress(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    let Some(stats) = app.export_subset.stats.clone() else {
+        return;
+    };
+
+    egui::Window::new(if app.export_subset.processing {
+        "⏳ Exporting..."
+    } else {
+        "✓ Export Complete"
+    })
+    .collapsible(false)
+    .resizable(false)
+    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+    .show(ctx, |ui| {
+        if app.export_subset.processing {
+            let progress = if stats.total > 0 {
+                stats.current_progress as f32 / stats.total as f32
+            } else {
+                0.0
+            };
+            ui.add(egui::ProgressBar::new(progress).text(format!(
+                "Copied {} / {} images",
+                stats.current_progress, stats.total
+            )));
+            ui.add_space(10.0);
+            if ui.button("❌ Cancel").clicked() {
+                app.cancel_export_subset();
+            }
+        } else {
+            ui.label(format!("🖼 Images copied: {} / {}", stats.images_copied, stats.total));
+            ui.label(format!("📝 Labels copied: {}", stats.labels_copied));
+            if stats.failed > 0 {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 150, 0),
+                    format!("⚠ Failed: {} (see log)", stats.failed),
+                );
+            }
+
+            ui.add_space(10.0);
+
+            if ui.button("Close").clicked() {
+                app.export_subset.stats = None;
+            }
+        }
+    });
+
+    // Request repaint to update progress
+    if app.export_subset.processing {
+        ctx.request_repaint();
+    }
+}
@@ -0,0 +1,167 @@
+use crate::app::DatasetCleanerApp;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+use std::path::{Path, PathBuf};
+
+/// Thumbnail edge length in pixels
+const THUMBNAIL_SIZE: u32 = 64;
+
+/// Thumbnails decoded per frame, so opening the dialog never stalls the UI
+const THUMBNAILS_PER_FRAME: usize = 4;
+
+/// Decode a small texture for a bookmarked image
+fn load_thumbnail(ctx: &egui::Context, path: &Path) -> Option<egui::TextureHandle> {
+    let img = image::open(path).ok()?;
+    let thumb = img.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgba8();
+    let size = [thumb.width() as _, thumb.height() as _];
+    let color_image =
+        egui::ColorImage::from_rgba_unmultiplied(size, thumb.as_flat_samples().as_slice());
+    Some(ctx.load_texture(
+        format!("bookmark_{}", path.display()),
+        color_image,
+        egui::TextureOptions::LINEAR,
+    ))
+}
+
+/// Render the bookmarks dialog (Tools > Bookmarks)
+pub fn render_bookmarks_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.bookmark_dialog.show_dialog {
+        return;
+    }
+
+    // Decode a few missing thumbnails per frame
+    let missing: Vec<PathBuf> = app
+        .bookmarks
+        .iter()
+        .filter(|path| !app.bookmark_dialog.thumbnails.contains_key(*path))
+        .take(THUMBNAILS_PER_FRAME)
+        .cloned()
+        .collect();
+    for path in missing {
+        let thumbnail = load_thumbnail(ctx, &path);
+        app.bookmark_dialog.thumbnails.insert(path, thumbnail);
+        ctx.request_repaint();
+    }
+
+    let current = app
+        .dataset
+        .get_image_files()
+        .get(app.current_index)
+        .cloned();
+    let mut jump_to = None;
+    let mut remove = None;
+    let mut close_dialog = false;
+
+    egui::Window::new(format!("{} Bookmarks", Icon::STAR))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(420.0);
+
+            if app.bookmarks.is_empty() {
+                ui.label("No bookmarks yet. Press B to bookmark the current image.");
+            } else {
+                ui.label(format!("{} bookmarked images", app.bookmarks.len()));
+                ui.add_space(5.0);
+
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        for path in app.bookmarks.iter() {
+                            ui.horizontal(|ui| {
+                                let thumb_size =
+                                    egui::vec2(THUMBNAIL_SIZE as f32, THUMBNAIL_SIZE as f32);
+                                match app.bookmark_dialog.thumbnails.get(path) {
+                                    Some(Some(texture)) => {
+                                        ui.add(egui::Image::new(texture).max_size(thumb_size));
+                                    }
+                                    Some(None) => {
+                                        ui.allocate_ui(thumb_size, |ui| {
+                                            ui.label(
+                                                egui::RichText::new(Icon::IMAGE_BROKEN)
+                                                    .size(32.0)
+                                                    .color(egui::Color32::GRAY),
+                                            );
+                                        });
+                                    }
+                                    None => {
+                                        ui.allocate_ui(thumb_size, |ui| {
+                                            ui.spinner();
+                                        });
+                                    }
+                                }
+
+                                ui.vertical(|ui| {
+                                    let name = path
+                                        .file_name()
+                                        .map(|n| n.to_string_lossy().into_owned())
+                                        .unwrap_or_default();
+                                    let mut text = egui::RichText::new(name).monospace();
+                                    if current.as_ref() == Some(path) {
+                                        text = text.strong();
+                                    }
+                                    ui.label(text);
+
+                                    let exists = path.exists();
+                                    if !exists {
+                                        ui.colored_label(
+                                            egui::Color32::from_rgb(255, 150, 0),
+                                            "⚠ File not found",
+                                        );
+                                    }
+
+                                    ui.horizontal(|ui| {
+                                        if ui
+                                            .add_enabled(
+                                                exists,
+                                                egui::Button::new(format!(
+                                                    "{} Jump",
+                                                    Icon::ARROW_SQUARE_OUT
+                                                )),
+                                            )
+                                            .clicked()
+                                        {
+                                            jump_to = Some(path.clone());
+                                        }
+                                        if ui
+                                            .button(format!("{} Remove", Icon::X))
+                                            .on_hover_text(
+                                                "Remove the bookmark (the image is kept)",
+                                            )
+                                            .clicked()
+                                        {
+                                            remove = Some(path.clone());
+                                        }
+                                    });
+                                });
+                            });
+                            ui.separator();
+                        }
+                    });
+            }
+
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    // Handle actions after the dialog is drawn
+    if let Some(path) = jump_to {
+        app.jump_to_bookmark(&path);
+    }
+
+    if let Some(path) = remove {
+        app.remove_bookmark(&path);
+    }
+
+    if close_dialog {
+        app.bookmark_dialog.show_dialog = false;
+    }
+}
@@ -0,0 +1,138 @@
+use crate::app::DatasetCleanerApp;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Render the convert image format dialog (Tools > Convert Format)
+pub fn render_convert_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.convert.show_dialog {
+        return;
+    }
+
+    let mut convert_clicked = false;
+    let mut cancel_clicked = false;
+    let mut close_dialog = false;
+
+    egui::Window::new(format!("{} Convert Format", Icon::FILE_IMAGE))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(380.0);
+
+            ui.label(format!(
+                "Convert the images in the {} split. Label files are left unchanged.",
+                app.dataset.current_split().as_str()
+            ));
+            ui.add_space(10.0);
+
+            ui.add_enabled_ui(!app.convert.processing, |ui| {
+                ui.group(|ui| {
+                    ui.label(egui::RichText::new("Conversion").strong().size(16.0));
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut app.convert.to_jpeg, true, "PNG → JPG");
+                        ui.radio_value(&mut app.convert.to_jpeg, false, "JPG → PNG");
+                    });
+
+                    if app.convert.to_jpeg {
+                        ui.add(
+                            egui::Slider::new(&mut app.convert.quality, 1..=100)
+                                .text("JPEG quality"),
+                        );
+                    }
+                });
+            });
+
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 150, 0),
+                "⚠ Originals are replaced and cannot be restored with undo",
+            );
+
+            ui.add_space(10.0);
+
+            // Progress of the running conversion
+            if app.convert.processing {
+                if let Some((current, total)) = app.convert.progress {
+                    let fraction = if total > 0 {
+                        current as f32 / total as f32
+                    } else {
+                        0.0
+                    };
+                    ui.add(
+                        egui::ProgressBar::new(fraction)
+                            .text(format!("{} / {} images", current, total))
+                            .animate(true),
+                    );
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Scanning images...");
+                    });
+                }
+                ctx.request_repaint();
+            }
+
+            // Report of the last conversion
+            if let Some(report) = &app.convert.report {
+                let heading = if app.convert.cancelled {
+                    "Cancelled"
+                } else {
+                    "Done"
+                };
+                let mb = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{}: {} of {} images converted ({:.1} MB → {:.1} MB)",
+                        heading,
+                        report.converted,
+                        report.total,
+                        mb(report.bytes_before),
+                        mb(report.bytes_after)
+                    ))
+                    .strong(),
+                );
+
+                if !report.errors.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 100, 100),
+                        format!("{} {} images failed", Icon::WARNING, report.errors.len()),
+                    );
+                }
+            }
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if app.convert.processing {
+                    if ui.button(format!("{} Cancel", Icon::X)).clicked() {
+                        cancel_clicked = true;
+                    }
+                    return;
+                }
+
+                if ui.button(format!("{} Convert", Icon::CHECK)).clicked() {
+                    convert_clicked = true;
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    // Handle actions after the dialog is drawn
+    if convert_clicked {
+        app.start_convert_format();
+    }
+
+    if cancel_clicked {
+        app.cancel_convert_format();
+    }
+
+    if close_dialog {
+        app.convert.show_dialog = false;
+    }
+}
@@ -0,0 +1,161 @@
+use crate::app::DatasetCleanerApp;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Render the crop export dialog (Tools > Export Crops)
+pub fn render_crop_export_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.crop_export.show_dialog {
+        return;
+    }
+
+    let image_count = app.filtered_image_files().len();
+    let mut start_clicked = false;
+    let mut cancel_clicked = false;
+    let mut close_dialog = false;
+
+    egui::Window::new(format!("{} Export Crops", Icon::SCISSORS))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(420.0);
+
+            ui.label("Save every detection as a full-resolution crop in a folder per class, e.g. `out/ct/frame_0.png`.");
+            ui.add_space(10.0);
+
+            ui.add_enabled_ui(!app.crop_export.processing, |ui| {
+                ui.group(|ui| {
+                    egui::Grid::new("crop_export_options")
+                        .num_columns(2)
+                        .spacing([10.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label("Padding:");
+                            ui.add(
+                                egui::Slider::new(&mut app.crop_export.padding, 0.0..=0.5)
+                                    .custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
+                                    .custom_parser(|s| {
+                                        s.trim_end_matches('%').trim().parse::<f64>().ok().map(|v| v / 100.0)
+                                    }),
+                            )
+                            .on_hover_text("Extra margin on every side, as a share of the box size");
+                            ui.end_row();
+
+                            ui.label("Output folder:");
+                            ui.horizontal(|ui| {
+                                let folder = app
+                                    .crop_export
+                                    .output_dir
+                                    .as_ref()
+                                    .map_or("Not set".to_string(), |path| path.display().to_string());
+                                ui.label(egui::RichText::new(folder).monospace().small());
+                                if ui.button(format!("{} Browse", Icon::FOLDER_OPEN)).clicked() {
+                                    if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                                        app.crop_export.output_dir = Some(path);
+                                    }
+                                }
+                            });
+                            ui.end_row();
+                        });
+                });
+            });
+
+            ui.add_space(10.0);
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} images in the current view will be cropped",
+                    image_count
+                ))
+                .strong(),
+            );
+
+            // Progress of the running batch
+            if app.crop_export.processing {
+                if let Some((current, total)) = app.crop_export.progress {
+                    let fraction = if total > 0 {
+                        current as f32 / total as f32
+                    } else {
+                        0.0
+                    };
+                    ui.add(
+                        egui::ProgressBar::new(fraction)
+                            .text(format!("{} / {} images", current, total))
+                            .animate(true),
+                    );
+                } else {
+                    ui.spinner();
+                }
+                ctx.request_repaint();
+            }
+
+            // Crops written per class by the last batch
+            if let Some(report) = &app.crop_export.report {
+                let heading = if app.crop_export.cancelled {
+                    "Cancelled"
+                } else {
+                    "Done"
+                };
+                ui.label(format!(
+                    "{}: {} crops written",
+                    heading,
+                    report.crops_written()
+                ));
+                egui::Grid::new("crop_export_summary")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (class, count) in &report.per_class {
+                            ui.label(egui::RichText::new(class).monospace());
+                            ui.label(count.to_string());
+                            ui.end_row();
+                        }
+                    });
+                if !report.errors.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 100, 100),
+                        format!("{} {} crops failed", Icon::WARNING, report.errors.len()),
+                    );
+                }
+            }
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if app.crop_export.processing {
+                    if ui.button(format!("{} Cancel", Icon::X)).clicked() {
+                        cancel_clicked = true;
+                    }
+                    return;
+                }
+
+                if ui
+                    .add_enabled(
+                        image_count > 0 && app.crop_export.output_dir.is_some(),
+                        egui::Button::new(format!("{} Export Crops", Icon::CHECK)),
+                    )
+                    .clicked()
+                {
+                    start_clicked = true;
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    // Handle actions after the dialog is drawn
+    if start_clicked {
+        app.start_crop_export();
+    }
+
+    if cancel_clicked {
+        app.cancel_crop_export();
+    }
+
+    if close_dialog {
+        app.crop_export.show_dialog = false;
+    }
+}
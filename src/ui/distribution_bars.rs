@@ -0,0 +1,191 @@
+//! Painted bar charts for category distributions.
+//!
+//! Bars are plain painter rects colored per category (the same colors as the
+//! minimap), with the exact counts in the hover text.
+
+use crate::config::AppConfig;
+use crate::core::analysis::{BalanceStats, GlobalBalanceStats, ImageCategory, TargetRatios};
+use crate::core::dataset::DatasetSplit;
+use eframe::egui;
+
+const BAR_HEIGHT: f32 = 16.0;
+const BAR_MAX_WIDTH: f32 = 320.0;
+const LABEL_WIDTH: f32 = 80.0;
+
+/// Display color for an image category (player categories follow the class table)
+pub fn category_color(config: &AppConfig, category: ImageCategory) -> egui::Color32 {
+    match category {
+        ImageCategory::CTOnly => config.get_class_colors(1).0,
+        ImageCategory::TOnly => config.get_class_colors(0).0,
+        ImageCategory::MultiplePlayer => egui::Color32::from_rgb(180, 100, 220),
+        ImageCategory::Background => egui::Color32::from_gray(90),
+        ImageCategory::HardCase => egui::Color32::from_rgb(220, 60, 60),
+        ImageCategory::Unknown => egui::Color32::from_rgb(230, 200, 40),
+    }
+}
+
+/// Small color swatch and name for each category
+pub fn render_category_legend(ui: &mut egui::Ui, config: &AppConfig, categories: &[ImageCategory]) {
+    ui.horizontal_wrapped(|ui| {
+        for &category in categories {
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+            ui.painter().rect_filled(rect, 2.0, category_color(config, category));
+            ui.label(egui::RichText::new(category.as_str()).small());
+            ui.add_space(6.0);
+        }
+    });
+}
+
+/// Paint `segments` left to right inside `rect`, each `count / scale` of its width
+///
+/// Returns the hover text listing every segment's count.
+fn paint_stacked_bar(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    segments: &[(ImageCategory, usize)],
+    scale: usize,
+    config: &AppConfig,
+) -> String {
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 3.0, egui::Color32::from_gray(50));
+
+    let total: usize = segments.iter().map(|(_, count)| count).sum();
+    let mut x = rect.min.x;
+    let mut lines = Vec::new();
+    for &(category, count) in segments {
+        if scale > 0 && count > 0 {
+            let width = rect.width() * count as f32 / scale as f32;
+            let segment = egui::Rect::from_min_size(egui::pos2(x, rect.min.y), egui::vec2(width, rect.height()));
+            painter.rect_filled(segment, 0.0, category_color(config, category));
+            x += width;
+        }
+        let pct = if total > 0 { count as f32 / total as f32 * 100.0 } else { 0.0 };
+        lines.push(format!("{}: {} ({:.1}%)", category.as_str(), count, pct));
+    }
+    lines.push(format!("Total: {}", total));
+    lines.join("\n")
+}
+
+/// Current share of players, background and hard cases against their targets
+///
+/// Each bar spans 0-100% of the split; the white tick marks the target share.
+pub fn render_target_bars(
+    ui: &mut egui::Ui,
+    stats: &BalanceStats,
+    targets: &TargetRatios,
+    config: &AppConfig,
+) {
+    let rows: [(&str, &[ImageCategory], f32); 3] = [
+        (
+            "Players",
+            &[ImageCategory::CTOnly, ImageCategory::TOnly, ImageCategory::MultiplePlayer],
+            targets.player_ratio,
+        ),
+        ("Background", &[ImageCategory::Background], targets.background_ratio),
+        ("Hard Cases", &[ImageCategory::HardCase], targets.hardcase_ratio),
+    ];
+
+    for (name, categories, target) in rows {
+        let segments: Vec<(ImageCategory, usize)> = categories
+            .iter()
+            .map(|&category| (category, stats.get_count(category)))
+            .collect();
+        let count: usize = segments.iter().map(|(_, count)| count).sum();
+        let pct = if stats.total_images > 0 {
+            count as f32 / stats.total_images as f32 * 100.0
+        } else {
+            0.0
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_sized([LABEL_WIDTH, BAR_HEIGHT], egui::Label::new(name));
+            let (rect, response) = ui.allocate_exact_size(
+                egui::vec2(ui.available_width().min(BAR_MAX_WIDTH), BAR_HEIGHT),
+                egui::Sense::hover(),
+            );
+            let mut hover = paint_stacked_bar(ui, rect, &segments, stats.total_images, config);
+
+            let target_x = rect.min.x + rect.width() * target.clamp(0.0, 1.0);
+            ui.painter().line_segment(
+                [egui::pos2(target_x, rect.min.y - 2.0), egui::pos2(target_x, rect.max.y + 2.0)],
+                egui::Stroke::new(2.0, egui::Color32::WHITE),
+            );
+
+            let target_count = (stats.total_images as f32 * target).round() as usize;
+            hover.push_str(&format!(
+                "\nTarget: {:.0}% (≈{} of {} images)",
+                target * 100.0,
+                target_count,
+                stats.total_images
+            ));
+            response.on_hover_text(hover);
+            ui.label(format!("{:.1}% / {:.0}%", pct, target * 100.0));
+        });
+    }
+    render_category_legend(
+        ui,
+        config,
+        &[
+            ImageCategory::CTOnly,
+            ImageCategory::TOnly,
+            ImageCategory::MultiplePlayer,
+            ImageCategory::Background,
+            ImageCategory::HardCase,
+        ],
+    );
+}
+
+/// Before/after stacked bars per split, all drawn to the same scale
+///
+/// The longest bar is the largest split before or after the moves, so bar
+/// lengths also show how the split sizes change.
+pub fn render_split_comparison_bars(
+    ui: &mut egui::Ui,
+    current: &GlobalBalanceStats,
+    projected: &GlobalBalanceStats,
+    config: &AppConfig,
+) {
+    let scale = DatasetSplit::all()
+        .into_iter()
+        .flat_map(|split| [current.get(split).total_images, projected.get(split).total_images])
+        .max()
+        .unwrap_or(0);
+    let segments_of = |stats: &BalanceStats| -> Vec<(ImageCategory, usize)> {
+        ImageCategory::ALL
+            .iter()
+            .map(|&category| (category, stats.get_count(category)))
+            .collect()
+    };
+
+    egui::Grid::new("split_comparison_bars")
+        .num_columns(3)
+        .spacing([8.0, 3.0])
+        .show(ui, |ui| {
+            for split in DatasetSplit::all() {
+                let before = current.get(split);
+                let after = projected.get(split);
+                for (is_after, stats) in [(false, before), (true, after)] {
+                    if is_after {
+                        ui.label("");
+                    } else {
+                        ui.label(egui::RichText::new(split.as_str().to_uppercase()).strong());
+                    }
+                    let row = if is_after { "after" } else { "before" };
+                    ui.label(egui::RichText::new(row).small().color(egui::Color32::GRAY));
+
+                    let (rect, response) = ui.allocate_exact_size(
+                        egui::vec2(BAR_MAX_WIDTH, BAR_HEIGHT),
+                        egui::Sense::hover(),
+                    );
+                    let mut hover = paint_stacked_bar(ui, rect, &segments_of(stats), scale, config);
+                    if is_after {
+                        let delta = after.total_images as i64 - before.total_images as i64;
+                        hover.push_str(&format!(" ({:+})", delta));
+                    }
+                    response.on_hover_text(hover);
+                    ui.end_row();
+                }
+            }
+        });
+    render_category_legend(ui, config, &ImageCategory::ALL);
+}
@@ -1,9 +1,128 @@
 use crate::app::DatasetCleanerApp;
-use crate::core::filter::{PlayerCountFilter, TeamFilter};
+use crate::core::analysis::ImageCategory;
+use crate::core::filter::{
+    date_start_timestamp, parse_filter_date, timestamp_date, FilterOptionCounts,
+    PlayerCountFilter, TeamFilter, COMMON_MAPS,
+};
+use crate::state::ManifestFilter;
+use chrono::NaiveDate;
 use eframe::egui;
 use egui_phosphor::regular as Icon;
 
+/// Format a count with thousands separators ("3,412")
+fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (idx, ch) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Option label suffix: " (3,412)", or " (?)" while the category cache is cold
+fn count_suffix(count: Option<usize>) -> String {
+    match count {
+        Some(count) => format!(" ({})", format_count(count)),
+        None => " (?)".to_string(),
+    }
+}
+
+/// Render an "is / is not" toggle for a criterion's invert flag
+fn render_match_mode(ui: &mut egui::Ui, invert: &mut bool) {
+    ui.horizontal(|ui| {
+        ui.selectable_value(invert, false, "is");
+        ui.selectable_value(invert, true, "is not");
+    });
+}
+
+/// Render a `YYYY-MM-DD` field for one end of the capture date range
+///
+/// Returns the new date when the text changes to a valid date, or `Some(None)`
+/// when it is cleared. While the field is not focused it shows `date`.
+fn render_date_input(
+    ui: &mut egui::Ui,
+    label: &str,
+    hover: &str,
+    input: &mut String,
+    date: Option<NaiveDate>,
+) -> Option<Option<NaiveDate>> {
+    let mut result = None;
+    ui.horizontal(|ui| {
+        ui.label(label).on_hover_text(hover);
+        let response = ui.add(
+            egui::TextEdit::singleline(input)
+                .hint_text("YYYY-MM-DD")
+                .desired_width(100.0),
+        );
+        let parsed = parse_filter_date(input);
+        if response.changed() {
+            if input.trim().is_empty() {
+                result = Some(None);
+            } else if parsed.is_some() {
+                result = Some(parsed);
+            }
+        }
+
+        if response.has_focus() {
+            if !input.trim().is_empty() && parsed.is_none() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 100, 100),
+                    format!("{} Use YYYY-MM-DD", Icon::WARNING),
+                );
+            }
+        } else {
+            // Follow presets, Clear All and settings; drops an unfinished entry
+            let shown = date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+            if *input != shown {
+                *input = shown;
+            }
+        }
+    });
+    result
+}
+
 /// Render the filter dialog for configuring image filters
+/// Most unmatched manifest entries listed in the filter dialog
+const MISSING_PREVIEW_COUNT: usize = 10;
+
+/// Render the summary of a manifest installed as the filter
+fn render_manifest_filter(ui: &mut egui::Ui, manifest: &ManifestFilter, matched: usize) {
+    ui.group(|ui| {
+        ui.label(
+            egui::RichText::new(format!("{} Manifest: {}", Icon::LIST_BULLETS, manifest.name))
+                .strong()
+                .size(16.0),
+        );
+        ui.add_space(5.0);
+        ui.label(format!(
+            "{} of {} entries found ({} images shown)",
+            format_count(manifest.entries.len() - manifest.missing.len()),
+            format_count(manifest.entries.len()),
+            format_count(matched)
+        ));
+
+        if !manifest.missing.is_empty() {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 150, 0),
+                format!("{} {} not found in this split", Icon::WARNING, format_count(manifest.missing.len())),
+            );
+            egui::CollapsingHeader::new("Missing entries")
+                .id_salt("manifest_missing")
+                .show(ui, |ui| {
+                    for entry in manifest.missing.iter().take(MISSING_PREVIEW_COUNT) {
+                        ui.label(egui::RichText::new(entry).monospace().small());
+                    }
+                    if manifest.missing.len() > MISSING_PREVIEW_COUNT {
+                        ui.label(format!("... and {} more", manifest.missing.len() - MISSING_PREVIEW_COUNT));
+                    }
+                });
+        }
+    });
+}
+
 pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     if !app.ui.show_filter_dialog {
         return;
@@ -12,6 +131,25 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     let mut apply_clicked = false;
     let mut clear_clicked = false;
     let mut close_dialog = false;
+    let mut load_preset = None;
+    let mut save_preset = false;
+    let mut delete_preset = None;
+    let mut compute_counts = false;
+    let mut export_list = None;
+    let mut load_manifest = None;
+
+    // Option counts come from the category cache instead of re-reading labels
+    let image_count = app.dataset.get_image_files().len();
+    let option_counts = app
+        .categories
+        .get_indexed(image_count)
+        .map(|(categories, detections)| FilterOptionCounts::from_index(categories, detections));
+    let team_count = |team| count_suffix(option_counts.as_ref().map(|c| c.team(team)));
+    let player_count =
+        |filter| count_suffix(option_counts.as_ref().map(|c| c.player_count(filter)));
+    if app.categories.is_indexing() {
+        ctx.request_repaint();
+    }
 
     egui::Window::new(format!("{} Filter Images", Icon::FUNNEL))
         .collapsible(false)
@@ -20,6 +158,117 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
         .show(ctx, |ui| {
             ui.set_min_width(350.0);
 
+            // A loaded manifest replaces the criteria until it is cleared
+            if let Some(manifest) = &app.filter.manifest {
+                render_manifest_filter(ui, manifest, app.filter.filtered_indices().len());
+                ui.add_space(10.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(egui::RichText::new(format!("{} Clear Manifest", Icon::X)).size(14.0))
+                        .clicked()
+                    {
+                        clear_clicked = true;
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("Close").clicked() {
+                            close_dialog = true;
+                        }
+                    });
+                });
+                return;
+            }
+
+            // Presets Section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Presets", Icon::BOOKMARKS))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                // An edited preset is marked instead of being overwritten
+                let active = app.filter.active_preset.as_ref().and_then(|name| {
+                    app.settings
+                        .filter_presets
+                        .iter()
+                        .find(|p| &p.name == name)
+                });
+                let selected_text = match active {
+                    Some(preset) if preset.criteria == app.filter.criteria => preset.name.clone(),
+                    Some(preset) => format!("{} (modified)", preset.name),
+                    None => "Choose a preset...".to_string(),
+                };
+
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("filter_preset")
+                        .selected_text(selected_text)
+                        .width(200.0)
+                        .show_ui(ui, |ui| {
+                            if app.settings.filter_presets.is_empty() {
+                                ui.label("No presets saved yet");
+                            }
+                            for (idx, preset) in app.settings.filter_presets.iter().enumerate() {
+                                let label = if idx < 9 {
+                                    format!("{}  (Ctrl+{})", preset.name, idx + 1)
+                                } else {
+                                    preset.name.clone()
+                                };
+                                let is_active = active.is_some_and(|a| a.name == preset.name);
+                                if ui.selectable_label(is_active, label).clicked() {
+                                    load_preset = Some(idx);
+                                }
+                            }
+                        });
+
+                    if ui
+                        .add_enabled(active.is_some(), egui::Button::new(format!("{} Delete", Icon::TRASH)))
+                        .clicked()
+                    {
+                        delete_preset = active.map(|p| p.name.clone());
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.filter.preset_name_input)
+                            .hint_text("Preset name")
+                            .desired_width(200.0),
+                    );
+                    let name = app.filter.preset_name_input.trim();
+                    let overwrites = app.settings.filter_presets.iter().any(|p| p.name == name);
+                    if ui
+                        .add_enabled(!name.is_empty(), egui::Button::new(format!("{} Save", Icon::FLOPPY_DISK)))
+                        .on_hover_text(if overwrites {
+                            "Replace the saved preset with the current filters"
+                        } else {
+                            "Save the current filters as a new preset"
+                        })
+                        .clicked()
+                    {
+                        save_preset = true;
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            if option_counts.is_none() {
+                ui.horizontal(|ui| {
+                    if app.categories.is_indexing() {
+                        ui.spinner();
+                        ui.label("Counting images per option...");
+                    } else {
+                        ui.label("Option counts are not available yet");
+                        if ui.button(format!("{} Compute counts", Icon::CALCULATOR)).clicked() {
+                            compute_counts = true;
+                        }
+                    }
+                });
+                ui.add_space(10.0);
+            }
+
             // Team Filter Section
             ui.group(|ui| {
                 ui.label(
@@ -29,41 +278,50 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                 );
                 ui.add_space(5.0);
 
+                // Team filters map to class 0 (T) and class 1 (CT); show their configured names
+                let t_name = app.config.get_class_name(0).to_string();
+                let ct_name = app.config.get_class_name(1).to_string();
+
+                render_match_mode(ui, &mut app.filter.criteria.invert_team);
+
                 ui.horizontal_wrapped(|ui| {
                     let selected_team = &mut app.filter.criteria.team;
 
                     if ui
-                        .selectable_label(*selected_team == TeamFilter::All, "All Teams")
+                        .selectable_label(
+                            *selected_team == TeamFilter::All,
+                            format!("All Teams{}", team_count(TeamFilter::All)),
+                        )
                         .clicked()
                     {
                         *selected_team = TeamFilter::All;
                     }
                     if ui
-                        .selectable_label(*selected_team == TeamFilter::TOnly, "T Only")
+                        .selectable_label(*selected_team == TeamFilter::TOnly, format!("{} Only{}", t_name, team_count(TeamFilter::TOnly)))
                         .clicked()
                     {
                         *selected_team = TeamFilter::TOnly;
                     }
                     if ui
-                        .selectable_label(*selected_team == TeamFilter::CTOnly, "CT Only")
+                        .selectable_label(*selected_team == TeamFilter::CTOnly, format!("{} Only{}", ct_name, team_count(TeamFilter::CTOnly)))
                         .clicked()
                     {
                         *selected_team = TeamFilter::CTOnly;
                     }
                     if ui
-                        .selectable_label(*selected_team == TeamFilter::Both, "Both T & CT")
+                        .selectable_label(*selected_team == TeamFilter::Both, format!("Both {} & {}{}", t_name, ct_name, team_count(TeamFilter::Both)))
                         .clicked()
                     {
                         *selected_team = TeamFilter::Both;
                     }
                     if ui
-                        .selectable_label(*selected_team == TeamFilter::TExclusive, "T Exclusive")
+                        .selectable_label(*selected_team == TeamFilter::TExclusive, format!("{} Exclusive{}", t_name, team_count(TeamFilter::TExclusive)))
                         .clicked()
                     {
                         *selected_team = TeamFilter::TExclusive;
                     }
                     if ui
-                        .selectable_label(*selected_team == TeamFilter::CTExclusive, "CT Exclusive")
+                        .selectable_label(*selected_team == TeamFilter::CTExclusive, format!("{} Exclusive{}", ct_name, team_count(TeamFilter::CTExclusive)))
                         .clicked()
                     {
                         *selected_team = TeamFilter::CTExclusive;
@@ -82,17 +340,25 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                 );
                 ui.add_space(5.0);
 
+                render_match_mode(ui, &mut app.filter.criteria.invert_player_count);
+
                 ui.horizontal_wrapped(|ui| {
                     let selected_count = &mut app.filter.criteria.player_count;
 
                     if ui
-                        .selectable_label(*selected_count == PlayerCountFilter::Any, "Any")
+                        .selectable_label(
+                            *selected_count == PlayerCountFilter::Any,
+                            format!("Any{}", player_count(PlayerCountFilter::Any)),
+                        )
                         .clicked()
                     {
                         *selected_count = PlayerCountFilter::Any;
                     }
                     if ui
-                        .selectable_label(*selected_count == PlayerCountFilter::Single, "Single")
+                        .selectable_label(
+                            *selected_count == PlayerCountFilter::Single,
+                            format!("Single{}", player_count(PlayerCountFilter::Single)),
+                        )
                         .clicked()
                     {
                         *selected_count = PlayerCountFilter::Single;
@@ -100,7 +366,7 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                     if ui
                         .selectable_label(
                             *selected_count == PlayerCountFilter::Multiple,
-                            "Multiple (2+)",
+                            format!("Multiple (2+){}", player_count(PlayerCountFilter::Multiple)),
                         )
                         .clicked()
                     {
@@ -109,7 +375,10 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                     if ui
                         .selectable_label(
                             *selected_count == PlayerCountFilter::Background,
-                            "Background (No Players)",
+                            format!(
+                                "Background (No Players){}",
+                                player_count(PlayerCountFilter::Background)
+                            ),
                         )
                         .clicked()
                     {
@@ -118,6 +387,248 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                 });
             });
 
+            ui.add_space(10.0);
+
+            // Category Filter Section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Category", Icon::TAG))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                render_match_mode(ui, &mut app.filter.criteria.invert_category);
+
+                ui.horizontal_wrapped(|ui| {
+                    let selected_category = &mut app.filter.criteria.category;
+
+                    if ui
+                        .selectable_label(selected_category.is_none(), "Any Category")
+                        .clicked()
+                    {
+                        *selected_category = None;
+                    }
+                    for category in ImageCategory::ALL {
+                        if ui
+                            .selectable_label(*selected_category == Some(category), category.as_str())
+                            .clicked()
+                        {
+                            *selected_category = Some(category);
+                        }
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            // Detection Count Range Section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Detection Count", Icon::HASH))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    let mut enabled = app.filter.criteria.detection_count_range.is_some();
+                    if ui.checkbox(&mut enabled, "Limit detections").changed() {
+                        app.filter.criteria.detection_count_range = enabled.then_some(1..=1);
+                    }
+                    if ui
+                        .small_button("Background only")
+                        .on_hover_text("Min and max 0: images without detections")
+                        .clicked()
+                    {
+                        app.filter.criteria.detection_count_range = Some(0..=0);
+                    }
+                });
+
+                if let Some(range) = &mut app.filter.criteria.detection_count_range {
+                    let (mut min, mut max) = (*range.start(), *range.end());
+                    ui.horizontal(|ui| {
+                        ui.label("Min detections");
+                        let min_changed = ui.add(egui::DragValue::new(&mut min).range(0..=99)).changed();
+                        ui.label("Max detections");
+                        let max_changed = ui.add(egui::DragValue::new(&mut max).range(0..=99)).changed();
+                        // Keep min <= max by moving the bound that was not edited
+                        if min_changed && min > max {
+                            max = min;
+                        } else if max_changed && max < min {
+                            min = max;
+                        }
+                    });
+                    *range = min..=max;
+                }
+            });
+
+            ui.add_space(10.0);
+
+            // Capture Date Section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Capture Date", Icon::CALENDAR))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                let criteria = &mut app.filter.criteria;
+                let after = criteria.min_timestamp.and_then(timestamp_date);
+                if let Some(date) = render_date_input(
+                    ui,
+                    "Captured after",
+                    "On or after this day (local time)",
+                    &mut app.filter.captured_after_input,
+                    after,
+                ) {
+                    criteria.min_timestamp = date.map(date_start_timestamp);
+                }
+
+                // Stored as the last second before the typed day
+                let before = criteria
+                    .max_timestamp
+                    .and_then(|max| timestamp_date(max + 1));
+                if let Some(date) = render_date_input(
+                    ui,
+                    "Captured before",
+                    "Before this day, not including it (local time)",
+                    &mut app.filter.captured_before_input,
+                    before,
+                ) {
+                    criteria.max_timestamp = date.map(|date| date_start_timestamp(date) - 1);
+                }
+
+                ui.label(
+                    egui::RichText::new("Uses the label's Time: metadata; images without it are hidden")
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(10.0);
+
+            // Map Filter Section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Map", Icon::MAP_TRIFOLD))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                if app.filter.available_maps.is_empty() {
+                    ui.label(
+                        egui::RichText::new("No map metadata found in this split")
+                            .italics()
+                            .color(egui::Color32::GRAY),
+                    );
+                    return;
+                }
+
+                render_match_mode(ui, &mut app.filter.criteria.invert_maps);
+
+                // Quick-select shortcuts for common maps present in this split
+                ui.horizontal_wrapped(|ui| {
+                    if ui
+                        .selectable_label(!app.filter.criteria.has_map_filter(), "All Maps")
+                        .clicked()
+                    {
+                        app.filter.criteria.map_filter = None;
+                    }
+                    for map in COMMON_MAPS {
+                        if !app.filter.available_maps.iter().any(|m| m == map) {
+                            continue;
+                        }
+                        if ui
+                            .small_button(format!("Only {}", map))
+                            .on_hover_text(format!("Select only {}", map))
+                            .clicked()
+                        {
+                            app.filter.criteria.map_filter = Some(vec![map.to_string()]);
+                        }
+                    }
+                });
+
+                ui.add_space(5.0);
+
+                ui.horizontal_wrapped(|ui| {
+                    for map in &app.filter.available_maps {
+                        let mut allowed = app.filter.criteria.has_map_filter()
+                            && app.filter.criteria.allows_map(Some(map));
+                        if ui.checkbox(&mut allowed, map).changed() {
+                            app.filter.criteria.set_map_allowed(map, allowed);
+                        }
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            // Confidence Filter Section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Confidence", Icon::GAUGE))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    let mut enabled = app.filter.criteria.low_confidence_below.is_some();
+                    if ui
+                        .checkbox(&mut enabled, "Any detection with confidence below")
+                        .changed()
+                    {
+                        app.filter.criteria.low_confidence_below = enabled.then_some(0.5);
+                    }
+                    if let Some(threshold) = &mut app.filter.criteria.low_confidence_below {
+                        ui.add(egui::Slider::new(threshold, 0.0..=1.0).fixed_decimals(2));
+                    }
+                });
+                ui.label(
+                    egui::RichText::new("Only labels with a 6th confidence column are matched")
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(10.0);
+
+            // Unknown Class Filter Section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Class IDs", Icon::QUESTION))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                ui.checkbox(
+                    &mut app.filter.criteria.unknown_classes,
+                    "Unknown classes only (class ID not in the class table)",
+                )
+                .on_hover_text("Find labels to relabel or delete");
+            });
+
+            ui.add_space(10.0);
+
+            // Bookmarks Filter Section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Bookmarks", Icon::STAR))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                ui.checkbox(
+                    &mut app.filter.criteria.bookmarked_only,
+                    format!("Bookmarked only ({} bookmarks)", app.bookmarks.len()),
+                );
+            });
+
             ui.add_space(15.0);
 
             // Preview count (live calculation based on current criteria)
@@ -130,17 +641,39 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                             .italics(),
                     );
 
-                    // Calculate preview count in real-time
+                    // Calculate preview count in real-time, from the cache when it can answer
                     let image_files = app.dataset.get_image_files();
-                    let preview_indices =
-                        crate::core::filter::apply_filters(image_files, &app.filter.criteria);
+                    let indexed = app
+                        .categories
+                        .get_indexed(image_files.len())
+                        .filter(|_| app.filter.criteria.is_indexable());
+                    let matching = match indexed {
+                        Some((categories, detections)) => image_files
+                            .iter()
+                            .zip(categories.iter().zip(detections))
+                            .filter(|(path, (&category, &count))| {
+                                app.filter.criteria.matches_indexed(
+                                    category,
+                                    count,
+                                    app.bookmarks.contains(path),
+                                )
+                            })
+                            .count(),
+                        None => crate::core::filter::apply_filters(
+                            image_files,
+                            &app.filter.criteria,
+                            &app.bookmarks,
+                            &app.config.class_ids(),
+                        )
+                        .len(),
+                    };
                     let total = image_files.len();
 
                     ui.label(
                         egui::RichText::new(format!(
-                            "{} / {} images",
-                            preview_indices.len(),
-                            total
+                            "{} / {} images will match",
+                            format_count(matching),
+                            format_count(total)
                         ))
                         .strong()
                         .color(egui::Color32::from_rgb(100, 149, 237)),
@@ -149,6 +682,63 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                 ui.add_space(10.0);
             }
 
+            // Export the applied filter's images in navigation order
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Image List", Icon::LIST_BULLETS))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Columns:");
+                    let columns = &mut app.filter.export_columns;
+                    ui.checkbox(&mut columns.category, "Category");
+                    ui.checkbox(&mut columns.detections, "Detections");
+                    ui.checkbox(&mut columns.map, "Map");
+                    ui.checkbox(&mut columns.location, "Location");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Paths:");
+                    ui.radio_value(&mut app.filter.export_relative, true, "Relative to dataset");
+                    ui.radio_value(&mut app.filter.export_relative, false, "Absolute");
+                });
+
+                let can_export = app.filter.is_active() && !app.filter.is_filtering();
+                if ui
+                    .add_enabled(
+                        can_export,
+                        egui::Button::new(format!(
+                            "{} Export list ({} images)",
+                            Icon::FLOPPY_DISK,
+                            format_count(app.filter.filtered_indices().len())
+                        )),
+                    )
+                    .on_hover_text("Write the filtered image paths, in navigation order, to a .txt or .csv file")
+                    .on_disabled_hover_text("Apply a filter first")
+                    .clicked()
+                {
+                    export_list = rfd::FileDialog::new()
+                        .add_filter("CSV", &["csv"])
+                        .add_filter("Text", &["txt"])
+                        .set_file_name("filtered_images.csv")
+                        .save_file();
+                }
+
+                if ui
+                    .button(format!("{} Load manifest as filter...", Icon::FOLDER_OPEN))
+                    .on_hover_text("Show only the images named in a text file (one filename or relative path per line)")
+                    .clicked()
+                {
+                    load_manifest = rfd::FileDialog::new()
+                        .add_filter("Manifest", &["txt", "csv", "lst"])
+                        .pick_file();
+                }
+            });
+
+            ui.add_space(15.0);
+
             ui.separator();
             ui.add_space(10.0);
 
@@ -190,14 +780,43 @@ pub fn render_filter_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
         });
 
     // Handle actions after the dialog is drawn
+    if let Some(idx) = load_preset {
+        app.apply_filter_preset(idx);
+    }
+
+    if compute_counts {
+        app.refresh_category_cache();
+    }
+
+    if let Some(path) = export_list {
+        app.export_filtered_list(&path);
+    }
+
+    if let Some(path) = load_manifest {
+        app.load_manifest_filter(&path);
+    }
+
+    if save_preset {
+        let name = app.filter.preset_name_input.clone();
+        app.save_filter_preset(&name);
+    }
+
+    if let Some(name) = delete_preset {
+        app.delete_filter_preset(&name);
+    }
+
     if apply_clicked {
         app.apply_filters();
     }
 
-    if clear_clicked {
+    if clear_clicked && app.filter.manifest.is_some() {
+        app.clear_filters();
+        app.apply_filters();
+    } else if clear_clicked {
         app.filter.criteria.clear();
+        app.filter.active_preset = None;
         // Optionally apply immediately after clearing
-        if !app.filter.filtered_indices.is_empty() {
+        if !app.filter.filtered_indices().is_empty() {
             app.clear_filters();
             app.apply_filters(); // Resets to show all
         }
@@ -0,0 +1,355 @@
+use crate::app::DatasetCleanerApp;
+use crate::core::analysis::ImageCategory;
+use crate::core::dataset::{parse_label_file, DatasetSplit};
+use crate::core::operations::get_label_path_for_image;
+use crate::state::FlipSource;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+use std::path::Path;
+
+/// Longest edge of the flipped preview in pixels
+const PREVIEW_SIZE: u32 = 360;
+
+const CATEGORIES: [ImageCategory; 5] = [
+    ImageCategory::Background,
+    ImageCategory::CTOnly,
+    ImageCategory::TOnly,
+    ImageCategory::MultiplePlayer,
+    ImageCategory::HardCase,
+];
+
+/// Decode a mirrored, downscaled texture of an image
+fn load_flipped_preview(ctx: &egui::Context, path: &Path) -> Option<egui::TextureHandle> {
+    let img = image::open(path).ok()?;
+    let flipped = img.fliph().thumbnail(PREVIEW_SIZE, PREVIEW_SIZE).to_rgba8();
+    let size = [flipped.width() as _, flipped.height() as _];
+    let color_image =
+        egui::ColorImage::from_rgba_unmultiplied(size, flipped.as_flat_samples().as_slice());
+    Some(ctx.load_texture(
+        format!("flip_preview_{}", path.display()),
+        color_image,
+        egui::TextureOptions::LINEAR,
+    ))
+}
+
+fn source_name(source: FlipSource) -> String {
+    match source {
+        FlipSource::View => "Current view".to_string(),
+        FlipSource::Category(category) => category.as_str().to_string(),
+    }
+}
+
+/// Render the flip augmentation dialog (Tools > Flip Augment)
+pub fn render_flip_augment_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.flip_augment.show_dialog {
+        return;
+    }
+
+    let candidates = app.flip_candidates();
+    let count = candidates.as_ref().map_or(0, Vec::len);
+    if app.flip_augment.preview_index >= count {
+        app.flip_augment.preview_index = 0;
+    }
+    let preview_path = candidates
+        .as_ref()
+        .and_then(|c| c.get(app.flip_augment.preview_index))
+        .cloned();
+
+    // Decode the preview when the previewed image changes
+    let stale = match (&app.flip_augment.preview, &preview_path) {
+        (Some((shown, _)), Some(path)) => shown != path,
+        (None, Some(_)) => true,
+        _ => false,
+    };
+    if stale {
+        if let Some(path) = &preview_path {
+            let texture = load_flipped_preview(ctx, path);
+            app.flip_augment.preview = Some((path.clone(), texture));
+        }
+    }
+
+    let mut start_clicked = false;
+    let mut cancel_clicked = false;
+    let mut remove_clicked = false;
+    let mut close_dialog = false;
+
+    egui::Window::new(format!("{} Flip Augment", Icon::FLIP_HORIZONTAL))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(420.0);
+
+            ui.label(format!(
+                "Write horizontally flipped `_flip` copies of images in the {} split, with mirrored labels.",
+                app.dataset.current_split().as_str()
+            ));
+            ui.add_space(10.0);
+
+            ui.add_enabled_ui(!app.flip_augment.processing, |ui| {
+                ui.group(|ui| {
+                    egui::Grid::new("flip_augment_options")
+                        .num_columns(2)
+                        .spacing([10.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label("Images:");
+                            egui::ComboBox::from_id_salt("flip_source")
+                                .selected_text(source_name(app.flip_augment.source))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut app.flip_augment.source,
+                                        FlipSource::View,
+                                        source_name(FlipSource::View),
+                                    )
+                                    .on_hover_text("The filtered images when a filter is active (e.g. one map), otherwise the whole split");
+                                    for category in CATEGORIES {
+                                        ui.selectable_value(
+                                            &mut app.flip_augment.source,
+                                            FlipSource::Category(category),
+                                            category.as_str(),
+                                        );
+                                    }
+                                });
+                            ui.end_row();
+
+                            ui.label("Write to:");
+                            let destination_name = |split: Option<DatasetSplit>| match split {
+                                Some(split) => split.as_str().to_uppercase(),
+                                None => "Same split".to_string(),
+                            };
+                            egui::ComboBox::from_id_salt("flip_destination")
+                                .selected_text(destination_name(app.flip_augment.destination))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut app.flip_augment.destination,
+                                        None,
+                                        destination_name(None),
+                                    );
+                                    for split in DatasetSplit::all() {
+                                        if split != app.dataset.current_split()
+                                            && app.dataset.has_split(split)
+                                        {
+                                            ui.selectable_value(
+                                                &mut app.flip_augment.destination,
+                                                Some(split),
+                                                destination_name(Some(split)),
+                                            );
+                                        }
+                                    }
+                                });
+                            ui.end_row();
+                        });
+                });
+            });
+
+            ui.add_space(10.0);
+
+            // Preview of the flipped image with its mirrored boxes
+            match &candidates {
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Categorizing images...");
+                    });
+                    ctx.request_repaint();
+                }
+                Some(_) if count == 0 => {
+                    ui.label(
+                        egui::RichText::new("No images match the selection")
+                            .color(egui::Color32::GRAY),
+                    );
+                }
+                Some(_) => {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("◀").clicked() {
+                            app.flip_augment.preview_index =
+                                (app.flip_augment.preview_index + count - 1) % count;
+                        }
+                        ui.label(format!(
+                            "Preview {} / {}",
+                            app.flip_augment.preview_index + 1,
+                            count
+                        ));
+                        if ui.small_button("▶").clicked() {
+                            app.flip_augment.preview_index =
+                                (app.flip_augment.preview_index + 1) % count;
+                        }
+                    });
+
+                    if let (Some(path), Some((_, texture))) =
+                        (&preview_path, &app.flip_augment.preview)
+                    {
+                        match texture {
+                            Some(texture) => {
+                                let response = ui.image(texture);
+                                let rect = response.rect;
+                                let detections = get_label_path_for_image(path)
+                                    .and_then(|label| parse_label_file(&label))
+                                    .map(|info| info.detections)
+                                    .unwrap_or_default();
+                                let painter = ui.painter_at(rect);
+                                for detection in detections {
+                                    let x_center = 1.0 - detection.x_center;
+                                    let min = egui::pos2(
+                                        rect.min.x + (x_center - detection.width / 2.0) * rect.width(),
+                                        rect.min.y
+                                            + (detection.y_center - detection.height / 2.0)
+                                                * rect.height(),
+                                    );
+                                    let size = egui::vec2(
+                                        detection.width * rect.width(),
+                                        detection.height * rect.height(),
+                                    );
+                                    let (color, _) = app.config.get_class_colors(detection.class_id);
+                                    painter.rect_stroke(
+                                        egui::Rect::from_min_size(min, size),
+                                        0.0,
+                                        egui::Stroke::new(2.0, color),
+                                    );
+                                }
+                            }
+                            None => {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} Could not load preview",
+                                        Icon::IMAGE_BROKEN
+                                    ))
+                                    .color(egui::Color32::GRAY),
+                                );
+                            }
+                        }
+                        if let Some(name) = path.file_name() {
+                            ui.label(egui::RichText::new(name.to_string_lossy()).monospace().small());
+                        }
+                    }
+                }
+            }
+
+            ui.add_space(10.0);
+            ui.label(egui::RichText::new(format!("{} images will be generated", count)).strong());
+
+            // Progress of the running batch
+            if app.flip_augment.processing {
+                if let Some((current, total)) = app.flip_augment.progress {
+                    let fraction = if total > 0 {
+                        current as f32 / total as f32
+                    } else {
+                        0.0
+                    };
+                    ui.add(
+                        egui::ProgressBar::new(fraction)
+                            .text(format!("{} / {} images", current, total))
+                            .animate(true),
+                    );
+                } else {
+                    ui.spinner();
+                }
+                ctx.request_repaint();
+            }
+
+            // Report of the last batch
+            if let Some(report) = &app.flip_augment.report {
+                let heading = if app.flip_augment.cancelled {
+                    "Cancelled"
+                } else {
+                    "Done"
+                };
+                ui.label(format!(
+                    "{}: {} of {} flipped copies written",
+                    heading,
+                    report.generated.len(),
+                    report.total
+                ));
+                if !report.errors.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 100, 100),
+                        format!("{} {} images failed", Icon::WARNING, report.errors.len()),
+                    );
+                }
+            }
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            // Generated files recorded in the manifest
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "{} generated files on record",
+                    app.flip_augment.manifest_count
+                ));
+                let enabled = app.flip_augment.manifest_count > 0 && !app.flip_augment.processing;
+                if app.flip_augment.confirm_remove {
+                    if ui
+                        .button(
+                            egui::RichText::new(format!("{} Confirm Remove", Icon::TRASH))
+                                .color(egui::Color32::from_rgb(255, 100, 100)),
+                        )
+                        .clicked()
+                    {
+                        remove_clicked = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        app.flip_augment.confirm_remove = false;
+                    }
+                } else if ui
+                    .add_enabled(
+                        enabled,
+                        egui::Button::new(format!("{} Remove Generated", Icon::TRASH)),
+                    )
+                    .on_hover_text("Permanently delete every augmented copy listed in .cleaner/augmented.json")
+                    .clicked()
+                {
+                    app.flip_augment.confirm_remove = true;
+                }
+            });
+            if let Some(message) = &app.flip_augment.remove_message {
+                ui.label(message);
+            }
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if app.flip_augment.processing {
+                    if ui.button(format!("{} Cancel", Icon::X)).clicked() {
+                        cancel_clicked = true;
+                    }
+                    return;
+                }
+
+                if ui
+                    .add_enabled(
+                        count > 0,
+                        egui::Button::new(format!("{} Generate", Icon::CHECK)),
+                    )
+                    .clicked()
+                {
+                    start_clicked = true;
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    // Handle actions after the dialog is drawn
+    if start_clicked {
+        app.start_flip_augment();
+    }
+
+    if cancel_clicked {
+        app.cancel_flip_augment();
+    }
+
+    if remove_clicked {
+        app.remove_generated_augmentations();
+    }
+
+    if close_dialog {
+        app.flip_augment.show_dialog = false;
+        app.flip_augment.preview = None;
+    }
+}
@@ -0,0 +1,144 @@
+use crate::app::DatasetCleanerApp;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Render the undo history dialog (History button in the bottom panel)
+pub fn render_history_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.ui.show_history_dialog {
+        return;
+    }
+
+    let mut undo_through = None;
+    let mut undo_only = None;
+    let mut close_dialog = false;
+    let manager = &app.undo_manager;
+
+    egui::Window::new(format!("{} Undo History", Icon::CLOCK_COUNTER_CLOCKWISE))
+        .collapsible(false)
+        .resizable(true)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(560.0);
+
+            // What Ctrl+Z and Ctrl+Y would do next
+            let next_undo = match manager.peek_undo() {
+                Some(action) => format!("Ctrl+Z undoes the {}", action.description()),
+                None => "Nothing to undo".to_string(),
+            };
+            ui.label(egui::RichText::new(next_undo).strong());
+            if let Some(action) = manager.peek_redo() {
+                ui.label(format!("Ctrl+Y redoes the {}", action.description()));
+            }
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} of {} actions kept (Settings > Deletion)",
+                    manager.undo_count(),
+                    manager.max_depth()
+                ))
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            ui.add_space(8.0);
+
+            if manager.undo_count() == 0 && manager.redo_count() == 0 {
+                ui.label("No actions yet.");
+            } else {
+                egui::ScrollArea::vertical()
+                    .max_height(360.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("undo_history_grid")
+                            .num_columns(4)
+                            .spacing([12.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label(egui::RichText::new("Time").strong());
+                                ui.label(egui::RichText::new("Type").strong());
+                                ui.label(egui::RichText::new("Item").strong());
+                                ui.label("");
+                                ui.end_row();
+
+                                // Undone actions, newest first; the last one is redone next
+                                for entry in manager.redo_stack() {
+                                    let gray = |text: String| {
+                                        egui::RichText::new(text).color(egui::Color32::GRAY)
+                                    };
+                                    ui.label(gray(entry.recorded_at.format("%H:%M:%S").to_string()).monospace());
+                                    ui.label(gray(entry.action.kind().to_string()));
+                                    ui.label(gray(entry.action.description()));
+                                    ui.label(gray("undone".to_string()).italics());
+                                    ui.end_row();
+                                }
+
+                                // Undoable actions, newest first
+                                let newest = manager.undo_count().saturating_sub(1);
+                                for (index, entry) in manager.undo_stack().iter().enumerate().rev() {
+                                    ui.label(
+                                        egui::RichText::new(entry.recorded_at.format("%H:%M:%S").to_string())
+                                            .monospace(),
+                                    );
+                                    ui.label(entry.action.kind());
+                                    if index == newest {
+                                        ui.label(egui::RichText::new(entry.action.description()).strong());
+                                    } else {
+                                        ui.label(entry.action.description());
+                                    }
+
+                                    ui.horizontal(|ui| {
+                                        let newer = newest - index;
+                                        let through_tooltip = if newer == 0 {
+                                            "Undo this action (Ctrl+Z)".to_string()
+                                        } else {
+                                            format!("Undo this action and the {} newer ones", newer)
+                                        };
+                                        if ui
+                                            .small_button(format!("{} Undo to here", Icon::ARROW_COUNTER_CLOCKWISE))
+                                            .on_hover_text(through_tooltip)
+                                            .clicked()
+                                        {
+                                            undo_through = Some(index);
+                                        }
+
+                                        if index != newest
+                                            && ui
+                                                .add_enabled(
+                                                    manager.can_undo_out_of_order(index),
+                                                    egui::Button::new("Only this").small(),
+                                                )
+                                                .on_hover_text("Undo this action and keep the newer ones")
+                                                .on_disabled_hover_text(
+                                                    "A newer action changes the same files, or this action is not undoable here",
+                                                )
+                                                .clicked()
+                                        {
+                                            undo_only = Some(index);
+                                        }
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Close").clicked() {
+                    close_dialog = true;
+                }
+            });
+        });
+
+    // Handle actions after the dialog is drawn
+    if let Some(index) = undo_through {
+        app.undo_through(index);
+    }
+
+    if let Some(index) = undo_only {
+        app.undo_out_of_order(index);
+    }
+
+    if close_dialog {
+        app.ui.show_history_dialog = false;
+    }
+}
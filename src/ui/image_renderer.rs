@@ -1,6 +1,6 @@
 use crate::config::AppConfig;
 use crate::core::dataset::{LabelInfo, YoloDetection};
-use eframe::egui::{self, Color32, Painter, Rect, Vec2};
+use eframe::egui::{self, Painter, Rect, Vec2};
 
 /// Image rendering utilities for displaying images and bounding boxes
 pub struct ImageRenderer;
@@ -22,13 +22,18 @@ impl ImageRenderer {
     /// * `image_rect` - The rectangle where the image is displayed on screen
     /// * `actual_image_size` - The actual loaded image dimensions
     /// * `config` - Application configuration for class names and colors
+    /// * `selected` - Index of the detection to highlight, if any
+    ///
+    /// # Returns
+    /// * Screen rects of the drawn boxes, in detection order (for hit testing)
     pub fn draw_bounding_boxes(
         painter: &Painter,
         label: &LabelInfo,
         image_rect: Rect,
         actual_image_size: Vec2,
         config: &AppConfig,
-    ) {
+        selected: Option<usize>,
+    ) -> Vec<Rect> {
         // Parse the original resolution from label metadata if available
         // This is the resolution the YOLO coordinates were generated for
         let original_resolution = Self::parse_resolution_from_label(label);
@@ -36,18 +41,36 @@ impl ImageRenderer {
         // Get the displayed image size from the rect
         let displayed_size = image_rect.size();
         
-        for (i, detection) in label.detections.iter().enumerate() {
-            Self::draw_single_box(
-                painter,
-                detection,
-                i,
-                image_rect,
-                original_resolution,
-                actual_image_size,
-                displayed_size,
-                config,
-            );
-        }
+        label
+            .detections
+            .iter()
+            .enumerate()
+            .map(|(i, detection)| {
+                Self::draw_single_box(
+                    painter,
+                    detection,
+                    i,
+                    image_rect,
+                    original_resolution,
+                    actual_image_size,
+                    displayed_size,
+                    config,
+                    selected == Some(i),
+                )
+            })
+            .collect()
+    }
+
+    /// Find the detection under a screen position
+    ///
+    /// When boxes overlap, the smallest one wins so nested boxes stay reachable.
+    pub fn hit_test(box_rects: &[Rect], pos: egui::Pos2) -> Option<usize> {
+        box_rects
+            .iter()
+            .enumerate()
+            .filter(|(_, rect)| rect.contains(pos))
+            .min_by(|(_, a), (_, b)| a.area().total_cmp(&b.area()))
+            .map(|(i, _)| i)
     }
 
     /// Parse resolution from label metadata (e.g., "2560x1440")
@@ -75,6 +98,8 @@ impl ImageRenderer {
     /// * `actual_image_size` - The actual current image file dimensions
     /// * `displayed_size` - The size of the displayed image on screen
     /// * `config` - Application configuration for class names and colors
+    /// * `is_selected` - Whether to draw the box highlighted (thicker stroke)
+    #[allow(clippy::too_many_arguments)]
     fn draw_single_box(
         painter: &Painter,
         detection: &YoloDetection,
@@ -84,7 +109,8 @@ impl ImageRenderer {
         actual_image_size: Vec2,
         displayed_size: Vec2,
         config: &AppConfig,
-    ) {
+        is_selected: bool,
+    ) -> Rect {
         // YOLO coordinates are normalized (0-1) relative to the ORIGINAL resolution
         // We need to: normalized -> original pixels -> actual pixels -> displayed pixels
         
@@ -131,14 +157,19 @@ impl ImageRenderer {
         // Draw filled rectangle
         painter.rect_filled(bbox_rect, 0.0, fill_color);
 
-        // Draw border
-        painter.rect_stroke(bbox_rect, 0.0, egui::Stroke::new(2.0, stroke_color));
+        // Draw border (thicker when selected)
+        let stroke_width = if is_selected { 4.0 } else { 2.0 };
+        painter.rect_stroke(bbox_rect, 0.0, egui::Stroke::new(stroke_width, stroke_color));
 
         // Draw label text
         let class_name = config.get_class_name(detection.class_id);
-        let label_text = format!("{} #{}", class_name, index + 1);
+        let label_text = match detection.confidence {
+            Some(confidence) => format!("{} #{} ({:.2})", class_name, index + 1, confidence),
+            None => format!("{} #{}", class_name, index + 1),
+        };
         let font_id = egui::FontId::proportional(14.0);
-        let text_galley = painter.layout_no_wrap(label_text, font_id, Color32::WHITE);
+        let text_color = config.get_class_text_color(detection.class_id);
+        let text_galley = painter.layout_no_wrap(label_text, font_id, text_color);
 
         // Draw text background
         let text_pos = bbox_rect.min + egui::vec2(2.0, -18.0);
@@ -147,6 +178,8 @@ impl ImageRenderer {
         painter.rect_filled(text_bg_rect, 2.0, stroke_color);
 
         // Draw text
-        painter.galley(text_pos + egui::vec2(3.0, 0.0), text_galley, Color32::WHITE);
+        painter.galley(text_pos + egui::vec2(3.0, 0.0), text_galley, text_color);
+
+        bbox_rect
     }
 }
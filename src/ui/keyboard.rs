@@ -12,10 +12,56 @@ pub fn handle_keyboard_shortcuts(app: &mut DatasetCleanerApp, ctx: &egui::Contex
     if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
         info!("[KEYBOARD] Escape key pressed");
 
-        // Priority order: filter dialog, batch confirmation, batch processing
-        if app.ui.show_filter_dialog {
+        // Priority order: dataset lock warning (stays read-only), shortcuts, filter dialog, settings, bookmarks, session stats, undo history, dataset summary, rename dialog, strip metadata, convert format, flip augment, crop export, batch confirmation, batch processing
+        if app.dataset_lock.conflict.is_some() {
+            app.open_read_only();
+            info!("[KEYBOARD] Closed dataset lock dialog (read-only)");
+        } else if app.ui.show_shortcuts_dialog {
+            app.ui.show_shortcuts_dialog = false;
+            info!("[KEYBOARD] Closed keyboard shortcuts dialog");
+        } else if app.ui.show_filter_dialog {
             app.ui.show_filter_dialog = false;
             info!("[KEYBOARD] Closed filter dialog");
+        } else if app.ui.show_settings_dialog {
+            app.close_settings_dialog();
+            info!("[KEYBOARD] Closed settings dialog");
+        } else if app.bookmark_dialog.show_dialog {
+            app.bookmark_dialog.show_dialog = false;
+            info!("[KEYBOARD] Closed bookmarks dialog");
+        } else if app.ui.show_session_stats {
+            app.ui.show_session_stats = false;
+            info!("[KEYBOARD] Closed session statistics dialog");
+        } else if app.ui.show_history_dialog {
+            app.ui.show_history_dialog = false;
+            info!("[KEYBOARD] Closed undo history dialog");
+        } else if app.summary.show_dialog {
+            app.summary.show_dialog = false;
+            info!("[KEYBOARD] Closed dataset summary");
+        } else if app.ui.empty_trash_preview.is_some() {
+            app.ui.empty_trash_preview = None;
+            info!("[KEYBOARD] Closed empty cleaner trash dialog");
+        } else if app.rename.show_dialog {
+            app.rename.show_dialog = false;
+            info!("[KEYBOARD] Closed batch rename dialog");
+        } else if app.strip_metadata.show_dialog && !app.strip_metadata.processing {
+            app.strip_metadata.show_dialog = false;
+            info!("[KEYBOARD] Closed strip metadata dialog");
+        } else if app.convert.show_dialog && !app.convert.processing {
+            app.convert.show_dialog = false;
+            info!("[KEYBOARD] Closed convert format dialog");
+        } else if app.flip_augment.show_dialog && !app.flip_augment.processing {
+            app.flip_augment.show_dialog = false;
+            app.flip_augment.preview = None;
+            info!("[KEYBOARD] Closed flip augment dialog");
+        } else if app.crop_export.show_dialog && !app.crop_export.processing {
+            app.crop_export.show_dialog = false;
+            info!("[KEYBOARD] Closed crop export dialog");
+        } else if app.export_subset.pending_dest.is_some() {
+            app.export_subset.pending_dest = None;
+            info!("[KEYBOARD] Closed export subset confirmation");
+        } else if app.ui.pending_yaml_overwrite.is_some() {
+            app.ui.pending_yaml_overwrite = None;
+            info!("[KEYBOARD] Closed dataset.yaml overwrite confirmation");
         } else if app.ui.show_batch_delete_confirm {
             app.ui.show_batch_delete_confirm = false;
             info!("[KEYBOARD] Closed batch delete confirmation dialog");
@@ -43,13 +89,13 @@ pub fn handle_keyboard_shortcuts(app: &mut DatasetCleanerApp, ctx: &egui::Contex
 
     if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Equals)) {
         info!("[KEYBOARD] Ctrl+= pressed - Zoom in");
-        app.image.zoom_level = (app.image.zoom_level + 0.1).clamp(0.5, 3.0);
+        app.set_zoom(app.image.zoom_level + 0.1);
         return;
     }
 
     if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Minus)) {
         info!("[KEYBOARD] Ctrl+- pressed - Zoom out");
-        app.image.zoom_level = (app.image.zoom_level - 0.1).clamp(0.5, 3.0);
+        app.set_zoom(app.image.zoom_level - 0.1);
         return;
     }
 
@@ -67,7 +113,72 @@ pub fn handle_keyboard_shortcuts(app: &mut DatasetCleanerApp, ctx: &egui::Contex
 
     if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::F)) {
         info!("[KEYBOARD] Ctrl+F pressed - Open filter dialog");
-        app.ui.show_filter_dialog = true;
+        app.open_filter_dialog();
+        return;
+    }
+
+    // Ctrl+1..9 - Apply the first nine filter presets
+    let preset_keys = [
+        egui::Key::Num1,
+        egui::Key::Num2,
+        egui::Key::Num3,
+        egui::Key::Num4,
+        egui::Key::Num5,
+        egui::Key::Num6,
+        egui::Key::Num7,
+        egui::Key::Num8,
+        egui::Key::Num9,
+    ];
+    if let Some(idx) = preset_keys
+        .iter()
+        .position(|&key| ctx.input(|i| i.modifiers.ctrl && i.key_pressed(key)))
+    {
+        info!("[KEYBOARD] Ctrl+{} pressed - Apply filter preset", idx + 1);
+        app.apply_filter_preset(idx);
+        return;
+    }
+
+    // Ctrl+? or F1 - Show keyboard shortcuts (? is Shift+/ on most layouts)
+    if ctx.input(|i| {
+        (i.modifiers.ctrl
+            && (i.key_pressed(egui::Key::Questionmark) || i.key_pressed(egui::Key::Slash)))
+            || i.key_pressed(egui::Key::F1)
+    }) {
+        info!("[KEYBOARD] Ctrl+? pressed - Show keyboard shortcuts");
+        app.ui.show_shortcuts_dialog = true;
+        return;
+    }
+
+    // Shift+Left/Right - Previous/next image of the category picked in the bottom panel
+    if ctx.input(|i| i.modifiers.shift && i.key_pressed(egui::Key::ArrowRight)) {
+        info!("[KEYBOARD] Shift+Right pressed - Next {} image", app.ui.jump_category.as_str());
+        app.jump_to_category(app.ui.jump_category, true);
+        return;
+    }
+
+    if ctx.input(|i| i.modifiers.shift && i.key_pressed(egui::Key::ArrowLeft)) {
+        info!("[KEYBOARD] Shift+Left pressed - Previous {} image", app.ui.jump_category.as_str());
+        app.jump_to_category(app.ui.jump_category, false);
+        return;
+    }
+
+    // Alt+Left/Right - Jump by 10 images
+    if ctx.input(|i| i.modifiers.alt && i.key_pressed(egui::Key::ArrowRight)) {
+        info!("[KEYBOARD] Alt+Right pressed - Jump forward 10");
+        app.jump_by_offset(10);
+        return;
+    }
+
+    if ctx.input(|i| i.modifiers.alt && i.key_pressed(egui::Key::ArrowLeft)) {
+        info!("[KEYBOARD] Alt+Left pressed - Jump back 10");
+        app.jump_by_offset(-10);
+        return;
+    }
+
+    // Alt+E - Next image that fails to load
+    if ctx.input(|i| i.modifiers.alt && i.key_pressed(egui::Key::E)) {
+        info!("[KEYBOARD] Alt+E pressed - Next unreadable image");
+        app.navigate_to_next_error();
         return;
     }
 
@@ -82,7 +193,7 @@ pub fn handle_keyboard_shortcuts(app: &mut DatasetCleanerApp, ctx: &egui::Contex
         app.prev_image();
     }
 
-    // Jump to first/last image
+    // Jump to first/last image (Home/End, with or without Ctrl)
     if ctx.input(|i| i.key_pressed(egui::Key::Home)) {
         info!("[KEYBOARD] Home key pressed");
         app.jump_to_first();
@@ -110,6 +221,18 @@ pub fn handle_keyboard_shortcuts(app: &mut DatasetCleanerApp, ctx: &egui::Contex
         app.toggle_fullscreen();
     }
 
+    // P - Pin the current image as the comparison reference (or unpin it)
+    if ctx.input(|i| i.key_pressed(egui::Key::P)) {
+        info!("[KEYBOARD] P pressed - Toggle pinned reference");
+        app.toggle_pin_current_image();
+    }
+
+    // B - Bookmark the current image (or remove its bookmark)
+    if ctx.input(|i| i.key_pressed(egui::Key::B)) {
+        info!("[KEYBOARD] B pressed - Toggle bookmark");
+        app.toggle_bookmark_current_image();
+    }
+
     // Delete current image
     if ctx.input(|i| i.key_pressed(egui::Key::Delete)) {
         info!("[KEYBOARD] Delete key pressed!");
@@ -121,27 +244,27 @@ pub fn handle_keyboard_shortcuts(app: &mut DatasetCleanerApp, ctx: &egui::Contex
         }
     }
 
-    // Ctrl+Z - Undo delete
+    // Ctrl+Z - Undo the last delete or label edit
     if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Z)) {
-        info!("[KEYBOARD] Ctrl+Z pressed - Undo delete");
+        info!("[KEYBOARD] Ctrl+Z pressed - Undo");
         if app.undo_manager.can_undo() {
-            app.undo_delete();
+            app.undo();
         }
     }
 
-    // Ctrl+Y - Redo delete (Windows standard)
+    // Ctrl+Y - Redo (Windows standard)
     if ctx.input(|i| i.modifiers.ctrl && !i.modifiers.shift && i.key_pressed(egui::Key::Y)) {
-        info!("[KEYBOARD] Ctrl+Y pressed - Redo delete");
+        info!("[KEYBOARD] Ctrl+Y pressed - Redo");
         if app.undo_manager.can_redo() {
-            app.redo_delete();
+            app.redo();
         }
     }
 
-    // Ctrl+Shift+Z - Redo delete (cross-platform alternative)
+    // Ctrl+Shift+Z - Redo (cross-platform alternative)
     if ctx.input(|i| i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(egui::Key::Z)) {
-        info!("[KEYBOARD] Ctrl+Shift+Z pressed - Redo delete");
+        info!("[KEYBOARD] Ctrl+Shift+Z pressed - Redo");
         if app.undo_manager.can_redo() {
-            app.redo_delete();
+            app.redo();
         }
     }
 
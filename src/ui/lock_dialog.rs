@@ -0,0 +1,72 @@
+use crate::app::DatasetCleanerApp;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Render the warning shown when another instance has the dataset open
+pub fn render_lock_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    let Some(holder) = app.dataset_lock.conflict.clone() else {
+        return;
+    };
+
+    let mut read_only = false;
+    let mut take_over = false;
+
+    egui::Window::new(format!("{} Dataset In Use", Icon::LOCK))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(380.0);
+
+            let started = chrono::DateTime::from_timestamp(holder.started_at as i64, 0)
+                .filter(|_| holder.started_at > 0)
+                .map(|time| {
+                    time.with_timezone(&chrono::Local)
+                        .format(", started %Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            ui.label(format!(
+                "Another instance of the cleaner (PID {}{}) has this dataset open.",
+                holder.pid, started
+            ));
+            if let Some(path) = app.dataset.dataset_path() {
+                ui.label(egui::RichText::new(path.display().to_string()).monospace().small());
+            }
+            ui.add_space(5.0);
+            ui.label("Changes from both instances can overwrite each other, e.g. two rebalances moving the same images.");
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 150, 0),
+                "⚠ Take over only if the other instance is hung or about to be closed",
+            );
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(format!("{} Open Read-Only", Icon::EYE))
+                    .on_hover_text("Browse and analyze; deleting, moving and editing are disabled")
+                    .clicked()
+                {
+                    read_only = true;
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .button(format!("{} Take Over", Icon::LOCK_OPEN))
+                        .on_hover_text("Replace the other instance's lock with this one")
+                        .clicked()
+                    {
+                        take_over = true;
+                    }
+                });
+            });
+        });
+
+    if take_over {
+        app.take_over_dataset_lock();
+    } else if read_only {
+        app.open_read_only();
+    }
+}
@@ -1,24 +1,70 @@
 pub mod balance_dialog;
 pub mod batch_dialogs;
+pub mod bookmarks_dialog;
+pub mod convert_dialog;
+pub mod crop_export_dialog;
+pub mod distribution_bars;
 pub mod filter_dialog;
+pub mod flip_augment_dialog;
+pub mod history_dialog;
 pub mod image_renderer;
 pub mod keyboard;
+pub mod lock_dialog;
 pub mod panels;
 pub mod rebalance_dialog;
+pub mod rename_dialog;
+pub mod session_stats_dialog;
+pub mod settings_dialog;
+pub mod shortcuts_dialog;
+pub mod strip_metadata_dialog;
+pub mod summary_dialog;
 pub mod toast;
+pub mod trash_dialog;
 
 // Re-export commonly used functions
-pub use panels::{render_bottom_panel, render_central_panel, render_label_panel, render_top_panel};
+pub use panels::{
+    render_bottom_panel, render_central_panel, render_label_panel, render_log_panel, render_minimap,
+    render_status_bar, render_top_panel,
+};
 
 pub use keyboard::handle_keyboard_shortcuts;
 
-pub use batch_dialogs::{render_batch_delete_confirmation, render_batch_progress};
+pub use batch_dialogs::{
+    render_batch_delete_confirmation, render_batch_progress, render_export_subset_confirmation,
+    render_export_subset_progress, render_yaml_overwrite_confirmation,
+};
 
-pub use toast::render_toast_notification;
+pub use toast::{render_message_toast, render_toast_notification};
+
+pub use bookmarks_dialog::render_bookmarks_dialog;
+
+pub use convert_dialog::render_convert_dialog;
+
+pub use crop_export_dialog::render_crop_export_dialog;
+
+pub use flip_augment_dialog::render_flip_augment_dialog;
+
+pub use trash_dialog::render_empty_trash_dialog;
 
 pub use filter_dialog::render_filter_dialog;
 
 pub use balance_dialog::render_balance_dialog;
 
+pub use history_dialog::render_history_dialog;
+
+pub use lock_dialog::render_lock_dialog;
+
 pub use rebalance_dialog::render_rebalance_dialog;
 
+pub use rename_dialog::render_rename_dialog;
+
+pub use session_stats_dialog::render_session_stats_dialog;
+
+pub use settings_dialog::render_settings_dialog;
+
+pub use shortcuts_dialog::render_shortcuts_dialog;
+
+pub use strip_metadata_dialog::render_strip_metadata_dialog;
+
+pub use summary_dialog::render_dataset_summary_dialog;
+
@@ -1,4 +1,6 @@
 use crate::app::DatasetCleanerApp;
+use crate::core::analysis::ImageCategory;
+use crate::state::AutoAnalysisStatus;
 use eframe::egui;
 use egui_phosphor::regular as Icon;
 
@@ -30,11 +32,12 @@ pub fn render_bottom_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
             ui.add_space(20.0);
             
             // Delete button
-            let delete_enabled = !app.dataset.get_image_files().is_empty();
+            let delete_enabled = !app.dataset.get_image_files().is_empty() && !app.is_read_only();
             let delete_btn = ui.add_enabled(
                 delete_enabled,
                 egui::Button::new(format!("{} Delete Image & Label", Icon::TRASH)).fill(egui::Color32::from_rgb(200, 50, 50)),
-            );
+            )
+            .on_disabled_hover_text(app.read_only_hint());
             
             if delete_btn.clicked() {
                 tracing::info!("[BUTTON] Delete button clicked!");
@@ -43,10 +46,115 @@ pub fn render_bottom_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
             && ui.input(|i| i.pointer.any_click()) {
                  tracing::warn!("[BUTTON] Delete button HOVERED and CLICKED (raw), but .clicked() is FALSE. Enabled: {}", delete_enabled);
             }
+
+            // Redo button (the next redo is named in the tooltip)
+            let redo_tooltip = match app.undo_manager.peek_redo() {
+                Some(action) => format!("Redo {} (Ctrl+Y)", action.description()),
+                None => "Nothing to redo".to_string(),
+            };
+            if ui
+                .add_enabled(
+                    app.undo_manager.can_redo(),
+                    egui::Button::new(format!("{} Redo", Icon::ARROW_CLOCKWISE)),
+                )
+                .on_hover_text(redo_tooltip.clone())
+                .on_disabled_hover_text(redo_tooltip)
+                .clicked()
+            {
+                tracing::info!("[BUTTON] Redo button clicked");
+                app.redo();
+            }
+
+            // Undo history (the next undo is named in the tooltip)
+            let history_tooltip = match app.undo_manager.peek_undo() {
+                Some(action) => format!("Next undo (Ctrl+Z): {}", action.description()),
+                None => "Nothing to undo".to_string(),
+            };
+            if ui
+                .button(format!("{} History", Icon::CLOCK_COUNTER_CLOCKWISE))
+                .on_hover_text(history_tooltip)
+                .clicked()
+            {
+                app.ui.show_history_dialog = true;
+            }
             
+            ui.add_space(20.0);
+
+            // Previous / next image of a category (from the category cache)
+            let category = app.ui.jump_category;
+            egui::ComboBox::from_id_salt("jump_category")
+                .selected_text(category.as_str())
+                .show_ui(ui, |ui| {
+                    for option in ImageCategory::ALL {
+                        ui.selectable_value(&mut app.ui.jump_category, option, option.as_str());
+                    }
+                });
+            let indexed = app.categories.get(app.dataset.get_image_files().len()).is_some();
+            if ui
+                .add_enabled(indexed, egui::Button::new(Icon::CARET_LEFT))
+                .on_hover_text(format!("Previous {} image (Shift+←)", category.as_str()))
+                .on_disabled_hover_text("Categorizing images...")
+                .clicked()
+            {
+                app.jump_to_category(category, false);
+            }
+            if ui
+                .add_enabled(indexed, egui::Button::new(Icon::CARET_RIGHT))
+                .on_hover_text(format!("Next {} image (Shift+→)", category.as_str()))
+                .on_disabled_hover_text("Categorizing images...")
+                .clicked()
+            {
+                app.jump_to_category(category, true);
+            }
+
+            ui.add_space(20.0);
+
+            // Pin the current image for side-by-side comparison
+            let is_pinned = app.image.pinned.as_ref().is_some_and(|pinned| {
+                app.dataset.get_image_files().get(app.current_index) == Some(&pinned.path)
+            });
+            let pin_text = if is_pinned {
+                format!("{} Unpin Reference", Icon::PUSH_PIN_SLASH)
+            } else {
+                format!("{} Pin as Reference", Icon::PUSH_PIN)
+            };
+            if ui
+                .add_enabled(app.image.texture.is_some(), egui::Button::new(pin_text))
+                .on_hover_text("Compare the next images side by side with this one (P)")
+                .clicked()
+            {
+                app.toggle_pin_current_image();
+            }
+
+            // Bookmark star (gold when the current image is bookmarked)
+            let is_bookmarked = app
+                .dataset
+                .get_image_files()
+                .get(app.current_index)
+                .is_some_and(|path| app.bookmarks.contains(path));
+            let star_color = if is_bookmarked {
+                egui::Color32::from_rgb(255, 200, 0)
+            } else {
+                egui::Color32::GRAY
+            };
+            if ui
+                .add_enabled(
+                    !app.dataset.get_image_files().is_empty(),
+                    egui::Button::new(egui::RichText::new(Icon::STAR).color(star_color)),
+                )
+                .on_hover_text(if is_bookmarked {
+                    "Remove bookmark (B)"
+                } else {
+                    "Bookmark this image (B)"
+                })
+                .clicked()
+            {
+                app.toggle_bookmark_current_image();
+            }
+
             ui.add_space(20.0);
             
-            // Batch delete black images button
+            // Batch delete black / white images button
             let button_text = if app.batch.processing {
                 if let Some(stats) = &app.batch.stats {
                     let total = stats.total_scanned.max(stats.current_progress);
@@ -60,14 +168,15 @@ pub fn render_bottom_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                     format!("{} Processing...", Icon::MAGIC_WAND)
                 }
             } else {
-                format!("{} Remove Black Images", Icon::MAGIC_WAND)
+                format!("{} Remove Black/White Images", Icon::MAGIC_WAND)
             };
             
             let button = egui::Button::new(&button_text).fill(egui::Color32::from_rgb(100, 100, 180));
             if ui.add_enabled(
-                !app.dataset.get_image_files().is_empty() && !app.batch.processing,
+                !app.dataset.get_image_files().is_empty() && !app.batch.processing && !app.is_read_only(),
                 button,
             )
+            .on_disabled_hover_text(app.read_only_hint())
             .clicked()
             {
                 app.ui.show_batch_delete_confirm = true;
@@ -81,8 +190,70 @@ pub fn render_bottom_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
             
             ui.add_space(20.0);
 
+            // Export a copy of the filtered images
+            let export_button = egui::Button::new(format!("{} Export Subset", Icon::EXPORT))
+                .fill(egui::Color32::from_rgb(80, 130, 150));
+            if ui
+                .add_enabled(
+                    !app.dataset.get_image_files().is_empty() && !app.export_subset.processing,
+                    export_button,
+                )
+                .on_hover_text("Copy the images in the current (filtered) view and their labels to a folder")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                    app.export_subset.pending_dest = Some(path);
+                }
+            }
+
+            ui.add_space(20.0);
+
+            // Extract the current image and its label (e.g. for a bug report)
+            let has_image = !app.dataset.get_image_files().is_empty();
+            if ui
+                .add_enabled(has_image, egui::Button::new(format!("{} Copy Image", Icon::COPY)))
+                .on_hover_text("Copy the current image to the clipboard")
+                .clicked()
+            {
+                app.copy_image_to_clipboard();
+            }
+            if ui
+                .add_enabled(has_image, egui::Button::new(format!("{} Export Pair...", Icon::FOLDER_SIMPLE_PLUS)))
+                .on_hover_text("Copy the current image and its label to a folder, keeping their names")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                    app.export_current_pair(&path);
+                }
+            }
+            ui.checkbox(&mut app.ui.burn_boxes_on_export, "Burn boxes")
+                .on_hover_text("Draw the bounding boxes into the exported image copy");
+            ui.add_space(20.0);
+
+            // Rename the images in the current (filtered) view
+            let rename_button = egui::Button::new(format!("{} Rename Images", Icon::TEXT_AA))
+                .fill(egui::Color32::from_rgb(110, 100, 150));
+            if ui
+                .add_enabled(
+                    !app.is_read_only()
+                        && !app.dataset.get_image_files().is_empty()
+                        && !app.rename.processing,
+                    rename_button,
+                )
+                .on_hover_text("Rename the images in the current (filtered) view and their labels")
+                .on_disabled_hover_text(app.read_only_hint())
+                .clicked()
+            {
+                app.rename.show_dialog = true;
+                app.rename.result_message = None;
+            }
+            ui.add_space(20.0);
+
             // Balance analyzer button
-            let balance_btn_text = if app.balance.analyzing {
+            // An automatic analysis running in the background can be opened at any time
+            let manual_analyzing =
+                app.balance.analyzing && app.balance.auto_status != AutoAnalysisStatus::Running;
+            let balance_btn_text = if manual_analyzing {
                 format!("{} Analyzing...", Icon::CHART_BAR)
             } else {
                 format!("{} Analyze Balance", Icon::CHART_BAR)
@@ -90,12 +261,43 @@ pub fn render_bottom_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
             
             let balance_button = egui::Button::new(&balance_btn_text).fill(egui::Color32::from_rgb(100, 150, 100));
             if ui.add_enabled(
-                !app.dataset.get_image_files().is_empty() && !app.balance.analyzing,
+                !app.dataset.get_image_files().is_empty() && !manual_analyzing,
                 balance_button,
             )
             .clicked()
             {
-                app.analyze_balance();
+                app.open_balance_analysis();
+            }
+            
+            ui.add_space(20.0);
+
+            // Export session report button
+            let report_button = egui::Button::new(format!("{} Export Report", Icon::FILE_HTML))
+                .fill(egui::Color32::from_rgb(150, 110, 60));
+            if ui.add_enabled(
+                app.dataset.dataset_path().is_some() && !app.report_export.processing,
+                report_button,
+            )
+            .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("HTML", &["html"])
+                    .set_file_name("dataset_report.html")
+                    .save_file()
+                {
+                    app.export_report(path);
+                }
+            }
+
+            if let Some(message) = &app.ui.delete_message {
+                ui.label(egui::RichText::new(message).small().color(egui::Color32::GRAY));
+            }
+            if let Some(message) = &app.ui.drop_message {
+                ui.label(
+                    egui::RichText::new(message)
+                        .small()
+                        .color(egui::Color32::from_rgb(255, 150, 0)),
+                );
             }
             
             ui.add_space(20.0);
@@ -111,3 +313,127 @@ pub fn render_bottom_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
         ui.add_space(10.0);
     });
 }
+
+/// Render the read-only status bar below the bottom panel
+pub fn render_status_bar(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    egui::TopBottomPanel::bottom("status_bar")
+        .exact_height(22.0)
+        .show(ctx, |ui| {
+            ui.horizontal_centered(|ui| {
+                let muted = egui::Color32::GRAY;
+                let current_image = app
+                    .dataset
+                    .get_image_files()
+                    .get(app.current_index)
+                    .cloned();
+
+                // Image dimensions
+                let dimensions = current_image
+                    .as_deref()
+                    .and_then(|path| app.status_bar.image_dimensions(path));
+                let dimensions_text = match dimensions {
+                    Some((width, height)) => format!("{}×{}", width, height),
+                    None => "—".to_string(),
+                };
+                ui.label(
+                    egui::RichText::new(format!("{} {}", Icon::FRAME_CORNERS, dimensions_text))
+                        .small(),
+                );
+                ui.separator();
+
+                // Detection count
+                let detections = app
+                    .image
+                    .label
+                    .as_ref()
+                    .map_or(0, |label| label.detections.len());
+                ui.label(
+                    egui::RichText::new(format!("{} {} detections", Icon::CROSSHAIR, detections))
+                        .small(),
+                );
+                ui.separator();
+
+                // Current split
+                let split_text = if app.dataset.is_multi_root() {
+                    format!("{} (multi)", app.dataset.current_split().as_str())
+                } else {
+                    app.dataset.current_split().as_str().to_string()
+                };
+                ui.label(
+                    egui::RichText::new(format!("{} {}", Icon::FOLDER, split_text)).small(),
+                );
+                ui.separator();
+
+                // Filter status
+                if app.filter.is_active() {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} Filtered: {} of {}",
+                            Icon::FUNNEL,
+                            app.filter.filtered_count(),
+                            app.dataset.get_image_files().len()
+                        ))
+                        .small()
+                        .color(egui::Color32::from_rgb(100, 149, 237)),
+                    );
+                } else {
+                    ui.label(
+                        egui::RichText::new(format!("{} No filter", Icon::FUNNEL))
+                            .small()
+                            .color(muted),
+                    );
+                }
+
+                // Report export progress, or the outcome of the last export
+                if app.report_export.processing {
+                    ui.separator();
+                    ui.spinner();
+                    let text = match app.report_export.progress {
+                        Some((step, current, total)) => format!("Report: {} {} / {}", step, current, total),
+                        None => "Building report...".to_string(),
+                    };
+                    ui.label(egui::RichText::new(format!("{} {}", Icon::FILE_HTML, text)).small());
+                    if ui.small_button("Cancel").clicked() {
+                        app.cancel_report_export();
+                    }
+                } else if let Some(result) = &app.report_export.result {
+                    ui.separator();
+                    let (text, color) = match result {
+                        Ok(path) => (format!("Report saved to {}", path.display()), muted),
+                        Err(e) => (
+                            format!("Failed to save report: {}", e),
+                            egui::Color32::from_rgb(255, 100, 100),
+                        ),
+                    };
+                    ui.label(
+                        egui::RichText::new(format!("{} {}", Icon::FILE_HTML, text))
+                            .small()
+                            .color(color),
+                    );
+                }
+
+                // RAM usage (right-aligned)
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let (used, total) = app.status_bar.memory_usage();
+                    let gib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} RAM {:.1} / {:.1} GB",
+                            Icon::MEMORY,
+                            gib(used),
+                            gib(total)
+                        ))
+                        .small()
+                        .color(muted),
+                    );
+                });
+            });
+        });
+
+    if app.report_export.processing {
+        ctx.request_repaint();
+    } else {
+        // Keep the memory reading current while the app is idle
+        ctx.request_repaint_after(std::time::Duration::from_secs(2));
+    }
+}
@@ -1,4 +1,7 @@
 use crate::app::DatasetCleanerApp;
+use crate::config::AppConfig;
+use crate::core::dataset::{Dataset, LabelInfo, YoloDetection};
+use crate::state::ZoomNavigateMode;
 use crate::ui::image_renderer::ImageRenderer;
 use eframe::egui;
 use egui_phosphor::regular as Icon;
@@ -11,6 +14,10 @@ const NAVIGATION_ARROW_SIZE: f32 = 20.0;
 const OVERLAY_HOVER_ALPHA: u8 = 50;
 const OVERLAY_SHADOW_ALPHA: u8 = 100;
 
+// Drag-and-drop overlay constants
+const DROP_OVERLAY_ALPHA: u8 = 180;
+const DROP_OVERLAY_TEXT_SIZE: f32 = 28.0;
+
 /// Direction for navigation arrows
 enum ArrowDirection {
     Left,
@@ -61,24 +68,545 @@ fn draw_navigation_arrow(
     ));
 }
 
+/// Draw the zoom level badge in the bottom-right corner and return its rect.
+///
+/// The badge doubles as the zoom-on-navigate switch: a reset icon means zoom
+/// resets, a pin means it carries over, and a bookmark means each image keeps
+/// its own zoom and scroll.
+fn render_zoom_toggle(
+    app: &mut DatasetCleanerApp,
+    ui: &mut egui::Ui,
+    available_rect: egui::Rect,
+) -> egui::Rect {
+    let mode = app.settings.zoom_navigate_mode();
+    let zoom_text = format!(
+        "{} {}%",
+        match mode {
+            ZoomNavigateMode::Reset => Icon::ARROW_COUNTER_CLOCKWISE,
+            ZoomNavigateMode::Keep => Icon::PUSH_PIN,
+            ZoomNavigateMode::PerImage => Icon::BOOKMARK_SIMPLE,
+        },
+        (app.image.zoom_level * 100.0).round() as i32
+    );
+    let font_id = egui::FontId::proportional(14.0);
+    let galley = ui
+        .painter()
+        .layout_no_wrap(zoom_text, font_id, egui::Color32::WHITE);
+
+    // Bottom-right corner
+    let zoom_pos = egui::pos2(
+        available_rect.max.x - galley.size().x - 20.0,
+        available_rect.max.y - 30.0,
+    );
+
+    let zoom_bg_rect = egui::Rect::from_min_size(
+        zoom_pos - egui::vec2(5.0, 3.0),
+        galley.size() + egui::vec2(10.0, 6.0),
+    );
+
+    let response = ui
+        .allocate_rect(zoom_bg_rect, egui::Sense::click())
+        .on_hover_text(match mode {
+            ZoomNavigateMode::Reset => {
+                "Zoom resets when navigating (click to keep zoom). Ctrl+0 resets zoom."
+            }
+            ZoomNavigateMode::Keep => {
+                "Zoom is kept when navigating (click to remember per image). Ctrl+0 resets zoom."
+            }
+            ZoomNavigateMode::PerImage => {
+                "Zoom and scroll are remembered per image (click to reset on navigate). Ctrl+0 resets zoom."
+            }
+        });
+
+    let bg_alpha = if response.hovered() { 220 } else { 180 };
+    ui.painter()
+        .rect_filled(zoom_bg_rect, 4.0, egui::Color32::from_black_alpha(bg_alpha));
+    ui.painter().galley(zoom_pos, galley, egui::Color32::WHITE);
+
+    if response.clicked() {
+        app.cycle_zoom_navigate_mode();
+    }
+
+    zoom_bg_rect
+}
+
+/// Draw the "Fit" and "1:1" zoom buttons to the left of the zoom badge
+fn render_zoom_reset_buttons(
+    app: &mut DatasetCleanerApp,
+    ui: &mut egui::Ui,
+    badge_rect: egui::Rect,
+    base_scale: f32,
+) {
+    let button_size = egui::vec2(36.0, badge_rect.height());
+    let one_to_one_rect = egui::Rect::from_min_size(
+        badge_rect.min - egui::vec2(button_size.x + 4.0, 0.0),
+        button_size,
+    );
+    let fit_rect = egui::Rect::from_min_size(
+        one_to_one_rect.min - egui::vec2(button_size.x + 4.0, 0.0),
+        button_size,
+    );
+
+    if ui
+        .put(fit_rect, egui::Button::new("Fit").small())
+        .on_hover_text("Fit image to view (Ctrl+0)")
+        .clicked()
+    {
+        app.set_zoom(1.0);
+    }
+    if ui
+        .put(one_to_one_rect, egui::Button::new("1:1").small())
+        .on_hover_text("Actual pixel size (100%)")
+        .clicked()
+    {
+        app.set_zoom(1.0 / base_scale);
+    }
+}
+
+/// Draw a new box with left-click-drag on the image (annotation mode)
+///
+/// The box being dragged is previewed in the class color and added to the
+/// label on release; it is saved with "Save Annotations" in the label panel.
+fn handle_annotation_drag(
+    app: &mut DatasetCleanerApp,
+    ui: &mut egui::Ui,
+    image_rect: egui::Rect,
+    viewport: egui::Rect,
+) {
+    let area = image_rect.intersect(viewport);
+    let response = ui.interact(area, ui.id().with("annotation_area"), egui::Sense::drag());
+    if response.hovered() || app.ui.annotation_drag_start.is_some() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::Crosshair);
+    }
+    if response.drag_started_by(egui::PointerButton::Primary) {
+        app.ui.annotation_drag_start = response.interact_pointer_pos();
+    }
+
+    let Some(start) = app.ui.annotation_drag_start else {
+        return;
+    };
+    let Some(end) = ui.ctx().pointer_latest_pos() else {
+        return;
+    };
+    let rect = egui::Rect::from_two_pos(start, end).intersect(image_rect);
+    let (stroke_color, fill_color) = app.config.get_class_colors(app.ui.annotation_class);
+    ui.painter().rect_filled(rect, 0.0, fill_color);
+    ui.painter().rect_stroke(rect, 0.0, egui::Stroke::new(2.0, stroke_color));
+
+    if !response.drag_stopped() {
+        return;
+    }
+    app.ui.annotation_drag_start = None;
+    // Ignore clicks and slips of the mouse
+    if rect.width() < 3.0 || rect.height() < 3.0 {
+        return;
+    }
+    let size = image_rect.size();
+    let center = rect.center() - image_rect.min;
+    app.add_annotation(YoloDetection {
+        class_id: app.ui.annotation_class,
+        x_center: center.x / size.x,
+        y_center: center.y / size.y,
+        width: rect.width() / size.x,
+        height: rect.height() / size.y,
+        confidence: None,
+    });
+}
+
+/// Show a tooltip for the box under the pointer and select it on click.
+///
+/// Selecting a box highlights it and scrolls its entry in the label panel into view.
+fn handle_box_interaction(
+    app: &mut DatasetCleanerApp,
+    ui: &mut egui::Ui,
+    box_rects: &[egui::Rect],
+    viewport: egui::Rect,
+) {
+    let Some(pointer_pos) = ui.ctx().pointer_hover_pos() else {
+        return;
+    };
+    if !viewport.contains(pointer_pos) {
+        return;
+    }
+    let Some(index) = ImageRenderer::hit_test(box_rects, pointer_pos) else {
+        return;
+    };
+    let Some(detection) = app
+        .image
+        .label
+        .as_ref()
+        .and_then(|label| label.detections.get(index))
+    else {
+        return;
+    };
+
+    let mut tooltip = format!(
+        "{} #{}\nSize: {:.4} × {:.4}",
+        app.config.get_class_name(detection.class_id),
+        index + 1,
+        detection.width,
+        detection.height
+    );
+    if let Some(confidence) = detection.confidence {
+        tooltip.push_str(&format!("\nConfidence: {:.2}", confidence));
+    }
+    let hit_rect = box_rects[index].intersect(viewport);
+    let response = ui
+        .interact(hit_rect, ui.id().with(("bbox_hit", index)), egui::Sense::click())
+        .on_hover_text_at_pointer(tooltip);
+
+    if response.clicked() {
+        if app.image.selected_detection == Some(index) {
+            app.image.selected_detection = None;
+        } else {
+            app.image.selected_detection = Some(index);
+            app.image.scroll_to_selected = true;
+        }
+        // The label panel is drawn before this one; repaint so it picks up the selection
+        ui.ctx().request_repaint();
+    }
+}
+
+/// Handle Ctrl+scroll zoom anchored at the cursor and middle-mouse panning.
+///
+/// Both work by scheduling a scroll offset for the next frame, when the new
+/// zoom level is laid out.
+fn handle_zoom_and_pan(
+    app: &mut DatasetCleanerApp,
+    ctx: &egui::Context,
+    fit_size: egui::Vec2,
+    image_rect: egui::Rect,
+    viewport: egui::Rect,
+    scroll_offset: egui::Vec2,
+) {
+    let (zoom_delta, pointer_pos, middle_down, pointer_delta) = ctx.input(|i| {
+        (
+            i.zoom_delta(),
+            i.pointer.hover_pos(),
+            i.pointer.middle_down(),
+            i.pointer.delta(),
+        )
+    });
+
+    let Some(pointer_pos) = pointer_pos else {
+        return;
+    };
+    if !viewport.contains(pointer_pos) {
+        return;
+    }
+
+    if zoom_delta != 1.0 {
+        app.set_zoom(app.image.zoom_level * zoom_delta);
+        let new_size = fit_size * app.image.zoom_level;
+
+        // Keep the image point under the cursor fixed while zooming
+        let anchor = ((pointer_pos - image_rect.min) / image_rect.size())
+            .clamp(egui::Vec2::ZERO, egui::Vec2::splat(1.0));
+        let max_offset = (new_size - viewport.size()).max(egui::Vec2::ZERO);
+        let offset = (anchor * new_size - (pointer_pos - viewport.min))
+            .clamp(egui::Vec2::ZERO, max_offset);
+        app.image.pending_scroll_offset = Some(offset);
+    } else if middle_down && pointer_delta != egui::Vec2::ZERO {
+        app.image.pending_scroll_offset = Some(scroll_offset - pointer_delta);
+    }
+}
+
+/// What was drawn in one side of the comparison view
+struct ComparePane {
+    image_rect: egui::Rect,
+    box_rects: Vec<egui::Rect>,
+    viewport: egui::Rect,
+    offset: egui::Vec2,
+    fit_size: egui::Vec2,
+}
+
+/// Button clicked in a comparison view header
+enum CompareAction {
+    Unpin,
+    DeleteReference,
+    DeleteCurrent,
+}
+
+/// Draw a comparison pane header with the filename and a delete button
+fn render_compare_header(
+    ui: &mut egui::Ui,
+    title: String,
+    is_reference: bool,
+    action: &mut Option<CompareAction>,
+) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(title).strong());
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui
+                .small_button(format!("{} Delete", Icon::TRASH))
+                .on_hover_text("Delete this image and its label (undoable like a normal delete)")
+                .clicked()
+            {
+                *action = Some(if is_reference {
+                    CompareAction::DeleteReference
+                } else {
+                    CompareAction::DeleteCurrent
+                });
+            }
+            if is_reference
+                && ui
+                    .small_button(format!("{} Unpin", Icon::PUSH_PIN_SLASH))
+                    .on_hover_text("Close the comparison view (P)")
+                    .clicked()
+            {
+                *action = Some(CompareAction::Unpin);
+            }
+        });
+    });
+}
+
+/// Draw one side of the comparison view at the shared zoom and scroll offset
+#[allow(clippy::too_many_arguments)]
+fn render_compare_pane(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    texture: &egui::TextureHandle,
+    label: Option<&LabelInfo>,
+    config: &AppConfig,
+    selected: Option<usize>,
+    zoom: f32,
+    offset: egui::Vec2,
+) -> ComparePane {
+    let img_size = texture.size_vec2();
+    let fit_size = img_size * ImageRenderer::calculate_image_scale(img_size, ui.available_size());
+    let scaled_size = fit_size * zoom;
+
+    let output = egui::ScrollArea::both()
+        .id_salt(id_salt)
+        .auto_shrink([false, false])
+        .scroll_offset(offset)
+        .show(ui, |ui| {
+            ui.set_min_size(fit_size);
+            ui.centered_and_justified(|ui| {
+                let image_rect = ui
+                    .add(egui::Image::new((texture.id(), scaled_size)).fit_to_original_size(1.0))
+                    .rect;
+                let box_rects = label
+                    .map(|label| {
+                        ImageRenderer::draw_bounding_boxes(
+                            ui.painter(),
+                            label,
+                            image_rect,
+                            img_size,
+                            config,
+                            selected,
+                        )
+                    })
+                    .unwrap_or_default();
+                (image_rect, box_rects)
+            })
+            .inner
+        });
+
+    let (image_rect, box_rects) = output.inner;
+    ComparePane {
+        image_rect,
+        box_rects,
+        viewport: output.inner_rect,
+        offset: output.state.offset,
+        fit_size,
+    }
+}
+
+/// Render the pinned reference (left) next to the current image (right)
+///
+/// Both sides share the zoom level and scroll offset; the side under the
+/// pointer drives the scrolling of the other.
+fn render_compare_view(app: &mut DatasetCleanerApp, ui: &mut egui::Ui, ctx: &egui::Context) {
+    let shared_offset = app
+        .image
+        .pending_scroll_offset
+        .take()
+        .unwrap_or(app.image.scroll_offset);
+    let (Some(pinned), Some(texture)) = (&app.image.pinned, &app.image.texture) else {
+        return;
+    };
+
+    let file_name = |path: &std::path::Path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+    let current_name = app
+        .dataset
+        .get_image_files()
+        .get(app.current_index)
+        .map(|path| file_name(path))
+        .unwrap_or_default();
+    let zoom = app.image.zoom_level;
+    let mut action = None;
+
+    let (left, right) = ui.columns(2, |columns| {
+        render_compare_header(
+            &mut columns[0],
+            format!("{} Reference: {}", Icon::PUSH_PIN, file_name(&pinned.path)),
+            true,
+            &mut action,
+        );
+        let left = render_compare_pane(
+            &mut columns[0],
+            "compare_reference",
+            &pinned.texture,
+            pinned.label.as_ref(),
+            &app.config,
+            None,
+            zoom,
+            shared_offset,
+        );
+
+        render_compare_header(
+            &mut columns[1],
+            format!("{} Current: {}", Icon::IMAGE, current_name),
+            false,
+            &mut action,
+        );
+        let right = render_compare_pane(
+            &mut columns[1],
+            "compare_current",
+            texture,
+            app.image.label.as_ref(),
+            &app.config,
+            app.image.selected_detection,
+            zoom,
+            shared_offset,
+        );
+        (left, right)
+    });
+
+    let pointer_in_left = ctx
+        .input(|i| i.pointer.hover_pos())
+        .is_some_and(|pos| left.viewport.contains(pos));
+    let active = if pointer_in_left { &left } else { &right };
+    app.image.scroll_offset = active.offset;
+
+    // Boxes of the current image stay selectable as in the single view
+    handle_box_interaction(app, ui, &right.box_rects, right.viewport);
+    handle_zoom_and_pan(
+        app,
+        ctx,
+        active.fit_size,
+        active.image_rect,
+        active.viewport,
+        active.offset,
+    );
+
+    match action {
+        Some(CompareAction::Unpin) => app.image.pinned = None,
+        Some(CompareAction::DeleteReference) => app.delete_pinned_image(),
+        Some(CompareAction::DeleteCurrent) => app.delete_current_image(),
+        None => {}
+    }
+}
+
+/// Load the dataset folders dropped onto the window
+///
+/// Every dropped path must be a folder containing at least one
+/// `<split>/images` directory; anything else is rejected with a message in
+/// the bottom panel. Several folders are merged into one view, the first one
+/// being the primary root.
+fn handle_dropped_dataset(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    let dropped: Vec<_> = ctx.input(|i| {
+        i.raw
+            .dropped_files
+            .iter()
+            .filter_map(|file| file.path.clone())
+            .collect()
+    });
+    if dropped.is_empty() {
+        return;
+    }
+
+    if let Some(path) = dropped.iter().find(|path| !path.is_dir()) {
+        app.ui.drop_message = Some(format!("{} is not a folder", path.display()));
+    } else if let Some(path) = dropped.iter().find(|path| !Dataset::is_dataset_root(path)) {
+        tracing::warn!("Dropped folder is not a dataset: {:?}", path);
+        app.ui.drop_message = Some(format!(
+            "{} has no images, train/images, val/images or test/images folder or data.yaml",
+            path.display()
+        ));
+    } else if dropped.len() == 1 {
+        app.ui.drop_message = None;
+        app.load_dataset(dropped[0].clone());
+    } else {
+        app.ui.drop_message = None;
+        app.load_merged_datasets(dropped);
+    }
+}
+
+/// Draw the "Drop dataset folder here" hint over the central panel
+fn render_drop_overlay(ctx: &egui::Context, rect: egui::Rect, dragging: bool) {
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("dataset_drop_overlay"),
+    ));
+    if dragging {
+        painter.rect_filled(
+            rect,
+            0.0,
+            egui::Color32::from_black_alpha(DROP_OVERLAY_ALPHA),
+        );
+    }
+    painter.text(
+        rect.center(),
+        egui::Align2::CENTER_BOTTOM,
+        format!("{} Drop dataset folder here", Icon::FOLDER_OPEN),
+        egui::FontId::proportional(DROP_OVERLAY_TEXT_SIZE),
+        egui::Color32::WHITE,
+    );
+    if !dragging {
+        painter.text(
+            rect.center() + egui::vec2(0.0, 8.0),
+            egui::Align2::CENTER_TOP,
+            "or click 'Open Dataset Folder' to begin",
+            egui::FontId::proportional(14.0),
+            egui::Color32::GRAY,
+        );
+    }
+}
+
+/// Action picked from the image's context menu
+enum ImageAction {
+    CopyToClipboard,
+    ExportPair,
+}
+
 /// Render the central panel with the main image display
 pub fn render_central_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    handle_dropped_dataset(app, ctx);
+    let dragging = ctx.input(|i| !i.raw.hovered_files.is_empty());
+
     egui::CentralPanel::default().show(ctx, |ui| {
+        let panel_rect = ui.max_rect();
+        if dragging || app.dataset.get_image_files().is_empty() {
+            render_drop_overlay(ctx, panel_rect, dragging);
+        }
+
         if app.dataset.get_image_files().is_empty() {
+            // The drop overlay doubles as the empty-state hint
+        } else if app.filter.is_filtering() && app.filter.filtered_count() == 0 {
+            // Waiting for the first result of a background filter job
             ui.centered_and_justified(|ui| {
-                ui.heading("No dataset loaded. Click 'Open Dataset Folder' to begin.");
+                ui.spinner();
+                ui.label("Applying filters...");
             });
         } else if app.filter.is_active() && app.filter.filtered_count() == 0 {
             // Show "No results" message when filter has 0 matches
             render_no_filter_results(app, ui);
         } else {
-            // Load image if not already loaded
-            if app.image.texture.is_none() {
+            // Load image if not already loaded, preferring the prefetched copy
+            if app.image.texture.is_none() && !app.use_prefetched_image(ctx) {
                 app.load_current_image(ctx);
             }
 
-            // Display the image
-            if let Some(texture) = &app.image.texture {
+            // Display the image, next to the pinned reference if there is one
+            if app.image.pinned.is_some() && app.image.texture.is_some() && !app.ui.fullscreen_mode {
+                render_compare_view(app, ui, ctx);
+            } else if let Some(texture) = &app.image.texture {
                 let available_size = ui.available_size();
                 let img_size = texture.size_vec2();
 
@@ -95,25 +623,14 @@ pub fn render_central_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                 let container_rect =
                     egui::Rect::from_center_size(available_rect.center(), container_size);
 
-                // Handle zoom with Ctrl + mouse wheel (only when hovering over the container)
-                ctx.input(|i| {
-                    if i.modifiers.ctrl && i.smooth_scroll_delta.y != 0.0 {
-                        if let Some(pointer_pos) = i.pointer.hover_pos() {
-                            // Only zoom if the mouse is over the container
-                            if container_rect.contains(pointer_pos) {
-                                // Zoom in/out based on scroll direction
-                                // Positive scroll_delta.y = scroll up = zoom in
-                                let zoom_delta = i.smooth_scroll_delta.y * 0.001;
-                                app.image.zoom_level =
-                                    (app.image.zoom_level + zoom_delta).clamp(0.5, 3.0);
-                            }
-                        }
-                    }
-                });
+                let mut image_action = None;
 
                 // Create a scroll area for the image
-                let scroll_response = egui::ScrollArea::both()
-                    .auto_shrink([false, false])
+                let mut scroll_area = egui::ScrollArea::both().auto_shrink([false, false]);
+                if let Some(offset) = app.image.pending_scroll_offset.take() {
+                    scroll_area = scroll_area.scroll_offset(offset);
+                }
+                let scroll_output = scroll_area
                     .show(ui, |ui| {
                         // Set minimum size to the container size to ensure centering works
                         ui.set_min_size(container_size);
@@ -124,29 +641,70 @@ pub fn render_central_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                             let img_response = ui.add(
                                 egui::Image::new((texture.id(), scaled_size))
                                     .fit_to_original_size(1.0)
+                                    .sense(egui::Sense::click())
                             );
+                            img_response.context_menu(|ui| {
+                                if ui.button(format!("{} Copy image to clipboard", Icon::COPY)).clicked() {
+                                    image_action = Some(ImageAction::CopyToClipboard);
+                                    ui.close_menu();
+                                }
+                                if ui.button(format!("{} Export pair to folder...", Icon::FOLDER_SIMPLE_PLUS)).clicked() {
+                                    image_action = Some(ImageAction::ExportPair);
+                                    ui.close_menu();
+                                }
+                            });
                             
                             // Get the actual rect where the image was placed
                             let image_rect = img_response.rect;
                             
                             // Draw bounding boxes if label data exists (not in fullscreen mode)
+                            let mut box_rects = Vec::new();
                             if !app.ui.fullscreen_mode {
                                 if let Some(label) = &app.image.label {
-                                    ImageRenderer::draw_bounding_boxes(
+                                    box_rects = ImageRenderer::draw_bounding_boxes(
                                         ui.painter(),
                                         label,
                                         image_rect,
                                         img_size,
                                         &app.config,
+                                        app.image.selected_detection,
                                     );
                                 }
                             }
                             
-                            image_rect
+                            (image_rect, box_rects)
                         }).inner
                     });
-                
-                let image_rect = scroll_response.inner;
+                let (image_rect, box_rects) = scroll_output.inner;
+                app.image.scroll_offset = scroll_output.state.offset;
+
+                match image_action {
+                    Some(ImageAction::CopyToClipboard) => app.copy_image_to_clipboard(),
+                    Some(ImageAction::ExportPair) => {
+                        if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                            app.export_current_pair(&path);
+                        }
+                    }
+                    None => {}
+                }
+
+                // Draw new boxes in annotation mode, otherwise box hover tooltip and click-to-select
+                let annotating = app.ui.annotation_mode && !app.ui.fullscreen_mode;
+                if annotating {
+                    handle_annotation_drag(app, ui, image_rect, scroll_output.inner_rect);
+                } else {
+                    handle_box_interaction(app, ui, &box_rects, scroll_output.inner_rect);
+                }
+
+                // Zoom toward the cursor with Ctrl + mouse wheel, pan with middle-drag
+                handle_zoom_and_pan(
+                    app,
+                    ctx,
+                    img_size * base_scale,
+                    image_rect,
+                    scroll_output.inner_rect,
+                    scroll_output.state.offset,
+                );
 
                 // Show fullscreen hint overlay
                 if app.ui.fullscreen_mode {
@@ -178,38 +736,8 @@ pub fn render_central_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                     ui.painter().galley(hint_pos, galley, egui::Color32::WHITE);
                 }
 
-                // Show zoom indicator when not at 100%
-                if (app.image.zoom_level - 1.0).abs() > 0.01 {
-                    let zoom_text = format!("{}%", (app.image.zoom_level * 100.0) as i32);
-                    let font_id = egui::FontId::proportional(14.0);
-                    let galley = ui.painter().layout_no_wrap(
-                        zoom_text,
-                        font_id.clone(),
-                        egui::Color32::WHITE,
-                    );
-
-                    // Bottom-right corner
-                    let zoom_pos = egui::pos2(
-                        available_rect.max.x - galley.size().x - 20.0,
-                        available_rect.max.y - 30.0,
-                    );
-
-                    let zoom_bg_rect = egui::Rect::from_min_size(
-                        zoom_pos - egui::vec2(5.0, 3.0),
-                        galley.size() + egui::vec2(10.0, 6.0),
-                    );
-
-                    ui.painter().rect_filled(
-                        zoom_bg_rect,
-                        4.0,
-                        egui::Color32::from_black_alpha(180),
-                    );
-
-                    ui.painter().galley(zoom_pos, galley, egui::Color32::WHITE);
-                }
-
-                // --- Navigation Overlays (hidden in fullscreen mode) ---
-                if !app.ui.fullscreen_mode {
+                // --- Navigation Overlays (hidden in fullscreen and annotation mode) ---
+                if !app.ui.fullscreen_mode && !annotating {
                     // Previous Button (Left)
                     if app.current_index > 0 {
                         let prev_rect = egui::Rect::from_min_size(
@@ -279,8 +807,14 @@ pub fn render_central_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                         }
                     }
                 }
+                // Zoom indicator; clicking it toggles reset-on-navigate.
+                // Drawn after the navigation overlays so it stays clickable on top of them.
+                let badge_rect = render_zoom_toggle(app, ui, available_rect);
+                render_zoom_reset_buttons(app, ui, badge_rect, base_scale);
             } else if let Some(error_msg) = &app.image.load_error {
                 // Display error message instead of loading spinner
+                let scanning = app.image.is_scanning_for_errors();
+                let mut next_error = false;
                 ui.centered_and_justified(|ui| {
                     ui.vertical_centered(|ui| {
                         ui.add_space(20.0);
@@ -296,8 +830,24 @@ pub fn render_central_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                                 .size(14.0)
                                 .color(egui::Color32::GRAY),
                         );
+                        ui.add_space(10.0);
+                        if scanning {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label("Looking for the next unreadable image...");
+                            });
+                        } else if ui
+                            .small_button("⏭ Next Error")
+                            .on_hover_text("Jump to the next image that fails to load (Alt+E)")
+                            .clicked()
+                        {
+                            next_error = true;
+                        }
                     });
                 });
+                if next_error {
+                    app.navigate_to_next_error();
+                }
             } else {
                 // Show loading spinner only if no error
                 ui.centered_and_justified(|ui| {
@@ -73,18 +73,73 @@ pub fn render_no_filter_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui)
                 ui.label(egui::RichText::new("Active Filters:").strong().size(14.0));
                 ui.add_space(5.0);
 
+                let mode = |invert: bool| if invert { "is not " } else { "" };
+
                 // Show team filter if not All
                 if app.filter.criteria.team != crate::core::filter::TeamFilter::All {
-                    ui.label(format!("• Team: {:?}", app.filter.criteria.team));
+                    ui.label(format!(
+                        "• Team: {}{:?}",
+                        mode(app.filter.criteria.invert_team),
+                        app.filter.criteria.team
+                    ));
                 }
 
                 // Show player count filter if not Any
                 if app.filter.criteria.player_count != crate::core::filter::PlayerCountFilter::Any {
                     ui.label(format!(
-                        "• Player Count: {:?}",
+                        "• Player Count: {}{:?}",
+                        mode(app.filter.criteria.invert_player_count),
                         app.filter.criteria.player_count
                     ));
                 }
+
+                // Show category filter if set
+                if let Some(category) = app.filter.criteria.category {
+                    ui.label(format!(
+                        "• Category: {}{}",
+                        mode(app.filter.criteria.invert_category),
+                        category.as_str()
+                    ));
+                }
+
+                // Show detection count range if set
+                if let Some(range) = &app.filter.criteria.detection_count_range {
+                    ui.label(format!("• Detections: {} to {}", range.start(), range.end()));
+                }
+
+                // Show capture date range if set
+                let date = |time: Option<i64>| {
+                    time.and_then(crate::core::filter::timestamp_date)
+                        .map(|d| d.format("%Y-%m-%d").to_string())
+                };
+                if let Some(after) = date(app.filter.criteria.min_timestamp) {
+                    ui.label(format!("• Captured after: {}", after));
+                }
+                if let Some(before) = date(app.filter.criteria.max_timestamp.map(|max| max + 1)) {
+                    ui.label(format!("• Captured before: {}", before));
+                }
+
+                // Show map filter if set
+                if let Some(maps) = app.filter.criteria.map_filter.as_ref().filter(|m| !m.is_empty()) {
+                    ui.label(format!(
+                        "• Maps: {}{}",
+                        mode(app.filter.criteria.invert_maps),
+                        maps.join(", ")
+                    ));
+                }
+
+                // Show confidence filter if set
+                if let Some(threshold) = app.filter.criteria.low_confidence_below {
+                    ui.label(format!("• Confidence below: {:.2}", threshold));
+                }
+
+                if app.filter.criteria.bookmarked_only {
+                    ui.label("• Bookmarked only");
+                }
+
+                if app.filter.criteria.unknown_classes {
+                    ui.label("• Unknown classes");
+                }
             });
 
             ui.add_space(20.0);
@@ -106,7 +161,7 @@ pub fn render_no_filter_results(app: &mut DatasetCleanerApp, ui: &mut egui::Ui)
                     )
                     .clicked()
                 {
-                    app.ui.show_filter_dialog = true;
+                    app.open_filter_dialog();
                 }
             });
         });
@@ -1,7 +1,19 @@
 use crate::app::DatasetCleanerApp;
+use crate::core::image::{read_image_metadata, AugmentationType};
+use crate::ui::image_renderer::ImageRenderer;
 use eframe::egui;
 use egui_phosphor::regular as Icon;
 
+/// Format a Unix timestamp as a local date and time (e.g., "2024-03-15 14:02:11")
+fn format_capture_date(timestamp_str: &str) -> Option<String> {
+    let timestamp = timestamp_str.parse::<i64>().ok()?;
+    chrono::DateTime::from_timestamp(timestamp, 0).map(|time| {
+        time.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    })
+}
+
 /// Format a Unix timestamp as a relative time string (e.g., "2 hours ago")
 fn format_relative_time(timestamp_str: &str) -> String {
     // Parse the Unix timestamp
@@ -43,134 +55,241 @@ fn format_relative_time(timestamp_str: &str) -> String {
 
 /// Render the right side panel with label information
 pub fn render_label_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
-    egui::SidePanel::right("label_panel")
-        .default_width(app.config.side_panel_width)
+    // The saved width seeds the panel on the first frame; egui tracks the live
+    // width afterwards, so restoring never fights the user's drag.
+    let saved_width = app
+        .settings
+        .label_panel_width
+        .unwrap_or(app.config.side_panel_width);
+    let mut sections = app.settings.label_panel_sections;
+    let selected = app.image.selected_detection;
+    let scroll_to_selected = app.image.scroll_to_selected;
+    let mut clicked_detection = None;
+    let mut crop_detection = None;
+    let mut save_raw_label = false;
+    let mut revert_raw_label = false;
+    let mut augmentation = None;
+    let mut save_annotations = false;
+
+    let response = egui::SidePanel::right("label_panel")
+        .default_width(saved_width)
         .resizable(true)
         .show(ctx, |ui| {
-            ui.heading("📊 Label Information");
+            ui.horizontal(|ui| {
+                ui.heading("📊 Label Information");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.toggle_value(&mut sections.thumbnail, Icon::IMAGE.to_string())
+                        .on_hover_text("Show a thumbnail of the image with its boxes");
+                    ui.toggle_value(&mut app.ui.annotation_mode, Icon::PENCIL_SIMPLE.to_string())
+                        .on_hover_text("Annotation mode: drag on the image to draw new boxes");
+                });
+            });
             ui.add_space(10.0);
             ui.separator();
             ui.add_space(10.0);
 
-            // Display dominant color
-            if let Some(color) = app.image.dominant_color {
-                ui.label(
-                    egui::RichText::new(format!("{} Dominant Color", Icon::PALETTE))
-                        .strong()
-                        .size(16.0),
-                );
+            // Class and save button for boxes drawn in annotation mode
+            if app.ui.annotation_mode {
+                ui.horizontal(|ui| {
+                    ui.label("New boxes:");
+                    egui::ComboBox::from_id_salt("annotation_class")
+                        .selected_text(app.config.get_class_name(app.ui.annotation_class).to_string())
+                        .show_ui(ui, |ui| {
+                            for class in &app.config.classes {
+                                ui.selectable_value(&mut app.ui.annotation_class, class.id, &class.name);
+                            }
+                        });
+                });
+                let pending = app.image.pending_annotations;
+                if ui
+                    .add_enabled(
+                        pending > 0,
+                        egui::Button::new(format!("{} Save Annotations ({})", Icon::FLOPPY_DISK, pending)),
+                    )
+                    .clicked()
+                {
+                    save_annotations = true;
+                }
                 ui.add_space(5.0);
+                ui.separator();
+                ui.add_space(5.0);
+            }
 
-                ui.horizontal(|ui| {
-                    // Color swatch
-                    let (rect, _response) =
-                        ui.allocate_exact_size(egui::vec2(60.0, 40.0), egui::Sense::hover());
-                    ui.painter().rect_filled(rect, 4.0, color);
-                    ui.painter().rect_stroke(
-                        rect,
-                        4.0,
-                        egui::Stroke::new(2.0, egui::Color32::from_gray(128)),
-                    );
-
-                    ui.add_space(10.0);
-
-                    // RGB values
-                    ui.vertical(|ui| {
-                        ui.label(format!("R: {}", color.r()));
-                        ui.label(format!("G: {}", color.g()));
-                        ui.label(format!("B: {}", color.b()));
+            // Thumbnail with boxes for context while reading coordinates
+            if sections.thumbnail {
+                if let Some(texture) = &app.image.texture {
+                    ui.vertical_centered(|ui| {
+                        let image_rect = ui
+                            .add(egui::Image::new(texture).max_size(egui::vec2(200.0, 150.0)))
+                            .rect;
+                        if let Some(label) = &app.image.label {
+                            ImageRenderer::draw_bounding_boxes(
+                                &ui.painter_at(image_rect),
+                                label,
+                                image_rect,
+                                texture.size_vec2(),
+                                &app.config,
+                                selected,
+                            );
+                        }
                     });
-                });
+                    ui.add_space(5.0);
+                    ui.separator();
+                    ui.add_space(5.0);
+                }
+            }
 
-                ui.add_space(10.0);
+            // Display dominant color
+            if let Some(color) = app.image.dominant_color {
+                section_header(
+                    ui,
+                    "label_panel_dominant_color",
+                    format!("{} Dominant Color", Icon::PALETTE),
+                    &mut sections.dominant_color,
+                    |ui| {
+                        ui.horizontal(|ui| {
+                            // Color swatch
+                            let (rect, _response) = ui
+                                .allocate_exact_size(egui::vec2(60.0, 40.0), egui::Sense::hover());
+                            ui.painter().rect_filled(rect, 4.0, color);
+                            ui.painter().rect_stroke(
+                                rect,
+                                4.0,
+                                egui::Stroke::new(2.0, egui::Color32::from_gray(128)),
+                            );
+
+                            ui.add_space(10.0);
+
+                            // RGB values
+                            ui.vertical(|ui| {
+                                ui.label(format!("R: {}", color.r()));
+                                ui.label(format!("G: {}", color.g()));
+                                ui.label(format!("B: {}", color.b()));
+                            });
+                        });
+                    },
+                );
+                ui.add_space(5.0);
                 ui.separator();
-                ui.add_space(10.0);
+                ui.add_space(5.0);
             }
 
             if let Some(label) = &app.image.label {
-                // Detection count
-                ui.label(
-                    egui::RichText::new(format!(
-                        "{} Detections: {}",
-                        Icon::TARGET,
-                        label.detections.len()
-                    ))
-                    .strong()
-                    .size(16.0),
+                // Metadata
+                section_header(
+                    ui,
+                    "label_panel_metadata",
+                    format!("{} Metadata", Icon::INFO),
+                    &mut sections.metadata,
+                    |ui| {
+                        if let Some(res) = &label.resolution {
+                            ui.label(format!("{} Resolution: {}", Icon::RULER, res));
+                        }
+                        if let Some(map) = &label.map {
+                            ui.label(format!("{} Map: {}", Icon::MAP_TRIFOLD, map));
+                        }
+                        if let Some(loc) = &label.location {
+                            ui.label(format!("{} Location: {}", Icon::MAP_PIN, loc));
+                        }
+                        if let Some(pos) = &label.position {
+                            ui.label(format!("{} Position: {}", Icon::CROSSHAIR, pos));
+                        }
+                        if let Some(time) = &label.timestamp {
+                            let relative = format_relative_time(time);
+                            if !relative.is_empty() {
+                                ui.label(format!("{} Timestamp: {} ({})", Icon::CLOCK, time, relative));
+                            } else {
+                                ui.label(format!("{} Timestamp: {}", Icon::CLOCK, time));
+                            }
+                            if let Some(date) = format_capture_date(time) {
+                                ui.label(egui::RichText::new(format!("     {}", date)).weak());
+                            }
+                        }
+                    },
                 );
 
-                ui.add_space(10.0);
+                ui.add_space(5.0);
+                ui.separator();
+                ui.add_space(5.0);
 
-                // Metadata
-                if let Some(res) = &label.resolution {
-                    ui.label(format!("{} Resolution: {}", Icon::RULER, res));
-                }
-                if let Some(map) = &label.map {
-                    ui.label(format!("{} Map: {}", Icon::MAP_TRIFOLD, map));
-                }
-                if let Some(loc) = &label.location {
-                    ui.label(format!("{} Location: {}", Icon::MAP_PIN, loc));
-                }
-                if let Some(pos) = &label.position {
-                    ui.label(format!("{} Position: {}", Icon::CROSSHAIR, pos));
-                }
-                if let Some(time) = &label.timestamp {
-                    let relative = format_relative_time(time);
-                    if !relative.is_empty() {
-                        ui.label(format!("{} Timestamp: {} ({})", Icon::CLOCK, time, relative));
-                    } else {
-                        ui.label(format!("{} Timestamp: {}", Icon::CLOCK, time));
-                    }
-                }
+                // Detection details (click an entry to highlight its box)
+                section_header(
+                    ui,
+                    "label_panel_detections",
+                    format!("{} Detections: {}", Icon::TARGET, label.detections.len()),
+                    &mut sections.detections,
+                    |ui| {
+                        if label.detections.is_empty() {
+                            ui.label(
+                                egui::RichText::new("No players detected")
+                                    .italics()
+                                    .color(egui::Color32::GRAY),
+                            );
+                            return;
+                        }
 
-                ui.add_space(10.0);
-                ui.separator();
-                ui.add_space(10.0);
-
-                // Detection details
-                if label.detections.is_empty() {
-                    ui.label(
-                        egui::RichText::new("No players detected")
-                            .italics()
-                            .color(egui::Color32::GRAY),
-                    );
-                } else {
-                    ui.label(egui::RichText::new("Detected Players:").strong().size(14.0));
-                    ui.add_space(5.0);
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            for (i, detection) in label.detections.iter().enumerate() {
+                                let (class_color, _) =
+                                    app.config.get_class_colors(detection.class_id);
+                                let is_selected = selected == Some(i);
+                                let mut frame = egui::Frame::group(ui.style());
+                                if is_selected {
+                                    frame = frame.stroke(egui::Stroke::new(2.0, class_color));
+                                }
 
-                    egui::ScrollArea::vertical().show(ui, |ui| {
-                        for (i, detection) in label.detections.iter().enumerate() {
-                            ui.group(|ui| {
-                                ui.horizontal(|ui| {
-                                    let (class_color, _) =
-                                        app.config.get_class_colors(detection.class_id);
-
-                                    ui.label(egui::RichText::new(format!("#{}", i + 1)).strong());
-                                    ui.label(
-                                        egui::RichText::new(
-                                            app.config.get_class_name(detection.class_id),
-                                        )
-                                        .strong()
-                                        .color(class_color),
-                                    );
-                                });
+                                let entry = frame.show(ui, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label(egui::RichText::new(format!("#{}", i + 1)).strong());
+                                        ui.label(
+                                            egui::RichText::new(
+                                                app.config.get_class_name(detection.class_id),
+                                            )
+                                            .strong()
+                                            .color(class_color),
+                                        );
+                                        ui.with_layout(
+                                            egui::Layout::right_to_left(egui::Align::Center),
+                                            |ui| {
+                                                if ui
+                                                    .small_button(Icon::SCISSORS)
+                                                    .on_hover_text("Save this box as a crop in the class folder")
+                                                    .clicked()
+                                                {
+                                                    crop_detection = Some(i);
+                                                }
+                                            },
+                                        );
+                                    });
 
-                                ui.add_space(5.0);
+                                    ui.add_space(5.0);
 
-                                ui.label(format!(
-                                    "Center: ({:.4}, {:.4})",
-                                    detection.x_center, detection.y_center
-                                ));
-                                ui.label(format!(
-                                    "Size: {:.4} × {:.4}",
-                                    detection.width, detection.height
-                                ));
-                            });
+                                    ui.label(format!(
+                                        "Center: ({:.4}, {:.4})",
+                                        detection.x_center, detection.y_center
+                                    ));
+                                    ui.label(format!(
+                                        "Size: {:.4} × {:.4}",
+                                        detection.width, detection.height
+                                    ));
+                                    if let Some(confidence) = detection.confidence {
+                                        ui.label(format!("Confidence: {:.2}", confidence));
+                                    }
+                                });
 
-                            ui.add_space(5.0);
-                        }
-                    });
-                }
+                                let response = entry.response.interact(egui::Sense::click());
+                                if response.clicked() {
+                                    clicked_detection = Some(i);
+                                }
+                                if is_selected && scroll_to_selected {
+                                    response.scroll_to_me(Some(egui::Align::Center));
+                                }
+
+                                ui.add_space(5.0);
+                            }
+                        });
+                    },
+                );
             } else {
                 ui.label(
                     egui::RichText::new("No label file found")
@@ -178,5 +297,210 @@ pub fn render_label_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                         .color(egui::Color32::GRAY),
                 );
             }
+
+            // Raw label file contents, editable in place
+            if app.raw_label.label_path.is_some() {
+                ui.add_space(5.0);
+                ui.separator();
+                ui.add_space(5.0);
+
+                let modified = app.raw_label.is_modified();
+                let title = if modified {
+                    format!("{} Raw label *", Icon::FILE_TEXT)
+                } else {
+                    format!("{} Raw label", Icon::FILE_TEXT)
+                };
+                section_header(ui, "label_panel_raw_label", title, &mut sections.raw_label, |ui| {
+                    egui::ScrollArea::vertical()
+                        .id_salt("raw_label_scroll")
+                        .max_height(240.0)
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::multiline(&mut app.raw_label.text)
+                                    .font(egui::TextStyle::Monospace)
+                                    .desired_width(f32::INFINITY)
+                                    .desired_rows(6),
+                            );
+                        });
+
+                    for line_error in &app.raw_label.errors {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 100, 100),
+                            format!(
+                                "{} Line {}: {}",
+                                Icon::WARNING,
+                                line_error.line_number,
+                                line_error.message
+                            ),
+                        );
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(
+                                modified,
+                                egui::Button::new(format!("{} Save", Icon::FLOPPY_DISK)),
+                            )
+                            .clicked()
+                        {
+                            save_raw_label = true;
+                        }
+                        if ui
+                            .add_enabled(
+                                modified,
+                                egui::Button::new(format!(
+                                    "{} Revert",
+                                    Icon::ARROW_COUNTER_CLOCKWISE
+                                )),
+                            )
+                            .clicked()
+                        {
+                            revert_raw_label = true;
+                        }
+                        if let Some(message) = &app.raw_label.message {
+                            ui.label(egui::RichText::new(message).small().color(egui::Color32::GRAY));
+                        }
+                    });
+                });
+            }
+
+            // Save an augmented copy of the image next to the original
+            ui.add_space(5.0);
+            ui.separator();
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                ui.menu_button(format!("{} Augment Current Image", Icon::SPARKLE), |ui| {
+                    if ui
+                        .button(format!("{} Horizontal Flip", Icon::FLIP_HORIZONTAL))
+                        .clicked()
+                    {
+                        augmentation = Some(AugmentationType::HorizontalFlip);
+                        ui.close_menu();
+                    }
+                    if ui
+                        .button(format!("{} Brightness Jitter (±20%)", Icon::SUN))
+                        .clicked()
+                    {
+                        let amount = rand::Rng::gen_range(&mut rand::thread_rng(), -0.2..=0.2);
+                        augmentation = Some(AugmentationType::BrightnessJitter(amount));
+                        ui.close_menu();
+                    }
+                });
+                if let Some(message) = &app.ui.augment_message {
+                    ui.label(egui::RichText::new(message).small().color(egui::Color32::GRAY));
+                }
+            });
+
+            // Fields read from the image file itself (PNG chunks, dimensions)
+            if let Some(path) = app.dataset.get_image_files().get(app.current_index).cloned() {
+                ui.add_space(5.0);
+                ui.separator();
+                ui.add_space(5.0);
+                section_header(
+                    ui,
+                    "label_panel_image_metadata",
+                    format!("{} Image Metadata", Icon::FILE_IMAGE),
+                    &mut sections.image_metadata,
+                    |ui| {
+                        // Only read the file while the section is open, once per image
+                        let cached = app
+                            .image
+                            .file_metadata
+                            .as_ref()
+                            .is_some_and(|(cached_path, _)| *cached_path == path);
+                        if !cached {
+                            let fields = read_image_metadata(&path);
+                            app.image.file_metadata = Some((path, fields));
+                        }
+                        let Some((_, fields)) = &app.image.file_metadata else {
+                            return;
+                        };
+
+                        if fields.is_empty() {
+                            ui.label(
+                                egui::RichText::new("No metadata")
+                                    .italics()
+                                    .color(egui::Color32::GRAY),
+                            );
+                            return;
+                        }
+
+                        let mut keys: Vec<&String> = fields.keys().collect();
+                        keys.sort();
+                        egui::Grid::new("image_metadata_grid")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for key in keys {
+                                    ui.label(egui::RichText::new(key).strong());
+                                    ui.label(&fields[key]);
+                                    ui.end_row();
+                                }
+                            });
+                    },
+                );
+            }
         });
+
+    if let Some(aug) = augmentation {
+        app.augment_current_image(aug);
+    }
+
+    if save_annotations {
+        app.annotate_current_image();
+    }
+
+    if save_raw_label {
+        app.save_raw_label();
+    }
+    if revert_raw_label {
+        app.revert_raw_label();
+    }
+
+    // Clicking an entry toggles its highlight in the image view
+    if let Some(index) = clicked_detection {
+        app.image.selected_detection = if selected == Some(index) {
+            None
+        } else {
+            Some(index)
+        };
+    }
+    app.image.scroll_to_selected = false;
+
+    if let Some(index) = crop_detection {
+        app.crop_current_detection(index);
+    }
+
+    // Persist section toggles and the resized width (once the drag is released)
+    let mut changed = sections != app.settings.label_panel_sections;
+    app.settings.label_panel_sections = sections;
+
+    let width = response.response.rect.width();
+    let dragging = ctx.input(|i| i.pointer.any_down());
+    if !dragging && (width - saved_width).abs() > 0.5 {
+        app.settings.label_panel_width = Some(width);
+        changed = true;
+    }
+
+    if changed {
+        app.settings.save();
+    }
+}
+
+/// Draw a collapsible label-panel section, tracking its open state in `open`
+fn section_header(
+    ui: &mut egui::Ui,
+    id: &str,
+    title: String,
+    open: &mut bool,
+    add_body: impl FnOnce(&mut egui::Ui),
+) {
+    let response = egui::CollapsingHeader::new(egui::RichText::new(title).strong().size(16.0))
+        .id_salt(id)
+        .default_open(*open)
+        .show(ui, add_body);
+
+    if response.header_response.clicked() {
+        *open = !*open;
+    }
 }
@@ -0,0 +1,75 @@
+use crate::app::DatasetCleanerApp;
+use crate::infrastructure::logging::{log_buffer, LOG_BUFFER_CAPACITY};
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+use tracing::Level;
+
+/// Color of a log line by level
+fn level_color(level: Level) -> egui::Color32 {
+    match level {
+        Level::ERROR => egui::Color32::from_rgb(255, 100, 100),
+        Level::WARN => egui::Color32::from_rgb(255, 220, 80),
+        Level::INFO => egui::Color32::WHITE,
+        _ => egui::Color32::GRAY,
+    }
+}
+
+/// Render the log panel with the most recent log events (Log button in the top panel)
+pub fn render_log_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.ui.show_log_panel {
+        return;
+    }
+
+    egui::SidePanel::left("log_panel")
+        .default_width(420.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(format!("{} Log", Icon::SCROLL));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(Icon::X).on_hover_text("Close").clicked() {
+                        app.ui.show_log_panel = false;
+                    }
+                    if ui.button("Clear").clicked() {
+                        log_buffer().clear();
+                    }
+                    egui::ComboBox::from_id_salt("log_level_filter")
+                        .selected_text(app.ui.log_level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in [Level::DEBUG, Level::INFO, Level::WARN, Level::ERROR] {
+                                ui.selectable_value(&mut app.ui.log_level, level, level.as_str());
+                            }
+                        });
+                });
+            });
+            ui.label(
+                egui::RichText::new(format!("Last {} events; the log file has everything", LOG_BUFFER_CAPACITY))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+            ui.separator();
+
+            // Sticks to the newest line unless the user scrolls up
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for entry in log_buffer().entries(app.ui.log_level) {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} {:5} {}",
+                                entry.timestamp.format("%H:%M:%S"),
+                                entry.level.as_str(),
+                                entry.message
+                            ))
+                            .monospace()
+                            .small()
+                            .color(level_color(entry.level)),
+                        );
+                    }
+                });
+        });
+
+    // Pick up events logged by background threads while the panel is open
+    ctx.request_repaint_after(std::time::Duration::from_millis(500));
+}
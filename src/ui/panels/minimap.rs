@@ -0,0 +1,113 @@
+use crate::app::DatasetCleanerApp;
+use crate::ui::distribution_bars::category_color;
+use eframe::egui;
+
+/// Number of images shown on each side of the current position
+const MINIMAP_RADIUS: usize = 200;
+const MINIMAP_HEIGHT: f32 = 14.0;
+
+/// Render the category overview strip above the bottom panel.
+///
+/// Shows one tick per image around the current position. Nothing is drawn
+/// until the category cache for the current split is ready.
+pub fn render_minimap(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if app.ui.fullscreen_mode {
+        return;
+    }
+
+    let image_count = app.dataset.get_image_files().len();
+    let Some(categories) = app.categories.get(image_count) else {
+        return;
+    };
+    if image_count == 0 {
+        return;
+    }
+
+    // Positions refer to the filtered list when a filter is active
+    let visible_count = if app.filter.is_active() {
+        app.filter.filtered_count()
+    } else {
+        image_count
+    };
+    if visible_count == 0 {
+        return;
+    }
+    let position = app
+        .filter
+        .get_filtered_index(app.current_index)
+        .unwrap_or(0);
+    let start = position.saturating_sub(MINIMAP_RADIUS);
+    let end = (position + MINIMAP_RADIUS + 1).min(visible_count);
+
+    let ticks: Vec<(usize, egui::Color32)> = (start..end)
+        .filter_map(|pos| app.filter.get_actual_index(pos))
+        .filter_map(|index| {
+            categories
+                .get(index)
+                .map(|&category| (index, category_color(&app.config, category)))
+        })
+        .collect();
+
+    let mut clicked_index = None;
+
+    egui::TopBottomPanel::bottom("minimap_panel")
+        .resizable(false)
+        .show(ctx, |ui| {
+            let (rect, response) = ui.allocate_exact_size(
+                egui::vec2(ui.available_width(), MINIMAP_HEIGHT),
+                egui::Sense::click(),
+            );
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 2.0, egui::Color32::from_black_alpha(120));
+
+            // Always lay out the full window width so the current marker stays centered
+            let slot_count = 2 * MINIMAP_RADIUS + 1;
+            let tick_width = rect.width() / slot_count as f32;
+            let slot_x = |pos: usize| {
+                let slot = pos + MINIMAP_RADIUS - position;
+                rect.min.x + slot as f32 * tick_width
+            };
+
+            for (offset, &(_, color)) in ticks.iter().enumerate() {
+                let x = slot_x(start + offset);
+                let tick_rect = egui::Rect::from_min_max(
+                    egui::pos2(x, rect.min.y),
+                    egui::pos2(x + tick_width.max(1.0), rect.max.y),
+                );
+                painter.rect_filled(tick_rect, 0.0, color);
+            }
+
+            // Current position marker
+            let x = slot_x(position);
+            let marker_rect = egui::Rect::from_min_max(
+                egui::pos2(x - 1.0, rect.min.y),
+                egui::pos2(x + tick_width.max(1.0) + 1.0, rect.max.y),
+            );
+            painter.rect_stroke(marker_rect, 0.0, egui::Stroke::new(2.0, egui::Color32::WHITE));
+
+            // Map the pointer back to the tick under it
+            let hovered_tick = response.hover_pos().and_then(|pointer| {
+                let slot = ((pointer.x - rect.min.x) / tick_width).floor() as isize;
+                let pos = slot + position as isize - MINIMAP_RADIUS as isize;
+                if pos < start as isize || pos >= end as isize {
+                    return None;
+                }
+                ticks.get(pos as usize - start).map(|&(index, _)| index)
+            });
+
+            if let Some(index) = hovered_tick {
+                let response = response.on_hover_text(format!(
+                    "Image {} — {}",
+                    index + 1,
+                    categories[index].as_str()
+                ));
+                if response.clicked() {
+                    clicked_index = Some(index);
+                }
+            }
+        });
+
+    if let Some(index) = clicked_index {
+        app.jump_to_index(index);
+    }
+}
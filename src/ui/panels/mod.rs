@@ -2,9 +2,13 @@ mod bottom;
 mod central;
 mod helpers;
 mod label;
+mod log;
+mod minimap;
 mod top;
 
-pub use bottom::render_bottom_panel;
+pub use bottom::{render_bottom_panel, render_status_bar};
 pub use central::render_central_panel;
 pub use label::render_label_panel;
+pub use log::render_log_panel;
+pub use minimap::render_minimap;
 pub use top::render_top_panel;
@@ -1,5 +1,6 @@
 use crate::app::DatasetCleanerApp;
-use crate::core::dataset::DatasetSplit;
+use crate::core::dataset::{DatasetSplit, LayoutKind};
+use crate::state::AutoAnalysisStatus;
 use eframe::egui;
 use egui_phosphor::regular as Icon;
 
@@ -7,6 +8,8 @@ use super::helpers::handle_manual_index_input;
 
 /// Render the top panel with navigation and dataset controls
 pub fn render_top_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    render_read_only_banner(app, ctx);
+
     egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
         ui.horizontal(|ui| {
             ui.heading(format!("{} YOLO Dataset Cleaner", Icon::FOLDERS));
@@ -22,41 +25,333 @@ pub fn render_top_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                 }
             }
 
+            let mut open_recent = None;
+            ui.add_enabled_ui(!app.settings.recent_datasets.is_empty(), |ui| {
+                ui.menu_button(format!("{} Recent", Icon::CLOCK_COUNTER_CLOCKWISE), |ui| {
+                    let current = app.dataset.dataset_path().map(|path| path.to_path_buf());
+                    for (index, entry) in app.settings.recent_datasets.iter().enumerate() {
+                        let label = if current.as_ref() == Some(&entry.path) {
+                            egui::RichText::new(entry.name()).strong()
+                        } else {
+                            egui::RichText::new(entry.name())
+                        };
+                        if ui
+                            .button(label)
+                            .on_hover_text(entry.path.display().to_string())
+                            .clicked()
+                        {
+                            open_recent = Some(index);
+                            ui.close_menu();
+                        }
+                    }
+                    ui.separator();
+                    if ui.button("Clear list").clicked() {
+                        app.settings.recent_datasets.clear();
+                        app.settings.save();
+                        ui.close_menu();
+                    }
+                })
+                .response
+                .on_disabled_hover_text("No datasets opened yet");
+            });
+            if let Some(index) = open_recent {
+                app.open_recent_dataset(index);
+            }
+
+            if ui
+                .add_enabled(
+                    app.dataset.dataset_path().is_some(),
+                    egui::Button::new(format!("{} Add secondary dataset", Icon::FOLDER_PLUS)),
+                )
+                .on_hover_text("Merge images from another dataset root into this view")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                    app.add_secondary_dataset(path);
+                }
+            }
+
             ui.add_space(20.0);
 
             // Split selection buttons
             if app.dataset.dataset_path().is_some() {
-                ui.label("Split:");
+                if app.dataset.is_multi_root() {
+                    ui.label("Split (multi):").on_hover_text(
+                        app.dataset
+                            .roots()
+                            .map(|root| root.display().to_string())
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    );
+                } else if app.dataset.layout() == LayoutKind::Split {
+                    ui.label("Split:");
+                }
+
+                if app.dataset.layout() == LayoutKind::Flat {
+                    // No split folders: the root images/ folder is the only view
+                    ui.label(format!("{} Flat layout", Icon::FOLDER_SIMPLE))
+                        .on_hover_text("images/ and labels/ at the dataset root, without train/val/test folders");
+                } else {
+                    if ui
+                        .selectable_label(app.dataset.current_split() == DatasetSplit::Train, "Train")
+                        .clicked()
+                    {
+                        app.change_split(DatasetSplit::Train);
+                    }
+
+                    if ui
+                        .selectable_label(app.dataset.current_split() == DatasetSplit::Val, "Val")
+                        .clicked()
+                    {
+                        app.change_split(DatasetSplit::Val);
+                    }
+
+                    if ui
+                        .selectable_label(app.dataset.current_split() == DatasetSplit::Test, "Test")
+                        .clicked()
+                    {
+                        app.change_split(DatasetSplit::Test);
+                    }
+                }
 
                 if ui
-                    .selectable_label(app.dataset.current_split() == DatasetSplit::Train, "Train")
+                    .button(format!("{} Summary", Icon::INFO))
+                    .on_hover_text("Images, labels and disk usage per split")
                     .clicked()
                 {
-                    app.change_split(DatasetSplit::Train);
+                    app.open_dataset_summary();
+                }
+
+                // Whether the class names and split paths came from data.yaml
+                match app.dataset.data_yaml() {
+                    Some(Ok(yaml)) => {
+                        let mut hover = String::from("Loaded from data.yaml");
+                        for (id, name) in &yaml.names {
+                            hover.push_str(&format!("\n{}: {}", id, name));
+                        }
+                        for split in DatasetSplit::all() {
+                            let Some(dir) = yaml.split_images.get(&split) else {
+                                continue;
+                            };
+                            hover.push_str(&format!("\n{}: {}", split.as_str(), dir.display()));
+                        }
+                        ui.label(format!("{} data.yaml", Icon::FILE_TEXT)).on_hover_text(hover);
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 150, 0),
+                            format!("{} data.yaml", Icon::WARNING),
+                        )
+                        .on_hover_text(format!("Could not read data.yaml: {}", e));
+                    }
+                    None => {}
                 }
 
+                render_auto_analysis_status(app, ui);
+
+                ui.add_space(20.0);
+            }
+
+            // Filter button (always visible)
+            if ui.button(format!("{} Filter", Icon::FUNNEL)).clicked() {
+                app.open_filter_dialog();
+            }
+
+            // Progress of a filter running in the background
+            if let Some((current, total)) = app.filter.progress {
+                ui.spinner();
+                ui.label(
+                    egui::RichText::new(format!("Filtering {} / {}", current, total))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                ctx.request_repaint();
+            }
+
+            // Move the filtered images to another split
+            let read_only_hint = app.read_only_hint();
+            let writable = read_only_hint.is_empty();
+            let can_bulk_move = writable
+                && app.filter.is_active()
+                && app.filter.filtered_count() > 0
+                && !app.dataset.is_multi_root()
+                && app.dataset.layout() == LayoutKind::Split;
+            ui.add_enabled_ui(can_bulk_move, |ui| {
+                ui.menu_button(format!("{} Move filtered to split", Icon::ARROW_BEND_UP_RIGHT), |ui| {
+                    for split in DatasetSplit::all() {
+                        if split == app.dataset.current_split() {
+                            continue;
+                        }
+                        if ui
+                            .button(format!(
+                                "→ {} ({} images)",
+                                split.as_str().to_uppercase(),
+                                app.filter.filtered_count()
+                            ))
+                            .clicked()
+                        {
+                            app.bulk_move_to_split(split);
+                            ui.close_menu();
+                        }
+                    }
+                })
+                .response
+                .on_disabled_hover_text(if writable {
+                    "Apply a filter first (not available with multiple dataset roots or a flat layout)"
+                } else {
+                    read_only_hint.as_str()
+                });
+            });
+
+            // Tools menu
+            ui.menu_button(format!("{} Tools", Icon::WRENCH), |ui| {
                 if ui
-                    .selectable_label(app.dataset.current_split() == DatasetSplit::Val, "Val")
+                    .add_enabled(
+                        writable && !app.dataset.get_image_files().is_empty(),
+                        egui::Button::new(format!("{} Batch Rename...", Icon::TEXT_AA)),
+                    )
+                    .on_disabled_hover_text(&read_only_hint)
                     .clicked()
                 {
-                    app.change_split(DatasetSplit::Val);
+                    app.rename.show_dialog = true;
+                    app.rename.result_message = None;
+                    ui.close_menu();
                 }
 
                 if ui
-                    .selectable_label(app.dataset.current_split() == DatasetSplit::Test, "Test")
+                    .add_enabled(
+                        app.dataset.dataset_path().is_some(),
+                        egui::Button::new(format!("{} Strip Label Metadata...", Icon::ERASER)),
+                    )
                     .clicked()
                 {
-                    app.change_split(DatasetSplit::Test);
+                    app.strip_metadata.show_dialog = true;
+                    app.strip_metadata.result_message = None;
+                    ui.close_menu();
                 }
 
-                ui.add_space(20.0);
-            }
+                if ui
+                    .add_enabled(
+                        writable && !app.dataset.get_image_files().is_empty(),
+                        egui::Button::new(format!("{} Convert Format...", Icon::FILE_IMAGE)),
+                    )
+                    .on_disabled_hover_text(&read_only_hint)
+                    .clicked()
+                {
+                    app.convert.show_dialog = true;
+                    app.convert.report = None;
+                    ui.close_menu();
+                }
 
-            // Filter button (always visible)
-            if ui.button(format!("{} Filter", Icon::FUNNEL)).clicked() {
-                app.ui.show_filter_dialog = true;
+                if ui
+                    .add_enabled(
+                        writable && !app.dataset.get_image_files().is_empty(),
+                        egui::Button::new(format!("{} Flip Augment...", Icon::FLIP_HORIZONTAL)),
+                    )
+                    .on_hover_text("Generate mirrored copies of a category to fill a deficient split")
+                    .on_disabled_hover_text(&read_only_hint)
+                    .clicked()
+                {
+                    app.open_flip_augment();
+                    ui.close_menu();
+                }
+
+                if ui
+                    .add_enabled(
+                        !app.dataset.get_image_files().is_empty(),
+                        egui::Button::new(format!("{} Export Crops...", Icon::SCISSORS)),
+                    )
+                    .on_hover_text("Save every detection as a crop in a folder per class")
+                    .clicked()
+                {
+                    app.crop_export.show_dialog = true;
+                    app.crop_export.report = None;
+                    ui.close_menu();
+                }
+
+                if ui
+                    .add_enabled(
+                        writable && app.dataset.dataset_path().is_some(),
+                        egui::Button::new(format!("{} Write data.yaml", Icon::FILE_TEXT)),
+                    )
+                    .on_hover_text("Write the split folders and class names to data.yaml, keeping other keys")
+                    .on_disabled_hover_text(&read_only_hint)
+                    .clicked()
+                {
+                    app.write_data_yaml();
+                    ui.close_menu();
+                }
+
+                if ui
+                    .add_enabled(
+                        app.dataset.dataset_path().is_some(),
+                        egui::Button::new(format!("{} Export dataset.yaml...", Icon::FILE_TEXT)),
+                    )
+                    .on_hover_text("Save a training config with the dataset path, split folders and class names")
+                    .clicked()
+                {
+                    ui.close_menu();
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("YAML", &["yaml", "yml"])
+                        .set_file_name("dataset.yaml")
+                        .save_file()
+                    {
+                        app.request_yolo_yaml_export(path);
+                    }
+                }
+
+                ui.separator();
+
+                if ui
+                    .add_enabled(
+                        app.dataset.dataset_path().is_some(),
+                        egui::Button::new(format!("{} Bookmarks...", Icon::STAR)),
+                    )
+                    .clicked()
+                {
+                    app.bookmark_dialog.show_dialog = true;
+                    ui.close_menu();
+                }
+
+                if ui
+                    .button(format!("{} Session Statistics...", Icon::TIMER))
+                    .clicked()
+                {
+                    app.ui.show_session_stats = true;
+                    ui.close_menu();
+                }
+
+                if ui
+                    .add_enabled(
+                        app.dataset.dataset_path().is_some(),
+                        egui::Button::new(format!("{} Empty Cleaner Trash...", Icon::BROOM)),
+                    )
+                    .on_hover_text("Permanently delete the files in .cleaner/trash")
+                    .clicked()
+                {
+                    app.ui.empty_trash_preview = Some(app.cleaner_trash_usage());
+                    ui.close_menu();
+                }
+            });
+
+            if ui.button(format!("{} Settings", Icon::GEAR)).clicked() {
+                app.ui.show_settings_dialog = true;
             }
 
+            ui.toggle_value(&mut app.ui.show_log_panel, format!("{} Log", Icon::SCROLL))
+                .on_hover_text("Show recent log messages");
+
+            // Help menu
+            ui.menu_button(format!("{} Help", Icon::QUESTION), |ui| {
+                if ui
+                    .button(format!("{} Keyboard Shortcuts", Icon::KEYBOARD))
+                    .clicked()
+                {
+                    app.ui.show_shortcuts_dialog = true;
+                    ui.close_menu();
+                }
+            });
+
             ui.add_space(20.0);
 
             if !app.dataset.get_image_files().is_empty() {
@@ -124,3 +419,94 @@ pub fn render_top_panel(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
         });
     });
 }
+
+/// Spinner while automatic analyses run, then a checkmark that opens their results
+fn render_auto_analysis_status(app: &mut DatasetCleanerApp, ui: &mut egui::Ui) {
+    let balance = app.balance.auto_status;
+    let integrity = app.integrity.auto_status;
+
+    if balance == AutoAnalysisStatus::Running || integrity == AutoAnalysisStatus::Running {
+        let mut hover = Vec::new();
+        if balance == AutoAnalysisStatus::Running {
+            hover.push(format!(
+                "Analyzing balance: {} / {} images",
+                app.balance.current_progress, app.balance.total_images
+            ));
+        }
+        if integrity == AutoAnalysisStatus::Running {
+            hover.push(format!(
+                "Checking integrity: {} / {} files",
+                app.integrity.current_progress, app.integrity.total_files
+            ));
+        }
+        ui.spinner().on_hover_text(hover.join("\n"));
+    } else if balance == AutoAnalysisStatus::Done || integrity == AutoAnalysisStatus::Done {
+        let response = ui
+            .add(
+                egui::Label::new(
+                    egui::RichText::new(Icon::CHECK_CIRCLE).color(egui::Color32::from_rgb(100, 200, 100)),
+                )
+                .sense(egui::Sense::click()),
+            )
+            .on_hover_text("Automatic analysis finished - click to open the results");
+        if response.clicked() {
+            if balance == AutoAnalysisStatus::Done {
+                app.open_balance_analysis();
+            } else {
+                app.balance.current_tab = 1;
+                app.balance.show_dialog = true;
+            }
+        }
+    }
+}
+
+/// Banner above the top panel while the dataset is open read-only
+fn render_read_only_banner(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    let Some(reason) = app.read_only_reason() else {
+        return;
+    };
+
+    egui::TopBottomPanel::top("read_only_banner")
+        .frame(
+            egui::Frame::none()
+                .fill(egui::Color32::from_rgb(120, 70, 0))
+                .inner_margin(egui::Margin::symmetric(8.0, 4.0)),
+        )
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} READ-ONLY", Icon::LOCK))
+                        .strong()
+                        .color(egui::Color32::WHITE),
+                );
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{}. Browsing, filtering and analysis work; deleting, moving and rewriting files is disabled.",
+                        capitalize(reason)
+                    ))
+                    .color(egui::Color32::WHITE),
+                );
+
+                if app.dataset_lock.read_only {
+                    if ui
+                        .button("Check Again")
+                        .on_hover_text("Check whether the other instance is still running, or take over")
+                        .clicked()
+                    {
+                        app.review_dataset_lock();
+                    }
+                } else if !app.launched_read_only && ui.button("Turn Off").clicked() {
+                    app.settings.read_only = false;
+                    app.settings.save();
+                }
+            });
+        });
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -1,11 +1,14 @@
 //! Rebalance dialog for previewing and executing dataset rebalancing.
 
 use crate::app::DatasetCleanerApp;
-use crate::core::analysis::{
-    ImageCategory, RebalanceConfig, SelectionStrategy, TargetRatios,
-};
+use crate::core::analysis::{CollisionPolicy, ImageCategory};
 use crate::core::dataset::DatasetSplit;
+use crate::ui::distribution_bars::render_split_comparison_bars;
 use eframe::egui;
+use std::path::PathBuf;
+
+/// Share of a split's images a global plan may move out before the preview warns
+const SHRINK_WARNING_RATIO: f32 = 0.20;
 
 /// Render the rebalance dialog (preview, progress, or results)
 pub fn render_rebalance_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
@@ -29,7 +32,10 @@ pub fn render_rebalance_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context)
 fn render_preview_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     let mut should_execute = false;
     let mut should_close = false;
+    let mut execute_remaining = false;
+    let mut recalculate = false;
     let is_global = app.rebalance.is_global;
+    let read_only_hint = app.read_only_hint();
 
     let title = if is_global { "🌐 Global Rebalance Preview" } else { "📦 Rebalance Preview" };
 
@@ -39,12 +45,63 @@ fn render_preview_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
         .default_width(if is_global { 600.0 } else { 500.0 })
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
+            // Images moved or deleted since the plan was calculated
+            if let Some(stale) = app.rebalance.stale_actions {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(80, 50, 20))
+                    .inner_margin(8.0)
+                    .rounding(4.0)
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "⚠ {} image(s) in this plan were moved or deleted since it was calculated",
+                                stale
+                            ))
+                            .strong()
+                            .color(egui::Color32::from_rgb(255, 150, 0)),
+                        );
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(read_only_hint.is_empty(), egui::Button::new("▶ Execute Remaining"))
+                                .on_disabled_hover_text(&read_only_hint)
+                                .clicked()
+                            {
+                                execute_remaining = true;
+                            }
+                            if ui.button("🔄 Recalculate Plan").clicked() {
+                                recalculate = true;
+                            }
+                        });
+                    });
+                ui.add_space(10.0);
+            }
+
             if is_global {
+                let ratios_valid = app.global_split_ratios().is_valid();
                 // Global plan preview
-                if let Some(plan) = &app.rebalance.global_plan {
+                if let Some(plan) = &mut app.rebalance.global_plan {
                     ui.heading("Global Multi-Split Optimization");
                     ui.add_space(10.0);
 
+                    // Targets the plan could not reach
+                    if !plan.warnings.is_empty() {
+                        egui::Frame::none()
+                            .fill(egui::Color32::from_rgb(80, 50, 20))
+                            .inner_margin(8.0)
+                            .rounding(4.0)
+                            .show(ui, |ui| {
+                                ui.label(
+                                    egui::RichText::new(format!("⚠ {} target(s) could not be met", plan.warnings.len()))
+                                        .strong()
+                                        .color(egui::Color32::from_rgb(255, 150, 0)),
+                                );
+                                for warning in &plan.warnings {
+                                    ui.label(format!("• {}", warning.message()));
+                                }
+                            });
+                        ui.add_space(10.0);
+                    }
+
                     // Move summary
                     ui.group(|ui| {
                         ui.label(egui::RichText::new("MOVE SUMMARY").strong().size(14.0));
@@ -52,17 +109,31 @@ fn render_preview_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                         ui.label(format!("Total files to move: {}", plan.total_moves));
                         ui.label(format!("Move groups: {} (iterations: {})", plan.moves.len(), plan.iterations_used));
                         ui.add_space(5.0);
-                        for move_group in &plan.moves {
-                            ui.label(format!(
-                                "  {} → {}: {} {} images",
-                                move_group.from_split.as_str().to_uppercase(),
-                                move_group.to_split.as_str().to_uppercase(),
-                                move_group.count,
-                                move_group.category.as_str()
-                            ));
+                        render_preview_table(ui, "global_preview_table", &plan.preview_as_table());
+
+                        // Net outcome per split, e.g. "TRAIN: -143 / VAL: +89 / TEST: +54"
+                        let deltas = plan.split_deltas();
+                        let net: Vec<String> = DatasetSplit::all()
+                            .iter()
+                            .map(|split| format!("{}: {:+}", split.as_str().to_uppercase(), deltas[split]))
+                            .collect();
+                        ui.add_space(5.0);
+                        ui.label(egui::RichText::new(format!("Net change: {}", net.join(" / "))).strong());
+                        for split in plan.shrinking_splits(SHRINK_WARNING_RATIO) {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(255, 150, 0),
+                                format!(
+                                    "⚠ {} loses more than {:.0}% of its images",
+                                    split.as_str().to_uppercase(),
+                                    SHRINK_WARNING_RATIO * 100.0
+                                ),
+                            );
                         }
                     });
 
+                    // Names already taken in a destination split
+                    render_collisions(ui, "global_rebalance_collisions", &plan.collisions, &mut plan.collision_policy);
+
                     // Projected stats
                     if let (Some(current), Some(projected)) = (&plan.current_stats, &plan.projected_stats) {
                         ui.add_space(10.0);
@@ -72,6 +143,19 @@ fn render_preview_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                             for split in [DatasetSplit::Train, DatasetSplit::Val, DatasetSplit::Test] {
                                 let cur = current.get(split);
                                 let proj = projected.get(split);
+                                if !current.is_present(split) {
+                                    let text = if proj.total_images == 0 {
+                                        format!("{}: not present", split.as_str().to_uppercase())
+                                    } else {
+                                        format!(
+                                            "{}: not present → {} images (folder will be created)",
+                                            split.as_str().to_uppercase(),
+                                            proj.total_images
+                                        )
+                                    };
+                                    ui.label(egui::RichText::new(text).color(egui::Color32::GRAY));
+                                    continue;
+                                }
                                 ui.label(format!(
                                     "{}: BG {:.1}%→{:.1}%, Player {:.1}%→{:.1}%",
                                     split.as_str().to_uppercase(),
@@ -81,15 +165,30 @@ fn render_preview_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                                     proj.player_percentage()
                                 ));
                             }
+                            ui.add_space(8.0);
+                            render_split_comparison_bars(ui, current, projected, &app.config);
                         });
                     }
 
+                    render_move_estimate(ui, app.rebalance.move_estimate);
                     ui.add_space(15.0);
                     ui.colored_label(egui::Color32::from_rgb(255, 200, 100), "⚠️ Files will be physically moved. This can be undone.");
                     ui.add_space(10.0);
 
+                    if !ratios_valid {
+                        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), "⚠ Split sizes must sum to 100%");
+                    }
+                    let hint = if ratios_valid { read_only_hint.clone() } else { "Split sizes must sum to 100%".to_string() };
+
                     ui.horizontal(|ui| {
-                        if ui.button(egui::RichText::new("✓ Execute").color(egui::Color32::GREEN)).clicked() {
+                        if ui
+                            .add_enabled(
+                                read_only_hint.is_empty() && ratios_valid,
+                                egui::Button::new(egui::RichText::new("✓ Execute").color(egui::Color32::GREEN)),
+                            )
+                            .on_disabled_hover_text(&hint)
+                            .clicked()
+                        {
                             should_execute = true;
                         }
                         if ui.button("❌ Cancel").clicked() {
@@ -104,20 +203,19 @@ fn render_preview_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                 }
             } else {
                 // Single-split plan preview
-                if let Some(plan) = &app.rebalance.plan {
+                if let Some(plan) = &mut app.rebalance.plan {
                     ui.heading("Proposed Changes");
                     ui.add_space(10.0);
 
                     ui.group(|ui| {
                         ui.label(egui::RichText::new("MOVE SUMMARY").strong().size(14.0));
                         ui.add_space(5.0);
-                        let from = plan.from_split.map(|s| s.as_str().to_uppercase()).unwrap_or_else(|| "?".to_string());
-                        let to = plan.to_split.map(|s| s.as_str().to_uppercase()).unwrap_or_else(|| "?".to_string());
-                        let cat = plan.category.map(|c| c.as_str().to_string()).unwrap_or_else(|| "?".to_string());
-                        ui.label(format!("Move {} {} images", plan.len(), cat));
-                        ui.label(format!("From: {} → To: {}", from, to));
+                        render_preview_table(ui, "single_preview_table", &plan.preview_as_table());
                     });
 
+                    // Names already taken in the destination split
+                    render_collisions(ui, "rebalance_collisions", &plan.collisions, &mut plan.collision_policy);
+
                     if let (Some(current), Some(projected)) = (&plan.current_stats, &plan.projected_stats) {
                         ui.add_space(10.0);
                         ui.group(|ui| {
@@ -133,15 +231,40 @@ fn render_preview_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                                 current.background, current.get_percentage(ImageCategory::Background),
                                 projected.background, projected.get_percentage(ImageCategory::Background)
                             ));
+                            if let Some((before, after)) = plan.quality {
+                                let improvement = if before > 0.0 {
+                                    (before - after) / before * 100.0
+                                } else {
+                                    0.0
+                                };
+                                let color = if after <= before {
+                                    egui::Color32::from_rgb(100, 200, 100)
+                                } else {
+                                    egui::Color32::from_rgb(255, 100, 100)
+                                };
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("Expected improvement: {:.3} → {:.3}", before, after))
+                                        .on_hover_text("Imbalance score of the source split: distance of the player, background and hard-case shares from their targets (0 = on target)");
+                                    ui.colored_label(color, format!("({:+.0}%)", -improvement));
+                                });
+                            }
                         });
                     }
 
+                    render_move_estimate(ui, app.rebalance.move_estimate);
                     ui.add_space(15.0);
                     ui.colored_label(egui::Color32::from_rgb(255, 200, 100), "⚠️ Files will be physically moved. This can be undone.");
                     ui.add_space(10.0);
 
                     ui.horizontal(|ui| {
-                        if ui.button(egui::RichText::new("✓ Execute").color(egui::Color32::GREEN)).clicked() {
+                        if ui
+                            .add_enabled(
+                                read_only_hint.is_empty(),
+                                egui::Button::new(egui::RichText::new("✓ Execute").color(egui::Color32::GREEN)),
+                            )
+                            .on_disabled_hover_text(&read_only_hint)
+                            .clicked()
+                        {
                             should_execute = true;
                         }
                         if ui.button("❌ Cancel").clicked() {
@@ -165,11 +288,93 @@ fn render_preview_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
         }
     }
 
+    if execute_remaining {
+        app.execute_remaining_rebalance();
+    }
+
+    if recalculate {
+        app.recalculate_rebalance_plan();
+    }
+
     if should_close {
         app.close_rebalance();
     }
 }
 
+/// Warn about images whose name is already taken in the destination and let
+/// the user pick the collision policy (nothing is shown without collisions)
+fn render_collisions(ui: &mut egui::Ui, id: &str, collisions: &[PathBuf], policy: &mut CollisionPolicy) {
+    if collisions.is_empty() {
+        return;
+    }
+    ui.add_space(10.0);
+    egui::Frame::none()
+        .fill(egui::Color32::from_rgb(80, 50, 20))
+        .inner_margin(8.0)
+        .rounding(4.0)
+        .show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(format!(
+                    "⚠ {} image(s) share a name with a file in the destination",
+                    collisions.len()
+                ))
+                .strong()
+                .color(egui::Color32::from_rgb(255, 150, 0)),
+            );
+            ui.horizontal(|ui| {
+                ui.label("On collision:");
+                for option in [CollisionPolicy::Skip, CollisionPolicy::Rename] {
+                    ui.radio_value(policy, option, option.as_str());
+                }
+            });
+            egui::CollapsingHeader::new("Colliding images")
+                .id_salt(id)
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                        for path in collisions {
+                            let name = path
+                                .file_name()
+                                .map(|n| n.to_string_lossy().to_string())
+                                .unwrap_or_default();
+                            ui.label(egui::RichText::new(name).monospace().small());
+                        }
+                    });
+                });
+        });
+}
+
+/// Render the measured move throughput and how long the plan should take
+fn render_move_estimate(ui: &mut egui::Ui, estimate: Option<(f64, std::time::Duration)>) {
+    let Some((speed, duration)) = estimate else {
+        return;
+    };
+    let minutes = (duration.as_secs_f64() / 60.0).ceil().max(1.0);
+    ui.add_space(10.0);
+    ui.label(format!("Estimated throughput: {:.1} MB/s, ~{:.0} minute(s)", speed, minutes))
+        .on_hover_text("Measured by moving a 1 MB test file between the split folders");
+}
+
+/// Render (source, destination, description) plan rows as a grid
+fn render_preview_table(ui: &mut egui::Ui, id: &str, rows: &[(String, String, String)]) {
+    egui::Grid::new(id)
+        .num_columns(3)
+        .spacing([20.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for heading in ["From", "To", "Move"] {
+                ui.label(egui::RichText::new(heading).strong());
+            }
+            ui.end_row();
+
+            for (from, to, description) in rows {
+                ui.label(egui::RichText::new(from).monospace());
+                ui.label(egui::RichText::new(to).monospace());
+                ui.label(description);
+                ui.end_row();
+            }
+        });
+}
+
 /// Render progress dialog during execution
 fn render_progress_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     egui::Window::new("🔄 Rebalancing...")
@@ -192,6 +397,10 @@ fn render_progress_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                     "{} / {} images moved",
                     current, total
                 )));
+                let rate = app.rebalance.rate.label(current, total, "images");
+                if !rate.is_empty() {
+                    ui.label(egui::RichText::new(rate).small().weak());
+                }
             } else {
                 ui.spinner();
             }
@@ -218,6 +427,7 @@ fn render_progress_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
 fn render_result_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     let mut should_close = false;
     let mut should_undo = false;
+    let read_only_hint = app.read_only_hint();
 
     egui::Window::new("✅ Rebalance Complete")
         .collapsible(false)
@@ -246,11 +456,66 @@ fn render_result_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
                     );
 
                     if failed_count > 0 {
+                        egui::CollapsingHeader::new(
+                            egui::RichText::new(format!("✗ Failed: {} images", failed_count))
+                                .color(egui::Color32::from_rgb(255, 100, 100)),
+                        )
+                        .id_salt("rebalance_failed_files")
+                        .show(ui, |ui| {
+                            egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                                for result in results.iter().filter(|r| !r.success) {
+                                    let name = result
+                                        .action
+                                        .image_path
+                                        .file_name()
+                                        .map(|n| n.to_string_lossy().to_string())
+                                        .unwrap_or_default();
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "{}: {}",
+                                            name,
+                                            result.error.as_deref().unwrap_or("unknown error")
+                                        ))
+                                        .monospace()
+                                        .small(),
+                                    );
+                                }
+                            });
+                        });
+                    }
+
+                    if app.rebalance.stale_dropped > 0 {
                         ui.colored_label(
-                            egui::Color32::from_rgb(255, 100, 100),
-                            format!("✗ Failed: {} images", failed_count),
+                            egui::Color32::GRAY,
+                            format!(
+                                "⊘ Skipped: {} images moved or deleted since planning",
+                                app.rebalance.stale_dropped
+                            ),
                         );
                     }
+
+                    // Files on another drive were copied and deleted instead of renamed
+                    let copied: Vec<_> = results.iter().filter(|r| r.copied).collect();
+                    if !copied.is_empty() {
+                        egui::CollapsingHeader::new(format!(
+                            "⇄ Copied across drives: {} images",
+                            copied.len()
+                        ))
+                        .id_salt("rebalance_copied_files")
+                        .show(ui, |ui| {
+                            egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                                for result in copied {
+                                    let name = result
+                                        .action
+                                        .image_path
+                                        .file_name()
+                                        .map(|n| n.to_string_lossy().to_string())
+                                        .unwrap_or_default();
+                                    ui.label(egui::RichText::new(name).monospace().small());
+                                }
+                            });
+                        });
+                    }
                 });
 
                 ui.add_space(10.0);
@@ -261,10 +526,13 @@ fn render_result_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
 
                 // Action buttons
                 ui.horizontal(|ui| {
-                    if app.rebalance.can_undo() {
-                        if ui.button("↩ Undo All").clicked() {
-                            should_undo = true;
-                        }
+                    if app.rebalance.can_undo()
+                        && ui
+                            .add_enabled(read_only_hint.is_empty(), egui::Button::new("↩ Undo All"))
+                            .on_disabled_hover_text(&read_only_hint)
+                            .clicked()
+                    {
+                        should_undo = true;
                     }
 
                     if ui.button("✓ Done").clicked() {
@@ -291,68 +559,13 @@ fn render_result_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
     if should_undo {
         app.undo_rebalance();
         app.rebalance.show_result = false;
+        app.rebalance.stale_dropped = 0;
     }
 
     if should_close {
         // Clear results when closing (can't undo anymore)
         app.rebalance.last_results = None;
         app.rebalance.show_result = false;
+        app.rebalance.stale_dropped = 0;
     }
 }
-
-/// Configuration UI component that can be embedded in the balance dialog
-pub fn render_rebalance_config(
-    ui: &mut egui::Ui,
-    current_split: DatasetSplit,
-) -> Option<RebalanceConfig> {
-    let mut config: Option<RebalanceConfig> = None;
-
-    ui.group(|ui| {
-        ui.label(
-            egui::RichText::new("🔄 AUTO-REBALANCE")
-                .strong()
-                .size(14.0)
-                .color(egui::Color32::from_rgb(100, 180, 255)),
-        );
-        ui.add_space(5.0);
-
-        ui.label("Move excess images to another split:");
-        ui.add_space(5.0);
-
-        // Destination split selection
-        ui.horizontal(|ui| {
-            ui.label("Move to:");
-            
-            // Show options excluding current split
-            let destinations: Vec<DatasetSplit> = 
-                [DatasetSplit::Train, DatasetSplit::Val, DatasetSplit::Test]
-                    .into_iter()
-                    .filter(|s| *s != current_split)
-                    .collect();
-
-            for dest in destinations {
-                if ui.button(dest.as_str()).clicked() {
-                    // Create config for moving Background images (most common imbalance)
-                    config = Some(RebalanceConfig {
-                        target_ratios: TargetRatios::default(),
-                        selection_strategy: SelectionStrategy::Random,
-                        preserve_ct_t_balance: true,
-                        source_split: current_split,
-                        destination_split: dest,
-                        category: ImageCategory::Background,
-                    });
-                }
-            }
-        });
-
-        ui.add_space(5.0);
-        ui.label(
-            egui::RichText::new("Moves excess background images to balance the dataset.")
-                .size(11.0)
-                .italics()
-                .color(egui::Color32::GRAY),
-        );
-    });
-
-    config
-}
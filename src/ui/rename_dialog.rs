@@ -0,0 +1,172 @@
+use crate::app::DatasetCleanerApp;
+use crate::core::operations::plan_rename_stems;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Number of example names shown in the preview
+const PREVIEW_COUNT: usize = 5;
+
+/// Render the batch rename dialog (Tools > Batch Rename, or "Rename Images" in the bottom panel)
+pub fn render_rename_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.rename.show_dialog {
+        return;
+    }
+
+    let mut rename_clicked = false;
+    let mut close_dialog = false;
+
+    egui::Window::new(format!("{} Batch Rename", Icon::TEXT_AA))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(380.0);
+
+            // Only the images matching the active filter, in navigation order
+            let image_files = app.filtered_image_files();
+            if app.filter.is_active() {
+                ui.label(format!(
+                    "Rename the {} filtered images in the {} split (labels follow their images).",
+                    image_files.len(),
+                    app.dataset.current_split().as_str()
+                ));
+            } else {
+                ui.label(format!(
+                    "Rename all {} images in the {} split (labels follow their images).",
+                    image_files.len(),
+                    app.dataset.current_split().as_str()
+                ));
+            }
+            ui.add_space(10.0);
+
+            // Scheme selection
+            ui.add_enabled_ui(!app.rename.processing && !app.rename.confirm, |ui| ui.group(|ui| {
+                ui.label(egui::RichText::new("Naming Scheme").strong().size(16.0));
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut app.rename.use_timestamp, false, "Sequential");
+                    ui.radio_value(&mut app.rename.use_timestamp, true, "Timestamp");
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Prefix:");
+                    ui.add(egui::TextEdit::singleline(&mut app.rename.prefix).desired_width(120.0));
+                });
+
+                if !app.rename.use_timestamp {
+                    ui.horizontal(|ui| {
+                        ui.label("Digits:");
+                        ui.add(egui::DragValue::new(&mut app.rename.digits).range(1..=10));
+                    });
+                } else {
+                    ui.label(
+                        egui::RichText::new("Uses the label's Time metadata, or the file's modified time")
+                            .small()
+                            .color(egui::Color32::GRAY),
+                    );
+                }
+            }));
+
+            ui.add_space(10.0);
+
+            // Preview of the first few names
+            let preview_files: Vec<_> = image_files.iter().take(PREVIEW_COUNT).cloned().collect();
+            let stems = plan_rename_stems(&preview_files, &app.rename.scheme());
+            ui.label(egui::RichText::new(format!("{} Preview:", Icon::MAGNIFYING_GLASS)).italics());
+            for (path, stem) in preview_files.iter().zip(&stems) {
+                let old_name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let ext = path
+                    .extension()
+                    .map(|e| format!(".{}", e.to_string_lossy()))
+                    .unwrap_or_default();
+                ui.label(
+                    egui::RichText::new(format!("{} → {}{}", old_name, stem, ext))
+                        .monospace()
+                        .small(),
+                );
+            }
+
+            ui.add_space(10.0);
+
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 150, 0),
+                "⚠ Renaming clears the delete and rebalance undo history.",
+            );
+
+            // Progress of the running rename
+            if app.rename.processing {
+                match app.rename.progress {
+                    Some((current, total)) => {
+                        let fraction = if total > 0 { current as f32 / total as f32 } else { 0.0 };
+                        ui.add(
+                            egui::ProgressBar::new(fraction)
+                                .text(format!("{} / {} renames", current, total))
+                                .animate(true),
+                        );
+                    }
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Renaming...");
+                        });
+                    }
+                }
+                ctx.request_repaint();
+            }
+
+            if let Some(message) = &app.rename.result_message {
+                ui.add_space(5.0);
+                ui.label(egui::RichText::new(message).strong());
+            }
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if app.rename.confirm {
+                    ui.label(format!("Rename {} images?", image_files.len()));
+                    if ui.button(format!("{} Yes, Rename", Icon::CHECK)).clicked() {
+                        rename_clicked = true;
+                        app.rename.confirm = false;
+                    }
+                    if ui.button("Back").clicked() {
+                        app.rename.confirm = false;
+                    }
+                    return;
+                }
+
+                let can_rename = !image_files.is_empty()
+                    && !app.rename.prefix.trim().is_empty()
+                    && !app.rename.processing;
+                if ui
+                    .add_enabled(
+                        can_rename,
+                        egui::Button::new(format!("{} Rename", Icon::CHECK)),
+                    )
+                    .clicked()
+                {
+                    app.rename.confirm = true;
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    // Handle actions after the dialog is drawn
+    if rename_clicked {
+        app.start_batch_rename();
+    }
+
+    if close_dialog {
+        app.rename.show_dialog = false;
+        app.rename.confirm = false;
+    }
+}
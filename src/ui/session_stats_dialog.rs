@@ -0,0 +1,89 @@
+use crate::app::DatasetCleanerApp;
+use crate::core::statistics::format_duration;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Render the session statistics dialog (Tools > Session Statistics)
+pub fn render_session_stats_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.ui.show_session_stats {
+        return;
+    }
+
+    let mut close_dialog = false;
+    let stats = &app.session_stats;
+
+    egui::Window::new(format!("{} Session Statistics", Icon::TIMER))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(300.0);
+
+            let average = match stats.average_seconds_per_image() {
+                Some(seconds) => format!("{:.1} s", seconds),
+                None => "—".to_string(),
+            };
+            let rows = [
+                (
+                    Icon::CLOCK,
+                    "Time elapsed",
+                    format_duration(stats.elapsed()),
+                ),
+                (
+                    Icon::EYE,
+                    "Images viewed",
+                    stats.images_viewed().to_string(),
+                ),
+                (Icon::TRASH, "Deleted", stats.deletes.to_string()),
+                (
+                    Icon::ARROW_COUNTER_CLOCKWISE,
+                    "Undos",
+                    stats.undos.to_string(),
+                ),
+                (
+                    Icon::ARROWS_LEFT_RIGHT,
+                    "Moved between splits",
+                    stats.moves.to_string(),
+                ),
+                (Icon::GAUGE, "Average per image", average),
+            ];
+
+            egui::Grid::new("session_stats_grid")
+                .num_columns(2)
+                .spacing([20.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for (icon, name, value) in rows {
+                        ui.label(format!("{} {}", icon, name));
+                        ui.label(egui::RichText::new(value).monospace().strong());
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(format!("{} Copy as Text", Icon::COPY))
+                    .on_hover_text("Copy the statistics to the clipboard for a work log")
+                    .clicked()
+                {
+                    ctx.copy_text(stats.to_text());
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    // Keep the elapsed time ticking
+    ctx.request_repaint_after(std::time::Duration::from_secs(1));
+
+    if close_dialog {
+        app.ui.show_session_stats = false;
+    }
+}
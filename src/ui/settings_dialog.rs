@@ -0,0 +1,328 @@
+use crate::app::DatasetCleanerApp;
+use crate::config::{default_classes, ClassStyle};
+use crate::core::operations::DeletionMode;
+use crate::state::{ZoomNavigateMode, MAX_AUTO_ADVANCE_DELAY_MS, MAX_UNDO_DEPTH};
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Render the settings dialog with the class table editor
+pub fn render_settings_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.ui.show_settings_dialog {
+        return;
+    }
+
+    let mut close_dialog = false;
+    let mut remove_index = None;
+    // Every edit is saved right away, like the other settings
+    let mut classes_changed = false;
+    let mut settings_changed = false;
+
+    egui::Window::new(format!("{} Settings", Icon::GEAR))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(420.0);
+
+            // Class table section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Classes", Icon::TAG))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.label(
+                    egui::RichText::new("Changes apply immediately to the displayed image")
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+                ui.add_space(5.0);
+
+                egui::Grid::new("class_table")
+                    .num_columns(5)
+                    .spacing([10.0, 6.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new("ID").strong());
+                        ui.label(egui::RichText::new("Name").strong());
+                        ui.label(egui::RichText::new("Box").strong());
+                        ui.label(egui::RichText::new("Text").strong());
+                        ui.label("");
+                        ui.end_row();
+
+                        for (idx, class) in app.config.classes.iter_mut().enumerate() {
+                            classes_changed |= ui.add(egui::DragValue::new(&mut class.id).range(0..=999)).changed();
+                            classes_changed |= ui
+                                .add(egui::TextEdit::singleline(&mut class.name).desired_width(120.0))
+                                .changed();
+                            classes_changed |= ui.color_edit_button_srgb(&mut class.box_color).changed();
+                            classes_changed |= ui.color_edit_button_srgb(&mut class.text_color).changed();
+                            if ui
+                                .small_button(Icon::TRASH.to_string())
+                                .on_hover_text("Remove class")
+                                .clicked()
+                            {
+                                remove_index = Some(idx);
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                // Duplicate ids make lookups ambiguous (first match wins)
+                let ids = app.config.class_ids();
+                let has_duplicates = ids
+                    .iter()
+                    .enumerate()
+                    .any(|(i, id)| ids[..i].contains(id));
+                if has_duplicates {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 150, 0),
+                        "⚠ Duplicate class ids: only the first entry is used",
+                    );
+                }
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.button(format!("{} Add Class", Icon::PLUS)).clicked() {
+                        let next_id = ids.iter().max().map(|id| id + 1).unwrap_or(0);
+                        app.config
+                            .classes
+                            .push(ClassStyle::new(next_id, &format!("Class {}", next_id), [200, 200, 200]));
+                        classes_changed = true;
+                    }
+                    if ui.button(format!("{} Reset to Defaults", Icon::ARROW_COUNTER_CLOCKWISE)).clicked() {
+                        app.config.classes = default_classes();
+                        classes_changed = true;
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            // Viewer section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Viewer", Icon::MAGNIFYING_GLASS_PLUS))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                ui.label("When navigating between images:");
+                let mut mode = app.settings.zoom_navigate_mode();
+                let mut changed = false;
+                for (value, text) in [
+                    (ZoomNavigateMode::Reset, "Reset zoom"),
+                    (ZoomNavigateMode::Keep, "Keep the current zoom"),
+                    (ZoomNavigateMode::PerImage, "Remember zoom and scroll per image"),
+                ] {
+                    changed |= ui.radio_value(&mut mode, value, text).changed();
+                }
+                if changed {
+                    app.set_zoom_navigate_mode(mode);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Maximum zoom:");
+                    let response = ui.add(
+                        egui::Slider::new(&mut app.settings.max_zoom, 1.5..=10.0)
+                            .custom_formatter(|value, _| format!("{:.0}%", value * 100.0)),
+                    );
+                    if response.changed() {
+                        // Keep the current zoom inside the new range
+                        app.set_zoom(app.image.zoom_level);
+                        settings_changed = true;
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            // Review section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Review", Icon::EYE))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+                settings_changed |= ui.checkbox(&mut app.settings.read_only, "Read-only mode").changed();
+                ui.label(
+                    egui::RichText::new(
+                        "Browse, filter and analyze without deleting, moving or rewriting files",
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(10.0);
+
+            // Deletion section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Deletion", Icon::TRASH))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                ui.label("Deleted images and labels go to:");
+                for mode in DeletionMode::all() {
+                    settings_changed |= ui
+                        .radio_value(&mut app.settings.deletion_mode, mode, mode.as_str())
+                        .changed();
+                }
+                let hint = match app.settings.deletion_mode {
+                    DeletionMode::TempDir => "Undoable; the OS may clear the temp folder",
+                    DeletionMode::RecycleBin => "Restore from the OS recycle bin; not undoable here",
+                    DeletionMode::DatasetTrash => {
+                        "Undoable; kept in the dataset until Tools > Empty Cleaner Trash"
+                    }
+                };
+                ui.label(egui::RichText::new(hint).small().color(egui::Color32::GRAY));
+
+                ui.horizontal(|ui| {
+                    ui.label("Undo history:");
+                    let response = ui.add(
+                        egui::Slider::new(&mut app.settings.undo_depth, 1..=MAX_UNDO_DEPTH)
+                            .logarithmic(true)
+                            .suffix(" actions"),
+                    );
+                    if response.changed() {
+                        app.set_undo_depth(app.settings.undo_depth);
+                        settings_changed = true;
+                    }
+                });
+                ui.label(
+                    egui::RichText::new(
+                        "Older actions are dropped and their temp-folder copies deleted",
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(10.0);
+
+            // Auto-advance section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} Auto-advance", Icon::FAST_FORWARD))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                settings_changed |= ui
+                    .checkbox(
+                        &mut app.settings.auto_advance,
+                        "Go to the next image after saving a label edit or augmenting",
+                    )
+                    .changed();
+                ui.add_enabled_ui(app.settings.auto_advance, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Delay:");
+                        settings_changed |= ui
+                            .add(
+                                egui::Slider::new(
+                                    &mut app.settings.auto_advance_delay_ms,
+                                    0..=MAX_AUTO_ADVANCE_DELAY_MS,
+                                )
+                                .suffix(" ms"),
+                            )
+                            .changed();
+                    });
+                });
+                ui.label(
+                    egui::RichText::new("Deleting already shows the next image in its place")
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(10.0);
+
+            // On-load analysis section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} When Opening a Dataset or Switching Splits", Icon::CHART_BAR))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                settings_changed |= ui
+                    .checkbox(
+                        &mut app.settings.auto_analyze_on_load,
+                        "Analyze balance automatically",
+                    )
+                    .changed();
+                settings_changed |= ui
+                    .checkbox(
+                        &mut app.settings.auto_analyze_integrity,
+                        "Run the integrity check",
+                    )
+                    .changed();
+                ui.label(
+                    egui::RichText::new("Both run in the background; the top panel shows when results are ready and Analyze Balance opens them")
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(10.0);
+
+            // data.yaml section
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{} data.yaml", Icon::FILE_TEXT))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.add_space(5.0);
+
+                settings_changed |= ui
+                    .checkbox(
+                        &mut app.settings.auto_update_data_yaml,
+                        "Update data.yaml after a global rebalance or split swap",
+                    )
+                    .changed();
+                ui.label(
+                    egui::RichText::new(
+                        "Writes the split folders, nc and names; other keys are kept",
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    // Handle actions after the dialog is drawn
+    if let Some(idx) = remove_index {
+        app.config.classes.remove(idx);
+        classes_changed = true;
+    }
+
+    if classes_changed {
+        app.save_class_table();
+    }
+    if settings_changed {
+        app.settings.save();
+    }
+
+    if close_dialog {
+        app.close_settings_dialog();
+    }
+}
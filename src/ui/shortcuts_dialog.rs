@@ -0,0 +1,95 @@
+use crate::app::DatasetCleanerApp;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Shortcut groups shown in the dialog: (section title, [(keys, description)])
+const SHORTCUT_SECTIONS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Navigation",
+        &[
+            ("← / →", "Previous / next image"),
+            ("Alt + ← / →", "Jump 10 images back / forward"),
+            ("Shift + ← / →", "Previous / next image of the category picked in the bottom panel"),
+            ("Page Up / Page Down", "Jump 10 images back / forward"),
+            ("Home / Ctrl + Home", "First image (in the filtered view)"),
+            ("End / Ctrl + End", "Last image (in the filtered view)"),
+            ("Alt + E", "Next image that fails to load"),
+            ("1 / 2 / 3", "Switch to Train / Val / Test split"),
+        ],
+    ),
+    (
+        "View",
+        &[
+            ("Space", "Toggle fullscreen mode"),
+            ("Ctrl + Mouse Wheel", "Zoom at the cursor"),
+            ("Middle Mouse Drag", "Pan the zoomed image"),
+            ("Ctrl + = / Ctrl + -", "Zoom in / out by 10%"),
+            ("Ctrl + 0", "Reset zoom to fit"),
+            ("P", "Pin / unpin the current image for side-by-side comparison"),
+        ],
+    ),
+    (
+        "Actions",
+        &[
+            ("Delete", "Delete current image and label"),
+            ("B", "Bookmark / unbookmark the current image"),
+            ("Ctrl + Z", "Undo delete or label edit"),
+            ("Ctrl + Y / Ctrl + Shift + Z", "Redo delete or label edit"),
+            ("Ctrl + O", "Open dataset folder"),
+            ("Ctrl + F", "Open filter dialog"),
+            ("Ctrl + 1 … 9", "Apply filter preset 1–9"),
+            ("Ctrl + ? / F1", "Show this dialog"),
+            ("Escape", "Close dialog / exit fullscreen"),
+        ],
+    ),
+];
+
+/// Render the keyboard shortcuts reference dialog (Help > Keyboard Shortcuts)
+pub fn render_shortcuts_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.ui.show_shortcuts_dialog {
+        return;
+    }
+
+    let mut close_dialog = false;
+
+    egui::Window::new(format!("{} Keyboard Shortcuts", Icon::KEYBOARD))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(380.0);
+
+            for (section, shortcuts) in SHORTCUT_SECTIONS {
+                ui.label(egui::RichText::new(*section).strong().size(16.0));
+                ui.add_space(5.0);
+
+                egui::Grid::new(("shortcuts_grid", *section))
+                    .num_columns(2)
+                    .spacing([20.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (keys, description) in shortcuts.iter() {
+                            ui.label(egui::RichText::new(*keys).monospace().strong());
+                            ui.label(*description);
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(10.0);
+            }
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    if close_dialog {
+        app.ui.show_shortcuts_dialog = false;
+    }
+}
@@ -0,0 +1,251 @@
+use crate::app::DatasetCleanerApp;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Maximum number of changed files listed in the dry-run preview
+const MAX_LISTED_FILES: usize = 200;
+
+/// Render the strip label metadata dialog (Tools > Strip Label Metadata)
+pub fn render_strip_metadata_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.strip_metadata.show_dialog {
+        return;
+    }
+
+    let mut dry_run_clicked = false;
+    let mut run_clicked = false;
+    let mut cancel_clicked = false;
+    let mut undo_clicked = false;
+    let mut close_dialog = false;
+
+    egui::Window::new(format!("{} Strip Label Metadata", Icon::ERASER))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(420.0);
+
+            ui.label(
+                "Remove metadata comment lines (# ...) from label files, keeping only detections.",
+            );
+            ui.add_space(10.0);
+
+            ui.add_enabled_ui(!app.strip_metadata.processing, |ui| {
+                ui.group(|ui| {
+                    ui.label(egui::RichText::new("Splits").strong().size(16.0));
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut app.strip_metadata.all_splits, true, "All splits");
+                        ui.radio_value(
+                            &mut app.strip_metadata.all_splits,
+                            false,
+                            format!("Current split ({})", app.dataset.current_split().as_str()),
+                        );
+                    });
+                });
+
+                ui.add_space(5.0);
+
+                ui.group(|ui| {
+                    ui.label(egui::RichText::new("Output").strong().size(16.0));
+                    ui.add_space(5.0);
+                    ui.radio_value(
+                        &mut app.strip_metadata.export_copy,
+                        false,
+                        "Rewrite in place (originals backed up for undo)",
+                    );
+                    ui.radio_value(
+                        &mut app.strip_metadata.export_copy,
+                        true,
+                        "Export a cleaned copy of the labels folder",
+                    );
+
+                    if app.strip_metadata.export_copy {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(format!("{} Choose Folder...", Icon::FOLDER_OPEN))
+                                .clicked()
+                            {
+                                if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                                    app.strip_metadata.output_dir = Some(path);
+                                }
+                            }
+                            let folder = app
+                                .strip_metadata
+                                .output_dir
+                                .as_ref()
+                                .map(|p| p.display().to_string())
+                                .unwrap_or_else(|| "No folder selected".to_string());
+                            ui.label(egui::RichText::new(folder).monospace().small());
+                        });
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            // Progress of the running operation
+            if app.strip_metadata.processing {
+                if let Some((current, total)) = app.strip_metadata.progress {
+                    let fraction = if total > 0 {
+                        current as f32 / total as f32
+                    } else {
+                        0.0
+                    };
+                    ui.add(
+                        egui::ProgressBar::new(fraction)
+                            .text(format!("{} / {} files", current, total))
+                            .animate(true),
+                    );
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Scanning label files...");
+                    });
+                }
+                ctx.request_repaint();
+            }
+
+            // Summary of the last dry run or strip
+            if let Some(summary) = &app.strip_metadata.summary {
+                let heading = match (summary.dry_run, app.strip_metadata.cancelled) {
+                    (_, true) => "Cancelled",
+                    (true, false) => "Dry run",
+                    (false, false) => "Done",
+                };
+                let verb = if summary.dry_run {
+                    "would be modified"
+                } else {
+                    "modified"
+                };
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{}: {} of {} files {}, {} lines removed",
+                        heading,
+                        summary.files_modified,
+                        summary.files_scanned,
+                        verb,
+                        summary.lines_removed
+                    ))
+                    .strong(),
+                );
+
+                if !summary.errors.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 100, 100),
+                        format!("{} {} files failed", Icon::WARNING, summary.errors.len()),
+                    );
+                }
+
+                if summary.dry_run && !summary.changes.is_empty() {
+                    egui::ScrollArea::vertical()
+                        .max_height(180.0)
+                        .show(ui, |ui| {
+                            for change in summary.changes.iter().take(MAX_LISTED_FILES) {
+                                let name = change
+                                    .label_path
+                                    .file_name()
+                                    .map(|n| n.to_string_lossy().into_owned())
+                                    .unwrap_or_default();
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} (-{} lines)",
+                                        name, change.removed_lines
+                                    ))
+                                    .monospace()
+                                    .small(),
+                                );
+                            }
+                            if summary.changes.len() > MAX_LISTED_FILES {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "... and {} more",
+                                        summary.changes.len() - MAX_LISTED_FILES
+                                    ))
+                                    .italics()
+                                    .small(),
+                                );
+                            }
+                        });
+                }
+            }
+
+            if let Some(message) = &app.strip_metadata.result_message {
+                ui.add_space(5.0);
+                ui.label(egui::RichText::new(message).strong());
+            }
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if app.strip_metadata.processing {
+                    if ui.button(format!("{} Cancel", Icon::X)).clicked() {
+                        cancel_clicked = true;
+                    }
+                    return;
+                }
+
+                let can_run =
+                    !app.strip_metadata.export_copy || app.strip_metadata.output_dir.is_some();
+                if ui
+                    .add_enabled(
+                        can_run,
+                        egui::Button::new(format!("{} Dry Run", Icon::MAGNIFYING_GLASS)),
+                    )
+                    .clicked()
+                {
+                    dry_run_clicked = true;
+                }
+                let run_label = if app.strip_metadata.export_copy {
+                    "Export"
+                } else {
+                    "Strip"
+                };
+                if ui
+                    .add_enabled(
+                        can_run,
+                        egui::Button::new(format!("{} {}", Icon::CHECK, run_label)),
+                    )
+                    .clicked()
+                {
+                    run_clicked = true;
+                }
+                if ui
+                    .add_enabled(
+                        app.strip_metadata.can_undo(),
+                        egui::Button::new(format!("{} Undo Strip", Icon::ARROW_COUNTER_CLOCKWISE)),
+                    )
+                    .clicked()
+                {
+                    undo_clicked = true;
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    // Handle actions after the dialog is drawn
+    if dry_run_clicked {
+        app.start_strip_metadata(true);
+    }
+
+    if run_clicked {
+        app.start_strip_metadata(false);
+    }
+
+    if cancel_clicked {
+        app.cancel_strip_metadata();
+    }
+
+    if undo_clicked {
+        app.undo_strip_metadata();
+    }
+
+    if close_dialog {
+        app.strip_metadata.show_dialog = false;
+    }
+}
@@ -0,0 +1,164 @@
+use crate::app::DatasetCleanerApp;
+use crate::core::dataset::LayoutKind;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+use std::time::SystemTime;
+
+/// Format a file modification time in local time ("—" if unknown)
+fn format_modified(time: Option<SystemTime>) -> String {
+    match time {
+        Some(time) => chrono::DateTime::<chrono::Local>::from(time)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+        None => "—".to_string(),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Render the dataset summary window (shown on load and from the top panel)
+pub fn render_dataset_summary_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    if !app.summary.show_dialog {
+        return;
+    }
+
+    let mut switch_to = None;
+    let mut refresh = false;
+    let mut close_dialog = false;
+
+    egui::Window::new(format!("{} Dataset Summary", Icon::INFO))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(480.0);
+
+            if let Some(path) = app.dataset.dataset_path() {
+                ui.label(egui::RichText::new(path.display().to_string()).monospace());
+                ui.add_space(5.0);
+            }
+
+            match &app.summary.summary {
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Reading split folders...");
+                    });
+                    ctx.request_repaint();
+                }
+                Some(summary) => {
+                    egui::Grid::new("dataset_summary_grid")
+                        .num_columns(5)
+                        .spacing([20.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for heading in
+                                ["Split", "Images", "Labels", "Disk usage", "Last modified"]
+                            {
+                                ui.label(egui::RichText::new(heading).strong());
+                            }
+                            ui.end_row();
+
+                            // A flat dataset only fills the train slot
+                            let is_flat = app.dataset.layout() == LayoutKind::Flat;
+                            for split in summary.splits.iter().filter(|s| app.dataset.has_split(s.split)) {
+                                let is_current = split.split == app.dataset.current_split();
+                                let name = if is_flat {
+                                    "ROOT".to_string()
+                                } else {
+                                    split.split.as_str().to_uppercase()
+                                };
+                                if ui
+                                    .selectable_label(is_current, name)
+                                    .on_hover_text("Switch to this split")
+                                    .clicked()
+                                {
+                                    switch_to = Some(split.split);
+                                }
+                                ui.label(egui::RichText::new(split.images.to_string()).monospace());
+                                ui.label(egui::RichText::new(split.labels.to_string()).monospace());
+                                ui.label(
+                                    egui::RichText::new(format_size(split.disk_bytes)).monospace(),
+                                );
+                                ui.label(format_modified(split.last_modified));
+                                ui.end_row();
+                            }
+
+                            ui.label(egui::RichText::new("Total").strong());
+                            ui.label(
+                                egui::RichText::new(summary.total_images().to_string())
+                                    .monospace()
+                                    .strong(),
+                            );
+                            ui.label(
+                                egui::RichText::new(summary.total_labels().to_string())
+                                    .monospace()
+                                    .strong(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format_size(summary.total_bytes()))
+                                    .monospace()
+                                    .strong(),
+                            );
+                            ui.label(format_modified(summary.last_modified()));
+                            ui.end_row();
+                        });
+                }
+            }
+
+            ui.add_space(10.0);
+
+            // Balance analysis held in memory from earlier in this session
+            let has_analysis =
+                app.balance.results.is_some() || app.balance.all_splits_results.is_some();
+            match app.balance.analyzed_at.filter(|_| has_analysis) {
+                Some(at) => ui.label(format!(
+                    "{} Balance analysis available (from {})",
+                    Icon::CHART_BAR,
+                    at.format("%Y-%m-%d %H:%M")
+                )),
+                None => ui.label(
+                    egui::RichText::new(format!("{} No balance analysis yet", Icon::CHART_BAR))
+                        .color(egui::Color32::GRAY),
+                ),
+            };
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !app.summary.is_computing(),
+                        egui::Button::new(format!("{} Refresh", Icon::ARROW_CLOCKWISE)),
+                    )
+                    .clicked()
+                {
+                    refresh = true;
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        });
+
+    // Handle actions after the dialog is drawn
+    if let Some(split) = switch_to {
+        if split != app.dataset.current_split() {
+            app.change_split(split);
+        }
+    }
+
+    if refresh {
+        app.refresh_dataset_summary();
+    }
+
+    if close_dialog {
+        app.summary.show_dialog = false;
+    }
+}
@@ -1,5 +1,9 @@
 use crate::app::DatasetCleanerApp;
 use eframe::egui;
+use std::time::Duration;
+
+/// How long a message toast stays on screen
+const MESSAGE_TOAST_DURATION: Duration = Duration::from_secs(6);
 
 /// Render the toast notification for undo/redo operations
 pub fn render_toast_notification(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
@@ -109,9 +113,51 @@ pub fn render_toast_notification(app: &mut DatasetCleanerApp, ctx: &egui::Contex
 
     // Handle actions outside of the borrow
     if should_undo {
-        app.undo_delete();
+        app.undo();
     }
     if should_redo {
-        app.redo_delete();
+        app.redo();
+    }
+}
+
+/// Render the transient message toast (e.g. a data.yaml that was written or failed to parse)
+pub fn render_message_toast(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    let Some((message, shown_at)) = &app.ui.toast_message else {
+        return;
+    };
+
+    let elapsed = shown_at.elapsed();
+    if elapsed >= MESSAGE_TOAST_DURATION {
+        app.ui.toast_message = None;
+        return;
+    }
+    ctx.request_repaint_after(MESSAGE_TOAST_DURATION - elapsed);
+
+    let mut dismiss = false;
+
+    // Show toast in the top-right corner, below the top panel
+    egui::Window::new("message_toast")
+        .title_bar(false)
+        .resizable(false)
+        .collapsible(false)
+        .fixed_pos(egui::pos2(ctx.screen_rect().width() - 380.0, 60.0))
+        .show(ctx, |ui| {
+            ui.set_max_width(340.0);
+            ui.horizontal(|ui| {
+                // Warnings carry a leading ⚠
+                let text = egui::RichText::new(message.as_str());
+                if message.starts_with('⚠') {
+                    ui.label(text.color(egui::Color32::from_rgb(255, 150, 0)));
+                } else {
+                    ui.label(text);
+                }
+                if ui.small_button("✖").on_hover_text("Dismiss").clicked() {
+                    dismiss = true;
+                }
+            });
+        });
+
+    if dismiss {
+        app.ui.toast_message = None;
     }
 }
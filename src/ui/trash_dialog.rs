@@ -0,0 +1,63 @@
+use crate::app::DatasetCleanerApp;
+use eframe::egui;
+use egui_phosphor::regular as Icon;
+
+/// Render the Empty Cleaner Trash confirmation (Tools > Empty Cleaner Trash)
+pub fn render_empty_trash_dialog(app: &mut DatasetCleanerApp, ctx: &egui::Context) {
+    let Some((files, bytes)) = app.ui.empty_trash_preview else {
+        return;
+    };
+
+    let mut confirm = false;
+    let mut cancel = false;
+
+    egui::Window::new(format!("{} Empty Cleaner Trash", Icon::BROOM))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_min_width(320.0);
+
+            if files == 0 {
+                ui.label("The cleaner trash (.cleaner/trash) is empty.");
+            } else {
+                ui.label(format!(
+                    "Permanently delete {} files ({:.1} MB) from .cleaner/trash?",
+                    files,
+                    bytes as f64 / (1024.0 * 1024.0)
+                ));
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 150, 0),
+                    "⚠ Deletions moved to the trash can no longer be undone",
+                );
+            }
+
+            ui.add_space(10.0);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        files > 0,
+                        egui::Button::new(format!("{} Empty Trash", Icon::TRASH)),
+                    )
+                    .clicked()
+                {
+                    confirm = true;
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+        });
+
+    if confirm {
+        app.ui.empty_trash_preview = None;
+        app.empty_cleaner_trash();
+    } else if cancel {
+        app.ui.empty_trash_preview = None;
+    }
+}